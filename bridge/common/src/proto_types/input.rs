@@ -620,6 +620,8 @@ pub mod import_bundles {
         pub dsnp_keys: ::protobuf::MessageField<super::DsnpKeys>,
         // @@protoc_insertion_point(field:ImportBundles.ImportBundle.pages)
         pub pages: ::std::vec::Vec<super::PageData>,
+        // @@protoc_insertion_point(field:ImportBundles.ImportBundle.chain_head)
+        pub chain_head: ::protobuf::MessageField<super::ChainHead>,
         // special fields
         // @@protoc_insertion_point(special_field:ImportBundles.ImportBundle.special_fields)
         pub special_fields: ::protobuf::SpecialFields,
@@ -637,7 +639,7 @@ pub mod import_bundles {
         }
 
         pub(in super) fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-            let mut fields = ::std::vec::Vec::with_capacity(5);
+            let mut fields = ::std::vec::Vec::with_capacity(6);
             let mut oneofs = ::std::vec::Vec::with_capacity(0);
             fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
                 "dsnp_user_id",
@@ -664,6 +666,11 @@ pub mod import_bundles {
                 |m: &ImportBundle| { &m.pages },
                 |m: &mut ImportBundle| { &mut m.pages },
             ));
+            fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, super::ChainHead>(
+                "chain_head",
+                |m: &ImportBundle| { &m.chain_head },
+                |m: &mut ImportBundle| { &mut m.chain_head },
+            ));
             ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ImportBundle>(
                 "ImportBundles.ImportBundle",
                 fields,
@@ -697,6 +704,9 @@ pub mod import_bundles {
                     42 => {
                         self.pages.push(is.read_message()?);
                     },
+                    50 => {
+                        ::protobuf::rt::read_singular_message_into_field(is, &mut self.chain_head)?;
+                    },
                     tag => {
                         ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                     },
@@ -727,6 +737,10 @@ pub mod import_bundles {
                 let len = value.compute_size();
                 my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
             };
+            if let Some(v) = self.chain_head.as_ref() {
+                let len = v.compute_size();
+                my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+            }
             my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
             self.special_fields.cached_size().set(my_size as u32);
             my_size
@@ -748,6 +762,9 @@ pub mod import_bundles {
             for v in &self.pages {
                 ::protobuf::rt::write_message_field_with_cached_size(5, v, os)?;
             };
+            if let Some(v) = self.chain_head.as_ref() {
+                ::protobuf::rt::write_message_field_with_cached_size(6, v, os)?;
+            }
             os.write_unknown_fields(self.special_fields.unknown_fields())?;
             ::std::result::Result::Ok(())
         }
@@ -770,6 +787,7 @@ pub mod import_bundles {
             self.key_pairs.clear();
             self.dsnp_keys.clear();
             self.pages.clear();
+            self.chain_head.clear();
             self.special_fields.clear();
         }
 
@@ -780,6 +798,7 @@ pub mod import_bundles {
                 key_pairs: ::std::vec::Vec::new(),
                 dsnp_keys: ::protobuf::MessageField::none(),
                 pages: ::std::vec::Vec::new(),
+                chain_head: ::protobuf::MessageField::none(),
                 special_fields: ::protobuf::SpecialFields::new(),
             };
             &instance
@@ -1260,6 +1279,12 @@ pub mod actions {
         pub ignore_missing_connections: bool,
         // @@protoc_insertion_point(field:Actions.ActionOptions.disable_auto_commit)
         pub disable_auto_commit: bool,
+        // @@protoc_insertion_point(field:Actions.ActionOptions.timestamp_policy)
+        pub timestamp_policy: ::protobuf::EnumOrUnknown<super::TimestampPolicy>,
+        // @@protoc_insertion_point(field:Actions.ActionOptions.max_batch_size)
+        pub max_batch_size: u32,
+        // @@protoc_insertion_point(field:Actions.ActionOptions.collect_errors)
+        pub collect_errors: bool,
         // special fields
         // @@protoc_insertion_point(special_field:Actions.ActionOptions.special_fields)
         pub special_fields: ::protobuf::SpecialFields,
@@ -1277,7 +1302,7 @@ pub mod actions {
         }
 
         pub(in super) fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-            let mut fields = ::std::vec::Vec::with_capacity(3);
+            let mut fields = ::std::vec::Vec::with_capacity(6);
             let mut oneofs = ::std::vec::Vec::with_capacity(0);
             fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
                 "ignore_existing_connections",
@@ -1294,6 +1319,21 @@ pub mod actions {
                 |m: &ActionOptions| { &m.disable_auto_commit },
                 |m: &mut ActionOptions| { &mut m.disable_auto_commit },
             ));
+            fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+                "timestamp_policy",
+                |m: &ActionOptions| { &m.timestamp_policy },
+                |m: &mut ActionOptions| { &mut m.timestamp_policy },
+            ));
+            fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+                "max_batch_size",
+                |m: &ActionOptions| { &m.max_batch_size },
+                |m: &mut ActionOptions| { &mut m.max_batch_size },
+            ));
+            fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+                "collect_errors",
+                |m: &ActionOptions| { &m.collect_errors },
+                |m: &mut ActionOptions| { &mut m.collect_errors },
+            ));
             ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ActionOptions>(
                 "Actions.ActionOptions",
                 fields,
@@ -1321,6 +1361,15 @@ pub mod actions {
                     24 => {
                         self.disable_auto_commit = is.read_bool()?;
                     },
+                    32 => {
+                        self.timestamp_policy = is.read_enum_or_unknown()?;
+                    },
+                    40 => {
+                        self.max_batch_size = is.read_uint32()?;
+                    },
+                    48 => {
+                        self.collect_errors = is.read_bool()?;
+                    },
                     tag => {
                         ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                     },
@@ -1342,6 +1391,15 @@ pub mod actions {
             if self.disable_auto_commit != false {
                 my_size += 1 + 1;
             }
+            if self.timestamp_policy != ::protobuf::EnumOrUnknown::new(super::TimestampPolicy::CallerProvided) {
+                my_size += ::protobuf::rt::int32_size(4, self.timestamp_policy.value());
+            }
+            if self.max_batch_size != 0 {
+                my_size += ::protobuf::rt::uint32_size(5, self.max_batch_size);
+            }
+            if self.collect_errors != false {
+                my_size += 1 + 1;
+            }
             my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
             self.special_fields.cached_size().set(my_size as u32);
             my_size
@@ -1357,6 +1415,15 @@ pub mod actions {
             if self.disable_auto_commit != false {
                 os.write_bool(3, self.disable_auto_commit)?;
             }
+            if self.timestamp_policy != ::protobuf::EnumOrUnknown::new(super::TimestampPolicy::CallerProvided) {
+                os.write_enum(4, ::protobuf::EnumOrUnknown::value(&self.timestamp_policy))?;
+            }
+            if self.max_batch_size != 0 {
+                os.write_uint32(5, self.max_batch_size)?;
+            }
+            if self.collect_errors != false {
+                os.write_bool(6, self.collect_errors)?;
+            }
             os.write_unknown_fields(self.special_fields.unknown_fields())?;
             ::std::result::Result::Ok(())
         }
@@ -1377,6 +1444,9 @@ pub mod actions {
             self.ignore_existing_connections = false;
             self.ignore_missing_connections = false;
             self.disable_auto_commit = false;
+            self.timestamp_policy = ::protobuf::EnumOrUnknown::new(super::TimestampPolicy::CallerProvided);
+            self.max_batch_size = 0;
+            self.collect_errors = false;
             self.special_fields.clear();
         }
 
@@ -1385,6 +1455,9 @@ pub mod actions {
                 ignore_existing_connections: false,
                 ignore_missing_connections: false,
                 disable_auto_commit: false,
+                timestamp_policy: ::protobuf::EnumOrUnknown::from_i32(0),
+                max_batch_size: 0,
+                collect_errors: false,
                 special_fields: ::protobuf::SpecialFields::new(),
             };
             &instance
@@ -1764,6 +1837,8 @@ pub mod actions {
             pub connection: ::protobuf::MessageField<super::super::Connection>,
             // @@protoc_insertion_point(field:Actions.Action.ConnectAction.dsnp_keys)
             pub dsnp_keys: ::protobuf::MessageField<super::super::DsnpKeys>,
+            // @@protoc_insertion_point(field:Actions.Action.ConnectAction.since)
+            pub since: u64,
             // special fields
             // @@protoc_insertion_point(special_field:Actions.Action.ConnectAction.special_fields)
             pub special_fields: ::protobuf::SpecialFields,
@@ -1781,7 +1856,7 @@ pub mod actions {
             }
 
             pub(in super::super) fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-                let mut fields = ::std::vec::Vec::with_capacity(3);
+                let mut fields = ::std::vec::Vec::with_capacity(4);
                 let mut oneofs = ::std::vec::Vec::with_capacity(0);
                 fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
                     "owner_dsnp_user_id",
@@ -1798,6 +1873,11 @@ pub mod actions {
                     |m: &ConnectAction| { &m.dsnp_keys },
                     |m: &mut ConnectAction| { &mut m.dsnp_keys },
                 ));
+                fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+                    "since",
+                    |m: &ConnectAction| { &m.since },
+                    |m: &mut ConnectAction| { &mut m.since },
+                ));
                 ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ConnectAction>(
                     "Actions.Action.ConnectAction",
                     fields,
@@ -1825,6 +1905,9 @@ pub mod actions {
                         26 => {
                             ::protobuf::rt::read_singular_message_into_field(is, &mut self.dsnp_keys)?;
                         },
+                        32 => {
+                            self.since = is.read_uint64()?;
+                        },
                         tag => {
                             ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                         },
@@ -1848,6 +1931,9 @@ pub mod actions {
                     let len = v.compute_size();
                     my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
                 }
+                if self.since != 0 {
+                    my_size += ::protobuf::rt::uint64_size(4, self.since);
+                }
                 my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
                 self.special_fields.cached_size().set(my_size as u32);
                 my_size
@@ -1863,6 +1949,9 @@ pub mod actions {
                 if let Some(v) = self.dsnp_keys.as_ref() {
                     ::protobuf::rt::write_message_field_with_cached_size(3, v, os)?;
                 }
+                if self.since != 0 {
+                    os.write_uint64(4, self.since)?;
+                }
                 os.write_unknown_fields(self.special_fields.unknown_fields())?;
                 ::std::result::Result::Ok(())
             }
@@ -1883,6 +1972,7 @@ pub mod actions {
                 self.owner_dsnp_user_id = 0;
                 self.connection.clear();
                 self.dsnp_keys.clear();
+                self.since = 0;
                 self.special_fields.clear();
             }
 
@@ -1891,6 +1981,7 @@ pub mod actions {
                     owner_dsnp_user_id: 0,
                     connection: ::protobuf::MessageField::none(),
                     dsnp_keys: ::protobuf::MessageField::none(),
+                    since: 0,
                     special_fields: ::protobuf::SpecialFields::new(),
                 };
                 &instance
@@ -2197,6 +2288,147 @@ pub mod actions {
     }
 }
 
+///  block of the chain at which the imported data was read
+// @@protoc_insertion_point(message:ChainHead)
+#[derive(PartialEq,Clone,Default,Debug)]
+pub struct ChainHead {
+    // message fields
+    // @@protoc_insertion_point(field:ChainHead.block_number)
+    pub block_number: u32,
+    // @@protoc_insertion_point(field:ChainHead.block_hash)
+    pub block_hash: ::std::vec::Vec<u8>,
+    // special fields
+    // @@protoc_insertion_point(special_field:ChainHead.special_fields)
+    pub special_fields: ::protobuf::SpecialFields,
+}
+
+impl<'a> ::std::default::Default for &'a ChainHead {
+    fn default() -> &'a ChainHead {
+        <ChainHead as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl ChainHead {
+    pub fn new() -> ChainHead {
+        ::std::default::Default::default()
+    }
+
+    fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
+        let mut fields = ::std::vec::Vec::with_capacity(2);
+        let mut oneofs = ::std::vec::Vec::with_capacity(0);
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "block_number",
+            |m: &ChainHead| { &m.block_number },
+            |m: &mut ChainHead| { &mut m.block_number },
+        ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "block_hash",
+            |m: &ChainHead| { &m.block_hash },
+            |m: &mut ChainHead| { &mut m.block_hash },
+        ));
+        ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<ChainHead>(
+            "ChainHead",
+            fields,
+            oneofs,
+        )
+    }
+}
+
+impl ::protobuf::Message for ChainHead {
+    const NAME: &'static str = "ChainHead";
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::Result<()> {
+        while let Some(tag) = is.read_raw_tag_or_eof()? {
+            match tag {
+                8 => {
+                    self.block_number = is.read_uint32()?;
+                },
+                18 => {
+                    self.block_hash = is.read_bytes()?;
+                },
+                tag => {
+                    ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u64 {
+        let mut my_size = 0;
+        if self.block_number != 0 {
+            my_size += ::protobuf::rt::uint32_size(1, self.block_number);
+        }
+        if !self.block_hash.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.block_hash);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
+        self.special_fields.cached_size().set(my_size as u32);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::Result<()> {
+        if self.block_number != 0 {
+            os.write_uint32(1, self.block_number)?;
+        }
+        if !self.block_hash.is_empty() {
+            os.write_bytes(2, &self.block_hash)?;
+        }
+        os.write_unknown_fields(self.special_fields.unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn special_fields(&self) -> &::protobuf::SpecialFields {
+        &self.special_fields
+    }
+
+    fn mut_special_fields(&mut self) -> &mut ::protobuf::SpecialFields {
+        &mut self.special_fields
+    }
+
+    fn new() -> ChainHead {
+        ChainHead::new()
+    }
+
+    fn clear(&mut self) {
+        self.block_number = 0;
+        self.block_hash.clear();
+        self.special_fields.clear();
+    }
+
+    fn default_instance() -> &'static ChainHead {
+        static instance: ChainHead = ChainHead {
+            block_number: 0,
+            block_hash: ::std::vec::Vec::new(),
+            special_fields: ::protobuf::SpecialFields::new(),
+        };
+        &instance
+    }
+}
+
+impl ::protobuf::MessageFull for ChainHead {
+    fn descriptor() -> ::protobuf::reflect::MessageDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().message_by_package_relative_name("ChainHead").unwrap()).clone()
+    }
+}
+
+impl ::std::fmt::Display for ChainHead {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ChainHead {
+    type RuntimeType = ::protobuf::reflect::rt::RuntimeTypeMessage<Self>;
+}
+
 #[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
 // @@protoc_insertion_point(enum:GraphKeyType)
 pub enum GraphKeyType {
@@ -2254,6 +2486,73 @@ impl GraphKeyType {
     }
 }
 
+#[derive(Clone,Copy,PartialEq,Eq,Debug,Hash)]
+// @@protoc_insertion_point(enum:TimestampPolicy)
+pub enum TimestampPolicy {
+    // @@protoc_insertion_point(enum_value:TimestampPolicy.CallerProvided)
+    CallerProvided = 0,
+    // @@protoc_insertion_point(enum_value:TimestampPolicy.Now)
+    Now = 1,
+    // @@protoc_insertion_point(enum_value:TimestampPolicy.Zero)
+    Zero = 2,
+}
+
+impl ::protobuf::Enum for TimestampPolicy {
+    const NAME: &'static str = "TimestampPolicy";
+
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<TimestampPolicy> {
+        match value {
+            0 => ::std::option::Option::Some(TimestampPolicy::CallerProvided),
+            1 => ::std::option::Option::Some(TimestampPolicy::Now),
+            2 => ::std::option::Option::Some(TimestampPolicy::Zero),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn from_str(str: &str) -> ::std::option::Option<TimestampPolicy> {
+        match str {
+            "CallerProvided" => ::std::option::Option::Some(TimestampPolicy::CallerProvided),
+            "Now" => ::std::option::Option::Some(TimestampPolicy::Now),
+            "Zero" => ::std::option::Option::Some(TimestampPolicy::Zero),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    const VALUES: &'static [TimestampPolicy] = &[
+        TimestampPolicy::CallerProvided,
+        TimestampPolicy::Now,
+        TimestampPolicy::Zero,
+    ];
+}
+
+impl ::protobuf::EnumFull for TimestampPolicy {
+    fn enum_descriptor() -> ::protobuf::reflect::EnumDescriptor {
+        static descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::rt::Lazy::new();
+        descriptor.get(|| file_descriptor().enum_by_package_relative_name("TimestampPolicy").unwrap()).clone()
+    }
+
+    fn descriptor(&self) -> ::protobuf::reflect::EnumValueDescriptor {
+        let index = *self as usize;
+        Self::enum_descriptor().value_by_index(index)
+    }
+}
+
+impl ::std::default::Default for TimestampPolicy {
+    fn default() -> Self {
+        TimestampPolicy::CallerProvided
+    }
+}
+
+impl TimestampPolicy {
+    fn generated_enum_descriptor_data() -> ::protobuf::reflect::GeneratedEnumDescriptorData {
+        ::protobuf::reflect::GeneratedEnumDescriptorData::new::<TimestampPolicy>("TimestampPolicy")
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x20bridge/common/protos/input.proto\"9\n\x07KeyData\x12\x14\n\x05inde\
     x\x18\x01\x20\x01(\rR\x05index\x12\x18\n\x07content\x18\x02\x20\x01(\x0c\
@@ -2262,171 +2561,215 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     \x0ccontent_hash\x18\x03\x20\x01(\rR\x0bcontentHash\"g\n\x08DsnpKeys\x12\
     \x20\n\x0cdsnp_user_id\x18\x01\x20\x01(\x04R\ndsnpUserId\x12\x1b\n\tkeys\
     _hash\x18\x02\x20\x01(\rR\x08keysHash\x12\x1c\n\x04keys\x18\x03\x20\x03(\
-    \x0b2\x08.KeyDataR\x04keys\"\xb1\x03\n\rImportBundles\x125\n\x07bundles\
+    \x0b2\x08.KeyDataR\x04keys\"\xf0\x03\n\rImportBundles\x125\n\x07bundles\
     \x18\x01\x20\x03(\x0b2\x1b.ImportBundles.ImportBundleR\x07bundles\x1a\
-    \xe8\x02\n\x0cImportBundle\x12\x20\n\x0cdsnp_user_id\x18\x01\x20\x01(\
+    \xa7\x03\n\x0cImportBundle\x12\x20\n\x0cdsnp_user_id\x18\x01\x20\x01(\
     \x04R\ndsnpUserId\x12\x1b\n\tschema_id\x18\x02\x20\x01(\rR\x08schemaId\
     \x12E\n\tkey_pairs\x18\x03\x20\x03(\x0b2(.ImportBundles.ImportBundle.Gra\
     phKeyPairR\x08keyPairs\x12+\n\tdsnp_keys\x18\x04\x20\x01(\x0b2\t.DsnpKey\
     sH\0R\x08dsnpKeys\x88\x01\x01\x12\x1f\n\x05pages\x18\x05\x20\x03(\x0b2\t\
-    .PageDataR\x05pages\x1av\n\x0cGraphKeyPair\x12(\n\x08key_type\x18\x01\
-    \x20\x01(\x0e2\r.GraphKeyTypeR\x07keyType\x12\x1d\n\npublic_key\x18\x02\
-    \x20\x01(\x0cR\tpublicKey\x12\x1d\n\nsecret_key\x18\x03\x20\x01(\x0cR\ts\
-    ecretKeyB\x0c\n\n_dsnp_keys\"K\n\nConnection\x12\x20\n\x0cdsnp_user_id\
-    \x18\x01\x20\x01(\x04R\ndsnpUserId\x12\x1b\n\tschema_id\x18\x02\x20\x01(\
-    \rR\x08schemaId\"\xa0\x07\n\x07Actions\x12)\n\x07actions\x18\x01\x20\x03\
-    (\x0b2\x0f.Actions.ActionR\x07actions\x125\n\x07options\x18\x02\x20\x01(\
-    \x0b2\x16.Actions.ActionOptionsH\0R\x07options\x88\x01\x01\x1a\xbd\x01\n\
-    \rActionOptions\x12>\n\x1bignore_existing_connections\x18\x01\x20\x01(\
-    \x08R\x19ignoreExistingConnections\x12<\n\x1aignore_missing_connections\
-    \x18\x02\x20\x01(\x08R\x18ignoreMissingConnections\x12.\n\x13disable_aut\
-    o_commit\x18\x03\x20\x01(\x08R\x11disableAutoCommit\x1a\xe6\x04\n\x06Act\
-    ion\x12F\n\x0econnect_action\x18\x01\x20\x01(\x0b2\x1d.Actions.Action.Co\
-    nnectActionH\0R\rconnectAction\x12O\n\x11disconnect_action\x18\x02\x20\
-    \x01(\x0b2\x20.Actions.Action.DisconnectActionH\0R\x10disconnectAction\
-    \x12C\n\x0eadd_key_action\x18\x03\x20\x01(\x0b2\x1b.Actions.Action.AddGr\
-    aphKeyH\0R\x0caddKeyAction\x1a\xa4\x01\n\rConnectAction\x12+\n\x12owner_\
-    dsnp_user_id\x18\x01\x20\x01(\x04R\x0fownerDsnpUserId\x12+\n\nconnection\
-    \x18\x02\x20\x01(\x0b2\x0b.ConnectionR\nconnection\x12+\n\tdsnp_keys\x18\
-    \x03\x20\x01(\x0b2\t.DsnpKeysH\0R\x08dsnpKeys\x88\x01\x01B\x0c\n\n_dsnp_\
-    keys\x1al\n\x10DisconnectAction\x12+\n\x12owner_dsnp_user_id\x18\x01\x20\
-    \x01(\x04R\x0fownerDsnpUserId\x12+\n\nconnection\x18\x02\x20\x01(\x0b2\
-    \x0b.ConnectionR\nconnection\x1a`\n\x0bAddGraphKey\x12+\n\x12owner_dsnp_\
-    user_id\x18\x01\x20\x01(\x04R\x0fownerDsnpUserId\x12$\n\x0enew_public_ke\
-    y\x18\x02\x20\x01(\x0cR\x0cnewPublicKeyB\x07\n\x05innerB\n\n\x08_options\
-    *\x1a\n\x0cGraphKeyType\x12\n\n\x06X25519\x10\0B%\n!io.projectliberty.gr\
-    aphsdk.modelsP\x01J\xd9\x15\n\x06\x12\x04\0\0R\x01\n\x08\n\x01\x0c\x12\
-    \x03\0\0\x12\n\x08\n\x01\x08\x12\x03\x02\0:\n\t\n\x02\x08\x01\x12\x03\
-    \x02\0:\n\x08\n\x01\x08\x12\x03\x03\0\"\n\t\n\x02\x08\n\x12\x03\x03\0\"\
-    \n\n\n\x02\x05\0\x12\x04\x05\0\x07\x01\n\n\n\x03\x05\0\x01\x12\x03\x05\
-    \x05\x11\n\x0b\n\x04\x05\0\x02\0\x12\x03\x06\x02\r\n\x0c\n\x05\x05\0\x02\
-    \0\x01\x12\x03\x06\x02\x08\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\x06\x0b\
-    \x0c\n\n\n\x02\x04\0\x12\x04\t\0\x0c\x01\n\n\n\x03\x04\0\x01\x12\x03\t\
-    \x08\x0f\n\x0b\n\x04\x04\0\x02\0\x12\x03\n\x02\x13\n\x0c\n\x05\x04\0\x02\
-    \0\x05\x12\x03\n\x02\x08\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\n\t\x0e\n\
-    \x0c\n\x05\x04\0\x02\0\x03\x12\x03\n\x11\x12\n\x0b\n\x04\x04\0\x02\x01\
-    \x12\x03\x0b\x02\x14\n\x0c\n\x05\x04\0\x02\x01\x05\x12\x03\x0b\x02\x07\n\
-    \x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x0b\x08\x0f\n\x0c\n\x05\x04\0\x02\
-    \x01\x03\x12\x03\x0b\x12\x13\n\n\n\x02\x04\x01\x12\x04\x0e\0\x12\x01\n\n\
-    \n\x03\x04\x01\x01\x12\x03\x0e\x08\x10\n\x0b\n\x04\x04\x01\x02\0\x12\x03\
-    \x0f\x02\x15\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\x0f\x02\x08\n\x0c\n\
-    \x05\x04\x01\x02\0\x01\x12\x03\x0f\t\x10\n\x0c\n\x05\x04\x01\x02\0\x03\
-    \x12\x03\x0f\x13\x14\n\x0b\n\x04\x04\x01\x02\x01\x12\x03\x10\x02\x14\n\
-    \x0c\n\x05\x04\x01\x02\x01\x05\x12\x03\x10\x02\x07\n\x0c\n\x05\x04\x01\
-    \x02\x01\x01\x12\x03\x10\x08\x0f\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\
-    \x10\x12\x13\n\x0b\n\x04\x04\x01\x02\x02\x12\x03\x11\x02\x1a\n\x0c\n\x05\
-    \x04\x01\x02\x02\x05\x12\x03\x11\x02\x08\n\x0c\n\x05\x04\x01\x02\x02\x01\
-    \x12\x03\x11\t\x15\n\x0c\n\x05\x04\x01\x02\x02\x03\x12\x03\x11\x18\x19\n\
-    \n\n\x02\x04\x02\x12\x04\x14\0\x18\x01\n\n\n\x03\x04\x02\x01\x12\x03\x14\
-    \x08\x10\n\x0b\n\x04\x04\x02\x02\0\x12\x03\x15\x02\x1a\n\x0c\n\x05\x04\
-    \x02\x02\0\x05\x12\x03\x15\x02\x08\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\
-    \x15\t\x15\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03\x15\x18\x19\n\x0b\n\x04\
-    \x04\x02\x02\x01\x12\x03\x16\x02\x17\n\x0c\n\x05\x04\x02\x02\x01\x05\x12\
-    \x03\x16\x02\x08\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03\x16\t\x12\n\x0c\
-    \n\x05\x04\x02\x02\x01\x03\x12\x03\x16\x15\x16\n\x0b\n\x04\x04\x02\x02\
-    \x02\x12\x03\x17\x02\x1c\n\x0c\n\x05\x04\x02\x02\x02\x04\x12\x03\x17\x02\
-    \n\n\x0c\n\x05\x04\x02\x02\x02\x06\x12\x03\x17\x0b\x12\n\x0c\n\x05\x04\
-    \x02\x02\x02\x01\x12\x03\x17\x13\x17\n\x0c\n\x05\x04\x02\x02\x02\x03\x12\
-    \x03\x17\x1a\x1b\n\n\n\x02\x04\x03\x12\x04\x1a\0)\x01\n\n\n\x03\x04\x03\
-    \x01\x12\x03\x1a\x08\x15\n\x0c\n\x04\x04\x03\x03\0\x12\x04\x1b\x02'\x03\
-    \n\x0c\n\x05\x04\x03\x03\0\x01\x12\x03\x1b\n\x16\n\x0e\n\x06\x04\x03\x03\
-    \0\x03\0\x12\x04\x1c\x04\x20\x05\n\x0e\n\x07\x04\x03\x03\0\x03\0\x01\x12\
-    \x03\x1c\x0c\x18\n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\0\x12\x03\x1d\x06\
-    \x20\n\x10\n\t\x04\x03\x03\0\x03\0\x02\0\x06\x12\x03\x1d\x06\x12\n\x10\n\
-    \t\x04\x03\x03\0\x03\0\x02\0\x01\x12\x03\x1d\x13\x1b\n\x10\n\t\x04\x03\
-    \x03\0\x03\0\x02\0\x03\x12\x03\x1d\x1e\x1f\n\x0f\n\x08\x04\x03\x03\0\x03\
-    \0\x02\x01\x12\x03\x1e\x06\x1b\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x01\x05\
-    \x12\x03\x1e\x06\x0b\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x01\x01\x12\x03\
-    \x1e\x0c\x16\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x01\x03\x12\x03\x1e\x19\
-    \x1a\n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\x02\x12\x03\x1f\x06\x1b\n\x10\n\
-    \t\x04\x03\x03\0\x03\0\x02\x02\x05\x12\x03\x1f\x06\x0b\n\x10\n\t\x04\x03\
-    \x03\0\x03\0\x02\x02\x01\x12\x03\x1f\x0c\x16\n\x10\n\t\x04\x03\x03\0\x03\
-    \0\x02\x02\x03\x12\x03\x1f\x19\x1a\n\r\n\x06\x04\x03\x03\0\x02\0\x12\x03\
-    \"\x04\x1c\n\x0e\n\x07\x04\x03\x03\0\x02\0\x05\x12\x03\"\x04\n\n\x0e\n\
-    \x07\x04\x03\x03\0\x02\0\x01\x12\x03\"\x0b\x17\n\x0e\n\x07\x04\x03\x03\0\
-    \x02\0\x03\x12\x03\"\x1a\x1b\n\r\n\x06\x04\x03\x03\0\x02\x01\x12\x03#\
-    \x04\x19\n\x0e\n\x07\x04\x03\x03\0\x02\x01\x05\x12\x03#\x04\n\n\x0e\n\
-    \x07\x04\x03\x03\0\x02\x01\x01\x12\x03#\x0b\x14\n\x0e\n\x07\x04\x03\x03\
-    \0\x02\x01\x03\x12\x03#\x17\x18\n\r\n\x06\x04\x03\x03\0\x02\x02\x12\x03$\
-    \x04(\n\x0e\n\x07\x04\x03\x03\0\x02\x02\x04\x12\x03$\x04\x0c\n\x0e\n\x07\
-    \x04\x03\x03\0\x02\x02\x06\x12\x03$\r\x19\n\x0e\n\x07\x04\x03\x03\0\x02\
-    \x02\x01\x12\x03$\x1a#\n\x0e\n\x07\x04\x03\x03\0\x02\x02\x03\x12\x03$&'\
-    \n\r\n\x06\x04\x03\x03\0\x02\x03\x12\x03%\x04$\n\x0e\n\x07\x04\x03\x03\0\
-    \x02\x03\x04\x12\x03%\x04\x0c\n\x0e\n\x07\x04\x03\x03\0\x02\x03\x06\x12\
-    \x03%\r\x15\n\x0e\n\x07\x04\x03\x03\0\x02\x03\x01\x12\x03%\x16\x1f\n\x0e\
-    \n\x07\x04\x03\x03\0\x02\x03\x03\x12\x03%\"#\n\r\n\x06\x04\x03\x03\0\x02\
-    \x04\x12\x03&\x04\x20\n\x0e\n\x07\x04\x03\x03\0\x02\x04\x04\x12\x03&\x04\
-    \x0c\n\x0e\n\x07\x04\x03\x03\0\x02\x04\x06\x12\x03&\r\x15\n\x0e\n\x07\
-    \x04\x03\x03\0\x02\x04\x01\x12\x03&\x16\x1b\n\x0e\n\x07\x04\x03\x03\0\
-    \x02\x04\x03\x12\x03&\x1e\x1f\n\x0b\n\x04\x04\x03\x02\0\x12\x03(\x02$\n\
-    \x0c\n\x05\x04\x03\x02\0\x04\x12\x03(\x02\n\n\x0c\n\x05\x04\x03\x02\0\
-    \x06\x12\x03(\x0b\x17\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03(\x18\x1f\n\
-    \x0c\n\x05\x04\x03\x02\0\x03\x12\x03(\"#\n\"\n\x02\x04\x04\x12\x04,\0/\
-    \x01\x1a\x16\x20Action\x20related\x20types\n\n\n\n\x03\x04\x04\x01\x12\
-    \x03,\x08\x12\n\x0b\n\x04\x04\x04\x02\0\x12\x03-\x02\x1a\n\x0c\n\x05\x04\
-    \x04\x02\0\x05\x12\x03-\x02\x08\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03-\t\
-    \x15\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x03-\x18\x19\n\x0b\n\x04\x04\x04\
-    \x02\x01\x12\x03.\x02\x17\n\x0c\n\x05\x04\x04\x02\x01\x05\x12\x03.\x02\
-    \x08\n\x0c\n\x05\x04\x04\x02\x01\x01\x12\x03.\t\x12\n\x0c\n\x05\x04\x04\
-    \x02\x01\x03\x12\x03.\x15\x16\n\n\n\x02\x04\x05\x12\x041\0R\x01\n\n\n\
-    \x03\x04\x05\x01\x12\x031\x08\x0f\n\x0c\n\x04\x04\x05\x03\0\x12\x042\x02\
-    6\x03\n\x0c\n\x05\x04\x05\x03\0\x01\x12\x032\n\x17\n\r\n\x06\x04\x05\x03\
-    \0\x02\0\x12\x033\x04)\n\x0e\n\x07\x04\x05\x03\0\x02\0\x05\x12\x033\x04\
-    \x08\n\x0e\n\x07\x04\x05\x03\0\x02\0\x01\x12\x033\t$\n\x0e\n\x07\x04\x05\
-    \x03\0\x02\0\x03\x12\x033'(\n\r\n\x06\x04\x05\x03\0\x02\x01\x12\x034\x04\
-    (\n\x0e\n\x07\x04\x05\x03\0\x02\x01\x05\x12\x034\x04\x08\n\x0e\n\x07\x04\
-    \x05\x03\0\x02\x01\x01\x12\x034\t#\n\x0e\n\x07\x04\x05\x03\0\x02\x01\x03\
-    \x12\x034&'\n\r\n\x06\x04\x05\x03\0\x02\x02\x12\x035\x04!\n\x0e\n\x07\
-    \x04\x05\x03\0\x02\x02\x05\x12\x035\x04\x08\n\x0e\n\x07\x04\x05\x03\0\
-    \x02\x02\x01\x12\x035\t\x1c\n\x0e\n\x07\x04\x05\x03\0\x02\x02\x03\x12\
-    \x035\x1f\x20\n\x0c\n\x04\x04\x05\x03\x01\x12\x048\x02N\x03\n\x0c\n\x05\
-    \x04\x05\x03\x01\x01\x12\x038\n\x10\n\x0e\n\x06\x04\x05\x03\x01\x03\0\
-    \x12\x049\x04=\x05\n\x0e\n\x07\x04\x05\x03\x01\x03\0\x01\x12\x039\x0c\
-    \x19\n\x0f\n\x08\x04\x05\x03\x01\x03\0\x02\0\x12\x03:\x06$\n\x10\n\t\x04\
-    \x05\x03\x01\x03\0\x02\0\x05\x12\x03:\x06\x0c\n\x10\n\t\x04\x05\x03\x01\
-    \x03\0\x02\0\x01\x12\x03:\r\x1f\n\x10\n\t\x04\x05\x03\x01\x03\0\x02\0\
-    \x03\x12\x03:\"#\n\x0f\n\x08\x04\x05\x03\x01\x03\0\x02\x01\x12\x03;\x06\
-    \x20\n\x10\n\t\x04\x05\x03\x01\x03\0\x02\x01\x06\x12\x03;\x06\x10\n\x10\
-    \n\t\x04\x05\x03\x01\x03\0\x02\x01\x01\x12\x03;\x11\x1b\n\x10\n\t\x04\
-    \x05\x03\x01\x03\0\x02\x01\x03\x12\x03;\x1e\x1f\n\x0f\n\x08\x04\x05\x03\
-    \x01\x03\0\x02\x02\x12\x03<\x06&\n\x10\n\t\x04\x05\x03\x01\x03\0\x02\x02\
-    \x04\x12\x03<\x06\x0e\n\x10\n\t\x04\x05\x03\x01\x03\0\x02\x02\x06\x12\
-    \x03<\x0f\x17\n\x10\n\t\x04\x05\x03\x01\x03\0\x02\x02\x01\x12\x03<\x18!\
-    \n\x10\n\t\x04\x05\x03\x01\x03\0\x02\x02\x03\x12\x03<$%\n\x0e\n\x06\x04\
-    \x05\x03\x01\x03\x01\x12\x04?\x04B\x05\n\x0e\n\x07\x04\x05\x03\x01\x03\
-    \x01\x01\x12\x03?\x0c\x1c\n\x0f\n\x08\x04\x05\x03\x01\x03\x01\x02\0\x12\
-    \x03@\x06$\n\x10\n\t\x04\x05\x03\x01\x03\x01\x02\0\x05\x12\x03@\x06\x0c\
-    \n\x10\n\t\x04\x05\x03\x01\x03\x01\x02\0\x01\x12\x03@\r\x1f\n\x10\n\t\
-    \x04\x05\x03\x01\x03\x01\x02\0\x03\x12\x03@\"#\n\x0f\n\x08\x04\x05\x03\
-    \x01\x03\x01\x02\x01\x12\x03A\x06\x20\n\x10\n\t\x04\x05\x03\x01\x03\x01\
-    \x02\x01\x06\x12\x03A\x06\x10\n\x10\n\t\x04\x05\x03\x01\x03\x01\x02\x01\
-    \x01\x12\x03A\x11\x1b\n\x10\n\t\x04\x05\x03\x01\x03\x01\x02\x01\x03\x12\
-    \x03A\x1e\x1f\n\x0e\n\x06\x04\x05\x03\x01\x03\x02\x12\x04D\x04G\x05\n\
-    \x0e\n\x07\x04\x05\x03\x01\x03\x02\x01\x12\x03D\x0c\x17\n\x0f\n\x08\x04\
-    \x05\x03\x01\x03\x02\x02\0\x12\x03E\x06$\n\x10\n\t\x04\x05\x03\x01\x03\
-    \x02\x02\0\x05\x12\x03E\x06\x0c\n\x10\n\t\x04\x05\x03\x01\x03\x02\x02\0\
-    \x01\x12\x03E\r\x1f\n\x10\n\t\x04\x05\x03\x01\x03\x02\x02\0\x03\x12\x03E\
-    \"#\n\x0f\n\x08\x04\x05\x03\x01\x03\x02\x02\x01\x12\x03F\x06\x1f\n\x10\n\
-    \t\x04\x05\x03\x01\x03\x02\x02\x01\x05\x12\x03F\x06\x0b\n\x10\n\t\x04\
-    \x05\x03\x01\x03\x02\x02\x01\x01\x12\x03F\x0c\x1a\n\x10\n\t\x04\x05\x03\
-    \x01\x03\x02\x02\x01\x03\x12\x03F\x1d\x1e\n\x0e\n\x06\x04\x05\x03\x01\
-    \x08\0\x12\x04I\x04M\x05\n\x0e\n\x07\x04\x05\x03\x01\x08\0\x01\x12\x03I\
-    \n\x0f\n\r\n\x06\x04\x05\x03\x01\x02\0\x12\x03J\x06'\n\x0e\n\x07\x04\x05\
-    \x03\x01\x02\0\x06\x12\x03J\x06\x13\n\x0e\n\x07\x04\x05\x03\x01\x02\0\
-    \x01\x12\x03J\x14\"\n\x0e\n\x07\x04\x05\x03\x01\x02\0\x03\x12\x03J%&\n\r\
-    \n\x06\x04\x05\x03\x01\x02\x01\x12\x03K\x06-\n\x0e\n\x07\x04\x05\x03\x01\
-    \x02\x01\x06\x12\x03K\x06\x16\n\x0e\n\x07\x04\x05\x03\x01\x02\x01\x01\
-    \x12\x03K\x17(\n\x0e\n\x07\x04\x05\x03\x01\x02\x01\x03\x12\x03K+,\n\r\n\
-    \x06\x04\x05\x03\x01\x02\x02\x12\x03L\x06%\n\x0e\n\x07\x04\x05\x03\x01\
-    \x02\x02\x06\x12\x03L\x06\x11\n\x0e\n\x07\x04\x05\x03\x01\x02\x02\x01\
-    \x12\x03L\x12\x20\n\x0e\n\x07\x04\x05\x03\x01\x02\x02\x03\x12\x03L#$\n\
-    \x0b\n\x04\x04\x05\x02\0\x12\x03P\x02\x1e\n\x0c\n\x05\x04\x05\x02\0\x04\
-    \x12\x03P\x02\n\n\x0c\n\x05\x04\x05\x02\0\x06\x12\x03P\x0b\x11\n\x0c\n\
-    \x05\x04\x05\x02\0\x01\x12\x03P\x12\x19\n\x0c\n\x05\x04\x05\x02\0\x03\
-    \x12\x03P\x1c\x1d\n\x0b\n\x04\x04\x05\x02\x01\x12\x03Q\x02%\n\x0c\n\x05\
-    \x04\x05\x02\x01\x04\x12\x03Q\x02\n\n\x0c\n\x05\x04\x05\x02\x01\x06\x12\
-    \x03Q\x0b\x18\n\x0c\n\x05\x04\x05\x02\x01\x01\x12\x03Q\x19\x20\n\x0c\n\
-    \x05\x04\x05\x02\x01\x03\x12\x03Q#$b\x06proto3\
+    .PageDataR\x05pages\x12.\n\nchain_head\x18\x06\x20\x01(\x0b2\n.ChainHead\
+    H\x01R\tchainHead\x88\x01\x01\x1av\n\x0cGraphKeyPair\x12(\n\x08key_type\
+    \x18\x01\x20\x01(\x0e2\r.GraphKeyTypeR\x07keyType\x12\x1d\n\npublic_key\
+    \x18\x02\x20\x01(\x0cR\tpublicKey\x12\x1d\n\nsecret_key\x18\x03\x20\x01(\
+    \x0cR\tsecretKeyB\x0c\n\n_dsnp_keysB\r\n\x0b_chain_head\"K\n\nConnection\
+    \x12\x20\n\x0cdsnp_user_id\x18\x01\x20\x01(\x04R\ndsnpUserId\x12\x1b\n\t\
+    schema_id\x18\x02\x20\x01(\rR\x08schemaId\"\xc0\x08\n\x07Actions\x12)\n\
+    \x07actions\x18\x01\x20\x03(\x0b2\x0f.Actions.ActionR\x07actions\x125\n\
+    \x07options\x18\x02\x20\x01(\x0b2\x16.Actions.ActionOptionsH\0R\x07optio\
+    ns\x88\x01\x01\x1a\xc7\x02\n\rActionOptions\x12>\n\x1bignore_existing_co\
+    nnections\x18\x01\x20\x01(\x08R\x19ignoreExistingConnections\x12<\n\x1ai\
+    gnore_missing_connections\x18\x02\x20\x01(\x08R\x18ignoreMissingConnecti\
+    ons\x12.\n\x13disable_auto_commit\x18\x03\x20\x01(\x08R\x11disableAutoCo\
+    mmit\x12;\n\x10timestamp_policy\x18\x04\x20\x01(\x0e2\x10.TimestampPolic\
+    yR\x0ftimestampPolicy\x12$\n\x0emax_batch_size\x18\x05\x20\x01(\rR\x0cma\
+    xBatchSize\x12%\n\x0ecollect_errors\x18\x06\x20\x01(\x08R\rcollectErrors\
+    \x1a\xfc\x04\n\x06Action\x12F\n\x0econnect_action\x18\x01\x20\x01(\x0b2\
+    \x1d.Actions.Action.ConnectActionH\0R\rconnectAction\x12O\n\x11disconnec\
+    t_action\x18\x02\x20\x01(\x0b2\x20.Actions.Action.DisconnectActionH\0R\
+    \x10disconnectAction\x12C\n\x0eadd_key_action\x18\x03\x20\x01(\x0b2\x1b.\
+    Actions.Action.AddGraphKeyH\0R\x0caddKeyAction\x1a\xba\x01\n\rConnectAct\
+    ion\x12+\n\x12owner_dsnp_user_id\x18\x01\x20\x01(\x04R\x0fownerDsnpUserI\
+    d\x12+\n\nconnection\x18\x02\x20\x01(\x0b2\x0b.ConnectionR\nconnection\
+    \x12+\n\tdsnp_keys\x18\x03\x20\x01(\x0b2\t.DsnpKeysH\0R\x08dsnpKeys\x88\
+    \x01\x01\x12\x14\n\x05since\x18\x04\x20\x01(\x04R\x05sinceB\x0c\n\n_dsnp\
+    _keys\x1al\n\x10DisconnectAction\x12+\n\x12owner_dsnp_user_id\x18\x01\
+    \x20\x01(\x04R\x0fownerDsnpUserId\x12+\n\nconnection\x18\x02\x20\x01(\
+    \x0b2\x0b.ConnectionR\nconnection\x1a`\n\x0bAddGraphKey\x12+\n\x12owner_\
+    dsnp_user_id\x18\x01\x20\x01(\x04R\x0fownerDsnpUserId\x12$\n\x0enew_publ\
+    ic_key\x18\x02\x20\x01(\x0cR\x0cnewPublicKeyB\x07\n\x05innerB\n\n\x08_op\
+    tions\"M\n\tChainHead\x12!\n\x0cblock_number\x18\x01\x20\x01(\rR\x0bbloc\
+    kNumber\x12\x1d\n\nblock_hash\x18\x02\x20\x01(\x0cR\tblockHash*\x1a\n\
+    \x0cGraphKeyType\x12\n\n\x06X25519\x10\0*8\n\x0fTimestampPolicy\x12\x12\
+    \n\x0eCallerProvided\x10\0\x12\x07\n\x03Now\x10\x01\x12\x08\n\x04Zero\
+    \x10\x02B%\n!io.projectliberty.graphsdk.modelsP\x01J\xc9\x1c\n\x06\x12\
+    \x04\0\0f\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\x08\x12\x03\
+    \x02\0:\n\t\n\x02\x08\x01\x12\x03\x02\0:\n\x08\n\x01\x08\x12\x03\x03\0\"\
+    \n\t\n\x02\x08\n\x12\x03\x03\0\"\n\n\n\x02\x05\0\x12\x04\x05\0\x07\x01\n\
+    \n\n\x03\x05\0\x01\x12\x03\x05\x05\x11\n\x0b\n\x04\x05\0\x02\0\x12\x03\
+    \x06\x02\r\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03\x06\x02\x08\n\x0c\n\x05\
+    \x05\0\x02\0\x02\x12\x03\x06\x0b\x0c\n\n\n\x02\x05\x01\x12\x04\t\0\r\x01\
+    \n\n\n\x03\x05\x01\x01\x12\x03\t\x05\x14\n\x0b\n\x04\x05\x01\x02\0\x12\
+    \x03\n\x02\x15\n\x0c\n\x05\x05\x01\x02\0\x01\x12\x03\n\x02\x10\n\x0c\n\
+    \x05\x05\x01\x02\0\x02\x12\x03\n\x13\x14\n\x0b\n\x04\x05\x01\x02\x01\x12\
+    \x03\x0b\x02\n\n\x0c\n\x05\x05\x01\x02\x01\x01\x12\x03\x0b\x02\x05\n\x0c\
+    \n\x05\x05\x01\x02\x01\x02\x12\x03\x0b\x08\t\n\x0b\n\x04\x05\x01\x02\x02\
+    \x12\x03\x0c\x02\x0b\n\x0c\n\x05\x05\x01\x02\x02\x01\x12\x03\x0c\x02\x06\
+    \n\x0c\n\x05\x05\x01\x02\x02\x02\x12\x03\x0c\t\n\n\n\n\x02\x04\0\x12\x04\
+    \x0f\0\x12\x01\n\n\n\x03\x04\0\x01\x12\x03\x0f\x08\x0f\n\x0b\n\x04\x04\0\
+    \x02\0\x12\x03\x10\x02\x13\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x10\x02\
+    \x08\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x10\t\x0e\n\x0c\n\x05\x04\0\x02\
+    \0\x03\x12\x03\x10\x11\x12\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x11\x02\x14\
+    \n\x0c\n\x05\x04\0\x02\x01\x05\x12\x03\x11\x02\x07\n\x0c\n\x05\x04\0\x02\
+    \x01\x01\x12\x03\x11\x08\x0f\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x11\
+    \x12\x13\n\n\n\x02\x04\x01\x12\x04\x14\0\x18\x01\n\n\n\x03\x04\x01\x01\
+    \x12\x03\x14\x08\x10\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x15\x02\x15\n\x0c\
+    \n\x05\x04\x01\x02\0\x05\x12\x03\x15\x02\x08\n\x0c\n\x05\x04\x01\x02\0\
+    \x01\x12\x03\x15\t\x10\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x15\x13\x14\
+    \n\x0b\n\x04\x04\x01\x02\x01\x12\x03\x16\x02\x14\n\x0c\n\x05\x04\x01\x02\
+    \x01\x05\x12\x03\x16\x02\x07\n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\x16\
+    \x08\x0f\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\x16\x12\x13\n\x0b\n\x04\
+    \x04\x01\x02\x02\x12\x03\x17\x02\x1a\n\x0c\n\x05\x04\x01\x02\x02\x05\x12\
+    \x03\x17\x02\x08\n\x0c\n\x05\x04\x01\x02\x02\x01\x12\x03\x17\t\x15\n\x0c\
+    \n\x05\x04\x01\x02\x02\x03\x12\x03\x17\x18\x19\n\n\n\x02\x04\x02\x12\x04\
+    \x1a\0\x1e\x01\n\n\n\x03\x04\x02\x01\x12\x03\x1a\x08\x10\n\x0b\n\x04\x04\
+    \x02\x02\0\x12\x03\x1b\x02\x1a\n\x0c\n\x05\x04\x02\x02\0\x05\x12\x03\x1b\
+    \x02\x08\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\x1b\t\x15\n\x0c\n\x05\x04\
+    \x02\x02\0\x03\x12\x03\x1b\x18\x19\n\x0b\n\x04\x04\x02\x02\x01\x12\x03\
+    \x1c\x02\x17\n\x0c\n\x05\x04\x02\x02\x01\x05\x12\x03\x1c\x02\x08\n\x0c\n\
+    \x05\x04\x02\x02\x01\x01\x12\x03\x1c\t\x12\n\x0c\n\x05\x04\x02\x02\x01\
+    \x03\x12\x03\x1c\x15\x16\n\x0b\n\x04\x04\x02\x02\x02\x12\x03\x1d\x02\x1c\
+    \n\x0c\n\x05\x04\x02\x02\x02\x04\x12\x03\x1d\x02\n\n\x0c\n\x05\x04\x02\
+    \x02\x02\x06\x12\x03\x1d\x0b\x12\n\x0c\n\x05\x04\x02\x02\x02\x01\x12\x03\
+    \x1d\x13\x17\n\x0c\n\x05\x04\x02\x02\x02\x03\x12\x03\x1d\x1a\x1b\n\n\n\
+    \x02\x04\x03\x12\x04\x20\00\x01\n\n\n\x03\x04\x03\x01\x12\x03\x20\x08\
+    \x15\n\x0c\n\x04\x04\x03\x03\0\x12\x04!\x02.\x03\n\x0c\n\x05\x04\x03\x03\
+    \0\x01\x12\x03!\n\x16\n\x0e\n\x06\x04\x03\x03\0\x03\0\x12\x04\"\x04&\x05\
+    \n\x0e\n\x07\x04\x03\x03\0\x03\0\x01\x12\x03\"\x0c\x18\n\x0f\n\x08\x04\
+    \x03\x03\0\x03\0\x02\0\x12\x03#\x06\x20\n\x10\n\t\x04\x03\x03\0\x03\0\
+    \x02\0\x06\x12\x03#\x06\x12\n\x10\n\t\x04\x03\x03\0\x03\0\x02\0\x01\x12\
+    \x03#\x13\x1b\n\x10\n\t\x04\x03\x03\0\x03\0\x02\0\x03\x12\x03#\x1e\x1f\n\
+    \x0f\n\x08\x04\x03\x03\0\x03\0\x02\x01\x12\x03$\x06\x1b\n\x10\n\t\x04\
+    \x03\x03\0\x03\0\x02\x01\x05\x12\x03$\x06\x0b\n\x10\n\t\x04\x03\x03\0\
+    \x03\0\x02\x01\x01\x12\x03$\x0c\x16\n\x10\n\t\x04\x03\x03\0\x03\0\x02\
+    \x01\x03\x12\x03$\x19\x1a\n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\x02\x12\
+    \x03%\x06\x1b\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x02\x05\x12\x03%\x06\x0b\
+    \n\x10\n\t\x04\x03\x03\0\x03\0\x02\x02\x01\x12\x03%\x0c\x16\n\x10\n\t\
+    \x04\x03\x03\0\x03\0\x02\x02\x03\x12\x03%\x19\x1a\n\r\n\x06\x04\x03\x03\
+    \0\x02\0\x12\x03(\x04\x1c\n\x0e\n\x07\x04\x03\x03\0\x02\0\x05\x12\x03(\
+    \x04\n\n\x0e\n\x07\x04\x03\x03\0\x02\0\x01\x12\x03(\x0b\x17\n\x0e\n\x07\
+    \x04\x03\x03\0\x02\0\x03\x12\x03(\x1a\x1b\n\r\n\x06\x04\x03\x03\0\x02\
+    \x01\x12\x03)\x04\x19\n\x0e\n\x07\x04\x03\x03\0\x02\x01\x05\x12\x03)\x04\
+    \n\n\x0e\n\x07\x04\x03\x03\0\x02\x01\x01\x12\x03)\x0b\x14\n\x0e\n\x07\
+    \x04\x03\x03\0\x02\x01\x03\x12\x03)\x17\x18\n\r\n\x06\x04\x03\x03\0\x02\
+    \x02\x12\x03*\x04(\n\x0e\n\x07\x04\x03\x03\0\x02\x02\x04\x12\x03*\x04\
+    \x0c\n\x0e\n\x07\x04\x03\x03\0\x02\x02\x06\x12\x03*\r\x19\n\x0e\n\x07\
+    \x04\x03\x03\0\x02\x02\x01\x12\x03*\x1a#\n\x0e\n\x07\x04\x03\x03\0\x02\
+    \x02\x03\x12\x03*&'\n\r\n\x06\x04\x03\x03\0\x02\x03\x12\x03+\x04$\n\x0e\
+    \n\x07\x04\x03\x03\0\x02\x03\x04\x12\x03+\x04\x0c\n\x0e\n\x07\x04\x03\
+    \x03\0\x02\x03\x06\x12\x03+\r\x15\n\x0e\n\x07\x04\x03\x03\0\x02\x03\x01\
+    \x12\x03+\x16\x1f\n\x0e\n\x07\x04\x03\x03\0\x02\x03\x03\x12\x03+\"#\n\r\
+    \n\x06\x04\x03\x03\0\x02\x04\x12\x03,\x04\x20\n\x0e\n\x07\x04\x03\x03\0\
+    \x02\x04\x04\x12\x03,\x04\x0c\n\x0e\n\x07\x04\x03\x03\0\x02\x04\x06\x12\
+    \x03,\r\x15\n\x0e\n\x07\x04\x03\x03\0\x02\x04\x01\x12\x03,\x16\x1b\n\x0e\
+    \n\x07\x04\x03\x03\0\x02\x04\x03\x12\x03,\x1e\x1f\n\r\n\x06\x04\x03\x03\
+    \0\x02\x05\x12\x03-\x04&\n\x0e\n\x07\x04\x03\x03\0\x02\x05\x04\x12\x03-\
+    \x04\x0c\n\x0e\n\x07\x04\x03\x03\0\x02\x05\x06\x12\x03-\r\x16\n\x0e\n\
+    \x07\x04\x03\x03\0\x02\x05\x01\x12\x03-\x17!\n\x0e\n\x07\x04\x03\x03\0\
+    \x02\x05\x03\x12\x03-$%\n\x0b\n\x04\x04\x03\x02\0\x12\x03/\x02$\n\x0c\n\
+    \x05\x04\x03\x02\0\x04\x12\x03/\x02\n\n\x0c\n\x05\x04\x03\x02\0\x06\x12\
+    \x03/\x0b\x17\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03/\x18\x1f\n\x0c\n\x05\
+    \x04\x03\x02\0\x03\x12\x03/\"#\n\"\n\x02\x04\x04\x12\x043\06\x01\x1a\x16\
+    \x20Action\x20related\x20types\n\n\n\n\x03\x04\x04\x01\x12\x033\x08\x12\
+    \n\x0b\n\x04\x04\x04\x02\0\x12\x034\x02\x1a\n\x0c\n\x05\x04\x04\x02\0\
+    \x05\x12\x034\x02\x08\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x034\t\x15\n\x0c\
+    \n\x05\x04\x04\x02\0\x03\x12\x034\x18\x19\n\x0b\n\x04\x04\x04\x02\x01\
+    \x12\x035\x02\x17\n\x0c\n\x05\x04\x04\x02\x01\x05\x12\x035\x02\x08\n\x0c\
+    \n\x05\x04\x04\x02\x01\x01\x12\x035\t\x12\n\x0c\n\x05\x04\x04\x02\x01\
+    \x03\x12\x035\x15\x16\n\n\n\x02\x04\x05\x12\x048\0`\x01\n\n\n\x03\x04\
+    \x05\x01\x12\x038\x08\x0f\n\x0c\n\x04\x04\x05\x03\0\x12\x049\x02B\x03\n\
+    \x0c\n\x05\x04\x05\x03\0\x01\x12\x039\n\x17\n\r\n\x06\x04\x05\x03\0\x02\
+    \0\x12\x03:\x04)\n\x0e\n\x07\x04\x05\x03\0\x02\0\x05\x12\x03:\x04\x08\n\
+    \x0e\n\x07\x04\x05\x03\0\x02\0\x01\x12\x03:\t$\n\x0e\n\x07\x04\x05\x03\0\
+    \x02\0\x03\x12\x03:'(\n\r\n\x06\x04\x05\x03\0\x02\x01\x12\x03;\x04(\n\
+    \x0e\n\x07\x04\x05\x03\0\x02\x01\x05\x12\x03;\x04\x08\n\x0e\n\x07\x04\
+    \x05\x03\0\x02\x01\x01\x12\x03;\t#\n\x0e\n\x07\x04\x05\x03\0\x02\x01\x03\
+    \x12\x03;&'\n\r\n\x06\x04\x05\x03\0\x02\x02\x12\x03<\x04!\n\x0e\n\x07\
+    \x04\x05\x03\0\x02\x02\x05\x12\x03<\x04\x08\n\x0e\n\x07\x04\x05\x03\0\
+    \x02\x02\x01\x12\x03<\t\x1c\n\x0e\n\x07\x04\x05\x03\0\x02\x02\x03\x12\
+    \x03<\x1f\x20\n\r\n\x06\x04\x05\x03\0\x02\x03\x12\x03=\x04)\n\x0e\n\x07\
+    \x04\x05\x03\0\x02\x03\x06\x12\x03=\x04\x13\n\x0e\n\x07\x04\x05\x03\0\
+    \x02\x03\x01\x12\x03=\x14$\n\x0e\n\x07\x04\x05\x03\0\x02\x03\x03\x12\x03\
+    ='(\nD\n\x06\x04\x05\x03\0\x02\x04\x12\x03?\x04\x1e\x1a5\x20maximum\x20n\
+    umber\x20of\x20actions\x20in\x20a\x20call,\x200\x20for\x20no\x20limit\n\
+    \n\x0e\n\x07\x04\x05\x03\0\x02\x04\x05\x12\x03?\x04\n\n\x0e\n\x07\x04\
+    \x05\x03\0\x02\x04\x01\x12\x03?\x0b\x19\n\x0e\n\x07\x04\x05\x03\0\x02\
+    \x04\x03\x12\x03?\x1c\x1d\nl\n\x06\x04\x05\x03\0\x02\x05\x12\x03A\x04\
+    \x1c\x1a]\x20attempt\x20all\x20actions\x20and\x20fail\x20with\x20every\
+    \x20failing\x20action\x20instead\x20of\x20stopping\x20on\x20the\x20first\
+    \x20one\n\n\x0e\n\x07\x04\x05\x03\0\x02\x05\x05\x12\x03A\x04\x08\n\x0e\n\
+    \x07\x04\x05\x03\0\x02\x05\x01\x12\x03A\t\x17\n\x0e\n\x07\x04\x05\x03\0\
+    \x02\x05\x03\x12\x03A\x1a\x1b\n\x0c\n\x04\x04\x05\x03\x01\x12\x04D\x02\\\
+    \x03\n\x0c\n\x05\x04\x05\x03\x01\x01\x12\x03D\n\x10\n\x0e\n\x06\x04\x05\
+    \x03\x01\x03\0\x12\x04E\x04K\x05\n\x0e\n\x07\x04\x05\x03\x01\x03\0\x01\
+    \x12\x03E\x0c\x19\n\x0f\n\x08\x04\x05\x03\x01\x03\0\x02\0\x12\x03F\x06$\
+    \n\x10\n\t\x04\x05\x03\x01\x03\0\x02\0\x05\x12\x03F\x06\x0c\n\x10\n\t\
+    \x04\x05\x03\x01\x03\0\x02\0\x01\x12\x03F\r\x1f\n\x10\n\t\x04\x05\x03\
+    \x01\x03\0\x02\0\x03\x12\x03F\"#\n\x0f\n\x08\x04\x05\x03\x01\x03\0\x02\
+    \x01\x12\x03G\x06\x20\n\x10\n\t\x04\x05\x03\x01\x03\0\x02\x01\x06\x12\
+    \x03G\x06\x10\n\x10\n\t\x04\x05\x03\x01\x03\0\x02\x01\x01\x12\x03G\x11\
+    \x1b\n\x10\n\t\x04\x05\x03\x01\x03\0\x02\x01\x03\x12\x03G\x1e\x1f\n\x0f\
+    \n\x08\x04\x05\x03\x01\x03\0\x02\x02\x12\x03H\x06&\n\x10\n\t\x04\x05\x03\
+    \x01\x03\0\x02\x02\x04\x12\x03H\x06\x0e\n\x10\n\t\x04\x05\x03\x01\x03\0\
+    \x02\x02\x06\x12\x03H\x0f\x17\n\x10\n\t\x04\x05\x03\x01\x03\0\x02\x02\
+    \x01\x12\x03H\x18!\n\x10\n\t\x04\x05\x03\x01\x03\0\x02\x02\x03\x12\x03H$\
+    %\nC\n\x08\x04\x05\x03\x01\x03\0\x02\x03\x12\x03J\x06\x17\x1a2\x20since\
+    \x20value\x20of\x20the\x20connection,\x200\x20if\x20not\x20provided\n\n\
+    \x10\n\t\x04\x05\x03\x01\x03\0\x02\x03\x05\x12\x03J\x06\x0c\n\x10\n\t\
+    \x04\x05\x03\x01\x03\0\x02\x03\x01\x12\x03J\r\x12\n\x10\n\t\x04\x05\x03\
+    \x01\x03\0\x02\x03\x03\x12\x03J\x15\x16\n\x0e\n\x06\x04\x05\x03\x01\x03\
+    \x01\x12\x04M\x04P\x05\n\x0e\n\x07\x04\x05\x03\x01\x03\x01\x01\x12\x03M\
+    \x0c\x1c\n\x0f\n\x08\x04\x05\x03\x01\x03\x01\x02\0\x12\x03N\x06$\n\x10\n\
+    \t\x04\x05\x03\x01\x03\x01\x02\0\x05\x12\x03N\x06\x0c\n\x10\n\t\x04\x05\
+    \x03\x01\x03\x01\x02\0\x01\x12\x03N\r\x1f\n\x10\n\t\x04\x05\x03\x01\x03\
+    \x01\x02\0\x03\x12\x03N\"#\n\x0f\n\x08\x04\x05\x03\x01\x03\x01\x02\x01\
+    \x12\x03O\x06\x20\n\x10\n\t\x04\x05\x03\x01\x03\x01\x02\x01\x06\x12\x03O\
+    \x06\x10\n\x10\n\t\x04\x05\x03\x01\x03\x01\x02\x01\x01\x12\x03O\x11\x1b\
+    \n\x10\n\t\x04\x05\x03\x01\x03\x01\x02\x01\x03\x12\x03O\x1e\x1f\n\x0e\n\
+    \x06\x04\x05\x03\x01\x03\x02\x12\x04R\x04U\x05\n\x0e\n\x07\x04\x05\x03\
+    \x01\x03\x02\x01\x12\x03R\x0c\x17\n\x0f\n\x08\x04\x05\x03\x01\x03\x02\
+    \x02\0\x12\x03S\x06$\n\x10\n\t\x04\x05\x03\x01\x03\x02\x02\0\x05\x12\x03\
+    S\x06\x0c\n\x10\n\t\x04\x05\x03\x01\x03\x02\x02\0\x01\x12\x03S\r\x1f\n\
+    \x10\n\t\x04\x05\x03\x01\x03\x02\x02\0\x03\x12\x03S\"#\n\x0f\n\x08\x04\
+    \x05\x03\x01\x03\x02\x02\x01\x12\x03T\x06\x1f\n\x10\n\t\x04\x05\x03\x01\
+    \x03\x02\x02\x01\x05\x12\x03T\x06\x0b\n\x10\n\t\x04\x05\x03\x01\x03\x02\
+    \x02\x01\x01\x12\x03T\x0c\x1a\n\x10\n\t\x04\x05\x03\x01\x03\x02\x02\x01\
+    \x03\x12\x03T\x1d\x1e\n\x0e\n\x06\x04\x05\x03\x01\x08\0\x12\x04W\x04[\
+    \x05\n\x0e\n\x07\x04\x05\x03\x01\x08\0\x01\x12\x03W\n\x0f\n\r\n\x06\x04\
+    \x05\x03\x01\x02\0\x12\x03X\x06'\n\x0e\n\x07\x04\x05\x03\x01\x02\0\x06\
+    \x12\x03X\x06\x13\n\x0e\n\x07\x04\x05\x03\x01\x02\0\x01\x12\x03X\x14\"\n\
+    \x0e\n\x07\x04\x05\x03\x01\x02\0\x03\x12\x03X%&\n\r\n\x06\x04\x05\x03\
+    \x01\x02\x01\x12\x03Y\x06-\n\x0e\n\x07\x04\x05\x03\x01\x02\x01\x06\x12\
+    \x03Y\x06\x16\n\x0e\n\x07\x04\x05\x03\x01\x02\x01\x01\x12\x03Y\x17(\n\
+    \x0e\n\x07\x04\x05\x03\x01\x02\x01\x03\x12\x03Y+,\n\r\n\x06\x04\x05\x03\
+    \x01\x02\x02\x12\x03Z\x06%\n\x0e\n\x07\x04\x05\x03\x01\x02\x02\x06\x12\
+    \x03Z\x06\x11\n\x0e\n\x07\x04\x05\x03\x01\x02\x02\x01\x12\x03Z\x12\x20\n\
+    \x0e\n\x07\x04\x05\x03\x01\x02\x02\x03\x12\x03Z#$\n\x0b\n\x04\x04\x05\
+    \x02\0\x12\x03^\x02\x1e\n\x0c\n\x05\x04\x05\x02\0\x04\x12\x03^\x02\n\n\
+    \x0c\n\x05\x04\x05\x02\0\x06\x12\x03^\x0b\x11\n\x0c\n\x05\x04\x05\x02\0\
+    \x01\x12\x03^\x12\x19\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x03^\x1c\x1d\n\
+    \x0b\n\x04\x04\x05\x02\x01\x12\x03_\x02%\n\x0c\n\x05\x04\x05\x02\x01\x04\
+    \x12\x03_\x02\n\n\x0c\n\x05\x04\x05\x02\x01\x06\x12\x03_\x0b\x18\n\x0c\n\
+    \x05\x04\x05\x02\x01\x01\x12\x03_\x19\x20\n\x0c\n\x05\x04\x05\x02\x01\
+    \x03\x12\x03_#$\nD\n\x02\x04\x06\x12\x04c\0f\x01\x1a8\x20block\x20of\x20\
+    the\x20chain\x20at\x20which\x20the\x20imported\x20data\x20was\x20read\n\
+    \n\n\n\x03\x04\x06\x01\x12\x03c\x08\x11\n\x0b\n\x04\x04\x06\x02\0\x12\
+    \x03d\x02\x1a\n\x0c\n\x05\x04\x06\x02\0\x05\x12\x03d\x02\x08\n\x0c\n\x05\
+    \x04\x06\x02\0\x01\x12\x03d\t\x15\n\x0c\n\x05\x04\x06\x02\0\x03\x12\x03d\
+    \x18\x19\n\x0b\n\x04\x04\x06\x02\x01\x12\x03e\x02\x17\n\x0c\n\x05\x04\
+    \x06\x02\x01\x05\x12\x03e\x02\x07\n\x0c\n\x05\x04\x06\x02\x01\x01\x12\
+    \x03e\x08\x12\n\x0c\n\x05\x04\x06\x02\x01\x03\x12\x03e\x15\x16b\x06proto\
+    3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -2444,13 +2787,14 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
     file_descriptor.get(|| {
         let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
             let mut deps = ::std::vec::Vec::with_capacity(0);
-            let mut messages = ::std::vec::Vec::with_capacity(13);
+            let mut messages = ::std::vec::Vec::with_capacity(14);
             messages.push(KeyData::generated_message_descriptor_data());
             messages.push(PageData::generated_message_descriptor_data());
             messages.push(DsnpKeys::generated_message_descriptor_data());
             messages.push(ImportBundles::generated_message_descriptor_data());
             messages.push(Connection::generated_message_descriptor_data());
             messages.push(Actions::generated_message_descriptor_data());
+            messages.push(ChainHead::generated_message_descriptor_data());
             messages.push(import_bundles::ImportBundle::generated_message_descriptor_data());
             messages.push(import_bundles::import_bundle::GraphKeyPair::generated_message_descriptor_data());
             messages.push(actions::ActionOptions::generated_message_descriptor_data());
@@ -2458,8 +2802,9 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
             messages.push(actions::action::ConnectAction::generated_message_descriptor_data());
             messages.push(actions::action::DisconnectAction::generated_message_descriptor_data());
             messages.push(actions::action::AddGraphKey::generated_message_descriptor_data());
-            let mut enums = ::std::vec::Vec::with_capacity(1);
+            let mut enums = ::std::vec::Vec::with_capacity(2);
             enums.push(GraphKeyType::generated_enum_descriptor_data());
+            enums.push(TimestampPolicy::generated_enum_descriptor_data());
             ::protobuf::reflect::GeneratedFileDescriptor::new_generated(
                 file_descriptor_proto(),
                 deps,
@@ -182,6 +182,8 @@ pub struct Config {
     pub dsnp_versions: ::std::vec::Vec<::protobuf::EnumOrUnknown<DsnpVersion>>,
     // @@protoc_insertion_point(field:Config.graph_public_key_schema_id)
     pub graph_public_key_schema_id: u32,
+    // @@protoc_insertion_point(field:Config.max_update_payload_bytes)
+    pub max_update_payload_bytes: u32,
     // special fields
     // @@protoc_insertion_point(special_field:Config.special_fields)
     pub special_fields: ::protobuf::SpecialFields,
@@ -199,7 +201,7 @@ impl Config {
     }
 
     fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-        let mut fields = ::std::vec::Vec::with_capacity(7);
+        let mut fields = ::std::vec::Vec::with_capacity(8);
         let mut oneofs = ::std::vec::Vec::with_capacity(0);
         fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
             "sdk_max_stale_friendship_days",
@@ -236,6 +238,11 @@ impl Config {
             |m: &Config| { &m.graph_public_key_schema_id },
             |m: &mut Config| { &mut m.graph_public_key_schema_id },
         ));
+        fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
+            "max_update_payload_bytes",
+            |m: &Config| { &m.max_update_payload_bytes },
+            |m: &mut Config| { &mut m.max_update_payload_bytes },
+        ));
         ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<Config>(
             "Config",
             fields,
@@ -290,6 +297,9 @@ impl ::protobuf::Message for Config {
                 56 => {
                     self.graph_public_key_schema_id = is.read_uint32()?;
                 },
+                64 => {
+                    self.max_update_payload_bytes = is.read_uint32()?;
+                },
                 tag => {
                     ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                 },
@@ -325,6 +335,9 @@ impl ::protobuf::Message for Config {
         if self.graph_public_key_schema_id != 0 {
             my_size += ::protobuf::rt::uint32_size(7, self.graph_public_key_schema_id);
         }
+        if self.max_update_payload_bytes != 0 {
+            my_size += ::protobuf::rt::uint32_size(8, self.max_update_payload_bytes);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
         self.special_fields.cached_size().set(my_size as u32);
         my_size
@@ -357,6 +370,9 @@ impl ::protobuf::Message for Config {
         if self.graph_public_key_schema_id != 0 {
             os.write_uint32(7, self.graph_public_key_schema_id)?;
         }
+        if self.max_update_payload_bytes != 0 {
+            os.write_uint32(8, self.max_update_payload_bytes)?;
+        }
         os.write_unknown_fields(self.special_fields.unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -381,6 +397,7 @@ impl ::protobuf::Message for Config {
         self.schema_map.clear();
         self.dsnp_versions.clear();
         self.graph_public_key_schema_id = 0;
+        self.max_update_payload_bytes = 0;
         self.special_fields.clear();
     }
 
@@ -1033,6 +1050,8 @@ pub mod updates {
             pub prev_hash: u32,
             // @@protoc_insertion_point(field:Updates.Update.PersistPageUpdate.payload)
             pub payload: ::std::vec::Vec<u8>,
+            // @@protoc_insertion_point(field:Updates.Update.PersistPageUpdate.chain_head)
+            pub chain_head: ::protobuf::MessageField<super::super::super::input::ChainHead>,
             // special fields
             // @@protoc_insertion_point(special_field:Updates.Update.PersistPageUpdate.special_fields)
             pub special_fields: ::protobuf::SpecialFields,
@@ -1050,7 +1069,7 @@ pub mod updates {
             }
 
             pub(in super::super) fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-                let mut fields = ::std::vec::Vec::with_capacity(5);
+                let mut fields = ::std::vec::Vec::with_capacity(6);
                 let mut oneofs = ::std::vec::Vec::with_capacity(0);
                 fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
                     "owner_dsnp_user_id",
@@ -1077,6 +1096,11 @@ pub mod updates {
                     |m: &PersistPageUpdate| { &m.payload },
                     |m: &mut PersistPageUpdate| { &mut m.payload },
                 ));
+                fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, super::super::super::input::ChainHead>(
+                    "chain_head",
+                    |m: &PersistPageUpdate| { &m.chain_head },
+                    |m: &mut PersistPageUpdate| { &mut m.chain_head },
+                ));
                 ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<PersistPageUpdate>(
                     "Updates.Update.PersistPageUpdate",
                     fields,
@@ -1110,6 +1134,9 @@ pub mod updates {
                         42 => {
                             self.payload = is.read_bytes()?;
                         },
+                        50 => {
+                            ::protobuf::rt::read_singular_message_into_field(is, &mut self.chain_head)?;
+                        },
                         tag => {
                             ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                         },
@@ -1137,6 +1164,10 @@ pub mod updates {
                 if !self.payload.is_empty() {
                     my_size += ::protobuf::rt::bytes_size(5, &self.payload);
                 }
+                if let Some(v) = self.chain_head.as_ref() {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                }
                 my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
                 self.special_fields.cached_size().set(my_size as u32);
                 my_size
@@ -1158,6 +1189,9 @@ pub mod updates {
                 if !self.payload.is_empty() {
                     os.write_bytes(5, &self.payload)?;
                 }
+                if let Some(v) = self.chain_head.as_ref() {
+                    ::protobuf::rt::write_message_field_with_cached_size(6, v, os)?;
+                }
                 os.write_unknown_fields(self.special_fields.unknown_fields())?;
                 ::std::result::Result::Ok(())
             }
@@ -1180,6 +1214,7 @@ pub mod updates {
                 self.page_id = 0;
                 self.prev_hash = 0;
                 self.payload.clear();
+                self.chain_head.clear();
                 self.special_fields.clear();
             }
 
@@ -1190,6 +1225,7 @@ pub mod updates {
                     page_id: 0,
                     prev_hash: 0,
                     payload: ::std::vec::Vec::new(),
+                    chain_head: ::protobuf::MessageField::none(),
                     special_fields: ::protobuf::SpecialFields::new(),
                 };
                 &instance
@@ -1225,6 +1261,8 @@ pub mod updates {
             pub page_id: u32,
             // @@protoc_insertion_point(field:Updates.Update.DeletePageUpdate.prev_hash)
             pub prev_hash: u32,
+            // @@protoc_insertion_point(field:Updates.Update.DeletePageUpdate.chain_head)
+            pub chain_head: ::protobuf::MessageField<super::super::super::input::ChainHead>,
             // special fields
             // @@protoc_insertion_point(special_field:Updates.Update.DeletePageUpdate.special_fields)
             pub special_fields: ::protobuf::SpecialFields,
@@ -1242,7 +1280,7 @@ pub mod updates {
             }
 
             pub(in super::super) fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-                let mut fields = ::std::vec::Vec::with_capacity(4);
+                let mut fields = ::std::vec::Vec::with_capacity(5);
                 let mut oneofs = ::std::vec::Vec::with_capacity(0);
                 fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
                     "owner_dsnp_user_id",
@@ -1264,6 +1302,11 @@ pub mod updates {
                     |m: &DeletePageUpdate| { &m.prev_hash },
                     |m: &mut DeletePageUpdate| { &mut m.prev_hash },
                 ));
+                fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, super::super::super::input::ChainHead>(
+                    "chain_head",
+                    |m: &DeletePageUpdate| { &m.chain_head },
+                    |m: &mut DeletePageUpdate| { &mut m.chain_head },
+                ));
                 ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<DeletePageUpdate>(
                     "Updates.Update.DeletePageUpdate",
                     fields,
@@ -1294,6 +1337,9 @@ pub mod updates {
                         32 => {
                             self.prev_hash = is.read_uint32()?;
                         },
+                        42 => {
+                            ::protobuf::rt::read_singular_message_into_field(is, &mut self.chain_head)?;
+                        },
                         tag => {
                             ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                         },
@@ -1318,6 +1364,10 @@ pub mod updates {
                 if self.prev_hash != 0 {
                     my_size += ::protobuf::rt::uint32_size(4, self.prev_hash);
                 }
+                if let Some(v) = self.chain_head.as_ref() {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                }
                 my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
                 self.special_fields.cached_size().set(my_size as u32);
                 my_size
@@ -1336,6 +1386,9 @@ pub mod updates {
                 if self.prev_hash != 0 {
                     os.write_uint32(4, self.prev_hash)?;
                 }
+                if let Some(v) = self.chain_head.as_ref() {
+                    ::protobuf::rt::write_message_field_with_cached_size(5, v, os)?;
+                }
                 os.write_unknown_fields(self.special_fields.unknown_fields())?;
                 ::std::result::Result::Ok(())
             }
@@ -1357,6 +1410,7 @@ pub mod updates {
                 self.schema_id = 0;
                 self.page_id = 0;
                 self.prev_hash = 0;
+                self.chain_head.clear();
                 self.special_fields.clear();
             }
 
@@ -1366,6 +1420,7 @@ pub mod updates {
                     schema_id: 0,
                     page_id: 0,
                     prev_hash: 0,
+                    chain_head: ::protobuf::MessageField::none(),
                     special_fields: ::protobuf::SpecialFields::new(),
                 };
                 &instance
@@ -1399,6 +1454,8 @@ pub mod updates {
             pub prev_hash: u32,
             // @@protoc_insertion_point(field:Updates.Update.AddKeyUpdate.payload)
             pub payload: ::std::vec::Vec<u8>,
+            // @@protoc_insertion_point(field:Updates.Update.AddKeyUpdate.chain_head)
+            pub chain_head: ::protobuf::MessageField<super::super::super::input::ChainHead>,
             // special fields
             // @@protoc_insertion_point(special_field:Updates.Update.AddKeyUpdate.special_fields)
             pub special_fields: ::protobuf::SpecialFields,
@@ -1416,7 +1473,7 @@ pub mod updates {
             }
 
             pub(in super::super) fn generated_message_descriptor_data() -> ::protobuf::reflect::GeneratedMessageDescriptorData {
-                let mut fields = ::std::vec::Vec::with_capacity(3);
+                let mut fields = ::std::vec::Vec::with_capacity(4);
                 let mut oneofs = ::std::vec::Vec::with_capacity(0);
                 fields.push(::protobuf::reflect::rt::v2::make_simpler_field_accessor::<_, _>(
                     "owner_dsnp_user_id",
@@ -1433,6 +1490,11 @@ pub mod updates {
                     |m: &AddKeyUpdate| { &m.payload },
                     |m: &mut AddKeyUpdate| { &mut m.payload },
                 ));
+                fields.push(::protobuf::reflect::rt::v2::make_message_field_accessor::<_, super::super::super::input::ChainHead>(
+                    "chain_head",
+                    |m: &AddKeyUpdate| { &m.chain_head },
+                    |m: &mut AddKeyUpdate| { &mut m.chain_head },
+                ));
                 ::protobuf::reflect::GeneratedMessageDescriptorData::new_2::<AddKeyUpdate>(
                     "Updates.Update.AddKeyUpdate",
                     fields,
@@ -1460,6 +1522,9 @@ pub mod updates {
                         26 => {
                             self.payload = is.read_bytes()?;
                         },
+                        34 => {
+                            ::protobuf::rt::read_singular_message_into_field(is, &mut self.chain_head)?;
+                        },
                         tag => {
                             ::protobuf::rt::read_unknown_or_skip_group(tag, is, self.special_fields.mut_unknown_fields())?;
                         },
@@ -1481,6 +1546,10 @@ pub mod updates {
                 if !self.payload.is_empty() {
                     my_size += ::protobuf::rt::bytes_size(3, &self.payload);
                 }
+                if let Some(v) = self.chain_head.as_ref() {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint64_size(len) + len;
+                }
                 my_size += ::protobuf::rt::unknown_fields_size(self.special_fields.unknown_fields());
                 self.special_fields.cached_size().set(my_size as u32);
                 my_size
@@ -1496,6 +1565,9 @@ pub mod updates {
                 if !self.payload.is_empty() {
                     os.write_bytes(3, &self.payload)?;
                 }
+                if let Some(v) = self.chain_head.as_ref() {
+                    ::protobuf::rt::write_message_field_with_cached_size(4, v, os)?;
+                }
                 os.write_unknown_fields(self.special_fields.unknown_fields())?;
                 ::std::result::Result::Ok(())
             }
@@ -1516,6 +1588,7 @@ pub mod updates {
                 self.owner_dsnp_user_id = 0;
                 self.prev_hash = 0;
                 self.payload.clear();
+                self.chain_head.clear();
                 self.special_fields.clear();
             }
 
@@ -1524,6 +1597,7 @@ pub mod updates {
                     owner_dsnp_user_id: 0,
                     prev_hash: 0,
                     payload: ::std::vec::Vec::new(),
+                    chain_head: ::protobuf::MessageField::none(),
                     special_fields: ::protobuf::SpecialFields::new(),
                 };
                 &instance
@@ -2466,203 +2540,224 @@ impl EnvironmentType {
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n!bridge/common/protos/output.proto\"y\n\x0cSchemaConfig\x12/\n\x0cdsnp\
-    _version\x18\x01\x20\x01(\x0e2\x0c.DsnpVersionR\x0bdsnpVersion\x128\n\
-    \x0fconnection_type\x18\x02\x20\x01(\x0e2\x0f.ConnectionTypeR\x0econnect\
-    ionType\"\xcd\x03\n\x06Config\x12@\n\x1dsdk_max_stale_friendship_days\
-    \x18\x01\x20\x01(\rR\x19sdkMaxStaleFriendshipDays\x128\n\x19max_graph_pa\
-    ge_size_bytes\x18\x02\x20\x01(\rR\x15maxGraphPageSizeBytes\x12\x1e\n\x0b\
-    max_page_id\x18\x03\x20\x01(\rR\tmaxPageId\x124\n\x17max_key_page_size_b\
-    ytes\x18\x04\x20\x01(\rR\x13maxKeyPageSizeBytes\x125\n\nschema_map\x18\
-    \x05\x20\x03(\x0b2\x16.Config.SchemaMapEntryR\tschemaMap\x121\n\rdsnp_ve\
-    rsions\x18\x06\x20\x03(\x0e2\x0c.DsnpVersionR\x0cdsnpVersions\x12:\n\x1a\
-    graph_public_key_schema_id\x18\x07\x20\x01(\rR\x16graphPublicKeySchemaId\
-    \x1aK\n\x0eSchemaMapEntry\x12\x10\n\x03key\x18\x01\x20\x01(\rR\x03key\
-    \x12#\n\x05value\x18\x02\x20\x01(\x0b2\r.SchemaConfigR\x05value:\x028\
-    \x01\"{\n\x0bEnvironment\x12;\n\x10environment_type\x18\x01\x20\x01(\x0e\
-    2\x10.EnvironmentTypeR\x0fenvironmentType\x12$\n\x06config\x18\x02\x20\
-    \x01(\x0b2\x07.ConfigH\0R\x06config\x88\x01\x01B\t\n\x07_config\"\xb3\
-    \x05\n\x07Updates\x12'\n\x06update\x18\x01\x20\x03(\x0b2\x0f.Updates.Upd\
-    ateR\x06update\x1a\xfe\x04\n\x06Update\x12=\n\x07persist\x18\x01\x20\x01\
-    (\x0b2!.Updates.Update.PersistPageUpdateH\0R\x07persist\x12:\n\x06delete\
-    \x18\x02\x20\x01(\x0b2\x20.Updates.Update.DeletePageUpdateH\0R\x06delete\
-    \x127\n\x07add_key\x18\x03\x20\x01(\x0b2\x1c.Updates.Update.AddKeyUpdate\
-    H\0R\x06addKey\x1a\xad\x01\n\x11PersistPageUpdate\x12+\n\x12owner_dsnp_u\
-    ser_id\x18\x01\x20\x01(\x04R\x0fownerDsnpUserId\x12\x1b\n\tschema_id\x18\
-    \x02\x20\x01(\rR\x08schemaId\x12\x17\n\x07page_id\x18\x03\x20\x01(\rR\
-    \x06pageId\x12\x1b\n\tprev_hash\x18\x04\x20\x01(\rR\x08prevHash\x12\x18\
-    \n\x07payload\x18\x05\x20\x01(\x0cR\x07payload\x1a\x92\x01\n\x10DeletePa\
-    geUpdate\x12+\n\x12owner_dsnp_user_id\x18\x01\x20\x01(\x04R\x0fownerDsnp\
-    UserId\x12\x1b\n\tschema_id\x18\x02\x20\x01(\rR\x08schemaId\x12\x17\n\
+    \n!bridge/common/protos/output.proto\x1a\x20bridge/common/protos/input.p\
+    roto\"y\n\x0cSchemaConfig\x12/\n\x0cdsnp_version\x18\x01\x20\x01(\x0e2\
+    \x0c.DsnpVersionR\x0bdsnpVersion\x128\n\x0fconnection_type\x18\x02\x20\
+    \x01(\x0e2\x0f.ConnectionTypeR\x0econnectionType\"\x86\x04\n\x06Config\
+    \x12@\n\x1dsdk_max_stale_friendship_days\x18\x01\x20\x01(\rR\x19sdkMaxSt\
+    aleFriendshipDays\x128\n\x19max_graph_page_size_bytes\x18\x02\x20\x01(\r\
+    R\x15maxGraphPageSizeBytes\x12\x1e\n\x0bmax_page_id\x18\x03\x20\x01(\rR\
+    \tmaxPageId\x124\n\x17max_key_page_size_bytes\x18\x04\x20\x01(\rR\x13max\
+    KeyPageSizeBytes\x125\n\nschema_map\x18\x05\x20\x03(\x0b2\x16.Config.Sch\
+    emaMapEntryR\tschemaMap\x121\n\rdsnp_versions\x18\x06\x20\x03(\x0e2\x0c.\
+    DsnpVersionR\x0cdsnpVersions\x12:\n\x1agraph_public_key_schema_id\x18\
+    \x07\x20\x01(\rR\x16graphPublicKeySchemaId\x127\n\x18max_update_payload_\
+    bytes\x18\x08\x20\x01(\rR\x15maxUpdatePayloadBytes\x1aK\n\x0eSchemaMapEn\
+    try\x12\x10\n\x03key\x18\x01\x20\x01(\rR\x03key\x12#\n\x05value\x18\x02\
+    \x20\x01(\x0b2\r.SchemaConfigR\x05value:\x028\x01\"{\n\x0bEnvironment\
+    \x12;\n\x10environment_type\x18\x01\x20\x01(\x0e2\x10.EnvironmentTypeR\
+    \x0fenvironmentType\x12$\n\x06config\x18\x02\x20\x01(\x0b2\x07.ConfigH\0\
+    R\x06config\x88\x01\x01B\t\n\x07_config\"\xf1\x06\n\x07Updates\x12'\n\
+    \x06update\x18\x01\x20\x03(\x0b2\x0f.Updates.UpdateR\x06update\x1a\xbc\
+    \x06\n\x06Update\x12=\n\x07persist\x18\x01\x20\x01(\x0b2!.Updates.Update\
+    .PersistPageUpdateH\0R\x07persist\x12:\n\x06delete\x18\x02\x20\x01(\x0b2\
+    \x20.Updates.Update.DeletePageUpdateH\0R\x06delete\x127\n\x07add_key\x18\
+    \x03\x20\x01(\x0b2\x1c.Updates.Update.AddKeyUpdateH\0R\x06addKey\x1a\xec\
+    \x01\n\x11PersistPageUpdate\x12+\n\x12owner_dsnp_user_id\x18\x01\x20\x01\
+    (\x04R\x0fownerDsnpUserId\x12\x1b\n\tschema_id\x18\x02\x20\x01(\rR\x08sc\
+    hemaId\x12\x17\n\x07page_id\x18\x03\x20\x01(\rR\x06pageId\x12\x1b\n\tpre\
+    v_hash\x18\x04\x20\x01(\rR\x08prevHash\x12\x18\n\x07payload\x18\x05\x20\
+    \x01(\x0cR\x07payload\x12.\n\nchain_head\x18\x06\x20\x01(\x0b2\n.ChainHe\
+    adH\0R\tchainHead\x88\x01\x01B\r\n\x0b_chain_head\x1a\xd1\x01\n\x10Delet\
+    ePageUpdate\x12+\n\x12owner_dsnp_user_id\x18\x01\x20\x01(\x04R\x0fownerD\
+    snpUserId\x12\x1b\n\tschema_id\x18\x02\x20\x01(\rR\x08schemaId\x12\x17\n\
     \x07page_id\x18\x03\x20\x01(\rR\x06pageId\x12\x1b\n\tprev_hash\x18\x04\
-    \x20\x01(\rR\x08prevHash\x1ar\n\x0cAddKeyUpdate\x12+\n\x12owner_dsnp_use\
-    r_id\x18\x01\x20\x01(\x04R\x0fownerDsnpUserId\x12\x1b\n\tprev_hash\x18\
-    \x02\x20\x01(\rR\x08prevHash\x12\x18\n\x07payload\x18\x03\x20\x01(\x0cR\
-    \x07payloadB\x07\n\x05inner\"\x83\x01\n\x0eDsnpGraphEdges\x121\n\x04edge\
-    \x18\x01\x20\x03(\x0b2\x1d.DsnpGraphEdges.DsnpGraphEdgeR\x04edge\x1a>\n\
-    \rDsnpGraphEdge\x12\x17\n\x07user_id\x18\x01\x20\x01(\x04R\x06userId\x12\
-    \x14\n\x05since\x18\x02\x20\x01(\x04R\x05since\"\x1f\n\tDsnpUsers\x12\
-    \x12\n\x04user\x18\x01\x20\x03(\x04R\x04user\"\x88\x01\n\x0eDsnpPublicKe\
-    ys\x12<\n\npublic_key\x18\x01\x20\x03(\x0b2\x1d.DsnpPublicKeys.DsnpPubli\
-    cKeyR\tpublicKey\x1a8\n\rDsnpPublicKey\x12\x10\n\x03key\x18\x01\x20\x01(\
-    \x0cR\x03key\x12\x15\n\x06key_id\x18\x02\x20\x01(\x04R\x05keyId*&\n\x0bP\
-    rivacyType\x12\n\n\x06Public\x10\0\x12\x0b\n\x07Private\x10\x01*b\n\x0eC\
-    onnectionType\x12\x10\n\x0cFollowPublic\x10\0\x12\x11\n\rFollowPrivate\
-    \x10\x01\x12\x14\n\x10FriendshipPublic\x10\x02\x12\x15\n\x11FriendshipPr\
-    ivate\x10\x03*\x1d\n\x0bDsnpVersion\x12\x0e\n\nVersion1_0\x10\0*E\n\x0fE\
-    nvironmentType\x12\x0b\n\x07MainNet\x10\0\x12\x10\n\x0cTestnetPaseo\x10\
-    \x01\x12\n\n\x06Rococo\x10\x02\x12\x07\n\x03Dev\x10\x03B%\n!io.projectli\
-    berty.graphsdk.modelsP\x01J\xf5\x18\n\x06\x12\x04\0\0c\x01\n\x08\n\x01\
-    \x0c\x12\x03\0\0\x12\n\x08\n\x01\x08\x12\x03\x02\0:\n\t\n\x02\x08\x01\
-    \x12\x03\x02\0:\n\x08\n\x01\x08\x12\x03\x03\0\"\n\t\n\x02\x08\n\x12\x03\
-    \x03\0\"\n\n\n\x02\x05\0\x12\x04\x05\0\x08\x01\n\n\n\x03\x05\0\x01\x12\
-    \x03\x05\x05\x10\n\x0b\n\x04\x05\0\x02\0\x12\x03\x06\x04\x0f\n\x0c\n\x05\
-    \x05\0\x02\0\x01\x12\x03\x06\x04\n\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\
-    \x06\r\x0e\n\x0b\n\x04\x05\0\x02\x01\x12\x03\x07\x04\x10\n\x0c\n\x05\x05\
-    \0\x02\x01\x01\x12\x03\x07\x04\x0b\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03\
-    \x07\x0e\x0f\n\n\n\x02\x05\x01\x12\x04\n\0\x0f\x01\n\n\n\x03\x05\x01\x01\
-    \x12\x03\n\x05\x13\n\x0b\n\x04\x05\x01\x02\0\x12\x03\x0b\x02\x13\n\x0c\n\
-    \x05\x05\x01\x02\0\x01\x12\x03\x0b\x02\x0e\n\x0c\n\x05\x05\x01\x02\0\x02\
-    \x12\x03\x0b\x11\x12\n\x0b\n\x04\x05\x01\x02\x01\x12\x03\x0c\x02\x14\n\
-    \x0c\n\x05\x05\x01\x02\x01\x01\x12\x03\x0c\x02\x0f\n\x0c\n\x05\x05\x01\
-    \x02\x01\x02\x12\x03\x0c\x12\x13\n\x0b\n\x04\x05\x01\x02\x02\x12\x03\r\
-    \x02\x17\n\x0c\n\x05\x05\x01\x02\x02\x01\x12\x03\r\x02\x12\n\x0c\n\x05\
-    \x05\x01\x02\x02\x02\x12\x03\r\x15\x16\n\x0b\n\x04\x05\x01\x02\x03\x12\
-    \x03\x0e\x02\x18\n\x0c\n\x05\x05\x01\x02\x03\x01\x12\x03\x0e\x02\x13\n\
-    \x0c\n\x05\x05\x01\x02\x03\x02\x12\x03\x0e\x16\x17\n\n\n\x02\x05\x02\x12\
-    \x04\x11\0\x13\x01\n\n\n\x03\x05\x02\x01\x12\x03\x11\x05\x10\n\x0b\n\x04\
-    \x05\x02\x02\0\x12\x03\x12\x04\x14\n\x0c\n\x05\x05\x02\x02\0\x01\x12\x03\
-    \x12\x04\x0e\n\x0c\n\x05\x05\x02\x02\0\x02\x12\x03\x12\x11\x12\n\n\n\x02\
-    \x04\0\x12\x04\x15\0\x18\x01\n\n\n\x03\x04\0\x01\x12\x03\x15\x08\x14\n\
-    \x0b\n\x04\x04\0\x02\0\x12\x03\x16\x02\x1f\n\x0c\n\x05\x04\0\x02\0\x06\
-    \x12\x03\x16\x02\r\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x16\x0e\x1a\n\x0c\
-    \n\x05\x04\0\x02\0\x03\x12\x03\x16\x1d\x1e\n\x0b\n\x04\x04\0\x02\x01\x12\
-    \x03\x17\x02%\n\x0c\n\x05\x04\0\x02\x01\x06\x12\x03\x17\x02\x10\n\x0c\n\
-    \x05\x04\0\x02\x01\x01\x12\x03\x17\x11\x20\n\x0c\n\x05\x04\0\x02\x01\x03\
-    \x12\x03\x17#$\n\n\n\x02\x04\x01\x12\x04\x1a\0\"\x01\n\n\n\x03\x04\x01\
-    \x01\x12\x03\x1a\x08\x0e\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x1b\x02+\n\
-    \x0c\n\x05\x04\x01\x02\0\x05\x12\x03\x1b\x02\x08\n\x0c\n\x05\x04\x01\x02\
-    \0\x01\x12\x03\x1b\t&\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x1b)*\n\x0b\
-    \n\x04\x04\x01\x02\x01\x12\x03\x1c\x02'\n\x0c\n\x05\x04\x01\x02\x01\x05\
-    \x12\x03\x1c\x02\x08\n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\x1c\t\"\n\
-    \x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\x1c%&\n\x0b\n\x04\x04\x01\x02\x02\
-    \x12\x03\x1d\x02\x19\n\x0c\n\x05\x04\x01\x02\x02\x05\x12\x03\x1d\x02\x08\
-    \n\x0c\n\x05\x04\x01\x02\x02\x01\x12\x03\x1d\t\x14\n\x0c\n\x05\x04\x01\
-    \x02\x02\x03\x12\x03\x1d\x17\x18\n\x0b\n\x04\x04\x01\x02\x03\x12\x03\x1e\
-    \x02%\n\x0c\n\x05\x04\x01\x02\x03\x05\x12\x03\x1e\x02\x08\n\x0c\n\x05\
-    \x04\x01\x02\x03\x01\x12\x03\x1e\t\x20\n\x0c\n\x05\x04\x01\x02\x03\x03\
-    \x12\x03\x1e#$\n\x0b\n\x04\x04\x01\x02\x04\x12\x03\x1f\x02+\n\x0c\n\x05\
-    \x04\x01\x02\x04\x06\x12\x03\x1f\x02\x1b\n\x0c\n\x05\x04\x01\x02\x04\x01\
-    \x12\x03\x1f\x1c&\n\x0c\n\x05\x04\x01\x02\x04\x03\x12\x03\x1f)*\n\x0b\n\
-    \x04\x04\x01\x02\x05\x12\x03\x20\x02)\n\x0c\n\x05\x04\x01\x02\x05\x04\
-    \x12\x03\x20\x02\n\n\x0c\n\x05\x04\x01\x02\x05\x06\x12\x03\x20\x0b\x16\n\
-    \x0c\n\x05\x04\x01\x02\x05\x01\x12\x03\x20\x17$\n\x0c\n\x05\x04\x01\x02\
-    \x05\x03\x12\x03\x20'(\n\x0b\n\x04\x04\x01\x02\x06\x12\x03!\x02(\n\x0c\n\
-    \x05\x04\x01\x02\x06\x05\x12\x03!\x02\x08\n\x0c\n\x05\x04\x01\x02\x06\
-    \x01\x12\x03!\t#\n\x0c\n\x05\x04\x01\x02\x06\x03\x12\x03!&'\n\n\n\x02\
-    \x05\x03\x12\x04$\0)\x01\n\n\n\x03\x05\x03\x01\x12\x03$\x05\x14\n\x0b\n\
-    \x04\x05\x03\x02\0\x12\x03%\x02\x0e\n\x0c\n\x05\x05\x03\x02\0\x01\x12\
-    \x03%\x02\t\n\x0c\n\x05\x05\x03\x02\0\x02\x12\x03%\x0c\r\n\x0b\n\x04\x05\
-    \x03\x02\x01\x12\x03&\x02\x13\n\x0c\n\x05\x05\x03\x02\x01\x01\x12\x03&\
-    \x02\x0e\n\x0c\n\x05\x05\x03\x02\x01\x02\x12\x03&\x11\x12\n\x0b\n\x04\
-    \x05\x03\x02\x02\x12\x03'\x02\r\n\x0c\n\x05\x05\x03\x02\x02\x01\x12\x03'\
-    \x02\x08\n\x0c\n\x05\x05\x03\x02\x02\x02\x12\x03'\x0b\x0c\n\x0b\n\x04\
-    \x05\x03\x02\x03\x12\x03(\x02\n\n\x0c\n\x05\x05\x03\x02\x03\x01\x12\x03(\
-    \x02\x05\n\x0c\n\x05\x05\x03\x02\x03\x02\x12\x03(\x08\t\n\n\n\x02\x04\
-    \x02\x12\x04+\0.\x01\n\n\n\x03\x04\x02\x01\x12\x03+\x08\x13\n\x0b\n\x04\
-    \x04\x02\x02\0\x12\x03,\x02'\n\x0c\n\x05\x04\x02\x02\0\x06\x12\x03,\x02\
-    \x11\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03,\x12\"\n\x0c\n\x05\x04\x02\
-    \x02\0\x03\x12\x03,%&\n\x0b\n\x04\x04\x02\x02\x01\x12\x03-\x02\x1d\n\x0c\
-    \n\x05\x04\x02\x02\x01\x04\x12\x03-\x02\n\n\x0c\n\x05\x04\x02\x02\x01\
-    \x06\x12\x03-\x0b\x11\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03-\x12\x18\n\
-    \x0c\n\x05\x04\x02\x02\x01\x03\x12\x03-\x1b\x1c\n\n\n\x02\x04\x03\x12\
-    \x040\0O\x01\n\n\n\x03\x04\x03\x01\x12\x030\x08\x0f\n\x0c\n\x04\x04\x03\
-    \x03\0\x12\x041\x02L\x03\n\x0c\n\x05\x04\x03\x03\0\x01\x12\x031\n\x10\n\
-    \x0e\n\x06\x04\x03\x03\0\x03\0\x12\x042\x048\x05\n\x0e\n\x07\x04\x03\x03\
-    \0\x03\0\x01\x12\x032\x0c\x1d\n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\0\x12\
-    \x033\x06$\n\x10\n\t\x04\x03\x03\0\x03\0\x02\0\x05\x12\x033\x06\x0c\n\
-    \x10\n\t\x04\x03\x03\0\x03\0\x02\0\x01\x12\x033\r\x1f\n\x10\n\t\x04\x03\
-    \x03\0\x03\0\x02\0\x03\x12\x033\"#\n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\
-    \x01\x12\x034\x06\x1b\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x01\x05\x12\x034\
-    \x06\x0c\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x01\x01\x12\x034\r\x16\n\x10\
-    \n\t\x04\x03\x03\0\x03\0\x02\x01\x03\x12\x034\x19\x1a\n\x0f\n\x08\x04\
-    \x03\x03\0\x03\0\x02\x02\x12\x035\x06\x19\n\x10\n\t\x04\x03\x03\0\x03\0\
-    \x02\x02\x05\x12\x035\x06\x0c\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x02\x01\
-    \x12\x035\r\x14\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x02\x03\x12\x035\x17\
-    \x18\n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\x03\x12\x036\x06\x1b\n\x10\n\t\
-    \x04\x03\x03\0\x03\0\x02\x03\x05\x12\x036\x06\x0c\n\x10\n\t\x04\x03\x03\
-    \0\x03\0\x02\x03\x01\x12\x036\r\x16\n\x10\n\t\x04\x03\x03\0\x03\0\x02\
-    \x03\x03\x12\x036\x19\x1a\n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\x04\x12\
-    \x037\x06\x18\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x04\x05\x12\x037\x06\x0b\
-    \n\x10\n\t\x04\x03\x03\0\x03\0\x02\x04\x01\x12\x037\x0c\x13\n\x10\n\t\
-    \x04\x03\x03\0\x03\0\x02\x04\x03\x12\x037\x16\x17\n\x0e\n\x06\x04\x03\
-    \x03\0\x03\x01\x12\x04:\x04?\x05\n\x0e\n\x07\x04\x03\x03\0\x03\x01\x01\
-    \x12\x03:\x0c\x1c\n\x0f\n\x08\x04\x03\x03\0\x03\x01\x02\0\x12\x03;\x06$\
-    \n\x10\n\t\x04\x03\x03\0\x03\x01\x02\0\x05\x12\x03;\x06\x0c\n\x10\n\t\
-    \x04\x03\x03\0\x03\x01\x02\0\x01\x12\x03;\r\x1f\n\x10\n\t\x04\x03\x03\0\
-    \x03\x01\x02\0\x03\x12\x03;\"#\n\x0f\n\x08\x04\x03\x03\0\x03\x01\x02\x01\
-    \x12\x03<\x06\x1b\n\x10\n\t\x04\x03\x03\0\x03\x01\x02\x01\x05\x12\x03<\
-    \x06\x0c\n\x10\n\t\x04\x03\x03\0\x03\x01\x02\x01\x01\x12\x03<\r\x16\n\
-    \x10\n\t\x04\x03\x03\0\x03\x01\x02\x01\x03\x12\x03<\x19\x1a\n\x0f\n\x08\
-    \x04\x03\x03\0\x03\x01\x02\x02\x12\x03=\x06\x19\n\x10\n\t\x04\x03\x03\0\
-    \x03\x01\x02\x02\x05\x12\x03=\x06\x0c\n\x10\n\t\x04\x03\x03\0\x03\x01\
-    \x02\x02\x01\x12\x03=\r\x14\n\x10\n\t\x04\x03\x03\0\x03\x01\x02\x02\x03\
-    \x12\x03=\x17\x18\n\x0f\n\x08\x04\x03\x03\0\x03\x01\x02\x03\x12\x03>\x06\
-    \x1b\n\x10\n\t\x04\x03\x03\0\x03\x01\x02\x03\x05\x12\x03>\x06\x0c\n\x10\
-    \n\t\x04\x03\x03\0\x03\x01\x02\x03\x01\x12\x03>\r\x16\n\x10\n\t\x04\x03\
-    \x03\0\x03\x01\x02\x03\x03\x12\x03>\x19\x1a\n\x0e\n\x06\x04\x03\x03\0\
-    \x03\x02\x12\x04A\x04E\x05\n\x0e\n\x07\x04\x03\x03\0\x03\x02\x01\x12\x03\
-    A\x0c\x18\n\x0f\n\x08\x04\x03\x03\0\x03\x02\x02\0\x12\x03B\x06$\n\x10\n\
-    \t\x04\x03\x03\0\x03\x02\x02\0\x05\x12\x03B\x06\x0c\n\x10\n\t\x04\x03\
-    \x03\0\x03\x02\x02\0\x01\x12\x03B\r\x1f\n\x10\n\t\x04\x03\x03\0\x03\x02\
-    \x02\0\x03\x12\x03B\"#\n\x0f\n\x08\x04\x03\x03\0\x03\x02\x02\x01\x12\x03\
-    C\x06\x1b\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\x01\x05\x12\x03C\x06\x0c\n\
-    \x10\n\t\x04\x03\x03\0\x03\x02\x02\x01\x01\x12\x03C\r\x16\n\x10\n\t\x04\
-    \x03\x03\0\x03\x02\x02\x01\x03\x12\x03C\x19\x1a\n\x0f\n\x08\x04\x03\x03\
-    \0\x03\x02\x02\x02\x12\x03D\x06\x18\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\
-    \x02\x05\x12\x03D\x06\x0b\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\x02\x01\
-    \x12\x03D\x0c\x13\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\x02\x03\x12\x03D\
-    \x16\x17\n\x0e\n\x06\x04\x03\x03\0\x08\0\x12\x04G\x04K\x05\n\x0e\n\x07\
-    \x04\x03\x03\0\x08\0\x01\x12\x03G\n\x0f\n\r\n\x06\x04\x03\x03\0\x02\0\
-    \x12\x03H\x06$\n\x0e\n\x07\x04\x03\x03\0\x02\0\x06\x12\x03H\x06\x17\n\
-    \x0e\n\x07\x04\x03\x03\0\x02\0\x01\x12\x03H\x18\x1f\n\x0e\n\x07\x04\x03\
-    \x03\0\x02\0\x03\x12\x03H\"#\n\r\n\x06\x04\x03\x03\0\x02\x01\x12\x03I\
-    \x06\"\n\x0e\n\x07\x04\x03\x03\0\x02\x01\x06\x12\x03I\x06\x16\n\x0e\n\
-    \x07\x04\x03\x03\0\x02\x01\x01\x12\x03I\x17\x1d\n\x0e\n\x07\x04\x03\x03\
-    \0\x02\x01\x03\x12\x03I\x20!\n\r\n\x06\x04\x03\x03\0\x02\x02\x12\x03J\
-    \x06\x1f\n\x0e\n\x07\x04\x03\x03\0\x02\x02\x06\x12\x03J\x06\x12\n\x0e\n\
-    \x07\x04\x03\x03\0\x02\x02\x01\x12\x03J\x13\x1a\n\x0e\n\x07\x04\x03\x03\
-    \0\x02\x02\x03\x12\x03J\x1d\x1e\n\x0b\n\x04\x04\x03\x02\0\x12\x03N\x02\
-    \x1d\n\x0c\n\x05\x04\x03\x02\0\x04\x12\x03N\x02\n\n\x0c\n\x05\x04\x03\
-    \x02\0\x06\x12\x03N\x0b\x11\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03N\x12\
-    \x18\n\x0c\n\x05\x04\x03\x02\0\x03\x12\x03N\x1b\x1c\n\n\n\x02\x04\x04\
-    \x12\x04Q\0W\x01\n\n\n\x03\x04\x04\x01\x12\x03Q\x08\x16\n\x0c\n\x04\x04\
-    \x04\x03\0\x12\x04R\x02U\x03\n\x0c\n\x05\x04\x04\x03\0\x01\x12\x03R\n\
-    \x17\n\r\n\x06\x04\x04\x03\0\x02\0\x12\x03S\x04\x17\n\x0e\n\x07\x04\x04\
-    \x03\0\x02\0\x05\x12\x03S\x04\n\n\x0e\n\x07\x04\x04\x03\0\x02\0\x01\x12\
-    \x03S\x0b\x12\n\x0e\n\x07\x04\x04\x03\0\x02\0\x03\x12\x03S\x15\x16\n\r\n\
-    \x06\x04\x04\x03\0\x02\x01\x12\x03T\x04\x15\n\x0e\n\x07\x04\x04\x03\0\
-    \x02\x01\x05\x12\x03T\x04\n\n\x0e\n\x07\x04\x04\x03\0\x02\x01\x01\x12\
-    \x03T\x0b\x10\n\x0e\n\x07\x04\x04\x03\0\x02\x01\x03\x12\x03T\x13\x14\n\
-    \x0b\n\x04\x04\x04\x02\0\x12\x03V\x02\"\n\x0c\n\x05\x04\x04\x02\0\x04\
-    \x12\x03V\x02\n\n\x0c\n\x05\x04\x04\x02\0\x06\x12\x03V\x0b\x18\n\x0c\n\
-    \x05\x04\x04\x02\0\x01\x12\x03V\x19\x1d\n\x0c\n\x05\x04\x04\x02\0\x03\
-    \x12\x03V\x20!\n\n\n\x02\x04\x05\x12\x04Y\0[\x01\n\n\n\x03\x04\x05\x01\
-    \x12\x03Y\x08\x11\n\x0b\n\x04\x04\x05\x02\0\x12\x03Z\x02\x1b\n\x0c\n\x05\
-    \x04\x05\x02\0\x04\x12\x03Z\x02\n\n\x0c\n\x05\x04\x05\x02\0\x05\x12\x03Z\
-    \x0b\x11\n\x0c\n\x05\x04\x05\x02\0\x01\x12\x03Z\x12\x16\n\x0c\n\x05\x04\
-    \x05\x02\0\x03\x12\x03Z\x19\x1a\n\n\n\x02\x04\x06\x12\x04]\0c\x01\n\n\n\
-    \x03\x04\x06\x01\x12\x03]\x08\x16\n\x0c\n\x04\x04\x06\x03\0\x12\x04^\x02\
-    a\x03\n\x0c\n\x05\x04\x06\x03\0\x01\x12\x03^\n\x17\n\r\n\x06\x04\x06\x03\
-    \0\x02\0\x12\x03_\x04\x12\n\x0e\n\x07\x04\x06\x03\0\x02\0\x05\x12\x03_\
-    \x04\t\n\x0e\n\x07\x04\x06\x03\0\x02\0\x01\x12\x03_\n\r\n\x0e\n\x07\x04\
-    \x06\x03\0\x02\0\x03\x12\x03_\x10\x11\n\r\n\x06\x04\x06\x03\0\x02\x01\
-    \x12\x03`\x04\x16\n\x0e\n\x07\x04\x06\x03\0\x02\x01\x05\x12\x03`\x04\n\n\
-    \x0e\n\x07\x04\x06\x03\0\x02\x01\x01\x12\x03`\x0b\x11\n\x0e\n\x07\x04\
-    \x06\x03\0\x02\x01\x03\x12\x03`\x14\x15\n\x0b\n\x04\x04\x06\x02\0\x12\
-    \x03b\x02(\n\x0c\n\x05\x04\x06\x02\0\x04\x12\x03b\x02\n\n\x0c\n\x05\x04\
-    \x06\x02\0\x06\x12\x03b\x0b\x18\n\x0c\n\x05\x04\x06\x02\0\x01\x12\x03b\
-    \x19#\n\x0c\n\x05\x04\x06\x02\0\x03\x12\x03b&'b\x06proto3\
+    \x20\x01(\rR\x08prevHash\x12.\n\nchain_head\x18\x05\x20\x01(\x0b2\n.Chai\
+    nHeadH\0R\tchainHead\x88\x01\x01B\r\n\x0b_chain_head\x1a\xb1\x01\n\x0cAd\
+    dKeyUpdate\x12+\n\x12owner_dsnp_user_id\x18\x01\x20\x01(\x04R\x0fownerDs\
+    npUserId\x12\x1b\n\tprev_hash\x18\x02\x20\x01(\rR\x08prevHash\x12\x18\n\
+    \x07payload\x18\x03\x20\x01(\x0cR\x07payload\x12.\n\nchain_head\x18\x04\
+    \x20\x01(\x0b2\n.ChainHeadH\0R\tchainHead\x88\x01\x01B\r\n\x0b_chain_hea\
+    dB\x07\n\x05inner\"\x83\x01\n\x0eDsnpGraphEdges\x121\n\x04edge\x18\x01\
+    \x20\x03(\x0b2\x1d.DsnpGraphEdges.DsnpGraphEdgeR\x04edge\x1a>\n\rDsnpGra\
+    phEdge\x12\x17\n\x07user_id\x18\x01\x20\x01(\x04R\x06userId\x12\x14\n\
+    \x05since\x18\x02\x20\x01(\x04R\x05since\"\x1f\n\tDsnpUsers\x12\x12\n\
+    \x04user\x18\x01\x20\x03(\x04R\x04user\"\x88\x01\n\x0eDsnpPublicKeys\x12\
+    <\n\npublic_key\x18\x01\x20\x03(\x0b2\x1d.DsnpPublicKeys.DsnpPublicKeyR\
+    \tpublicKey\x1a8\n\rDsnpPublicKey\x12\x10\n\x03key\x18\x01\x20\x01(\x0cR\
+    \x03key\x12\x15\n\x06key_id\x18\x02\x20\x01(\x04R\x05keyId*&\n\x0bPrivac\
+    yType\x12\n\n\x06Public\x10\0\x12\x0b\n\x07Private\x10\x01*b\n\x0eConnec\
+    tionType\x12\x10\n\x0cFollowPublic\x10\0\x12\x11\n\rFollowPrivate\x10\
+    \x01\x12\x14\n\x10FriendshipPublic\x10\x02\x12\x15\n\x11FriendshipPrivat\
+    e\x10\x03*\x1d\n\x0bDsnpVersion\x12\x0e\n\nVersion1_0\x10\0*E\n\x0fEnvir\
+    onmentType\x12\x0b\n\x07MainNet\x10\0\x12\x10\n\x0cTestnetPaseo\x10\x01\
+    \x12\n\n\x06Rococo\x10\x02\x12\x07\n\x03Dev\x10\x03B%\n!io.projectlibert\
+    y.graphsdk.modelsP\x01J\xc2\x1b\n\x06\x12\x04\0\0i\x01\n\x08\n\x01\x0c\
+    \x12\x03\0\0\x12\n\x08\n\x01\x08\x12\x03\x02\0:\n\t\n\x02\x08\x01\x12\
+    \x03\x02\0:\n\x08\n\x01\x08\x12\x03\x03\0\"\n\t\n\x02\x08\n\x12\x03\x03\
+    \0\"\n\t\n\x02\x03\0\x12\x03\x05\0*\n\n\n\x02\x05\0\x12\x04\x07\0\n\x01\
+    \n\n\n\x03\x05\0\x01\x12\x03\x07\x05\x10\n\x0b\n\x04\x05\0\x02\0\x12\x03\
+    \x08\x04\x0f\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03\x08\x04\n\n\x0c\n\x05\
+    \x05\0\x02\0\x02\x12\x03\x08\r\x0e\n\x0b\n\x04\x05\0\x02\x01\x12\x03\t\
+    \x04\x10\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03\t\x04\x0b\n\x0c\n\x05\x05\
+    \0\x02\x01\x02\x12\x03\t\x0e\x0f\n\n\n\x02\x05\x01\x12\x04\x0c\0\x11\x01\
+    \n\n\n\x03\x05\x01\x01\x12\x03\x0c\x05\x13\n\x0b\n\x04\x05\x01\x02\0\x12\
+    \x03\r\x02\x13\n\x0c\n\x05\x05\x01\x02\0\x01\x12\x03\r\x02\x0e\n\x0c\n\
+    \x05\x05\x01\x02\0\x02\x12\x03\r\x11\x12\n\x0b\n\x04\x05\x01\x02\x01\x12\
+    \x03\x0e\x02\x14\n\x0c\n\x05\x05\x01\x02\x01\x01\x12\x03\x0e\x02\x0f\n\
+    \x0c\n\x05\x05\x01\x02\x01\x02\x12\x03\x0e\x12\x13\n\x0b\n\x04\x05\x01\
+    \x02\x02\x12\x03\x0f\x02\x17\n\x0c\n\x05\x05\x01\x02\x02\x01\x12\x03\x0f\
+    \x02\x12\n\x0c\n\x05\x05\x01\x02\x02\x02\x12\x03\x0f\x15\x16\n\x0b\n\x04\
+    \x05\x01\x02\x03\x12\x03\x10\x02\x18\n\x0c\n\x05\x05\x01\x02\x03\x01\x12\
+    \x03\x10\x02\x13\n\x0c\n\x05\x05\x01\x02\x03\x02\x12\x03\x10\x16\x17\n\n\
+    \n\x02\x05\x02\x12\x04\x13\0\x15\x01\n\n\n\x03\x05\x02\x01\x12\x03\x13\
+    \x05\x10\n\x0b\n\x04\x05\x02\x02\0\x12\x03\x14\x04\x14\n\x0c\n\x05\x05\
+    \x02\x02\0\x01\x12\x03\x14\x04\x0e\n\x0c\n\x05\x05\x02\x02\0\x02\x12\x03\
+    \x14\x11\x12\n\n\n\x02\x04\0\x12\x04\x17\0\x1a\x01\n\n\n\x03\x04\0\x01\
+    \x12\x03\x17\x08\x14\n\x0b\n\x04\x04\0\x02\0\x12\x03\x18\x02\x1f\n\x0c\n\
+    \x05\x04\0\x02\0\x06\x12\x03\x18\x02\r\n\x0c\n\x05\x04\0\x02\0\x01\x12\
+    \x03\x18\x0e\x1a\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x18\x1d\x1e\n\x0b\n\
+    \x04\x04\0\x02\x01\x12\x03\x19\x02%\n\x0c\n\x05\x04\0\x02\x01\x06\x12\
+    \x03\x19\x02\x10\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x19\x11\x20\n\x0c\
+    \n\x05\x04\0\x02\x01\x03\x12\x03\x19#$\n\n\n\x02\x04\x01\x12\x04\x1c\0%\
+    \x01\n\n\n\x03\x04\x01\x01\x12\x03\x1c\x08\x0e\n\x0b\n\x04\x04\x01\x02\0\
+    \x12\x03\x1d\x02+\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\x1d\x02\x08\n\
+    \x0c\n\x05\x04\x01\x02\0\x01\x12\x03\x1d\t&\n\x0c\n\x05\x04\x01\x02\0\
+    \x03\x12\x03\x1d)*\n\x0b\n\x04\x04\x01\x02\x01\x12\x03\x1e\x02'\n\x0c\n\
+    \x05\x04\x01\x02\x01\x05\x12\x03\x1e\x02\x08\n\x0c\n\x05\x04\x01\x02\x01\
+    \x01\x12\x03\x1e\t\"\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\x1e%&\n\x0b\
+    \n\x04\x04\x01\x02\x02\x12\x03\x1f\x02\x19\n\x0c\n\x05\x04\x01\x02\x02\
+    \x05\x12\x03\x1f\x02\x08\n\x0c\n\x05\x04\x01\x02\x02\x01\x12\x03\x1f\t\
+    \x14\n\x0c\n\x05\x04\x01\x02\x02\x03\x12\x03\x1f\x17\x18\n\x0b\n\x04\x04\
+    \x01\x02\x03\x12\x03\x20\x02%\n\x0c\n\x05\x04\x01\x02\x03\x05\x12\x03\
+    \x20\x02\x08\n\x0c\n\x05\x04\x01\x02\x03\x01\x12\x03\x20\t\x20\n\x0c\n\
+    \x05\x04\x01\x02\x03\x03\x12\x03\x20#$\n\x0b\n\x04\x04\x01\x02\x04\x12\
+    \x03!\x02+\n\x0c\n\x05\x04\x01\x02\x04\x06\x12\x03!\x02\x1b\n\x0c\n\x05\
+    \x04\x01\x02\x04\x01\x12\x03!\x1c&\n\x0c\n\x05\x04\x01\x02\x04\x03\x12\
+    \x03!)*\n\x0b\n\x04\x04\x01\x02\x05\x12\x03\"\x02)\n\x0c\n\x05\x04\x01\
+    \x02\x05\x04\x12\x03\"\x02\n\n\x0c\n\x05\x04\x01\x02\x05\x06\x12\x03\"\
+    \x0b\x16\n\x0c\n\x05\x04\x01\x02\x05\x01\x12\x03\"\x17$\n\x0c\n\x05\x04\
+    \x01\x02\x05\x03\x12\x03\"'(\n\x0b\n\x04\x04\x01\x02\x06\x12\x03#\x02(\n\
+    \x0c\n\x05\x04\x01\x02\x06\x05\x12\x03#\x02\x08\n\x0c\n\x05\x04\x01\x02\
+    \x06\x01\x12\x03#\t#\n\x0c\n\x05\x04\x01\x02\x06\x03\x12\x03#&'\n\x0b\n\
+    \x04\x04\x01\x02\x07\x12\x03$\x02&\n\x0c\n\x05\x04\x01\x02\x07\x05\x12\
+    \x03$\x02\x08\n\x0c\n\x05\x04\x01\x02\x07\x01\x12\x03$\t!\n\x0c\n\x05\
+    \x04\x01\x02\x07\x03\x12\x03$$%\n\n\n\x02\x05\x03\x12\x04'\0,\x01\n\n\n\
+    \x03\x05\x03\x01\x12\x03'\x05\x14\n\x0b\n\x04\x05\x03\x02\0\x12\x03(\x02\
+    \x0e\n\x0c\n\x05\x05\x03\x02\0\x01\x12\x03(\x02\t\n\x0c\n\x05\x05\x03\
+    \x02\0\x02\x12\x03(\x0c\r\n\x0b\n\x04\x05\x03\x02\x01\x12\x03)\x02\x13\n\
+    \x0c\n\x05\x05\x03\x02\x01\x01\x12\x03)\x02\x0e\n\x0c\n\x05\x05\x03\x02\
+    \x01\x02\x12\x03)\x11\x12\n\x0b\n\x04\x05\x03\x02\x02\x12\x03*\x02\r\n\
+    \x0c\n\x05\x05\x03\x02\x02\x01\x12\x03*\x02\x08\n\x0c\n\x05\x05\x03\x02\
+    \x02\x02\x12\x03*\x0b\x0c\n\x0b\n\x04\x05\x03\x02\x03\x12\x03+\x02\n\n\
+    \x0c\n\x05\x05\x03\x02\x03\x01\x12\x03+\x02\x05\n\x0c\n\x05\x05\x03\x02\
+    \x03\x02\x12\x03+\x08\t\n\n\n\x02\x04\x02\x12\x04.\01\x01\n\n\n\x03\x04\
+    \x02\x01\x12\x03.\x08\x13\n\x0b\n\x04\x04\x02\x02\0\x12\x03/\x02'\n\x0c\
+    \n\x05\x04\x02\x02\0\x06\x12\x03/\x02\x11\n\x0c\n\x05\x04\x02\x02\0\x01\
+    \x12\x03/\x12\"\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03/%&\n\x0b\n\x04\x04\
+    \x02\x02\x01\x12\x030\x02\x1d\n\x0c\n\x05\x04\x02\x02\x01\x04\x12\x030\
+    \x02\n\n\x0c\n\x05\x04\x02\x02\x01\x06\x12\x030\x0b\x11\n\x0c\n\x05\x04\
+    \x02\x02\x01\x01\x12\x030\x12\x18\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\
+    \x030\x1b\x1c\n\n\n\x02\x04\x03\x12\x043\0U\x01\n\n\n\x03\x04\x03\x01\
+    \x12\x033\x08\x0f\n\x0c\n\x04\x04\x03\x03\0\x12\x044\x02R\x03\n\x0c\n\
+    \x05\x04\x03\x03\0\x01\x12\x034\n\x10\n\x0e\n\x06\x04\x03\x03\0\x03\0\
+    \x12\x045\x04<\x05\n\x0e\n\x07\x04\x03\x03\0\x03\0\x01\x12\x035\x0c\x1d\
+    \n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\0\x12\x036\x06$\n\x10\n\t\x04\x03\
+    \x03\0\x03\0\x02\0\x05\x12\x036\x06\x0c\n\x10\n\t\x04\x03\x03\0\x03\0\
+    \x02\0\x01\x12\x036\r\x1f\n\x10\n\t\x04\x03\x03\0\x03\0\x02\0\x03\x12\
+    \x036\"#\n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\x01\x12\x037\x06\x1b\n\x10\
+    \n\t\x04\x03\x03\0\x03\0\x02\x01\x05\x12\x037\x06\x0c\n\x10\n\t\x04\x03\
+    \x03\0\x03\0\x02\x01\x01\x12\x037\r\x16\n\x10\n\t\x04\x03\x03\0\x03\0\
+    \x02\x01\x03\x12\x037\x19\x1a\n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\x02\
+    \x12\x038\x06\x19\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x02\x05\x12\x038\x06\
+    \x0c\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x02\x01\x12\x038\r\x14\n\x10\n\t\
+    \x04\x03\x03\0\x03\0\x02\x02\x03\x12\x038\x17\x18\n\x0f\n\x08\x04\x03\
+    \x03\0\x03\0\x02\x03\x12\x039\x06\x1b\n\x10\n\t\x04\x03\x03\0\x03\0\x02\
+    \x03\x05\x12\x039\x06\x0c\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x03\x01\x12\
+    \x039\r\x16\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x03\x03\x12\x039\x19\x1a\n\
+    \x0f\n\x08\x04\x03\x03\0\x03\0\x02\x04\x12\x03:\x06\x18\n\x10\n\t\x04\
+    \x03\x03\0\x03\0\x02\x04\x05\x12\x03:\x06\x0b\n\x10\n\t\x04\x03\x03\0\
+    \x03\0\x02\x04\x01\x12\x03:\x0c\x13\n\x10\n\t\x04\x03\x03\0\x03\0\x02\
+    \x04\x03\x12\x03:\x16\x17\n\x0f\n\x08\x04\x03\x03\0\x03\0\x02\x05\x12\
+    \x03;\x06(\n\x10\n\t\x04\x03\x03\0\x03\0\x02\x05\x04\x12\x03;\x06\x0e\n\
+    \x10\n\t\x04\x03\x03\0\x03\0\x02\x05\x06\x12\x03;\x0f\x18\n\x10\n\t\x04\
+    \x03\x03\0\x03\0\x02\x05\x01\x12\x03;\x19#\n\x10\n\t\x04\x03\x03\0\x03\0\
+    \x02\x05\x03\x12\x03;&'\n\x0e\n\x06\x04\x03\x03\0\x03\x01\x12\x04>\x04D\
+    \x05\n\x0e\n\x07\x04\x03\x03\0\x03\x01\x01\x12\x03>\x0c\x1c\n\x0f\n\x08\
+    \x04\x03\x03\0\x03\x01\x02\0\x12\x03?\x06$\n\x10\n\t\x04\x03\x03\0\x03\
+    \x01\x02\0\x05\x12\x03?\x06\x0c\n\x10\n\t\x04\x03\x03\0\x03\x01\x02\0\
+    \x01\x12\x03?\r\x1f\n\x10\n\t\x04\x03\x03\0\x03\x01\x02\0\x03\x12\x03?\"\
+    #\n\x0f\n\x08\x04\x03\x03\0\x03\x01\x02\x01\x12\x03@\x06\x1b\n\x10\n\t\
+    \x04\x03\x03\0\x03\x01\x02\x01\x05\x12\x03@\x06\x0c\n\x10\n\t\x04\x03\
+    \x03\0\x03\x01\x02\x01\x01\x12\x03@\r\x16\n\x10\n\t\x04\x03\x03\0\x03\
+    \x01\x02\x01\x03\x12\x03@\x19\x1a\n\x0f\n\x08\x04\x03\x03\0\x03\x01\x02\
+    \x02\x12\x03A\x06\x19\n\x10\n\t\x04\x03\x03\0\x03\x01\x02\x02\x05\x12\
+    \x03A\x06\x0c\n\x10\n\t\x04\x03\x03\0\x03\x01\x02\x02\x01\x12\x03A\r\x14\
+    \n\x10\n\t\x04\x03\x03\0\x03\x01\x02\x02\x03\x12\x03A\x17\x18\n\x0f\n\
+    \x08\x04\x03\x03\0\x03\x01\x02\x03\x12\x03B\x06\x1b\n\x10\n\t\x04\x03\
+    \x03\0\x03\x01\x02\x03\x05\x12\x03B\x06\x0c\n\x10\n\t\x04\x03\x03\0\x03\
+    \x01\x02\x03\x01\x12\x03B\r\x16\n\x10\n\t\x04\x03\x03\0\x03\x01\x02\x03\
+    \x03\x12\x03B\x19\x1a\n\x0f\n\x08\x04\x03\x03\0\x03\x01\x02\x04\x12\x03C\
+    \x06(\n\x10\n\t\x04\x03\x03\0\x03\x01\x02\x04\x04\x12\x03C\x06\x0e\n\x10\
+    \n\t\x04\x03\x03\0\x03\x01\x02\x04\x06\x12\x03C\x0f\x18\n\x10\n\t\x04\
+    \x03\x03\0\x03\x01\x02\x04\x01\x12\x03C\x19#\n\x10\n\t\x04\x03\x03\0\x03\
+    \x01\x02\x04\x03\x12\x03C&'\n\x0e\n\x06\x04\x03\x03\0\x03\x02\x12\x04F\
+    \x04K\x05\n\x0e\n\x07\x04\x03\x03\0\x03\x02\x01\x12\x03F\x0c\x18\n\x0f\n\
+    \x08\x04\x03\x03\0\x03\x02\x02\0\x12\x03G\x06$\n\x10\n\t\x04\x03\x03\0\
+    \x03\x02\x02\0\x05\x12\x03G\x06\x0c\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\
+    \0\x01\x12\x03G\r\x1f\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\0\x03\x12\x03G\
+    \"#\n\x0f\n\x08\x04\x03\x03\0\x03\x02\x02\x01\x12\x03H\x06\x1b\n\x10\n\t\
+    \x04\x03\x03\0\x03\x02\x02\x01\x05\x12\x03H\x06\x0c\n\x10\n\t\x04\x03\
+    \x03\0\x03\x02\x02\x01\x01\x12\x03H\r\x16\n\x10\n\t\x04\x03\x03\0\x03\
+    \x02\x02\x01\x03\x12\x03H\x19\x1a\n\x0f\n\x08\x04\x03\x03\0\x03\x02\x02\
+    \x02\x12\x03I\x06\x18\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\x02\x05\x12\
+    \x03I\x06\x0b\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\x02\x01\x12\x03I\x0c\
+    \x13\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\x02\x03\x12\x03I\x16\x17\n\x0f\
+    \n\x08\x04\x03\x03\0\x03\x02\x02\x03\x12\x03J\x06(\n\x10\n\t\x04\x03\x03\
+    \0\x03\x02\x02\x03\x04\x12\x03J\x06\x0e\n\x10\n\t\x04\x03\x03\0\x03\x02\
+    \x02\x03\x06\x12\x03J\x0f\x18\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\x03\
+    \x01\x12\x03J\x19#\n\x10\n\t\x04\x03\x03\0\x03\x02\x02\x03\x03\x12\x03J&\
+    '\n\x0e\n\x06\x04\x03\x03\0\x08\0\x12\x04M\x04Q\x05\n\x0e\n\x07\x04\x03\
+    \x03\0\x08\0\x01\x12\x03M\n\x0f\n\r\n\x06\x04\x03\x03\0\x02\0\x12\x03N\
+    \x06$\n\x0e\n\x07\x04\x03\x03\0\x02\0\x06\x12\x03N\x06\x17\n\x0e\n\x07\
+    \x04\x03\x03\0\x02\0\x01\x12\x03N\x18\x1f\n\x0e\n\x07\x04\x03\x03\0\x02\
+    \0\x03\x12\x03N\"#\n\r\n\x06\x04\x03\x03\0\x02\x01\x12\x03O\x06\"\n\x0e\
+    \n\x07\x04\x03\x03\0\x02\x01\x06\x12\x03O\x06\x16\n\x0e\n\x07\x04\x03\
+    \x03\0\x02\x01\x01\x12\x03O\x17\x1d\n\x0e\n\x07\x04\x03\x03\0\x02\x01\
+    \x03\x12\x03O\x20!\n\r\n\x06\x04\x03\x03\0\x02\x02\x12\x03P\x06\x1f\n\
+    \x0e\n\x07\x04\x03\x03\0\x02\x02\x06\x12\x03P\x06\x12\n\x0e\n\x07\x04\
+    \x03\x03\0\x02\x02\x01\x12\x03P\x13\x1a\n\x0e\n\x07\x04\x03\x03\0\x02\
+    \x02\x03\x12\x03P\x1d\x1e\n\x0b\n\x04\x04\x03\x02\0\x12\x03T\x02\x1d\n\
+    \x0c\n\x05\x04\x03\x02\0\x04\x12\x03T\x02\n\n\x0c\n\x05\x04\x03\x02\0\
+    \x06\x12\x03T\x0b\x11\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03T\x12\x18\n\
+    \x0c\n\x05\x04\x03\x02\0\x03\x12\x03T\x1b\x1c\n\n\n\x02\x04\x04\x12\x04W\
+    \0]\x01\n\n\n\x03\x04\x04\x01\x12\x03W\x08\x16\n\x0c\n\x04\x04\x04\x03\0\
+    \x12\x04X\x02[\x03\n\x0c\n\x05\x04\x04\x03\0\x01\x12\x03X\n\x17\n\r\n\
+    \x06\x04\x04\x03\0\x02\0\x12\x03Y\x04\x17\n\x0e\n\x07\x04\x04\x03\0\x02\
+    \0\x05\x12\x03Y\x04\n\n\x0e\n\x07\x04\x04\x03\0\x02\0\x01\x12\x03Y\x0b\
+    \x12\n\x0e\n\x07\x04\x04\x03\0\x02\0\x03\x12\x03Y\x15\x16\n\r\n\x06\x04\
+    \x04\x03\0\x02\x01\x12\x03Z\x04\x15\n\x0e\n\x07\x04\x04\x03\0\x02\x01\
+    \x05\x12\x03Z\x04\n\n\x0e\n\x07\x04\x04\x03\0\x02\x01\x01\x12\x03Z\x0b\
+    \x10\n\x0e\n\x07\x04\x04\x03\0\x02\x01\x03\x12\x03Z\x13\x14\n\x0b\n\x04\
+    \x04\x04\x02\0\x12\x03\\\x02\"\n\x0c\n\x05\x04\x04\x02\0\x04\x12\x03\\\
+    \x02\n\n\x0c\n\x05\x04\x04\x02\0\x06\x12\x03\\\x0b\x18\n\x0c\n\x05\x04\
+    \x04\x02\0\x01\x12\x03\\\x19\x1d\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x03\\\
+    \x20!\n\n\n\x02\x04\x05\x12\x04_\0a\x01\n\n\n\x03\x04\x05\x01\x12\x03_\
+    \x08\x11\n\x0b\n\x04\x04\x05\x02\0\x12\x03`\x02\x1b\n\x0c\n\x05\x04\x05\
+    \x02\0\x04\x12\x03`\x02\n\n\x0c\n\x05\x04\x05\x02\0\x05\x12\x03`\x0b\x11\
+    \n\x0c\n\x05\x04\x05\x02\0\x01\x12\x03`\x12\x16\n\x0c\n\x05\x04\x05\x02\
+    \0\x03\x12\x03`\x19\x1a\n\n\n\x02\x04\x06\x12\x04c\0i\x01\n\n\n\x03\x04\
+    \x06\x01\x12\x03c\x08\x16\n\x0c\n\x04\x04\x06\x03\0\x12\x04d\x02g\x03\n\
+    \x0c\n\x05\x04\x06\x03\0\x01\x12\x03d\n\x17\n\r\n\x06\x04\x06\x03\0\x02\
+    \0\x12\x03e\x04\x12\n\x0e\n\x07\x04\x06\x03\0\x02\0\x05\x12\x03e\x04\t\n\
+    \x0e\n\x07\x04\x06\x03\0\x02\0\x01\x12\x03e\n\r\n\x0e\n\x07\x04\x06\x03\
+    \0\x02\0\x03\x12\x03e\x10\x11\n\r\n\x06\x04\x06\x03\0\x02\x01\x12\x03f\
+    \x04\x16\n\x0e\n\x07\x04\x06\x03\0\x02\x01\x05\x12\x03f\x04\n\n\x0e\n\
+    \x07\x04\x06\x03\0\x02\x01\x01\x12\x03f\x0b\x11\n\x0e\n\x07\x04\x06\x03\
+    \0\x02\x01\x03\x12\x03f\x14\x15\n\x0b\n\x04\x04\x06\x02\0\x12\x03h\x02(\
+    \n\x0c\n\x05\x04\x06\x02\0\x04\x12\x03h\x02\n\n\x0c\n\x05\x04\x06\x02\0\
+    \x06\x12\x03h\x0b\x18\n\x0c\n\x05\x04\x06\x02\0\x01\x12\x03h\x19#\n\x0c\
+    \n\x05\x04\x06\x02\0\x03\x12\x03h&'b\x06proto3\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -2679,7 +2774,8 @@ pub fn file_descriptor() -> &'static ::protobuf::reflect::FileDescriptor {
     static file_descriptor: ::protobuf::rt::Lazy<::protobuf::reflect::FileDescriptor> = ::protobuf::rt::Lazy::new();
     file_descriptor.get(|| {
         let generated_file_descriptor = generated_file_descriptor_lazy.get(|| {
-            let mut deps = ::std::vec::Vec::with_capacity(0);
+            let mut deps = ::std::vec::Vec::with_capacity(1);
+            deps.push(super::input::file_descriptor().clone());
             let mut messages = ::std::vec::Vec::with_capacity(13);
             messages.push(SchemaConfig::generated_message_descriptor_data());
             messages.push(Config::generated_message_descriptor_data());
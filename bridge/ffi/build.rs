@@ -0,0 +1,39 @@
+//! Generates the C header `src/c_example/dsnp_graph_sdk_ffi.h` when the `generate-header` feature
+//! is enabled. It runs the `cbindgen` CLI (overridable via the `CBINDGEN` env variable) which
+//! requires a nightly toolchain to expand the macros of this crate.
+use std::{env, io::ErrorKind, path::PathBuf, process::Command};
+
+fn main() {
+	println!("cargo:rerun-if-changed=cbindgen.toml");
+	for source in ["lib.rs", "bindings.rs", "c_api.rs", "errors.rs", "utils.rs"] {
+		println!("cargo:rerun-if-changed=src/{}", source);
+	}
+	println!("cargo:rerun-if-env-changed=CBINDGEN");
+
+	if env::var_os("CARGO_FEATURE_GENERATE_HEADER").is_none() {
+		return
+	}
+
+	let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+	let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+	let header = crate_dir.join("src").join("c_example").join("dsnp_graph_sdk_ffi.h");
+	let cbindgen = env::var("CBINDGEN").unwrap_or_else(|_| "cbindgen".to_string());
+
+	let status = Command::new(&cbindgen)
+		.current_dir(&crate_dir)
+		.args(["--config", "cbindgen.toml", "--crate", "dsnp-graph-sdk-ffi", "--output"])
+		.arg(&header)
+		// macro expansion runs another cargo build which would block on the lock of this one
+		.env("CARGO_EXPAND_TARGET_DIR", out_dir.join("cbindgen"))
+		.status();
+	let status = match status {
+		Ok(status) => status,
+		// keeps `--all-features` builds working on machines without cbindgen installed
+		Err(e) if e.kind() == ErrorKind::NotFound => {
+			println!("cargo:warning={} not found, skipped generating the C header", cbindgen);
+			return
+		},
+		Err(e) => panic!("failed to run {}: {}", cbindgen, e),
+	};
+	assert!(status.success(), "{} failed to generate {}", cbindgen, header.display());
+}
@@ -1,9 +1,10 @@
 use dsnp_graph_config::{DsnpVersion, GraphKeyType, SchemaConfig, SchemaId};
 use dsnp_graph_core::{
-	api::api_types::{Connection, PageHash, PageId},
+	api::api_types::{Connection, IdempotencyKey, PageHash, PageId},
 	dsnp::dsnp_types::{DsnpGraphEdge, DsnpUserId},
 };
 use libc::size_t;
+use std::ffi::c_char;
 
 /// `dsnp_graph_core::dsnp::api_types::KeyData` type
 #[repr(C)]
@@ -70,6 +71,17 @@ pub struct DsnpKeys {
 	pub keys_len: size_t,
 }
 
+/// `dsnp_graph_core::dsnp::api_types::ChainHead` type
+#[repr(C)]
+pub struct ChainHead {
+	/// block number
+	pub block_number: u32,
+
+	/// block hash
+	pub block_hash: *mut u8,
+	pub block_hash_len: size_t,
+}
+
 /// `dsnp_graph_core::dsnp::api_types::ImportBundle` type
 #[repr(C)]
 pub struct ImportBundle {
@@ -89,6 +101,9 @@ pub struct ImportBundle {
 	/// Page data containing the social graph retrieved from chain
 	pub pages: *mut PageData,
 	pub pages_len: size_t,
+
+	/// optional block at which the pages and keys were read from chain, null if not known
+	pub chain_head: *const ChainHead,
 }
 
 /// `dsnp_graph_core::dsnp::api_types::Update::PersistPage` type
@@ -109,6 +124,12 @@ pub struct PersistPage {
 	/// social graph page data
 	pub payload: *mut u8,
 	pub payload_len: size_t,
+
+	/// deterministic key which can be used to dedupe retried submissions of this update
+	pub idempotency_key: IdempotencyKey,
+
+	/// chain head of the imported data this update was computed against, null if not known
+	pub chain_head: *mut ChainHead,
 }
 
 /// `dsnp_graph_core::dsnp::api_types::Update::DeletePage` type
@@ -125,6 +146,12 @@ pub struct DeletePage {
 
 	/// previous hash value is used to avoid updating a stale state
 	pub prev_hash: PageHash,
+
+	/// deterministic key which can be used to dedupe retried submissions of this update
+	pub idempotency_key: IdempotencyKey,
+
+	/// chain head of the imported data this update was computed against, null if not known
+	pub chain_head: *mut ChainHead,
 }
 
 // `dsnp_graph_core::dsnp::api_types::Update::AddKey` type
@@ -139,6 +166,12 @@ pub struct AddKey {
 	/// social graph page data
 	pub payload: *mut u8,
 	pub payload_len: size_t,
+
+	/// deterministic key which can be used to dedupe retried submissions of this update
+	pub idempotency_key: IdempotencyKey,
+
+	/// chain head of the imported data this update was computed against, null if not known
+	pub chain_head: *mut ChainHead,
 }
 
 //// `dsnp_graph_core::dsnp::api_types::Update` type
@@ -162,8 +195,10 @@ pub struct SchemaConfigTuple {
 pub struct Config {
 	pub sdk_max_stale_friendship_days: u32,
 	pub max_graph_page_size_bytes: u32,
-	pub max_page_id: u32,
+	pub max_page_id: PageId,
 	pub max_key_page_size_bytes: u32,
+	/// maximum size of graph update payloads, 0 limits them by `max_graph_page_size_bytes` only
+	pub max_update_payload_bytes: u32,
 	pub schema_map_len: size_t,
 	pub schema_map: *mut SchemaConfigTuple,
 	pub graph_public_key_schema_id: SchemaId,
@@ -178,6 +213,8 @@ pub enum Environment {
 	Rococo,
 	TestnetPaseo,
 	Dev(Config),
+	/// Name of a config registered with `register_environment_config`
+	Custom(*const c_char),
 }
 
 /// Output type for`dsnp_graph_core::dsnp::dsn_types::DsnpGraphEdge` list
@@ -215,6 +252,12 @@ pub enum Action {
 
 		/// optional key to import
 		dsnp_keys: *mut DsnpKeys,
+
+		/// optional page to place the connection into, null for the normal packing
+		preferred_page_id: *const PageId,
+
+		/// optional since value of the connection, null if not provided
+		since: *const u64,
 	},
 
 	/// an action that defines removing an existing connection from social graph
@@ -235,4 +278,86 @@ pub enum Action {
 		new_public_key: *const u8,
 		new_public_key_len: size_t,
 	},
+
+	/// an action that defines adding a friendship between two users owned by the same wallet
+	ConnectMutual {
+		/// first user of the friendship
+		user_a: DsnpUserId,
+
+		/// second user of the friendship
+		user_b: DsnpUserId,
+
+		/// Schema id of the friendship graph
+		schema_id: SchemaId,
+
+		/// optional since value of the friendship, null if not provided
+		since: *const u64,
+	},
+
+	/// an action that defines moving an existing connection to the graph of the same connection
+	/// kind with the other privacy type
+	MigrateConnection {
+		/// owner of the social graph
+		owner_dsnp_user_id: DsnpUserId,
+
+		/// Schema id of the graph the connection is removed from
+		from_schema_id: SchemaId,
+
+		/// Schema id of the graph the connection is added to
+		to_schema_id: SchemaId,
+
+		/// user of the migrated connection
+		dsnp_user_id: DsnpUserId,
+
+		/// optional since value of the migrated connection, null if not provided
+		since: *const u64,
+	},
+}
+
+/// `dsnp_graph_core::api::api_types::PageOverflowPolicy` type
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageOverflowPolicy {
+	/// fails the export with `GraphCapacityExceeded` error
+	Error,
+
+	/// exports the connections that fit and keeps the overflowing ones pending
+	SkipOverflow,
+}
+
+/// `dsnp_graph_core::api::api_types::NonceMode` type
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonceMode {
+	/// uses a new random ephemeral key and nonce for every export
+	Random,
+
+	/// derives the ephemeral key and nonce from the exported page so retries are idempotent
+	Deterministic,
+}
+
+/// `dsnp_graph_core::api::api_types::EncryptionMode` type
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionMode {
+	/// seals private pages to the active key of the owner only
+	SingleRecipient,
+
+	/// seals the content key of private pages to every configured recipient
+	MultiRecipientV1,
+}
+
+/// `dsnp_graph_core::api::api_types::ExportOptions` type
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExportOptions {
+	/// behavior when added connections do not fit into the pages allowed for a graph
+	pub page_overflow_policy: PageOverflowPolicy,
+
+	/// strategy used to generate the encryption randomness of private pages
+	pub nonce_mode: NonceMode,
+
+	/// recipients private pages are sealed to, `MultiRecipientV1` additionally seals them to the keys
+	/// selected using `graph_set_additional_recipient_keys` and fails if none are selected
+	pub encryption_mode: EncryptionMode,
 }
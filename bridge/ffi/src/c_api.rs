@@ -1,18 +1,21 @@
-use crate::{bindings::*, utils::*, FFIResult, GraphError};
-use dsnp_graph_config::{errors::DsnpGraphError, Config as RustConfig, ConnectionType, SchemaId};
+use crate::{bindings::*, registry::GraphStateRegistry, utils::*, FFIResult, GraphError};
+use dsnp_graph_config::{
+	builder::ConfigBuilder, errors::DsnpGraphError, registry::EnvironmentRegistry,
+	Config as RustConfig, ConnectionType, SchemaConfig, SchemaId,
+};
 use dsnp_graph_core::{
 	api::{
 		api::{GraphAPI, GraphState},
-		api_types::ActionOptions,
+		api_types::{ActionOptions, KeyPageStats},
 	},
 	dsnp::dsnp_types::DsnpUserId,
 	util::transactional_hashmap::Transactional,
 };
+use lazy_static::lazy_static;
 use std::{
-	ffi::{c_char, CString},
+	ffi::{c_char, c_void, CString},
 	mem::ManuallyDrop,
 	panic,
-	sync::Mutex,
 };
 
 #[no_mangle]
@@ -20,9 +23,26 @@ pub extern "C" fn print_hello_graph() {
 	println!("Hello, Graph!");
 }
 
-// Collection of GraphStates
-#[allow(clippy::vec_box)]
-static GRAPH_STATES: Mutex<Vec<Box<GraphState>>> = Mutex::new(Vec::new());
+lazy_static! {
+	// Collection of GraphStates
+	static ref GRAPH_STATES: GraphStateRegistry = GraphStateRegistry::new();
+}
+
+/// resolves the handle of a graph state, failing if it is null or not owned by `GRAPH_STATES`
+unsafe fn graph_state_from_handle<'a>(
+	graph_state: *mut GraphState,
+) -> Result<&'a mut GraphState, *mut GraphError> {
+	if graph_state.is_null() {
+		return Err(GraphError::from_error(DsnpGraphError::FFIError(
+			"Graph state is null".to_string(),
+		)))
+	}
+	GRAPH_STATES.get(graph_state).ok_or_else(|| {
+		GraphError::from_error(DsnpGraphError::FFIError(
+			"Graph state is unknown or was freed".to_string(),
+		))
+	})
+}
 
 /// Get the graph config for the given environment
 /// # Safety
@@ -39,8 +59,10 @@ pub unsafe extern "C" fn get_graph_config(
 ) -> FFIResult<Config, GraphError> {
 	let result = panic::catch_unwind(|| {
 		let env = &*environment;
-		let config_for_ffi = get_config_for_ffi(env);
-		FFIResult::new(config_for_ffi)
+		match get_config_for_ffi(env) {
+			Ok(config_for_ffi) => FFIResult::new(config_for_ffi),
+			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
+		}
 	});
 
 	result.unwrap_or_else(|error| {
@@ -78,6 +100,111 @@ pub unsafe extern "C" fn get_schema_id_from_config(
 	}
 }
 
+/// Get the connection type, including its privacy type, and the DSNP version of a schema id
+/// using the given Config.
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer
+/// # Arguments
+/// * `schema_id` - the schema id to resolve
+/// * `config` - a pointer to a Config struct
+/// # Returns
+/// * `SchemaConfig` - the connection type and DSNP version of the schema
+/// # Errors
+/// * `GraphError` - if the schema id is not part of the config
+#[no_mangle]
+pub unsafe extern "C" fn get_connection_type_from_schema_id(
+	schema_id: SchemaId,
+	config: *const Config,
+) -> FFIResult<SchemaConfig, GraphError> {
+	let cfg = &*config;
+	let rust_config: RustConfig = config_from_ffi(cfg);
+	match (
+		rust_config.get_connection_type_from_schema_id(schema_id),
+		rust_config.get_dsnp_version_from_schema_id(schema_id),
+	) {
+		(Some(connection_type), Some(dsnp_version)) => FFIResult::new(SchemaConfig {
+			dsnp_version,
+			connection_type,
+			page_capacity: rust_config.get_page_capacity_from_schema_id(schema_id),
+		}),
+		_ => FFIResult::new_mut_error(GraphError::from_error(
+			rust_config.invalid_schema_id(schema_id),
+		)),
+	}
+}
+
+/// Register a config under the given name, to be used with `Environment::Custom`
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers
+/// # Arguments
+/// * `name` - a pointer to a null terminated environment name
+/// * `config` - a pointer to a Config struct
+/// # Returns
+/// * `bool` - true if the config was registered
+/// # Errors
+/// * `GraphError` - if the name or config is invalid
+#[no_mangle]
+pub unsafe extern "C" fn register_environment_config(
+	name: *const c_char,
+	config: *const Config,
+) -> FFIResult<bool, GraphError> {
+	let result = panic::catch_unwind(|| {
+		if config.is_null() {
+			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
+				"Config is null".to_string(),
+			)));
+		}
+		let rust_config = config_from_ffi(&*config);
+		match string_from_ffi(name)
+			.and_then(|name| EnvironmentRegistry::register(&name, rust_config))
+		{
+			Ok(_) => FFIResult::new(true),
+			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
+		}
+	});
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed to register environment config: {:?}",
+			error
+		))))
+	})
+}
+
+/// Register a config built from the Mainnet config with overrides, to be used with
+/// `Environment::Custom`
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers
+/// # Arguments
+/// * `name` - a pointer to a null terminated environment name
+/// * `overrides_json` - a pointer to a null terminated JSON object of the overridden config fields
+/// # Returns
+/// * `bool` - true if the config was registered
+/// # Errors
+/// * `GraphError` - if the name, overrides or resulting config are invalid
+#[no_mangle]
+pub unsafe extern "C" fn register_environment_config_from_mainnet(
+	name: *const c_char,
+	overrides_json: *const c_char,
+) -> FFIResult<bool, GraphError> {
+	let result = panic::catch_unwind(|| {
+		let registered = string_from_ffi(overrides_json)
+			.and_then(|overrides| ConfigBuilder::from_mainnet_json(&overrides))
+			.and_then(|config| {
+				string_from_ffi(name).and_then(|name| EnvironmentRegistry::register(&name, config))
+			});
+		match registered {
+			Ok(_) => FFIResult::new(true),
+			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
+		}
+	});
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed to register environment config: {:?}",
+			error
+		))))
+	})
+}
+
 /// Initialize a graph state with the given environment
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
@@ -93,12 +220,15 @@ pub unsafe extern "C" fn initialize_graph_state(
 ) -> FFIResult<GraphState, GraphError> {
 	let result = panic::catch_unwind(|| {
 		let environment = &*environment;
-		let rust_environment = environment_from_ffi(environment);
-		let graph_state = Box::new(GraphState::new(rust_environment));
-		let graph_state_ptr = Box::into_raw(graph_state);
-		let mut graph_states = GRAPH_STATES.lock().unwrap();
-		graph_states.push(Box::from_raw(graph_state_ptr));
-		FFIResult::new_mut(graph_state_ptr)
+		let rust_environment = match environment_from_ffi(environment) {
+			Ok(rust_environment) => rust_environment,
+			Err(error) => return FFIResult::new_mut_error(GraphError::from_error(error)),
+		};
+		let graph_state = match GraphState::try_new(rust_environment) {
+			Ok(graph_state) => Box::new(graph_state),
+			Err(error) => return FFIResult::new_mut_error(GraphError::from_error(error)),
+		};
+		FFIResult::new_mut(GRAPH_STATES.insert(graph_state))
 	});
 	result.unwrap_or_else(|error| {
 		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
@@ -115,10 +245,7 @@ pub unsafe extern "C" fn initialize_graph_state(
 /// * `GraphError` - if the count of graph states cannot be retrieved
 #[no_mangle]
 pub unsafe extern "C" fn get_graph_states_count() -> FFIResult<usize, GraphError> {
-	let result = panic::catch_unwind(|| {
-		let graph_states = GRAPH_STATES.lock().unwrap();
-		FFIResult::new(graph_states.len())
-	});
+	let result = panic::catch_unwind(|| FFIResult::new(GRAPH_STATES.len()));
 	result.unwrap_or_else(|error| {
 		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
 			"Failed to get graph states count: {:?}",
@@ -130,6 +257,8 @@ pub unsafe extern "C" fn get_graph_states_count() -> FFIResult<usize, GraphError
 /// Check if a given state contains user graph
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
 /// * `user_id` - a pointer to a user id
@@ -143,12 +272,10 @@ pub unsafe extern "C" fn graph_contains_user(
 	user_id: *const DsnpUserId,
 ) -> FFIResult<bool, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		let user_id = &*user_id;
 		FFIResult::new(graph_state.contains_user_graph(user_id))
 	});
@@ -160,9 +287,48 @@ pub unsafe extern "C" fn graph_contains_user(
 	})
 }
 
+/// Check if the graph of a user can be exported, which for private graphs requires an imported
+/// secret key of the user
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
+/// # Arguments
+/// * `graph_state` - a pointer to a graph state
+/// * `user_id` - a pointer to a user id
+/// * `schema_id` - a pointer to a schema id
+/// # Returns
+/// * `bool` - true if the user graph is writable, false if it is read-only or not imported
+/// # Errors
+/// * `GraphError` - if state fails to check the user graph
+#[no_mangle]
+pub unsafe extern "C" fn graph_is_user_writable(
+	graph_state: *mut GraphState,
+	user_id: *const DsnpUserId,
+	schema_id: *const SchemaId,
+) -> FFIResult<bool, GraphError> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
+		let user_id = &*user_id;
+		let schema_id = &*schema_id;
+		FFIResult::new(graph_state.is_user_graph_writable(user_id, schema_id))
+	});
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed to check if user graph is writable: {:?}",
+			error
+		))))
+	})
+}
+
 /// Count of users in current graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
 /// # Returns
@@ -174,12 +340,10 @@ pub unsafe extern "C" fn graph_users_count(
 	graph_state: *mut GraphState,
 ) -> FFIResult<usize, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		FFIResult::new(graph_state.len())
 	});
 	result.unwrap_or_else(|error| {
@@ -193,6 +357,8 @@ pub unsafe extern "C" fn graph_users_count(
 /// Remove user from graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
 /// * `user_id` - a pointer to a user id
@@ -206,12 +372,10 @@ pub unsafe extern "C" fn graph_remove_user(
 	user_id: *const DsnpUserId,
 ) -> FFIResult<bool, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		let user_id = &*user_id;
 		graph_state.remove_user_graph(user_id);
 		FFIResult::new(true)
@@ -227,6 +391,8 @@ pub unsafe extern "C" fn graph_remove_user(
 /// Import users data to graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
 /// * `payloads` - a pointer to an array of payloads
@@ -242,15 +408,13 @@ pub unsafe extern "C" fn graph_import_users_data(
 	payloads_len: usize,
 ) -> FFIResult<bool, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		let payloads = std::slice::from_raw_parts(payloads, payloads_len);
 		let payloads = payloads_from_ffi(&payloads);
-		let imported = graph_state.import_users_data(&payloads);
+		let imported = graph_state.import_users_data_borrowed(&payloads);
 		match imported {
 			Ok(_) => FFIResult::new(true),
 			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
@@ -267,6 +431,8 @@ pub unsafe extern "C" fn graph_import_users_data(
 /// Export updates from graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
 /// # Returns
@@ -278,12 +444,10 @@ pub unsafe extern "C" fn graph_export_updates(
 	graph_state: *mut GraphState,
 ) -> FFIResult<GraphUpdates, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		match graph_state.export_updates() {
 			Ok(updates) => {
 				let ffi_updates = updates_to_ffi(updates);
@@ -306,6 +470,8 @@ pub unsafe extern "C" fn graph_export_updates(
 /// Export updates from graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
 /// # Returns
@@ -318,12 +484,10 @@ pub unsafe extern "C" fn graph_export_user_graph_updates(
 	user_id: *const DsnpUserId,
 ) -> FFIResult<GraphUpdates, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		match graph_state.export_user_graph_updates(&*user_id) {
 			Ok(updates) => {
 				let ffi_updates = updates_to_ffi(updates);
@@ -346,6 +510,8 @@ pub unsafe extern "C" fn graph_export_user_graph_updates(
 /// Force recalculate graph updates from graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to the graph state
 /// * `user_id` - a pointer to a user id
@@ -359,7 +525,10 @@ pub unsafe extern "C" fn graph_force_recalculate_updates(
 	user_id: *const DsnpUserId,
 ) -> FFIResult<GraphUpdates, GraphError> {
 	let result = panic::catch_unwind(|| {
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		match graph_state.force_recalculate_graphs(&*user_id) {
 			Ok(updates) => {
 				let ffi_updates = updates_to_ffi(updates);
@@ -380,9 +549,53 @@ pub unsafe extern "C" fn graph_force_recalculate_updates(
 	})
 }
 
+/// Export removal of all graph pages of a user from graph state
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
+/// # Arguments
+/// * `graph_state` - a pointer to the graph state
+/// * `user_id` - a pointer to a user id
+/// # Returns
+/// * `GraphUpdates` - the pointer to the graph updates
+/// # Errors
+/// * `GraphError` - if the graph updates cannot be retrieved
+#[no_mangle]
+pub unsafe extern "C" fn graph_export_graph_removal(
+	graph_state: *mut GraphState,
+	user_id: *const DsnpUserId,
+) -> FFIResult<GraphUpdates, GraphError> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
+		match graph_state.export_graph_removal(&*user_id) {
+			Ok(updates) => {
+				let ffi_updates = updates_to_ffi(updates);
+				let updates_len = ffi_updates.len();
+				let updates_ptr = ManuallyDrop::new(ffi_updates).as_mut_ptr();
+				let graph_updates = GraphUpdates { updates: updates_ptr, updates_len };
+				FFIResult::new(graph_updates)
+			},
+			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
+		}
+	});
+
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed to export graph removal from graph: {:?}",
+			error
+		))))
+	})
+}
+
 /// Apply actions to graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
 /// * `actions` - a pointer to an array of actions
@@ -399,12 +612,10 @@ pub unsafe extern "C" fn graph_apply_actions(
 	options: *const ActionOptions,
 ) -> FFIResult<bool, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		let actions = std::slice::from_raw_parts(actions, actions_len);
 		let actions = actions_from_ffi(&actions);
 		let mut rust_options: Option<ActionOptions> = None;
@@ -425,24 +636,216 @@ pub unsafe extern "C" fn graph_apply_actions(
 	})
 }
 
-/// Commit pending actions to a graph state
+/// Apply actions to graph state and report the fraction of processed actions
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
+/// * `actions` - a pointer to an array of actions
+/// * `actions_len` - the length of the actions array
+/// * `options` - a pointer to the action options, can be null
+/// * `on_progress` - called on the calling thread with the fraction of processed actions and
+/// `context` after each processed chunk of actions, can be null
+/// * `context` - a pointer passed back to `on_progress`, can be null
 /// # Returns
-/// * `void`
+/// * `bool` - true if the actions were applied, false otherwise
 /// # Errors
 /// * `GraphError` - if the actions cannot be applied to the graph state
 #[no_mangle]
-pub unsafe extern "C" fn graph_commit(graph_state: *mut GraphState) -> FFIResult<bool, GraphError> {
+pub unsafe extern "C" fn graph_apply_actions_with_progress(
+	graph_state: *mut GraphState,
+	actions: *const Action,
+	actions_len: usize,
+	options: *const ActionOptions,
+	on_progress: Option<unsafe extern "C" fn(f64, *mut c_void)>,
+	context: *mut c_void,
+) -> FFIResult<bool, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
+		let actions = std::slice::from_raw_parts(actions, actions_len);
+		let actions = actions_from_ffi(&actions);
+		let mut rust_options: Option<ActionOptions> = None;
+		if !options.is_null() {
+			let options = &*options;
+			rust_options = Some(options.clone());
+		}
+		let mut report_progress = |fraction: f64| {
+			if let Some(on_progress) = on_progress {
+				on_progress(fraction, context);
+			}
+		};
+		match graph_state.apply_actions_with_progress(&actions, &rust_options, &mut report_progress)
+		{
+			Ok(_) => FFIResult::new(true),
+			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
+		}
+	});
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed to apply actions to graph: {:?}",
+			error
+		))))
+	})
+}
+
+/// Apply actions to graph state and export the updates in a single call
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
+/// # Arguments
+/// * `graph_state` - a pointer to a graph state
+/// * `actions` - a pointer to an array of actions
+/// * `actions_len` - the length of the actions array
+/// * `options` - a pointer to the action options, can be null
+/// # Returns
+/// * `GraphUpdates` - the pointer to the graph updates
+/// # Errors
+/// * `GraphError` - if the actions cannot be applied or the updates cannot be exported
+#[no_mangle]
+pub unsafe extern "C" fn graph_apply_actions_and_export(
+	graph_state: *mut GraphState,
+	actions: *const Action,
+	actions_len: usize,
+	options: *const ActionOptions,
+) -> FFIResult<GraphUpdates, GraphError> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
+		let actions = std::slice::from_raw_parts(actions, actions_len);
+		let actions = actions_from_ffi(&actions);
+		let mut rust_options: Option<ActionOptions> = None;
+		if !options.is_null() {
+			let options = &*options;
+			rust_options = Some(options.clone());
+		}
+		match graph_state.apply_actions_and_export(&actions, &rust_options) {
+			Ok(updates) => {
+				let ffi_updates = updates_to_ffi(updates);
+				let updates_len = ffi_updates.len();
+				let updates_ptr = ManuallyDrop::new(ffi_updates).as_mut_ptr();
+				let graph_updates = GraphUpdates { updates: updates_ptr, updates_len };
+				FFIResult::new(graph_updates)
+			},
+			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
+		}
+	});
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed to apply actions and export updates from graph: {:?}",
+			error
+		))))
+	})
+}
+
+/// Set the options used when exporting updates of a graph state
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
+/// # Arguments
+/// * `graph_state` - a pointer to a graph state
+/// * `options` - a pointer to the export options
+/// # Returns
+/// * `bool` - true if the options were set
+/// # Errors
+/// * `GraphError` - if the graph state or the options are null
+#[no_mangle]
+pub unsafe extern "C" fn graph_set_export_options(
+	graph_state: *mut GraphState,
+	options: *const ExportOptions,
+) -> FFIResult<bool, GraphError> {
+	let result = panic::catch_unwind(|| {
+		if options.is_null() {
 			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
+				"Export options is null".to_string(),
 			)));
 		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
+		graph_state.set_export_options(export_options_from_ffi(&*options));
+		FFIResult::new(true)
+	});
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed to set export options of graph: {:?}",
+			error
+		))))
+	})
+}
+
+/// Select published keys of a user that private pages are sealed to in addition to the active key
+/// when exported using `EncryptionMode::MultiRecipientV1`
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
+/// # Arguments
+/// * `graph_state` - a pointer to the graph state
+/// * `user_id` - a pointer to a user id
+/// * `key_ids` - a pointer to an array of key ids, can be null if `key_ids_len` is 0
+/// * `key_ids_len` - the length of the key ids array, 0 removes the additional recipients
+/// # Returns
+/// * `bool` - true if the additional recipients were set
+/// # Errors
+/// * `GraphError` - if a key id is not among the imported public keys of the user
+#[no_mangle]
+pub unsafe extern "C" fn graph_set_additional_recipient_keys(
+	graph_state: *mut GraphState,
+	user_id: *const DsnpUserId,
+	key_ids: *const u64,
+	key_ids_len: usize,
+) -> FFIResult<bool, GraphError> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
+		let user_id = &*user_id;
+		let key_ids = match key_ids_len {
+			0 => &[][..],
+			_ => std::slice::from_raw_parts(key_ids, key_ids_len),
+		};
+		match graph_state.set_additional_recipient_keys(user_id, key_ids) {
+			Ok(_) => FFIResult::new(true),
+			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
+		}
+	});
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed to set additional recipient keys of graph: {:?}",
+			error
+		))))
+	})
+}
+
+/// Commit pending actions to a graph state
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
+/// # Arguments
+/// * `graph_state` - a pointer to a graph state
+/// # Returns
+/// * `void`
+/// # Errors
+/// * `GraphError` - if the actions cannot be applied to the graph state
+#[no_mangle]
+pub unsafe extern "C" fn graph_commit(graph_state: *mut GraphState) -> FFIResult<bool, GraphError> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		graph_state.commit();
 		FFIResult::new(true)
 	});
@@ -457,6 +860,8 @@ pub unsafe extern "C" fn graph_commit(graph_state: *mut GraphState) -> FFIResult
 /// Rollback pending actions in a graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
 /// # Returns
@@ -468,12 +873,10 @@ pub unsafe extern "C" fn graph_rollback(
 	graph_state: *mut GraphState,
 ) -> FFIResult<bool, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		graph_state.rollback();
 		FFIResult::new(true)
 	});
@@ -488,6 +891,8 @@ pub unsafe extern "C" fn graph_rollback(
 /// Get connections for user from graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
 /// * `user_id` - a pointer to a user id
@@ -505,12 +910,10 @@ pub unsafe extern "C" fn graph_get_connections_for_user(
 	include_pending: bool,
 ) -> FFIResult<GraphConnections, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		let user_id = &*user_id;
 		let schema_id = &*schema_id;
 		match graph_state.get_connections_for_user_graph(user_id, schema_id, include_pending) {
@@ -535,8 +938,11 @@ pub unsafe extern "C" fn graph_get_connections_for_user(
 /// Get user connections without keys from graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
+/// * `include_pending` - a boolean to include pending connections
 /// # Returns
 /// * `GraphConnectionsWithoutKeys` - the pointer to the graph connections without keys
 /// # Errors
@@ -544,15 +950,14 @@ pub unsafe extern "C" fn graph_get_connections_for_user(
 #[no_mangle]
 pub unsafe extern "C" fn graph_get_connections_without_keys(
 	graph_state: *mut GraphState,
+	include_pending: bool,
 ) -> FFIResult<GraphConnectionsWithoutKeys, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
-		match graph_state.get_connections_without_keys() {
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
+		match graph_state.get_connections_without_keys(include_pending) {
 			Ok(connections) => {
 				let connections_len = connections.len();
 				let connections_ptr = ManuallyDrop::new(connections).as_mut_ptr();
@@ -571,12 +976,62 @@ pub unsafe extern "C" fn graph_get_connections_without_keys(
 	})
 }
 
+/// Get a page of user connections without keys from graph state, sorted by dsnp user id
+/// # Safety
+/// This function is unsafe because it dereferences raw pointers
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
+/// # Arguments
+/// * `graph_state` - a pointer to a graph state
+/// * `include_pending` - a boolean to include pending connections
+/// * `cursor` - a pointer to the last dsnp user id of the previous page, or null for the first page
+/// * `limit` - maximum number of returned connections, or 0 for no limit
+/// # Returns
+/// * `GraphConnectionsWithoutKeys` - the pointer to the graph connections without keys
+/// # Errors
+/// * `GraphError` - if the connections cannot be retrieved
+#[no_mangle]
+pub unsafe extern "C" fn graph_get_connections_without_keys_page(
+	graph_state: *mut GraphState,
+	include_pending: bool,
+	cursor: *const DsnpUserId,
+	limit: usize,
+) -> FFIResult<GraphConnectionsWithoutKeys, GraphError> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
+		let cursor = if cursor.is_null() { None } else { Some(*cursor) };
+		let limit = if limit == 0 { None } else { Some(limit) };
+		match graph_state.get_connections_without_keys_page(include_pending, cursor, limit) {
+			Ok(connections) => {
+				let connections_len = connections.len();
+				let connections_ptr = ManuallyDrop::new(connections).as_mut_ptr();
+				let graph_connections =
+					GraphConnectionsWithoutKeys { connections: connections_ptr, connections_len };
+				FFIResult::new(graph_connections)
+			},
+			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
+		}
+	});
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed get connections without keys page from graph: {:?}",
+			error
+		))))
+	})
+}
+
 /// Get one sided private friendship connections for a user from graph state
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to a graph state
 /// * `user_id` - a pointer to a user id
+/// * `include_pending` - a boolean to include pending connections
 /// # Returns
 /// * `GraphConnections` - the pointer to the graph connections
 /// # Errors
@@ -585,16 +1040,15 @@ pub unsafe extern "C" fn graph_get_connections_without_keys(
 pub unsafe extern "C" fn graph_get_one_sided_private_friendship_connections(
 	graph_state: *mut GraphState,
 	user_id: *const DsnpUserId,
+	include_pending: bool,
 ) -> FFIResult<GraphConnections, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		let user_id = &*user_id;
-		match graph_state.get_one_sided_private_friendship_connections(user_id) {
+		match graph_state.get_one_sided_private_friendship_connections(user_id, include_pending) {
 			Ok(connections) => {
 				let connections_len = connections.len();
 				let connections_ptr = ManuallyDrop::new(connections).as_mut_ptr();
@@ -616,6 +1070,8 @@ pub unsafe extern "C" fn graph_get_one_sided_private_friendship_connections(
 /// Get a list of published and imported public keys associated with a user
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
 /// # Arguments
 /// * `graph_state` - a pointer to the graph state
 /// * `user_id` - a pointer to a user id
@@ -629,12 +1085,10 @@ pub unsafe extern "C" fn graph_get_public_keys(
 	user_id: *const DsnpUserId,
 ) -> FFIResult<DsnpPublicKeys, GraphError> {
 	let result = panic::catch_unwind(|| {
-		if graph_state.is_null() {
-			return FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::FFIError(
-				"Graph state is null".to_string(),
-			)));
-		}
-		let graph_state = &mut *graph_state;
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
 		let user_id = &*user_id;
 
 		match graph_state.get_public_keys(user_id) {
@@ -656,6 +1110,80 @@ pub unsafe extern "C" fn graph_get_public_keys(
 	})
 }
 
+/// Get key count and key page utilization for a user
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
+/// # Arguments
+/// * `graph_state` - a pointer to the graph state
+/// * `user_id` - a pointer to a user id
+/// # Returns
+/// * `KeyPageStats` - the pointer to the key page stats
+/// # Errors
+/// * `GraphError` - if the key page stats cannot be calculated
+#[no_mangle]
+pub unsafe extern "C" fn graph_get_key_page_stats(
+	graph_state: *mut GraphState,
+	user_id: *const DsnpUserId,
+) -> FFIResult<KeyPageStats, GraphError> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
+		let user_id = &*user_id;
+
+		match graph_state.get_key_page_stats(user_id) {
+			Ok(stats) => FFIResult::new(stats),
+			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
+		}
+	});
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed to get key page stats from graph: {:?}",
+			error
+		))))
+	})
+}
+
+/// Get the key id the next published key of a user will be assigned
+/// # Safety
+/// This function is unsafe because it dereferences a raw pointer
+/// `graph_state` must come from `initialize_graph_state`; null and freed handles are rejected,
+/// but calls on the same graph state, including `free_graph_state`, must not run concurrently
+/// # Arguments
+/// * `graph_state` - a pointer to the graph state
+/// * `user_id` - a pointer to a user id
+/// # Returns
+/// * `u64` - the next key id
+/// # Errors
+/// * `GraphError` - if the next key id cannot be calculated
+#[no_mangle]
+pub unsafe extern "C" fn graph_get_next_key_id(
+	graph_state: *mut GraphState,
+	user_id: *const DsnpUserId,
+) -> FFIResult<u64, GraphError> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = match graph_state_from_handle(graph_state) {
+			Ok(graph_state) => graph_state,
+			Err(error) => return FFIResult::new_mut_error(error),
+		};
+		let user_id = &*user_id;
+
+		match graph_state.get_next_key_id(user_id) {
+			Ok(key_id) => FFIResult::new(key_id),
+			Err(error) => FFIResult::new_mut_error(GraphError::from_error(error)),
+		}
+	});
+	result.unwrap_or_else(|error| {
+		FFIResult::new_mut_error(GraphError::from_error(DsnpGraphError::Unknown(anyhow::anyhow!(
+			"Failed to get next key id from graph: {:?}",
+			error
+		))))
+	})
+}
+
 /// Returns the deserialized dsnp keys
 /// # Safety
 /// This function is unsafe because it dereferences a raw pointer
@@ -698,12 +1226,9 @@ pub unsafe extern "C" fn graph_deserialize_dsnp_keys(
 pub unsafe extern "C" fn free_graph_state(graph_state: *mut GraphState) {
 	let result = panic::catch_unwind(|| {
 		if graph_state.is_null() {
-			return;
+			return
 		}
-		let mut graph_states = GRAPH_STATES.lock().unwrap();
-		let index =
-			graph_states.iter().position(|x| x.as_ref() as *const _ == graph_state).unwrap();
-		graph_states.remove(index);
+		GRAPH_STATES.remove(graph_state);
 	});
 	result.unwrap_or(())
 }
@@ -714,8 +1239,7 @@ pub unsafe extern "C" fn free_graph_state(graph_state: *mut GraphState) {
 #[no_mangle]
 pub extern "C" fn free_graph_states() {
 	let result = panic::catch_unwind(|| {
-		let mut graph_states = GRAPH_STATES.lock().unwrap();
-		graph_states.clear();
+		GRAPH_STATES.clear();
 	});
 	result.unwrap_or(())
 }
@@ -15,6 +15,10 @@ extern "C" {
         } \
     } while (0)
 
+void record_progress(double fraction, void *context) {
+    *(double *)context = fraction;
+}
+
 int test_initialize_and_clear_states() {
     Environment environment;
     environment.tag = Mainnet;
@@ -66,12 +70,12 @@ int test_initialize_and_clear_states() {
     free_dsnp_graph_error(connectionsresult.error);
     free_dsnp_graph_error_message(errormessage);
 
-    DsnpGraphConnectionsWithoutKeysResult_Error connectionswithoutkeysresult = graph_get_connections_without_keys(graphstate);
+    DsnpGraphConnectionsWithoutKeysResult_Error connectionswithoutkeysresult = graph_get_connections_without_keys(graphstate, true);
     ASSERT(connectionswithoutkeysresult.error == NULL, "Failed to get connections without keys");
     GraphConnectionsWithoutKeys connectionswithoutkeys = *(connectionswithoutkeysresult.result);
     ASSERT(connectionswithoutkeys.connections != NULL, "Expected zero length");
 
-    DsnpGraphConnectionsResult_Error onesidedconnectionsresult = graph_get_one_sided_private_friendship_connections(graphstate, &userid);
+    DsnpGraphConnectionsResult_Error onesidedconnectionsresult = graph_get_one_sided_private_friendship_connections(graphstate, &userid, true);
     ASSERT(onesidedconnectionsresult.error != NULL, "Expected error to get one sided private friendship connections");
     errormessage = dsnp_graph_error_message(onesidedconnectionsresult.error);
     ASSERT(errormessage != NULL, "Failed to get error message");
@@ -835,8 +839,10 @@ int api_apply_actions_should_work_as_expected_and_include_changes_in_pending() {
     ASSERT(apply_result.error == NULL, "Failed to apply redundant actions with options object");
     apply_result = graph_apply_actions(state, actions_3, 1, &options);
     ASSERT(apply_result.error == NULL, "Failed to apply non-existent disconnections with options object");
-    apply_result = graph_apply_actions(state, actions_4, 1, NULL);
+    double progress = 0.0;
+    apply_result = graph_apply_actions_with_progress(state, actions_4, 1, NULL, record_progress, &progress);
     ASSERT(apply_result.error == NULL, "Failed to apply additions to other user's graph");
+    ASSERT(progress == 1.0, "Failed to report progress of applied actions");
 
     connections_result = graph_get_connections_for_user(state, &dsnp_user_id_1, &schema_id, true);
     ASSERT(connections_result.error == NULL, "Failed to get connections");
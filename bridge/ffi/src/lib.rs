@@ -9,6 +9,13 @@
 //! To use the FFI layer, you need to build the dynamic library (e.g., .so, .dylib, or .dll) by adding
 //! the appropriate configuration to `Cargo.toml`. Then, include the generated dynamic library and
 //! the C header file `dsnp_graph_sdk_ffi.h` in your C, C++, or other FFI-compatible projects.
+//!
+//! ## Thread Safety
+//!
+//! Different graph states can be used concurrently from different threads, since calls operating
+//! on a graph state do not lock any global state. Calls on the same graph state have to be
+//! serialized by the caller. Creating and freeing graph states is thread safe, and a panic inside
+//! a call is returned as an error without affecting other graph states.
 
 mod c_api;
 pub use c_api::*;
@@ -18,6 +25,7 @@ mod utils;
 pub use utils::*;
 mod errors;
 pub use errors::*;
+mod registry;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,92 @@
+//! Registry owning the graph states created through the C API
+//!
+//! Graph states are kept in shards keyed by their handle, so creating or freeing a state only
+//! locks the shard it belongs to. Poisoned shards are recovered instead of failing, which is safe
+//! since every operation on a shard leaves it consistent, so a panic on one state does not affect
+//! the other states or later calls.
+//!
+//! The shards only hold the addresses of the boxed states, which are turned back into boxes when
+//! the states are freed. Calls operating on a graph state only lock its shard to check that the
+//! handle is still registered, so different states can be used concurrently from different
+//! threads, while calls on the same state have to be serialized by the caller.
+use dsnp_graph_core::api::api::GraphState;
+use std::{
+	collections::HashSet,
+	sync::{Mutex, MutexGuard, PoisonError},
+};
+
+/// number of independently locked shards
+const SHARD_COUNT: usize = 16;
+
+/// addresses of the leaked boxes of the graph states
+type Shard = HashSet<usize>;
+
+/// Sharded collection of graph states keyed by their handle
+pub(crate) struct GraphStateRegistry {
+	shards: Vec<Mutex<Shard>>,
+}
+
+impl GraphStateRegistry {
+	pub(crate) fn new() -> Self {
+		Self { shards: (0..SHARD_COUNT).map(|_| Mutex::new(HashSet::new())).collect() }
+	}
+
+	/// takes the ownership of the graph state and returns its handle
+	pub(crate) fn insert(&self, graph_state: Box<GraphState>) -> *mut GraphState {
+		let handle = Box::into_raw(graph_state);
+		self.shard(handle as usize).insert(handle as usize);
+		handle
+	}
+
+	/// returns the graph state of the handle, or None if the handle is unknown or was removed
+	///
+	/// # Safety
+	/// the returned reference must not outlive the removal of the state, and must not be used
+	/// while another reference to the same state is alive
+	pub(crate) unsafe fn get<'a>(&self, handle: *mut GraphState) -> Option<&'a mut GraphState> {
+		match self.shard(handle as usize).contains(&(handle as usize)) {
+			true => Some(&mut *handle),
+			false => None,
+		}
+	}
+
+	/// drops the graph state of the handle, returns false if the handle is unknown
+	pub(crate) fn remove(&self, handle: *const GraphState) -> bool {
+		let removed = self.shard(handle as usize).remove(&(handle as usize));
+		if removed {
+			// the address was registered by `insert` and is only removed once
+			drop(unsafe { Box::from_raw(handle as *mut GraphState) });
+		}
+		removed
+	}
+
+	pub(crate) fn len(&self) -> usize {
+		(0..SHARD_COUNT).map(|index| self.lock(index).len()).sum()
+	}
+
+	pub(crate) fn clear(&self) {
+		for index in 0..SHARD_COUNT {
+			let handles: Vec<usize> = self.lock(index).drain().collect();
+			for handle in handles {
+				// the addresses were registered by `insert` and are no longer in the shard
+				drop(unsafe { Box::from_raw(handle as *mut GraphState) });
+			}
+		}
+	}
+
+	/// locks the shard of the handle
+	pub(crate) fn shard(&self, handle: usize) -> MutexGuard<'_, Shard> {
+		// handles are aligned so the lower bits are not used to pick the shard
+		self.lock((handle / std::mem::align_of::<GraphState>()) % SHARD_COUNT)
+	}
+
+	fn lock(&self, index: usize) -> MutexGuard<'_, Shard> {
+		self.shards[index].lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+impl Drop for GraphStateRegistry {
+	fn drop(&mut self) {
+		self.clear();
+	}
+}
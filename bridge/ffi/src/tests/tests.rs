@@ -1,5 +1,6 @@
-use crate::{bindings::*, c_api::*};
-use std::ptr;
+use crate::{bindings::*, c_api::*, registry::GraphStateRegistry, utils::*};
+use dsnp_graph_core::api::api::GraphState;
+use std::{ptr, sync::Arc, thread};
 
 #[cfg(test)]
 mod tests {
@@ -11,6 +12,7 @@ mod tests {
 			max_graph_page_size_bytes: 1024,
 			max_page_id: 10,
 			max_key_page_size_bytes: 1024,
+			max_update_payload_bytes: 0,
 			schema_map: ptr::null_mut(),
 			schema_map_len: 0,
 			graph_public_key_schema_id: 0,
@@ -58,5 +60,153 @@ mod tests {
 		}
 	}
 
-	// Add more tests as needed
+	#[test]
+	fn test_config_from_ffi_should_map_max_update_payload_bytes() {
+		let mut c_config = get_config_for_ffi(&Environment::Mainnet).expect("should get config");
+		assert_eq!(c_config.max_update_payload_bytes, 0);
+		assert_eq!(config_from_ffi(&c_config).max_update_payload_bytes, None);
+
+		c_config.max_update_payload_bytes = 512;
+		assert_eq!(config_from_ffi(&c_config).max_update_payload_bytes, Some(512));
+	}
+
+	#[test]
+	fn test_export_options_from_ffi() {
+		let options = ExportOptions {
+			page_overflow_policy: PageOverflowPolicy::SkipOverflow,
+			nonce_mode: NonceMode::Deterministic,
+			encryption_mode: EncryptionMode::MultiRecipientV1,
+		};
+
+		let rust_options = export_options_from_ffi(&options);
+		assert_eq!(
+			rust_options.page_overflow_policy,
+			dsnp_graph_core::api::api_types::PageOverflowPolicy::SkipOverflow
+		);
+		assert_eq!(
+			rust_options.nonce_mode,
+			dsnp_graph_core::api::api_types::NonceMode::Deterministic
+		);
+		assert_eq!(
+			rust_options.encryption_mode,
+			dsnp_graph_core::api::api_types::EncryptionMode::MultiRecipientV1
+		);
+
+		unsafe {
+			let result =
+				graph_set_export_options(ptr::null_mut(), &options as *const ExportOptions);
+			assert!(result.result.is_none());
+			assert!(result.error.is_some());
+			free_dsnp_graph_error(result.error.unwrap().as_ptr());
+		}
+	}
+
+	#[test]
+	fn test_chain_head_should_be_mapped_from_import_bundle_and_into_updates() {
+		// arrange
+		let mut block_hash = vec![7u8; 32];
+		let chain_head = ChainHead {
+			block_number: 42,
+			block_hash: block_hash.as_mut_ptr(),
+			block_hash_len: block_hash.len(),
+		};
+		let import_bundle = ImportBundle {
+			dsnp_user_id: 1,
+			schema_id: 1,
+			key_pairs: ptr::null_mut(),
+			key_pairs_len: 0,
+			dsnp_keys: DsnpKeys {
+				dsnp_user_id: 1,
+				keys_hash: 0,
+				keys: ptr::null_mut(),
+				keys_len: 0,
+			},
+			pages: ptr::null_mut(),
+			pages_len: 0,
+			chain_head: &chain_head as *const ChainHead,
+		};
+		let expected = dsnp_graph_core::api::api_types::ChainHead {
+			block_number: 42,
+			block_hash: block_hash.clone(),
+		};
+		let update = dsnp_graph_core::api::api_types::Update::DeletePage {
+			owner_dsnp_user_id: 1,
+			schema_id: 1,
+			page_id: 0,
+			prev_hash: 0,
+			chain_head: Some(expected.clone()),
+		};
+
+		// act
+		let rust_bundle = import_bundle_from_ffi(&import_bundle);
+		let ffi_updates = updates_to_ffi(vec![update]);
+
+		// assert
+		assert_eq!(rust_bundle.chain_head, Some(expected));
+		match &ffi_updates[0] {
+			Update::Delete(delete_page) => unsafe {
+				let chain_head = delete_page.chain_head.as_ref().expect("should map chain head");
+				assert_eq!(chain_head.block_number, 42);
+				assert_eq!(
+					std::slice::from_raw_parts(chain_head.block_hash, chain_head.block_hash_len),
+					block_hash.as_slice()
+				);
+			},
+			_ => panic!("should be a delete update"),
+		}
+	}
+
+	#[test]
+	fn test_graph_state_registry_should_isolate_states_and_recover_poisoned_shards() {
+		let registry = Arc::new(GraphStateRegistry::new());
+		let handles: Vec<usize> = (0..8)
+			.map(|_| {
+				let registry = registry.clone();
+				thread::spawn(move || {
+					(0..4)
+						.map(|_| {
+							let graph_state =
+								GraphState::new(dsnp_graph_config::Environment::Mainnet);
+							registry.insert(Box::new(graph_state)) as usize
+						})
+						.collect::<Vec<_>>()
+				})
+			})
+			.flat_map(|t| t.join().unwrap())
+			.collect();
+		assert_eq!(registry.len(), 32);
+
+		// poisoning the shard of a state should not affect later calls
+		let poisoned = handles[0];
+		let poisoning_registry = registry.clone();
+		let poisoning = thread::spawn(move || {
+			let _shard = poisoning_registry.shard(poisoned);
+			panic!("poisoning the shard");
+		});
+		assert!(poisoning.join().is_err());
+
+		assert!(registry.remove(poisoned as *const GraphState));
+		assert!(!registry.remove(poisoned as *const GraphState));
+		assert_eq!(registry.len(), 31);
+		registry.clear();
+		assert_eq!(registry.len(), 0);
+	}
+	#[test]
+	fn test_graph_state_registry_should_reject_freed_handles() {
+		let registry = GraphStateRegistry::new();
+		let handle =
+			registry.insert(Box::new(GraphState::new(dsnp_graph_config::Environment::Mainnet)));
+		let unknown = registry
+			.insert(Box::new(GraphState::new(dsnp_graph_config::Environment::Mainnet)))
+			.wrapping_add(1);
+
+		unsafe {
+			assert!(registry.get(handle).is_some());
+			assert!(registry.get(unknown).is_none());
+
+			assert!(registry.remove(handle));
+			assert!(registry.get(handle).is_none());
+		}
+		assert_eq!(registry.len(), 1);
+	}
 }
@@ -1,16 +1,40 @@
 use crate::bindings::*;
 use dsnp_graph_config::{
+	errors::{DsnpGraphError, DsnpGraphResult},
+	registry::EnvironmentRegistry,
 	Config as RustConfig, DsnpVersion, MAINNET_CONFIG, ROCOCO_CONFIG, TESTNET_PASEO_CONFIG,
 };
-use std::{collections::HashMap, mem::ManuallyDrop};
+use std::{
+	collections::HashMap,
+	ffi::{c_char, CStr},
+	mem::ManuallyDrop,
+};
 
-pub fn get_config_for_ffi(environment: &Environment) -> Config {
-	match environment {
+pub fn get_config_for_ffi(environment: &Environment) -> DsnpGraphResult<Config> {
+	Ok(match environment {
 		Environment::Mainnet => get_config_from_rust_config(&MAINNET_CONFIG),
 		Environment::Rococo => get_config_from_rust_config(&ROCOCO_CONFIG),
 		Environment::TestnetPaseo => get_config_from_rust_config(&TESTNET_PASEO_CONFIG),
 		Environment::Dev(config) => config.clone(),
+		Environment::Custom(name) => {
+			let rust_environment =
+				EnvironmentRegistry::environment(&unsafe { string_from_ffi(*name) }?)?;
+			get_config_from_rust_config(rust_environment.get_config())
+		},
+	})
+}
+
+/// Function to convert a C string to a Rust `String`
+/// # Safety
+/// `value` should be null or point to a null terminated string
+pub unsafe fn string_from_ffi(value: *const c_char) -> DsnpGraphResult<String> {
+	if value.is_null() {
+		return Err(DsnpGraphError::FFIError("String is null".to_string()))
 	}
+	CStr::from_ptr(value)
+		.to_str()
+		.map(|s| s.to_string())
+		.map_err(|e| DsnpGraphError::FFIError(format!("Invalid string: {}", e)))
 }
 
 fn get_config_from_rust_config(rust_config: &RustConfig) -> Config {
@@ -36,6 +60,7 @@ fn get_config_from_rust_config(rust_config: &RustConfig) -> Config {
 		max_graph_page_size_bytes: rust_config.max_graph_page_size_bytes,
 		max_page_id: rust_config.max_page_id,
 		max_key_page_size_bytes: rust_config.max_key_page_size_bytes,
+		max_update_payload_bytes: rust_config.max_update_payload_bytes.unwrap_or(0),
 		schema_map_len: schema_map.len(),
 		schema_map: ManuallyDrop::new(schema_map).as_mut_ptr(),
 		graph_public_key_schema_id: rust_config.graph_public_key_schema_id,
@@ -74,6 +99,11 @@ pub fn config_from_ffi(config: &Config) -> RustConfig {
 		max_graph_page_size_bytes: config.max_graph_page_size_bytes,
 		max_page_id: config.max_page_id,
 		max_key_page_size_bytes: config.max_key_page_size_bytes,
+		max_update_payload_bytes: match config.max_update_payload_bytes {
+			0 => None,
+			max_update_payload_bytes => Some(max_update_payload_bytes),
+		},
+		legacy_public_page_order: false,
 		graph_public_key_schema_id: config.graph_public_key_schema_id,
 		schema_map,
 		dsnp_versions,
@@ -81,8 +111,10 @@ pub fn config_from_ffi(config: &Config) -> RustConfig {
 }
 
 // Function to convert C-compatible `SchemaConfig` to a Rust `SchemaConfig`
-pub fn environment_from_ffi(environment: &Environment) -> dsnp_graph_config::Environment {
-	match environment {
+pub fn environment_from_ffi(
+	environment: &Environment,
+) -> DsnpGraphResult<dsnp_graph_config::Environment> {
+	Ok(match environment {
 		Environment::Mainnet => dsnp_graph_config::Environment::Mainnet,
 		Environment::Rococo => dsnp_graph_config::Environment::Rococo,
 		Environment::TestnetPaseo => dsnp_graph_config::Environment::TestnetPaseo,
@@ -90,7 +122,9 @@ pub fn environment_from_ffi(environment: &Environment) -> dsnp_graph_config::Env
 			let rust_config = config_from_ffi(config);
 			dsnp_graph_config::Environment::Dev(rust_config)
 		},
-	}
+		Environment::Custom(name) =>
+			EnvironmentRegistry::environment(&unsafe { string_from_ffi(*name) }?)?,
+	})
 }
 
 // Function to convert C-compatible `GraphKeyPair` to a Rust `GraphKeyPair`
@@ -126,20 +160,48 @@ pub fn dsnp_keys_from_ffi(dsnp_keys: &DsnpKeys) -> dsnp_graph_core::api::api_typ
 	}
 }
 
-// Function to convert C-compatible PageData to Rust PageData
-fn page_data_from_ffi(page_data: &PageData) -> dsnp_graph_core::api::api_types::PageData {
+// Function to borrow C-compatible PageData as Rust PageDataRef without copying its content
+fn page_data_from_ffi(page_data: &PageData) -> dsnp_graph_core::api::api_types::PageDataRef<'_> {
 	let content = unsafe { std::slice::from_raw_parts(page_data.content, page_data.content_len) };
-	dsnp_graph_core::api::api_types::PageData {
+	dsnp_graph_core::api::api_types::PageDataRef {
 		page_id: page_data.page_id,
-		content: content.to_vec(),
+		content,
 		content_hash: page_data.content_hash,
 	}
 }
 
-// Function to convert C-compatible ImportBundle to Rust ImportBundle
+// Function to convert C-compatible nullable ChainHead to Rust ChainHead
+fn chain_head_from_ffi(
+	chain_head: *const ChainHead,
+) -> Option<dsnp_graph_core::api::api_types::ChainHead> {
+	unsafe { chain_head.as_ref() }.map(|chain_head| {
+		let block_hash =
+			unsafe { std::slice::from_raw_parts(chain_head.block_hash, chain_head.block_hash_len) };
+		dsnp_graph_core::api::api_types::ChainHead {
+			block_number: chain_head.block_number,
+			block_hash: block_hash.to_vec(),
+		}
+	})
+}
+
+// Function to convert Rust ChainHead to C-compatible nullable ChainHead
+fn chain_head_to_ffi(
+	chain_head: Option<dsnp_graph_core::api::api_types::ChainHead>,
+) -> *mut ChainHead {
+	match chain_head {
+		Some(chain_head) => Box::into_raw(Box::new(ChainHead {
+			block_number: chain_head.block_number,
+			block_hash_len: chain_head.block_hash.len(),
+			block_hash: ManuallyDrop::new(chain_head.block_hash).as_mut_ptr(),
+		})),
+		None => std::ptr::null_mut(),
+	}
+}
+
+// Function to convert C-compatible ImportBundle to Rust ImportBundleRef borrowing the page contents
 pub fn import_bundle_from_ffi(
 	import_bundle: &ImportBundle,
-) -> dsnp_graph_core::api::api_types::ImportBundle {
+) -> dsnp_graph_core::api::api_types::ImportBundleRef<'_> {
 	let key_pairs_slice =
 		unsafe { std::slice::from_raw_parts(import_bundle.key_pairs, import_bundle.key_pairs_len) };
 	let mut key_pairs = Vec::new();
@@ -156,19 +218,20 @@ pub fn import_bundle_from_ffi(
 		pages.push(page_data_from_ffi(page_data));
 	}
 
-	dsnp_graph_core::api::api_types::ImportBundle {
+	dsnp_graph_core::api::api_types::ImportBundleRef {
 		dsnp_user_id: import_bundle.dsnp_user_id,
 		schema_id: import_bundle.schema_id,
 		key_pairs,
 		dsnp_keys: Some(dsnp_keys),
 		pages,
+		chain_head: chain_head_from_ffi(import_bundle.chain_head),
 	}
 }
 
-// Function to convert C-compatible `ImportBundle` slice to a Rust `ImportBundle` vector
+// Function to convert C-compatible `ImportBundle` slice to a Rust `ImportBundleRef` vector
 pub fn payloads_from_ffi(
 	payloads: &[ImportBundle],
-) -> Vec<dsnp_graph_core::api::api_types::ImportBundle> {
+) -> Vec<dsnp_graph_core::api::api_types::ImportBundleRef<'_>> {
 	let mut rust_payloads = Vec::new();
 	for payload in payloads {
 		let rust_payload = import_bundle_from_ffi(payload);
@@ -181,6 +244,8 @@ pub fn payloads_from_ffi(
 pub fn updates_to_ffi(updates: Vec<dsnp_graph_core::api::api_types::Update>) -> Vec<Update> {
 	let mut ffi_updates = Vec::new();
 	for update in updates {
+		let idempotency_key = update.idempotency_key();
+		let chain_head = chain_head_to_ffi(update.chain_head().cloned());
 		match update {
 			dsnp_graph_core::api::api_types::Update::PersistPage {
 				owner_dsnp_user_id,
@@ -188,6 +253,7 @@ pub fn updates_to_ffi(updates: Vec<dsnp_graph_core::api::api_types::Update>) ->
 				page_id,
 				prev_hash,
 				payload,
+				..
 			} => {
 				let ffi_persist_page = PersistPage {
 					owner_dsnp_user_id,
@@ -196,6 +262,8 @@ pub fn updates_to_ffi(updates: Vec<dsnp_graph_core::api::api_types::Update>) ->
 					prev_hash,
 					payload_len: payload.len(),
 					payload: ManuallyDrop::new(payload).as_mut_ptr(),
+					idempotency_key,
+					chain_head,
 				};
 				ffi_updates.push(Update::Persist(ffi_persist_page));
 			},
@@ -204,21 +272,31 @@ pub fn updates_to_ffi(updates: Vec<dsnp_graph_core::api::api_types::Update>) ->
 				schema_id,
 				page_id,
 				prev_hash,
+				..
 			} => {
-				let ffi_delete_page =
-					DeletePage { owner_dsnp_user_id, schema_id, page_id, prev_hash };
+				let ffi_delete_page = DeletePage {
+					owner_dsnp_user_id,
+					schema_id,
+					page_id,
+					prev_hash,
+					idempotency_key,
+					chain_head,
+				};
 				ffi_updates.push(Update::Delete(ffi_delete_page));
 			},
 			dsnp_graph_core::api::api_types::Update::AddKey {
 				owner_dsnp_user_id,
 				prev_hash,
 				payload,
+				..
 			} => {
 				let ffi_add_key = AddKey {
 					owner_dsnp_user_id,
 					prev_hash,
 					payload_len: payload.len(),
 					payload: ManuallyDrop::new(payload).as_mut_ptr(),
+					idempotency_key,
+					chain_head,
 				};
 				ffi_updates.push(Update::Add(ffi_add_key));
 			},
@@ -231,7 +309,13 @@ pub fn actions_from_ffi(actions: &[Action]) -> Vec<dsnp_graph_core::api::api_typ
 	let mut rust_actions = Vec::new();
 	for action in actions {
 		match action {
-			Action::Connect { owner_dsnp_user_id, connection, dsnp_keys } => {
+			Action::Connect {
+				owner_dsnp_user_id,
+				connection,
+				dsnp_keys,
+				preferred_page_id,
+				since,
+			} => {
 				let rust_action = dsnp_graph_core::api::api_types::Action::Connect {
 					owner_dsnp_user_id: *owner_dsnp_user_id,
 					connection: connection.clone(),
@@ -239,6 +323,8 @@ pub fn actions_from_ffi(actions: &[Action]) -> Vec<dsnp_graph_core::api::api_typ
 						Some(keys) => Some(dsnp_keys_from_ffi(keys)),
 						None => None,
 					},
+					preferred_page_id: unsafe { preferred_page_id.as_ref() }.copied(),
+					since: unsafe { since.as_ref() }.copied(),
 				};
 				rust_actions.push(rust_action);
 			},
@@ -258,11 +344,58 @@ pub fn actions_from_ffi(actions: &[Action]) -> Vec<dsnp_graph_core::api::api_typ
 				};
 				rust_actions.push(rust_action);
 			},
+			Action::ConnectMutual { user_a, user_b, schema_id, since } => {
+				let rust_action = dsnp_graph_core::api::api_types::Action::ConnectMutual {
+					user_a: *user_a,
+					user_b: *user_b,
+					schema_id: *schema_id,
+					since: unsafe { since.as_ref() }.copied(),
+				};
+				rust_actions.push(rust_action);
+			},
+			Action::MigrateConnection {
+				owner_dsnp_user_id,
+				from_schema_id,
+				to_schema_id,
+				dsnp_user_id,
+				since,
+			} => {
+				let rust_action = dsnp_graph_core::api::api_types::Action::MigrateConnection {
+					owner_dsnp_user_id: *owner_dsnp_user_id,
+					from_schema_id: *from_schema_id,
+					to_schema_id: *to_schema_id,
+					dsnp_user_id: *dsnp_user_id,
+					since: unsafe { since.as_ref() }.copied(),
+				};
+				rust_actions.push(rust_action);
+			},
 		}
 	}
 	rust_actions
 }
 
+pub fn export_options_from_ffi(
+	export_options: &ExportOptions,
+) -> dsnp_graph_core::api::api_types::ExportOptions {
+	dsnp_graph_core::api::api_types::ExportOptions {
+		page_overflow_policy: match export_options.page_overflow_policy {
+			PageOverflowPolicy::Error => dsnp_graph_core::api::api_types::PageOverflowPolicy::Error,
+			PageOverflowPolicy::SkipOverflow =>
+				dsnp_graph_core::api::api_types::PageOverflowPolicy::SkipOverflow,
+		},
+		nonce_mode: match export_options.nonce_mode {
+			NonceMode::Random => dsnp_graph_core::api::api_types::NonceMode::Random,
+			NonceMode::Deterministic => dsnp_graph_core::api::api_types::NonceMode::Deterministic,
+		},
+		encryption_mode: match export_options.encryption_mode {
+			EncryptionMode::SingleRecipient =>
+				dsnp_graph_core::api::api_types::EncryptionMode::SingleRecipient,
+			EncryptionMode::MultiRecipientV1 =>
+				dsnp_graph_core::api::api_types::EncryptionMode::MultiRecipientV1,
+		},
+	}
+}
+
 pub fn dsnp_public_keys_to_ffi(
 	keys: Vec<dsnp_graph_core::dsnp::dsnp_types::DsnpPublicKey>,
 ) -> Vec<DsnpPublicKey> {
@@ -1,32 +1,53 @@
 use crate::{
 	errors::SdkJniError,
-	helper::{handle_result, validate_handle},
+	helper::{get_graph_state, handle_result},
 	mappings::{
-		convert_jboolean, map_to_actions, map_to_dsnp_keys, map_to_environment, map_to_imports,
-		serialize_config, serialize_dsnp_users, serialize_graph_edges, serialize_graph_keypair,
-		serialize_graph_updates, serialize_public_keys,
+		convert_jboolean, map_to_actions, map_to_config, map_to_dsnp_keys, map_to_environment,
+		map_to_imports, map_to_key_ids, serialize_config, serialize_dsnp_users,
+		serialize_graph_edges, serialize_graph_keypair, serialize_graph_updates,
+		serialize_key_page_stats, serialize_public_keys, serialize_schema_config,
 	},
 };
-use dsnp_graph_config::{DsnpUserId, GraphKeyType, SchemaId};
+use dsnp_graph_config::{
+	registry::EnvironmentRegistry, DsnpUserId, Environment, GraphKeyType, SchemaConfig, SchemaId,
+};
 use dsnp_graph_core::{
 	api::api::{GraphAPI, GraphState},
 	util::transactional_hashmap::Transactional,
 };
 use jni::{
-	objects::{JByteArray, JClass, JObject, JString},
+	objects::{JByteArray, JClass, JIntArray, JLongArray, JObject, JString, JValue},
 	sys::{jboolean, jint, jlong},
 	JNIEnv,
 };
 use std::{
-	ops::{Deref, DerefMut},
+	collections::BTreeMap,
 	panic,
-	sync::RwLock,
+	sync::{
+		atomic::{AtomicI64, Ordering},
+		Arc, Mutex, RwLock,
+	},
 };
 
 pub type SdkJniResult<V> = Result<V, SdkJniError>;
 
-// Collection of GraphStates memory locations
-static GRAPH_STATES_MEMORY_LOCATIONS: RwLock<Vec<jlong>> = RwLock::new(Vec::new());
+fn add_graph_state(environment: Environment) -> SdkJniResult<jlong> {
+	let graph_state = Arc::new(Mutex::new(GraphState::try_new(environment)?));
+	let mut graph_states = GRAPH_STATES.write().map_err(|_| SdkJniError::LockError)?;
+
+	let handle = NEXT_GRAPH_STATE_HANDLE.fetch_add(1, Ordering::SeqCst);
+	graph_states.insert(handle, graph_state);
+	Ok(handle)
+}
+
+/// Map of GraphState handles to their shared states
+pub type GraphStates = BTreeMap<jlong, Arc<Mutex<GraphState>>>;
+
+// Collection of GraphStates keyed by their handles
+static GRAPH_STATES: RwLock<GraphStates> = RwLock::new(BTreeMap::new());
+
+// Counter used to assign a unique handle to every initialized GraphState
+static NEXT_GRAPH_STATE_HANDLE: AtomicI64 = AtomicI64::new(1);
 
 #[no_mangle]
 pub extern "C" fn Java_io_projectliberty_graphsdk_Native_hello<'local>(
@@ -54,8 +75,8 @@ pub extern "C" fn Java_io_projectliberty_graphsdk_Native_keepAlive<'local>(
 }
 
 /// Initializes the graph state and returns a handle to it.
-/// The handle is a pointer to the memory location of the state.
-/// The memory will be freed when `freeGraphState` is called.
+/// The handle is an opaque identifier of the state inside the handle table.
+/// The state will be dropped when `freeGraphState` is called and no other call is using it.
 /// # Arguments
 /// * `environment` - the environment to initialize the graph state with
 /// # Returns
@@ -70,15 +91,56 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_initializeGraphS
 ) -> jlong {
 	let result = panic::catch_unwind(|| {
 		let rust_environment = map_to_environment(&env, &environment)?;
-		let graph_state = GraphState::new(rust_environment);
-		let boxed = Box::new(graph_state);
-		let mut graph_states =
-			GRAPH_STATES_MEMORY_LOCATIONS.write().map_err(|_| SdkJniError::LockError)?;
-
-		// graph state memory will be handled manually after following line execution
-		let handle = Box::into_raw(boxed) as jlong;
-		graph_states.push(handle);
-		Ok(handle)
+		add_graph_state(rust_environment)
+	});
+	handle_result(&mut env, result)
+}
+
+/// Initializes a new graph state for a custom environment.
+/// # Arguments
+/// * `name` - the name the environment config is registered with
+/// # Returns
+/// * `jlong` - the handle to the graph state
+/// # Errors
+/// * `SdkJniError` - if no config is registered with the given name
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_initializeCustomGraphState<
+	'local,
+>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	name: JString<'local>,
+) -> jlong {
+	let name = env.get_string(&name).map(String::from);
+	let result = panic::catch_unwind(|| {
+		let name = name.map_err(|e| SdkJniError::from(e))?;
+		let rust_environment = EnvironmentRegistry::environment(&name)?;
+		add_graph_state(rust_environment)
+	});
+	handle_result(&mut env, result)
+}
+
+/// Registers a config to be used by custom environments with the given name.
+/// # Arguments
+/// * `name` - the name of the environment
+/// * `config` - the serialized config
+/// # Errors
+/// * `SdkJniError` - if the name or config is invalid
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_registerEnvironmentConfig<
+	'local,
+>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	name: JString<'local>,
+	config: JByteArray,
+) {
+	let name = env.get_string(&name).map(String::from);
+	let result = panic::catch_unwind(|| {
+		let name = name.map_err(|e| SdkJniError::from(e))?;
+		let rust_config = map_to_config(&env, &config)?;
+		EnvironmentRegistry::register(&name, rust_config)?;
+		Ok(())
 	});
 	handle_result(&mut env, result)
 }
@@ -98,16 +160,12 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_freeGraphState<'
 		if handle == 0 {
 			return Err(SdkJniError::InvalidHandle("is null"));
 		}
-		let mut graph_states =
-			GRAPH_STATES_MEMORY_LOCATIONS.write().map_err(|_| SdkJniError::LockError)?;
-		let index = graph_states
-			.iter()
-			.position(|x| *x == handle)
-			.ok_or(SdkJniError::InvalidHandle("does not exist"))?;
-		graph_states.remove(index);
+		let mut graph_states = GRAPH_STATES.write().map_err(|_| SdkJniError::LockError)?;
 
-		// following line frees the allocated memory for state
-		let _ = unsafe { Box::from_raw(handle as *mut GraphState) };
+		// state is dropped as soon as the last in-flight call using it releases its reference
+		graph_states
+			.remove(&handle)
+			.ok_or(SdkJniError::InvalidHandle("does not exist"))?;
 		Ok(())
 	});
 	handle_result(&mut env, result);
@@ -135,6 +193,42 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getConfig<'local
 	handle_result(&mut env, result)
 }
 
+/// Get the connection type, including its privacy type, and the DSNP version of a schema id.
+/// # Arguments
+/// * `environment` - the environment whose config is used
+/// * `schema_id` - the schema id to resolve
+/// # Returns
+/// * `jbyteArray` - the serialized schema config
+/// # Errors
+/// * `SdkJniError` - if the schema id is not part of the config
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getConnectionTypeFromSchemaId<
+	'local,
+>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	environment: JByteArray,
+	schema_id: jint,
+) -> JByteArray<'local> {
+	let result = panic::catch_unwind(|| {
+		let rust_environment = map_to_environment(&env, &environment)?;
+		let schema_id = SchemaId::try_from(schema_id)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid schema_id"))?;
+		let config = rust_environment.get_config();
+		let schema_config = SchemaConfig {
+			connection_type: config
+				.get_connection_type_from_schema_id(schema_id)
+				.ok_or_else(|| rust_environment.invalid_schema_id(schema_id))?,
+			dsnp_version: config
+				.get_dsnp_version_from_schema_id(schema_id)
+				.ok_or_else(|| rust_environment.invalid_schema_id(schema_id))?,
+			page_capacity: config.get_page_capacity_from_schema_id(schema_id),
+		};
+		serialize_schema_config(&env, &schema_config)
+	});
+	handle_result(&mut env, result)
+}
+
 /// Check if user graph exists.
 /// # Arguments
 /// * `handle` - the handle to the graph state
@@ -151,18 +245,46 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_containsUserGrap
 	dsnp_user_id: jlong,
 ) -> jboolean {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 		// TODO: test edge case that dsnp_user_id is bigger than i64
 		let user_id = u64::try_from(dsnp_user_id)
 			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
 
-		// locking to read from state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.read().map_err(|_| SdkJniError::LockError)?;
-		let graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		let result = graph.deref().contains_user_graph(&user_id).into();
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		let result = graph.contains_user_graph(&user_id).into();
+		Ok(result)
+	});
+	handle_result(&mut env, result)
+}
 
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
+/// Check if user graph can be exported.
+/// # Arguments
+/// * `handle` - the handle to the graph state
+/// * `dsnp_user_id` - the dsnp user id
+/// * `schema_id` - the schema id of the graph
+/// # Returns
+/// * `jboolean` - true if user graph is writable, false if it is read-only or not imported
+/// # Errors
+/// * `SdkJniError` - if checking user graph fails
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_isUserGraphWritable<'local>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	handle: jlong,
+	dsnp_user_id: jlong,
+	schema_id: jint,
+) -> jboolean {
+	let result = panic::catch_unwind(|| {
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
+		let dsnp_user_id = DsnpUserId::try_from(dsnp_user_id)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
+		let schema_id = SchemaId::try_from(schema_id)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid schema_id"))?;
+
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		let result = graph.is_user_graph_writable(&dsnp_user_id, &schema_id).into();
 		Ok(result)
 	});
 	handle_result(&mut env, result)
@@ -182,15 +304,11 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getGraphUsersLen
 	handle: jlong,
 ) -> jint {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 
-		// locking to read from state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.read().map_err(|_| SdkJniError::LockError)?;
-		let graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		let result = graph.deref().len() as jint;
-
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		let result = graph.len() as jint;
 		Ok(result)
 	});
 	handle_result(&mut env, result)
@@ -210,17 +328,13 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_removeUserGraph<
 	dsnp_user_id: jlong,
 ) {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 		let user_id = u64::try_from(dsnp_user_id)
 			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
 
-		// locking to write in state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.write().map_err(|_| SdkJniError::LockError)?;
-		let mut graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		graph.deref_mut().remove_user_graph(&user_id);
-
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
+		// locking the state for the duration of this call
+		let mut graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph.remove_user_graph(&user_id);
 		Ok(())
 	});
 	handle_result(&mut env, result)
@@ -240,21 +354,12 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_importUserData<'
 	imports: JByteArray,
 ) {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 		let rust_imports = map_to_imports(&env, &imports)?;
 
-		// locking to write in state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.write().map_err(|_| SdkJniError::LockError)?;
-		let mut graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		let result = graph
-			.deref_mut()
-			.import_users_data(&rust_imports)
-			.map_err(|e| SdkJniError::from(e));
-
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
-		result
+		// locking the state for the duration of this call
+		let mut graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph.import_users_data(&rust_imports).map_err(|e| SdkJniError::from(e))
 	});
 	handle_result(&mut env, result)
 }
@@ -273,21 +378,14 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_exportUpdates<'l
 	handle: jlong,
 ) -> JByteArray<'local> {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
-
-		// locking to read from state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.read().map_err(|_| SdkJniError::LockError)?;
-		let graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		let result = graph
-			.deref()
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
+
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
 			.export_updates()
 			.map_err(|e| SdkJniError::from(e))
-			.and_then(|updates| serialize_graph_updates(&env, &updates));
-
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
-		result
+			.and_then(|updates| serialize_graph_updates(&env, &updates))
 	});
 	handle_result(&mut env, result)
 }
@@ -308,23 +406,16 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_exportUserGraphU
 	dsnp_user_id: jlong,
 ) -> JByteArray<'local> {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 		let dsnp_user_id = DsnpUserId::try_from(dsnp_user_id)
 			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
 
-		// locking to read from state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.read().map_err(|_| SdkJniError::LockError)?;
-		let graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		let result = graph
-			.deref()
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
 			.export_user_graph_updates(&dsnp_user_id)
 			.map_err(|e| SdkJniError::from(e))
-			.and_then(|updates| serialize_graph_updates(&env, &updates));
-
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
-		result
+			.and_then(|updates| serialize_graph_updates(&env, &updates))
 	});
 	handle_result(&mut env, result)
 }
@@ -343,21 +434,89 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_applyActions<'lo
 	actions: JByteArray,
 ) {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
+		let (actions, options) = map_to_actions(&env, &actions)?;
+
+		// locking the state for the duration of this call
+		let mut graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph.apply_actions(&actions, &options).map_err(|e| SdkJniError::from(e))
+	});
+	handle_result(&mut env, result)
+}
+
+/// Apply actions to graph state and report the progress to a listener.
+/// # Arguments
+/// * `handle` - the handle to the graph state
+/// * `actions` - the serialized actions
+/// * `listener` - a `ProgressListener` called on the calling thread with the fraction of processed
+/// actions after each processed chunk, an exception thrown by it is cleared and stops further calls
+/// # Errors
+/// * `SdkJniError` - if applying actions fails
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_applyActionsWithProgress<'local>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	handle: jlong,
+	actions: JByteArray,
+	listener: JObject,
+) {
+	let result = panic::catch_unwind(|| {
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 		let (actions, options) = map_to_actions(&env, &actions)?;
 
-		// locking to write in state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.write().map_err(|_| SdkJniError::LockError)?;
-		let mut graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		let result = graph
-			.deref_mut()
-			.apply_actions(&actions, &options)
-			.map_err(|e| SdkJniError::from(e));
-
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
-		result
+		// the listener is called on this thread while `env` is borrowed by this closure
+		let mut listener_env = env.unsafe_clone();
+		let mut listener_failed = listener.is_null();
+		let mut report_progress = |fraction: f64| {
+			if listener_failed {
+				return
+			}
+			let result = listener_env.call_method(
+				&listener,
+				"onProgress",
+				"(D)V",
+				&[JValue::Double(fraction)],
+			);
+			if result.is_err() || listener_env.exception_check().unwrap_or(true) {
+				let _ = listener_env.exception_clear();
+				listener_failed = true;
+			}
+		};
+
+		// locking the state for the duration of this call
+		let mut graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
+			.apply_actions_with_progress(&actions, &options, &mut report_progress)
+			.map_err(|e| SdkJniError::from(e))
+	});
+	handle_result(&mut env, result)
+}
+
+/// Apply actions to graph state and export the updates in a single call.
+/// # Arguments
+/// * `handle` - the handle to the graph state
+/// * `actions` - the serialized actions
+/// # Returns
+/// * `jbyteArray` - the serialized updates
+/// # Errors
+/// * `SdkJniError` - if applying actions or exporting updates fails
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_applyActionsAndExport<'local>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	handle: jlong,
+	actions: JByteArray,
+) -> JByteArray<'local> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
+		let (actions, options) = map_to_actions(&env, &actions)?;
+
+		// locking the state for the duration of this call
+		let mut graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
+			.apply_actions_and_export(&actions, &options)
+			.map_err(|e| SdkJniError::from(e))
+			.and_then(|updates| serialize_graph_updates(&env, &updates))
 	});
 	handle_result(&mut env, result)
 }
@@ -375,16 +534,11 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_commit<'local>(
 	handle: jlong,
 ) {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 
-		// locking to write in state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.write().map_err(|_| SdkJniError::LockError)?;
-		let mut graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		graph.deref_mut().commit();
-
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
+		// locking the state for the duration of this call
+		let mut graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph.commit();
 		Ok(())
 	});
 	handle_result(&mut env, result)
@@ -403,16 +557,11 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_rollback<'local>
 	handle: jlong,
 ) {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
-
-		// locking to write in state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.write().map_err(|_| SdkJniError::LockError)?;
-		let mut graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		graph.deref_mut().rollback();
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
+		// locking the state for the duration of this call
+		let mut graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph.rollback();
 		Ok(())
 	});
 	handle_result(&mut env, result)
@@ -434,23 +583,46 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_forceCalculateGr
 	dsnp_user_id: jlong,
 ) -> JByteArray<'local> {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 		let dsnp_user_id = DsnpUserId::try_from(dsnp_user_id)
 			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
 
-		// locking to read from state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.read().map_err(|_| SdkJniError::LockError)?;
-		let graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		let result = graph
-			.deref()
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
 			.force_recalculate_graphs(&dsnp_user_id)
 			.map_err(|e| SdkJniError::from(e))
-			.and_then(|updates| serialize_graph_updates(&env, &updates));
+			.and_then(|updates| serialize_graph_updates(&env, &updates))
+	});
+	handle_result(&mut env, result)
+}
 
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
-		result
+/// Export removal of all graph pages of a user.
+/// # Arguments
+/// * `handle` - the handle to the graph state
+/// * `dsnp_user_id` - the user id to remove the graph of
+/// # Returns
+/// * `jbyteArray` - the serialized updates
+/// # Errors
+/// * `SdkJniError` - if exporting the removal fails
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_exportGraphRemoval<'local>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	handle: jlong,
+	dsnp_user_id: jlong,
+) -> JByteArray<'local> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
+		let dsnp_user_id = DsnpUserId::try_from(dsnp_user_id)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
+
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
+			.export_graph_removal(&dsnp_user_id)
+			.map_err(|e| SdkJniError::from(e))
+			.and_then(|updates| serialize_graph_updates(&env, &updates))
 	});
 	handle_result(&mut env, result)
 }
@@ -477,7 +649,7 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getConnectionsFo
 	include_pending: jboolean,
 ) -> JByteArray<'local> {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 		let dsnp_user_id = DsnpUserId::try_from(dsnp_user_id)
 			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
 		let schema_id = SchemaId::try_from(schema_id)
@@ -485,19 +657,12 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getConnectionsFo
 		let include_pending = convert_jboolean(include_pending)
 			.map_err(|_| SdkJniError::BadJniParameter("invalid include_pending"))?;
 
-		// locking to read from state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.read().map_err(|_| SdkJniError::LockError)?;
-		let graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		let result = graph
-			.deref()
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
 			.get_connections_for_user_graph(&dsnp_user_id, &schema_id, include_pending)
 			.map_err(|e| SdkJniError::from(e))
-			.and_then(|graph_edges| serialize_graph_edges(&env, &graph_edges));
-
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
-		result
+			.and_then(|graph_edges| serialize_graph_edges(&env, &graph_edges))
 	});
 	handle_result(&mut env, result)
 }
@@ -505,6 +670,7 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getConnectionsFo
 /// Get users connections without keys.
 /// # Arguments
 /// * `handle` - the handle to the graph state
+/// * `include_pending` - whether to include pending connections
 /// # Returns
 /// * `jbyteArray` - the serialized users
 /// # Errors
@@ -514,23 +680,60 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getUsersWithoutK
 	mut env: JNIEnv<'local>,
 	_class: JClass<'local>,
 	handle: jlong,
+	include_pending: jboolean,
 ) -> JByteArray<'local> {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
-
-		// locking to read from state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.read().map_err(|_| SdkJniError::LockError)?;
-		let graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		let result = graph
-			.deref()
-			.get_connections_without_keys()
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
+		let include_pending = convert_jboolean(include_pending)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid include_pending"))?;
+
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
+			.get_connections_without_keys(include_pending)
 			.map_err(|e| SdkJniError::from(e))
-			.and_then(|dsnp_users| serialize_dsnp_users(&env, &dsnp_users));
+			.and_then(|dsnp_users| serialize_dsnp_users(&env, &dsnp_users))
+	});
+	handle_result(&mut env, result)
+}
 
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
-		result
+/// Get a page of users connections without keys, sorted by dsnp user id.
+/// # Arguments
+/// * `handle` - the handle to the graph state
+/// * `include_pending` - whether to include pending connections
+/// * `cursor` - the last dsnp user id of the previous page, or a negative value for the first page
+/// * `limit` - maximum number of returned users, or 0 for no limit
+/// # Returns
+/// * `jbyteArray` - the serialized users
+/// # Errors
+/// * `SdkJniError` - if getting users fails
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getUsersWithoutKeysPage<'local>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	handle: jlong,
+	include_pending: jboolean,
+	cursor: jlong,
+	limit: jint,
+) -> JByteArray<'local> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
+		let include_pending = convert_jboolean(include_pending)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid include_pending"))?;
+		let cursor = DsnpUserId::try_from(cursor).ok();
+		let limit = match usize::try_from(limit)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid limit"))?
+		{
+			0 => None,
+			limit => Some(limit),
+		};
+
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
+			.get_connections_without_keys_page(include_pending, cursor, limit)
+			.map_err(|e| SdkJniError::from(e))
+			.and_then(|dsnp_users| serialize_dsnp_users(&env, &dsnp_users))
 	});
 	handle_result(&mut env, result)
 }
@@ -539,6 +742,7 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getUsersWithoutK
 /// # Arguments
 /// * `handle` - the handle to the graph state
 /// * `dsnp_user_id` - the user id to get connections for
+/// * `include_pending` - whether to include pending connections
 /// # Returns
 /// * `jbyteArray` - the serialized connections
 /// # Errors
@@ -551,25 +755,21 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getOneSidedPriva
 	_class: JClass<'local>,
 	handle: jlong,
 	dsnp_user_id: jlong,
+	include_pending: jboolean,
 ) -> JByteArray<'local> {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 		let user_id = u64::try_from(dsnp_user_id)
 			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
+		let include_pending = convert_jboolean(include_pending)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid include_pending"))?;
 
-		// locking to read from state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.read().map_err(|_| SdkJniError::LockError)?;
-		let graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		let result = graph
-			.deref()
-			.get_one_sided_private_friendship_connections(&user_id)
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
+			.get_one_sided_private_friendship_connections(&user_id, include_pending)
 			.map_err(|e| SdkJniError::from(e))
-			.and_then(|graph_edges| serialize_graph_edges(&env, &graph_edges));
-
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
-		result
+			.and_then(|graph_edges| serialize_graph_edges(&env, &graph_edges))
 	});
 	handle_result(&mut env, result)
 }
@@ -590,23 +790,104 @@ pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getPublicKeys<'l
 	dsnp_user_id: jlong,
 ) -> JByteArray<'local> {
 	let result = panic::catch_unwind(|| {
-		validate_handle(&GRAPH_STATES_MEMORY_LOCATIONS, handle)?;
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
 		let user_id = u64::try_from(dsnp_user_id)
 			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
 
-		// locking to read from state
-		let _lock = GRAPH_STATES_MEMORY_LOCATIONS.read().map_err(|_| SdkJniError::LockError)?;
-		let graph = unsafe { Box::from_raw(handle as *mut GraphState) };
-		// do not use `?` here to handle the error since it would drop the memory
-		let result = graph
-			.deref()
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph
 			.get_public_keys(&user_id)
 			.map_err(|e| SdkJniError::from(e))
-			.and_then(|public_keys| serialize_public_keys(&env, &public_keys));
+			.and_then(|public_keys| serialize_public_keys(&env, &public_keys))
+	});
+	handle_result(&mut env, result)
+}
 
-		// pulling out of the box as raw so that memory stays allocated
-		let _ = Box::into_raw(graph) as jlong;
-		result
+/// Get key count and key page utilization for a user.
+/// # Arguments
+/// * `handle` - the handle to the graph state
+/// * `dsnp_user_id` - the user id to get key page stats for
+/// # Returns
+/// * `jintArray` - key count, used bytes and max bytes of the key page
+/// # Errors
+/// * `SdkJniError` - if getting key page stats fails
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getKeyPageStats<'local>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	handle: jlong,
+	dsnp_user_id: jlong,
+) -> JIntArray<'local> {
+	let result = panic::catch_unwind(|| {
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
+		let user_id = u64::try_from(dsnp_user_id)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
+
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		let stats = graph.get_key_page_stats(&user_id)?;
+		serialize_key_page_stats(&env, &stats)
+	});
+	handle_result(&mut env, result)
+}
+
+/// Get the key id the next published key of a user will be assigned.
+/// # Arguments
+/// * `handle` - the handle to the graph state
+/// * `dsnp_user_id` - the user id to get the next key id for
+/// # Returns
+/// * `jlong` - the next key id
+/// # Errors
+/// * `SdkJniError` - if getting next key id fails
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_getNextKeyId<'local>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	handle: jlong,
+	dsnp_user_id: jlong,
+) -> jlong {
+	let result = panic::catch_unwind(|| {
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
+		let user_id = u64::try_from(dsnp_user_id)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
+
+		// locking the state for the duration of this call
+		let graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		let key_id = graph.get_next_key_id(&user_id)?;
+		jlong::try_from(key_id).map_err(|_| SdkJniError::UnexpectedResponse("invalid key id"))
+	});
+	handle_result(&mut env, result)
+}
+
+/// Select published keys of a user that private pages are sealed to in addition to the active key
+/// when exported using multi-recipient encryption.
+/// # Arguments
+/// * `handle` - the handle to the graph state
+/// * `dsnp_user_id` - the user id to set the additional recipients for
+/// * `key_ids` - the ids of imported public keys of the user, empty removes the recipients
+/// # Errors
+/// * `SdkJniError` - if a key id is not among the imported public keys of the user
+#[no_mangle]
+pub unsafe extern "C" fn Java_io_projectliberty_graphsdk_Native_setAdditionalRecipientKeys<
+	'local,
+>(
+	mut env: JNIEnv<'local>,
+	_class: JClass<'local>,
+	handle: jlong,
+	dsnp_user_id: jlong,
+	key_ids: JLongArray,
+) {
+	let result = panic::catch_unwind(|| {
+		let graph_state = get_graph_state(&GRAPH_STATES, handle)?;
+		let user_id = u64::try_from(dsnp_user_id)
+			.map_err(|_| SdkJniError::BadJniParameter("invalid dsnp_user_id"))?;
+		let key_ids = map_to_key_ids(&env, &key_ids)?;
+
+		// locking the state for the duration of this call
+		let mut graph = graph_state.lock().map_err(|_| SdkJniError::LockError)?;
+		graph.set_additional_recipient_keys(&user_id, &key_ids)?;
+		Ok(())
 	});
 	handle_result(&mut env, result)
 }
@@ -1,13 +1,14 @@
 use crate::{
-	api::SdkJniResult,
+	api::{GraphStates, SdkJniResult},
 	errors::{throw_exception, SdkJniError},
 };
+use dsnp_graph_core::api::api::GraphState;
 use jni::{
-	objects::JByteArray,
+	objects::{JByteArray, JIntArray},
 	sys::{jboolean, jint, jlong, JNI_FALSE},
 	JNIEnv,
 };
-use std::sync::RwLock;
+use std::sync::{Arc, Mutex, RwLock};
 
 #[inline(always)]
 pub fn handle_result<R, E>(env: &mut JNIEnv, result: Result<SdkJniResult<R>, E>) -> R
@@ -29,15 +30,18 @@ where
 }
 
 #[inline(always)]
-pub fn validate_handle(states: &RwLock<Vec<jlong>>, handle: jlong) -> SdkJniResult<()> {
+pub fn get_graph_state(
+	states: &RwLock<GraphStates>,
+	handle: jlong,
+) -> SdkJniResult<Arc<Mutex<GraphState>>> {
 	if handle == 0 {
 		return Err(SdkJniError::InvalidHandle("is null"))
 	}
 	let graph_states = states.read().map_err(|_| SdkJniError::LockError)?;
-	if !graph_states.contains(&handle) {
-		return Err(SdkJniError::InvalidHandle("does not exist"))
-	}
-	Ok(())
+	graph_states
+		.get(&handle)
+		.cloned()
+		.ok_or(SdkJniError::InvalidHandle("does not exist"))
 }
 
 /// Provides a return value when an exception is thrown.
@@ -72,3 +76,9 @@ impl JniReturnValue for JByteArray<'_> {
 		JByteArray::default()
 	}
 }
+
+impl JniReturnValue for JIntArray<'_> {
+	fn default_value() -> Self {
+		JIntArray::default()
+	}
+}
@@ -9,10 +9,12 @@ use dsnp_graph_config::{
 };
 use dsnp_graph_core::{
 	api::api_types::{
-		Action as RustAction, ActionOptions as RustActionOptions, Connection as RustConnection,
-		ConnectionType as RustConnectionType, DsnpKeys as RustDsnpKeys,
-		GraphKeyPair as RustGraphKeyPair, ImportBundle as RustImportBundle, KeyData as RustKeyData,
-		PageData as RustPageData, PrivacyType as RustPrivacyType, Update as RustUpdate,
+		Action as RustAction, ActionOptions as RustActionOptions, ChainHead as RustChainHead,
+		Connection as RustConnection, ConnectionType as RustConnectionType,
+		DsnpKeys as RustDsnpKeys, GraphKeyPair as RustGraphKeyPair,
+		ImportBundle as RustImportBundle, KeyData as RustKeyData, KeyPageStats as RustKeyPageStats,
+		PageData as RustPageData, PrivacyType as RustPrivacyType,
+		TimestampPolicy as RustTimestampPolicy, Update as RustUpdate,
 	},
 	dsnp::dsnp_types::{DsnpGraphEdge as RustDsnpGraphEdge, DsnpPublicKey as RustDsnpPublicKey},
 };
@@ -24,12 +26,12 @@ use dsnp_graph_sdk_common::proto_types::{
 	},
 };
 use jni::{
-	objects::JByteArray,
-	sys::{jboolean, JNI_FALSE, JNI_TRUE},
+	objects::{JByteArray, JIntArray, JLongArray},
+	sys::{jboolean, jint, jlong, JNI_FALSE, JNI_TRUE},
 	JNIEnv,
 };
-use protobuf::{EnumOrUnknown, Message, SpecialFields};
-use std::collections::HashMap;
+use protobuf::{EnumOrUnknown, Message, MessageField, SpecialFields};
+use std::{collections::HashMap, num::NonZeroU32};
 
 pub fn map_to_environment(
 	env: &JNIEnv<'_>,
@@ -58,6 +60,13 @@ pub fn map_to_environment(
 	Ok(result)
 }
 
+pub fn map_to_config(env: &JNIEnv<'_>, config: &JByteArray) -> SdkJniResult<RustConfig> {
+	let bytes = env.convert_byte_array(config).map_err(|e| SdkJniError::from(e))?;
+	let config_proto =
+		proto_output::Config::parse_from_bytes(&bytes).map_err(|e| SdkJniError::from(e))?;
+	map_config_to_rust(config_proto)
+}
+
 pub fn map_to_actions(
 	env: &JNIEnv<'_>,
 	actions: &JByteArray,
@@ -75,6 +84,10 @@ pub fn map_to_actions(
 			ignore_existing_connections: options.ignore_existing_connections,
 			ignore_missing_connections: options.ignore_missing_connections,
 			disable_auto_commit: options.disable_auto_commit,
+			collect_errors: options.collect_errors,
+			timestamp_policy: map_timestamp_policy_to_rust(options.timestamp_policy)?,
+			max_batch_size: NonZeroU32::new(options.max_batch_size),
+			..RustActionOptions::default()
 		}),
 		None => None,
 	};
@@ -97,6 +110,7 @@ pub fn map_to_imports(
 			dsnp_keys: map_dsnp_keys_to_rust(&i.dsnp_keys.into_option())?,
 			key_pairs: map_graph_key_pairs_to_rust(&i.key_pairs)?,
 			pages: map_page_datas_to_rust(&i.pages)?,
+			chain_head: map_chain_head_to_rust(&i.chain_head.into_option()),
 		});
 	}
 	Ok(result)
@@ -112,6 +126,19 @@ pub fn map_to_dsnp_keys(
 	map_dsnp_keys_to_rust(&Some(dsnp_keys_proto))
 }
 
+pub fn map_to_key_ids(env: &JNIEnv<'_>, key_ids: &JLongArray) -> SdkJniResult<Vec<u64>> {
+	let len = env.get_array_length(key_ids).map_err(|e| SdkJniError::from(e))?;
+	let mut values: Vec<jlong> = vec![0; len as usize];
+	env.get_long_array_region(key_ids, 0, &mut values)
+		.map_err(|e| SdkJniError::from(e))?;
+	values
+		.into_iter()
+		.map(|key_id| {
+			u64::try_from(key_id).map_err(|_| SdkJniError::BadJniParameter("invalid key id"))
+		})
+		.collect()
+}
+
 pub fn serialize_graph_keypair<'local>(
 	env: &JNIEnv<'local>,
 	key_pair: &dsnp_graph_core::api::api_types::GraphKeyPair,
@@ -130,6 +157,23 @@ pub fn serialize_graph_keypair<'local>(
 	Ok(arr)
 }
 
+pub fn serialize_key_page_stats<'local>(
+	env: &JNIEnv<'local>,
+	stats: &RustKeyPageStats,
+) -> SdkJniResult<JIntArray<'local>> {
+	let mut values = vec![];
+	for value in [stats.key_count, stats.used_bytes, stats.max_bytes] {
+		values.push(
+			jint::try_from(value)
+				.map_err(|_| SdkJniError::UnexpectedResponse("key page stats out of range"))?,
+		);
+	}
+
+	let arr = env.new_int_array(values.len() as jint).map_err(|e| SdkJniError::from(e))?;
+	env.set_int_array_region(&arr, 0, &values).map_err(|e| SdkJniError::from(e))?;
+	Ok(arr)
+}
+
 pub fn serialize_public_keys<'local>(
 	env: &JNIEnv<'local>,
 	public_keys: &[RustDsnpPublicKey],
@@ -193,7 +237,7 @@ pub fn serialize_config<'local>(
 	config: &RustConfig,
 ) -> SdkJniResult<JByteArray<'local>> {
 	let proto = proto_output::Config {
-		max_page_id: config.max_page_id,
+		max_page_id: u32::from(config.max_page_id),
 		max_key_page_size_bytes: config.max_key_page_size_bytes,
 		sdk_max_stale_friendship_days: config.sdk_max_stale_friendship_days,
 		max_graph_page_size_bytes: config.max_graph_page_size_bytes,
@@ -201,6 +245,7 @@ pub fn serialize_config<'local>(
 		schema_map: map_schema_map_to_proto(&config.schema_map)?,
 		graph_public_key_schema_id: u32::try_from(config.graph_public_key_schema_id)
 			.map_err(|_| SdkJniError::UnexpectedResponse("invalid SchemaId"))?,
+		max_update_payload_bytes: config.max_update_payload_bytes.unwrap_or(0),
 		special_fields: SpecialFields::default(),
 	};
 
@@ -209,6 +254,16 @@ pub fn serialize_config<'local>(
 	Ok(arr)
 }
 
+pub fn serialize_schema_config<'local>(
+	env: &JNIEnv<'local>,
+	schema_config: &SchemaConfig,
+) -> SdkJniResult<JByteArray<'local>> {
+	let proto = map_schema_config_to_proto(schema_config)?;
+	let bytes = proto.write_to_bytes().map_err(|e| SdkJniError::from(e))?;
+	let arr = env.byte_array_from_slice(&bytes).map_err(|e| SdkJniError::from(e))?;
+	Ok(arr)
+}
+
 pub fn serialize_dsnp_users<'local>(
 	env: &JNIEnv<'local>,
 	dsnp_users: &[DsnpUserId],
@@ -248,6 +303,11 @@ fn map_action_to_rust(action: proto_input::actions::Action) -> SdkJniResult<Rust
 					.ok_or(SdkJniError::InvalidRequest("connection not set!"))?,
 			)?,
 			dsnp_keys: map_dsnp_keys_to_rust(&connect.dsnp_keys.as_ref().cloned())?,
+			preferred_page_id: None,
+			since: match connect.since {
+				0 => None,
+				since => Some(since),
+			},
 		},
 		proto_input::actions::action::Inner::DisconnectAction(disconnect) =>
 			RustAction::Disconnect {
@@ -263,6 +323,21 @@ fn map_action_to_rust(action: proto_input::actions::Action) -> SdkJniResult<Rust
 	})
 }
 
+fn map_timestamp_policy_to_rust(
+	policy: EnumOrUnknown<proto_input::TimestampPolicy>,
+) -> SdkJniResult<RustTimestampPolicy> {
+	Ok(
+		match policy
+			.enum_value()
+			.map_err(|_| SdkJniError::InvalidRequest("invalid timestamp_policy!"))?
+		{
+			proto_input::TimestampPolicy::CallerProvided => RustTimestampPolicy::CallerProvided,
+			proto_input::TimestampPolicy::Now => RustTimestampPolicy::Now,
+			proto_input::TimestampPolicy::Zero => RustTimestampPolicy::Zero,
+		},
+	)
+}
+
 fn map_connection_to_rust(conection: &proto_input::Connection) -> SdkJniResult<RustConnection> {
 	Ok(RustConnection {
 		dsnp_user_id: conection.dsnp_user_id,
@@ -284,6 +359,13 @@ fn map_dsnp_keys_to_rust(
 	}
 }
 
+fn map_chain_head_to_rust(chain_head: &Option<proto_input::ChainHead>) -> Option<RustChainHead> {
+	chain_head.as_ref().map(|head| RustChainHead {
+		block_number: head.block_number,
+		block_hash: head.block_hash.clone(),
+	})
+}
+
 fn map_key_data_to_rust(key_datas: &Vec<proto_input::KeyData>) -> SdkJniResult<Vec<RustKeyData>> {
 	let mut keys = vec![];
 	for k in key_datas {
@@ -317,8 +399,14 @@ fn map_config_to_rust(config: proto_output::Config) -> SdkJniResult<RustConfig>
 	Ok(RustConfig {
 		max_graph_page_size_bytes: config.max_graph_page_size_bytes,
 		sdk_max_stale_friendship_days: config.sdk_max_stale_friendship_days,
-		max_page_id: config.max_page_id,
+		max_page_id: PageId::try_from(config.max_page_id)
+			.map_err(|_| SdkJniError::InvalidRequest("invalid max PageId"))?,
 		max_key_page_size_bytes: config.max_key_page_size_bytes,
+		max_update_payload_bytes: match config.max_update_payload_bytes {
+			0 => None,
+			max_update_payload_bytes => Some(max_update_payload_bytes),
+		},
+		legacy_public_page_order: false,
 		dsnp_versions,
 		schema_map,
 		graph_public_key_schema_id: SchemaId::try_from(config.graph_public_key_schema_id)
@@ -348,6 +436,8 @@ fn map_schema_config_to_rust(
 	let result = RustSchemaConfig {
 		dsnp_version: map_dsnp_version_to_rust(version)?,
 		connection_type: map_connection_type_to_rust(connection_type)?,
+		// page capacity overrides are not part of the protobuf schema yet
+		page_capacity: None,
 	};
 
 	Ok(result)
@@ -371,18 +461,25 @@ fn map_connection_type_to_rust(
 fn map_update_to_proto(update: &RustUpdate) -> SdkJniResult<proto_output::updates::Update> {
 	let mut proto = proto_output::updates::Update::new();
 	let inner = match update {
-		RustUpdate::PersistPage { schema_id, page_id, prev_hash, owner_dsnp_user_id, payload } =>
-			proto_output::updates::update::Inner::Persist(PersistPageUpdate {
-				owner_dsnp_user_id: *owner_dsnp_user_id,
-				prev_hash: *prev_hash,
-				page_id: u32::try_from(*page_id)
-					.map_err(|_| SdkJniError::InvalidRequest("invalid PageId"))?,
-				schema_id: u32::try_from(*schema_id)
-					.map_err(|_| SdkJniError::InvalidRequest("invalid SchemaId"))?,
-				payload: payload.clone(),
-				special_fields: SpecialFields::default(),
-			}),
-		RustUpdate::DeletePage { schema_id, page_id, prev_hash, owner_dsnp_user_id } =>
+		RustUpdate::PersistPage {
+			schema_id,
+			page_id,
+			prev_hash,
+			owner_dsnp_user_id,
+			payload,
+			..
+		} => proto_output::updates::update::Inner::Persist(PersistPageUpdate {
+			owner_dsnp_user_id: *owner_dsnp_user_id,
+			prev_hash: *prev_hash,
+			page_id: u32::try_from(*page_id)
+				.map_err(|_| SdkJniError::InvalidRequest("invalid PageId"))?,
+			schema_id: u32::try_from(*schema_id)
+				.map_err(|_| SdkJniError::InvalidRequest("invalid SchemaId"))?,
+			payload: payload.clone(),
+			chain_head: map_chain_head_to_proto(update.chain_head()),
+			special_fields: SpecialFields::default(),
+		}),
+		RustUpdate::DeletePage { schema_id, page_id, prev_hash, owner_dsnp_user_id, .. } =>
 			proto_output::updates::update::Inner::Delete(DeletePageUpdate {
 				owner_dsnp_user_id: *owner_dsnp_user_id,
 				prev_hash: *prev_hash,
@@ -390,13 +487,15 @@ fn map_update_to_proto(update: &RustUpdate) -> SdkJniResult<proto_output::update
 					.map_err(|_| SdkJniError::InvalidRequest("invalid PageId"))?,
 				schema_id: u32::try_from(*schema_id)
 					.map_err(|_| SdkJniError::InvalidRequest("invalid SchemaId"))?,
+				chain_head: map_chain_head_to_proto(update.chain_head()),
 				special_fields: SpecialFields::default(),
 			}),
-		RustUpdate::AddKey { prev_hash, owner_dsnp_user_id, payload } =>
+		RustUpdate::AddKey { prev_hash, owner_dsnp_user_id, payload, .. } =>
 			proto_output::updates::update::Inner::AddKey(AddKeyUpdate {
 				owner_dsnp_user_id: *owner_dsnp_user_id,
 				prev_hash: *prev_hash,
 				payload: payload.clone(),
+				chain_head: map_chain_head_to_proto(update.chain_head()),
 				special_fields: SpecialFields::default(),
 			}),
 	};
@@ -404,6 +503,16 @@ fn map_update_to_proto(update: &RustUpdate) -> SdkJniResult<proto_output::update
 	Ok(proto)
 }
 
+fn map_chain_head_to_proto(
+	chain_head: Option<&RustChainHead>,
+) -> MessageField<proto_input::ChainHead> {
+	MessageField::from_option(chain_head.map(|head| proto_input::ChainHead {
+		block_number: head.block_number,
+		block_hash: head.block_hash.clone(),
+		special_fields: SpecialFields::default(),
+	}))
+}
+
 fn map_graph_key_pairs_to_rust(
 	key_pairs: &[proto_input::import_bundles::import_bundle::GraphKeyPair],
 ) -> SdkJniResult<Vec<RustGraphKeyPair>> {
@@ -13,6 +13,10 @@ import {
   GraphKeyType,
   PersistPageUpdate,
   Update,
+  ConversionError,
+  ConversionErrorCode,
+  isConversionError,
+  GraphStateHandle,
 } from "./models";
 import {
   Config,
@@ -21,6 +25,7 @@ import {
   PrivacyType,
 } from "./models/config";
 import {
+  CustomEnvironment,
   DevEnvironment,
   EnvironmentInterface,
   EnvironmentType,
@@ -63,7 +68,7 @@ const environment: DevEnvironment = {
 
 describe("Graph tests", () => {
   let graph: Graph;
-  let handle: number;
+  let handle: GraphStateHandle;
 
   beforeEach(() => {
     graph = new Graph(environment);
@@ -105,6 +110,19 @@ describe("Graph tests", () => {
     graph.freeGraphState();
   });
 
+  test("getConnectionTypeFromSchemaId should resolve the schema id of the config", async () => {
+    const environment: EnvironmentInterface = {
+      environmentType: EnvironmentType.Mainnet,
+    };
+    const graph = new Graph(environment);
+    const schemaId = graph.getSchemaIdFromConfig(environment, ConnectionType.Friendship, PrivacyType.Private);
+    const schemaConfig = graph.getConnectionTypeFromSchemaId(environment, schemaId);
+    expect(schemaConfig.connectionType).toEqual(ConnectionType.Friendship);
+    expect(schemaConfig.privacyType).toEqual(PrivacyType.Private);
+    expect(() => graph.getConnectionTypeFromSchemaId(environment, 1000)).toThrow();
+    graph.freeGraphState();
+  });
+
   test("getGraphConfig with Testnet Paseo environment should return the graph config", async () => {
     const environment: EnvironmentInterface = {
       environmentType: EnvironmentType.TestnetPaseo,
@@ -125,6 +143,38 @@ describe("Graph tests", () => {
     graph.freeGraphState();
   });
 
+  test("registerEnvironmentConfig should allow using a Custom environment", async () => {
+    expect(Graph.registerEnvironmentConfig("custom-test", config)).toEqual(true);
+    const environment: CustomEnvironment = {
+      environmentType: EnvironmentType.Custom,
+      name: "custom-test",
+    };
+    const graph = new Graph(environment);
+    const config_ret = graph.getGraphConfig(environment);
+    expect(config_ret.graphPublicKeySchemaId).toEqual(11);
+    graph.freeGraphState();
+  });
+
+  test("registerEnvironmentConfigFromMainnet should apply overrides to mainnet", async () => {
+    expect(Graph.registerEnvironmentConfigFromMainnet("custom-overrides-test", '{"maxPageId": 10}')).toEqual(true);
+    const environment: CustomEnvironment = {
+      environmentType: EnvironmentType.Custom,
+      name: "custom-overrides-test",
+    };
+    const graph = new Graph(environment);
+    const config_ret = graph.getGraphConfig(environment);
+    expect(config_ret.maxPageId).toEqual(10);
+    graph.freeGraphState();
+  });
+
+  test("Custom environment without registered config should throw", async () => {
+    const environment: CustomEnvironment = {
+      environmentType: EnvironmentType.Custom,
+      name: "not-registered",
+    };
+    expect(() => new Graph(environment)).toThrow();
+  });
+
   test("getGraphStatesCount should be unchanged after create/free new graph", async () => {
     const originalCount = graph.getGraphStatesCount();
     const secondGraph = new Graph(environment);
@@ -132,6 +182,13 @@ describe("Graph tests", () => {
     expect(secondGraph.getGraphStatesCount()).toEqual(originalCount);
   });
 
+  test("freeGraphState should throw for an already freed graph", async () => {
+    const secondGraph = new Graph(environment);
+    expect(secondGraph.freeGraphState()).toEqual(true);
+    expect(() => secondGraph.freeGraphState()).toThrow("Graph state not found");
+    expect(() => secondGraph.getGraphUsersCount()).toThrow("Graph state not found");
+  });
+
   test("getGraphStatesCount should be one after graph is initialized", async () => {
     const count = graph.getGraphStatesCount();
     expect(count).toEqual(1);
@@ -147,6 +204,11 @@ describe("Graph tests", () => {
     expect(contains).toEqual(false);
   });
 
+  test("isUserGraphWritable should return false for a user that is not imported", async () => {
+    const writable = graph.isUserGraphWritable("1", 1);
+    expect(writable).toEqual(false);
+  });
+
   test("removeUserGraph should pass through on initialized graph", async () => {
     const removed = graph.removeUserGraph("1");
     expect(removed).toEqual(true);
@@ -229,6 +291,89 @@ describe("Graph tests", () => {
     expect(exported.length).toEqual(1);
   });
 
+  test("async variants of applyActions and exportUpdates should resolve on initialized graph", async () => {
+    const actions: Action[] = [
+      {
+        type: "Connect",
+        ownerDsnpUserId: "1",
+        connection: {
+          dsnpUserId: "2",
+          schemaId: 1,
+        },
+      },
+    ];
+
+    const applied = await graph.applyActionsAsync(actions);
+    expect(applied).toEqual(true);
+
+    const exported = await graph.exportUpdatesAsync();
+    expect(exported.length).toEqual(1);
+  });
+
+  test("applyActionsAsync should report progress and reject batches over the max batch size", async () => {
+    const actions: Action[] = [2, 3].map((dsnpUserId) => ({
+      type: "Connect",
+      ownerDsnpUserId: "1",
+      connection: { dsnpUserId: dsnpUserId.toString(), schemaId: 1 },
+    }));
+    const progress: number[] = [];
+
+    await expect(graph.applyActionsAsync(actions, { maxBatchSize: 1 })).rejects.toThrow();
+    const applied = await graph.applyActionsAsync(actions, undefined, (fraction) => progress.push(fraction));
+
+    expect(applied).toEqual(true);
+    expect(progress).toEqual([1]);
+  });
+
+  test("importUserDataAsync should reject on invalid payload", async () => {
+    const importBundle: ImportBundle = {
+      dsnpUserId: "1",
+      schemaId: 1,
+      keyPairs: [],
+      dsnpKeys: { dsnpUserId: "1", keysHash: 100, keys: [] },
+      pages: [{ pageId: 1, content: new Uint8Array([1, 2, 3]), contentHash: 100 }],
+    };
+
+    await expect(graph.importUserDataAsync([importBundle])).rejects.toThrow();
+  });
+
+  test("importUserData with invalid nested value should throw conversion error with path", async () => {
+    const importBundle = {
+      dsnpUserId: "1",
+      schemaId: 1,
+      keyPairs: [],
+      dsnpKeys: { dsnpUserId: "1", keysHash: 100, keys: [{ index: 0, content: "invalid" }] },
+      pages: [],
+    } as unknown as ImportBundle;
+
+    let thrown: unknown;
+    try {
+      graph.importUserData([importBundle]);
+    } catch (e) {
+      thrown = e;
+    }
+
+    expect(isConversionError(thrown)).toEqual(true);
+    const error = thrown as ConversionError;
+    expect(error.code).toEqual(ConversionErrorCode.InvalidType);
+    expect(error.path).toEqual("payload[0].dsnpKeys.keys[0].content");
+    expect(error.message).toEqual("Expected Uint8Array at payload[0].dsnpKeys.keys[0].content");
+  });
+
+  test("applyActions with invalid dsnp user id should throw conversion error with path", async () => {
+    const actions = [
+      {
+        type: "Connect",
+        ownerDsnpUserId: "1",
+        connection: { dsnpUserId: "not a number", schemaId: 1 } as Connection,
+      } as ConnectAction,
+    ];
+
+    expect(() => graph.applyActions(actions)).toThrow(
+      "Invalid dsnp user id at actions[0].connection.dsnpUserId",
+    );
+  });
+
   test("applyActions with options should honor options", async () => {
     // Add some connections to 2 empty graphs
     const dsnpId_1 = "1";
@@ -362,22 +507,171 @@ describe("Graph tests", () => {
     expect(forceCalculateGraphs.length).toEqual(0);
   });
 
+  test("dsnp user ids should be accepted as bigint or number and returned as bigint if useBigInt is set", async () => {
+    const bigIntGraph = new Graph(environment, { useBigInt: true });
+    const action = {
+      type: "Connect",
+      ownerDsnpUserId: 1n,
+      connection: {
+        dsnpUserId: 2,
+        schemaId: 1,
+      } as Connection,
+    } as ConnectAction;
+
+    expect(bigIntGraph.applyActions([action])).toEqual(true);
+    expect(bigIntGraph.containsUserGraph(1n)).toEqual(true);
+    const connections = bigIntGraph.getConnectionsForUserGraph(1, 1, true);
+    expect(connections.length).toEqual(1);
+    expect(connections[0].userId).toEqual(2n);
+    expect(() => bigIntGraph.containsUserGraph(1.5)).toThrow("Invalid dsnp user id at dsnpUserId");
+    expect(() => bigIntGraph.containsUserGraph(-1n)).toThrow("Invalid dsnp user id at dsnpUserId");
+
+    bigIntGraph.freeGraphState();
+  });
+
+  test("exportGraphRemoval for a user without imported pages should return empty array", async () => {
+    const bundle: ImportBundle = {
+      dsnpUserId: "1",
+      schemaId: 1,
+      pages: [],
+      dsnpKeys: { dsnpUserId: "1", keysHash: 100, keys: [] },
+      keyPairs: [],
+    };
+    graph.importUserData([bundle]);
+
+    const updates = graph.exportGraphRemoval("1");
+    expect(updates).toBeDefined();
+    expect(updates.length).toEqual(0);
+  });
+
   test("getConnectionsWithoutKeys with empty connections should return empty array", async () => {
-    const connections = graph.getConnectionsWithoutKeys();
+    const connections = graph.getConnectionsWithoutKeys(true);
     expect(connections).toBeDefined();
     expect(connections.length).toEqual(0);
 
-    expect(() => graph.getOneSidedPrivateFriendshipConnections("1")).toThrow(
+    expect(() => graph.getOneSidedPrivateFriendshipConnections("1", true)).toThrow(
       "User graph for 1 is not imported",
     );
   });
 
+  test("getConnectionsWithoutKeys with cursor and limit should return a page", async () => {
+    const connections = graph.getConnectionsWithoutKeys(true, "1", 10);
+    expect(connections).toBeDefined();
+    expect(connections.length).toEqual(0);
+
+    expect(() => graph.getConnectionsWithoutKeys(true, undefined, 0)).toThrow(
+      "limit should be greater than zero",
+    );
+  });
+
   test("getPublicKeys with empty connections should return empty array", async () => {
     const keys = graph.getPublicKeys("1");
     expect(keys).toBeDefined();
     expect(keys.length).toEqual(0);
   });
 
+  test("getKeyPageStats without imported keys should return empty usage", async () => {
+    const stats = graph.getKeyPageStats("1");
+    expect(stats.keyCount).toEqual(0);
+    expect(stats.usedBytes).toEqual(0);
+    expect(stats.maxBytes).toEqual(config.maxKeyPageSizeBytes);
+  });
+
+  test("getNextKeyId without imported keys should return first index", async () => {
+    expect(graph.getNextKeyId("1")).toEqual("0");
+  });
+
+  test("setAdditionalRecipientKeys for a user without imported graph should throw", async () => {
+    expect(() => graph.setAdditionalRecipientKeys("1", ["0"])).toThrow();
+  });
+
+  test("getMemoryFootprint on empty graph should not report any user graph", async () => {
+    const report = graph.getMemoryFootprint();
+    expect(report.userGraphBytes.length).toEqual(0);
+    expect(report.pendingUpdateBytes).toEqual(0);
+    expect(report.totalBytes).toBeGreaterThan(0);
+  });
+
+  test("getUserGraphStats should report imported pages of the user graph", async () => {
+    const importBundle: ImportBundle = {
+      dsnpUserId: "1",
+      schemaId: 1,
+      keyPairs: [],
+      pages: [
+        {
+          pageId: 1,
+          content: new Uint8Array([24, 227, 96, 97, 96, 99, 224, 96, 224, 98, 96, 0, 0]),
+          contentHash: 100,
+        },
+      ],
+    };
+    graph.importUserData([importBundle]);
+
+    const stats = graph.getUserGraphStats("1", 1);
+    expect(stats.dsnpUserId).toEqual("1");
+    expect(stats.schemaId).toEqual(1);
+    expect(stats.decryptedPages).toEqual(1);
+    expect(stats.pageHashes).toEqual([{ pageId: 1, contentHash: 100 }]);
+    expect(stats.encryptedPages.length).toEqual(0);
+    expect(stats.isMetadataOnly).toEqual(false);
+  });
+
+  test("exportUpdates should carry the chain head of the imported bundle", async () => {
+    const importBundle: ImportBundle = {
+      dsnpUserId: "1",
+      schemaId: 1,
+      keyPairs: [],
+      pages: [
+        {
+          pageId: 1,
+          content: new Uint8Array([24, 227, 96, 97, 96, 99, 224, 96, 224, 98, 96, 0, 0]),
+          contentHash: 100,
+        },
+      ],
+      chainHead: { blockNumber: 42, blockHash: new Uint8Array([1, 2, 3, 4]) },
+    };
+    graph.importUserData([importBundle]);
+    const actions: Action[] = [
+      {
+        type: "Connect",
+        ownerDsnpUserId: "1",
+        connection: {
+          dsnpUserId: "12345",
+          schemaId: 1,
+        },
+      },
+    ];
+    graph.applyActions(actions);
+
+    const exported = graph.exportUpdates();
+    expect(exported.length).toEqual(1);
+    expect(exported[0].chainHead?.blockNumber).toEqual(42);
+    expect(Array.from(exported[0].chainHead?.blockHash ?? [])).toEqual([1, 2, 3, 4]);
+  });
+
+  test("getPendingUpdates should return added connections which are not exported", async () => {
+    const actions: Action[] = [
+      {
+        type: "Connect",
+        ownerDsnpUserId: "1",
+        connection: {
+          dsnpUserId: "2",
+          schemaId: 1,
+        },
+      },
+    ];
+    graph.applyActions(actions);
+
+    const pending = graph.getPendingUpdates("1", 1);
+    expect(pending.adds).toEqual(["2"]);
+    expect(pending.removes).toEqual([]);
+  });
+
+  test("isStale should only return true for states older than the allowed age", async () => {
+    expect(graph.isStale(3600)).toEqual(false);
+    expect(() => graph.isStale(-1)).toThrow();
+  });
+
   test("deserializeDsnpKeys with empty keys should return empty array", async () => {
     const keys = {
       dsnpUserId: "2",
@@ -530,6 +824,20 @@ describe("Graph tests", () => {
     expect(keyPair.keyType).toEqual(GraphKeyType.X25519);
   });
 
+  test("publicKey conversions should roundtrip through JWK and multibase", async () => {
+    const keyPair = Graph.generateKeyPair(GraphKeyType.X25519);
+
+    const jwk = Graph.publicKeyToJwk(keyPair);
+    const multibase = Graph.publicKeyToMultibase(keyPair);
+
+    expect(jwk.kty).toEqual("OKP");
+    expect(jwk.crv).toEqual("X25519");
+    expect(multibase.startsWith("z6LS")).toEqual(true);
+    expect(Buffer.from(Graph.publicKeyFromJwk(jwk))).toEqual(Buffer.from(keyPair.publicKey));
+    expect(Buffer.from(Graph.publicKeyFromMultibase(multibase))).toEqual(Buffer.from(keyPair.publicKey));
+    expect(() => Graph.publicKeyFromMultibase("invalid")).toThrow();
+  });
+
   test("Private Graph: Import bundle without schema id and empty pages should import keys", async () => {
     const dsnpUserId = "1000";
     const keyPair: GraphKeyPair = {
@@ -11,35 +11,41 @@ import {
   PrivacyType,
   GraphKeyPair,
   ActionOptions,
+  KeyPageStats,
+  MemoryReport,
+  PendingUpdates,
+  PublicKeyJwk,
+  SchemaConfig,
+  DsnpUserIdInput,
+  DsnpUserIdOutput,
+  GraphOptions,
+  GraphStateHandle,
+  UserGraphStats,
 } from "./models";
 import { EnvironmentInterface } from "./models/environment";
 
 export class Graph {
-  /// while there is no guarantee that this will free the allocated resources, and we should always manually
-  /// call freeGraphState for cleanup, having FinalizationRegistry might help in some cases that we forgot.
-  static #finalizerInstance: FinalizationRegistry<number>;
-
-  /// The handle to the native graph state
-  private readonly handle: number;
-
-  constructor(environment: EnvironmentInterface) {
-    if (!Graph.#finalizerInstance) {
-      Graph.#finalizerInstance = new FinalizationRegistry<number>(
-        (handle: number) => {
-          try {
-            graphsdkModule.freeGraphState(handle);
-          } catch {
-            // nothing to do here
-          }
-        },
-      );
-    }
-    const my_handle = graphsdkModule.initializeGraphState(environment);
-    Graph.#finalizerInstance.register(this, my_handle);
-    this.handle = my_handle;
+  /// The handle to the native graph state, the native graph state is freed when the handle is
+  /// garbage collected but freeGraphState should still be called to release it deterministically.
+  private readonly handle: GraphStateHandle;
+
+  constructor(environment: EnvironmentInterface, options?: GraphOptions) {
+    this.handle = options
+      ? graphsdkModule.initializeGraphState(environment, options)
+      : graphsdkModule.initializeGraphState(environment);
+  }
+
+  /// registers a config to be used by environments of type `Custom` with the same name
+  static registerEnvironmentConfig(name: string, config: Config): boolean {
+    return graphsdkModule.registerEnvironmentConfig(name, config);
   }
 
-  getGraphHandle(): number {
+  /// registers a config built from the Mainnet config with the overridden fields of a JSON object
+  static registerEnvironmentConfigFromMainnet(name: string, overridesJson: string): boolean {
+    return graphsdkModule.registerEnvironmentConfigFromMainnet(name, overridesJson);
+  }
+
+  getGraphHandle(): GraphStateHandle {
     return this.handle;
   }
 
@@ -59,19 +65,27 @@ export class Graph {
     );
   }
 
+  getConnectionTypeFromSchemaId(environment: EnvironmentInterface, schemaId: number): SchemaConfig {
+    return graphsdkModule.getConnectionTypeFromSchemaId(environment, schemaId);
+  }
+
   getGraphStatesCount(): number {
     return graphsdkModule.getGraphStatesCount();
   }
 
-  containsUserGraph(dsnpUserId: string): boolean {
+  containsUserGraph(dsnpUserId: DsnpUserIdInput): boolean {
     return graphsdkModule.containsUserGraph(this.handle, dsnpUserId);
   }
 
+  isUserGraphWritable(dsnpUserId: DsnpUserIdInput, schemaId: number): boolean {
+    return graphsdkModule.isUserGraphWritable(this.handle, dsnpUserId, schemaId);
+  }
+
   getGraphUsersCount(): number {
     return graphsdkModule.getGraphUsersCount(this.handle);
   }
 
-  removeUserGraph(dsnpUserId: string): boolean {
+  removeUserGraph(dsnpUserId: DsnpUserIdInput): boolean {
     return graphsdkModule.removeUserGraph(this.handle, dsnpUserId);
   }
 
@@ -83,12 +97,20 @@ export class Graph {
     return graphsdkModule.exportUpdates(this.handle);
   }
 
-  exportUserGraphUpdates(dsnpUserId: string): Update[] {
+  importUserDataAsync(payload: ImportBundle[]): Promise<boolean> {
+    return graphsdkModule.importUserDataAsync(this.handle, payload);
+  }
+
+  exportUpdatesAsync(): Promise<Update[]> {
+    return graphsdkModule.exportUpdatesAsync(this.handle);
+  }
+
+  exportUserGraphUpdates(dsnpUserId: DsnpUserIdInput): Update[] {
     return graphsdkModule.exportUserGraphUpdates(this.handle, dsnpUserId);
   }
 
   getConnectionsForUserGraph(
-    dsnpUserId: string,
+    dsnpUserId: DsnpUserIdInput,
     schemaId: number,
     includePending: boolean,
   ): DsnpGraphEdge[] {
@@ -107,6 +129,27 @@ export class Graph {
     return graphsdkModule.applyActions(this.handle, actions);
   }
 
+  applyActionsAndExport(actions: Action[], options?: ActionOptions): Update[] {
+    if (options) {
+      return graphsdkModule.applyActionsAndExport(this.handle, actions, options);
+    }
+    return graphsdkModule.applyActionsAndExport(this.handle, actions);
+  }
+
+  applyActionsAsync(
+    actions: Action[],
+    options?: ActionOptions,
+    onProgress?: (fraction: number) => void,
+  ): Promise<boolean> {
+    if (onProgress) {
+      return graphsdkModule.applyActionsAsync(this.handle, actions, options, onProgress);
+    }
+    if (options) {
+      return graphsdkModule.applyActionsAsync(this.handle, actions, options);
+    }
+    return graphsdkModule.applyActionsAsync(this.handle, actions);
+  }
+
   commit(): void {
     return graphsdkModule.commit(this.handle);
   }
@@ -115,25 +158,79 @@ export class Graph {
     return graphsdkModule.rollback(this.handle);
   }
 
-  forceCalculateGraphs(dsnpUserId: string): Update[] {
+  forceCalculateGraphs(dsnpUserId: DsnpUserIdInput): Update[] {
     return graphsdkModule.forceCalculateGraphs(this.handle, dsnpUserId);
   }
 
-  getConnectionsWithoutKeys(): string[] {
-    return graphsdkModule.getConnectionsWithoutKeys(this.handle);
+  exportGraphRemoval(dsnpUserId: DsnpUserIdInput): Update[] {
+    return graphsdkModule.exportGraphRemoval(this.handle, dsnpUserId);
+  }
+
+  /// returns the connections without keys sorted by id, which can be fetched in pages of at most
+  /// `limit` connections starting after `cursor`, the last connection of the previous page
+  getConnectionsWithoutKeys(
+    includePending: boolean,
+    cursor?: DsnpUserIdInput,
+    limit?: number,
+  ): DsnpUserIdOutput[] {
+    return graphsdkModule.getConnectionsWithoutKeys(
+      this.handle,
+      includePending,
+      cursor,
+      limit,
+    );
   }
 
-  getOneSidedPrivateFriendshipConnections(dsnpUserId: string): DsnpGraphEdge[] {
+  getOneSidedPrivateFriendshipConnections(
+    dsnpUserId: DsnpUserIdInput,
+    includePending: boolean,
+  ): DsnpGraphEdge[] {
     return graphsdkModule.getOneSidedPrivateFriendshipConnections(
       this.handle,
       dsnpUserId,
+      includePending,
     );
   }
 
-  getPublicKeys(dsnpUserId: string): DsnpPublicKey[] {
+  getPublicKeys(dsnpUserId: DsnpUserIdInput): DsnpPublicKey[] {
     return graphsdkModule.getPublicKeys(this.handle, dsnpUserId);
   }
 
+  getKeyPageStats(dsnpUserId: DsnpUserIdInput): KeyPageStats {
+    return graphsdkModule.getKeyPageStats(this.handle, dsnpUserId);
+  }
+
+  /// returns the key id the next published key of the user will be assigned
+  getNextKeyId(dsnpUserId: DsnpUserIdInput): string {
+    return graphsdkModule.getNextKeyId(this.handle, dsnpUserId);
+  }
+
+  /// selects imported public keys of the user that private pages are sealed to in addition to the
+  /// active key when exported using multi-recipient encryption, an empty list removes them
+  setAdditionalRecipientKeys(dsnpUserId: DsnpUserIdInput, keyIds: string[]): boolean {
+    return graphsdkModule.setAdditionalRecipientKeys(this.handle, dsnpUserId, keyIds);
+  }
+
+  /// returns the estimated memory used by this graph state
+  getMemoryFootprint(): MemoryReport {
+    return graphsdkModule.getMemoryFootprint(this.handle);
+  }
+
+  /// returns the page and connection counts of a graph along with the content hashes of its pages
+  getUserGraphStats(dsnpUserId: DsnpUserIdInput, schemaId: number): UserGraphStats {
+    return graphsdkModule.getUserGraphStats(this.handle, dsnpUserId, schemaId);
+  }
+
+  /// returns the connections of a graph which are added or removed but not exported yet
+  getPendingUpdates(dsnpUserId: DsnpUserIdInput, schemaId: number): PendingUpdates {
+    return graphsdkModule.getPendingUpdates(this.handle, dsnpUserId, schemaId);
+  }
+
+  /// returns true if this graph state was created more than maxAgeSecs seconds ago
+  isStale(maxAgeSecs: number): boolean {
+    return graphsdkModule.isStale(this.handle, maxAgeSecs);
+  }
+
   static deserializeDsnpKeys(keys: DsnpKeys): DsnpPublicKey[] {
     return graphsdkModule.deserializeDsnpKeys(keys);
   }
@@ -142,6 +239,22 @@ export class Graph {
     return graphsdkModule.generateKeyPair(keyType);
   }
 
+  static publicKeyToJwk(keyPair: GraphKeyPair): PublicKeyJwk {
+    return graphsdkModule.publicKeyToJwk(keyPair);
+  }
+
+  static publicKeyToMultibase(keyPair: GraphKeyPair): string {
+    return graphsdkModule.publicKeyToMultibase(keyPair);
+  }
+
+  static publicKeyFromJwk(jwk: PublicKeyJwk): Uint8Array {
+    return graphsdkModule.publicKeyFromJwk(jwk);
+  }
+
+  static publicKeyFromMultibase(encoded: string): Uint8Array {
+    return graphsdkModule.publicKeyFromMultibase(encoded);
+  }
+
   freeGraphState(): boolean {
     return graphsdkModule.freeGraphState(this.handle);
   }
@@ -2,12 +2,13 @@ import {
   GraphKeyType,
   GraphKeyPair,
   DsnpKeys,
+  DsnpUserIdInput,
   ImportBundle,
   PageData,
 } from "./models";
 
 export interface IImportBundleBuilder {
-  dsnpUserId?: string;
+  dsnpUserId?: DsnpUserIdInput;
   schemaId?: number;
   keyPairs?: GraphKeyPair[];
   dsnpKeys?: DsnpKeys;
@@ -23,7 +24,7 @@ export class ImportBundleBuilder {
     }
   }
 
-  public withDsnpUserId(dsnpUserId: string): ImportBundleBuilder {
+  public withDsnpUserId(dsnpUserId: DsnpUserIdInput): ImportBundleBuilder {
     return new ImportBundleBuilder({ ...this.values, dsnpUserId });
   }
 
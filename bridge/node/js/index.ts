@@ -9,12 +9,22 @@ import {
   DsnpGraphEdge,
   DsnpKeys,
   DsnpPublicKey,
+  DsnpUserIdInput,
+  DsnpUserIdOutput,
   EnvironmentInterface,
   GraphKeyPair,
   GraphKeyType,
+  GraphOptions,
+  GraphStateHandle,
   ImportBundle,
+  KeyPageStats,
+  MemoryReport,
+  PendingUpdates,
   PrivacyType,
+  PublicKeyJwk,
+  SchemaConfig,
   Update,
+  UserGraphStats,
 } from "./models";
 
 // Load the native neon graphsdk module
@@ -68,43 +78,84 @@ function getTarget(): String {
 // Define the Native interface
 export interface Native {
   printHelloGraph(): void;
-  initializeGraphState(environment: EnvironmentInterface): number;
+  initializeGraphState(
+    environment: EnvironmentInterface,
+    options?: GraphOptions,
+  ): GraphStateHandle;
   getGraphConfig(environment: EnvironmentInterface): Config;
+  registerEnvironmentConfig(name: string, config: Config): boolean;
+  registerEnvironmentConfigFromMainnet(name: string, overridesJson: string): boolean;
   getSchemaIdFromConfig(
     environment: EnvironmentInterface,
     connectionType: ConnectionType,
     privacyType: PrivacyType,
   ): number;
+  getConnectionTypeFromSchemaId(environment: EnvironmentInterface, schemaId: number): SchemaConfig;
   getGraphStatesCount(): number;
-  getGraphUsersCount(handle: number): number;
-  containsUserGraph(handle: number, dsnpUserId: string): boolean;
-  removeUserGraph(handle: number, dsnpUserId: string): boolean;
-  importUserData(handle: number, payload: ImportBundle[]): boolean;
+  getGraphUsersCount(handle: GraphStateHandle): number;
+  containsUserGraph(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput): boolean;
+  isUserGraphWritable(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput, schemaId: number): boolean;
+  removeUserGraph(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput): boolean;
+  importUserData(handle: GraphStateHandle, payload: ImportBundle[]): boolean;
   applyActions(
-    handle: number,
+    handle: GraphStateHandle,
     actions: Action[],
     options?: ActionOptions,
   ): boolean;
-  commit(handle: number): void;
-  rollback(handle: number): void;
-  exportUpdates(handle: number): Update[];
-  exportUserGraphUpdates(handle: number, dsnpUserId: string): Update[];
+  applyActionsAndExport(
+    handle: GraphStateHandle,
+    actions: Action[],
+    options?: ActionOptions,
+  ): Update[];
+  commit(handle: GraphStateHandle): void;
+  rollback(handle: GraphStateHandle): void;
+  exportUpdates(handle: GraphStateHandle): Update[];
+  importUserDataAsync(
+    handle: GraphStateHandle,
+    payload: ImportBundle[],
+  ): Promise<boolean>;
+  applyActionsAsync(
+    handle: GraphStateHandle,
+    actions: Action[],
+    options?: ActionOptions,
+    onProgress?: (fraction: number) => void,
+  ): Promise<boolean>;
+  exportUpdatesAsync(handle: GraphStateHandle): Promise<Update[]>;
+  exportUserGraphUpdates(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput): Update[];
   getConnectionsForUserGraph(
-    handle: number,
-    dsnpUserId: string,
+    handle: GraphStateHandle,
+    dsnpUserId: DsnpUserIdInput,
     schemaId: number,
     includePending: boolean,
   ): DsnpGraphEdge[];
-  forceCalculateGraphs(handle: number, dsnpUserId: string): Update[];
-  getConnectionsWithoutKeys(handle: number): string[];
+  forceCalculateGraphs(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput): Update[];
+  exportGraphRemoval(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput): Update[];
+  getConnectionsWithoutKeys(
+    handle: GraphStateHandle,
+    includePending: boolean,
+    cursor?: DsnpUserIdInput,
+    limit?: number,
+  ): DsnpUserIdOutput[];
   getOneSidedPrivateFriendshipConnections(
-    handle: number,
-    dsnpUserId: string,
+    handle: GraphStateHandle,
+    dsnpUserId: DsnpUserIdInput,
+    includePending: boolean,
   ): DsnpGraphEdge[];
-  getPublicKeys(handle: number, dsnpUserId: string): DsnpPublicKey[];
+  getPublicKeys(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput): DsnpPublicKey[];
+  getKeyPageStats(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput): KeyPageStats;
+  getNextKeyId(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput): string;
+  setAdditionalRecipientKeys(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput, keyIds: string[]): boolean;
+  getMemoryFootprint(handle: GraphStateHandle): MemoryReport;
+  getUserGraphStats(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput, schemaId: number): UserGraphStats;
+  getPendingUpdates(handle: GraphStateHandle, dsnpUserId: DsnpUserIdInput, schemaId: number): PendingUpdates;
+  isStale(handle: GraphStateHandle, maxAgeSecs: number): boolean;
   deserializeDsnpKeys(keys: DsnpKeys): DsnpPublicKey[];
   generateKeyPair(keyType: GraphKeyType): GraphKeyPair;
-  freeGraphState(handle: number): boolean;
+  publicKeyToJwk(keyPair: GraphKeyPair): PublicKeyJwk;
+  publicKeyToMultibase(keyPair: GraphKeyPair): string;
+  publicKeyFromJwk(jwk: PublicKeyJwk): Uint8Array;
+  publicKeyFromMultibase(encoded: string): Uint8Array;
+  freeGraphState(handle: GraphStateHandle): boolean;
 }
 
 // Export the graphsdk module
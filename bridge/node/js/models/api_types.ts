@@ -0,0 +1,156 @@
+// This file is generated from the Rust api types by `make node-types`, do not edit it
+import type { DsnpUserIdInput, DsnpUserIdOutput } from "./dsnp_user_id";
+import type { GraphKeyType } from "./import_bundle";
+import type { ConnectionType, DsnpVersion, PrivacyType } from "./config";
+import type { TimestampPolicy } from "./action";
+
+export interface KeyData {
+  index: number;
+  content: Uint8Array;
+}
+
+export interface DsnpKeys {
+  dsnpUserId: DsnpUserIdInput;
+  keysHash: number;
+  keys: KeyData[];
+}
+
+export interface GraphKeyPair {
+  keyType: GraphKeyType;
+  publicKey: Uint8Array;
+  secretKey: Uint8Array;
+}
+
+export interface PageData {
+  pageId: number;
+  content: Uint8Array;
+  contentHash: number;
+}
+
+export interface ChainHead {
+  blockNumber: number;
+  blockHash: Uint8Array;
+}
+
+export interface ImportBundle {
+  dsnpUserId: DsnpUserIdInput;
+  schemaId: number;
+  keyPairs: GraphKeyPair[];
+  dsnpKeys?: DsnpKeys;
+  pages: PageData[];
+  chainHead?: ChainHead;
+}
+
+export interface Connection {
+  dsnpUserId: DsnpUserIdInput;
+  schemaId: number;
+}
+
+export interface ActionOptions {
+  ignoreExistingConnections?: boolean;
+  ignoreMissingConnections?: boolean;
+  disableAutoCommit?: boolean;
+  collectErrors?: boolean;
+  requireCounterpartKeys?: boolean;
+  maxBatchSize?: number;
+  timestampPolicy?: TimestampPolicy;
+}
+
+export interface ConnectAction {
+  type: "Connect";
+  ownerDsnpUserId: DsnpUserIdInput;
+  connection: Connection;
+  dsnpKeys?: DsnpKeys;
+  preferredPageId?: number;
+  since?: number;
+}
+
+export interface DisconnectAction {
+  type: "Disconnect";
+  ownerDsnpUserId: DsnpUserIdInput;
+  connection: Connection;
+}
+
+export interface AddGraphKeyAction {
+  type: "AddGraphKey";
+  ownerDsnpUserId: DsnpUserIdInput;
+  newPublicKey: Uint8Array;
+}
+
+export interface ConnectMutualAction {
+  type: "ConnectMutual";
+  userA: DsnpUserIdInput;
+  userB: DsnpUserIdInput;
+  schemaId: number;
+  since?: number;
+}
+
+export interface MigrateConnectionAction {
+  type: "MigrateConnection";
+  ownerDsnpUserId: DsnpUserIdInput;
+  fromSchemaId: number;
+  toSchemaId: number;
+  dsnpUserId: DsnpUserIdInput;
+  since?: number;
+}
+
+export type Action =
+  | ConnectAction
+  | DisconnectAction
+  | AddGraphKeyAction
+  | ConnectMutualAction
+  | MigrateConnectionAction;
+
+export interface PersistPageUpdate {
+  type: "PersistPage";
+  ownerDsnpUserId: DsnpUserIdOutput;
+  schemaId: number;
+  pageId: number;
+  prevHash: number;
+  payload: Uint8Array;
+  chainHead?: ChainHead;
+  idempotencyKey: Uint8Array;
+}
+
+export interface DeletePageUpdate {
+  type: "DeletePage";
+  ownerDsnpUserId: DsnpUserIdOutput;
+  schemaId: number;
+  pageId: number;
+  prevHash: number;
+  chainHead?: ChainHead;
+  idempotencyKey: Uint8Array;
+}
+
+export interface AddKeyUpdate {
+  type: "AddKey";
+  ownerDsnpUserId: DsnpUserIdOutput;
+  prevHash: number;
+  payload: Uint8Array;
+  chainHead?: ChainHead;
+  idempotencyKey: Uint8Array;
+}
+
+export type Update =
+  | PersistPageUpdate
+  | DeletePageUpdate
+  | AddKeyUpdate;
+
+export interface SchemaConfig {
+  dsnpVersion: DsnpVersion;
+  connectionType: ConnectionType;
+  privacyType: PrivacyType;
+  pageCapacity?: number;
+}
+
+export interface Config {
+  sdkMaxStaleFriendshipDays: number;
+  maxGraphPageSizeBytes: number;
+  maxPageId: number;
+  maxKeyPageSizeBytes: number;
+  maxUpdatePayloadBytes?: number;
+  legacyPublicPageOrder?: boolean;
+  schemaMap: { [key: number]: SchemaConfig };
+  graphPublicKeySchemaId: number;
+  dsnpVersions: DsnpVersion[];
+}
@@ -12,20 +12,5 @@ enum PrivacyType {
   Private = "private",
 }
 
-interface SchemaConfig {
-  dsnpVersion: DsnpVersion;
-  connectionType: ConnectionType;
-  privacyType: PrivacyType;
-}
-
-interface Config {
-  sdkMaxStaleFriendshipDays: number;
-  maxGraphPageSizeBytes: number;
-  maxPageId: number;
-  maxKeyPageSizeBytes: number;
-  schemaMap: { [key: number]: SchemaConfig };
-  graphPublicKeySchemaId: number;
-  dsnpVersions: DsnpVersion[];
-}
-
-export { Config, ConnectionType, DsnpVersion, SchemaConfig, PrivacyType };
+export { ConnectionType, DsnpVersion, PrivacyType };
+export type { Config, SchemaConfig } from "./api_types";
@@ -1,4 +1 @@
-export interface Connection {
-  dsnpUserId: string;
-  schemaId: number;
-}
+export type { Connection } from "./api_types";
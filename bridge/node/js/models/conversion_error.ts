@@ -0,0 +1,23 @@
+/// codes of the errors thrown when a provided value can not be converted
+enum ConversionErrorCode {
+  InvalidType = "ERR_GRAPH_SDK_INVALID_TYPE",
+  InvalidValue = "ERR_GRAPH_SDK_INVALID_VALUE",
+}
+
+/// error thrown when a provided value can not be converted, `path` points to the failed value
+/// such as `payload[2].dsnpKeys.keys[0].content`
+interface ConversionError extends Error {
+  code: ConversionErrorCode;
+  path: string;
+}
+
+/// checks if the thrown error is a `ConversionError`
+function isConversionError(error: unknown): error is ConversionError {
+  return (
+    error instanceof Error &&
+    Object.values(ConversionErrorCode).includes((error as ConversionError).code) &&
+    typeof (error as ConversionError).path === "string"
+  );
+}
+
+export { ConversionErrorCode, ConversionError, isConversionError };
@@ -0,0 +1,10 @@
+/// dsnp user id accepted as input, numbers should be within `Number.MAX_SAFE_INTEGER`
+export type DsnpUserIdInput = string | bigint | number;
+
+/// dsnp user id returned from the graph, a `bigint` if `useBigInt` option is set otherwise a `string`
+export type DsnpUserIdOutput = string | bigint;
+
+export interface GraphOptions {
+  /// returns dsnp user ids as `bigint` instead of `string`
+  useBigInt?: boolean;
+}
@@ -5,6 +5,7 @@ enum EnvironmentType {
   TestnetPaseo = "TestnetPaseo",
   Rococo = "Rococo",
   Dev = "Dev",
+  Custom = "Custom",
 }
 
 interface EnvironmentInterface {
@@ -20,6 +21,19 @@ interface DevEnvironment extends EnvironmentInterface {
   config: Config;
 }
 
+/// environment using a config registered with `Graph.registerEnvironmentConfig`
+interface CustomEnvironment extends EnvironmentInterface {
+  environmentType: EnvironmentType.Custom;
+  name: string;
+}
+
 type EnvironmentConfig = DevEnvironment;
 
-export { EnvironmentType, Environment, DevEnvironment, EnvironmentConfig, EnvironmentInterface };
+export {
+  EnvironmentType,
+  Environment,
+  DevEnvironment,
+  CustomEnvironment,
+  EnvironmentConfig,
+  EnvironmentInterface,
+};
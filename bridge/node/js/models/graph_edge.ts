@@ -1,4 +1,6 @@
+import { DsnpUserIdOutput } from "./dsnp_user_id";
+
 export interface DsnpGraphEdge {
-  userId: string;
+  userId: DsnpUserIdOutput;
   since: number;
 }
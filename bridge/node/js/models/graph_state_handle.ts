@@ -0,0 +1,5 @@
+/// Opaque handle of a native graph state, the graph state is freed when the handle is garbage
+/// collected unless it is already freed using `freeGraphState`
+export interface GraphStateHandle {
+  readonly __graphStateHandle: unique symbol;
+}
@@ -0,0 +1,29 @@
+import { DsnpUserIdOutput } from "./dsnp_user_id";
+
+export interface PageHashEntry {
+  pageId: number;
+  contentHash: number;
+}
+
+/// metadata of an imported page which could not be decrypted
+export interface PageMetadata {
+  pageId: number;
+  contentSize: number;
+  contentHash: number;
+}
+
+export interface UserGraphStats {
+  dsnpUserId: DsnpUserIdOutput;
+  schemaId: number;
+  decryptedPages: number;
+  connections: number;
+  pageHashes: PageHashEntry[];
+  encryptedPages: PageMetadata[];
+  isMetadataOnly: boolean;
+}
+
+/// connections of a graph which are added or removed but not exported yet
+export interface PendingUpdates {
+  adds: DsnpUserIdOutput[];
+  removes: DsnpUserIdOutput[];
+}
@@ -1,10 +1,15 @@
 /// Index file for models
 export * from "./action";
 export * from "./connection";
+export * from "./dsnp_user_id";
 export * from "./graph_edge";
 export * from "./import_bundle";
 export * from "./public_keys";
+export * from "./memory_report";
+export * from "./graph_stats";
+export * from "./conversion_error";
 export * from "./update";
 export * from "./config";
 export * from "./environment";
+export * from "./graph_state_handle";
 // # sourceMappingURL=index.js.map
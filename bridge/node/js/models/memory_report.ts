@@ -0,0 +1,13 @@
+import { DsnpUserIdOutput } from "./dsnp_user_id";
+
+export interface UserGraphMemoryUsage {
+  dsnpUserId: DsnpUserIdOutput;
+  bytes: number;
+}
+
+export interface MemoryReport {
+  userGraphBytes: UserGraphMemoryUsage[];
+  sharedStateBytes: number;
+  pendingUpdateBytes: number;
+  totalBytes: number;
+}
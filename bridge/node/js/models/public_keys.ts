@@ -2,3 +2,16 @@ export interface DsnpPublicKey {
   key: Uint8Array;
   keyId?: string;
 }
+
+export interface KeyPageStats {
+  keyCount: number;
+  usedBytes: number;
+  maxBytes: number;
+}
+
+/// public key in JSON Web Key format as defined in RFC 8037
+export interface PublicKeyJwk {
+  kty: string;
+  crv: string;
+  x: string;
+}
@@ -0,0 +1,226 @@
+import * as fs from "fs";
+import * as path from "path";
+import { Graph } from "./graph";
+import {
+  Action,
+  ActionOptions,
+  DsnpKeys,
+  GraphKeyType,
+  ImportBundle,
+  TimestampPolicy,
+  Update,
+} from "./models";
+import { EnvironmentInterface, EnvironmentType } from "./models/environment";
+
+/// fixtures shared with the core and the other bridges, see `bridge/parity`
+const FIXTURES_DIR = path.join(__dirname, "..", "..", "parity", "fixtures");
+
+/// fixtures use the serde format of the core api types with base64 encoded bytes
+interface FixtureDsnpKeys {
+  dsnpUserId: number;
+  keysHash: number;
+  keys: { index: number; content: string }[];
+}
+
+interface FixtureChainHead {
+  blockNumber: number;
+  blockHash: string;
+}
+
+interface FixtureImportBundle {
+  dsnpUserId: number;
+  schemaId: number;
+  keyPairs: { keyType: keyof typeof GraphKeyType; publicKey: string; secretKey: string }[];
+  dsnpKeys: FixtureDsnpKeys | null;
+  pages: { pageId: number; content: string; contentHash: number }[];
+  chainHead?: FixtureChainHead | null;
+}
+
+interface FixtureUpdate {
+  type: Update["type"];
+  ownerDsnpUserId: number;
+  schemaId?: number;
+  pageId?: number;
+  prevHash: number;
+  payload?: string;
+  chainHead?: FixtureChainHead;
+}
+
+interface FixtureActionOptions extends Omit<ActionOptions, "timestampPolicy"> {
+  timestampPolicy?: keyof typeof TimestampPolicy;
+}
+
+interface Fixture {
+  description: string;
+  imports: FixtureImportBundle[];
+  actions: { [type: string]: any }[];
+  actionOptions?: FixtureActionOptions;
+  exportOptions?: { [option: string]: string };
+  expectedUpdates: FixtureUpdate[];
+}
+
+/// update with the fields compared between the core and the bridge
+interface ComparedUpdate {
+  type: string;
+  ownerDsnpUserId: string;
+  schemaId?: number;
+  pageId?: number;
+  prevHash: number;
+  payload?: string;
+  chainHead?: FixtureChainHead;
+}
+
+const bytes = (encoded: string): Uint8Array => new Uint8Array(Buffer.from(encoded, "base64"));
+
+function dsnpKeysFromFixture(dsnpKeys: FixtureDsnpKeys): DsnpKeys {
+  return {
+    dsnpUserId: String(dsnpKeys.dsnpUserId),
+    keysHash: dsnpKeys.keysHash,
+    keys: dsnpKeys.keys.map((key) => ({ index: key.index, content: bytes(key.content) })),
+  };
+}
+
+function importBundleFromFixture(bundle: FixtureImportBundle): ImportBundle {
+  return {
+    dsnpUserId: String(bundle.dsnpUserId),
+    schemaId: bundle.schemaId,
+    keyPairs: bundle.keyPairs.map((pair) => ({
+      keyType: GraphKeyType[pair.keyType],
+      publicKey: bytes(pair.publicKey),
+      secretKey: bytes(pair.secretKey),
+    })),
+    dsnpKeys: bundle.dsnpKeys ? dsnpKeysFromFixture(bundle.dsnpKeys) : undefined,
+    pages: bundle.pages.map((page) => ({
+      pageId: page.pageId,
+      content: bytes(page.content),
+      contentHash: page.contentHash,
+    })),
+    chainHead: bundle.chainHead
+      ? { blockNumber: bundle.chainHead.blockNumber, blockHash: bytes(bundle.chainHead.blockHash) }
+      : undefined,
+  };
+}
+
+function actionFromFixture(action: { [type: string]: any }): Action {
+  const [type, fields] = Object.entries(action)[0];
+  switch (type) {
+    case "Connect":
+      return {
+        type: "Connect",
+        ownerDsnpUserId: String(fields.ownerDsnpUserId),
+        connection: {
+          dsnpUserId: String(fields.connection.dsnpUserId),
+          schemaId: fields.connection.schemaId,
+        },
+        dsnpKeys: fields.dsnpKeys ? dsnpKeysFromFixture(fields.dsnpKeys) : undefined,
+        preferredPageId: fields.preferredPageId ?? undefined,
+      };
+    case "Disconnect":
+      return {
+        type: "Disconnect",
+        ownerDsnpUserId: String(fields.ownerDsnpUserId),
+        connection: {
+          dsnpUserId: String(fields.connection.dsnpUserId),
+          schemaId: fields.connection.schemaId,
+        },
+      };
+    case "AddGraphKey":
+      return {
+        type: "AddGraphKey",
+        ownerDsnpUserId: String(fields.ownerDsnpUserId),
+        newPublicKey: bytes(fields.newPublicKey),
+      };
+    case "ConnectMutual":
+      return {
+        type: "ConnectMutual",
+        userA: String(fields.userA),
+        userB: String(fields.userB),
+        schemaId: fields.schemaId,
+      };
+    case "MigrateConnection":
+      return {
+        type: "MigrateConnection",
+        ownerDsnpUserId: String(fields.ownerDsnpUserId),
+        fromSchemaId: fields.fromSchemaId,
+        toSchemaId: fields.toSchemaId,
+        dsnpUserId: String(fields.dsnpUserId),
+      };
+    default:
+      throw new Error(`unsupported fixture action ${type}`);
+  }
+}
+
+function actionOptionsFromFixture(options?: FixtureActionOptions): ActionOptions | undefined {
+  if (!options) {
+    return undefined;
+  }
+  return {
+    ...options,
+    timestampPolicy: options.timestampPolicy ? TimestampPolicy[options.timestampPolicy] : undefined,
+  };
+}
+
+function comparedUpdate(update: Update | FixtureUpdate): ComparedUpdate {
+  let payload: string | undefined;
+  if ("payload" in update && update.payload !== undefined) {
+    payload =
+      typeof update.payload === "string"
+        ? update.payload
+        : Buffer.from(update.payload).toString("base64");
+  }
+  let chainHead: FixtureChainHead | undefined;
+  if (update.chainHead) {
+    const blockHash = update.chainHead.blockHash;
+    chainHead = {
+      blockNumber: update.chainHead.blockNumber,
+      blockHash: typeof blockHash === "string" ? blockHash : Buffer.from(blockHash).toString("base64"),
+    };
+  }
+  return {
+    type: update.type,
+    ownerDsnpUserId: String(update.ownerDsnpUserId),
+    schemaId: "schemaId" in update ? update.schemaId : undefined,
+    pageId: "pageId" in update ? update.pageId : undefined,
+    prevHash: update.prevHash,
+    payload,
+    chainHead,
+  };
+}
+
+function sortedUpdates(updates: (Update | FixtureUpdate)[]): ComparedUpdate[] {
+  return updates
+    .map(comparedUpdate)
+    .sort((a, b) => JSON.stringify(a).localeCompare(JSON.stringify(b)));
+}
+
+describe("Parity tests", () => {
+  const fixtures: [string, Fixture][] = fs
+    .readdirSync(FIXTURES_DIR)
+    .filter((file) => file.endsWith(".json"))
+    .sort()
+    .map((file) => [file, JSON.parse(fs.readFileSync(path.join(FIXTURES_DIR, file), "utf8"))]);
+  // export options can not be set through the node bridge yet, so fixtures relying on them are
+  // only covered by the core and FFI runners
+  const supported = fixtures.filter(([, fixture]) => !fixture.exportOptions);
+  const unsupported = fixtures.filter(([, fixture]) => fixture.exportOptions);
+
+  test("fixtures should exist", () => {
+    expect(supported.length).toBeGreaterThan(0);
+  });
+
+  test.each(supported)("%s should export the same updates as the core", (_file, fixture) => {
+    const environment: EnvironmentInterface = { environmentType: EnvironmentType.Mainnet };
+    const graph = new Graph(environment);
+    try {
+      graph.importUserData(fixture.imports.map(importBundleFromFixture));
+      graph.applyActions(fixture.actions.map(actionFromFixture), actionOptionsFromFixture(fixture.actionOptions));
+      const updates = graph.exportUpdates();
+
+      expect(sortedUpdates(updates)).toEqual(sortedUpdates(fixture.expectedUpdates));
+    } finally {
+      graph.freeGraphState();
+    }
+  });
+
+  test.skip.each(unsupported)("%s needs export options which the node bridge does not support", () => {});
+});
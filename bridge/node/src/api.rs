@@ -1,12 +1,15 @@
 //! API implementation for the DSNP Graph SDK using Neon
 //! This crate provides a bridge between the DSNP graph sdk and Node.js.
 //! It is intended to be used as a dependency in the `@projectlibertylabs/graph-sdk` npm package.
-use crate::helper::*;
-use dsnp_graph_config::{Config, ConnectionType, DsnpUserId, GraphKeyType, PrivacyType};
+use crate::{errors::*, helper::*};
+use dsnp_graph_config::{
+	builder::ConfigBuilder, registry::EnvironmentRegistry, Config, ConnectionType, GraphKeyType,
+	PrivacyType, SchemaConfig, SchemaId,
+};
 use dsnp_graph_core::{
 	api::{
 		api::{GraphAPI, GraphState},
-		api_types::{Action, ActionOptions, DsnpKeys, ImportBundle},
+		api_types::{Action, ActionOptions, DsnpKeys, GraphKeyPair, ImportBundle},
 	},
 	dsnp::dsnp_types::DsnpPublicKey,
 	util::transactional_hashmap::Transactional,
@@ -16,6 +19,7 @@ use once_cell::sync::Lazy;
 use std::{
 	collections::HashMap,
 	sync::{Arc, Mutex},
+	time::Duration,
 };
 
 /// Global counter for graph state ids
@@ -26,6 +30,39 @@ static NEXT_GRAPH_STATE_ID: Lazy<Mutex<usize>> = Lazy::new(|| Mutex::new(0));
 static GRAPH_STATES: Lazy<Mutex<HashMap<usize, Arc<Mutex<GraphState>>>>> =
 	Lazy::new(|| Mutex::new(HashMap::new()));
 
+/// Format of the returned dsnp user ids for each graph state
+static USER_ID_FORMATS: Lazy<Mutex<HashMap<usize, UserIdFormat>>> =
+	Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Handle of a graph state which is returned to JS, the graph state gets freed when the handle is
+/// garbage collected unless it is already freed using `freeGraphState`
+pub struct GraphStateHandle {
+	/// unique identifier of the graph state
+	id: usize,
+}
+
+impl Finalize for GraphStateHandle {
+	fn finalize<'a, C: Context<'a>>(self, _: &mut C) {
+		remove_graph_state(self.id);
+	}
+}
+
+/// removes the graph state and its user id format, returns false if it does not exist
+fn remove_graph_state(graph_state_id: usize) -> bool {
+	USER_ID_FORMATS.lock().unwrap().remove(&graph_state_id);
+	GRAPH_STATES.lock().unwrap().remove(&graph_state_id).is_some()
+}
+
+/// returns the format of the returned dsnp user ids for the graph state
+fn user_id_format(graph_state_id: usize) -> UserIdFormat {
+	USER_ID_FORMATS
+		.lock()
+		.unwrap()
+		.get(&graph_state_id)
+		.copied()
+		.unwrap_or_default()
+}
+
 /// Neon implementation of print_hello_graph function
 pub fn print_hello_graph(mut cx: FunctionContext) -> JsResult<JsString> {
 	println!("Hello, Graph!");
@@ -50,6 +87,48 @@ pub fn get_graph_config(mut cx: FunctionContext) -> JsResult<JsObject> {
 	Ok(config_js)
 }
 
+/// Register a config under the given name, to be used with a `Custom` environment
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `name` - environment name
+/// * `config` - Neon Config object
+/// # Returns
+/// * `JsResult<JsBoolean>` - true if the config was registered
+/// # Errors
+/// * Throws a Neon error if the name or config is invalid
+pub fn register_environment_config(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+	let name = cx.argument::<JsString>(0)?.value(&mut cx);
+	let config_obj = cx.argument::<JsObject>(1)?;
+	let config = config_from_js(&mut cx, config_obj)?;
+
+	match EnvironmentRegistry::register(&name, config) {
+		Ok(_) => Ok(cx.boolean(true)),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Register a config built from the Mainnet config with overrides, to be used with a `Custom`
+/// environment
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `name` - environment name
+/// * `overrides_json` - JSON object of the overridden config fields
+/// # Returns
+/// * `JsResult<JsBoolean>` - true if the config was registered
+/// # Errors
+/// * Throws a Neon error if the name, overrides or resulting config are invalid
+pub fn register_environment_config_from_mainnet(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+	let name = cx.argument::<JsString>(0)?.value(&mut cx);
+	let overrides_json = cx.argument::<JsString>(1)?.value(&mut cx);
+
+	match ConfigBuilder::from_mainnet_json(&overrides_json)
+		.and_then(|config| EnvironmentRegistry::register(&name, config))
+	{
+		Ok(_) => Ok(cx.boolean(true)),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
 /// Function to get SchemaId for given ConnectionType and PrivacyType
 /// # Arguments
 /// * `cx` - Neon FunctionContext
@@ -91,19 +170,67 @@ pub fn get_schema_id_from_config(mut cx: FunctionContext) -> JsResult<JsNumber>
 	Ok(cx.number(schema_id as f64))
 }
 
+/// Function to get the ConnectionType, PrivacyType and DSNP version of a SchemaId
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `environment` - Neon Environment object
+/// * `schema_id` - SchemaId number
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject containing the schema config
+/// # Errors
+/// * Throws a Neon error if the SchemaId is not part of the config
+pub fn get_connection_type_from_schema_id(mut cx: FunctionContext) -> JsResult<JsObject> {
+	let environment_obj = cx.argument::<JsObject>(0)?;
+	let environment = unsafe { environment_from_js(&mut cx, environment_obj) }?;
+
+	let schema_id = cx.argument::<JsNumber>(1)?.value(&mut cx);
+	if schema_id.fract() != 0.0 || !(0.0..=SchemaId::MAX as f64).contains(&schema_id) {
+		return cx.throw_error("Invalid schema id")
+	}
+	let schema_id = schema_id as SchemaId;
+
+	let config: &Config = environment.get_config();
+	match (
+		config.get_connection_type_from_schema_id(schema_id),
+		config.get_dsnp_version_from_schema_id(schema_id),
+	) {
+		(Some(connection_type), Some(dsnp_version)) => {
+			let page_capacity = config.get_page_capacity_from_schema_id(schema_id);
+			let schema_config = SchemaConfig { dsnp_version, connection_type, page_capacity };
+			schema_config_to_js(&mut cx, &schema_config)
+		},
+		_ => cx.throw_error(environment.invalid_schema_id(schema_id).to_string()),
+	}
+}
+
 /// Create a new graph state
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `env` - Neon Environment object extracted from context
+/// * `options` - optional `{ useBigInt?: boolean }` object, returning user ids as BigInts if set
 /// # Returns
-/// * `JsResult<JsObject>` - Neon JsObject containing the graph state
+/// * `JsResult<JsBox<GraphStateHandle>>` - handle of the graph state which frees it when garbage
+/// collected
 /// # Errors
 /// * Throws a Neon error if the graph state cannot be created
 /// # Safety
-pub fn initialize_graph_state(mut cx: FunctionContext) -> JsResult<JsNumber> {
+pub fn initialize_graph_state(mut cx: FunctionContext) -> JsResult<JsBox<GraphStateHandle>> {
 	let environment_obj = cx.argument::<JsObject>(0)?;
 	let rust_environment = unsafe { environment_from_js(&mut cx, environment_obj) }?;
-	let graph_state = GraphState::new(rust_environment);
+	let user_id_format = match cx.argument_opt(1) {
+		Some(options) if !options.is_a::<JsUndefined, _>(&mut cx) => {
+			let options = downcast_at::<JsObject, _>(&mut cx, options, "options")?;
+			match get_opt_field::<JsBoolean, _>(&mut cx, options, "options", "useBigInt")? {
+				Some(use_big_int) if use_big_int.value(&mut cx) => UserIdFormat::BigInt,
+				_ => UserIdFormat::String,
+			}
+		},
+		_ => UserIdFormat::String,
+	};
+	let graph_state = match GraphState::try_new(rust_environment) {
+		Ok(graph_state) => graph_state,
+		Err(e) => return cx.throw_error(e.to_string()),
+	};
 
 	// Generate a unique identifier for the graph state
 	let graph_state_id = {
@@ -116,8 +243,9 @@ pub fn initialize_graph_state(mut cx: FunctionContext) -> JsResult<JsNumber> {
 		let mut states = GRAPH_STATES.lock().unwrap();
 		states.insert(graph_state_id, Arc::new(Mutex::new(graph_state)));
 	}
+	USER_ID_FORMATS.lock().unwrap().insert(graph_state_id, user_id_format);
 
-	Ok(cx.number(graph_state_id as f64))
+	Ok(cx.boxed(GraphStateHandle { id: graph_state_id }))
 }
 
 /// Get total count of graph states
@@ -143,8 +271,7 @@ pub fn get_graph_states_count(mut cx: FunctionContext) -> JsResult<JsNumber> {
 /// # Errors
 /// * Throws a Neon error
 pub fn get_graph_users_count(mut cx: FunctionContext) -> JsResult<JsNumber> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
 
 	let states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get(&graph_state_id);
@@ -168,13 +295,9 @@ pub fn get_graph_users_count(mut cx: FunctionContext) -> JsResult<JsNumber> {
 /// # Errors
 /// * Throws a Neon error
 pub fn contains_user_graph(mut cx: FunctionContext) -> JsResult<JsBoolean> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
-	let dsnp_user_id = cx.argument::<JsString>(1)?;
-	let dsnp_user_id = match dsnp_user_id.value(&mut cx).parse::<DsnpUserId>() {
-		Ok(id) => id,
-		Err(_) => return cx.throw_error("Invalid DSNP user id"),
-	};
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
 
 	let states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get(&graph_state_id);
@@ -188,6 +311,36 @@ pub fn contains_user_graph(mut cx: FunctionContext) -> JsResult<JsBoolean> {
 	Ok(cx.boolean(contains_user))
 }
 
+/// Function to check if the graph of a user can be exported, which for private graphs requires
+/// an imported secret key of the user
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `dsnp_user_id` - DSNP user id
+/// * `schema_id` - Schema id of the graph
+/// # Returns
+/// * `JsResult<JsBoolean>` - Neon JsBoolean
+/// # Errors
+/// * Throws a Neon error
+pub fn is_user_graph_writable(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
+	let schema_id = cx.argument::<JsNumber>(2)?;
+	let schema_id = schema_id.value(&mut cx) as u16;
+
+	let states = GRAPH_STATES.lock().unwrap();
+	let graph_state = states.get(&graph_state_id);
+	if graph_state.is_none() {
+		return cx.throw_error("Graph state not found");
+	}
+	let graph_state = graph_state.unwrap();
+	let graph_state = graph_state.lock().unwrap();
+	let writable = graph_state.is_user_graph_writable(&dsnp_user_id, &schema_id);
+
+	Ok(cx.boolean(writable))
+}
+
 /// Function to remove user graph from the graph state
 /// # Arguments
 /// * `cx` - Neon FunctionContext
@@ -198,13 +351,9 @@ pub fn contains_user_graph(mut cx: FunctionContext) -> JsResult<JsBoolean> {
 /// # Errors
 /// * Throws a Neon error
 pub fn remove_user_graph(mut cx: FunctionContext) -> JsResult<JsBoolean> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
-	let dsnp_user_id = cx.argument::<JsString>(1)?;
-	let dsnp_user_id = match dsnp_user_id.value(&mut cx).parse::<DsnpUserId>() {
-		Ok(id) => id,
-		Err(_) => return cx.throw_error("Invalid DSNP user id"),
-	};
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
 
 	let mut states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get_mut(&graph_state_id);
@@ -228,10 +377,9 @@ pub fn remove_user_graph(mut cx: FunctionContext) -> JsResult<JsBoolean> {
 /// # Errors
 /// * Throws a Neon error
 pub fn import_user_data(mut cx: FunctionContext) -> JsResult<JsBoolean> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
 	let payload = cx.argument::<JsArray>(1)?;
-	let rust_payload: Vec<ImportBundle> = import_bundle_from_js(&mut cx, payload)?;
+	let rust_payload: Vec<ImportBundle> = import_bundle_from_js(&mut cx, payload, "payload")?;
 
 	let mut states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get_mut(&graph_state_id);
@@ -257,8 +405,7 @@ pub fn import_user_data(mut cx: FunctionContext) -> JsResult<JsBoolean> {
 /// # Errors
 /// * Throws a Neon error
 pub fn export_graph_updates(mut cx: FunctionContext) -> JsResult<JsArray> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
 
 	let mut states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get_mut(&graph_state_id);
@@ -271,7 +418,47 @@ pub fn export_graph_updates(mut cx: FunctionContext) -> JsResult<JsArray> {
 	let updates = graph_state.export_updates();
 	match updates {
 		Ok(updates) => {
-			let updates_js = updates_to_js(&mut cx, updates)?;
+			let updates_js = updates_to_js(&mut cx, updates, user_id_format(graph_state_id))?;
+			Ok(updates_js)
+		},
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Function to apply actions to the graph state and export the updates in a single call
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `actions` - JSArray containing the actions to apply
+/// # Returns
+/// * `JsResult<JsArray>` - Neon JsArray containing the exported updates
+/// # Errors
+/// * Throws a Neon error
+pub fn apply_actions_and_export(mut cx: FunctionContext) -> JsResult<JsArray> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let actions: Handle<'_, JsArray> = cx.argument::<JsArray>(1)?;
+	let rust_actions: Vec<Action> = actions_from_js(&mut cx, actions, "actions")?;
+	let mut rust_options: Option<ActionOptions> = None;
+	match cx.argument_opt(2) {
+		Some(opt_value) => {
+			let options: Handle<'_, JsObject> = opt_value.downcast_or_throw(&mut cx)?;
+			rust_options = Some(action_options_from_js(&mut cx, options, "options")?);
+		},
+		None => (),
+	};
+
+	let mut states = GRAPH_STATES.lock().unwrap();
+	let graph_state = states.get_mut(&graph_state_id);
+	if graph_state.is_none() {
+		return cx.throw_error("Graph state not found");
+	}
+	let graph_state = graph_state.unwrap();
+	let mut graph_state = graph_state.lock().unwrap();
+
+	let updates = graph_state.apply_actions_and_export(&rust_actions, &rust_options);
+	match updates {
+		Ok(updates) => {
+			let updates_js = updates_to_js(&mut cx, updates, user_id_format(graph_state_id))?;
 			Ok(updates_js)
 		},
 		Err(e) => cx.throw_error(e.to_string()),
@@ -287,13 +474,9 @@ pub fn export_graph_updates(mut cx: FunctionContext) -> JsResult<JsArray> {
 /// # Errors
 /// * Throws a Neon error
 pub fn export_user_graph_updates(mut cx: FunctionContext) -> JsResult<JsArray> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
-	let dsnp_user_id: Handle<'_, JsString> = cx.argument::<JsString>(1)?;
-	let dsnp_user_id = match dsnp_user_id.value(&mut cx).parse::<DsnpUserId>() {
-		Ok(id) => id,
-		Err(_) => return cx.throw_error("Invalid DSNP user id"),
-	};
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
 
 	let mut states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get_mut(&graph_state_id);
@@ -306,7 +489,7 @@ pub fn export_user_graph_updates(mut cx: FunctionContext) -> JsResult<JsArray> {
 	let updates = graph_state.export_user_graph_updates(&dsnp_user_id);
 	match updates {
 		Ok(updates) => {
-			let updates_js = updates_to_js(&mut cx, updates)?;
+			let updates_js = updates_to_js(&mut cx, updates, user_id_format(graph_state_id))?;
 			Ok(updates_js)
 		},
 		Err(e) => cx.throw_error(e.to_string()),
@@ -323,13 +506,9 @@ pub fn export_user_graph_updates(mut cx: FunctionContext) -> JsResult<JsArray> {
 /// # Errors
 /// * Throws a Neon error
 pub fn get_connections_for_user_graph(mut cx: FunctionContext) -> JsResult<JsArray> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
-	let dsnp_user_id: Handle<'_, JsString> = cx.argument::<JsString>(1)?;
-	let dsnp_user_id = match dsnp_user_id.value(&mut cx).parse::<DsnpUserId>() {
-		Ok(id) => id,
-		Err(_) => return cx.throw_error("Invalid DSNP user id"),
-	};
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
 
 	let schema_id = cx.argument::<JsNumber>(2)?;
 	let schema_id = schema_id.value(&mut cx) as u16;
@@ -347,7 +526,8 @@ pub fn get_connections_for_user_graph(mut cx: FunctionContext) -> JsResult<JsArr
 		graph_state.get_connections_for_user_graph(&dsnp_user_id, &schema_id, include_pending);
 	match connections {
 		Ok(connections) => {
-			let connections_js = connections_to_js(&mut cx, connections)?;
+			let connections_js =
+				connections_to_js(&mut cx, connections, user_id_format(graph_state_id))?;
 			Ok(connections_js)
 		},
 		Err(e) => cx.throw_error(e.to_string()),
@@ -364,15 +544,14 @@ pub fn get_connections_for_user_graph(mut cx: FunctionContext) -> JsResult<JsArr
 /// # Errors
 /// * Throws a Neon error
 pub fn apply_actions(mut cx: FunctionContext) -> JsResult<JsBoolean> {
-	let graph_state_id: Handle<'_, JsNumber> = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
 	let actions: Handle<'_, JsArray> = cx.argument::<JsArray>(1)?;
-	let rust_actions: Vec<Action> = actions_from_js(&mut cx, actions)?;
+	let rust_actions: Vec<Action> = actions_from_js(&mut cx, actions, "actions")?;
 	let mut rust_options: Option<ActionOptions> = None;
 	match cx.argument_opt(2) {
 		Some(opt_value) => {
 			let options: Handle<'_, JsObject> = opt_value.downcast_or_throw(&mut cx)?;
-			rust_options = Some(action_options_from_js(&mut cx, options)?);
+			rust_options = Some(action_options_from_js(&mut cx, options, "options")?);
 		},
 		None => (),
 	};
@@ -392,6 +571,132 @@ pub fn apply_actions(mut cx: FunctionContext) -> JsResult<JsBoolean> {
 	}
 }
 
+/// Function to import user data on the Neon thread pool without blocking the event loop
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `payload` - JSON object for `ImportBundle`
+/// # Returns
+/// * `JsResult<JsPromise>` - Neon JsPromise resolving to true once imported
+/// # Errors
+/// * Rejects the promise with a Neon error
+pub fn import_user_data_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let payload = cx.argument::<JsArray>(1)?;
+	let rust_payload: Vec<ImportBundle> = import_bundle_from_js(&mut cx, payload, "payload")?;
+
+	let graph_state = match GRAPH_STATES.lock().unwrap().get(&graph_state_id) {
+		Some(graph_state) => graph_state.clone(),
+		None => return cx.throw_error("Graph state not found"),
+	};
+
+	let promise = cx
+		.task(move || {
+			let mut graph_state = graph_state.lock().unwrap();
+			graph_state.import_users_data(&rust_payload).map_err(|e| e.to_string())
+		})
+		.promise(|mut cx, import_result| match import_result {
+			Ok(_) => Ok(cx.boolean(true)),
+			Err(e) => cx.throw_error(e),
+		});
+	Ok(promise)
+}
+
+/// Function to export graph updates on the Neon thread pool without blocking the event loop
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// # Returns
+/// * `JsResult<JsPromise>` - Neon JsPromise resolving to a JsArray containing the exported updates
+/// # Errors
+/// * Rejects the promise with a Neon error
+pub fn export_graph_updates_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+
+	let graph_state = match GRAPH_STATES.lock().unwrap().get(&graph_state_id) {
+		Some(graph_state) => graph_state.clone(),
+		None => return cx.throw_error("Graph state not found"),
+	};
+	let format = user_id_format(graph_state_id);
+
+	let promise = cx
+		.task(move || {
+			let graph_state = graph_state.lock().unwrap();
+			graph_state.export_updates().map_err(|e| e.to_string())
+		})
+		.promise(move |mut cx, updates| match updates {
+			Ok(updates) => updates_to_js(&mut cx, updates, format),
+			Err(e) => cx.throw_error(e),
+		});
+	Ok(promise)
+}
+
+/// Function to applyActions to the graph state on the Neon thread pool without blocking the
+/// event loop
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `actions` - JSArray containing the actions to apply
+/// * `options` - optional JsObject containing the action options
+/// * `on_progress` - optional JsFunction called with the fraction of processed actions, all
+/// calls are delivered before the promise resolves
+/// # Returns
+/// * `JsResult<JsPromise>` - Neon JsPromise resolving to true once applied
+/// # Errors
+/// * Rejects the promise with a Neon error
+pub fn apply_actions_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let actions: Handle<'_, JsArray> = cx.argument::<JsArray>(1)?;
+	let rust_actions: Vec<Action> = actions_from_js(&mut cx, actions, "actions")?;
+	let rust_options: Option<ActionOptions> = match cx.argument_opt(2) {
+		Some(opt_value) if !opt_value.is_a::<JsUndefined, _>(&mut cx) => {
+			let options: Handle<'_, JsObject> = opt_value.downcast_or_throw(&mut cx)?;
+			Some(action_options_from_js(&mut cx, options, "options")?)
+		},
+		_ => None,
+	};
+	let on_progress: Option<Arc<Root<JsFunction>>> = match cx.argument_opt(3) {
+		Some(opt_value) => {
+			let on_progress: Handle<'_, JsFunction> = opt_value.downcast_or_throw(&mut cx)?;
+			Some(Arc::new(on_progress.root(&mut cx)))
+		},
+		None => None,
+	};
+	let channel = cx.channel();
+
+	let graph_state = match GRAPH_STATES.lock().unwrap().get(&graph_state_id) {
+		Some(graph_state) => graph_state.clone(),
+		None => return cx.throw_error("Graph state not found"),
+	};
+
+	let promise = cx
+		.task(move || {
+			let mut graph_state = graph_state.lock().unwrap();
+			let mut progress_events = vec![];
+			let mut report_progress = |fraction: f64| {
+				if let Some(on_progress) = &on_progress {
+					let on_progress = on_progress.clone();
+					progress_events.push(channel.send(move |mut cx| {
+						let fraction = cx.number(fraction);
+						on_progress.to_inner(&mut cx).call_with(&cx).arg(fraction).exec(&mut cx)
+					}));
+				}
+			};
+			let result = graph_state
+				.apply_actions_with_progress(&rust_actions, &rust_options, &mut report_progress)
+				.map_err(|e| e.to_string());
+			for progress_event in progress_events {
+				let _ = progress_event.join();
+			}
+			result
+		})
+		.promise(|mut cx, apply_result| match apply_result {
+			Ok(_) => Ok(cx.boolean(true)),
+			Err(e) => cx.throw_error(e),
+		});
+	Ok(promise)
+}
+
 /// Function to commit pending changes to a graph state
 /// # Arguments
 /// * `cx` - Neon FunctionContext
@@ -401,8 +706,7 @@ pub fn apply_actions(mut cx: FunctionContext) -> JsResult<JsBoolean> {
 /// Errors
 /// Does not throw
 pub fn commit(mut cx: FunctionContext) -> JsResult<JsBoolean> {
-	let graph_state_id: Handle<'_, JsNumber> = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
 
 	let mut states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get_mut(&graph_state_id);
@@ -426,8 +730,7 @@ pub fn commit(mut cx: FunctionContext) -> JsResult<JsBoolean> {
 /// Errors
 /// Does not throw
 pub fn rollback(mut cx: FunctionContext) -> JsResult<JsBoolean> {
-	let graph_state_id: Handle<'_, JsNumber> = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
 
 	let mut states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get_mut(&graph_state_id);
@@ -452,13 +755,9 @@ pub fn rollback(mut cx: FunctionContext) -> JsResult<JsBoolean> {
 /// # Errors
 /// * Throws a Neon error
 pub fn force_calculate_graphs(mut cx: FunctionContext) -> JsResult<JsArray> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
-	let dsnp_user_id = cx.argument::<JsString>(1)?;
-	let dsnp_user_id = match dsnp_user_id.value(&mut cx).parse::<DsnpUserId>() {
-		Ok(id) => id,
-		Err(_) => return cx.throw_error("Invalid DSNP user id"),
-	};
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
 
 	let mut states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get_mut(&graph_state_id);
@@ -471,24 +770,68 @@ pub fn force_calculate_graphs(mut cx: FunctionContext) -> JsResult<JsArray> {
 	let update = graph_state.force_recalculate_graphs(&dsnp_user_id);
 	match update {
 		Ok(update) => {
-			let update_js = updates_to_js(&mut cx, update)?;
+			let update_js = updates_to_js(&mut cx, update, user_id_format(graph_state_id))?;
 			Ok(update_js)
 		},
 		Err(e) => cx.throw_error(e.to_string()),
 	}
 }
 
+/// Function to export removal of all graph pages of a user
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `dsnp_user_id` - DSNP user id
+/// # Returns
+/// * `JsResult<JsArray>` - Neon JsArray containing the exported updates
+/// # Errors
+/// * Throws a Neon error
+pub fn export_graph_removal(mut cx: FunctionContext) -> JsResult<JsArray> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
+
+	let mut states = GRAPH_STATES.lock().unwrap();
+	let graph_state = states.get_mut(&graph_state_id);
+	if graph_state.is_none() {
+		return cx.throw_error("Graph state not found");
+	}
+	let graph_state = graph_state.unwrap();
+	let graph_state = graph_state.lock().unwrap();
+
+	match graph_state.export_graph_removal(&dsnp_user_id) {
+		Ok(updates) => updates_to_js(&mut cx, updates, user_id_format(graph_state_id)),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
 /// Function to get connections for user from the graph state (getConnectionsWithoutKeys)
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `graph_state_id` - Unique identifier for the graph state
+/// * `include_pending` - Include pending connections
+/// * `cursor` - Optional last dsnp user id of the previous page
+/// * `limit` - Optional maximum number of returned connections
 /// # Returns
-/// * `JsResult<JsArray>` - Neon JsArray containing the connections which is list of DSNPGraphEdge
+/// * `JsResult<JsArray>` - Neon JsArray containing the connections sorted by dsnp user id
 /// # Errors
 /// * Throws a Neon error
 pub fn get_connections_without_keys(mut cx: FunctionContext) -> JsResult<JsArray> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let include_pending = cx.argument::<JsBoolean>(1)?;
+	let include_pending = include_pending.value(&mut cx);
+	let cursor = match cx.argument_opt(2) {
+		Some(cursor) if !cursor.is_a::<JsUndefined, _>(&mut cx) =>
+			Some(dsnp_user_id_from_js(&mut cx, cursor, "cursor")?),
+		_ => None,
+	};
+	let limit = match cx.argument_opt(3) {
+		Some(limit) if !limit.is_a::<JsUndefined, _>(&mut cx) => {
+			let limit: Handle<'_, JsNumber> = limit.downcast_or_throw(&mut cx)?;
+			Some(limit.value(&mut cx) as usize)
+		},
+		_ => None,
+	};
 
 	let mut states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get_mut(&graph_state_id);
@@ -498,13 +841,14 @@ pub fn get_connections_without_keys(mut cx: FunctionContext) -> JsResult<JsArray
 	let graph_state = graph_state.unwrap();
 	let graph_state = graph_state.lock().unwrap();
 
-	let connections = graph_state.get_connections_without_keys();
+	let format = user_id_format(graph_state_id);
+	let connections = graph_state.get_connections_without_keys_page(include_pending, cursor, limit);
 	match connections {
 		Ok(connections) => {
 			let connections_js = cx.empty_array();
 			for (i, connection) in connections.iter().enumerate() {
-				let connection_js_string: Handle<'_, JsString> = cx.string(connection.to_string());
-				connections_js.set(&mut cx, i as u32, connection_js_string)?;
+				let connection_js = dsnp_user_id_to_js(&mut cx, *connection, format);
+				connections_js.set(&mut cx, i as u32, connection_js)?;
 			}
 			Ok(connections_js)
 		},
@@ -517,18 +861,17 @@ pub fn get_connections_without_keys(mut cx: FunctionContext) -> JsResult<JsArray
 /// * `cx` - Neon FunctionContext
 /// * `graph_state_id` - Unique identifier for the graph state
 /// * `dsnp_user_id` - DSNP user id
+/// * `include_pending` - Include pending connections
 /// # Returns
 /// * `JsResult<JsArray>` - Neon JsArray containing the connections which is list of DSNPGraphEdge
 /// # Errors
 /// * Throws a Neon error
 pub fn get_one_sided_private_friendship_connections(mut cx: FunctionContext) -> JsResult<JsArray> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
-	let dsnp_user_id = cx.argument::<JsString>(1)?;
-	let dsnp_user_id = match dsnp_user_id.value(&mut cx).parse::<DsnpUserId>() {
-		Ok(id) => id,
-		Err(_) => return cx.throw_error("Invalid DSNP user id"),
-	};
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
+	let include_pending = cx.argument::<JsBoolean>(2)?;
+	let include_pending = include_pending.value(&mut cx);
 
 	let mut states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get_mut(&graph_state_id);
@@ -538,10 +881,12 @@ pub fn get_one_sided_private_friendship_connections(mut cx: FunctionContext) ->
 	let graph_state = graph_state.unwrap();
 	let graph_state = graph_state.lock().unwrap();
 
-	let connections = graph_state.get_one_sided_private_friendship_connections(&dsnp_user_id);
+	let connections =
+		graph_state.get_one_sided_private_friendship_connections(&dsnp_user_id, include_pending);
 	match connections {
 		Ok(connections) => {
-			let connections_js = connections_to_js(&mut cx, connections)?;
+			let connections_js =
+				connections_to_js(&mut cx, connections, user_id_format(graph_state_id))?;
 			Ok(connections_js)
 		},
 		Err(e) => cx.throw_error(e.to_string()),
@@ -558,13 +903,9 @@ pub fn get_one_sided_private_friendship_connections(mut cx: FunctionContext) ->
 /// # Errors
 /// * Throws a Neon error
 pub fn get_public_keys(mut cx: FunctionContext) -> JsResult<JsArray> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
-	let dsnp_user_id = cx.argument::<JsString>(1)?;
-	let dsnp_user_id = match dsnp_user_id.value(&mut cx).parse::<DsnpUserId>() {
-		Ok(id) => id,
-		Err(_) => return cx.throw_error("Invalid DSNP user id"),
-	};
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
 
 	let mut states = GRAPH_STATES.lock().unwrap();
 	let graph_state = states.get_mut(&graph_state_id);
@@ -584,6 +925,214 @@ pub fn get_public_keys(mut cx: FunctionContext) -> JsResult<JsArray> {
 	}
 }
 
+/// Function to get key count and key page utilization for a user
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `dsnp_user_id` - DSNP user id
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject containing the KeyPageStats
+/// # Errors
+/// * Throws a Neon error
+pub fn get_key_page_stats(mut cx: FunctionContext) -> JsResult<JsObject> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
+
+	let mut states = GRAPH_STATES.lock().unwrap();
+	let graph_state = states.get_mut(&graph_state_id);
+	if graph_state.is_none() {
+		return cx.throw_error("Graph state not found");
+	}
+	let graph_state = graph_state.unwrap();
+	let graph_state = graph_state.lock().unwrap();
+
+	match graph_state.get_key_page_stats(&dsnp_user_id) {
+		Ok(stats) => key_page_stats_to_js(&mut cx, &stats),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Function to get the key id the next published key of a user will be assigned
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `dsnp_user_id` - DSNP user id
+/// # Returns
+/// * `JsResult<JsString>` - Neon JsString containing the key id
+/// # Errors
+/// * Throws a Neon error
+pub fn get_next_key_id(mut cx: FunctionContext) -> JsResult<JsString> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
+
+	let mut states = GRAPH_STATES.lock().unwrap();
+	let graph_state = states.get_mut(&graph_state_id);
+	if graph_state.is_none() {
+		return cx.throw_error("Graph state not found");
+	}
+	let graph_state = graph_state.unwrap();
+	let graph_state = graph_state.lock().unwrap();
+
+	match graph_state.get_next_key_id(&dsnp_user_id) {
+		Ok(key_id) => Ok(cx.string(key_id.to_string())),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Function to select published keys of a user that private pages are sealed to in addition to
+/// the active key when exported using multi-recipient encryption
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `dsnp_user_id` - DSNP user id
+/// * `key_ids` - Ids of the imported public keys of the user, empty removes the recipients
+/// # Returns
+/// * `JsResult<JsBoolean>` - Neon JsBoolean
+/// # Errors
+/// * Throws a Neon error
+pub fn set_additional_recipient_keys(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
+	let key_ids = cx.argument::<JsArray>(2)?;
+	let key_ids = key_ids_from_js(&mut cx, key_ids, "keyIds")?;
+
+	let mut states = GRAPH_STATES.lock().unwrap();
+	let graph_state = states.get_mut(&graph_state_id);
+	if graph_state.is_none() {
+		return cx.throw_error("Graph state not found");
+	}
+	let graph_state = graph_state.unwrap();
+	let mut graph_state = graph_state.lock().unwrap();
+
+	match graph_state.set_additional_recipient_keys(&dsnp_user_id, &key_ids) {
+		Ok(_) => Ok(cx.boolean(true)),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Function to estimate the memory used by a graph state
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject containing the MemoryReport
+/// # Errors
+/// * Throws a Neon error
+pub fn get_memory_footprint(mut cx: FunctionContext) -> JsResult<JsObject> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+
+	let mut states = GRAPH_STATES.lock().unwrap();
+	let graph_state = states.get_mut(&graph_state_id);
+	if graph_state.is_none() {
+		return cx.throw_error("Graph state not found");
+	}
+	let graph_state = graph_state.unwrap();
+	let graph_state = graph_state.lock().unwrap();
+
+	match graph_state.memory_footprint() {
+		Ok(report) => memory_report_to_js(&mut cx, &report, user_id_format(graph_state_id)),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Function to get the statistics of an imported graph of a user
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `dsnp_user_id` - DSNP user id
+/// * `schema_id` - Schema id of the graph
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject containing the UserGraphStats
+/// # Errors
+/// * Throws a Neon error
+pub fn get_user_graph_stats(mut cx: FunctionContext) -> JsResult<JsObject> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
+	let schema_id = cx.argument::<JsNumber>(2)?;
+	let schema_id = schema_id.value(&mut cx) as u16;
+
+	let states = GRAPH_STATES.lock().unwrap();
+	let graph_state = states.get(&graph_state_id);
+	if graph_state.is_none() {
+		return cx.throw_error("Graph state not found");
+	}
+	let graph_state = graph_state.unwrap();
+	let graph_state = graph_state.lock().unwrap();
+
+	match graph_state.get_user_graph_stats(&dsnp_user_id, &schema_id) {
+		Ok(stats) => user_graph_stats_to_js(&mut cx, &stats, user_id_format(graph_state_id)),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Function to get the connections of a graph which are added or removed but not exported yet
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `dsnp_user_id` - DSNP user id
+/// * `schema_id` - Schema id of the graph
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject containing the pending adds and removes
+/// # Errors
+/// * Throws a Neon error
+pub fn get_pending_updates(mut cx: FunctionContext) -> JsResult<JsObject> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let dsnp_user_id = cx.argument::<JsValue>(1)?;
+	let dsnp_user_id = dsnp_user_id_from_js(&mut cx, dsnp_user_id, "dsnpUserId")?;
+	let schema_id = cx.argument::<JsNumber>(2)?;
+	let schema_id = schema_id.value(&mut cx) as u16;
+
+	let states = GRAPH_STATES.lock().unwrap();
+	let graph_state = states.get(&graph_state_id);
+	if graph_state.is_none() {
+		return cx.throw_error("Graph state not found");
+	}
+	let graph_state = graph_state.unwrap();
+	let graph_state = graph_state.lock().unwrap();
+
+	let pending = graph_state
+		.pending_adds(&dsnp_user_id, &schema_id)
+		.and_then(|adds| Ok((adds, graph_state.pending_removes(&dsnp_user_id, &schema_id)?)));
+	match pending {
+		Ok((adds, removes)) =>
+			pending_updates_to_js(&mut cx, &adds, &removes, user_id_format(graph_state_id)),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Function to check if a graph state was created more than the allowed age ago
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `graph_state_id` - Unique identifier for the graph state
+/// * `max_age_secs` - allowed age of the graph state in seconds
+/// # Returns
+/// * `JsResult<JsBoolean>` - Neon JsBoolean
+/// # Errors
+/// * Throws a Neon error
+pub fn is_stale(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
+	let max_age_secs = cx.argument::<JsNumber>(1)?;
+	let max_age_secs = max_age_secs.value(&mut cx);
+	if !max_age_secs.is_finite() || max_age_secs < 0.0 {
+		return cx.throw_range_error("maxAgeSecs should be a non-negative number");
+	}
+
+	let states = GRAPH_STATES.lock().unwrap();
+	let graph_state = states.get(&graph_state_id);
+	if graph_state.is_none() {
+		return cx.throw_error("Graph state not found");
+	}
+	let graph_state = graph_state.unwrap();
+	let graph_state = graph_state.lock().unwrap();
+	let stale = graph_state.assert_fresh(Duration::from_secs(max_age_secs as u64)).is_err();
+
+	Ok(cx.boolean(stale))
+}
+
 /// Function to deserialize DSNP keys
 /// # Arguments
 /// * `cx` - Neon FunctionContext
@@ -594,7 +1143,7 @@ pub fn get_public_keys(mut cx: FunctionContext) -> JsResult<JsArray> {
 /// * Throws a Neon error
 pub fn deserialize_dsnp_keys(mut cx: FunctionContext) -> JsResult<JsArray> {
 	let keys: Handle<'_, JsObject> = cx.argument::<JsObject>(0)?;
-	let rust_keys: DsnpKeys = dsnp_keys_from_js(&mut cx, keys)?;
+	let rust_keys: DsnpKeys = dsnp_keys_from_js(&mut cx, keys, "keys")?;
 	let deserialized_keys: Vec<DsnpPublicKey> =
 		GraphState::deserialize_dsnp_keys(&Some(rust_keys)).unwrap_or_default();
 	let keys_js = public_keys_to_js(&mut cx, deserialized_keys)?;
@@ -622,6 +1171,78 @@ pub fn generate_keypair(mut cx: FunctionContext) -> JsResult<JsObject> {
 	Ok(keypair_js)
 }
 
+/// Function to export the public key of a key pair as a JWK
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `key_pair` - GraphKeyPair JsObject
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject containing the JWK
+/// # Errors
+/// * Throws a Neon error if the public key is not valid
+pub fn public_key_to_jwk(mut cx: FunctionContext) -> JsResult<JsObject> {
+	let key_pair = cx.argument::<JsObject>(0)?;
+	let key_pair = key_pair_from_js(&mut cx, key_pair, "keyPair")?;
+
+	match key_pair.public_key_to_jwk() {
+		Ok(jwk) => public_key_jwk_to_js(&mut cx, &jwk),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Function to export the public key of a key pair as a base58btc multibase string
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `key_pair` - GraphKeyPair JsObject
+/// # Returns
+/// * `JsResult<JsString>` - Neon JsString containing the multibase encoded public key
+/// # Errors
+/// * Throws a Neon error if the public key is not valid
+pub fn public_key_to_multibase(mut cx: FunctionContext) -> JsResult<JsString> {
+	let key_pair = cx.argument::<JsObject>(0)?;
+	let key_pair = key_pair_from_js(&mut cx, key_pair, "keyPair")?;
+
+	match key_pair.public_key_to_multibase() {
+		Ok(encoded) => Ok(cx.string(encoded)),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Function to import a raw public key from a JWK
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `jwk` - JWK JsObject
+/// # Returns
+/// * `JsResult<JsBuffer>` - Neon JsBuffer containing the raw public key
+/// # Errors
+/// * Throws a Neon error if the JWK is not a valid public key
+pub fn public_key_from_jwk(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+	let jwk = cx.argument::<JsObject>(0)?;
+	let jwk = public_key_jwk_from_js(&mut cx, jwk, "jwk")?;
+
+	match GraphKeyPair::public_key_from_jwk(&jwk) {
+		Ok((_, public_key)) => JsBuffer::from_slice(&mut cx, &public_key),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
+/// Function to import a raw public key from a base58btc multibase string
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `encoded` - multibase encoded public key
+/// # Returns
+/// * `JsResult<JsBuffer>` - Neon JsBuffer containing the raw public key
+/// # Errors
+/// * Throws a Neon error if the string is not a valid multibase public key
+pub fn public_key_from_multibase(mut cx: FunctionContext) -> JsResult<JsBuffer> {
+	let encoded = cx.argument::<JsString>(0)?;
+	let encoded = encoded.value(&mut cx);
+
+	match GraphKeyPair::public_key_from_multibase(&encoded) {
+		Ok((_, public_key)) => JsBuffer::from_slice(&mut cx, &public_key),
+		Err(e) => cx.throw_error(e.to_string()),
+	}
+}
+
 /// Function to free the graph state
 /// # Arguments
 /// * `cx` - Neon FunctionContext
@@ -631,17 +1252,11 @@ pub fn generate_keypair(mut cx: FunctionContext) -> JsResult<JsObject> {
 /// # Errors
 /// * Throws a Neon error
 pub fn free_graph_state(mut cx: FunctionContext) -> JsResult<JsBoolean> {
-	let graph_state_id = cx.argument::<JsNumber>(0)?;
-	let graph_state_id = graph_state_id.value(&mut cx) as usize;
+	let graph_state_id = cx.argument::<JsBox<GraphStateHandle>>(0)?.id;
 
-	let mut states = GRAPH_STATES.lock().unwrap();
-	let graph_state = states.remove(&graph_state_id);
-	if graph_state.is_none() {
+	if !remove_graph_state(graph_state_id) {
 		return cx.throw_error("Graph state not found");
 	}
-	let graph_state = graph_state.unwrap();
-	let graph_state = graph_state.lock().unwrap();
-	drop(graph_state);
 
 	Ok(cx.boolean(true))
 }
@@ -651,27 +1266,50 @@ fn main(mut cx: ModuleContext) -> NeonResult<()> {
 	cx.export_function("printHelloGraph", print_hello_graph)?;
 	cx.export_function("getGraphConfig", get_graph_config)?;
 	cx.export_function("getSchemaIdFromConfig", get_schema_id_from_config)?;
+	cx.export_function("getConnectionTypeFromSchemaId", get_connection_type_from_schema_id)?;
+	cx.export_function("registerEnvironmentConfig", register_environment_config)?;
+	cx.export_function(
+		"registerEnvironmentConfigFromMainnet",
+		register_environment_config_from_mainnet,
+	)?;
 	cx.export_function("initializeGraphState", initialize_graph_state)?;
 	cx.export_function("getGraphStatesCount", get_graph_states_count)?;
 	cx.export_function("getGraphUsersCount", get_graph_users_count)?;
 	cx.export_function("containsUserGraph", contains_user_graph)?;
+	cx.export_function("isUserGraphWritable", is_user_graph_writable)?;
 	cx.export_function("removeUserGraph", remove_user_graph)?;
 	cx.export_function("importUserData", import_user_data)?;
 	cx.export_function("exportUpdates", export_graph_updates)?;
 	cx.export_function("exportUserGraphUpdates", export_user_graph_updates)?;
 	cx.export_function("getConnectionsForUserGraph", get_connections_for_user_graph)?;
 	cx.export_function("applyActions", apply_actions)?;
+	cx.export_function("applyActionsAndExport", apply_actions_and_export)?;
+	cx.export_function("importUserDataAsync", import_user_data_async)?;
+	cx.export_function("exportUpdatesAsync", export_graph_updates_async)?;
+	cx.export_function("applyActionsAsync", apply_actions_async)?;
 	cx.export_function("commit", commit)?;
 	cx.export_function("rollback", rollback)?;
 	cx.export_function("forceCalculateGraphs", force_calculate_graphs)?;
+	cx.export_function("exportGraphRemoval", export_graph_removal)?;
 	cx.export_function("getConnectionsWithoutKeys", get_connections_without_keys)?;
 	cx.export_function(
 		"getOneSidedPrivateFriendshipConnections",
 		get_one_sided_private_friendship_connections,
 	)?;
 	cx.export_function("getPublicKeys", get_public_keys)?;
+	cx.export_function("getKeyPageStats", get_key_page_stats)?;
+	cx.export_function("getNextKeyId", get_next_key_id)?;
+	cx.export_function("setAdditionalRecipientKeys", set_additional_recipient_keys)?;
+	cx.export_function("getMemoryFootprint", get_memory_footprint)?;
+	cx.export_function("getUserGraphStats", get_user_graph_stats)?;
+	cx.export_function("getPendingUpdates", get_pending_updates)?;
+	cx.export_function("isStale", is_stale)?;
 	cx.export_function("deserializeDsnpKeys", deserialize_dsnp_keys)?;
 	cx.export_function("generateKeyPair", generate_keypair)?;
+	cx.export_function("publicKeyToJwk", public_key_to_jwk)?;
+	cx.export_function("publicKeyToMultibase", public_key_to_multibase)?;
+	cx.export_function("publicKeyFromJwk", public_key_from_jwk)?;
+	cx.export_function("publicKeyFromMultibase", public_key_from_multibase)?;
 	cx.export_function("freeGraphState", free_graph_state)?;
 	Ok(())
 }
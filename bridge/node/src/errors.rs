@@ -0,0 +1,234 @@
+//! Structured errors thrown when converting JS values into rust types
+//!
+//! Thrown errors are regular JS `Error` objects with two extra properties
+//! - `code` which is one of the `ConversionErrorCode` values
+//! - `path` which is the JSON path of the value that failed, such as `payload[2].dsnpKeys.keys[0].content`
+use dsnp_graph_config::DsnpUserId;
+use neon::{
+	handle::Handle,
+	object::Object,
+	prelude::Context,
+	result::NeonResult,
+	types::{
+		JsArray, JsBigInt, JsBoolean, JsNull, JsNumber, JsObject, JsString, JsTypedArray,
+		JsUndefined, JsValue, Value,
+	},
+};
+
+/// Codes of the errors thrown when a JS value can not be converted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionErrorCode {
+	/// value is missing or its JS type is not the expected one
+	InvalidType,
+	/// value has the expected JS type but its content is not valid
+	InvalidValue,
+}
+
+impl ConversionErrorCode {
+	/// returns the value of the `code` property of the thrown error
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ConversionErrorCode::InvalidType => "ERR_GRAPH_SDK_INVALID_TYPE",
+			ConversionErrorCode::InvalidValue => "ERR_GRAPH_SDK_INVALID_VALUE",
+		}
+	}
+}
+
+/// JS types that can be expected while converting, named the way they show up in error messages
+pub trait ExpectedJsType: Value {
+	/// name of the JS type
+	const NAME: &'static str;
+}
+
+impl ExpectedJsType for JsString {
+	const NAME: &'static str = "string";
+}
+
+impl ExpectedJsType for JsNumber {
+	const NAME: &'static str = "number";
+}
+
+impl ExpectedJsType for JsBoolean {
+	const NAME: &'static str = "boolean";
+}
+
+impl ExpectedJsType for JsObject {
+	const NAME: &'static str = "object";
+}
+
+impl ExpectedJsType for JsArray {
+	const NAME: &'static str = "array";
+}
+
+impl ExpectedJsType for JsTypedArray<u8> {
+	const NAME: &'static str = "Uint8Array";
+}
+
+/// Function to throw a JS `Error` with `code` and `path` properties
+/// # Arguments
+/// * `cx` - Neon Context
+/// * `code` - code of the error
+/// * `path` - JSON path of the value that failed to convert
+/// * `reason` - description of the failure
+/// # Errors
+/// * Always throws the created error
+pub fn throw_conversion_error<'a, C: Context<'a>, T>(
+	cx: &mut C,
+	code: ConversionErrorCode,
+	path: &str,
+	reason: &str,
+) -> NeonResult<T> {
+	let error = cx.error(format!("{} at {}", reason, path))?;
+	let code = cx.string(code.as_str());
+	error.set(cx, "code", code)?;
+	let path = cx.string(path);
+	error.set(cx, "path", path)?;
+	cx.throw(error)
+}
+
+/// Function to downcast a JS value into the expected type
+/// # Arguments
+/// * `cx` - Neon Context
+/// * `value` - JS value to downcast
+/// * `path` - JSON path of the value
+/// # Returns
+/// * `NeonResult<Handle<V>>` - downcasted value
+/// # Errors
+/// * Throws an `InvalidType` conversion error if the value is not of the expected type
+pub fn downcast_at<'a, V: ExpectedJsType, C: Context<'a>>(
+	cx: &mut C,
+	value: Handle<'a, JsValue>,
+	path: &str,
+) -> NeonResult<Handle<'a, V>> {
+	match value.downcast::<V, _>(cx) {
+		Ok(value) => Ok(value),
+		Err(_) => throw_conversion_error(
+			cx,
+			ConversionErrorCode::InvalidType,
+			path,
+			&format!("Expected {}", V::NAME),
+		),
+	}
+}
+
+/// Function to get a required field of a JS object
+/// # Arguments
+/// * `cx` - Neon Context
+/// * `object` - JS object containing the field
+/// * `path` - JSON path of the object
+/// * `key` - name of the field
+/// # Returns
+/// * `NeonResult<Handle<V>>` - value of the field
+/// # Errors
+/// * Throws an `InvalidType` conversion error if the field is missing or has an unexpected type
+pub fn get_field<'a, V: ExpectedJsType, C: Context<'a>>(
+	cx: &mut C,
+	object: Handle<'_, JsObject>,
+	path: &str,
+	key: &str,
+) -> NeonResult<Handle<'a, V>> {
+	let value = object.get_value(cx, key)?;
+	downcast_at(cx, value, &field_path(path, key))
+}
+
+/// Function to get an optional field of a JS object where `undefined` and `null` are treated as missing
+/// # Arguments
+/// * `cx` - Neon Context
+/// * `object` - JS object containing the field
+/// * `path` - JSON path of the object
+/// * `key` - name of the field
+/// # Returns
+/// * `NeonResult<Option<Handle<V>>>` - value of the field if exists
+/// # Errors
+/// * Throws an `InvalidType` conversion error if the field has an unexpected type
+pub fn get_opt_field<'a, V: ExpectedJsType, C: Context<'a>>(
+	cx: &mut C,
+	object: Handle<'_, JsObject>,
+	path: &str,
+	key: &str,
+) -> NeonResult<Option<Handle<'a, V>>> {
+	let value = object.get_value(cx, key)?;
+	if value.is_a::<JsUndefined, _>(cx) || value.is_a::<JsNull, _>(cx) {
+		return Ok(None)
+	}
+	downcast_at(cx, value, &field_path(path, key)).map(Some)
+}
+
+/// Largest integer that a JS number can represent exactly (`Number.MAX_SAFE_INTEGER`)
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Function to convert a dsnp user id passed as a string, a BigInt or a safe integer number
+/// # Arguments
+/// * `cx` - Neon Context
+/// * `value` - JS value of the dsnp user id
+/// * `path` - JSON path of the value
+/// # Returns
+/// * `NeonResult<DsnpUserId>` - parsed dsnp user id
+/// # Errors
+/// * Throws an `InvalidType` or `InvalidValue` conversion error if the value can not be converted
+pub fn dsnp_user_id_from_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	value: Handle<'_, JsValue>,
+	path: &str,
+) -> NeonResult<DsnpUserId> {
+	let dsnp_user_id = if let Ok(value) = value.downcast::<JsString, _>(cx) {
+		value.value(cx).parse::<DsnpUserId>().ok()
+	} else if let Ok(value) = value.downcast::<JsBigInt, _>(cx) {
+		value.to_u64(cx).ok()
+	} else if let Ok(value) = value.downcast::<JsNumber, _>(cx) {
+		// numbers above the safe range might have already lost precision
+		let value = value.value(cx);
+		match value.fract() == 0.0 && (0.0..=MAX_SAFE_INTEGER).contains(&value) {
+			true => Some(value as DsnpUserId),
+			false => None,
+		}
+	} else {
+		return throw_conversion_error(
+			cx,
+			ConversionErrorCode::InvalidType,
+			path,
+			"Expected string, bigint or number",
+		)
+	};
+
+	match dsnp_user_id {
+		Some(dsnp_user_id) => Ok(dsnp_user_id),
+		None => throw_conversion_error(
+			cx,
+			ConversionErrorCode::InvalidValue,
+			path,
+			"Invalid dsnp user id",
+		),
+	}
+}
+
+/// Function to get a required dsnp user id field which is passed as a string, a BigInt or a
+/// safe integer number
+/// # Arguments
+/// * `cx` - Neon Context
+/// * `object` - JS object containing the field
+/// * `path` - JSON path of the object
+/// * `key` - name of the field
+/// # Returns
+/// * `NeonResult<DsnpUserId>` - parsed dsnp user id
+/// # Errors
+/// * Throws an `InvalidType` or `InvalidValue` conversion error if the field can not be parsed
+pub fn get_dsnp_user_id_field<'a, C: Context<'a>>(
+	cx: &mut C,
+	object: Handle<'_, JsObject>,
+	path: &str,
+	key: &str,
+) -> NeonResult<DsnpUserId> {
+	let value = object.get_value(cx, key)?;
+	dsnp_user_id_from_js(cx, value, &field_path(path, key))
+}
+
+/// returns the JSON path of a field inside the object at `path`
+pub fn field_path(path: &str, key: &str) -> String {
+	format!("{}.{}", path, key)
+}
+
+/// returns the JSON path of an element inside the array at `path`
+pub fn element_path(path: &str, index: usize) -> String {
+	format!("{}[{}]", path, index)
+}
@@ -1,11 +1,17 @@
 //! Helper functions for converting between JS and Rust types and vice versa
+use crate::errors::{
+	downcast_at, element_path, field_path, get_dsnp_user_id_field, get_field, get_opt_field,
+	throw_conversion_error, ConversionErrorCode,
+};
 use dsnp_graph_config::{
-	Config, ConnectionType, DsnpUserId, DsnpVersion, Environment, PageId, SchemaConfig, SchemaId,
+	registry::EnvironmentRegistry, Config, ConnectionType, DsnpUserId, DsnpVersion, Environment,
+	PageId, SchemaConfig, SchemaId,
 };
 use dsnp_graph_core::{
 	api::api_types::{
-		Action, ActionOptions, Connection, DsnpKeys, GraphKeyPair, ImportBundle, KeyData, PageData,
-		PageHash, Update,
+		Action, ActionOptions, ChainHead, Connection, DsnpKeys, GraphKeyPair, ImportBundle,
+		KeyData, KeyPageStats, MemoryReport, PageData, PageHash, PublicKeyJwk, TimestampPolicy,
+		Update, UserGraphStats,
 	},
 	dsnp::dsnp_types::{DsnpGraphEdge, DsnpPublicKey},
 };
@@ -14,8 +20,40 @@ use neon::{
 	object::Object,
 	prelude::{Context, FunctionContext},
 	result::{JsResult, NeonResult},
-	types::{buffer::TypedArray, JsArray, JsBoolean, JsNumber, JsObject, JsString, JsTypedArray},
+	types::{
+		buffer::TypedArray, JsArray, JsBigInt, JsBoolean, JsNumber, JsObject, JsString,
+		JsTypedArray, JsValue,
+	},
 };
+use std::num::NonZeroU32;
+
+/// Format of the dsnp user ids returned to JS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UserIdFormat {
+	/// user ids are returned as decimal strings
+	#[default]
+	String,
+	/// user ids are returned as BigInts
+	BigInt,
+}
+
+/// Function to convert a dsnp user id to a JS value of the requested format
+/// # Arguments
+/// * `cx` - Neon Context
+/// * `dsnp_user_id` - dsnp user id
+/// * `format` - format of the returned value
+/// # Returns
+/// * `Handle<JsValue>` - JsString or JsBigInt containing the user id
+pub fn dsnp_user_id_to_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	dsnp_user_id: DsnpUserId,
+	format: UserIdFormat,
+) -> Handle<'a, JsValue> {
+	match format {
+		UserIdFormat::String => cx.string(dsnp_user_id.to_string()).upcast(),
+		UserIdFormat::BigInt => JsBigInt::from_u64(cx, dsnp_user_id).upcast(),
+	}
+}
 
 /// Convert environment from JSObject to Environment
 /// # Arguments
@@ -42,6 +80,11 @@ pub unsafe fn environment_from_js(
 			let config = config_from_js(cx, config)?;
 			Ok(Environment::Dev(config))
 		},
+		"Custom" => {
+			let name: Handle<JsString> = environment_from_js.get(cx, "name")?;
+			let name = name.value(cx);
+			EnvironmentRegistry::environment(&name).or_else(|e| cx.throw_error(e.to_string()))
+		},
 		_ => cx.throw_error("Invalid environment type"),
 	}
 }
@@ -65,12 +108,24 @@ pub fn config_from_js(
 	let max_graph_page_size_bytes = max_graph_page_size_bytes.value(cx) as u32;
 
 	let max_page_id: Handle<JsNumber> = config_from_js.get(cx, "maxPageId")?;
-	let max_page_id = max_page_id.value(cx) as u32;
+	let max_page_id = max_page_id.value(cx);
+	if max_page_id.fract() != 0.0 || !(0.0..=PageId::MAX as f64).contains(&max_page_id) {
+		return cx.throw_range_error(format!("maxPageId should be between 0 and {}", PageId::MAX))
+	}
+	let max_page_id = max_page_id as PageId;
 
 	let max_key_page_size_bytes: Handle<JsNumber> =
 		config_from_js.get(cx, "maxKeyPageSizeBytes")?;
 	let max_key_page_size_bytes = max_key_page_size_bytes.value(cx) as u32;
 
+	let max_update_payload_bytes: Option<Handle<JsNumber>> =
+		config_from_js.get_opt(cx, "maxUpdatePayloadBytes")?;
+	let max_update_payload_bytes = max_update_payload_bytes.map(|v| v.value(cx) as u32);
+
+	let legacy_public_page_order: Option<Handle<JsBoolean>> =
+		config_from_js.get_opt(cx, "legacyPublicPageOrder")?;
+	let legacy_public_page_order = legacy_public_page_order.map_or(false, |v| v.value(cx));
+
 	let schema_map: Handle<JsObject> = config_from_js.get(cx, "schemaMap")?;
 	let schema_map = schema_map_from_js(cx, schema_map)?;
 
@@ -86,6 +141,8 @@ pub fn config_from_js(
 		max_graph_page_size_bytes,
 		max_page_id,
 		max_key_page_size_bytes,
+		max_update_payload_bytes,
+		legacy_public_page_order,
 		schema_map,
 		graph_public_key_schema_id,
 		dsnp_versions,
@@ -136,7 +193,15 @@ pub fn schema_map_from_js(
 			_ => cx.throw_error("Invalid connection type")?,
 		};
 
-		let schema_config = SchemaConfig { dsnp_version, connection_type };
+		let page_capacity: Option<Handle<JsNumber>> = schema_config.get_opt(cx, "pageCapacity")?;
+		let page_capacity = match page_capacity.map(|v| v.value(cx)) {
+			None => None,
+			Some(v) if v.fract() == 0.0 && (1.0..=u32::MAX as f64).contains(&v) =>
+				NonZeroU32::new(v as u32),
+			Some(_) => cx.throw_range_error("pageCapacity should be a positive integer")?,
+		};
+
+		let schema_config = SchemaConfig { dsnp_version, connection_type, page_capacity };
 		schema_map.insert(key_u16, schema_config);
 	}
 
@@ -190,6 +255,16 @@ pub fn config_to_js<'a, C: Context<'a>>(cx: &mut C, config: &Config) -> JsResult
 	let max_key_page_size_bytes = cx.number(config.max_key_page_size_bytes);
 	obj.set(cx, "maxKeyPageSizeBytes", max_key_page_size_bytes)?;
 
+	if let Some(max_update_payload_bytes) = config.max_update_payload_bytes {
+		let max_update_payload_bytes = cx.number(max_update_payload_bytes);
+		obj.set(cx, "maxUpdatePayloadBytes", max_update_payload_bytes)?;
+	}
+
+	if config.legacy_public_page_order {
+		let legacy_public_page_order = cx.boolean(true);
+		obj.set(cx, "legacyPublicPageOrder", legacy_public_page_order)?;
+	}
+
 	let schema_map = cx.empty_object();
 	for (schema_id, schema_config) in &config.schema_map {
 		let schema_id_val = cx.number(*schema_id);
@@ -244,26 +319,34 @@ pub fn schema_config_to_js<'a, C: Context<'a>>(
 		},
 	};
 	obj.set(cx, "privacyType", privacy_type_str)?;
+
+	if let Some(page_capacity) = schema_config.page_capacity {
+		let page_capacity = cx.number(page_capacity.get());
+		obj.set(cx, "pageCapacity", page_capacity)?;
+	}
 	Ok(obj)
 }
 
 /// Function to convert ImportBundle JsObject to ImportBundle struct
 /// # Arguments
 /// * `cx` - Neon FunctionContext
-/// * `import_bundle_js` - Neon JsObject containing the import bundle
+/// * `import_bundle_js` - Neon JsArray containing the import bundle
+/// * `path` - JSON path of the array used in conversion errors
 /// # Returns
 /// * `JsResult<Vec<ImportBundle>>` - rust ImportBundle struct
 /// # Errors
-/// * Throws a Neon error if the import bundle cannot be converted
+/// * Throws a conversion error if the import bundle cannot be converted
 pub fn import_bundle_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	import_bundle_js: Handle<'_, JsArray>,
+	path: &str,
 ) -> NeonResult<Vec<ImportBundle>> {
 	let mut import_bundles: Vec<ImportBundle> = Vec::new();
 	let import_bundle_js = import_bundle_js.to_vec(cx)?;
-	for import_bundle in import_bundle_js {
-		let import_bundle = import_bundle.downcast_or_throw::<JsObject, _>(cx)?;
-		let import_bundle = import_bundle_from_js_object(cx, import_bundle)?;
+	for (index, import_bundle) in import_bundle_js.into_iter().enumerate() {
+		let bundle_path = element_path(path, index);
+		let import_bundle = downcast_at::<JsObject, _>(cx, import_bundle, &bundle_path)?;
+		let import_bundle = import_bundle_from_js_object(cx, import_bundle, &bundle_path)?;
 		import_bundles.push(import_bundle);
 	}
 	Ok(import_bundles)
@@ -273,57 +356,112 @@ pub fn import_bundle_from_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `import_bundle_js` - Neon JsObject containing the import bundle
+/// * `path` - JSON path of the object used in conversion errors
 /// # Returns
 /// * `NeonResult<ImportBundle>` - rust ImportBundle struct
 /// # Errors
-/// * Throws a Neon error if the import bundle cannot be converted
+/// * Throws a conversion error if the import bundle cannot be converted
 pub fn import_bundle_from_js_object<'a, C: Context<'a>>(
 	cx: &mut C,
 	import_bundle_js: Handle<'_, JsObject>,
+	path: &str,
 ) -> NeonResult<ImportBundle> {
-	let dsnp_user_id: Handle<'_, JsString> = import_bundle_js.get(cx, "dsnpUserId")?;
-	let dsnp_user_id = match dsnp_user_id.value(cx).parse::<DsnpUserId>() {
-		Ok(dsnp_user_id) => dsnp_user_id,
-		Err(_) => cx.throw_error("Invalid dsnp user id")?,
-	};
-	let schema_id: Handle<'_, JsNumber> = import_bundle_js.get(cx, "schemaId")?;
+	let dsnp_user_id = get_dsnp_user_id_field(cx, import_bundle_js, path, "dsnpUserId")?;
+	let schema_id: Handle<'_, JsNumber> = get_field(cx, import_bundle_js, path, "schemaId")?;
 	let schema_id = schema_id.value(cx) as SchemaId;
-	let dsnp_keys: Option<Handle<'_, JsObject>> = import_bundle_js.get_opt(cx, "dsnpKeys")?;
+	let dsnp_keys: Option<Handle<'_, JsObject>> =
+		get_opt_field(cx, import_bundle_js, path, "dsnpKeys")?;
 	let dsnp_keys = match dsnp_keys {
-		Some(keys) => Some(dsnp_keys_from_js(cx, keys)?),
+		Some(keys) => Some(dsnp_keys_from_js(cx, keys, &field_path(path, "dsnpKeys"))?),
 		None => None,
 	};
 
-	let key_pairs: Option<Handle<'_, JsArray>> = import_bundle_js.get_opt(cx, "keyPairs")?;
+	let key_pairs: Option<Handle<'_, JsArray>> =
+		get_opt_field(cx, import_bundle_js, path, "keyPairs")?;
 	let key_pairs = match key_pairs {
-		Some(kp) => key_pairs_from_js(cx, kp)?,
+		Some(kp) => key_pairs_from_js(cx, kp, &field_path(path, "keyPairs"))?,
 		None => Vec::new(),
 	};
 
-	let pages: Handle<'_, JsArray> = import_bundle_js.get(cx, "pages")?;
-	let pages: Vec<PageData> = pages_from_js(cx, pages)?;
+	let pages: Handle<'_, JsArray> = get_field(cx, import_bundle_js, path, "pages")?;
+	let pages: Vec<PageData> = pages_from_js(cx, pages, &field_path(path, "pages"))?;
+
+	let chain_head: Option<Handle<'_, JsObject>> =
+		get_opt_field(cx, import_bundle_js, path, "chainHead")?;
+	let chain_head = match chain_head {
+		Some(head) => Some(chain_head_from_js(cx, head, &field_path(path, "chainHead"))?),
+		None => None,
+	};
 
-	let import_bundle = ImportBundle { dsnp_user_id, schema_id, dsnp_keys, key_pairs, pages };
+	let import_bundle =
+		ImportBundle { dsnp_user_id, schema_id, dsnp_keys, key_pairs, pages, chain_head };
 	Ok(import_bundle)
 }
 
+/// Function to convert JsObject of ChainHead to ChainHead
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `chain_head_js` - Neon JsObject
+/// * `path` - JSON path of the object used in conversion errors
+/// # Returns
+/// * `ChainHead` - ChainHead
+/// # Errors
+/// * Throws a conversion error if the chain head cannot be converted
+pub fn chain_head_from_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	chain_head_js: Handle<'_, JsObject>,
+	path: &str,
+) -> NeonResult<ChainHead> {
+	let block_number: Handle<'_, JsNumber> = get_field(cx, chain_head_js, path, "blockNumber")?;
+	let block_number = block_number.value(cx) as u32;
+
+	let block_hash: Handle<'_, JsTypedArray<u8>> = get_field(cx, chain_head_js, path, "blockHash")?;
+	let block_hash = block_hash.as_slice(cx).to_vec();
+	Ok(ChainHead { block_number, block_hash })
+}
+
+/// Function to convert rust ChainHead to JsObject
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `chain_head` - rust ChainHead
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject
+/// # Errors
+/// * Throws a Neon error if the chain head cannot be converted
+pub fn chain_head_to_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	chain_head: &ChainHead,
+) -> JsResult<'a, JsObject> {
+	let obj = cx.empty_object();
+	let block_number = cx.number(chain_head.block_number);
+	obj.set(cx, "blockNumber", block_number)?;
+
+	let mut block_hash = cx.buffer(chain_head.block_hash.len())?;
+	block_hash.as_mut_slice(cx).copy_from_slice(&chain_head.block_hash);
+	obj.set(cx, "blockHash", block_hash)?;
+	Ok(obj)
+}
+
 /// Function to convert JsArray of PageData to `Vec<PageData>`
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `pages_js` - Neon JsArray of JsObjects
+/// * `path` - JSON path of the array used in conversion errors
 /// # Returns
 /// * `Vec<PageData>`
 /// # Errors
-/// * Throws a Neon error if the pages cannot be converted
+/// * Throws a conversion error if the pages cannot be converted
 pub fn pages_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	pages_js: Handle<'_, JsArray>,
+	path: &str,
 ) -> NeonResult<Vec<PageData>> {
 	let mut pages: Vec<PageData> = Vec::new();
 	let pages_js = pages_js.to_vec(cx)?;
-	for page in pages_js {
-		let page = page.downcast_or_throw::<JsObject, _>(cx)?;
-		let page = page_from_js(cx, page)?;
+	for (index, page) in pages_js.into_iter().enumerate() {
+		let page_path = element_path(path, index);
+		let page = downcast_at::<JsObject, _>(cx, page, &page_path)?;
+		let page = page_from_js(cx, page, &page_path)?;
 		pages.push(page);
 	}
 	Ok(pages)
@@ -333,21 +471,23 @@ pub fn pages_from_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `page_js` - Neon JsObject
+/// * `path` - JSON path of the object used in conversion errors
 /// # Returns
 /// * `PageData` - PageData
 /// # Errors
-/// * Throws a Neon error if the page cannot be converted
+/// * Throws a conversion error if the page cannot be converted
 pub fn page_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	page_js: Handle<'_, JsObject>,
+	path: &str,
 ) -> NeonResult<PageData> {
-	let page_id: Handle<'_, JsNumber> = page_js.get(cx, "pageId")?;
+	let page_id: Handle<'_, JsNumber> = get_field(cx, page_js, path, "pageId")?;
 	let page_id = page_id.value(cx) as PageId;
 
-	let content_hash: Handle<'_, JsNumber> = page_js.get(cx, "contentHash")?;
+	let content_hash: Handle<'_, JsNumber> = get_field(cx, page_js, path, "contentHash")?;
 	let content_hash = content_hash.value(cx) as PageHash;
 
-	let content: Handle<'_, JsTypedArray<u8>> = page_js.get(cx, "content")?;
+	let content: Handle<'_, JsTypedArray<u8>> = get_field(cx, page_js, path, "content")?;
 	let content = content.as_slice(cx).to_vec();
 	Ok(PageData { page_id, content_hash, content })
 }
@@ -356,19 +496,22 @@ pub fn page_from_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `key_pairs_js` - Neon JsArray of JsObjects
+/// * `path` - JSON path of the array used in conversion errors
 /// # Returns
 /// * `Vec<GraphKeyPair>`
 /// # Errors
-/// * Throws a Neon error if the key pairs cannot be converted
+/// * Throws a conversion error if the key pairs cannot be converted
 pub fn key_pairs_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	key_pairs_js: Handle<'_, JsArray>,
+	path: &str,
 ) -> NeonResult<Vec<GraphKeyPair>> {
 	let mut key_pairs: Vec<GraphKeyPair> = Vec::new();
 	let key_pairs_js = key_pairs_js.to_vec(cx)?;
-	for key_pair in key_pairs_js {
-		let key_pair = key_pair.downcast_or_throw::<JsObject, _>(cx)?;
-		let key_pair = key_pair_from_js(cx, key_pair)?;
+	for (index, key_pair) in key_pairs_js.into_iter().enumerate() {
+		let key_pair_path = element_path(path, index);
+		let key_pair = downcast_at::<JsObject, _>(cx, key_pair, &key_pair_path)?;
+		let key_pair = key_pair_from_js(cx, key_pair, &key_pair_path)?;
 		key_pairs.push(key_pair);
 	}
 	Ok(key_pairs)
@@ -378,25 +521,32 @@ pub fn key_pairs_from_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `key_pair_js` - Neon JsObject
+/// * `path` - JSON path of the object used in conversion errors
 /// # Returns
 /// * `GraphKeyPair` - GraphKeyPair
 /// # Errors
-/// * Throws a Neon error if the key pair cannot be converted
+/// * Throws a conversion error if the key pair cannot be converted
 pub fn key_pair_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	key_pair_js: Handle<'_, JsObject>,
+	path: &str,
 ) -> NeonResult<GraphKeyPair> {
-	let key_type: Handle<'_, JsNumber> = key_pair_js.get(cx, "keyType")?;
+	let key_type: Handle<'_, JsNumber> = get_field(cx, key_pair_js, path, "keyType")?;
 	let key_type = key_type.value(cx);
 	let key_type = match key_type as u8 {
 		0 => dsnp_graph_config::GraphKeyType::X25519,
-		_ => cx.throw_error("Invalid key type")?,
+		_ => throw_conversion_error(
+			cx,
+			ConversionErrorCode::InvalidValue,
+			&field_path(path, "keyType"),
+			"Invalid key type",
+		)?,
 	};
 
-	let public_key: Handle<'_, JsTypedArray<u8>> = key_pair_js.get(cx, "publicKey")?;
+	let public_key: Handle<'_, JsTypedArray<u8>> = get_field(cx, key_pair_js, path, "publicKey")?;
 	let public_key = public_key.as_slice(cx).to_vec();
 
-	let secret_key: Handle<'_, JsTypedArray<u8>> = key_pair_js.get(cx, "secretKey")?;
+	let secret_key: Handle<'_, JsTypedArray<u8>> = get_field(cx, key_pair_js, path, "secretKey")?;
 	let secret_key = secret_key.as_slice(cx).to_vec();
 
 	Ok(GraphKeyPair { key_type, public_key, secret_key })
@@ -431,29 +581,71 @@ pub fn keypair_to_js<'a, C: Context<'a>>(
 	Ok(obj)
 }
 
+/// Function to convert JsObject of a JWK to PublicKeyJwk
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `jwk_js` - Neon JsObject
+/// * `path` - JSON path of the object used in conversion errors
+/// # Returns
+/// * `PublicKeyJwk` - PublicKeyJwk
+/// # Errors
+/// * Throws a conversion error if the JWK cannot be converted
+pub fn public_key_jwk_from_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	jwk_js: Handle<'_, JsObject>,
+	path: &str,
+) -> NeonResult<PublicKeyJwk> {
+	let kty: Handle<'_, JsString> = get_field(cx, jwk_js, path, "kty")?;
+	let crv: Handle<'_, JsString> = get_field(cx, jwk_js, path, "crv")?;
+	let x: Handle<'_, JsString> = get_field(cx, jwk_js, path, "x")?;
+
+	Ok(PublicKeyJwk { kty: kty.value(cx), crv: crv.value(cx), x: x.value(cx) })
+}
+
+/// Function to convert PublicKeyJwk to JsObject
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `jwk` - PublicKeyJwk
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject
+/// # Errors
+/// * Throws a Neon error if the JWK cannot be converted
+pub fn public_key_jwk_to_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	jwk: &PublicKeyJwk,
+) -> JsResult<'a, JsObject> {
+	let obj = cx.empty_object();
+	let kty = cx.string(&jwk.kty);
+	obj.set(cx, "kty", kty)?;
+	let crv = cx.string(&jwk.crv);
+	obj.set(cx, "crv", crv)?;
+	let x = cx.string(&jwk.x);
+	obj.set(cx, "x", x)?;
+
+	Ok(obj)
+}
+
 /// Function to convert DsnpKeys JsObject to DsnpKeys struct
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `dsnp_keys_js` - Neon JsObject containing the dsnp keys
+/// * `path` - JSON path of the object used in conversion errors
 /// # Returns
 /// * `NeonResult<DsnpKeys>` - rust DsnpKeys struct
 /// # Errors
-/// * Throws a Neon error if the dsnp keys cannot be converted
+/// * Throws a conversion error if the dsnp keys cannot be converted
 pub fn dsnp_keys_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	dsnp_keys_js: Handle<'_, JsObject>,
+	path: &str,
 ) -> NeonResult<DsnpKeys> {
-	let dsnp_user_id: Handle<'_, JsString> = dsnp_keys_js.get(cx, "dsnpUserId")?;
-	let dsnp_user_id = match dsnp_user_id.value(cx).parse::<DsnpUserId>() {
-		Ok(dsnp_user_id) => dsnp_user_id,
-		Err(_) => cx.throw_error("Invalid dsnp user id")?,
-	};
+	let dsnp_user_id = get_dsnp_user_id_field(cx, dsnp_keys_js, path, "dsnpUserId")?;
 
-	let keys_hash: Handle<'_, JsNumber> = dsnp_keys_js.get(cx, "keysHash")?;
+	let keys_hash: Handle<'_, JsNumber> = get_field(cx, dsnp_keys_js, path, "keysHash")?;
 	let keys_hash = keys_hash.value(cx) as PageHash;
 
-	let keys: Handle<'_, JsArray> = dsnp_keys_js.get(cx, "keys")?;
-	let keys: Vec<KeyData> = keys_from_js(cx, keys)?;
+	let keys: Handle<'_, JsArray> = get_field(cx, dsnp_keys_js, path, "keys")?;
+	let keys: Vec<KeyData> = keys_from_js(cx, keys, &field_path(path, "keys"))?;
 
 	Ok(DsnpKeys { dsnp_user_id, keys_hash, keys })
 }
@@ -461,21 +653,24 @@ pub fn dsnp_keys_from_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `keys_js` - Neon JsArray of JsObjects
+/// * `path` - JSON path of the array used in conversion errors
 /// # Returns
 /// * `Vec<KeyData>`
 /// # Errors
-/// * Throws a Neon error if the keys cannot be converted
+/// * Throws a conversion error if the keys cannot be converted
 pub fn keys_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	keys_js: Handle<'_, JsArray>,
+	path: &str,
 ) -> NeonResult<Vec<KeyData>> {
 	let mut keys: Vec<KeyData> = Vec::new();
 	let keys_js = keys_js.to_vec(cx)?;
-	for key in keys_js {
-		let key = key.downcast_or_throw::<JsObject, _>(cx)?;
-		let index: Handle<'_, JsNumber> = key.get(cx, "index")?;
+	for (position, key) in keys_js.into_iter().enumerate() {
+		let key_path = element_path(path, position);
+		let key = downcast_at::<JsObject, _>(cx, key, &key_path)?;
+		let index: Handle<'_, JsNumber> = get_field(cx, key, &key_path, "index")?;
 		let index = index.value(cx) as u16;
-		let content: Handle<'_, JsTypedArray<u8>> = key.get(cx, "content")?;
+		let content: Handle<'_, JsTypedArray<u8>> = get_field(cx, key, &key_path, "content")?;
 		let content = content.as_slice(cx).to_vec();
 		keys.push(KeyData { index, content });
 	}
@@ -486,6 +681,7 @@ pub fn keys_from_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `updates` - rust `Vec<Update>`
+/// * `format` - format of the returned user ids
 /// # Returns
 /// * `JsResult<JsArray>` - Neon JsArray of JsObjects
 /// # Errors
@@ -493,10 +689,11 @@ pub fn keys_from_js<'a, C: Context<'a>>(
 pub fn updates_to_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	updates: Vec<Update>,
+	format: UserIdFormat,
 ) -> JsResult<'a, JsArray> {
 	let updates_js = cx.empty_array();
 	for (i, update) in updates.iter().enumerate() {
-		let update_js = update_to_js(cx, update)?;
+		let update_js = update_to_js(cx, update, format)?;
 		updates_js.set(cx, i as u32, update_js)?;
 	}
 	Ok(updates_js)
@@ -506,6 +703,7 @@ pub fn updates_to_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `update` - rust Update
+/// * `format` - format of the returned user ids
 /// # Returns
 /// * `JsResult<JsObject>` - Neon JsObject
 /// # Errors
@@ -513,13 +711,14 @@ pub fn updates_to_js<'a, C: Context<'a>>(
 pub fn update_to_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	update: &Update,
+	format: UserIdFormat,
 ) -> NeonResult<Handle<'a, JsObject>> {
 	let obj = cx.empty_object();
 	match update {
-		Update::AddKey { owner_dsnp_user_id, prev_hash, payload } => {
+		Update::AddKey { owner_dsnp_user_id, prev_hash, payload, .. } => {
 			let type_update = cx.string("AddKey");
 			obj.set(cx, "type", type_update)?;
-			let owner_dsnp_user_id = cx.string(owner_dsnp_user_id.to_string());
+			let owner_dsnp_user_id = dsnp_user_id_to_js(cx, *owner_dsnp_user_id, format);
 			obj.set(cx, "ownerDsnpUserId", owner_dsnp_user_id)?;
 
 			let prev_hash = cx.number(*prev_hash);
@@ -530,10 +729,12 @@ pub fn update_to_js<'a, C: Context<'a>>(
 			payload_buffer.as_mut_slice(cx).copy_from_slice(&payload);
 			obj.set(cx, "payload", payload_buffer)?;
 		},
-		Update::PersistPage { owner_dsnp_user_id, schema_id, page_id, prev_hash, payload } => {
+		Update::PersistPage {
+			owner_dsnp_user_id, schema_id, page_id, prev_hash, payload, ..
+		} => {
 			let type_update = cx.string("PersistPage");
 			obj.set(cx, "type", type_update)?;
-			let owner_dsnp_user_id = cx.string(owner_dsnp_user_id.to_string());
+			let owner_dsnp_user_id = dsnp_user_id_to_js(cx, *owner_dsnp_user_id, format);
 			obj.set(cx, "ownerDsnpUserId", owner_dsnp_user_id)?;
 
 			let schema_id = cx.number(*schema_id);
@@ -551,10 +752,10 @@ pub fn update_to_js<'a, C: Context<'a>>(
 			payload_buffer.as_mut_slice(cx).copy_from_slice(&payload);
 			obj.set(cx, "payload", payload_buffer)?;
 		},
-		Update::DeletePage { owner_dsnp_user_id, schema_id, page_id, prev_hash } => {
+		Update::DeletePage { owner_dsnp_user_id, schema_id, page_id, prev_hash, .. } => {
 			let type_update = cx.string("DeletePage");
 			obj.set(cx, "type", type_update)?;
-			let owner_dsnp_user_id = cx.string(owner_dsnp_user_id.to_string());
+			let owner_dsnp_user_id = dsnp_user_id_to_js(cx, *owner_dsnp_user_id, format);
 			obj.set(cx, "ownerDsnpUserId", owner_dsnp_user_id)?;
 
 			let schema_id = cx.number(*schema_id);
@@ -567,6 +768,14 @@ pub fn update_to_js<'a, C: Context<'a>>(
 			obj.set(cx, "prevHash", prev_hash)?;
 		},
 	};
+	let idempotency_key = update.idempotency_key();
+	let mut idempotency_key_buffer = cx.buffer(idempotency_key.len())?;
+	idempotency_key_buffer.as_mut_slice(cx).copy_from_slice(&idempotency_key);
+	obj.set(cx, "idempotencyKey", idempotency_key_buffer)?;
+	if let Some(chain_head) = update.chain_head() {
+		let chain_head = chain_head_to_js(cx, chain_head)?;
+		obj.set(cx, "chainHead", chain_head)?;
+	}
 	Ok(obj)
 }
 
@@ -574,6 +783,7 @@ pub fn update_to_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `edges` - `Vec<DsnpGraphEdge>`
+/// * `format` - format of the returned user ids
 /// # Returns
 /// * `JsResult<JsArray>` - Neon JsArray of JsObjects
 /// # Errors
@@ -581,10 +791,11 @@ pub fn update_to_js<'a, C: Context<'a>>(
 pub fn connections_to_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	edges: Vec<DsnpGraphEdge>,
+	format: UserIdFormat,
 ) -> JsResult<'a, JsArray> {
 	let edges_js = cx.empty_array();
 	for (i, edge) in edges.iter().enumerate() {
-		let edge_js = connection_to_js(cx, edge)?;
+		let edge_js = connection_to_js(cx, edge, format)?;
 		edges_js.set(cx, i as u32, edge_js)?;
 	}
 	Ok(edges_js)
@@ -594,6 +805,7 @@ pub fn connections_to_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `edge` - DsnpGraphEdge
+/// * `format` - format of the returned user id
 /// # Returns
 /// * `JsResult<JsObject>` - Neon JsObject
 /// # Errors
@@ -601,9 +813,10 @@ pub fn connections_to_js<'a, C: Context<'a>>(
 pub fn connection_to_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	edge: &DsnpGraphEdge,
+	format: UserIdFormat,
 ) -> JsResult<'a, JsObject> {
 	let obj = cx.empty_object();
-	let dsnp_user_id = cx.string(edge.user_id.to_string());
+	let dsnp_user_id = dsnp_user_id_to_js(cx, edge.user_id, format);
 	obj.set(cx, "userId", dsnp_user_id)?;
 	let since: Handle<'_, JsNumber> = cx.number(edge.since as f64);
 	obj.set(cx, "since", since)?;
@@ -614,86 +827,163 @@ pub fn connection_to_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `actions_js` - Neon JsArray of JsObjects
+/// * `path` - JSON path of the array used in conversion errors
 /// # Returns
 /// * `Vec<Action>`
 /// # Errors
-/// * Throws a Neon error if the actions cannot be converted
+/// * Throws a conversion error if the actions cannot be converted
 pub fn actions_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	actions_js: Handle<'_, JsArray>,
+	path: &str,
 ) -> NeonResult<Vec<Action>> {
 	let mut actions: Vec<Action> = Vec::new();
 	let actions_vec = actions_js.to_vec(cx)?;
-	for action in actions_vec {
-		let action = action.downcast_or_throw::<JsObject, _>(cx)?;
-		let action = action_from_js(cx, action)?;
+	for (index, action) in actions_vec.into_iter().enumerate() {
+		let action_path = element_path(path, index);
+		let action = downcast_at::<JsObject, _>(cx, action, &action_path)?;
+		let action = action_from_js(cx, action, &action_path)?;
 		actions.push(action);
 	}
 	Ok(actions)
 }
 
+/// Function to convert JsArray of key ids passed as strings to `Vec<u64>`
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `key_ids_js` - Neon JsArray of JsStrings
+/// * `path` - JSON path of the array used in conversion errors
+/// # Returns
+/// * `Vec<u64>` - key ids
+/// # Errors
+/// * Throws a conversion error if a key id cannot be converted
+pub fn key_ids_from_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	key_ids_js: Handle<'_, JsArray>,
+	path: &str,
+) -> NeonResult<Vec<u64>> {
+	let mut key_ids: Vec<u64> = Vec::new();
+	let key_ids_vec = key_ids_js.to_vec(cx)?;
+	for (index, key_id) in key_ids_vec.into_iter().enumerate() {
+		let key_id_path = element_path(path, index);
+		let key_id = downcast_at::<JsString, _>(cx, key_id, &key_id_path)?.value(cx);
+		match key_id.parse::<u64>() {
+			Ok(key_id) => key_ids.push(key_id),
+			Err(_) =>
+				return throw_conversion_error(
+					cx,
+					ConversionErrorCode::InvalidValue,
+					&key_id_path,
+					"Invalid key id",
+				),
+		}
+	}
+	Ok(key_ids)
+}
+
+/// returns the optional since value of an add action
+fn get_since_field<'a, C: Context<'a>>(
+	cx: &mut C,
+	action_js: Handle<'_, JsObject>,
+	path: &str,
+) -> NeonResult<Option<u64>> {
+	let since: Option<Handle<'_, JsNumber>> = get_opt_field(cx, action_js, path, "since")?;
+	Ok(since.map(|v| v.value(cx) as u64))
+}
+
 /// Function to convert JsObject of Action to Action
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `action_js` - Neon JsObject
+/// * `path` - JSON path of the object used in conversion errors
 /// # Returns
 /// * `Action` - Action
 /// # Errors
-/// * Throws a Neon error if the action cannot be converted
+/// * Throws a conversion error if the action cannot be converted
 pub fn action_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	action_js: Handle<'_, JsObject>,
+	path: &str,
 ) -> NeonResult<Action> {
-	let action_type: Handle<'_, JsString> = action_js.get(cx, "type")?;
+	let action_type: Handle<'_, JsString> = get_field(cx, action_js, path, "type")?;
 	let action_type = action_type.value(cx);
 	let action = match action_type.as_str() {
 		"Connect" => {
-			let owner_dsnp_user_id: Handle<'_, JsString> = action_js.get(cx, "ownerDsnpUserId")?;
-			let owner_dsnp_user_id = match owner_dsnp_user_id.value(cx).parse::<DsnpUserId>() {
-				Ok(owner_dsnp_user_id) => owner_dsnp_user_id,
-				Err(_) => cx.throw_error("Invalid dsnp user id")?,
+			let owner_dsnp_user_id =
+				get_dsnp_user_id_field(cx, action_js, path, "ownerDsnpUserId")?;
+
+			let dsnp_keys: Option<Handle<'_, JsObject>> =
+				get_opt_field(cx, action_js, path, "dsnpKeys")?;
+			let dsnp_keys: Option<DsnpKeys> = match dsnp_keys {
+				Some(dsnp_keys) =>
+					Some(dsnp_keys_from_js(cx, dsnp_keys, &field_path(path, "dsnpKeys"))?),
+				None => None,
 			};
+			let connection: Handle<'_, JsObject> = get_field(cx, action_js, path, "connection")?;
+			let connection: Connection =
+				connection_from_js(cx, connection, &field_path(path, "connection"))?;
 
-			let dsnp_keys: Option<DsnpKeys> = match action_js.get_opt(cx, "dsnpKeys") {
-				Ok(dsnp_keys) => match dsnp_keys {
-					Some(dsnp_keys) => {
-						let dsnp_keys: DsnpKeys = dsnp_keys_from_js(cx, dsnp_keys)?;
-						Some(dsnp_keys)
-					},
-					None => None,
-				},
-				Err(_) => None,
-			};
-			let connection: Handle<'_, JsObject> = action_js.get(cx, "connection")?;
-			let connection: Connection = connection_from_js(cx, connection)?;
+			let preferred_page_id: Option<Handle<'_, JsNumber>> =
+				get_opt_field(cx, action_js, path, "preferredPageId")?;
+			let preferred_page_id = preferred_page_id.map(|v| v.value(cx) as PageId);
+			let since = get_since_field(cx, action_js, path)?;
 
-			Action::Connect { owner_dsnp_user_id, dsnp_keys, connection }
+			Action::Connect { owner_dsnp_user_id, dsnp_keys, connection, preferred_page_id, since }
 		},
 		"Disconnect" => {
-			let owner_dsnp_user_id: Handle<'_, JsString> = action_js.get(cx, "ownerDsnpUserId")?;
-			let owner_dsnp_user_id = match owner_dsnp_user_id.value(cx).parse::<DsnpUserId>() {
-				Ok(owner_dsnp_user_id) => owner_dsnp_user_id,
-				Err(_) => cx.throw_error("Invalid dsnp user id")?,
-			};
+			let owner_dsnp_user_id =
+				get_dsnp_user_id_field(cx, action_js, path, "ownerDsnpUserId")?;
 
-			let connection: Handle<'_, JsObject> = action_js.get(cx, "connection")?;
-			let connection: Connection = connection_from_js(cx, connection)?;
+			let connection: Handle<'_, JsObject> = get_field(cx, action_js, path, "connection")?;
+			let connection: Connection =
+				connection_from_js(cx, connection, &field_path(path, "connection"))?;
 
 			Action::Disconnect { owner_dsnp_user_id, connection }
 		},
 		"AddGraphKey" => {
-			let owner_dsnp_user_id: Handle<'_, JsString> = action_js.get(cx, "ownerDsnpUserId")?;
-			let owner_dsnp_user_id = match owner_dsnp_user_id.value(cx).parse::<DsnpUserId>() {
-				Ok(owner_dsnp_user_id) => owner_dsnp_user_id,
-				Err(_) => cx.throw_error("Invalid dsnp user id")?,
-			};
+			let owner_dsnp_user_id =
+				get_dsnp_user_id_field(cx, action_js, path, "ownerDsnpUserId")?;
 
-			let new_public_key: Handle<'_, JsTypedArray<u8>> = action_js.get(cx, "newPublicKey")?;
+			let new_public_key: Handle<'_, JsTypedArray<u8>> =
+				get_field(cx, action_js, path, "newPublicKey")?;
 			let new_public_key = new_public_key.as_slice(cx).to_vec();
 
 			Action::AddGraphKey { owner_dsnp_user_id, new_public_key }
 		},
-		_ => cx.throw_error("Invalid action type")?,
+		"ConnectMutual" => {
+			let user_a = get_dsnp_user_id_field(cx, action_js, path, "userA")?;
+			let user_b = get_dsnp_user_id_field(cx, action_js, path, "userB")?;
+			let schema_id: Handle<'_, JsNumber> = get_field(cx, action_js, path, "schemaId")?;
+			let schema_id = schema_id.value(cx) as SchemaId;
+			let since = get_since_field(cx, action_js, path)?;
+
+			Action::ConnectMutual { user_a, user_b, schema_id, since }
+		},
+		"MigrateConnection" => {
+			let owner_dsnp_user_id =
+				get_dsnp_user_id_field(cx, action_js, path, "ownerDsnpUserId")?;
+			let from_schema_id: Handle<'_, JsNumber> =
+				get_field(cx, action_js, path, "fromSchemaId")?;
+			let from_schema_id = from_schema_id.value(cx) as SchemaId;
+			let to_schema_id: Handle<'_, JsNumber> = get_field(cx, action_js, path, "toSchemaId")?;
+			let to_schema_id = to_schema_id.value(cx) as SchemaId;
+			let dsnp_user_id = get_dsnp_user_id_field(cx, action_js, path, "dsnpUserId")?;
+			let since = get_since_field(cx, action_js, path)?;
+
+			Action::MigrateConnection {
+				owner_dsnp_user_id,
+				from_schema_id,
+				to_schema_id,
+				dsnp_user_id,
+				since,
+			}
+		},
+		_ => throw_conversion_error(
+			cx,
+			ConversionErrorCode::InvalidValue,
+			&field_path(path, "type"),
+			"Invalid action type",
+		)?,
 	};
 	Ok(action)
 }
@@ -702,37 +992,80 @@ pub fn action_from_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `action_options_js` - Neon JsObject
+/// * `path` - JSON path of the object used in conversion errors
 /// # Returns
 /// * `ActionOption`
 /// # Errors
-/// * Throws a Neon error if the object cannot be converted
+/// * Throws a conversion error if the object cannot be converted
 pub fn action_options_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	action_options_js: Handle<'_, JsObject>,
+	path: &str,
 ) -> NeonResult<ActionOptions> {
 	let ignore_existing_connections: Option<Handle<'_, JsBoolean>> =
-		action_options_js.get_opt(cx, "ignoreExistingConnections")?;
+		get_opt_field(cx, action_options_js, path, "ignoreExistingConnections")?;
 	let ignore_existing_connections = match ignore_existing_connections {
 		Some(ignore) => ignore.value(cx),
 		None => false,
 	};
 	let ignore_missing_connections: Option<Handle<'_, JsBoolean>> =
-		action_options_js.get_opt(cx, "ignoreMissingConnections")?;
+		get_opt_field(cx, action_options_js, path, "ignoreMissingConnections")?;
 	let ignore_missing_connections = match ignore_missing_connections {
 		Some(ignore) => ignore.value(cx),
 		None => false,
 	};
 	let disable_auto_commit: Option<Handle<'_, JsBoolean>> =
-		action_options_js.get_opt(cx, "disableAutoCommit")?;
+		get_opt_field(cx, action_options_js, path, "disableAutoCommit")?;
 	let disable_auto_commit = match disable_auto_commit {
 		Some(disable) => disable.value(cx),
 		None => false,
 	};
+	let collect_errors: Option<Handle<'_, JsBoolean>> =
+		get_opt_field(cx, action_options_js, path, "collectErrors")?;
+	let collect_errors = match collect_errors {
+		Some(collect) => collect.value(cx),
+		None => false,
+	};
+	let require_counterpart_keys: Option<Handle<'_, JsBoolean>> =
+		get_opt_field(cx, action_options_js, path, "requireCounterpartKeys")?;
+	let require_counterpart_keys = match require_counterpart_keys {
+		Some(require) => require.value(cx),
+		None => false,
+	};
+
+	let max_batch_size: Option<Handle<'_, JsNumber>> =
+		get_opt_field(cx, action_options_js, path, "maxBatchSize")?;
+	let max_batch_size = match max_batch_size.map(|v| v.value(cx)) {
+		None => None,
+		Some(v) if v.fract() == 0.0 && (1.0..=u32::MAX as f64).contains(&v) =>
+			NonZeroU32::new(v as u32),
+		Some(_) =>
+			cx.throw_range_error(format!("{}.maxBatchSize should be a positive integer", path))?,
+	};
+
+	let timestamp_policy: Option<Handle<'_, JsNumber>> =
+		get_opt_field(cx, action_options_js, path, "timestampPolicy")?;
+	let timestamp_policy = match timestamp_policy.map(|v| v.value(cx)) {
+		None => TimestampPolicy::default(),
+		Some(v) if v == 0.0 => TimestampPolicy::CallerProvided,
+		Some(v) if v == 1.0 => TimestampPolicy::Now,
+		Some(v) if v == 2.0 => TimestampPolicy::Zero,
+		Some(_) => throw_conversion_error(
+			cx,
+			ConversionErrorCode::InvalidValue,
+			&field_path(path, "timestampPolicy"),
+			"Invalid timestamp policy",
+		)?,
+	};
 
 	return Ok(ActionOptions {
 		ignore_existing_connections,
 		ignore_missing_connections,
 		disable_auto_commit,
+		collect_errors,
+		require_counterpart_keys,
+		max_batch_size,
+		timestamp_policy,
 	});
 }
 
@@ -740,21 +1073,19 @@ pub fn action_options_from_js<'a, C: Context<'a>>(
 /// # Arguments
 /// * `cx` - Neon FunctionContext
 /// * `connection_js` - Neon JsObject
+/// * `path` - JSON path of the object used in conversion errors
 /// # Returns
 /// * `Connection` - Connection
 /// # Errors
-/// * Throws a Neon error if the connection cannot be converted
+/// * Throws a conversion error if the connection cannot be converted
 pub fn connection_from_js<'a, C: Context<'a>>(
 	cx: &mut C,
 	connection_js: Handle<'_, JsObject>,
+	path: &str,
 ) -> NeonResult<Connection> {
-	let dsnp_user_id: Handle<'_, JsString> = connection_js.get(cx, "dsnpUserId")?;
-	let dsnp_user_id = match dsnp_user_id.value(cx).parse::<DsnpUserId>() {
-		Ok(dsnp_user_id) => dsnp_user_id,
-		Err(_) => cx.throw_error("Invalid dsnp user id")?,
-	};
+	let dsnp_user_id = get_dsnp_user_id_field(cx, connection_js, path, "dsnpUserId")?;
 
-	let schema_id: Handle<'_, JsNumber> = connection_js.get(cx, "schemaId")?;
+	let schema_id: Handle<'_, JsNumber> = get_field(cx, connection_js, path, "schemaId")?;
 	let schema_id = schema_id.value(cx) as SchemaId;
 	Ok(Connection { dsnp_user_id, schema_id })
 }
@@ -804,3 +1135,154 @@ pub fn public_key_to_js<'a, C: Context<'a>>(
 
 	Ok(obj)
 }
+
+/// Function to convert KeyPageStats to JsObject
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `stats` - KeyPageStats
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject
+/// # Errors
+/// * Throws a Neon error if the stats cannot be converted
+pub fn key_page_stats_to_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	stats: &KeyPageStats,
+) -> NeonResult<Handle<'a, JsObject>> {
+	let obj = cx.empty_object();
+
+	let key_count = cx.number(stats.key_count);
+	obj.set(cx, "keyCount", key_count)?;
+
+	let used_bytes = cx.number(stats.used_bytes);
+	obj.set(cx, "usedBytes", used_bytes)?;
+
+	let max_bytes = cx.number(stats.max_bytes);
+	obj.set(cx, "maxBytes", max_bytes)?;
+
+	Ok(obj)
+}
+
+/// Function to convert MemoryReport to JsObject
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `report` - MemoryReport
+/// * `format` - format of the returned user ids
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject
+/// # Errors
+/// * Throws a Neon error if the report cannot be converted
+pub fn memory_report_to_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	report: &MemoryReport,
+	format: UserIdFormat,
+) -> NeonResult<Handle<'a, JsObject>> {
+	let obj = cx.empty_object();
+
+	let user_graph_bytes = cx.empty_array();
+	for (i, (dsnp_user_id, bytes)) in report.user_graph_bytes.iter().enumerate() {
+		let usage = cx.empty_object();
+		let dsnp_user_id = dsnp_user_id_to_js(cx, *dsnp_user_id, format);
+		usage.set(cx, "dsnpUserId", dsnp_user_id)?;
+		let bytes = cx.number(*bytes as f64);
+		usage.set(cx, "bytes", bytes)?;
+		user_graph_bytes.set(cx, i as u32, usage)?;
+	}
+	obj.set(cx, "userGraphBytes", user_graph_bytes)?;
+
+	let shared_state_bytes = cx.number(report.shared_state_bytes as f64);
+	obj.set(cx, "sharedStateBytes", shared_state_bytes)?;
+
+	let pending_update_bytes = cx.number(report.pending_update_bytes as f64);
+	obj.set(cx, "pendingUpdateBytes", pending_update_bytes)?;
+
+	let total_bytes = cx.number(report.total_bytes() as f64);
+	obj.set(cx, "totalBytes", total_bytes)?;
+
+	Ok(obj)
+}
+
+/// Function to convert UserGraphStats to JsObject
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `stats` - UserGraphStats
+/// * `format` - format of the returned user id
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject
+/// # Errors
+/// * Throws a Neon error if the stats cannot be converted
+pub fn user_graph_stats_to_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	stats: &UserGraphStats,
+	format: UserIdFormat,
+) -> NeonResult<Handle<'a, JsObject>> {
+	let obj = cx.empty_object();
+
+	let dsnp_user_id = dsnp_user_id_to_js(cx, stats.dsnp_user_id, format);
+	obj.set(cx, "dsnpUserId", dsnp_user_id)?;
+
+	let schema_id = cx.number(stats.schema_id);
+	obj.set(cx, "schemaId", schema_id)?;
+
+	let decrypted_pages = cx.number(stats.decrypted_pages as f64);
+	obj.set(cx, "decryptedPages", decrypted_pages)?;
+
+	let connections = cx.number(stats.connections as f64);
+	obj.set(cx, "connections", connections)?;
+
+	let page_hashes = cx.empty_array();
+	for (i, (page_id, content_hash)) in stats.page_hashes.iter().enumerate() {
+		let page_hash = cx.empty_object();
+		let page_id = cx.number(*page_id);
+		page_hash.set(cx, "pageId", page_id)?;
+		let content_hash = cx.number(*content_hash);
+		page_hash.set(cx, "contentHash", content_hash)?;
+		page_hashes.set(cx, i as u32, page_hash)?;
+	}
+	obj.set(cx, "pageHashes", page_hashes)?;
+
+	let encrypted_pages = cx.empty_array();
+	for (i, metadata) in stats.encrypted_pages.iter().enumerate() {
+		let page = cx.empty_object();
+		let page_id = cx.number(metadata.page_id);
+		page.set(cx, "pageId", page_id)?;
+		let content_size = cx.number(metadata.content_size as f64);
+		page.set(cx, "contentSize", content_size)?;
+		let content_hash = cx.number(metadata.content_hash);
+		page.set(cx, "contentHash", content_hash)?;
+		encrypted_pages.set(cx, i as u32, page)?;
+	}
+	obj.set(cx, "encryptedPages", encrypted_pages)?;
+
+	let is_metadata_only = cx.boolean(stats.is_metadata_only());
+	obj.set(cx, "isMetadataOnly", is_metadata_only)?;
+
+	Ok(obj)
+}
+
+/// Function to convert the pending added and removed connections of a graph to JsObject
+/// # Arguments
+/// * `cx` - Neon FunctionContext
+/// * `adds` - users of pending added connections
+/// * `removes` - users of pending removed connections
+/// * `format` - format of the returned user ids
+/// # Returns
+/// * `JsResult<JsObject>` - Neon JsObject
+/// # Errors
+/// * Throws a Neon error if the users cannot be converted
+pub fn pending_updates_to_js<'a, C: Context<'a>>(
+	cx: &mut C,
+	adds: &[DsnpUserId],
+	removes: &[DsnpUserId],
+	format: UserIdFormat,
+) -> NeonResult<Handle<'a, JsObject>> {
+	let obj = cx.empty_object();
+	for (name, dsnp_user_ids) in [("adds", adds), ("removes", removes)] {
+		let array = cx.empty_array();
+		for (i, dsnp_user_id) in dsnp_user_ids.iter().enumerate() {
+			let dsnp_user_id = dsnp_user_id_to_js(cx, *dsnp_user_id, format);
+			array.set(cx, i as u32, dsnp_user_id)?;
+		}
+		obj.set(cx, name, array)?;
+	}
+	Ok(obj)
+}
@@ -4,5 +4,7 @@
 //! It is intended to be used as a dependency in the `@projectlibertylabs/graph-sdk` npm package.
 pub mod api;
 pub use api::*;
+pub mod errors;
 pub mod helper;
 pub use helper::*;
+pub mod typescript;
@@ -0,0 +1,250 @@
+//! TypeScript definitions of the JS objects converted by the bridge
+//!
+//! Each definition lists every field of the Rust type along with the properties it is converted
+//! to, and fails to compile once a field is added to or removed from the Rust type. The definitions
+//! are written to `js/models/api_types.ts` using `make node-types`, which keeps the shapes of the npm
+//! package in sync with the Rust api types.
+use dsnp_graph_config::{Config, SchemaConfig};
+use dsnp_graph_core::api::api_types::{
+	Action, ActionOptions, ChainHead, Connection, DsnpKeys, GraphKeyPair, ImportBundle, KeyData,
+	PageData, Update,
+};
+
+/// Path of the generated definitions relative to the crate root
+pub const TYPE_DEFINITIONS_PATH: &str = "js/models/api_types.ts";
+
+/// Writes a TS interface with the given properties, the ones ending with `?` are optional
+fn interface(name: &str, properties: &[(&str, &str)]) -> String {
+	let mut definition = format!("export interface {} {{\n", name);
+	for (property, ts_type) in properties {
+		definition.push_str(&format!("  {}: {};\n", property, ts_type));
+	}
+	definition.push_str("}\n");
+	definition
+}
+
+/// Writes a TS union type of the given interfaces
+fn union(name: &str, interfaces: &[&str]) -> String {
+	let mut definition = format!("export type {} =\n", name);
+	for interface in interfaces {
+		definition.push_str(&format!("  | {}\n", interface));
+	}
+	definition.pop();
+	definition.push_str(";\n");
+	definition
+}
+
+/// Defines the interface of a struct, mapping each field to zero or more properties. Properties
+/// listed in `computed` are added by the bridge and have no matching field.
+macro_rules! ts_interface {
+	($rust:ident {
+		$($field:ident => [$($property:literal: $ts_type:literal),* $(,)?]),* $(,)?
+	} $(computed [$($computed:literal: $computed_type:literal),* $(,)?])?) => {{
+		#[allow(dead_code)]
+		fn fields_are_exhaustive(value: &$rust) {
+			let $rust { $($field: _),* } = value;
+		}
+		interface(
+			stringify!($rust),
+			&[$($(($property, $ts_type),)*)* $($(($computed, $computed_type),)*)?],
+		)
+	}};
+}
+
+/// Defines a union of interfaces tagged by a `type` property, one for each variant of an enum
+macro_rules! ts_tagged_union {
+	($rust:ident {
+		$($variant:ident as $interface:ident {
+			$($field:ident => [$($property:literal: $ts_type:literal),* $(,)?]),* $(,)?
+		} $(computed [$($computed:literal: $computed_type:literal),* $(,)?])?),* $(,)?
+	}) => {{
+		#[allow(dead_code)]
+		fn variants_are_exhaustive(value: &$rust) {
+			match value {
+				$($rust::$variant { $($field: _),* } => {}),*
+			}
+		}
+		let mut definitions = String::new();
+		$(
+			definitions.push_str(&interface(
+				stringify!($interface),
+				&[
+					("type", concat!("\"", stringify!($variant), "\"")),
+					$($(($property, $ts_type),)*)*
+					$($(($computed, $computed_type),)*)?
+				],
+			));
+			definitions.push('\n');
+		)*
+		definitions.push_str(&union(stringify!($rust), &[$(stringify!($interface)),*]));
+		definitions
+	}};
+}
+
+/// Function to generate the TS definitions of the JS objects converted by the bridge
+/// # Returns
+/// * `String` - contents of the generated TS module
+pub fn type_definitions() -> String {
+	let definitions = [
+		ts_interface!(KeyData {
+			index => ["index": "number"],
+			content => ["content": "Uint8Array"],
+		}),
+		ts_interface!(DsnpKeys {
+			dsnp_user_id => ["dsnpUserId": "DsnpUserIdInput"],
+			keys_hash => ["keysHash": "number"],
+			keys => ["keys": "KeyData[]"],
+		}),
+		ts_interface!(GraphKeyPair {
+			key_type => ["keyType": "GraphKeyType"],
+			public_key => ["publicKey": "Uint8Array"],
+			secret_key => ["secretKey": "Uint8Array"],
+		}),
+		ts_interface!(PageData {
+			page_id => ["pageId": "number"],
+			content => ["content": "Uint8Array"],
+			content_hash => ["contentHash": "number"],
+		}),
+		ts_interface!(ChainHead {
+			block_number => ["blockNumber": "number"],
+			block_hash => ["blockHash": "Uint8Array"],
+		}),
+		ts_interface!(ImportBundle {
+			dsnp_user_id => ["dsnpUserId": "DsnpUserIdInput"],
+			schema_id => ["schemaId": "number"],
+			key_pairs => ["keyPairs": "GraphKeyPair[]"],
+			dsnp_keys => ["dsnpKeys?": "DsnpKeys"],
+			pages => ["pages": "PageData[]"],
+			chain_head => ["chainHead?": "ChainHead"],
+		}),
+		ts_interface!(Connection {
+			dsnp_user_id => ["dsnpUserId": "DsnpUserIdInput"],
+			schema_id => ["schemaId": "number"],
+		}),
+		ts_interface!(ActionOptions {
+			ignore_existing_connections => ["ignoreExistingConnections?": "boolean"],
+			ignore_missing_connections => ["ignoreMissingConnections?": "boolean"],
+			disable_auto_commit => ["disableAutoCommit?": "boolean"],
+			collect_errors => ["collectErrors?": "boolean"],
+			require_counterpart_keys => ["requireCounterpartKeys?": "boolean"],
+			max_batch_size => ["maxBatchSize?": "number"],
+			timestamp_policy => ["timestampPolicy?": "TimestampPolicy"],
+		}),
+		ts_tagged_union!(Action {
+			Connect as ConnectAction {
+				owner_dsnp_user_id => ["ownerDsnpUserId": "DsnpUserIdInput"],
+				connection => ["connection": "Connection"],
+				dsnp_keys => ["dsnpKeys?": "DsnpKeys"],
+				preferred_page_id => ["preferredPageId?": "number"],
+				since => ["since?": "number"],
+			},
+			Disconnect as DisconnectAction {
+				owner_dsnp_user_id => ["ownerDsnpUserId": "DsnpUserIdInput"],
+				connection => ["connection": "Connection"],
+			},
+			AddGraphKey as AddGraphKeyAction {
+				owner_dsnp_user_id => ["ownerDsnpUserId": "DsnpUserIdInput"],
+				new_public_key => ["newPublicKey": "Uint8Array"],
+			},
+			ConnectMutual as ConnectMutualAction {
+				user_a => ["userA": "DsnpUserIdInput"],
+				user_b => ["userB": "DsnpUserIdInput"],
+				schema_id => ["schemaId": "number"],
+				since => ["since?": "number"],
+			},
+			MigrateConnection as MigrateConnectionAction {
+				owner_dsnp_user_id => ["ownerDsnpUserId": "DsnpUserIdInput"],
+				from_schema_id => ["fromSchemaId": "number"],
+				to_schema_id => ["toSchemaId": "number"],
+				dsnp_user_id => ["dsnpUserId": "DsnpUserIdInput"],
+				since => ["since?": "number"],
+			},
+		}),
+		ts_tagged_union!(Update {
+			PersistPage as PersistPageUpdate {
+				owner_dsnp_user_id => ["ownerDsnpUserId": "DsnpUserIdOutput"],
+				schema_id => ["schemaId": "number"],
+				page_id => ["pageId": "number"],
+				prev_hash => ["prevHash": "number"],
+				payload => ["payload": "Uint8Array"],
+				chain_head => ["chainHead?": "ChainHead"],
+			} computed ["idempotencyKey": "Uint8Array"],
+			DeletePage as DeletePageUpdate {
+				owner_dsnp_user_id => ["ownerDsnpUserId": "DsnpUserIdOutput"],
+				schema_id => ["schemaId": "number"],
+				page_id => ["pageId": "number"],
+				prev_hash => ["prevHash": "number"],
+				chain_head => ["chainHead?": "ChainHead"],
+			} computed ["idempotencyKey": "Uint8Array"],
+			AddKey as AddKeyUpdate {
+				owner_dsnp_user_id => ["ownerDsnpUserId": "DsnpUserIdOutput"],
+				prev_hash => ["prevHash": "number"],
+				payload => ["payload": "Uint8Array"],
+				chain_head => ["chainHead?": "ChainHead"],
+			} computed ["idempotencyKey": "Uint8Array"],
+		}),
+		ts_interface!(SchemaConfig {
+			dsnp_version => ["dsnpVersion": "DsnpVersion"],
+			connection_type => ["connectionType": "ConnectionType", "privacyType": "PrivacyType"],
+			page_capacity => ["pageCapacity?": "number"],
+		}),
+		ts_interface!(Config {
+			sdk_max_stale_friendship_days => ["sdkMaxStaleFriendshipDays": "number"],
+			max_graph_page_size_bytes => ["maxGraphPageSizeBytes": "number"],
+			max_page_id => ["maxPageId": "number"],
+			max_key_page_size_bytes => ["maxKeyPageSizeBytes": "number"],
+			max_update_payload_bytes => ["maxUpdatePayloadBytes?": "number"],
+			legacy_public_page_order => ["legacyPublicPageOrder?": "boolean"],
+			schema_map => ["schemaMap": "{ [key: number]: SchemaConfig }"],
+			graph_public_key_schema_id => ["graphPublicKeySchemaId": "number"],
+			dsnp_versions => ["dsnpVersions": "DsnpVersion[]"],
+		}),
+	];
+
+	let mut module = String::from(
+		"// This file is generated from the Rust api types by `make node-types`, do not edit it\n\
+		 import type { DsnpUserIdInput, DsnpUserIdOutput } from \"./dsnp_user_id\";\n\
+		 import type { GraphKeyType } from \"./import_bundle\";\n\
+		 import type { ConnectionType, DsnpVersion, PrivacyType } from \"./config\";\n\
+		 import type { TimestampPolicy } from \"./action\";\n",
+	);
+	for definition in definitions {
+		module.push('\n');
+		module.push_str(&definition);
+	}
+	module
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::path::PathBuf;
+
+	fn type_definitions_path() -> PathBuf {
+		PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TYPE_DEFINITIONS_PATH)
+	}
+
+	#[cfg(feature = "generate-types")]
+	#[test]
+	fn generate_type_definitions() {
+		std::fs::write(type_definitions_path(), type_definitions())
+			.expect("should write type definitions");
+	}
+
+	#[test]
+	fn type_definitions_should_match_generated_file() {
+		// arrange
+		let generated =
+			std::fs::read_to_string(type_definitions_path()).expect("should read type definitions");
+
+		// act
+		let definitions = type_definitions();
+
+		// assert
+		assert!(
+			generated == definitions,
+			"{} is outdated, regenerate it using `make node-types`",
+			TYPE_DEFINITIONS_PATH
+		);
+	}
+}
@@ -0,0 +1,102 @@
+//! Scenario fixtures shared by the parity runners of all bridges
+use dsnp_graph_config::errors::{DsnpGraphError, DsnpGraphResult};
+use dsnp_graph_core::{
+	api::api_types::{Action, ActionOptions, ExportOptions, ImportBundle, PageId, Update},
+	dsnp::dsnp_types::DsnpUserId,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+/// File extension of the fixtures
+const FIXTURE_EXTENSION: &str = "json";
+
+/// A scenario along with the updates it is expected to export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fixture {
+	/// human readable summary of what the scenario covers
+	pub description: String,
+
+	/// bundles imported before applying the actions
+	pub imports: Vec<ImportBundle>,
+
+	/// actions applied after importing the bundles
+	pub actions: Vec<Action>,
+
+	/// options used when applying the actions
+	#[serde(rename = "actionOptions", default, skip_serializing_if = "Option::is_none")]
+	pub action_options: Option<ActionOptions>,
+
+	/// options used when exporting the updates, the default ones if not set
+	#[serde(rename = "exportOptions", default, skip_serializing_if = "Option::is_none")]
+	pub export_options: Option<ExportOptions>,
+
+	/// updates exported by the core API after applying the actions, sorted by `sort_updates`
+	#[serde(rename = "expectedUpdates")]
+	pub expected_updates: Vec<Update>,
+}
+
+impl Fixture {
+	/// reads a fixture from a JSON file
+	pub fn read(path: &Path) -> DsnpGraphResult<Self> {
+		let content = fs::read_to_string(path)
+			.map_err(|e| DsnpGraphError::InvalidInput(format!("{}: {}", path.display(), e)))?;
+		serde_json::from_str(&content)
+			.map_err(|e| DsnpGraphError::InvalidInput(format!("{}: {}", path.display(), e)))
+	}
+
+	/// writes the fixture as pretty printed JSON
+	pub fn write(&self, path: &Path) -> DsnpGraphResult<()> {
+		let content = serde_json::to_string_pretty(self)
+			.map_err(|e| DsnpGraphError::InvalidInput(e.to_string()))?;
+		fs::write(path, content + "\n")
+			.map_err(|e| DsnpGraphError::InvalidInput(format!("{}: {}", path.display(), e)))
+	}
+}
+
+/// returns the directory containing the fixtures of this crate
+pub fn fixtures_dir() -> PathBuf {
+	PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures")
+}
+
+/// returns the path of the fixture with the given name
+pub fn fixture_path(name: &str) -> PathBuf {
+	fixtures_dir().join(name).with_extension(FIXTURE_EXTENSION)
+}
+
+/// reads all fixtures ordered by their names
+pub fn read_fixtures() -> DsnpGraphResult<Vec<(String, Fixture)>> {
+	let dir = fixtures_dir();
+	let entries = fs::read_dir(&dir)
+		.map_err(|e| DsnpGraphError::InvalidInput(format!("{}: {}", dir.display(), e)))?;
+	let mut fixtures = vec![];
+	for entry in entries {
+		let path = entry.map_err(|e| DsnpGraphError::InvalidInput(e.to_string()))?.path();
+		if path.extension().and_then(|e| e.to_str()) != Some(FIXTURE_EXTENSION) {
+			continue
+		}
+		let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+		fixtures.push((name, Fixture::read(&path)?));
+	}
+	fixtures.sort_by(|(a, _), (b, _)| a.cmp(b));
+	Ok(fixtures)
+}
+
+/// sorts the updates by owner, update type, schema id and page id so the updates exported by
+/// different runners can be compared regardless of their export order
+pub fn sort_updates(updates: &mut [Update]) {
+	updates.sort_by_key(update_sort_key);
+}
+
+/// key used when sorting the updates
+fn update_sort_key(update: &Update) -> (DsnpUserId, u8, u16, PageId) {
+	match update {
+		Update::PersistPage { owner_dsnp_user_id, schema_id, page_id, .. } =>
+			(*owner_dsnp_user_id, 0, *schema_id, *page_id),
+		Update::DeletePage { owner_dsnp_user_id, schema_id, page_id, .. } =>
+			(*owner_dsnp_user_id, 1, *schema_id, *page_id),
+		Update::AddKey { owner_dsnp_user_id, .. } => (*owner_dsnp_user_id, 2, 0, 0),
+	}
+}
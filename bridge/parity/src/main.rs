@@ -0,0 +1,15 @@
+//! Regenerates the parity fixtures from the scenarios using the core API
+use dsnp_graph_sdk_parity::{
+	fixture::fixture_path,
+	runner::{CoreRunner, ParityRunner},
+	scenarios::scenarios,
+};
+
+fn main() {
+	for (name, mut fixture) in scenarios() {
+		fixture.expected_updates = CoreRunner.run(&fixture).expect("scenario should run");
+		let path = fixture_path(name);
+		fixture.write(&path).expect("should write fixture");
+		println!("{} -> {} updates", path.display(), fixture.expected_updates.len());
+	}
+}
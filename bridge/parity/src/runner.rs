@@ -0,0 +1,356 @@
+//! Runners driving fixtures through the core API and the FFI bridge
+use crate::fixture::{sort_updates, Fixture};
+use dsnp_graph_config::{
+	errors::{DsnpGraphError, DsnpGraphResult},
+	Environment,
+};
+use dsnp_graph_core::api::{
+	api::{GraphAPI, GraphState},
+	api_types::{
+		Action, ChainHead, EncryptionMode, ExportOptions, ImportBundle, KeyData, NonceMode,
+		PageOverflowPolicy, Update,
+	},
+};
+use dsnp_graph_sdk_ffi as ffi;
+use std::{
+	ffi::CStr,
+	ptr::{self, NonNull},
+};
+
+/// Drives a fixture through one of the API surfaces of the SDK
+pub trait ParityRunner {
+	/// name of the API surface used in assertion messages
+	fn name(&self) -> &'static str;
+
+	/// imports the bundles of the fixture, applies its actions and returns the exported updates
+	/// sorted by `sort_updates`
+	fn run(&self, fixture: &Fixture) -> DsnpGraphResult<Vec<Update>>;
+}
+
+/// Runs fixtures using the rust API of the core
+#[derive(Debug, Default)]
+pub struct CoreRunner;
+
+impl ParityRunner for CoreRunner {
+	fn name(&self) -> &'static str {
+		"core"
+	}
+
+	fn run(&self, fixture: &Fixture) -> DsnpGraphResult<Vec<Update>> {
+		let mut state = GraphState::try_new(Environment::Mainnet)?;
+		state.import_users_data(&fixture.imports)?;
+		state.apply_actions(&fixture.actions, &fixture.action_options)?;
+		if let Some(export_options) = fixture.export_options {
+			state.set_export_options(export_options);
+		}
+		let mut updates = state.export_updates()?;
+		sort_updates(&mut updates);
+		Ok(updates)
+	}
+}
+
+/// Runs fixtures through the C API of the FFI bridge, converting the fixture into the C types the
+/// same way a C consumer would
+#[derive(Debug, Default)]
+pub struct FfiRunner;
+
+impl ParityRunner for FfiRunner {
+	fn name(&self) -> &'static str {
+		"ffi"
+	}
+
+	fn run(&self, fixture: &Fixture) -> DsnpGraphResult<Vec<Update>> {
+		let environment = ffi::Environment::Mainnet;
+		let graph_state = unsafe { ffi_result(ffi::initialize_graph_state(&environment))? };
+		let result = unsafe { run_ffi_fixture(graph_state.as_ptr(), fixture) };
+		unsafe { ffi::free_graph_state(graph_state.as_ptr()) };
+		result
+	}
+}
+
+/// imports, applies and exports a fixture using an initialized FFI graph state
+unsafe fn run_ffi_fixture(
+	graph_state: *mut GraphState,
+	fixture: &Fixture,
+) -> DsnpGraphResult<Vec<Update>> {
+	let buffers: Vec<_> = fixture.imports.iter().map(ImportBundleBuffers::new).collect();
+	let bundles: Vec<_> = buffers.iter().map(ImportBundleBuffers::to_ffi).collect();
+	let imported =
+		ffi_result(ffi::graph_import_users_data(graph_state, bundles.as_ptr(), bundles.len()))?;
+	drop(Box::from_raw(imported.as_ptr()));
+
+	let buffers: Vec<_> = fixture.actions.iter().map(ActionBuffers::new).collect();
+	let actions: Vec<_> = buffers.iter().map(ActionBuffers::to_ffi).collect();
+	let options = fixture.action_options.as_ref().map_or(ptr::null(), |o| o as *const _);
+	let applied = ffi_result(ffi::graph_apply_actions(
+		graph_state,
+		actions.as_ptr(),
+		actions.len(),
+		options,
+	))?;
+	drop(Box::from_raw(applied.as_ptr()));
+
+	if let Some(export_options) = &fixture.export_options {
+		let options = export_options_to_ffi(export_options);
+		let set = ffi_result(ffi::graph_set_export_options(graph_state, &options))?;
+		drop(Box::from_raw(set.as_ptr()));
+	}
+
+	let graph_updates = ffi_result(ffi::graph_export_updates(graph_state))?;
+	let updates: DsnpGraphResult<Vec<_>> = {
+		let graph_updates = graph_updates.as_ref();
+		slice_from_ffi(graph_updates.updates, graph_updates.updates_len)
+			.iter()
+			.map(|update| update_from_ffi(update))
+			.collect()
+	};
+	ffi::free_graph_updates(graph_updates.as_ptr());
+	let mut updates = updates?;
+	sort_updates(&mut updates);
+	Ok(updates)
+}
+
+/// returns the result of an FFI call or converts its error
+unsafe fn ffi_result<T>(result: ffi::FFIResult<T, ffi::GraphError>) -> DsnpGraphResult<NonNull<T>> {
+	if let Some(error) = result.error {
+		let message_ptr = ffi::dsnp_graph_error_message(error.as_ptr());
+		let message = match message_ptr.is_null() {
+			true => String::from("unknown error"),
+			false => CStr::from_ptr(message_ptr).to_string_lossy().to_string(),
+		};
+		ffi::free_dsnp_graph_error_message(message_ptr);
+		ffi::free_dsnp_graph_error(error.as_ptr());
+		return Err(DsnpGraphError::FFIError(message))
+	}
+	result
+		.result
+		.ok_or_else(|| DsnpGraphError::FFIError(String::from("missing result")))
+}
+
+/// borrows a C array, allowing null pointers for empty arrays
+unsafe fn slice_from_ffi<'a, T>(data: *const T, len: usize) -> &'a [T] {
+	match len {
+		0 => &[],
+		len => std::slice::from_raw_parts(data, len),
+	}
+}
+
+/// copies a C update into a core update, failing if its idempotency key does not match the one
+/// of the core update
+unsafe fn update_from_ffi(update: &ffi::Update) -> DsnpGraphResult<Update> {
+	let (core_update, idempotency_key) = match update {
+		ffi::Update::Persist(page) => (
+			Update::PersistPage {
+				owner_dsnp_user_id: page.owner_dsnp_user_id,
+				schema_id: page.schema_id,
+				page_id: page.page_id,
+				prev_hash: page.prev_hash,
+				payload: slice_from_ffi(page.payload, page.payload_len).to_vec(),
+				chain_head: chain_head_from_ffi(page.chain_head),
+			},
+			page.idempotency_key,
+		),
+		ffi::Update::Delete(page) => (
+			Update::DeletePage {
+				owner_dsnp_user_id: page.owner_dsnp_user_id,
+				schema_id: page.schema_id,
+				page_id: page.page_id,
+				prev_hash: page.prev_hash,
+				chain_head: chain_head_from_ffi(page.chain_head),
+			},
+			page.idempotency_key,
+		),
+		ffi::Update::Add(key) => (
+			Update::AddKey {
+				owner_dsnp_user_id: key.owner_dsnp_user_id,
+				prev_hash: key.prev_hash,
+				payload: slice_from_ffi(key.payload, key.payload_len).to_vec(),
+				chain_head: chain_head_from_ffi(key.chain_head),
+			},
+			key.idempotency_key,
+		),
+	};
+	if core_update.idempotency_key() != idempotency_key {
+		return Err(DsnpGraphError::FFIError(format!(
+			"idempotency key of {:?} does not match",
+			core_update
+		)))
+	}
+	Ok(core_update)
+}
+
+/// copies a nullable C chain head into a core chain head
+unsafe fn chain_head_from_ffi(chain_head: *const ffi::ChainHead) -> Option<ChainHead> {
+	chain_head.as_ref().map(|chain_head| ChainHead {
+		block_number: chain_head.block_number,
+		block_hash: slice_from_ffi(chain_head.block_hash, chain_head.block_hash_len).to_vec(),
+	})
+}
+
+/// copies core export options into the C export options
+fn export_options_to_ffi(options: &ExportOptions) -> ffi::ExportOptions {
+	ffi::ExportOptions {
+		page_overflow_policy: match options.page_overflow_policy {
+			PageOverflowPolicy::Error => ffi::PageOverflowPolicy::Error,
+			PageOverflowPolicy::SkipOverflow => ffi::PageOverflowPolicy::SkipOverflow,
+		},
+		nonce_mode: match options.nonce_mode {
+			NonceMode::Random => ffi::NonceMode::Random,
+			NonceMode::Deterministic => ffi::NonceMode::Deterministic,
+		},
+		encryption_mode: match options.encryption_mode {
+			EncryptionMode::SingleRecipient => ffi::EncryptionMode::SingleRecipient,
+			EncryptionMode::MultiRecipientV1 => ffi::EncryptionMode::MultiRecipientV1,
+		},
+	}
+}
+
+/// creates the C array of the keys pointing into their contents
+fn key_data_to_ffi(keys: &[KeyData]) -> Vec<ffi::KeyData> {
+	keys.iter()
+		.map(|key| ffi::KeyData {
+			index: key.index,
+			content: key.content.as_ptr() as *mut u8,
+			content_len: key.content.len(),
+		})
+		.collect()
+}
+
+/// C arrays referenced by the C type of an import bundle
+struct ImportBundleBuffers<'a> {
+	bundle: &'a ImportBundle,
+	key_pairs: Vec<ffi::GraphKeyPair>,
+	keys: Vec<ffi::KeyData>,
+	pages: Vec<ffi::PageData>,
+	chain_head: Option<ffi::ChainHead>,
+}
+
+impl<'a> ImportBundleBuffers<'a> {
+	/// creates the C arrays pointing into the contents of the bundle
+	fn new(bundle: &'a ImportBundle) -> Self {
+		let key_pairs = bundle
+			.key_pairs
+			.iter()
+			.map(|pair| ffi::GraphKeyPair {
+				key_type: pair.key_type.clone(),
+				public_key: pair.public_key.as_ptr(),
+				public_key_len: pair.public_key.len(),
+				secret_key: pair.secret_key.as_ptr(),
+				secret_key_len: pair.secret_key.len(),
+			})
+			.collect();
+		let keys = bundle.dsnp_keys.as_ref().map(|k| key_data_to_ffi(&k.keys)).unwrap_or_default();
+		let pages = bundle
+			.pages
+			.iter()
+			.map(|page| ffi::PageData {
+				page_id: page.page_id,
+				content: page.content.as_ptr() as *mut u8,
+				content_len: page.content.len(),
+				content_hash: page.content_hash,
+			})
+			.collect();
+		let chain_head = bundle.chain_head.as_ref().map(|head| ffi::ChainHead {
+			block_number: head.block_number,
+			block_hash: head.block_hash.as_ptr() as *mut u8,
+			block_hash_len: head.block_hash.len(),
+		});
+		Self { bundle, key_pairs, keys, pages, chain_head }
+	}
+
+	/// returns the C type of the bundle pointing into these arrays
+	fn to_ffi(&self) -> ffi::ImportBundle {
+		// the C type has no optional keys so missing keys are passed as an empty key list
+		let (dsnp_user_id, keys_hash) = match &self.bundle.dsnp_keys {
+			Some(dsnp_keys) => (dsnp_keys.dsnp_user_id, dsnp_keys.keys_hash),
+			None => (self.bundle.dsnp_user_id, 0),
+		};
+		ffi::ImportBundle {
+			dsnp_user_id: self.bundle.dsnp_user_id,
+			schema_id: self.bundle.schema_id,
+			key_pairs: self.key_pairs.as_ptr() as *mut _,
+			key_pairs_len: self.key_pairs.len(),
+			dsnp_keys: ffi::DsnpKeys {
+				dsnp_user_id,
+				keys_hash,
+				keys: self.keys.as_ptr() as *mut _,
+				keys_len: self.keys.len(),
+			},
+			pages: self.pages.as_ptr() as *mut _,
+			pages_len: self.pages.len(),
+			chain_head: self.chain_head.as_ref().map_or(ptr::null(), |h| h),
+		}
+	}
+}
+
+/// C values referenced by the C type of an action
+struct ActionBuffers<'a> {
+	action: &'a Action,
+	keys: Vec<ffi::KeyData>,
+	dsnp_keys: Option<Box<ffi::DsnpKeys>>,
+}
+
+impl<'a> ActionBuffers<'a> {
+	/// creates the C values pointing into the contents of the action
+	fn new(action: &'a Action) -> Self {
+		let mut buffers = Self { action, keys: vec![], dsnp_keys: None };
+		if let Action::Connect { dsnp_keys: Some(dsnp_keys), .. } = action {
+			buffers.keys = key_data_to_ffi(&dsnp_keys.keys);
+			buffers.dsnp_keys = Some(Box::new(ffi::DsnpKeys {
+				dsnp_user_id: dsnp_keys.dsnp_user_id,
+				keys_hash: dsnp_keys.keys_hash,
+				keys: buffers.keys.as_ptr() as *mut _,
+				keys_len: buffers.keys.len(),
+			}));
+		}
+		buffers
+	}
+
+	/// returns the C type of the action pointing into these values
+	fn to_ffi(&self) -> ffi::Action {
+		match self.action {
+			Action::Connect {
+				owner_dsnp_user_id, connection, preferred_page_id, since, ..
+			} => ffi::Action::Connect {
+				owner_dsnp_user_id: *owner_dsnp_user_id,
+				connection: connection.clone(),
+				dsnp_keys: self
+					.dsnp_keys
+					.as_ref()
+					.map_or(ptr::null_mut(), |k| k.as_ref() as *const _ as *mut _),
+				preferred_page_id: preferred_page_id.as_ref().map_or(ptr::null(), |p| p),
+				since: since.as_ref().map_or(ptr::null(), |s| s),
+			},
+			Action::Disconnect { owner_dsnp_user_id, connection } => ffi::Action::Disconnect {
+				owner_dsnp_user_id: *owner_dsnp_user_id,
+				connection: connection.clone(),
+			},
+			Action::AddGraphKey { owner_dsnp_user_id, new_public_key } =>
+				ffi::Action::AddGraphKey {
+					owner_dsnp_user_id: *owner_dsnp_user_id,
+					new_public_key: new_public_key.as_ptr(),
+					new_public_key_len: new_public_key.len(),
+				},
+			Action::ConnectMutual { user_a, user_b, schema_id, since } =>
+				ffi::Action::ConnectMutual {
+					user_a: *user_a,
+					user_b: *user_b,
+					schema_id: *schema_id,
+					since: since.as_ref().map_or(ptr::null(), |s| s),
+				},
+			Action::MigrateConnection {
+				owner_dsnp_user_id,
+				from_schema_id,
+				to_schema_id,
+				dsnp_user_id,
+				since,
+			} => ffi::Action::MigrateConnection {
+				owner_dsnp_user_id: *owner_dsnp_user_id,
+				from_schema_id: *from_schema_id,
+				to_schema_id: *to_schema_id,
+				dsnp_user_id: *dsnp_user_id,
+				since: since.as_ref().map_or(ptr::null(), |s| s),
+			},
+		}
+	}
+}
@@ -0,0 +1,224 @@
+//! Scenarios used to generate the fixtures, all of them use the Mainnet environment
+use crate::fixture::Fixture;
+use dryoc::keypair::StackKeyPair;
+use dsnp_graph_config::{ConnectionType, Environment, GraphKeyType, PrivacyType, SchemaId};
+use dsnp_graph_core::{
+	api::api_types::{
+		Action, ActionOptions, Connection, ExportOptions, GraphKeyPair, NonceMode, TimestampPolicy,
+	},
+	dsnp::dsnp_types::DsnpUserId,
+	util::builders::ImportBundleBuilder,
+};
+
+/// creation time of the imported connections
+const SINCE: u64 = 1_700_000;
+
+/// returns the name and fixture of every scenario, without any expected updates
+pub fn scenarios() -> Vec<(&'static str, Fixture)> {
+	vec![
+		("public_follow_disconnect", public_follow_disconnect()),
+		("ignore_missing_connections", ignore_missing_connections()),
+		("add_graph_key", add_graph_key()),
+		("multiple_users", multiple_users()),
+		("chain_head", chain_head()),
+		("connect_public_follow", connect_public_follow()),
+		("connect_private_follow", connect_private_follow()),
+		("connect_mutual_private_friendship", connect_mutual_private_friendship()),
+	]
+}
+
+/// removes connections from a public follow graph, updating one page and removing another
+fn public_follow_disconnect() -> Fixture {
+	let schema_id = schema_id(ConnectionType::Follow(PrivacyType::Public));
+	let owner = 1_000;
+	Fixture {
+		description: String::from("disconnects update a page and remove an emptied page"),
+		imports: vec![ImportBundleBuilder::new(Environment::Mainnet, owner, schema_id)
+			.with_page(0, &connections(&[1_001, 1_002, 1_003]), &[], 100)
+			.with_page(1, &connections(&[1_004]), &[], 101)
+			.build()],
+		actions: vec![disconnect(owner, 1_002, schema_id), disconnect(owner, 1_004, schema_id)],
+		action_options: None,
+		export_options: None,
+		expected_updates: vec![],
+	}
+}
+
+/// removes a missing connection which is ignored due to the provided action options
+fn ignore_missing_connections() -> Fixture {
+	let schema_id = schema_id(ConnectionType::Follow(PrivacyType::Public));
+	let owner = 2_000;
+	Fixture {
+		description: String::from("action options are passed through to the graph"),
+		imports: vec![ImportBundleBuilder::new(Environment::Mainnet, owner, schema_id)
+			.with_page(0, &connections(&[2_001, 2_002]), &[], 200)
+			.build()],
+		actions: vec![disconnect(owner, 2_001, schema_id), disconnect(owner, 2_999, schema_id)],
+		action_options: Some(ActionOptions {
+			ignore_missing_connections: true,
+			..ActionOptions::default()
+		}),
+		export_options: None,
+		expected_updates: vec![],
+	}
+}
+
+/// adds a new graph key for a user with imported keys
+fn add_graph_key() -> Fixture {
+	let schema_id = schema_id(ConnectionType::Follow(PrivacyType::Public));
+	let owner = 3_000;
+	Fixture {
+		description: String::from("new graph keys are exported as key page updates"),
+		imports: vec![ImportBundleBuilder::new(Environment::Mainnet, owner, schema_id)
+			.with_key_pairs(&[key_pair(1)])
+			.with_page(0, &connections(&[3_001]), &[], 300)
+			.build()],
+		actions: vec![Action::AddGraphKey {
+			owner_dsnp_user_id: owner,
+			new_public_key: key_pair(2).public_key,
+		}],
+		action_options: None,
+		export_options: None,
+		expected_updates: vec![],
+	}
+}
+
+/// updates the graphs of multiple users in a single batch
+fn multiple_users() -> Fixture {
+	let schema_id = schema_id(ConnectionType::Follow(PrivacyType::Public));
+	Fixture {
+		description: String::from("updates of all users are exported"),
+		imports: vec![
+			ImportBundleBuilder::new(Environment::Mainnet, 4_000, schema_id)
+				.with_page(0, &connections(&[4_001, 4_002]), &[], 400)
+				.build(),
+			ImportBundleBuilder::new(Environment::Mainnet, 4_100, schema_id)
+				.with_page(0, &connections(&[4_000, 4_101]), &[], 410)
+				.with_page(2, &connections(&[4_102]), &[], 412)
+				.build(),
+		],
+		actions: vec![disconnect(4_000, 4_001, schema_id), disconnect(4_100, 4_101, schema_id)],
+		action_options: None,
+		export_options: None,
+		expected_updates: vec![],
+	}
+}
+
+/// carries the chain head of the imported bundle into the exported updates
+fn chain_head() -> Fixture {
+	let schema_id = schema_id(ConnectionType::Follow(PrivacyType::Public));
+	let owner = 5_000;
+	Fixture {
+		description: String::from("updates carry the chain head of the imported bundle"),
+		imports: vec![ImportBundleBuilder::new(Environment::Mainnet, owner, schema_id)
+			.with_page(0, &connections(&[5_001, 5_002]), &[], 500)
+			.with_chain_head(42, &[1, 2, 3, 4])
+			.build()],
+		actions: vec![disconnect(owner, 5_001, schema_id)],
+		action_options: None,
+		export_options: None,
+		expected_updates: vec![],
+	}
+}
+
+/// adds a connection to a public follow graph, stamping it with a zero since value
+fn connect_public_follow() -> Fixture {
+	let schema_id = schema_id(ConnectionType::Follow(PrivacyType::Public));
+	let owner = 6_000;
+	Fixture {
+		description: String::from("connections are added to the existing page"),
+		imports: vec![ImportBundleBuilder::new(Environment::Mainnet, owner, schema_id)
+			.with_page(0, &connections(&[6_001]), &[], 600)
+			.build()],
+		actions: vec![connect(owner, 6_002, schema_id)],
+		action_options: Some(zero_timestamp_options()),
+		export_options: None,
+		expected_updates: vec![],
+	}
+}
+
+/// adds a connection to a private follow graph, encrypting the page with deterministic nonces
+fn connect_private_follow() -> Fixture {
+	let schema_id = schema_id(ConnectionType::Follow(PrivacyType::Private));
+	let owner = 7_000;
+	Fixture {
+		description: String::from("private pages are encrypted with the imported key pair"),
+		imports: vec![ImportBundleBuilder::new(Environment::Mainnet, owner, schema_id)
+			.with_key_pairs(&[key_pair(3)])
+			.build()],
+		actions: vec![connect(owner, 7_001, schema_id)],
+		action_options: Some(zero_timestamp_options()),
+		export_options: Some(deterministic_export_options()),
+		expected_updates: vec![],
+	}
+}
+
+/// connects two users with private friendship graphs to each other
+fn connect_mutual_private_friendship() -> Fixture {
+	let schema_id = schema_id(ConnectionType::Friendship(PrivacyType::Private));
+	let (user_a, user_b) = (8_000, 8_001);
+	Fixture {
+		description: String::from("mutual connections update the friendship graphs of both users"),
+		imports: vec![
+			ImportBundleBuilder::new(Environment::Mainnet, user_a, schema_id)
+				.with_key_pairs(&[key_pair(4)])
+				.build(),
+			ImportBundleBuilder::new(Environment::Mainnet, user_b, schema_id)
+				.with_key_pairs(&[key_pair(5)])
+				.build(),
+		],
+		actions: vec![Action::ConnectMutual { user_a, user_b, schema_id, since: None }],
+		action_options: Some(zero_timestamp_options()),
+		export_options: Some(deterministic_export_options()),
+		expected_updates: vec![],
+	}
+}
+
+/// returns the Mainnet schema id of the connection type
+fn schema_id(connection_type: ConnectionType) -> SchemaId {
+	Environment::Mainnet.schema_id_for(connection_type).expect("should exist")
+}
+
+/// returns connections to the users created at `SINCE`
+fn connections(user_ids: &[DsnpUserId]) -> Vec<(DsnpUserId, u64)> {
+	user_ids.iter().map(|user_id| (*user_id, SINCE)).collect()
+}
+
+/// returns an action removing a connection
+fn disconnect(owner: DsnpUserId, dsnp_user_id: DsnpUserId, schema_id: SchemaId) -> Action {
+	Action::Disconnect {
+		owner_dsnp_user_id: owner,
+		connection: Connection { dsnp_user_id, schema_id },
+	}
+}
+
+/// returns an action adding a connection without a since value
+fn connect(owner: DsnpUserId, dsnp_user_id: DsnpUserId, schema_id: SchemaId) -> Action {
+	Action::Connect {
+		owner_dsnp_user_id: owner,
+		connection: Connection { dsnp_user_id, schema_id },
+		dsnp_keys: None,
+		preferred_page_id: None,
+		since: None,
+	}
+}
+
+/// returns action options stamping added connections with zero instead of the current time
+fn zero_timestamp_options() -> ActionOptions {
+	ActionOptions { timestamp_policy: TimestampPolicy::Zero, ..ActionOptions::default() }
+}
+
+/// returns export options deriving the nonces of private pages from their contents
+fn deterministic_export_options() -> ExportOptions {
+	ExportOptions { nonce_mode: NonceMode::Deterministic, ..ExportOptions::default() }
+}
+
+/// returns a key pair derived from the seed so generated fixtures are reproducible
+fn key_pair(seed: u8) -> GraphKeyPair {
+	let key_pair = StackKeyPair::from_seed(&[seed; 32]);
+	GraphKeyPair {
+		key_type: GraphKeyType::X25519,
+		public_key: key_pair.public_key.to_vec(),
+		secret_key: key_pair.secret_key.to_vec(),
+	}
+}
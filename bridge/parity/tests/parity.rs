@@ -0,0 +1,39 @@
+use dsnp_graph_sdk_parity::{
+	fixture::read_fixtures,
+	runner::{CoreRunner, FfiRunner, ParityRunner},
+	scenarios::scenarios,
+};
+use std::collections::BTreeSet;
+
+#[test]
+fn fixtures_should_exist_for_all_scenarios() {
+	let fixtures: BTreeSet<_> = read_fixtures()
+		.expect("should read")
+		.into_iter()
+		.map(|(name, _)| name)
+		.collect();
+	let scenarios: BTreeSet<_> =
+		scenarios().into_iter().map(|(name, _)| name.to_string()).collect();
+
+	assert_eq!(fixtures, scenarios, "run `cargo run -p dsnp-graph-sdk-parity` to regenerate");
+}
+
+#[test]
+fn all_runners_should_export_expected_updates() {
+	let runners: Vec<Box<dyn ParityRunner>> = vec![Box::new(CoreRunner), Box::new(FfiRunner)];
+	for (name, fixture) in read_fixtures().expect("should read") {
+		assert!(!fixture.expected_updates.is_empty(), "{} should export updates", name);
+		for runner in &runners {
+			let updates = runner.run(&fixture).unwrap_or_else(|e| {
+				panic!("{} runner failed for {}: {:?}", runner.name(), name, e)
+			});
+			assert_eq!(
+				updates,
+				fixture.expected_updates,
+				"{} runner exported different updates for {}",
+				runner.name(),
+				name
+			);
+		}
+	}
+}
@@ -0,0 +1,58 @@
+//! Generates the typed schema id constants of the built in environments from their configs, so
+//! they can be used at compile time without loading a `Config`
+use serde_json::Value;
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+/// module name, config file and display name of each built in environment
+const ENVIRONMENTS: [(&str, &str, &str); 3] = [
+	("mainnet", "frequency.json", "Mainnet"),
+	("rococo", "frequency-rococo.json", "Rococo"),
+	("testnet_paseo", "frequency-testnet-paseo.json", "TestnetPaseo"),
+];
+
+fn main() {
+	let configs_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap())
+		.join("resources")
+		.join("configs");
+	let mut generated = String::new();
+	for (module, file, name) in ENVIRONMENTS {
+		let path = configs_dir.join(file);
+		println!("cargo:rerun-if-changed={}", path.display());
+		let config: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap())
+			.unwrap_or_else(|e| panic!("invalid config {}: {}", path.display(), e));
+
+		writeln!(generated, "/// Schema ids of the `{}` environment", name).unwrap();
+		writeln!(generated, "pub mod {} {{", module).unwrap();
+		writeln!(generated, "\tuse crate::SchemaId;").unwrap();
+		writeln!(generated, "\t/// Schema id of the graph public keys").unwrap();
+		writeln!(
+			generated,
+			"\tpub const GRAPH_PUBLIC_KEY_SCHEMA_ID: SchemaId = {};",
+			config["graphPublicKeySchemaId"].as_u64().expect("public key schema id")
+		)
+		.unwrap();
+		for entry in config["schemaMap"].as_array().expect("schema map") {
+			let schema_id = entry[0].as_u64().expect("schema id");
+			let connection_type = entry[1][1]["connectionType"].as_str().expect("connection type");
+			let privacy_type = entry[1][1]["privacyType"].as_str().expect("privacy type");
+			writeln!(
+				generated,
+				"\t/// Schema id of the {} {} graph",
+				privacy_type, connection_type
+			)
+			.unwrap();
+			writeln!(
+				generated,
+				"\tpub const {}_{}_SCHEMA_ID: SchemaId = {};",
+				privacy_type.to_uppercase(),
+				connection_type.to_uppercase(),
+				schema_id
+			)
+			.unwrap();
+		}
+		writeln!(generated, "}}").unwrap();
+	}
+
+	let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+	fs::write(out_dir.join("schema_ids.rs"), generated).unwrap();
+}
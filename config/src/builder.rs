@@ -1,32 +1,73 @@
 //! Builder utility to help creating a new Config
 //!
-#![allow(dead_code)]
-use crate::{Config, DsnpVersion, SchemaConfig, SchemaId};
+//! The builder starts from the Mainnet config so only the settings that differ need to be
+//! provided, and `try_build` validates the result before it is used in `Environment::Dev`.
+//! Bridges can use `from_mainnet_json` to apply overrides provided as JSON.
+use crate::{
+	errors::{DsnpGraphError, DsnpGraphResult},
+	Config, DsnpVersion, InputValidation, PageId, SchemaConfig, SchemaId, MAINNET_CONFIG,
+};
 use std::collections::HashMap;
 
 pub struct ConfigBuilder {
 	sdk_max_stale_friendship_days: u32,
 	max_graph_page_size_bytes: u32,
-	max_page_id: u32,
+	max_page_id: PageId,
 	max_key_page_size_bytes: u32,
+	max_update_payload_bytes: Option<u32>,
+	legacy_public_page_order: bool,
 	schema_map: HashMap<SchemaId, SchemaConfig>,
 	graph_public_key_schema_id: SchemaId,
 	dsnp_versions: Vec<DsnpVersion>,
 }
 
+impl Default for ConfigBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
 impl ConfigBuilder {
+	/// creates a builder with the settings of the Mainnet config
 	pub fn new() -> Self {
+		Self::from_config(&MAINNET_CONFIG)
+	}
+
+	/// creates a builder with the settings of an existing config
+	pub fn from_config(config: &Config) -> Self {
 		Self {
-			schema_map: HashMap::new(),
-			graph_public_key_schema_id: 0,
-			max_graph_page_size_bytes: 1024,
-			max_page_id: 16,
-			max_key_page_size_bytes: 65536,
-			sdk_max_stale_friendship_days: 90,
-			dsnp_versions: vec![],
+			sdk_max_stale_friendship_days: config.sdk_max_stale_friendship_days,
+			max_graph_page_size_bytes: config.max_graph_page_size_bytes,
+			max_page_id: config.max_page_id,
+			max_key_page_size_bytes: config.max_key_page_size_bytes,
+			max_update_payload_bytes: config.max_update_payload_bytes,
+			legacy_public_page_order: config.legacy_public_page_order,
+			schema_map: config.schema_map.clone(),
+			graph_public_key_schema_id: config.graph_public_key_schema_id,
+			dsnp_versions: config.dsnp_versions.clone(),
 		}
 	}
 
+	/// applies the overrides on top of the Mainnet config and returns the validated config
+	pub fn from_mainnet_with(overrides: impl FnOnce(Self) -> Self) -> DsnpGraphResult<Config> {
+		overrides(Self::new()).try_build()
+	}
+
+	/// applies the top level fields of a JSON object, using the same names as the serialized
+	/// config, on top of the Mainnet config and returns the validated config
+	pub fn from_mainnet_json(overrides: &str) -> DsnpGraphResult<Config> {
+		let overrides: serde_json::Map<String, serde_json::Value> = serde_json::from_str(overrides)
+			.map_err(|e| DsnpGraphError::InvalidConfig(e.to_string()))?;
+		let mut merged = match serde_json::to_value(&*MAINNET_CONFIG) {
+			Ok(serde_json::Value::Object(map)) => map,
+			_ => return Err(DsnpGraphError::InvalidConfig("unable to serialize mainnet".into())),
+		};
+		merged.extend(overrides);
+		let config: Config = serde_json::from_value(serde_json::Value::Object(merged))
+			.map_err(|e| DsnpGraphError::InvalidConfig(e.to_string()))?;
+		Self::from_config(&config).try_build()
+	}
+
 	pub fn with_sdk_max_stale_friendship_days(
 		mut self,
 		sdk_max_stale_friendship_days: u32,
@@ -35,7 +76,7 @@ impl ConfigBuilder {
 		self
 	}
 
-	pub fn with_max_page_id(mut self, max_page_id: u32) -> Self {
+	pub fn with_max_page_id(mut self, max_page_id: PageId) -> Self {
 		self.max_page_id = max_page_id;
 		self
 	}
@@ -50,15 +91,38 @@ impl ConfigBuilder {
 		self
 	}
 
+	pub fn with_max_update_payload_bytes(mut self, max_update_payload_bytes: u32) -> Self {
+		self.max_update_payload_bytes = Some(max_update_payload_bytes);
+		self
+	}
+
+	pub fn with_legacy_public_page_order(mut self, legacy_public_page_order: bool) -> Self {
+		self.legacy_public_page_order = legacy_public_page_order;
+		self
+	}
+
 	pub fn with_schema(mut self, schema_id: SchemaId, config: SchemaConfig) -> Self {
 		self.schema_map.insert(schema_id, config);
 		self
 	}
 
+	/// removes all schemas, such as the ones of Mainnet, so a different set can be added
+	pub fn without_schemas(mut self) -> Self {
+		self.schema_map.clear();
+		self
+	}
+
 	pub fn with_key_pair_schema_id(mut self, schema_id: SchemaId) -> Self {
 		self.graph_public_key_schema_id = schema_id;
 		self
 	}
+
+	pub fn with_dsnp_versions(mut self, dsnp_versions: Vec<DsnpVersion>) -> Self {
+		self.dsnp_versions = dsnp_versions;
+		self
+	}
+
+	/// returns the config without validating it
 	pub fn build(self) -> Config {
 		Config {
 			sdk_max_stale_friendship_days: self.sdk_max_stale_friendship_days,
@@ -66,8 +130,70 @@ impl ConfigBuilder {
 			schema_map: self.schema_map,
 			max_page_id: self.max_page_id,
 			max_key_page_size_bytes: self.max_key_page_size_bytes,
+			max_update_payload_bytes: self.max_update_payload_bytes,
+			legacy_public_page_order: self.legacy_public_page_order,
 			max_graph_page_size_bytes: self.max_graph_page_size_bytes,
 			dsnp_versions: self.dsnp_versions,
 		}
 	}
+
+	/// returns the config if it is valid
+	pub fn try_build(self) -> DsnpGraphResult<Config> {
+		let config = self.build();
+		config.validate()?;
+		Ok(config)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{ConnectionType, PrivacyType};
+	use pretty_assertions::assert_eq;
+
+	#[test]
+	fn config_builder_should_default_to_mainnet_and_apply_overrides() {
+		// act
+		let config =
+			ConfigBuilder::from_mainnet_with(|b| b.with_max_page_id(10)).expect("should be valid");
+		let from_json =
+			ConfigBuilder::from_mainnet_json(r#"{"maxPageId": 10}"#).expect("should be valid");
+
+		// assert
+		assert_eq!(ConfigBuilder::new().build(), *MAINNET_CONFIG);
+		assert_eq!(config.max_page_id, 10);
+		assert_eq!(config.schema_map, MAINNET_CONFIG.schema_map);
+		assert_eq!(from_json, config);
+	}
+
+	#[test]
+	fn config_builder_should_reject_invalid_configs() {
+		// arrange
+		let schema = SchemaConfig {
+			dsnp_version: DsnpVersion::Version1_0,
+			connection_type: ConnectionType::Follow(PrivacyType::Public),
+			page_capacity: None,
+		};
+		let duplicated_connection_type = ConfigBuilder::new()
+			.without_schemas()
+			.with_schema(1, schema.clone())
+			.with_schema(2, schema);
+
+		// act
+		let out_of_bounds = ConfigBuilder::from_mainnet_json(&format!(
+			r#"{{"maxPageId": {}}}"#,
+			u32::from(PageId::MAX) + 1
+		));
+		let duplicated = duplicated_connection_type.try_build();
+		let key_schema = ConfigBuilder::new()
+			.with_key_pair_schema_id(*MAINNET_CONFIG.schema_map.keys().next().unwrap())
+			.try_build();
+		let malformed_json = ConfigBuilder::from_mainnet_json(r#"{"maxPageId": "ten"}"#);
+
+		// assert
+		assert!(matches!(out_of_bounds, Err(DsnpGraphError::InvalidConfig(_))));
+		assert!(matches!(duplicated, Err(DsnpGraphError::InvalidConfig(_))));
+		assert!(matches!(key_schema, Err(DsnpGraphError::InvalidConfig(_))));
+		assert!(matches!(malformed_json, Err(DsnpGraphError::InvalidConfig(_))));
+	}
 }
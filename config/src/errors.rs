@@ -19,10 +19,16 @@ pub enum DsnpGraphError {
 	DuplicateConnectionDetected,
 
 	/// Calling apply_prids in non private friendship graph!
+	///
+	/// No longer returned, such calls fail with `UnsupportedOperation` instead. Kept so code
+	/// matching on the variant or its error code keeps working
 	#[error("Calling apply_prids in non private friendship graph!")]
 	CallToPridsInPublicGraph,
 
 	/// Calling private friends in non private graph
+	///
+	/// No longer returned, such calls fail with `UnsupportedOperation` instead. Kept so code
+	/// matching on the variant or its error code keeps working
 	#[error("Call to private friends in non private graph")]
 	CallToPrivateFriendsInPublicGraph,
 
@@ -75,16 +81,28 @@ pub enum DsnpGraphError {
 	FFIError(String),
 
 	///	Graph is full
+	///
+	/// No longer returned, a graph without free pages now fails with `GraphCapacityExceeded`
+	/// (error code 48) instead of this error (error code 16), which lists the full pages and the
+	/// number of connections that do not fit. Code 16 stays reserved so bridge consumers matching
+	/// on it keep compiling, but they should match on 48 instead.
+	#[deprecated(note = "graphs without free pages fail with `GraphCapacityExceeded` instead")]
 	#[error("Graph is full")]
 	GraphIsFull,
 
+	/// Graph has no free page left for the connections being added, listing the schema id, the
+	/// pages that are full and the number of connections that do not fit
+	#[error("Graph of schema {0} is full, {2} connection(s) do not fit into pages {1:?}")]
+	GraphCapacityExceeded(SchemaId, Vec<PageId>, usize),
+
 	/// Invalid DSNP user id
 	#[error("Invalid user id: {0}")]
 	InvalidDsnpUserId(DsnpUserId),
 
-	/// Invalid schema id
-	#[error("Invalid schema id: {0}")]
-	InvalidSchemaId(SchemaId),
+	/// Invalid schema id along with the schema ids configured in the environment, if known where
+	/// the error was raised
+	#[error("Invalid schema id: {0}{}", .1.describe(*.0))]
+	InvalidSchemaId(SchemaId, SchemaIdContext),
 
 	/// Invalid page id
 	#[error("Invalid Page ID: {0}")]
@@ -122,6 +140,11 @@ pub enum DsnpGraphError {
 	#[error("Key derivation error: {0}")]
 	KeyDerivationError(String),
 
+	/// Key page of a user would exceed the maximum allowed size after adding the new key, listing
+	/// the user, the required size and the maximum size in bytes
+	#[error("Key page of user {0} requires {1} bytes which exceeds the maximum of {2} bytes")]
+	KeyPageSizeExceeded(DsnpUserId, u32, u32),
+
 	/// No pris imported for user
 	#[error("No pris imported for user: {0}")]
 	NoPrisImportedForUser(DsnpUserId),
@@ -146,6 +169,11 @@ pub enum DsnpGraphError {
 	#[error("Page is trivially full")]
 	PageTriviallyFull,
 
+	/// No benchmarked page capacity exists for the connection type of a configured graph schema
+	/// and its schema config does not provide a `page_capacity`
+	#[error("No page capacity is configured for connection type {0:?}, set the page capacity of its schema")]
+	PageCapacityNotConfigured(ConnectionType),
+
 	/// Public key already exists
 	#[error("Given public key already exists: {0}")]
 	PublicKeyAlreadyExists(String),
@@ -160,6 +188,11 @@ pub enum DsnpGraphError {
 	)]
 	PridsLenShouldBeEqualToConnectionsLen(PageId, usize, usize),
 
+	/// Prids of a page do not match the connections at the same position, listing the page id and
+	/// the mismatched indexes
+	#[error("page_id: {0}, prids do not match the connections at indexes {1:?}")]
+	PridsMisaligned(PageId, Vec<usize>),
+
 	/// Unsupported schema
 	#[error("Unsupported schema: {0}")]
 	UnsupportedSchema(SchemaId),
@@ -179,10 +212,84 @@ pub enum DsnpGraphError {
 	/// Unsupported connection type
 	#[error("No schema ID found for connection type")]
 	UnsupportedConnectionTypeForConfig(ConnectionType),
+
+	/// One or more actions failed, listing the index of each failed action and its error
+	#[error("{} action(s) failed", .0.len())]
+	ActionsFailed(Vec<(usize, DsnpGraphError)>),
+
+	/// No config is registered for the custom environment
+	#[error("Unknown environment: {0}")]
+	UnknownEnvironment(String),
+
+	/// Config is malformed or inconsistent
+	#[error("Invalid config: {0}")]
+	InvalidConfig(String),
+
+	/// Key bundle is malformed, uses an unsupported version or belongs to another user
+	#[error("Invalid key bundle: {0}")]
+	InvalidKeyBundle(String),
+
+	/// Graph state is older than the allowed age
+	#[error("Graph state is {0} seconds old which exceeds the allowed {1} seconds")]
+	StaleGraphState(u64, u64),
+
+	/// Imported content hash of a page does not match the hash of its content, listing the page
+	/// id, the imported hash and the calculated hash
+	#[error("page_id: {0}, imported content hash {1} does not match calculated hash {2}")]
+	PageContentHashMismatch(PageId, u32, u32),
+
+	/// Operation requires a capability which is not supported by the connection type of the graph
+	#[error("{0} requires {1} which is not supported by {2} graphs")]
+	UnsupportedOperation(String, Capability, ConnectionType),
+
+	/// Number of applied actions exceeds the maximum batch size set in the action options, listing
+	/// the number of actions and the maximum batch size
+	#[error("Batch of {0} actions exceeds the maximum batch size of {1}")]
+	ActionBatchTooLarge(usize, u32),
+
+	/// Payload of a page holding a single connection exceeds the maximum update payload size, so
+	/// the connection can not be stored in any page. Lists the page id, the payload size and the
+	/// maximum update payload size
+	#[error("page_id: {0}, payload of a single connection is {1} bytes which exceeds the maximum update payload of {2} bytes")]
+	UpdatePayloadTooLarge(PageId, usize, u32),
+}
+
+/// Environment in which an invalid schema id was resolved, used to list the valid schema ids in
+/// the `InvalidSchemaId` error
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaIdContext {
+	/// name of the environment, if known
+	pub environment: Option<String>,
+
+	/// schema ids configured in the environment sorted ascending, empty if unknown
+	pub valid_schema_ids: Vec<SchemaId>,
+}
+
+impl SchemaIdContext {
+	/// Returns the valid schema id closest to `schema_id`, preferring the lower one on ties
+	pub fn nearest(&self, schema_id: SchemaId) -> Option<SchemaId> {
+		self.valid_schema_ids.iter().copied().min_by_key(|id| id.abs_diff(schema_id))
+	}
+
+	/// Describes the context appended to the message of an invalid `schema_id`
+	fn describe(&self, schema_id: SchemaId) -> String {
+		let mut description = String::new();
+		if let Some(environment) = &self.environment {
+			description.push_str(&format!(" in {} environment", environment));
+		}
+		if let Some(nearest) = self.nearest(schema_id) {
+			description.push_str(&format!(
+				", valid schema ids are {:?}, nearest is {}",
+				self.valid_schema_ids, nearest
+			));
+		}
+		description
+	}
 }
 
 impl DsnpGraphError {
 	/// Returns the error code for the error
+	#[allow(deprecated)]
 	pub fn error_code(&self) -> i32 {
 		match self {
 			DsnpGraphError::AvroError { .. } => 1,
@@ -202,7 +309,7 @@ impl DsnpGraphError {
 			DsnpGraphError::FailedtoWriteLock(_) => 15,
 			DsnpGraphError::GraphIsFull => 16,
 			DsnpGraphError::InvalidDsnpUserId(_) => 17,
-			DsnpGraphError::InvalidSchemaId(_) => 20,
+			DsnpGraphError::InvalidSchemaId(..) => 20,
 			DsnpGraphError::InvalidPageId(_) => 21,
 			DsnpGraphError::InvalidPrivateSchemaId => 22,
 			DsnpGraphError::InvalidPublicKey => 23,
@@ -227,8 +334,45 @@ impl DsnpGraphError {
 			DsnpGraphError::UnableToDecryptGraphChunkWithAnyKey => 42,
 			DsnpGraphError::FFIError(_) => 43,
 			DsnpGraphError::UnsupportedConnectionTypeForConfig(..) => 44,
+			DsnpGraphError::ActionsFailed(_) => 45,
+			DsnpGraphError::UnknownEnvironment(_) => 46,
+			DsnpGraphError::InvalidConfig(_) => 47,
+			DsnpGraphError::GraphCapacityExceeded(..) => 48,
+			DsnpGraphError::KeyPageSizeExceeded(..) => 49,
+			DsnpGraphError::PridsMisaligned(..) => 50,
+			DsnpGraphError::PageCapacityNotConfigured(_) => 51,
+			DsnpGraphError::InvalidKeyBundle(_) => 52,
+			DsnpGraphError::StaleGraphState(..) => 53,
+			DsnpGraphError::PageContentHashMismatch(..) => 54,
+			DsnpGraphError::UnsupportedOperation(..) => 55,
+			DsnpGraphError::ActionBatchTooLarge(..) => 56,
+			DsnpGraphError::UpdatePayloadTooLarge(..) => 57,
+		}
+	}
+
+	/// Returns the dsnp user ids carried by the error, including the ones of nested action errors,
+	/// which allows redacting them from logged error messages
+	pub fn dsnp_user_ids(&self) -> Vec<DsnpUserId> {
+		match self {
+			DsnpGraphError::ConnectionAlreadyExists(from, to) |
+			DsnpGraphError::ConnectionDoesNotExist(from, to) => vec![*from, *to],
+			DsnpGraphError::InvalidDsnpUserId(id) |
+			DsnpGraphError::ImportedKeyNotFound(id, _) |
+			DsnpGraphError::KeyPageSizeExceeded(id, ..) |
+			DsnpGraphError::NoPrisImportedForUser(id) |
+			DsnpGraphError::NoPublicKeyFoundForUser(id) |
+			DsnpGraphError::UserGraphNotImported(id) => vec![*id],
+			DsnpGraphError::ActionsFailed(errors) =>
+				errors.iter().flat_map(|(_, error)| error.dsnp_user_ids()).collect(),
+			_ => vec![],
 		}
 	}
+
+	/// Returns true if the error only means that a single page has no room for a connection, in
+	/// which case the connection can still be added to another page of the graph
+	pub fn is_page_full(&self) -> bool {
+		matches!(self, DsnpGraphError::PageTriviallyFull | DsnpGraphError::PageAggressivelyFull)
+	}
 }
 
 /// Macro to replicate `Option<T>::ok_or`, but logging if the returned
@@ -3,14 +3,19 @@
 //!
 pub mod builder;
 pub mod errors;
-use crate::errors::DsnpGraphResult;
+pub mod registry;
+use crate::{
+	errors::{DsnpGraphError, DsnpGraphResult, SchemaIdContext},
+	registry::CustomEnvironment,
+};
 use apache_avro::Schema;
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::{
-	collections::hash_map::HashMap,
+	collections::{hash_map::HashMap, HashSet},
 	fmt::{Display, Formatter},
+	num::NonZeroU32,
 };
 
 /// SchemaId type
@@ -20,12 +25,58 @@ pub type DsnpUserId = u64;
 /// PageId type
 pub type PageId = u16;
 
+// typed schema id constants of the built in environments, such as
+// `mainnet::PRIVATE_FRIENDSHIP_SCHEMA_ID`, generated from their configs by `build.rs`
+include!(concat!(env!("OUT_DIR"), "/schema_ids.rs"));
+
 /// a common trait to allow checks for api input types
 pub trait InputValidation {
 	fn validate(&self) -> DsnpGraphResult<()>;
+
+	/// returns the dsnp user ids contained in the input
+	fn dsnp_user_ids(&self) -> Vec<DsnpUserId> {
+		vec![]
+	}
+
+	/// validates the input and checks that all its dsnp user ids are issued on chain using
+	/// `validate_dsnp_user_id`
+	fn validate_with_max_dsnp_user_id(&self, max_issued_id: DsnpUserId) -> DsnpGraphResult<()> {
+		self.validate()?;
+		self.dsnp_user_ids()
+			.into_iter()
+			.try_for_each(|id| validate_dsnp_user_id(id, Some(max_issued_id)))
+	}
+}
+
+/// returns true if the id is a plausible MSA id, which is also the DSNP user id on Frequency
+///
+/// MSA ids are issued sequentially starting from 1 on every environment, so ids above the highest
+/// issued one, which is supplied by the caller since it grows over time, can not exist on chain
+pub const fn is_valid_dsnp_user_id(
+	dsnp_user_id: DsnpUserId,
+	max_issued_id: Option<DsnpUserId>,
+) -> bool {
+	match max_issued_id {
+		Some(max_issued_id) => dsnp_user_id != 0 && dsnp_user_id <= max_issued_id,
+		None => dsnp_user_id != 0,
+	}
+}
+
+/// fails with `InvalidDsnpUserId` if the id is not a plausible MSA id
+pub fn validate_dsnp_user_id(
+	dsnp_user_id: DsnpUserId,
+	max_issued_id: Option<DsnpUserId>,
+) -> DsnpGraphResult<()> {
+	match is_valid_dsnp_user_id(dsnp_user_id, max_issued_id) {
+		true => Ok(()),
+		false => Err(DsnpGraphError::InvalidDsnpUserId(dsnp_user_id)),
+	}
 }
 
 lazy_static! {
+	// The avro schemas only depend on the DSNP spec and not on the chain, so they are shared by the
+	// graph states of all environments, while everything chain specific is read from the `Config`
+
 	/// Schema for public key
 	pub static ref PUBLIC_KEY_SCHEMA: Schema =
 		Schema::parse_str(include_str!("../resources/schemas/public_key_schema.json")).unwrap();
@@ -103,6 +154,76 @@ impl ConnectionType {
 			Self::Follow(privacy) | Self::Friendship(privacy) => *privacy,
 		}
 	}
+
+	/// returns the operations supported by graphs of this connection type
+	pub const fn capabilities(&self) -> ConnectionCapabilities {
+		let encryptable = matches!(self.privacy_type(), PrivacyType::Private);
+		let double_sided = matches!(self, Self::Friendship(_));
+		ConnectionCapabilities {
+			supports_prids: encryptable && double_sided,
+			double_sided,
+			encryptable,
+		}
+	}
+
+	/// returns true if graphs of this connection type support the capability
+	pub const fn supports(&self, capability: Capability) -> bool {
+		let capabilities = self.capabilities();
+		match capability {
+			Capability::Prids => capabilities.supports_prids,
+			Capability::DoubleSided => capabilities.double_sided,
+			Capability::Encryption => capabilities.encryptable,
+		}
+	}
+}
+
+/// Operations supported by graphs of a connection type
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionCapabilities {
+	/// connections are verified using PRIds published by the counterpart
+	pub supports_prids: bool,
+
+	/// connections are stored in the graphs of both sides
+	pub double_sided: bool,
+
+	/// pages are encrypted using the graph keys of the owner
+	pub encryptable: bool,
+}
+
+/// A single capability of a connection type required by an operation
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Capability {
+	/// PRIds are calculated and verified for the connections
+	Prids,
+
+	/// connections are stored in the graphs of both sides
+	DoubleSided,
+
+	/// pages are encrypted
+	Encryption,
+}
+
+impl Display for Capability {
+	fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+		write!(
+			f,
+			"{}",
+			match self {
+				Capability::Prids => "PRIds",
+				Capability::DoubleSided => "double sided connections",
+				Capability::Encryption => "encryption",
+			}
+		)
+	}
+}
+
+/// returns the capabilities of all supported connection types, which allows UIs to only offer the
+/// operations supported by each graph
+pub fn connection_capability_matrix() -> Vec<(ConnectionType, ConnectionCapabilities)> {
+	ALL_CONNECTION_TYPES.iter().map(|c| (*c, c.capabilities())).collect()
 }
 
 /// a list of all supported Graphs and connections types
@@ -126,6 +247,8 @@ pub enum Environment {
 	Rococo,
 	TestnetPaseo,
 	Dev(Config),
+	/// Config registered by name in `EnvironmentRegistry`
+	Custom(CustomEnvironment),
 }
 
 impl Environment {
@@ -136,8 +259,35 @@ impl Environment {
 			Environment::Rococo => &ROCOCO_CONFIG,
 			Environment::TestnetPaseo => &TESTNET_PASEO_CONFIG,
 			Environment::Dev(cfg) => &cfg,
+			Environment::Custom(custom) => custom.config(),
 		}
 	}
+
+	/// Returns the schema id of the connection type in the environment, if the environment
+	/// supports it
+	pub fn schema_id_for(&self, connection_type: ConnectionType) -> Option<SchemaId> {
+		self.get_config().get_schema_id_from_connection_type(connection_type)
+	}
+
+	/// Returns the name of the environment, which is the registered name for custom environments
+	pub fn name(&self) -> &str {
+		match self {
+			Environment::Mainnet => "mainnet",
+			Environment::Rococo => "rococo",
+			Environment::TestnetPaseo => "testnet-paseo",
+			Environment::Dev(_) => "dev",
+			Environment::Custom(custom) => custom.name(),
+		}
+	}
+
+	/// Returns an `InvalidSchemaId` error listing the name and the schema ids of the environment
+	pub fn invalid_schema_id(&self, schema_id: SchemaId) -> DsnpGraphError {
+		let context = SchemaIdContext {
+			environment: Some(self.name().to_string()),
+			..self.get_config().schema_id_context()
+		};
+		DsnpGraphError::InvalidSchemaId(schema_id, context)
+	}
 }
 
 /// Supported Dsnp Versions
@@ -155,6 +305,11 @@ pub enum DsnpVersion {
 pub struct SchemaConfig {
 	pub dsnp_version: DsnpVersion,
 	pub connection_type: ConnectionType,
+	/// Number of connections that fit into a page of this schema, overriding the benchmarked or
+	/// calculated capacity of its connection type. Stored as `0` when not set, so the type stays
+	/// C compatible
+	#[serde(rename = "pageCapacity", default, skip_serializing_if = "Option::is_none")]
+	pub page_capacity: Option<NonZeroU32>,
 }
 
 /// Config
@@ -170,14 +325,25 @@ pub struct Config {
 	#[serde(rename = "maxGraphPageSizeBytes")]
 	pub max_graph_page_size_bytes: u32,
 
-	/// Maximum page id
+	/// Maximum page id, values outside of the `PageId` range are rejected when the config is
+	/// loaded
 	#[serde(rename = "maxPageId")]
-	pub max_page_id: u32,
+	pub max_page_id: PageId,
 
 	/// Maximum size of a key page in bytes
 	#[serde(rename = "maxKeyPageSizeBytes")]
 	pub max_key_page_size_bytes: u32,
 
+	/// Maximum size of an exported update payload in bytes, if chain imposes a lower limit than
+	/// the graph page size
+	#[serde(rename = "maxUpdatePayloadBytes", default, skip_serializing_if = "Option::is_none")]
+	pub max_update_payload_bytes: Option<u32>,
+
+	/// Exports the connections of public pages in the order they are stored instead of sorting
+	/// them by dsnp user id, which keeps the payloads of older sdk versions
+	#[serde(rename = "legacyPublicPageOrder", default, skip_serializing_if = "std::ops::Not::not")]
+	pub legacy_public_page_order: bool,
+
 	/// Schema map
 	#[serde(rename = "schemaMap")]
 	#[serde_as(as = "Vec<(_, _)>")]
@@ -199,7 +365,56 @@ impl TryFrom<&str> for Config {
 	}
 }
 
+impl InputValidation for Config {
+	fn validate(&self) -> DsnpGraphResult<()> {
+		if self.dsnp_versions.is_empty() {
+			return Err(DsnpGraphError::InvalidConfig("no dsnp versions".to_string()))
+		}
+		if self.max_graph_page_size_bytes == 0 || self.max_key_page_size_bytes == 0 {
+			return Err(DsnpGraphError::InvalidConfig("page sizes should be positive".to_string()))
+		}
+		let mut connection_types = HashSet::new();
+		let mut schema_ids: Vec<_> = self.schema_map.keys().collect();
+		schema_ids.sort();
+		for schema_id in schema_ids {
+			if !connection_types.insert(self.schema_map[schema_id].connection_type) {
+				return Err(DsnpGraphError::InvalidConfig(format!(
+					"schema id {} uses an already mapped connection type",
+					schema_id
+				)))
+			}
+		}
+		if self.schema_map.contains_key(&self.graph_public_key_schema_id) {
+			return Err(DsnpGraphError::InvalidConfig(format!(
+				"public key schema id {} is also a graph schema",
+				self.graph_public_key_schema_id
+			)))
+		}
+		if let Some((schema_id, _)) = self
+			.schema_map
+			.iter()
+			.find(|(_, schema)| !self.dsnp_versions.contains(&schema.dsnp_version))
+		{
+			return Err(DsnpGraphError::InvalidConfig(format!(
+				"schema id {} uses an unsupported dsnp version",
+				schema_id
+			)))
+		}
+		Ok(())
+	}
+}
+
 impl Config {
+	/// Returns the maximum allowed size of a graph page payload in bytes, taking into account
+	/// both page size and update payload limits
+	pub fn get_max_graph_page_payload_bytes(&self) -> u32 {
+		match self.max_update_payload_bytes {
+			Some(max_update_payload_bytes) =>
+				self.max_graph_page_size_bytes.min(max_update_payload_bytes),
+			None => self.max_graph_page_size_bytes,
+		}
+	}
+
 	/// Returns the DSNP version for the given schema id
 	pub fn get_dsnp_version_from_schema_id(&self, schema_id: SchemaId) -> Option<DsnpVersion> {
 		if let Some(schema_config) = self.schema_map.get(&schema_id) {
@@ -221,6 +436,26 @@ impl Config {
 		None
 	}
 
+	/// Returns the configured page capacity override for the given schema id
+	pub fn get_page_capacity_from_schema_id(&self, schema_id: SchemaId) -> Option<NonZeroU32> {
+		self.schema_map
+			.get(&schema_id)
+			.and_then(|schema_config| schema_config.page_capacity)
+	}
+
+	/// Returns the schema ids of this config, used as context of `InvalidSchemaId` errors raised
+	/// where the environment is unknown
+	pub fn schema_id_context(&self) -> SchemaIdContext {
+		let mut valid_schema_ids: Vec<_> = self.schema_map.keys().copied().collect();
+		valid_schema_ids.sort();
+		SchemaIdContext { environment: None, valid_schema_ids }
+	}
+
+	/// Returns an `InvalidSchemaId` error listing the schema ids of this config
+	pub fn invalid_schema_id(&self, schema_id: SchemaId) -> DsnpGraphError {
+		DsnpGraphError::InvalidSchemaId(schema_id, self.schema_id_context())
+	}
+
 	/// Returns the schema id for the given DSNP version and connection type
 	pub fn get_schema_id_from_connection_type(
 		&self,
@@ -269,6 +504,57 @@ mod config_tests {
 		);
 	}
 
+	#[test]
+	fn connection_capability_matrix_should_list_capabilities_of_all_connection_types() {
+		let matrix = connection_capability_matrix();
+
+		assert_eq!(
+			matrix,
+			vec![
+				(
+					ConnectionType::Follow(PrivacyType::Public),
+					ConnectionCapabilities {
+						supports_prids: false,
+						double_sided: false,
+						encryptable: false
+					}
+				),
+				(
+					ConnectionType::Follow(PrivacyType::Private),
+					ConnectionCapabilities {
+						supports_prids: false,
+						double_sided: false,
+						encryptable: true
+					}
+				),
+				(
+					ConnectionType::Friendship(PrivacyType::Private),
+					ConnectionCapabilities {
+						supports_prids: true,
+						double_sided: true,
+						encryptable: true
+					}
+				),
+			]
+		);
+		assert!(ConnectionType::Friendship(PrivacyType::Public).supports(Capability::DoubleSided));
+		assert!(!ConnectionType::Friendship(PrivacyType::Public).supports(Capability::Prids));
+	}
+
+	#[test]
+	fn validate_dsnp_user_id_should_reject_zero_and_ids_above_max_issued() {
+		assert!(is_valid_dsnp_user_id(1, None));
+		assert!(is_valid_dsnp_user_id(u64::MAX, None));
+		assert!(is_valid_dsnp_user_id(100, Some(100)));
+		assert!(!is_valid_dsnp_user_id(0, None));
+		assert!(!is_valid_dsnp_user_id(101, Some(100)));
+		assert!(matches!(
+			validate_dsnp_user_id(101, Some(100)),
+			Err(DsnpGraphError::InvalidDsnpUserId(101))
+		));
+		assert!(validate_dsnp_user_id(7, Some(100)).is_ok());
+	}
+
 	#[test]
 	fn config_import_success() -> Result<(), serde_json::Error> {
 		let expected_config = Config {
@@ -276,6 +562,8 @@ mod config_tests {
 			max_graph_page_size_bytes: 1024,
 			max_page_id: 32,
 			max_key_page_size_bytes: 65536,
+			max_update_payload_bytes: None,
+			legacy_public_page_order: false,
 			dsnp_versions: vec![DsnpVersion::Version1_0],
 			graph_public_key_schema_id: 7,
 			schema_map: HashMap::from([
@@ -284,6 +572,7 @@ mod config_tests {
 					SchemaConfig {
 						dsnp_version: DsnpVersion::Version1_0,
 						connection_type: ConnectionType::Follow(PrivacyType::Public),
+						page_capacity: None,
 					},
 				),
 				(
@@ -291,6 +580,7 @@ mod config_tests {
 					SchemaConfig {
 						dsnp_version: DsnpVersion::Version1_0,
 						connection_type: ConnectionType::Follow(PrivacyType::Private),
+						page_capacity: None,
 					},
 				),
 				(
@@ -298,6 +588,7 @@ mod config_tests {
 					SchemaConfig {
 						dsnp_version: DsnpVersion::Version1_0,
 						connection_type: ConnectionType::Friendship(PrivacyType::Private),
+						page_capacity: None,
 					},
 				),
 			]),
@@ -307,6 +598,104 @@ mod config_tests {
 		Ok(())
 	}
 
+	#[test]
+	fn max_graph_page_payload_bytes_should_respect_update_payload_limit() {
+		let mut config = MAINNET_CONFIG.clone();
+		assert_eq!(config.get_max_graph_page_payload_bytes(), config.max_graph_page_size_bytes);
+
+		config.max_update_payload_bytes = Some(config.max_graph_page_size_bytes / 2);
+		assert_eq!(config.get_max_graph_page_payload_bytes(), config.max_graph_page_size_bytes / 2);
+
+		config.max_update_payload_bytes = Some(config.max_graph_page_size_bytes * 2);
+		assert_eq!(config.get_max_graph_page_payload_bytes(), config.max_graph_page_size_bytes);
+	}
+
+	#[test]
+	fn generated_schema_ids_should_match_environment_configs() {
+		use ConnectionType::{Follow, Friendship};
+		use PrivacyType::{Private, Public};
+		let expected = [
+			(
+				Environment::Mainnet,
+				mainnet::GRAPH_PUBLIC_KEY_SCHEMA_ID,
+				[
+					mainnet::PUBLIC_FOLLOW_SCHEMA_ID,
+					mainnet::PRIVATE_FOLLOW_SCHEMA_ID,
+					mainnet::PRIVATE_FRIENDSHIP_SCHEMA_ID,
+				],
+			),
+			(
+				Environment::Rococo,
+				rococo::GRAPH_PUBLIC_KEY_SCHEMA_ID,
+				[
+					rococo::PUBLIC_FOLLOW_SCHEMA_ID,
+					rococo::PRIVATE_FOLLOW_SCHEMA_ID,
+					rococo::PRIVATE_FRIENDSHIP_SCHEMA_ID,
+				],
+			),
+			(
+				Environment::TestnetPaseo,
+				testnet_paseo::GRAPH_PUBLIC_KEY_SCHEMA_ID,
+				[
+					testnet_paseo::PUBLIC_FOLLOW_SCHEMA_ID,
+					testnet_paseo::PRIVATE_FOLLOW_SCHEMA_ID,
+					testnet_paseo::PRIVATE_FRIENDSHIP_SCHEMA_ID,
+				],
+			),
+		];
+
+		for (environment, public_key_schema_id, schema_ids) in expected {
+			assert_eq!(environment.get_config().graph_public_key_schema_id, public_key_schema_id);
+			for (connection_type, schema_id) in
+				[Follow(Public), Follow(Private), Friendship(Private)]
+					.into_iter()
+					.zip(schema_ids)
+			{
+				assert_eq!(environment.schema_id_for(connection_type), Some(schema_id));
+			}
+			assert_eq!(environment.schema_id_for(Friendship(Public)), None);
+		}
+	}
+
+	#[test]
+	fn invalid_schema_id_should_list_valid_schema_ids_of_environment() {
+		// arrange
+		let environment = Environment::Mainnet;
+		let mut valid_schema_ids: Vec<_> =
+			environment.get_config().schema_map.keys().copied().collect();
+		valid_schema_ids.sort();
+		let highest = *valid_schema_ids.last().expect("should have schema ids");
+
+		// act
+		let error = environment.invalid_schema_id(highest + 100);
+		let config_error = environment.get_config().invalid_schema_id(highest + 100);
+
+		// assert
+		let DsnpGraphError::InvalidSchemaId(schema_id, context) = &error else {
+			panic!("should be invalid schema id")
+		};
+		assert_eq!(*schema_id, highest + 100);
+		assert_eq!(context.environment.as_deref(), Some("mainnet"));
+		assert_eq!(context.valid_schema_ids, valid_schema_ids);
+		assert_eq!(context.nearest(highest + 100), Some(highest));
+		assert_eq!(
+			error.to_string(),
+			format!(
+				"Invalid schema id: {} in mainnet environment, valid schema ids are {:?}, nearest is {}",
+				highest + 100,
+				valid_schema_ids,
+				highest
+			)
+		);
+		assert!(config_error
+			.to_string()
+			.starts_with(&format!("Invalid schema id: {}, valid schema ids are", highest + 100)));
+		assert_eq!(
+			DsnpGraphError::InvalidSchemaId(0, SchemaIdContext::default()).to_string(),
+			"Invalid schema id: 0"
+		);
+	}
+
 	#[test]
 	fn config_import_failure() {
 		assert!(<Config as TryFrom<&str>>::try_from("bad json").is_err());
@@ -328,4 +717,11 @@ mod config_tests {
 		let _ = TESTNET_PASEO_CONFIG;
 		Ok(())
 	}
+
+	#[test]
+	fn bundled_configs_should_pass_validation() {
+		assert!(MAINNET_CONFIG.validate().is_ok());
+		assert!(ROCOCO_CONFIG.validate().is_ok());
+		assert!(TESTNET_PASEO_CONFIG.validate().is_ok());
+	}
 }
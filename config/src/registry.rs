@@ -0,0 +1,223 @@
+//! Registry of named custom environments, allowing configs to be loaded at runtime
+//!
+use crate::{
+	errors::{DsnpGraphError, DsnpGraphResult},
+	Config, Environment, InputValidation,
+};
+use lazy_static::lazy_static;
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::{Arc, RwLock},
+};
+
+lazy_static! {
+	/// Configs registered by name
+	static ref REGISTERED_CONFIGS: RwLock<HashMap<String, Arc<Config>>> =
+		RwLock::new(HashMap::new());
+}
+
+/// A custom environment referencing a config registered in [`EnvironmentRegistry`]
+///
+/// The config is captured when the environment is constructed, so re-registering a name only
+/// affects environments constructed afterwards
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CustomEnvironment {
+	name: String,
+	config: Arc<Config>,
+}
+
+impl CustomEnvironment {
+	/// Returns the name the config was registered with
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Returns the config
+	pub fn config(&self) -> &Config {
+		&self.config
+	}
+}
+
+/// Process wide registry of named configs used by `Environment::Custom`
+pub struct EnvironmentRegistry;
+
+impl EnvironmentRegistry {
+	/// Validates and registers a config under the given name, replacing any previous one
+	pub fn register(name: &str, config: Config) -> DsnpGraphResult<()> {
+		Self::validate_name(name)?;
+		config.validate()?;
+		let mut configs = REGISTERED_CONFIGS
+			.write()
+			.map_err(|e| DsnpGraphError::FailedtoWriteLock(e.to_string()))?;
+		configs.insert(name.to_string(), Arc::new(config));
+		Ok(())
+	}
+
+	/// Parses, validates and registers a config from its JSON representation
+	pub fn register_json(name: &str, json: &str) -> DsnpGraphResult<()> {
+		let config = Config::try_from(json)
+			.map_err(|e| DsnpGraphError::InvalidConfig(format!("{}: {}", name, e)))?;
+		Self::register(name, config)
+	}
+
+	/// Loads multiple configs from a JSON object keyed by environment name
+	///
+	/// All configs are validated before any of them is registered, and the registered names are
+	/// returned in sorted order
+	pub fn load_json(json: &str) -> DsnpGraphResult<Vec<String>> {
+		let configs: BTreeMap<String, Config> =
+			serde_json::from_str(json).map_err(|e| DsnpGraphError::InvalidConfig(e.to_string()))?;
+		for (name, config) in configs.iter() {
+			Self::validate_name(name)?;
+			config
+				.validate()
+				.map_err(|e| DsnpGraphError::InvalidConfig(format!("{}: {}", name, e)))?;
+		}
+
+		let mut registered = REGISTERED_CONFIGS
+			.write()
+			.map_err(|e| DsnpGraphError::FailedtoWriteLock(e.to_string()))?;
+		let names = configs.keys().cloned().collect();
+		registered.extend(configs.into_iter().map(|(name, config)| (name, Arc::new(config))));
+		Ok(names)
+	}
+
+	/// Removes a registered config, returns true if it existed
+	pub fn unregister(name: &str) -> DsnpGraphResult<bool> {
+		let mut configs = REGISTERED_CONFIGS
+			.write()
+			.map_err(|e| DsnpGraphError::FailedtoWriteLock(e.to_string()))?;
+		Ok(configs.remove(name).is_some())
+	}
+
+	/// Returns the config registered under the given name
+	pub fn get(name: &str) -> DsnpGraphResult<Option<Arc<Config>>> {
+		let configs = REGISTERED_CONFIGS
+			.read()
+			.map_err(|e| DsnpGraphError::FailedtoReadLock(e.to_string()))?;
+		Ok(configs.get(name).cloned())
+	}
+
+	/// Returns all registered names in sorted order
+	pub fn names() -> DsnpGraphResult<Vec<String>> {
+		let configs = REGISTERED_CONFIGS
+			.read()
+			.map_err(|e| DsnpGraphError::FailedtoReadLock(e.to_string()))?;
+		let mut names: Vec<_> = configs.keys().cloned().collect();
+		names.sort();
+		Ok(names)
+	}
+
+	/// Constructs a custom environment referencing the config registered under the given name
+	pub fn environment(name: &str) -> DsnpGraphResult<Environment> {
+		let config =
+			Self::get(name)?.ok_or_else(|| DsnpGraphError::UnknownEnvironment(name.to_string()))?;
+		Ok(Environment::Custom(CustomEnvironment { name: name.to_string(), config }))
+	}
+
+	fn validate_name(name: &str) -> DsnpGraphResult<()> {
+		if name.trim().is_empty() {
+			return Err(DsnpGraphError::InvalidConfig("environment name is empty".to_string()))
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{ConnectionType, DsnpVersion, PageId, PrivacyType, SchemaConfig};
+	use pretty_assertions::assert_eq;
+
+	fn test_config(max_page_id: PageId) -> Config {
+		Config {
+			sdk_max_stale_friendship_days: 90,
+			max_graph_page_size_bytes: 1024,
+			max_page_id,
+			max_key_page_size_bytes: 65536,
+			max_update_payload_bytes: None,
+			legacy_public_page_order: false,
+			dsnp_versions: vec![DsnpVersion::Version1_0],
+			graph_public_key_schema_id: 7,
+			schema_map: HashMap::from([(
+				8,
+				SchemaConfig {
+					dsnp_version: DsnpVersion::Version1_0,
+					connection_type: ConnectionType::Follow(PrivacyType::Public),
+					page_capacity: None,
+				},
+			)]),
+		}
+	}
+
+	#[test]
+	fn registered_config_should_be_resolvable_as_custom_environment() {
+		// arrange
+		EnvironmentRegistry::register("registry-test-a", test_config(10)).expect("should register");
+
+		// act
+		let env = EnvironmentRegistry::environment("registry-test-a").expect("should exist");
+
+		// assert
+		assert_eq!(env.get_config(), &test_config(10));
+		assert!(matches!(env, Environment::Custom(ref c) if c.name() == "registry-test-a"));
+	}
+
+	#[test]
+	fn re_registering_should_not_affect_existing_environments() {
+		// arrange
+		EnvironmentRegistry::register("registry-test-b", test_config(10)).expect("should register");
+		let old_env = EnvironmentRegistry::environment("registry-test-b").unwrap();
+
+		// act
+		EnvironmentRegistry::register("registry-test-b", test_config(20)).expect("should register");
+		let new_env = EnvironmentRegistry::environment("registry-test-b").unwrap();
+
+		// assert
+		assert_eq!(old_env.get_config().max_page_id, 10);
+		assert_eq!(new_env.get_config().max_page_id, 20);
+	}
+
+	#[test]
+	fn load_json_should_register_all_configs() {
+		// arrange
+		let config = serde_json::to_string(&test_config(5)).unwrap();
+		let json = format!(r#"{{"registry-test-c": {0}, "registry-test-d": {0}}}"#, config);
+
+		// act
+		let names = EnvironmentRegistry::load_json(&json).expect("should load");
+
+		// assert
+		assert_eq!(names, vec!["registry-test-c".to_string(), "registry-test-d".to_string()]);
+		assert!(EnvironmentRegistry::get("registry-test-d").unwrap().is_some());
+		assert!(EnvironmentRegistry::unregister("registry-test-d").unwrap());
+		assert!(matches!(
+			EnvironmentRegistry::environment("registry-test-d"),
+			Err(DsnpGraphError::UnknownEnvironment(_))
+		));
+	}
+
+	#[test]
+	fn load_json_with_invalid_config_should_register_nothing() {
+		// arrange
+		let valid = serde_json::to_string(&test_config(5)).unwrap();
+		let mut invalid_config = test_config(5);
+		invalid_config.dsnp_versions = vec![];
+		let invalid = serde_json::to_string(&invalid_config).unwrap();
+		let json = format!(r#"{{"registry-test-e": {}, "registry-test-f": {}}}"#, valid, invalid);
+
+		// act
+		let res = EnvironmentRegistry::load_json(&json);
+
+		// assert
+		assert!(matches!(res, Err(DsnpGraphError::InvalidConfig(_))));
+		assert!(EnvironmentRegistry::get("registry-test-e").unwrap().is_none());
+	}
+
+	#[test]
+	fn register_json_with_malformed_json_should_fail() {
+		let res = EnvironmentRegistry::register_json("registry-test-g", "{ not json");
+
+		assert!(matches!(res, Err(DsnpGraphError::InvalidConfig(_))));
+	}
+}
@@ -0,0 +1,185 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use dsnp_graph_config::{
+	builder::ConfigBuilder, ConnectionType, Environment, PageId, PrivacyType, SchemaId,
+};
+use dsnp_graph_core::{
+	api::{
+		api::{GraphAPI, GraphState},
+		api_types::{Action, Connection, ImportBundle},
+	},
+	util::generators::{
+		generate_connect_actions, generate_connections, generate_import_bundle, generate_key_pair,
+		generate_public_key_bundles,
+	},
+};
+
+const OWNER_DSNP_USER_ID: u64 = 1;
+const SMALL_GRAPH_SIZE: usize = 50;
+const LARGE_GRAPH_SIZE: usize = 1_500;
+const LARGE_ACTIONS_SIZE: usize = 10_000;
+const USERS_IN_ACTIONS: usize = 100;
+const HUGE_GRAPH_SIZE: usize = 100_000;
+const HUGE_GRAPH_MAX_PAGE_ID: PageId = 2_000;
+const UPDATE_BATCH_SIZE: usize = 1_000;
+
+fn schema_id_for(env: &Environment, connection_type: ConnectionType) -> SchemaId {
+	env.get_config()
+		.get_schema_id_from_connection_type(connection_type)
+		.expect("schema should exist")
+}
+
+fn import_bundle(
+	env: &Environment,
+	connection_type: ConnectionType,
+	graph_size: usize,
+) -> ImportBundle {
+	let schema_id = schema_id_for(env, connection_type);
+	let connections = generate_connections(1_000, graph_size);
+	let key_pair = match connection_type.privacy_type() {
+		PrivacyType::Public => None,
+		PrivacyType::Private => Some(generate_key_pair()),
+	};
+	generate_import_bundle(env, OWNER_DSNP_USER_ID, schema_id, &connections, key_pair.as_ref())
+}
+
+fn import_benchmarks(c: &mut Criterion) {
+	let env = Environment::Mainnet;
+	let mut group = c.benchmark_group("import");
+	for (name, connection_type) in [
+		("public_follow", ConnectionType::Follow(PrivacyType::Public)),
+		("private_follow", ConnectionType::Follow(PrivacyType::Private)),
+		("private_friendship", ConnectionType::Friendship(PrivacyType::Private)),
+	] {
+		for (size_name, size) in [("small", SMALL_GRAPH_SIZE), ("large", LARGE_GRAPH_SIZE)] {
+			let bundles = vec![import_bundle(&env, connection_type, size)];
+			group.bench_function(format!("{}_{}", name, size_name), |b| {
+				b.iter_batched(
+					|| GraphState::new(env.clone()),
+					|mut state| {
+						state.import_users_data(black_box(&bundles)).expect("should import");
+						state
+					},
+					BatchSize::SmallInput,
+				)
+			});
+		}
+	}
+	group.finish();
+}
+
+fn apply_actions_benchmarks(c: &mut Criterion) {
+	let env = Environment::Mainnet;
+	let schema_id = schema_id_for(&env, ConnectionType::Follow(PrivacyType::Public));
+	let per_user = LARGE_ACTIONS_SIZE / USERS_IN_ACTIONS;
+	let actions: Vec<Action> = (0..USERS_IN_ACTIONS as u64)
+		.flat_map(|owner| {
+			let ids: Vec<_> =
+				generate_connections(100_000, per_user).into_iter().map(|(id, _)| id).collect();
+			generate_connect_actions(owner + 1, schema_id, &ids)
+		})
+		.collect();
+
+	c.bench_function("apply_actions_10k", |b| {
+		b.iter_batched(
+			|| GraphState::new(env.clone()),
+			|mut state| {
+				state.apply_actions(black_box(&actions), &None).expect("should apply");
+				state
+			},
+			BatchSize::SmallInput,
+		)
+	});
+}
+
+fn export_benchmarks(c: &mut Criterion) {
+	let env = Environment::Mainnet;
+	let connection_type = ConnectionType::Friendship(PrivacyType::Private);
+	let schema_id = schema_id_for(&env, connection_type);
+	let existing = generate_connections(1_000, SMALL_GRAPH_SIZE);
+	let new_friends: Vec<_> = generate_connections(10_000, SMALL_GRAPH_SIZE)
+		.into_iter()
+		.map(|(id, _)| id)
+		.collect();
+
+	let key_pair = generate_key_pair();
+	let mut bundles = vec![generate_import_bundle(
+		&env,
+		OWNER_DSNP_USER_ID,
+		schema_id,
+		&existing,
+		Some(&key_pair),
+	)];
+	let all_friends: Vec<_> =
+		existing.iter().map(|(id, _)| *id).chain(new_friends.iter().copied()).collect();
+	bundles.extend(generate_public_key_bundles(&env, &all_friends));
+	let actions = generate_connect_actions(OWNER_DSNP_USER_ID, schema_id, &new_friends);
+
+	c.bench_function("export_private_friendship_with_prids", |b| {
+		b.iter_batched(
+			|| {
+				let mut state = GraphState::new(env.clone());
+				state.import_users_data(&bundles).expect("should import");
+				state.apply_actions(&actions, &None).expect("should apply");
+				state
+			},
+			|state| black_box(state.export_updates().expect("should export")),
+			BatchSize::SmallInput,
+		)
+	});
+}
+
+fn update_huge_graph_benchmarks(c: &mut Criterion) {
+	// the mainnet page limit can not hold 100k public follows
+	let config = ConfigBuilder::from_mainnet_with(|b| b.with_max_page_id(HUGE_GRAPH_MAX_PAGE_ID))
+		.expect("config should be valid");
+	let env = Environment::Dev(config);
+	let connection_type = ConnectionType::Follow(PrivacyType::Public);
+	let schema_id = schema_id_for(&env, connection_type);
+	let bundles = vec![import_bundle(&env, connection_type, HUGE_GRAPH_SIZE)];
+
+	let new_ids: Vec<_> = generate_connections(1_000_000, UPDATE_BATCH_SIZE)
+		.into_iter()
+		.map(|(id, _)| id)
+		.collect();
+	let mut actions = generate_connect_actions(OWNER_DSNP_USER_ID, schema_id, &new_ids);
+	// disconnects spread over all the imported pages
+	actions.extend(
+		(1_000..1_000 + HUGE_GRAPH_SIZE as u64)
+			.step_by(HUGE_GRAPH_SIZE / UPDATE_BATCH_SIZE)
+			.map(|dsnp_user_id| Action::Disconnect {
+				owner_dsnp_user_id: OWNER_DSNP_USER_ID,
+				connection: Connection { dsnp_user_id, schema_id },
+			}),
+	);
+
+	let filter_mode = match cfg!(feature = "disable-page-filter") {
+		true => "without_page_filter",
+		false => "with_page_filter",
+	};
+	let mut group = c.benchmark_group("update_100k");
+	group.sample_size(10);
+	group.bench_function(filter_mode, |b| {
+		b.iter_batched(
+			|| {
+				let mut state = GraphState::new(env.clone());
+				state.import_users_data(&bundles).expect("should import");
+				state
+			},
+			|mut state| {
+				state.apply_actions(black_box(&actions), &None).expect("should apply");
+				black_box(state.export_updates().expect("should export"))
+			},
+			BatchSize::LargeInput,
+		)
+	});
+	group.finish();
+}
+
+criterion_group!(
+	benches,
+	import_benchmarks,
+	apply_actions_benchmarks,
+	export_benchmarks,
+	update_huge_graph_benchmarks
+);
+criterion_main!(benches);
@@ -49,37 +49,70 @@
 
 use crate::{
 	api::api_types::{
-		Action, ActionOptions, Connection, DsnpKeys, ImportBundle, PrivacyType, Update,
+		Action, ActionOptions, AuditRecord, AuditResolution, CapacityReport, ChangesReport,
+		Connection, ConnectionPlacement, ConnectionRepair, DsnpKeys, ExportOptions, GraphMetrics,
+		GraphOperation, ImportBundle, ImportBundleRef, ImportOptions, ImportReport,
+		KeyPageCompaction, KeyPageStats, KeyRemovalReport, LintReport, MemoryReport, PageHash,
+		PageId, PageOverflowPolicy, PreviewedUpdate, PrivacyType, RotationImpact, RoundtripReport,
+		TimestampPolicy, Update, UpdateWithDetails, UserGraphStats,
 	},
 	dsnp::{
+		chain_adapter::{default_chain_adapter, SharedChainAdapter},
+		dsnp_configs::PublicKeyType,
 		dsnp_types::{DsnpGraphEdge, DsnpPublicKey, DsnpUserId},
 		reader_writer::DsnpReader,
 	},
 	frequency::Frequency,
 	graph::{
-		key_manager::{UserKeyProvider, USER_KEY_MANAGER},
+		graph::validate_page_capacities,
+		key_manager::{default_user_key_manager_factory, USER_KEY_MANAGER},
+		page_cache::DecryptedPageCache,
 		shared_state_manager::{
-			PriProvider, PublicKeyProvider, SharedStateManager, SHARED_STATE_MANAGER,
+			PriProvider, PublicKeyProvider, PublicKeySnapshot, SHARED_STATE_MANAGER,
 		},
 		updates::UpdateEvent,
 		user::UserGraph,
 	},
-	util::transactional_hashmap::{Transactional, TransactionalHashMap},
+	util::{
+		audit_log::AuditLog,
+		environment_usage::{environments_in_use, EnvironmentUsage},
+		instrumentation::Instrumentation,
+		key_bundle::{open_key_bundle, seal_key_bundle, KeyBundleLimits},
+		log_redaction::{redact_id, set_redaction_mode, RedactionMode},
+		state_hash::{StateHash, StateHasher},
+		time::{system_time_provider, SharedTimeProvider},
+		transactional_hashmap::{Transactional, TransactionalHashMap},
+	},
 };
 use dryoc::keypair::StackKeyPair;
 use dsnp_graph_config::{
 	errors::{DsnpGraphError, DsnpGraphResult},
-	ConnectionType, Environment, GraphKeyType, InputValidation, SchemaId,
+	Capability, ConnectionType, Environment, GraphKeyType, InputValidation, SchemaId,
 };
 use log::Level;
 use log_result_proc_macro::log_result_err;
+#[cfg(feature = "parallel-import")]
+use rayon::prelude::*;
 use std::{
-	collections::{hash_map::Entry, HashSet},
-	sync::{Arc, RwLock},
+	collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet},
+	sync::{Arc, Mutex, RwLock},
+	time::{Duration, Instant},
 };
 
 use super::api_types::GraphKeyPair;
 
+/// Key manager types needed to provide a custom `UserKeyManagerFactory` to `GraphStateBuilder`
+pub use crate::graph::{
+	key_manager::{
+		SharedUserKeyManager, SharedUserKeyManagerFactory, UserKeyManager, UserKeyManagerBase,
+		UserKeyManagerFactory, UserKeyProvider,
+	},
+	shared_state_manager::SharedStateManager,
+};
+
+/// Number of actions validated and applied at once, after which the progress is reported
+const ACTION_CHUNK_SIZE: usize = 1_000;
+
 /// Root data structure that stores all underlying data structures inside
 #[derive(Debug)]
 pub struct GraphState {
@@ -90,34 +123,401 @@ pub struct GraphState {
 	/// a repository for published public keys
 	shared_state_manager: Arc<RwLock<SharedStateManager>>,
 
-	/// Dsnp users and their corresponding social graphs
-	user_map: TransactionalHashMap<DsnpUserId, UserGraph>,
+	/// Dsnp users and their corresponding social graphs, which are shared with read-only snapshots
+	/// and copied on their first change after being frozen
+	user_map: TransactionalHashMap<DsnpUserId, Arc<UserGraph>>,
+
+	/// Users whose graphs are changed since the last commit or rollback
+	staged_users: HashSet<DsnpUserId>,
+
+	/// Time source used for connection timestamps and stale friendship checks
+	time_provider: SharedTimeProvider,
+
+	/// Creates the key manager of each imported user graph
+	key_manager_factory: SharedUserKeyManagerFactory,
+
+	/// Reads and writes the chain specific payloads of graph pages and keys
+	chain_adapter: SharedChainAdapter,
+
+	/// Options used when exporting updates such as page overflow behavior and nonce mode
+	export_options: ExportOptions,
+
+	/// Options used when importing pages such as strict PRId validation
+	import_options: ImportOptions,
+
+	/// Collector of operation durations which only exists when instrumentation is enabled
+	instrumentation: Option<Mutex<Instrumentation>>,
+
+	/// Log of the applied actions which only exists when the audit log is enabled
+	audit_log: Option<Mutex<AuditLog>>,
+
+	/// Recently decrypted private pages which are reused when the same content is imported again
+	page_cache: Arc<Mutex<DecryptedPageCache>>,
+
+	/// Timestamp in seconds at which this `GraphState` was created
+	created_at: u64,
+
+	/// Timestamp in seconds of the latest successful import of users data
+	last_imported_at: Option<u64>,
+
+	/// Marks the environment as in use while this `GraphState` is alive
+	_environment_usage: EnvironmentUsage,
+}
+
+/// Builder of a `GraphState` with a custom time source, key managers or chain adapter
+#[derive(Debug, Clone)]
+pub struct GraphStateBuilder {
+	/// Environment of the built `GraphState`
+	environment: Environment,
+
+	/// Time source used for connection timestamps and stale friendship checks
+	time_provider: SharedTimeProvider,
+
+	/// Creates the key manager of each imported user graph
+	key_manager_factory: SharedUserKeyManagerFactory,
+
+	/// Reads and writes the chain specific payloads of graph pages and keys
+	chain_adapter: SharedChainAdapter,
+
+	/// Redaction of dsnp user ids in log output which is set for the process when built
+	log_redaction: Option<RedactionMode>,
+}
+
+impl GraphStateBuilder {
+	/// creates a builder with the system clock, the default in memory key managers and the
+	/// `Frequency` chain adapter
+	pub fn new(environment: Environment) -> Self {
+		Self {
+			environment,
+			time_provider: system_time_provider(),
+			key_manager_factory: default_user_key_manager_factory(),
+			chain_adapter: default_chain_adapter(),
+			log_redaction: None,
+		}
+	}
+
+	/// uses the provided time source such as a chain based timestamp
+	pub fn with_time_provider(mut self, time_provider: SharedTimeProvider) -> Self {
+		self.time_provider = time_provider;
+		self
+	}
+
+	/// uses the provided factory to create the key manager of each user graph, which allows
+	/// integrators to back user keys by secure enclaves or HSMs
+	pub fn with_key_manager_factory(
+		mut self,
+		key_manager_factory: SharedUserKeyManagerFactory,
+	) -> Self {
+		self.key_manager_factory = key_manager_factory;
+		self
+	}
+
+	/// uses the provided chain adapter to read and write the payloads of graph pages and keys,
+	/// which allows supporting other DSNP systems or future Frequency runtime versions
+	pub fn with_chain_adapter(mut self, chain_adapter: SharedChainAdapter) -> Self {
+		self.chain_adapter = chain_adapter;
+		self
+	}
+
+	/// redacts dsnp user ids in the log output of the SDK using the provided mode
+	///
+	/// Since all graph states share the same logger, the mode is set for the whole process when
+	/// the `GraphState` is built. See `util::log_redaction` for details.
+	pub fn with_log_redaction(mut self, mode: RedactionMode) -> Self {
+		self.log_redaction = Some(mode);
+		self
+	}
+
+	/// builds the `GraphState` after checking that a page capacity exists for every graph schema
+	/// in the config of the environment, either configured in its schema config or for its
+	/// connection type
+	#[log_result_err(Level::Error)]
+	pub fn try_build(self) -> DsnpGraphResult<GraphState> {
+		validate_page_capacities(self.environment.get_config())?;
+		Ok(self.build())
+	}
+
+	/// builds the `GraphState`
+	pub fn build(self) -> GraphState {
+		if let Some(mode) = self.log_redaction {
+			set_redaction_mode(mode);
+		}
+		GraphState {
+			created_at: self.time_provider.now_in_secs(),
+			user_map: TransactionalHashMap::new(),
+			staged_users: HashSet::new(),
+			shared_state_manager: Arc::new(RwLock::new(SharedStateManager::with_chain_adapter(
+				self.chain_adapter.clone(),
+			))),
+			time_provider: self.time_provider,
+			key_manager_factory: self.key_manager_factory,
+			chain_adapter: self.chain_adapter,
+			export_options: ExportOptions::default(),
+			import_options: ImportOptions::default(),
+			instrumentation: None,
+			audit_log: None,
+			page_cache: Arc::new(Mutex::new(DecryptedPageCache::default())),
+			last_imported_at: None,
+			_environment_usage: EnvironmentUsage::new(&self.environment),
+			environment: self.environment,
+		}
+	}
+}
+
+/// Read-only snapshot of a `GraphState` created by `GraphState::freeze`
+///
+/// The snapshot only exposes the query APIs and is `Send` and `Sync`, so connection queries can be
+/// answered from many threads while a single writer state keeps handling imports and actions.
+/// It shares the committed user graphs and public keys with the graph state it was frozen from,
+/// which copies a user graph only when changing it, so freezing and cloning a snapshot are cheap.
+///
+/// Queries which need the secret keys of users such as one sided private friendships are not
+/// available on a snapshot. The graphs of users whose key manager holds key pairs are copied into
+/// the snapshot with a key manager without them, so a snapshot never references secret keys.
+#[derive(Debug, Clone)]
+pub struct ReadonlyGraphState {
+	/// environment of the frozen graph state
+	environment: Environment,
+
+	/// timestamp in seconds of the latest successful import before freezing
+	last_imported_at: Option<u64>,
+
+	/// committed user graphs shared with the frozen graph state
+	user_graphs: Arc<HashMap<DsnpUserId, Arc<UserGraph>>>,
+
+	/// committed public keys and PRIds shared with the frozen graph state
+	public_keys: Arc<PublicKeySnapshot>,
+}
+
+impl ReadonlyGraphState {
+	/// returns the environment of the frozen graph state
+	pub fn environment(&self) -> &Environment {
+		&self.environment
+	}
+
+	/// returns the timestamp in seconds of the latest successful import before freezing
+	pub fn last_imported_at(&self) -> Option<u64> {
+		self.last_imported_at
+	}
+
+	/// Checks if the snapshot contains a user
+	pub fn contains_user_graph(&self, user_id: &DsnpUserId) -> bool {
+		self.user_graphs.contains_key(user_id)
+	}
+
+	/// Returns number of users in the snapshot
+	pub fn len(&self) -> usize {
+		self.user_graphs.len()
+	}
+
+	/// Checks if the snapshot contains no users
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// returns the frozen graph of a user
+	fn user_graph(&self, user_id: &DsnpUserId) -> DsnpGraphResult<&UserGraph> {
+		self.user_graphs
+			.get(user_id)
+			.map(|user_graph| user_graph.as_ref())
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))
+	}
+
+	/// Gets a list of all connections of the indicated type for the user
+	pub fn get_connections_for_user_graph(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+		include_pending: bool,
+	) -> DsnpGraphResult<Vec<DsnpGraphEdge>> {
+		Ok(self.user_graph(user_id)?.get_all_connections_of(*schema_id, include_pending))
+	}
+
+	/// returns a list dsnp user ids that require keys sorted by id
+	pub fn get_connections_without_keys(
+		&self,
+		include_pending: bool,
+	) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		self.get_connections_without_keys_page(include_pending, None, None)
+	}
+
+	/// returns at most `limit` dsnp user ids that require keys, sorted by id and starting after
+	/// `cursor`
+	pub fn get_connections_without_keys_page(
+		&self,
+		include_pending: bool,
+		cursor: Option<DsnpUserId>,
+		limit: Option<usize>,
+	) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		connections_without_keys_page(
+			&self.environment,
+			self.user_graphs.values().map(|user_graph| user_graph.as_ref()),
+			include_pending,
+			cursor,
+			limit,
+			|connections| {
+				Ok(connections
+					.into_iter()
+					.filter(|id| self.public_keys.get_imported_keys(*id).is_empty())
+					.collect())
+			},
+		)
+	}
+
+	/// Returns the users of pending added connections of a graph which were not exported before
+	/// freezing
+	pub fn pending_adds(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		self.user_graph(user_id)?.pending_adds(*schema_id)
+	}
+
+	/// Returns the users of pending removed connections of a graph which were not exported before
+	/// freezing
+	pub fn pending_removes(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		self.user_graph(user_id)?.pending_removes(*schema_id)
+	}
+
+	/// Returns the connections added and removed by the imported pages of a graph since the pages
+	/// with the provided content hashes were imported
+	pub fn changes_since(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+		page_hashes: &[(PageId, PageHash)],
+	) -> DsnpGraphResult<ChangesReport> {
+		self.user_graph(user_id)?.changes_since(*schema_id, page_hashes)
+	}
+
+	/// Returns the statistics of a graph of a user
+	pub fn get_user_graph_stats(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<UserGraphStats> {
+		self.user_graph(user_id)?.stats(*schema_id)
+	}
+
+	/// Gets a list of the committed public keys associated with a user
+	pub fn get_public_keys(&self, user_id: &DsnpUserId) -> DsnpGraphResult<Vec<DsnpPublicKey>> {
+		Ok(self.public_keys.get_imported_keys(*user_id).to_vec())
+	}
+}
+
+/// returns at most `limit` private friendship connections of the provided user graphs, for which
+/// `without_keys` keeps the ones without imported keys, sorted by id and starting after `cursor`
+fn connections_without_keys_page<'a>(
+	environment: &Environment,
+	user_graphs: impl Iterator<Item = &'a UserGraph>,
+	include_pending: bool,
+	cursor: Option<DsnpUserId>,
+	limit: Option<usize>,
+	without_keys: impl FnOnce(Vec<DsnpUserId>) -> DsnpGraphResult<Vec<DsnpUserId>>,
+) -> DsnpGraphResult<Vec<DsnpUserId>> {
+	if limit == Some(0) {
+		return Err(DsnpGraphError::InvalidInput("limit should be greater than zero".into()))
+	}
+	let private_friendship_schema_id = environment
+		.get_config()
+		.get_schema_id_from_connection_type(ConnectionType::Friendship(PrivacyType::Private))
+		.ok_or(DsnpGraphError::InvalidPrivateSchemaId)?;
+	let all_connections: BTreeSet<_> = user_graphs
+		.flat_map(|user_graph| {
+			user_graph.get_all_connections_of(private_friendship_schema_id, include_pending)
+		})
+		.map(|edge| edge.user_id)
+		.filter(|user_id| cursor.map_or(true, |cursor| *user_id > cursor))
+		.collect();
+	let without_keys = without_keys(all_connections.into_iter().collect())?;
+	Ok(without_keys.into_iter().take(limit.unwrap_or(usize::MAX)).collect())
 }
 
 /// Defines the main API to interact with Graph
+///
+/// Operations which only apply to some connection types, such as PRIds or key usage of encrypted
+/// pages, check `ConnectionType::capabilities` of the graph before reading it and fail with
+/// `UnsupportedOperation`. `connection_capability_matrix` of the config crate lists the
+/// capabilities of all connection types so UIs can only offer the supported operations.
 pub trait GraphAPI {
 	/// Checks if graph state contains a user
 	fn contains_user_graph(&self, user_id: &DsnpUserId) -> bool;
 
+	/// Checks if the graph of a user with the schema id can be exported, which requires the user
+	/// to be imported and for private graphs also an imported secret key of the user. Returns false
+	/// for read-only graphs and unknown schema ids
+	fn is_user_graph_writable(&self, user_id: &DsnpUserId, schema_id: &SchemaId) -> bool;
+
 	/// Returns number of users in the current graph state
 	fn len(&self) -> usize;
 
-	/// Removes the user graph from an SDK instance
+	/// Removes the user graph from an SDK instance, including its decrypted pages kept in the
+	/// cache of the `GraphState` so no plaintext outlives the graph it belongs to
 	fn remove_user_graph(&mut self, user_id: &DsnpUserId);
 
 	/// Imports raw data retrieved from the blockchain into users graph.
 	/// Will overwrite any existing graph data for any existing user,
 	/// but pending updates will be preserved.
+	///
+	/// Connections imported on more than one page are removed from all of them except the page
+	/// with the lowest id and the repaired pages are rewritten on the next export, see
+	/// `ImportOptions` for the other validations and repairs of imported pages. Decrypted private
+	/// pages are kept in a bounded cache until the changes are committed or rolled back, so the
+	/// same encrypted content is only decrypted once per call.
+	///
+	/// With the `parallel-import` feature enabled, bundles of different users are imported in
+	/// parallel while the bundles of each user keep their order. The import stays transactional
+	/// and fails with the error of the first failed bundle in the order of the payloads.
 	fn import_users_data(&mut self, payloads: &Vec<ImportBundle>) -> DsnpGraphResult<()>;
 
+	/// Imports raw data retrieved from the blockchain into users graph without copying the page
+	/// contents. Behaves the same as `import_users_data`.
+	fn import_users_data_borrowed(&mut self, payloads: &[ImportBundleRef]) -> DsnpGraphResult<()>;
+
+	/// Imports public keys of a user, such as a friendship counterpart, without their key page.
+	/// Each key should have its key id set to its itemized index on chain.
+	/// Will overwrite any existing imported keys for the user.
+	fn import_public_keys(
+		&mut self,
+		user_id: &DsnpUserId,
+		keys: Vec<DsnpPublicKey>,
+	) -> DsnpGraphResult<()>;
+
 	/// Calculates the necessary new key and graph page updates for all imported users and graph using their active
 	/// encryption key and return a list of updates
+	///
+	/// Connections that do not fit into the graph fail the export with `GraphCapacityExceeded`
+	/// unless a different `PageOverflowPolicy` is set, while a full page only moves connections to
+	/// other pages so `PageTriviallyFull` and `PageAggressivelyFull` are never returned. A new key
+	/// fails with `KeyPageSizeExceeded` if the key page of the user would exceed
+	/// `max_key_page_size_bytes`, which can be checked beforehand using `get_key_page_stats`.
 	fn export_updates(&self) -> DsnpGraphResult<Vec<Update>>;
 
+	/// Calculates the same updates as `export_updates` where each graph page update is paired with
+	/// the connections added to and removed from the page and its resulting connection count
+	fn export_updates_with_details(&self) -> DsnpGraphResult<Vec<UpdateWithDetails>>;
+
 	/// Calculates the necessary graph page updates for a single user, using their active encryption
 	/// key, and returns a list of graph page updates
 	fn export_user_graph_updates(&self, user_id: &DsnpUserId) -> DsnpGraphResult<Vec<Update>>;
 
+	/// Calculates the same updates as `export_updates` for the graphs with the provided schema ids
+	/// only. New keys are exported if the graph public key schema id is provided, and unknown
+	/// schema ids fail with `InvalidSchemaId`
+	fn export_updates_filtered(&self, schema_ids: &[SchemaId]) -> DsnpGraphResult<Vec<Update>>;
+
+	/// Calculates the pages that pending added connections of a user will land in once the
+	/// updates are exported
+	fn get_connection_placements(
+		&self,
+		user_id: &DsnpUserId,
+	) -> DsnpGraphResult<Vec<ConnectionPlacement>>;
+
 	/// Applies Actions (Connect or Disconnect) to the list of pending actions for a users graph
 	fn apply_actions(
 		&mut self,
@@ -125,10 +525,95 @@ pub trait GraphAPI {
 		options: &Option<ActionOptions>,
 	) -> DsnpGraphResult<()>;
 
+	/// Applies the actions the same way as `apply_actions` and calls `on_progress` with the fraction
+	/// of processed actions after each processed chunk of actions
+	///
+	/// Actions are validated and applied in chunks, so a failing action does not require validating
+	/// the whole batch upfront. When auto commit is disabled all actions are still validated before
+	/// applying any of them, since nothing would revert the chunks applied before an invalid one.
+	fn apply_actions_with_progress(
+		&mut self,
+		actions: &[Action],
+		options: &Option<ActionOptions>,
+		on_progress: &mut dyn FnMut(f64),
+	) -> DsnpGraphResult<()>;
+
+	/// Applies the actions and exports the updates of all imported users in a single call, which
+	/// saves a round trip for bridge consumers. If applying the actions or exporting the updates
+	/// fails none of the actions are applied, unless auto commit is disabled in the options.
+	fn apply_actions_and_export(
+		&mut self,
+		actions: &[Action],
+		options: &Option<ActionOptions>,
+	) -> DsnpGraphResult<Vec<Update>>;
+
 	/// Force re-calculates the imported graphs. This is useful to ensure the pages are using the
 	/// latest encryption key or refresh calculated PRIds or remove any empty pages and ...
 	fn force_recalculate_graphs(&self, user_id: &DsnpUserId) -> DsnpGraphResult<Vec<Update>>;
 
+	/// Exports `DeletePage` updates for every imported page of all graphs of a user, which removes
+	/// the user's entire graph from the chain. Pending updates are ignored and published keys are
+	/// kept since graph keys can not be removed from the itemized key page.
+	fn export_graph_removal(&self, user_id: &DsnpUserId) -> DsnpGraphResult<Vec<Update>>;
+
+	/// Exports the graph of a user with pending updates applied and imports it back into a scratch
+	/// state, reporting whether the connections survive the round-trip. The graph is exported using
+	/// the export options of the state, like `export_updates` does, so connections skipped by the
+	/// `PageOverflowPolicy` are reported as missing. This can be used as a safety check before
+	/// submitting the updates on chain.
+	fn verify_roundtrip(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<RoundtripReport>;
+
+	/// Returns the statistics of a graph of a user, including the metadata of the private pages
+	/// which were imported without the secret keys
+	fn get_user_graph_stats(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<UserGraphStats>;
+
+	/// Checks the imported graphs of a user against the spec level rules of DSNP and the chain
+	/// limits, and returns the found warnings and errors
+	fn lint_user_graph(&self, user_id: &DsnpUserId) -> DsnpGraphResult<LintReport>;
+
+	/// Estimates the remaining capacity of a graph after applying the pending updates, which can
+	/// be used to check if more connections can be added before exporting the updates, since
+	/// exporting connections that do not fit fails with `GraphCapacityExceeded` by default
+	fn remaining_capacity(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<CapacityReport>;
+
+	/// Returns the connections added and removed by the imported pages of a graph since the pages
+	/// with the provided content hashes, such as the `page_hashes` of an older `ChangesReport`,
+	/// were imported. The last few imported versions of each page are kept for this, and changed
+	/// pages whose versions are unknown are reported as unresolved so they can be read again.
+	fn changes_since(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+		page_hashes: &[(PageId, PageHash)],
+	) -> DsnpGraphResult<ChangesReport>;
+
+	/// Returns the users of pending added connections of a graph which are not exported yet, which
+	/// can be used to resolve conflicts with changes made by the other side of a connection
+	fn pending_adds(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<Vec<DsnpUserId>>;
+
+	/// Returns the users of pending removed connections of a graph which are not exported yet
+	fn pending_removes(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<Vec<DsnpUserId>>;
+
 	/// Gets a list of all connections of the indicated type for the user
 	fn get_connections_for_user_graph(
 		&self,
@@ -137,18 +622,80 @@ pub trait GraphAPI {
 		include_pending: bool,
 	) -> DsnpGraphResult<Vec<DsnpGraphEdge>>;
 
-	/// returns a list dsnp user ids that require keys
-	fn get_connections_without_keys(&self) -> DsnpGraphResult<Vec<DsnpUserId>>;
+	/// returns a list dsnp user ids that require keys sorted by id
+	fn get_connections_without_keys(
+		&self,
+		include_pending: bool,
+	) -> DsnpGraphResult<Vec<DsnpUserId>>;
+
+	/// returns at most `limit` dsnp user ids that require keys, sorted by id and starting after
+	/// `cursor`. The last returned id is the cursor of the next page.
+	fn get_connections_without_keys_page(
+		&self,
+		include_pending: bool,
+		cursor: Option<DsnpUserId>,
+		limit: Option<usize>,
+	) -> DsnpGraphResult<Vec<DsnpUserId>>;
 
 	/// Gets a list of all private friendship connections that are only valid from users side
+	///
+	/// All imported public keys of the counterpart are tried, so friendships stay verified while
+	/// the counterpart is rotating its keys. `get_verifying_key_ids` returns which key id verified
+	/// each connection.
 	fn get_one_sided_private_friendship_connections(
 		&self,
 		user_id: &DsnpUserId,
+		include_pending: bool,
 	) -> DsnpGraphResult<Vec<DsnpGraphEdge>>;
 
+	/// Stages removal of all imported connections of a graph which were added more than
+	/// `older_than_days` ago and returns the ids of the pruned connections. Only
+	/// `disable_auto_commit` of the options is used, the same way as `apply_actions`
+	fn prune_connections(
+		&mut self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+		older_than_days: u32,
+		options: &Option<ActionOptions>,
+	) -> DsnpGraphResult<Vec<DsnpUserId>>;
+
 	/// Gets a list published and imported public keys associated with a user
 	fn get_public_keys(&self, user_id: &DsnpUserId) -> DsnpGraphResult<Vec<DsnpPublicKey>>;
 
+	/// Gets the id of the key that was used to decrypt each imported private page of a graph,
+	/// failing with `UnsupportedOperation` for graphs that are not encrypted. Exports do not change
+	/// the imported pages, so pages re-encrypted with a newer key report it once the exported pages
+	/// are imported again
+	fn get_page_key_usage(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<Vec<(PageId, u64)>>;
+
+	/// Gets the number of keys and the utilization of the key page for a user, including any
+	/// newly added key which is not published yet
+	fn get_key_page_stats(&self, user_id: &DsnpUserId) -> DsnpGraphResult<KeyPageStats>;
+
+	/// Calculates the itemized deletions that rewrite the key page of a user with only its unique
+	/// keys in chain order, or `None` if the key page has no duplicated keys. Fails if the user has
+	/// a new key which is not exported yet
+	///
+	/// The last occurrence of each key is kept so the active key does not change. Deleting items
+	/// shifts the ids of later keys, so pages encrypted with them should be re-exported afterwards
+	/// using `force_recalculate_graphs`.
+	fn export_key_page_compaction(
+		&self,
+		user_id: &DsnpUserId,
+	) -> DsnpGraphResult<Option<KeyPageCompaction>>;
+
+	/// Gets the key id that the next published key of a user will be assigned, which is also the
+	/// id of any added key which is not published yet
+	fn get_next_key_id(&self, user_id: &DsnpUserId) -> DsnpGraphResult<u64>;
+
+	/// Estimates the memory used by each imported user graph, the shared key store and the
+	/// pending updates
+	fn memory_footprint(&self) -> DsnpGraphResult<MemoryReport>;
+
 	/// Returns the deserialized dsnp keys without importing
 	fn deserialize_dsnp_keys(keys: &Option<DsnpKeys>) -> DsnpGraphResult<Vec<DsnpPublicKey>>;
 
@@ -160,26 +707,39 @@ pub trait GraphAPI {
 impl Transactional for GraphState {
 	/// Commits all underlying changes
 	fn commit(&mut self) {
-		let ids: Vec<_> = self.user_map.inner().keys().copied().collect();
-		for uid in ids {
+		// only graphs which are changed get copied if they are shared with a snapshot
+		for uid in self.staged_users.drain() {
 			if let Some(u) = self.user_map.get_mut(&uid) {
-				u.commit();
+				Arc::make_mut(u).commit();
 			}
 		}
+		for u in self.user_map.inner().values() {
+			u.user_key_manager.write().unwrap().commit();
+		}
 		self.user_map.commit();
 		self.shared_state_manager.write().unwrap().commit();
+		self.page_cache.lock().unwrap_or_else(|e| e.into_inner()).clear();
+		if let Some(audit_log) = &self.audit_log {
+			audit_log.lock().unwrap_or_else(|e| e.into_inner()).commit();
+		}
 	}
 
 	/// Rollbacks all underlying changes
 	fn rollback(&mut self) {
 		self.user_map.rollback();
-		let ids: Vec<_> = self.user_map.inner().keys().copied().collect();
-		for uid in ids {
+		for uid in self.staged_users.drain() {
 			if let Some(u) = self.user_map.get_mut(&uid) {
-				u.rollback();
+				Arc::make_mut(u).rollback();
 			}
 		}
+		for u in self.user_map.inner().values() {
+			u.user_key_manager.write().unwrap().rollback();
+		}
 		self.shared_state_manager.write().unwrap().rollback();
+		self.page_cache.lock().unwrap_or_else(|e| e.into_inner()).clear();
+		if let Some(audit_log) = &self.audit_log {
+			audit_log.lock().unwrap_or_else(|e| e.into_inner()).rollback();
+		}
 	}
 }
 
@@ -190,6 +750,27 @@ impl GraphAPI for GraphState {
 		self.user_map.inner().contains_key(user_id)
 	}
 
+	/// Checks if the graph of a user with the schema id can be exported
+	fn is_user_graph_writable(&self, user_id: &DsnpUserId, schema_id: &SchemaId) -> bool {
+		let user_graph = match self.user_map.get(user_id) {
+			Some(user_graph) => user_graph,
+			None => return false,
+		};
+		match self
+			.environment
+			.get_config()
+			.get_connection_type_from_schema_id(*schema_id)
+			.map(|c| c.privacy_type())
+		{
+			Some(PrivacyType::Public) => true,
+			Some(PrivacyType::Private) => user_graph
+				.user_key_manager
+				.read()
+				.map_or(false, |manager| manager.get_resolved_active_key(*user_id).is_some()),
+			None => false,
+		}
+	}
+
 	/// Returns number of users in the current graph state
 	fn len(&self) -> usize {
 		self.user_map.len()
@@ -199,6 +780,8 @@ impl GraphAPI for GraphState {
 	fn remove_user_graph(&mut self, user_id: &DsnpUserId) {
 		self.user_map.remove(user_id);
 		self.user_map.commit();
+		self.staged_users.remove(user_id);
+		self.page_cache.lock().unwrap_or_else(|e| e.into_inner()).remove_user(*user_id);
 	}
 
 	/// Imports raw data retrieved from the blockchain into a user graph.
@@ -206,31 +789,63 @@ impl GraphAPI for GraphState {
 	/// but pending updates will be preserved.
 	#[log_result_err(Level::Error)]
 	fn import_users_data(&mut self, payloads: &Vec<ImportBundle>) -> DsnpGraphResult<()> {
-		let result = self.do_import_users_data(payloads);
-		match result {
-			DsnpGraphResult::Ok(_) => self.commit(),
-			DsnpGraphResult::Err(_) => self.rollback(),
-		};
-		result
+		let payloads: Vec<ImportBundleRef> = payloads.iter().map(ImportBundleRef::from).collect();
+		self.import_users_data_borrowed(&payloads)
+	}
+
+	/// Imports raw data retrieved from the blockchain into a user graph without copying the page
+	/// contents.
+	fn import_users_data_borrowed(&mut self, payloads: &[ImportBundleRef]) -> DsnpGraphResult<()> {
+		self.import_users_data_borrowed_with_report(payloads).map(|_| ())
+	}
+
+	/// Imports public keys of a user without their key page
+	#[log_result_err(Level::Error)]
+	fn import_public_keys(
+		&mut self,
+		user_id: &DsnpUserId,
+		keys: Vec<DsnpPublicKey>,
+	) -> DsnpGraphResult<()> {
+		let mut shared_state_manager = self
+			.shared_state_manager
+			.write()
+			.map_err(|_| DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string()))?;
+		// a failing import does not stage any change, and only the keys of this user are
+		// committed so uncommitted changes of other calls are kept staged
+		shared_state_manager.import_public_keys(*user_id, &keys)?;
+		shared_state_manager.commit_user_keys(*user_id);
+		Ok(())
 	}
 
 	/// Calculates the necessary page updates for all users graphs and return as a map of pages to
 	/// be updated and/or removed or added keys
 	#[log_result_err(Level::Error)]
 	fn export_updates(&self) -> DsnpGraphResult<Vec<Update>> {
-		let mut result = self
+		Ok(self.export_updates_with_details()?.into_iter().map(|u| u.update).collect())
+	}
+
+	/// Calculates the necessary page updates for all users graphs paired with the connection
+	/// changes of each updated page
+	#[log_result_err(Level::Error)]
+	fn export_updates_with_details(&self) -> DsnpGraphResult<Vec<UpdateWithDetails>> {
+		let mut result: Vec<UpdateWithDetails> = self
 			.shared_state_manager
 			.read()
 			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
-			.export_new_key_updates()?;
+			.export_new_key_updates(self.environment.get_config().max_key_page_size_bytes)?
+			.into_iter()
+			.map(|update| UpdateWithDetails { update, details: None })
+			.collect();
 		let imported_users: Vec<_> = self.user_map.inner().keys().copied().collect();
 		for user_id in imported_users {
 			let user_graph = self
 				.user_map
 				.get(&user_id)
 				.ok_or(DsnpGraphError::UserGraphNotImported(user_id))?;
-			let updates = user_graph.calculate_updates()?;
-			result.extend(updates);
+			let start = self.start_timer();
+			let updates = user_graph.calculate_updates_with_placements(self.export_options);
+			self.record_timing(user_id, GraphOperation::Export, start);
+			result.extend(updates?.0);
 		}
 		Ok(result)
 	}
@@ -243,16 +858,67 @@ impl GraphAPI for GraphState {
 			.shared_state_manager
 			.read()
 			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
-			.export_new_key_updates_for_user(user_id)?;
+			.export_new_key_updates_for_user(
+				user_id,
+				self.environment.get_config().max_key_page_size_bytes,
+			)?;
 		let user_graph = self
 			.user_map
 			.get(&user_id)
 			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
-		let updates = user_graph.calculate_updates()?;
-		result.extend(updates);
+		let start = self.start_timer();
+		let updates = user_graph.calculate_updates(self.export_options);
+		self.record_timing(*user_id, GraphOperation::Export, start);
+		result.extend(updates?);
+		Ok(result)
+	}
+
+	/// Calculates the necessary page updates for the graphs with the provided schema ids of all
+	/// users and the added keys if the graph public key schema id is included
+	#[log_result_err(Level::Error)]
+	fn export_updates_filtered(&self, schema_ids: &[SchemaId]) -> DsnpGraphResult<Vec<Update>> {
+		let config = self.environment.get_config();
+		if let Some(unknown) = schema_ids.iter().find(|schema_id| {
+			**schema_id != config.graph_public_key_schema_id &&
+				config.get_connection_type_from_schema_id(**schema_id).is_none()
+		}) {
+			return Err(self.environment.invalid_schema_id(*unknown))
+		}
+
+		let mut result = vec![];
+		if schema_ids.contains(&config.graph_public_key_schema_id) {
+			result.extend(
+				self.shared_state_manager
+					.read()
+					.map_err(|_| {
+						DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string())
+					})?
+					.export_new_key_updates(config.max_key_page_size_bytes)?,
+			);
+		}
+		for (user_id, user_graph) in self.user_map.inner() {
+			let start = self.start_timer();
+			let updates = user_graph.calculate_updates_for_schemas(self.export_options, schema_ids);
+			self.record_timing(*user_id, GraphOperation::Export, start);
+			result.extend(updates?);
+		}
 		Ok(result)
 	}
 
+	/// Calculates the pages that pending added connections of a user will land in
+	#[log_result_err(Level::Error)]
+	fn get_connection_placements(
+		&self,
+		user_id: &DsnpUserId,
+	) -> DsnpGraphResult<Vec<ConnectionPlacement>> {
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+		let (_, placements) = user_graph.calculate_updates_with_placements(self.export_options)?;
+		Ok(placements)
+	}
+
 	/// Applies actions (Connect, Disconnect) to imported users graph
 	#[log_result_err(Level::Error)]
 	fn apply_actions(
@@ -260,20 +926,32 @@ impl GraphAPI for GraphState {
 		actions: &[Action],
 		options: &Option<ActionOptions>,
 	) -> DsnpGraphResult<()> {
-		let disable_auto_commit = match options {
-			Some(ActionOptions { disable_auto_commit, .. }) => disable_auto_commit,
-			None => &false,
-		};
+		self.apply_actions_with_progress(actions, options, &mut |_| {})
+	}
 
-		let result = self.do_apply_actions(actions, options);
+	/// Applies actions (Connect, Disconnect) to imported users graph and reports the progress
+	#[log_result_err(Level::Error)]
+	fn apply_actions_with_progress(
+		&mut self,
+		actions: &[Action],
+		options: &Option<ActionOptions>,
+		on_progress: &mut dyn FnMut(f64),
+	) -> DsnpGraphResult<()> {
+		let result = self.do_apply_actions(actions, options, on_progress);
+		self.auto_commit(options, result)
+	}
 
-		if !disable_auto_commit {
-			match result {
-				DsnpGraphResult::Ok(_) => self.commit(),
-				DsnpGraphResult::Err(_) => self.rollback(),
-			}
-		}
-		result
+	/// Applies the actions and exports the updates of all imported users
+	#[log_result_err(Level::Error)]
+	fn apply_actions_and_export(
+		&mut self,
+		actions: &[Action],
+		options: &Option<ActionOptions>,
+	) -> DsnpGraphResult<Vec<Update>> {
+		let result = self
+			.do_apply_actions(actions, options, &mut |_| {})
+			.and_then(|_| self.export_updates());
+		self.auto_commit(options, result)
 	}
 
 	/// Exports the graph pages for a certain user encrypted using the latest published key
@@ -284,47 +962,172 @@ impl GraphAPI for GraphState {
 			.get(&user_id)
 			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
 
-		user_graph.force_calculate_graphs()
+		user_graph.force_calculate_graphs(self.export_options)
 	}
 
-	/// Gets a list of all connections of the indicated type for the user
+	/// Exports removal of all imported pages of a user's graphs
 	#[log_result_err(Level::Error)]
-	fn get_connections_for_user_graph(
-		&self,
-		user_id: &DsnpUserId,
-		schema_id: &SchemaId,
-		include_pending: bool,
-	) -> DsnpGraphResult<Vec<DsnpGraphEdge>> {
+	fn export_graph_removal(&self, user_id: &DsnpUserId) -> DsnpGraphResult<Vec<Update>> {
 		let user_graph = self
 			.user_map
 			.get(user_id)
 			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
 
-		Ok(user_graph.get_all_connections_of(*schema_id, include_pending))
+		Ok(user_graph.calculate_graph_removal())
 	}
 
-	/// returns a list dsnp user ids that require keys
+	/// Verifies that the exported graph of a user can be imported back without losing connections
 	#[log_result_err(Level::Error)]
-	fn get_connections_without_keys(&self) -> DsnpGraphResult<Vec<DsnpUserId>> {
-		let private_friendship_schema_id = self
-			.environment
-			.get_config()
-			.get_schema_id_from_connection_type(ConnectionType::Friendship(PrivacyType::Private))
-			.ok_or(DsnpGraphError::InvalidPrivateSchemaId)?;
-		let all_connections: HashSet<_> = self
+	fn verify_roundtrip(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<RoundtripReport> {
+		let user_graph = self
 			.user_map
-			.inner()
-			.values()
-			.flat_map(|user_graph| {
-				user_graph.get_all_connections_of(private_friendship_schema_id, true)
-			})
-			.map(|edge| edge.user_id)
-			.collect();
-		Ok(self
-			.shared_state_manager
-			.read()
-			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
-			.find_users_without_keys(all_connections.into_iter().collect()))
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+
+		user_graph.verify_roundtrip(*schema_id, self.export_options)
+	}
+
+	/// Returns the statistics of a graph of a user
+	#[log_result_err(Level::Error)]
+	fn get_user_graph_stats(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<UserGraphStats> {
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+
+		user_graph.stats(*schema_id)
+	}
+
+	/// Lints the imported graphs of a user
+	#[log_result_err(Level::Error)]
+	fn lint_user_graph(&self, user_id: &DsnpUserId) -> DsnpGraphResult<LintReport> {
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+
+		Ok(LintReport { dsnp_user_id: *user_id, issues: user_graph.lint()? })
+	}
+
+	/// Estimates the remaining capacity of a graph of a user
+	#[log_result_err(Level::Error)]
+	fn remaining_capacity(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<CapacityReport> {
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+
+		user_graph.remaining_capacity(*schema_id)
+	}
+
+	/// Returns the connection changes of a graph since an older sync
+	#[log_result_err(Level::Error)]
+	fn changes_since(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+		page_hashes: &[(PageId, PageHash)],
+	) -> DsnpGraphResult<ChangesReport> {
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+
+		user_graph.changes_since(*schema_id, page_hashes)
+	}
+
+	/// Returns the users of pending added connections of a graph
+	#[log_result_err(Level::Error)]
+	fn pending_adds(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+
+		user_graph.pending_adds(*schema_id)
+	}
+
+	/// Returns the users of pending removed connections of a graph
+	#[log_result_err(Level::Error)]
+	fn pending_removes(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+
+		user_graph.pending_removes(*schema_id)
+	}
+
+	/// Gets a list of all connections of the indicated type for the user
+	#[log_result_err(Level::Error)]
+	fn get_connections_for_user_graph(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+		include_pending: bool,
+	) -> DsnpGraphResult<Vec<DsnpGraphEdge>> {
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+
+		Ok(user_graph.get_all_connections_of(*schema_id, include_pending))
+	}
+
+	/// returns a list dsnp user ids that require keys sorted by id
+	#[log_result_err(Level::Error)]
+	fn get_connections_without_keys(
+		&self,
+		include_pending: bool,
+	) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		self.get_connections_without_keys_page(include_pending, None, None)
+	}
+
+	/// returns at most `limit` dsnp user ids that require keys, sorted by id and starting after
+	/// `cursor`
+	#[log_result_err(Level::Error)]
+	fn get_connections_without_keys_page(
+		&self,
+		include_pending: bool,
+		cursor: Option<DsnpUserId>,
+		limit: Option<usize>,
+	) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		connections_without_keys_page(
+			&self.environment,
+			self.user_map.inner().values().map(|user_graph| user_graph.as_ref()),
+			include_pending,
+			cursor,
+			limit,
+			|connections| {
+				Ok(self
+					.shared_state_manager
+					.read()
+					.map_err(|_| {
+						DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string())
+					})?
+					.find_users_without_keys(connections))
+			},
+		)
 	}
 
 	/// Gets a list of all private friendship connections that are only valid from users side
@@ -332,20 +1135,56 @@ impl GraphAPI for GraphState {
 	fn get_one_sided_private_friendship_connections(
 		&self,
 		user_id: &DsnpUserId,
+		include_pending: bool,
 	) -> DsnpGraphResult<Vec<DsnpGraphEdge>> {
 		let private_friendship_schema_id = self
 			.environment
 			.get_config()
 			.get_schema_id_from_connection_type(ConnectionType::Friendship(PrivacyType::Private))
 			.ok_or(DsnpGraphError::InvalidPrivateSchemaId)?;
+		self.ensure_capability(
+			private_friendship_schema_id,
+			Capability::Prids,
+			"get_one_sided_private_friendship_connections",
+		)?;
 		let user_graph = match self.user_map.get(user_id) {
 			Some(graph) => graph,
 			None => return Err(DsnpGraphError::UserGraphNotImported(*user_id)),
 		};
+		user_graph.get_one_sided_friendships(private_friendship_schema_id, include_pending)
+	}
+
+	/// Gets the id of the key that was used to decrypt each imported private page of a graph
+	#[log_result_err(Level::Error)]
+	fn get_page_key_usage(
+		&self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+	) -> DsnpGraphResult<Vec<(PageId, u64)>> {
+		self.ensure_capability(*schema_id, Capability::Encryption, "get_page_key_usage")?;
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
 		let graph = user_graph
-			.graph(&private_friendship_schema_id)
-			.ok_or(DsnpGraphError::InvalidSchemaId(private_friendship_schema_id))?;
-		graph.get_one_sided_friendships()
+			.graph(schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(*schema_id))?;
+		Ok(graph.get_page_key_usage())
+	}
+
+	/// Stages removal of all imported connections of a graph which are older than provided days
+	#[log_result_err(Level::Error)]
+	fn prune_connections(
+		&mut self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+		older_than_days: u32,
+		options: &Option<ActionOptions>,
+	) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		let result = self.user_graph_mut(user_id).and_then(|user_graph| {
+			user_graph.prune_connections(*schema_id, older_than_days.into())
+		});
+		self.auto_commit(options, result)
 	}
 
 	/// Gets a list published and imported public keys associated with a user
@@ -357,6 +1196,63 @@ impl GraphAPI for GraphState {
 			.get_public_keys(user_id))
 	}
 
+	/// Gets the number of keys and the utilization of the key page for a user
+	fn get_key_page_stats(&self, user_id: &DsnpUserId) -> DsnpGraphResult<KeyPageStats> {
+		let (key_count, used_bytes) = self
+			.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.get_key_page_usage(user_id)?;
+		Ok(KeyPageStats {
+			key_count,
+			used_bytes,
+			max_bytes: self.environment.get_config().max_key_page_size_bytes,
+		})
+	}
+
+	/// Calculates the itemized deletions removing the duplicated keys of a user
+	#[log_result_err(Level::Error)]
+	fn export_key_page_compaction(
+		&self,
+		user_id: &DsnpUserId,
+	) -> DsnpGraphResult<Option<KeyPageCompaction>> {
+		self.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.get_key_page_compaction(user_id)
+	}
+
+	/// Gets the key id that the next published key of a user will be assigned
+	fn get_next_key_id(&self, user_id: &DsnpUserId) -> DsnpGraphResult<u64> {
+		Ok(self
+			.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.get_next_key_id(*user_id))
+	}
+
+	/// Estimates the memory used by user graphs, shared key store and pending updates
+	#[log_result_err(Level::Error)]
+	fn memory_footprint(&self) -> DsnpGraphResult<MemoryReport> {
+		let shared_state_manager = self
+			.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?;
+		let mut user_graph_bytes = vec![];
+		let mut pending_update_bytes = shared_state_manager.new_keys_memory_footprint();
+		for (user_id, user_graph) in self.user_map.inner() {
+			user_graph_bytes.push((*user_id, user_graph.memory_footprint()?));
+			pending_update_bytes += user_graph.update_tracker().memory_footprint();
+		}
+		user_graph_bytes.sort();
+
+		Ok(MemoryReport {
+			user_graph_bytes,
+			shared_state_bytes: shared_state_manager.memory_footprint(),
+			pending_update_bytes,
+		})
+	}
+
 	/// Returns the deserialized dsnp keys
 	fn deserialize_dsnp_keys(keys: &Option<DsnpKeys>) -> DsnpGraphResult<Vec<DsnpPublicKey>> {
 		// sorting by index in ascending mode
@@ -394,342 +1290,4107 @@ impl GraphAPI for GraphState {
 impl GraphState {
 	/// creates a new graph state with the given `Environment`
 	pub fn new(environment: Environment) -> Self {
-		Self {
-			environment,
-			user_map: TransactionalHashMap::new(),
-			shared_state_manager: Arc::new(RwLock::new(SharedStateManager::new())),
-		}
+		Self::with_time_provider(environment, system_time_provider())
 	}
 
-	/// Gets an existing or creates a new UserGraph
-	fn get_or_create_user_graph(
+	/// creates a new graph state with the given `Environment`, failing with
+	/// `PageCapacityNotConfigured` if its config and the page capacities are inconsistent
+	///
+	/// Dev configs with schemas of connection types without a benchmarked page capacity can set
+	/// the `page_capacity` of their `SchemaConfig`, which overrides the capacity of the type.
+	pub fn try_new(environment: Environment) -> DsnpGraphResult<Self> {
+		GraphStateBuilder::new(environment).try_build()
+	}
+
+	/// creates a new graph state with the given `Environment` and time source
+	///
+	/// Hosts can use this to provide a chain based timestamp instead of the system clock
+	pub fn with_time_provider(environment: Environment, time_provider: SharedTimeProvider) -> Self {
+		GraphStateBuilder::new(environment).with_time_provider(time_provider).build()
+	}
+
+	/// returns the number of alive graph states in this process by the name of their environment,
+	/// which allows hosts serving several chains to check which environments are in use
+	pub fn environments_in_use() -> BTreeMap<String, usize> {
+		environments_in_use()
+	}
+
+	/// imports raw data retrieved from the blockchain the same as `import_users_data` and returns
+	/// the repairs applied to inconsistent pages, such as connections imported on more than one
+	/// page. Repaired pages are rewritten on the next export.
+	pub fn import_users_data_with_report(
 		&mut self,
-		dsnp_user_id: DsnpUserId,
-	) -> DsnpGraphResult<&mut UserGraph> {
-		match self.user_map.entry(dsnp_user_id) {
-			Entry::Occupied(o) => Ok(o.into_mut()),
-			Entry::Vacant(v) => Ok(v.insert(UserGraph::new(
-				&dsnp_user_id,
-				&self.environment,
-				self.shared_state_manager.clone(),
-			))),
-		}
+		payloads: &Vec<ImportBundle>,
+	) -> DsnpGraphResult<ImportReport> {
+		let payloads: Vec<ImportBundleRef> = payloads.iter().map(ImportBundleRef::from).collect();
+		self.import_users_data_borrowed_with_report(&payloads)
 	}
 
-	/// main data importing logic
+	/// imports borrowed raw data the same as `import_users_data_borrowed` and returns the repairs
+	/// applied to inconsistent pages
 	#[log_result_err(Level::Error)]
-	fn do_import_users_data(&mut self, payloads: &Vec<ImportBundle>) -> DsnpGraphResult<()> {
-		for bundle in payloads {
-			bundle.validate()?;
-		}
-		for ImportBundle { schema_id, pages, dsnp_keys, dsnp_user_id, key_pairs } in payloads {
-			let connection_type_option =
-				self.environment.get_config().get_connection_type_from_schema_id(*schema_id);
+	pub fn import_users_data_borrowed_with_report(
+		&mut self,
+		payloads: &[ImportBundleRef],
+	) -> DsnpGraphResult<ImportReport> {
+		let result = self.do_import_users_data(payloads);
+		match result {
+			DsnpGraphResult::Ok(_) => {
+				self.commit();
+				self.last_imported_at = Some(self.time_provider.now_in_secs());
+			},
+			DsnpGraphResult::Err(_) => self.rollback(),
+		};
+		result
+	}
 
-			match dsnp_keys {
-				Some(dsnp_keys) => {
-					self.shared_state_manager
-						.write()
-						.map_err(|_| {
-							DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string())
-						})?
-						.import_dsnp_keys(&dsnp_keys)?;
-				},
-				None => (),
-			};
-			let user_graph = self.get_or_create_user_graph(*dsnp_user_id)?;
+	/// returns the timestamp in seconds at which this graph state was created
+	pub fn created_at(&self) -> u64 {
+		self.created_at
+	}
 
-			let include_secret_keys = !key_pairs.is_empty();
-			{
-				let mut user_key_manager = user_graph
-					.user_key_manager
-					.write()
-					.map_err(|_| DsnpGraphError::FailedtoWriteLock(USER_KEY_MANAGER.to_string()))?;
+	/// returns the timestamp in seconds of the latest successful import of users data
+	pub fn last_imported_at(&self) -> Option<u64> {
+		self.last_imported_at
+	}
 
-				user_key_manager.import_key_pairs(key_pairs.clone())?;
-			};
+	/// fails with `StaleGraphState` if this graph state was created more than `max_age` ago
+	///
+	/// Graph states are meant to be short lived, since imported pages and keys get outdated as
+	/// soon as they change on chain. Services that keep states around can call this before
+	/// applying actions or exporting updates to catch states that were accidentally cached.
+	#[log_result_err(Level::Info)]
+	pub fn assert_fresh(&self, max_age: Duration) -> DsnpGraphResult<()> {
+		let age = self.time_provider.now_in_secs().saturating_sub(self.created_at);
+		if age > max_age.as_secs() {
+			return Err(DsnpGraphError::StaleGraphState(age, max_age.as_secs()))
+		}
+		Ok(())
+	}
 
-			if pages.is_empty() {
-				// case where only keys are imported
-				continue;
-			}
+	/// returns the time source used by this graph state
+	pub fn time_provider(&self) -> &SharedTimeProvider {
+		&self.time_provider
+	}
 
-			let dsnp_config = user_graph
-				.get_dsnp_config(*schema_id)
-				.ok_or(DsnpGraphError::InvalidSchemaId(*schema_id))?;
+	/// Getter for the chain adapter used to read and write graph pages and keys
+	pub fn chain_adapter(&self) -> &SharedChainAdapter {
+		&self.chain_adapter
+	}
 
-			let graph = user_graph
-				.graph_mut(&schema_id)
-				.ok_or(DsnpGraphError::InvalidSchemaId(*schema_id))?;
-			graph.clear();
+	/// sets the behavior when exported connections do not fit into the allowed pages of a graph
+	pub fn set_page_overflow_policy(&mut self, page_overflow_policy: PageOverflowPolicy) {
+		self.export_options.page_overflow_policy = page_overflow_policy;
+	}
 
-			let connection_type =
-				connection_type_option.ok_or(DsnpGraphError::InvalidSchemaId(*schema_id))?;
+	/// returns the behavior when exported connections do not fit into the allowed pages of a graph
+	pub fn page_overflow_policy(&self) -> PageOverflowPolicy {
+		self.export_options.page_overflow_policy
+	}
 
-			match connection_type.privacy_type() {
-				PrivacyType::Public => {
-					graph.import_public(connection_type, pages)?;
-					user_graph.sync_updates(*schema_id);
-				},
-				PrivacyType::Private => {
-					// private keys are provided try to import the graph
-					if include_secret_keys {
-						graph.import_private(&dsnp_config, connection_type, pages)?;
-						user_graph.sync_updates(*schema_id);
-					}
+	/// sets the options used when exporting updates
+	pub fn set_export_options(&mut self, export_options: ExportOptions) {
+		self.export_options = export_options;
+	}
 
-					// since it's a private friendship import provided PRIs
-					if connection_type == ConnectionType::Friendship(PrivacyType::Private) {
-						self.shared_state_manager
-							.write()
-							.map_err(|_| {
-								DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string())
-							})?
-							.import_pri(*dsnp_user_id, pages)?;
-					}
-				},
-			};
-		}
-		Ok(())
+	/// returns the options used when exporting updates
+	pub fn export_options(&self) -> ExportOptions {
+		self.export_options
 	}
 
-	/// main updating logic
-	#[log_result_err(Level::Error)]
-	fn do_apply_actions(
-		&mut self,
-		actions: &[Action],
-		options: &Option<ActionOptions>,
-	) -> DsnpGraphResult<()> {
-		// pre validate all actions
-		for action in actions {
-			action.validate()?;
-		}
-
-		let (ignore_existing_connections, ignore_missing_connections) = match options {
-			Some(options) =>
-				(options.ignore_existing_connections, options.ignore_missing_connections),
-			None => (false, false),
-		};
-		// apply actions
-		for action in actions {
-			let owner_graph = self.get_or_create_user_graph(action.owner_dsnp_user_id())?;
-			match action {
-				Action::Connect {
-					connection: Connection { ref dsnp_user_id, ref schema_id },
-					dsnp_keys,
-					..
-				} => {
-					if owner_graph.graph_has_connection(*schema_id, *dsnp_user_id, true) {
-						if ignore_existing_connections {
-							log::warn!(
-								"Ignoring add redundant connection {} -> {}",
-								action.owner_dsnp_user_id(),
-								*dsnp_user_id
-							);
-							continue;
-						}
-
-						return Err(DsnpGraphError::ConnectionAlreadyExists(
-							action.owner_dsnp_user_id(),
-							*dsnp_user_id,
-						));
-					}
-					owner_graph.update_tracker_mut().register_update(
-						UpdateEvent::create_add(*dsnp_user_id, *schema_id),
-						ignore_existing_connections,
-					)?;
-					if let Some(inner_keys) = dsnp_keys {
-						self.shared_state_manager
-							.write()
-							.map_err(|_| {
-								DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string())
-							})?
-							.import_dsnp_keys(inner_keys)?;
-					}
-				},
-				Action::Disconnect {
-					connection: Connection { ref dsnp_user_id, ref schema_id },
-					..
-				} => {
-					if !owner_graph.graph_has_connection(*schema_id, *dsnp_user_id, true) {
-						if ignore_missing_connections {
-							log::warn!(
-								"Ignoring remove non-existent connection {} -> {}",
-								action.owner_dsnp_user_id(),
-								*dsnp_user_id
-							);
-							continue;
-						}
-
-						return Err(DsnpGraphError::ConnectionDoesNotExist(
-							action.owner_dsnp_user_id(),
-							*dsnp_user_id,
-						));
-					}
-					owner_graph.update_tracker_mut().register_update(
-						UpdateEvent::create_remove(*dsnp_user_id, *schema_id),
-						ignore_missing_connections,
-					)?;
-				},
-				Action::AddGraphKey { new_public_key, .. } => {
-					self.shared_state_manager
-						.write()
-						.map_err(|_| {
-							DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string())
-						})?
-						.add_new_key(action.owner_dsnp_user_id(), new_public_key.clone())?;
-				},
-			}
-		}
-		Ok(())
+	/// sets the options used when importing pages
+	pub fn set_import_options(&mut self, import_options: ImportOptions) {
+		self.import_options = import_options;
 	}
-}
 
-#[cfg(test)]
-mod test {
-	use super::*;
-	use crate::{
-		api::api_types::ResolvedKeyPair,
-		dsnp::{dsnp_configs::KeyPairType, dsnp_types::DsnpPrid},
-		util::builders::{ImportBundleBuilder, KeyDataBuilder},
-	};
-	use memory_stats::memory_stats;
-	use ntest::*;
+	/// returns the options used when importing pages
+	pub fn import_options(&self) -> ImportOptions {
+		self.import_options
+	}
 
-	#[test]
-	fn graph_contains_false() {
-		let state = GraphState::new(Environment::Mainnet);
-		assert!(!state.contains_user_graph(&0));
+	/// sets the maximum number of decrypted private pages kept to avoid decrypting the same page
+	/// content again when it is re-imported, zero disables caching. Cached pages are dropped when
+	/// the changes are committed or rolled back and when their user graph is removed.
+	pub fn set_decrypted_page_cache_capacity(&mut self, capacity: usize) {
+		self.page_cache.lock().unwrap_or_else(|e| e.into_inner()).set_capacity(capacity);
 	}
 
-	#[test]
-	fn graph_contains_true() {
-		let mut state = GraphState::new(Environment::Mainnet);
-		let _ = state.get_or_create_user_graph(0);
-		assert!(state.contains_user_graph(&0));
+	/// returns the maximum number of decrypted private pages kept
+	pub fn decrypted_page_cache_capacity(&self) -> usize {
+		self.page_cache.lock().unwrap_or_else(|e| e.into_inner()).capacity()
+	}
+
+	/// starts recording the durations of imports, exports and applied actions
+	pub fn enable_instrumentation(&mut self) {
+		if self.instrumentation.is_none() {
+			self.instrumentation = Some(Mutex::new(Instrumentation::default()));
+		}
+	}
+
+	/// stops recording durations and drops any recorded ones
+	pub fn disable_instrumentation(&mut self) {
+		self.instrumentation = None;
+	}
+
+	/// returns the timings recorded since the last call and clears them, or `None` if
+	/// instrumentation is not enabled
+	pub fn take_metrics(&self) -> Option<GraphMetrics> {
+		let instrumentation = self.instrumentation.as_ref()?;
+		// recorded durations stay valid even if a panic happened while holding the lock
+		let mut instrumentation = instrumentation.lock().unwrap_or_else(|e| e.into_inner());
+		Some(instrumentation.take_metrics())
+	}
+
+	/// starts recording the applied actions along with their resolution
+	pub fn enable_audit_log(&mut self) {
+		if self.audit_log.is_none() {
+			self.audit_log = Some(Mutex::new(AuditLog::default()));
+		}
+	}
+
+	/// stops recording applied actions and drops any recorded ones
+	pub fn disable_audit_log(&mut self) {
+		self.audit_log = None;
+	}
+
+	/// returns the actions committed or rolled back since the last call and clears them, or `None`
+	/// if the audit log is not enabled. Actions which are not committed or rolled back yet are
+	/// returned by a later call
+	pub fn drain_audit_log(&self) -> Option<Vec<AuditRecord>> {
+		let audit_log = self.audit_log.as_ref()?;
+		// recorded actions stay valid even if a panic happened while holding the lock
+		let mut audit_log = audit_log.lock().unwrap_or_else(|e| e.into_inner());
+		Some(audit_log.drain())
+	}
+
+	/// verifies the PRIds of multiple users towards `owner`, which can be used by diagnostic tools
+	/// to inspect private friendships. `None` is returned for the users that can not be verified,
+	/// such as the ones without imported PRIds or public keys. Only committed PRIds and keys are used
+	#[log_result_err(Level::Error)]
+	pub fn verify_connections(
+		&self,
+		owner: &DsnpUserId,
+		from: &[DsnpUserId],
+	) -> DsnpGraphResult<Vec<Option<bool>>> {
+		let user_graph =
+			self.user_map.get(owner).ok_or(DsnpGraphError::UserGraphNotImported(*owner))?;
+		let owner_keys = user_graph
+			.user_key_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+			.get_all_resolved_keys();
+
+		let snapshot = self
+			.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.committed_snapshot()
+			.load();
+		Ok(snapshot.verify_connections_batch(*owner, &owner_keys, from))
+	}
+
+	/// returns the ids of the public keys of `from` users which verify their connection to `owner`,
+	/// so diagnostic tools can tell which connections are verified by an older key of a user that
+	/// is rotating its keys. `None` is returned for the users that are not verified or can not be
+	/// verified. Only committed PRIds and keys are used
+	#[log_result_err(Level::Error)]
+	pub fn get_verifying_key_ids(
+		&self,
+		owner: &DsnpUserId,
+		from: &[DsnpUserId],
+	) -> DsnpGraphResult<Vec<Option<u64>>> {
+		let user_graph =
+			self.user_map.get(owner).ok_or(DsnpGraphError::UserGraphNotImported(*owner))?;
+		let owner_keys = user_graph
+			.user_key_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+			.get_all_resolved_keys();
+
+		let snapshot = self
+			.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.committed_snapshot()
+			.load();
+		Ok(snapshot.find_verifying_key_ids_batch(*owner, &owner_keys, from))
+	}
+
+	/// estimates the pages and bytes that get rewritten in the private graphs of a user after
+	/// publishing a new key, without changing this graph state
+	///
+	/// the forced recalculation runs on a deep clone where a newly generated key is published for
+	/// the user. Connections without published keys are reported in `users_missing_keys` and a
+	/// temporary key is used for their PRIds, which has the same size as the real ones.
+	#[log_result_err(Level::Error)]
+	pub fn estimate_key_rotation_impact(
+		&self,
+		user_id: &DsnpUserId,
+	) -> DsnpGraphResult<RotationImpact> {
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+		let config = self.environment.get_config();
+		let connections: Vec<_> = config
+			.get_schema_id_from_connection_type(ConnectionType::Friendship(PrivacyType::Private))
+			.map_or(vec![], |schema_id| user_graph.get_all_connections_of(schema_id, false))
+			.into_iter()
+			.map(|edge| edge.user_id)
+			.collect();
+		let mut users_missing_keys = self
+			.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.find_users_without_keys(connections);
+		users_missing_keys.sort();
+		let key_pairs: Vec<GraphKeyPair> = user_graph
+			.user_key_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+			.get_all_resolved_keys()
+			.iter()
+			.map(|resolved| GraphKeyPair::try_from(&resolved.key_pair))
+			.collect::<DsnpGraphResult<_>>()?;
+
+		let mut dry_run = self.deep_clone()?;
+		let temporary_key = Self::generate_keypair(GraphKeyType::X25519)?;
+		for missing in &users_missing_keys {
+			dry_run.import_public_keys(
+				missing,
+				vec![DsnpPublicKey { key: temporary_key.public_key.clone(), key_id: Some(0) }],
+			)?;
+		}
+		let new_key_pair = Self::generate_keypair(GraphKeyType::X25519)?;
+		let mut public_keys = dry_run.get_public_keys(user_id)?;
+		public_keys.push(DsnpPublicKey {
+			key: new_key_pair.public_key.clone(),
+			key_id: Some(dry_run.get_next_key_id(user_id)?),
+		});
+		dry_run.import_public_keys(user_id, public_keys)?;
+		dry_run
+			.get_or_create_user_graph(*user_id)?
+			.user_key_manager
+			.write()
+			.map_err(|_| DsnpGraphError::FailedtoWriteLock(USER_KEY_MANAGER.to_string()))?
+			.import_key_pairs([key_pairs, vec![new_key_pair]].concat())?;
+
+		let mut impact = RotationImpact { pages: 0, bytes: 0, users_missing_keys };
+		for update in dry_run.force_recalculate_graphs(user_id)? {
+			if let Update::PersistPage { schema_id, payload, .. } = update {
+				let is_private = config
+					.get_connection_type_from_schema_id(schema_id)
+					.map_or(false, |c| c.privacy_type() == PrivacyType::Private);
+				if is_private {
+					impact.pages += 1;
+					impact.bytes += payload.len();
+				}
+			}
+		}
+		Ok(impact)
+	}
+
+	/// exports the imported key pairs of a user as a versioned bundle which is encrypted using a
+	/// key derived from the passphrase (argon2id and XChaCha20-Poly1305), so wallets can migrate graph keys to another device by
+	/// importing the bundle there using `import_key_bundle`
+	#[log_result_err(Level::Error)]
+	pub fn export_key_bundle(
+		&self,
+		user_id: &DsnpUserId,
+		passphrase: &str,
+	) -> DsnpGraphResult<Vec<u8>> {
+		let key_pairs = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?
+			.user_key_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+			.export_key_pairs()?;
+		if key_pairs.is_empty() {
+			return Err(DsnpGraphError::InvalidInput(format!(
+				"No key pairs are imported for user {}",
+				user_id
+			)))
+		}
+
+		seal_key_bundle(*user_id, &key_pairs, passphrase, KeyBundleLimits::default())
+	}
+
+	/// imports the key pairs of a bundle created by `export_key_bundle`, replacing the imported
+	/// key pairs of the user. Fails with `InvalidKeyBundle` if the bundle belongs to another user
+	/// and with `DecryptionError` if the passphrase is wrong. The key pairs are staged like other
+	/// changes and only `disable_auto_commit` of the options is used, the same way as
+	/// `apply_actions`.
+	#[log_result_err(Level::Error)]
+	pub fn import_key_bundle(
+		&mut self,
+		user_id: &DsnpUserId,
+		bundle: &[u8],
+		passphrase: &str,
+		options: &Option<ActionOptions>,
+	) -> DsnpGraphResult<()> {
+		let (dsnp_user_id, key_pairs) = open_key_bundle(bundle, passphrase)?;
+		if dsnp_user_id != *user_id {
+			return Err(DsnpGraphError::InvalidKeyBundle(format!(
+				"bundle belongs to user {}",
+				dsnp_user_id
+			)))
+		}
+
+		let result = self.get_or_create_user_graph(dsnp_user_id).and_then(|user_graph| {
+			user_graph
+				.user_key_manager
+				.write()
+				.map_err(|_| DsnpGraphError::FailedtoWriteLock(USER_KEY_MANAGER.to_string()))?
+				.import_key_pairs(key_pairs)
+		});
+		self.auto_commit(options, result)
+	}
+
+	/// removes a single imported key pair of a user, such as a compromised or mistakenly imported
+	/// one, without rebuilding the graph state. Fails with `InvalidInput` if no imported key pair
+	/// matches the public key
+	///
+	/// the remaining key pairs are revalidated against the imported private pages, and the ones
+	/// which can not be decrypted anymore are listed in the returned report along with whether
+	/// the active key was removed, in which case a warning is logged as well. The removal is
+	/// staged like other changes and only `disable_auto_commit` of the options is used, the same
+	/// way as `apply_actions`.
+	#[log_result_err(Level::Error)]
+	pub fn remove_imported_keypair(
+		&mut self,
+		user_id: &DsnpUserId,
+		public_key: &[u8],
+		options: &Option<ActionOptions>,
+	) -> DsnpGraphResult<KeyRemovalReport> {
+		let result = self.do_remove_imported_keypair(user_id, public_key);
+		self.auto_commit(options, result)
+	}
+
+	/// stages the removal of an imported key pair and reports the pages it leaves undecryptable
+	fn do_remove_imported_keypair(
+		&mut self,
+		user_id: &DsnpUserId,
+		public_key: &[u8],
+	) -> DsnpGraphResult<KeyRemovalReport> {
+		let active_key_removed = {
+			let user_graph = self.user_graph_mut(user_id)?;
+			let mut user_key_manager = user_graph
+				.user_key_manager
+				.write()
+				.map_err(|_| DsnpGraphError::FailedtoWriteLock(USER_KEY_MANAGER.to_string()))?;
+			let active_key_removed = user_key_manager
+				.get_resolved_active_key(*user_id)
+				.map_or(false, |active| active.key_pair.get_public_key_raw() == public_key);
+			user_key_manager.remove_key_pair(public_key)?;
+			active_key_removed
+		};
+		self.page_cache
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.remove_key_pair(*user_id, public_key);
+
+		let user_graph = self
+			.user_map
+			.get(user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*user_id))?;
+		let mut undecryptable_pages = BTreeMap::new();
+		for (schema_id, graph) in user_graph.graphs().inner() {
+			let page_ids = graph.get_pages_without_resolved_keys()?;
+			if !page_ids.is_empty() {
+				undecryptable_pages.insert(*schema_id, page_ids);
+			}
+		}
+		let report =
+			KeyRemovalReport { dsnp_user_id: *user_id, active_key_removed, undecryptable_pages };
+		if !report.is_safe() {
+			log::warn!(
+				"Remaining key pairs of user {} can not decrypt {:?} or active key removed: {}",
+				redact_id(*user_id),
+				report.undecryptable_pages,
+				report.active_key_removed
+			);
+		}
+		Ok(report)
+	}
+
+	/// stages added connections of a graph in bulk from an edge list of connected users and the
+	/// time they were connected, such as follower lists migrated from other platforms, and
+	/// returns the number of staged connections
+	///
+	/// the time of each edge is the `since` value of the connection in ksecs since the unix epoch,
+	/// the same unit as the one stored on chain. Connections that already exist or are repeated in
+	/// the list are skipped. Fails with `GraphCapacityExceeded` without staging anything if the
+	/// remaining connections do not fit into the graph. Only `disable_auto_commit` of the options
+	/// is used, the same way as `apply_actions`.
+	#[log_result_err(Level::Error)]
+	pub fn import_edge_list(
+		&mut self,
+		user_id: &DsnpUserId,
+		schema_id: &SchemaId,
+		edges: impl Iterator<Item = (DsnpUserId, u64)>,
+		options: &Option<ActionOptions>,
+	) -> DsnpGraphResult<usize> {
+		let result = self
+			.get_or_create_user_graph(*user_id)
+			.and_then(|user_graph| user_graph.import_edge_list(*schema_id, edges));
+		self.auto_commit(options, result)
+	}
+
+	/// selects published keys of a user, such as a recovery key, that private pages of the user are
+	/// sealed to in addition to the active key when exported using
+	/// `EncryptionMode::MultiRecipientV1`, so any of their secret keys can decrypt the pages.
+	/// An empty list removes the additional recipients.
+	///
+	/// Fails with `ImportedKeyNotFound` if a key id is not among the imported public keys of the
+	/// user. The selection is not part of the transaction, so it is kept on rollbacks.
+	#[log_result_err(Level::Error)]
+	pub fn set_additional_recipient_keys(
+		&mut self,
+		user_id: &DsnpUserId,
+		key_ids: &[u64],
+	) -> DsnpGraphResult<()> {
+		let additional_recipients = {
+			let shared_state_manager = self
+				.shared_state_manager
+				.read()
+				.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?;
+			key_ids
+				.iter()
+				.map(|key_id| {
+					shared_state_manager
+						.get_key_by_id(*user_id, *key_id)
+						.ok_or(DsnpGraphError::ImportedKeyNotFound(*user_id, key_id.to_string()))?
+						.try_into()
+				})
+				.collect::<DsnpGraphResult<Vec<PublicKeyType>>>()?
+		};
+		self.user_graph_mut(user_id)?.set_additional_recipients(additional_recipients);
+		Ok(())
+	}
+
+	/// creates an independent copy of this graph state, including its uncommitted changes, which
+	/// can be used to run what-if analysis such as packing or exports without affecting this one
+	///
+	/// user graphs, key managers and shared keys and PRIds are duplicated, while the time source
+	/// and the key manager factory are shared. Instrumentation is not enabled on the copy.
+	#[log_result_err(Level::Error)]
+	pub fn deep_clone(&self) -> DsnpGraphResult<Self> {
+		let shared_state_manager = Arc::new(RwLock::new(
+			self.shared_state_manager
+				.read()
+				.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+				.clone(),
+		));
+
+		// current and rollback versions of a user graph share the same key manager
+		let mut key_managers: HashMap<DsnpUserId, SharedUserKeyManager> = HashMap::new();
+		let mut user_map = self.user_map.clone();
+		user_map.try_for_each_value_mut(|user_id, user_graph| -> DsnpGraphResult<()> {
+			let key_manager = match key_managers.entry(*user_id) {
+				Entry::Occupied(o) => o.get().clone(),
+				Entry::Vacant(v) => v
+					.insert(
+						user_graph
+							.user_key_manager
+							.read()
+							.map_err(|_| {
+								DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string())
+							})?
+							.clone_with_shared_state(shared_state_manager.clone()),
+					)
+					.clone(),
+			};
+			*user_graph = Arc::new(user_graph.clone_with_key_manager(key_manager));
+			Ok(())
+		})?;
+
+		Ok(Self {
+			environment: self.environment.clone(),
+			shared_state_manager,
+			user_map,
+			staged_users: self.staged_users.clone(),
+			time_provider: self.time_provider.clone(),
+			key_manager_factory: self.key_manager_factory.clone(),
+			chain_adapter: self.chain_adapter.clone(),
+			export_options: self.export_options,
+			import_options: self.import_options,
+			instrumentation: None,
+			audit_log: None,
+			page_cache: Arc::new(Mutex::new(DecryptedPageCache::new(
+				self.decrypted_page_cache_capacity(),
+			))),
+			created_at: self.created_at,
+			last_imported_at: self.last_imported_at,
+			_environment_usage: EnvironmentUsage::new(&self.environment),
+		})
+	}
+
+	/// returns the AddKey update that adding the public key to a user would export, together with
+	/// its size and the resulting usage of the key page, without staging the key
+	///
+	/// Fails the same way as applying an `AddGraphKey` action and exporting it would, so wallets
+	/// can show the exact payload before the user approves adding the key.
+	#[log_result_err(Level::Error)]
+	pub fn preview_add_key(
+		&self,
+		user_id: &DsnpUserId,
+		public_key: Vec<u8>,
+	) -> DsnpGraphResult<PreviewedUpdate> {
+		Action::AddGraphKey { owner_dsnp_user_id: *user_id, new_public_key: public_key.clone() }
+			.validate()?;
+		self.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.preview_add_key_update(
+				*user_id,
+				public_key,
+				self.environment.get_config().max_key_page_size_bytes,
+			)
+	}
+
+	/// creates a read-only snapshot of the committed changes of this graph state which can be
+	/// shared across threads, see `ReadonlyGraphState`
+	///
+	/// Later imports and actions of this graph state do not affect the snapshot. Uncommitted
+	/// changes, when auto commit is disabled, are left out of the snapshot. The committed user
+	/// graphs are shared instead of copied, so freezing after each write only copies the graphs
+	/// of users with uncommitted changes or imported key pairs, and a shared graph is copied once
+	/// it is changed next.
+	#[log_result_err(Level::Error)]
+	pub fn freeze(&self) -> DsnpGraphResult<ReadonlyGraphState> {
+		// committed user graphs are shared, only the ones with uncommitted changes are copied
+		let mut user_graphs = HashMap::new();
+		for (user_id, user_graph) in self.user_map.committed_iter() {
+			let user_graph = match self.staged_users.contains(user_id) {
+				true => Arc::new(user_graph.committed_copy()),
+				false => user_graph.clone(),
+			};
+			// key pairs are left out of the snapshot
+			let holds_key_pairs = user_graph
+				.user_key_manager
+				.read()
+				.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+				.holds_key_pairs();
+			let user_graph = match holds_key_pairs {
+				true => Arc::new(user_graph.clone_with_key_manager(Arc::new(RwLock::new(
+					UserKeyManager::new(*user_id, self.shared_state_manager.clone()),
+				)))),
+				false => user_graph,
+			};
+			user_graphs.insert(*user_id, user_graph);
+		}
+		let public_keys = self
+			.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.committed_snapshot()
+			.load();
+		Ok(ReadonlyGraphState {
+			environment: self.environment.clone(),
+			last_imported_at: self.last_imported_at,
+			user_graphs: Arc::new(user_graphs),
+			public_keys,
+		})
+	}
+
+	/// calculates a deterministic hash over the imported graphs, keys and pending updates,
+	/// including uncommitted changes, so services running the same imports and actions on
+	/// different nodes can cheaply verify they derived identical states before exporting
+	///
+	/// The hash does not depend on the order of imports or on the nonces of encrypted pages, see
+	/// `util::state_hash` for the canonical ordering of the hashed values.
+	#[log_result_err(Level::Error)]
+	pub fn state_hash(&self) -> DsnpGraphResult<StateHash> {
+		let mut hasher = StateHasher::new();
+		hasher.write_bytes(self.environment.name().as_bytes());
+		self.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.hash_into(&mut hasher);
+
+		let users: BTreeMap<_, _> = self.user_map.inner().iter().collect();
+		hasher.write_u64(users.len() as u64);
+		for (dsnp_user_id, user_graph) in users {
+			hasher.write_u64(*dsnp_user_id);
+			user_graph.hash_into(&mut hasher)?;
+		}
+		Ok(hasher.finish())
+	}
+
+	/// returns the connection type of a schema after checking that it supports the capability
+	/// required by the operation, so unsupported operations fail before reading any graph
+	fn ensure_capability(
+		&self,
+		schema_id: SchemaId,
+		capability: Capability,
+		operation: &str,
+	) -> DsnpGraphResult<ConnectionType> {
+		let connection_type = self
+			.environment
+			.get_config()
+			.get_connection_type_from_schema_id(schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(schema_id))?;
+		if !connection_type.supports(capability) {
+			return Err(DsnpGraphError::UnsupportedOperation(
+				operation.to_string(),
+				capability,
+				connection_type,
+			))
+		}
+		Ok(connection_type)
+	}
+
+	/// Gets an existing or creates a new UserGraph
+	fn get_or_create_user_graph(
+		&mut self,
+		dsnp_user_id: DsnpUserId,
+	) -> DsnpGraphResult<&mut UserGraph> {
+		self.staged_users.insert(dsnp_user_id);
+		let user_graph = match self.user_map.entry(dsnp_user_id) {
+			Entry::Occupied(o) => o.into_mut(),
+			Entry::Vacant(v) => v.insert(Arc::new(
+				UserGraph::with_key_manager(
+					&dsnp_user_id,
+					&self.environment,
+					self.key_manager_factory
+						.create(dsnp_user_id, self.shared_state_manager.clone()),
+					self.time_provider.clone(),
+				)
+				.with_chain_adapter(self.chain_adapter.clone()),
+			)),
+		};
+		Ok(Arc::make_mut(user_graph))
+	}
+
+	/// Gets an existing UserGraph to change it, which copies it first if it is shared with a
+	/// read-only snapshot
+	fn user_graph_mut(&mut self, dsnp_user_id: &DsnpUserId) -> DsnpGraphResult<&mut UserGraph> {
+		let user_graph = self
+			.user_map
+			.get_mut(dsnp_user_id)
+			.ok_or(DsnpGraphError::UserGraphNotImported(*dsnp_user_id))?;
+		self.staged_users.insert(*dsnp_user_id);
+		Ok(Arc::make_mut(user_graph))
+	}
+
+	/// returns the start time of a timed operation if instrumentation is enabled
+	fn start_timer(&self) -> Option<Instant> {
+		self.instrumentation.as_ref().map(|_| Instant::now())
+	}
+
+	/// records the duration of a timed operation started using `start_timer`
+	fn record_timing(
+		&self,
+		user_id: DsnpUserId,
+		operation: GraphOperation,
+		start: Option<Instant>,
+	) {
+		self.record_duration(user_id, operation, start.map(|start| start.elapsed()));
+	}
+
+	/// records the duration of an operation measured while instrumentation is enabled
+	fn record_duration(
+		&self,
+		user_id: DsnpUserId,
+		operation: GraphOperation,
+		duration: Option<Duration>,
+	) {
+		if let (Some(instrumentation), Some(duration)) = (&self.instrumentation, duration) {
+			instrumentation
+				.lock()
+				.unwrap_or_else(|e| e.into_inner())
+				.record(user_id, operation, duration);
+		}
+	}
+
+	/// main data importing logic
+	#[log_result_err(Level::Error)]
+	fn do_import_users_data(
+		&mut self,
+		payloads: &[ImportBundleRef],
+	) -> DsnpGraphResult<ImportReport> {
+		for bundle in payloads {
+			match self.import_options.max_dsnp_user_id {
+				Some(max_issued_id) => bundle.validate_with_max_dsnp_user_id(max_issued_id)?,
+				None => bundle.validate()?,
+			}
+		}
+		let mut report = ImportReport::default();
+		for repairs in self.import_bundles(payloads)? {
+			report.repairs.extend(repairs);
+		}
+		if self.import_options.strict_prid_validation {
+			self.verify_imported_prids(payloads)?;
+		}
+		if !report.is_clean() {
+			log::warn!(
+				"Repaired {} connections which were imported on more than one page",
+				report.repairs.len()
+			);
+		}
+		Ok(report)
+	}
+
+	/// imports the validated bundles one after another and returns the repairs of each bundle
+	#[cfg(not(feature = "parallel-import"))]
+	fn import_bundles(
+		&mut self,
+		payloads: &[ImportBundleRef],
+	) -> DsnpGraphResult<Vec<Vec<ConnectionRepair>>> {
+		let mut repairs = vec![];
+		for bundle in payloads {
+			let start = self.start_timer();
+			self.get_or_create_user_graph(bundle.dsnp_user_id)?;
+			let user_graph = self
+				.user_map
+				.get_mut(&bundle.dsnp_user_id)
+				.ok_or(DsnpGraphError::UserGraphNotImported(bundle.dsnp_user_id))?;
+			let result = Self::import_user_bundle(
+				Arc::make_mut(user_graph),
+				bundle,
+				&self.environment,
+				&self.shared_state_manager,
+				&self.page_cache,
+				&self.import_options,
+			);
+			self.record_timing(bundle.dsnp_user_id, GraphOperation::Import, start);
+			repairs.push(result?);
+		}
+		Ok(repairs)
+	}
+
+	/// imports the validated bundles of different users in parallel and returns the repairs of
+	/// each bundle in the order of the payloads
+	///
+	/// Bundles of the same user are still imported one after another in their original order, and
+	/// the first failed bundle in the order of the payloads is returned as the error. Since the
+	/// import is transactional the state is rolled back on failure the same as a serial import.
+	#[cfg(feature = "parallel-import")]
+	fn import_bundles(
+		&mut self,
+		payloads: &[ImportBundleRef],
+	) -> DsnpGraphResult<Vec<Vec<ConnectionRepair>>> {
+		let mut bundles_by_user: HashMap<DsnpUserId, Vec<(usize, &ImportBundleRef)>> =
+			HashMap::new();
+		for (index, bundle) in payloads.iter().enumerate() {
+			self.get_or_create_user_graph(bundle.dsnp_user_id)?;
+			bundles_by_user.entry(bundle.dsnp_user_id).or_default().push((index, bundle));
+		}
+
+		let timed = self.instrumentation.is_some();
+		let (environment, shared_state_manager, page_cache, import_options) =
+			(&self.environment, &self.shared_state_manager, &self.page_cache, &self.import_options);
+		let mut user_graphs: Vec<_> = self
+			.user_map
+			.iter_mut()
+			.filter_map(|(dsnp_user_id, user_graph)| {
+				bundles_by_user
+					.remove(dsnp_user_id)
+					.map(|bundles| (Arc::make_mut(user_graph), bundles))
+			})
+			.collect();
+		let mut results: Vec<_> = user_graphs
+			.par_iter_mut()
+			.flat_map_iter(|(user_graph, bundles)| {
+				let mut results = vec![];
+				for (index, bundle) in bundles.iter() {
+					let start = timed.then(Instant::now);
+					let result = Self::import_user_bundle(
+						user_graph,
+						bundle,
+						environment,
+						shared_state_manager,
+						page_cache,
+						import_options,
+					);
+					let duration = start.map(|start| start.elapsed());
+					let failed = result.is_err();
+					results.push((*index, bundle.dsnp_user_id, duration, result));
+					if failed {
+						break
+					}
+				}
+				results
+			})
+			.collect();
+		results.sort_by_key(|(index, ..)| *index);
+
+		let mut repairs = vec![];
+		for (_, dsnp_user_id, duration, result) in results {
+			self.record_duration(dsnp_user_id, GraphOperation::Import, duration);
+			repairs.push(result?);
+		}
+		Ok(repairs)
+	}
+
+	/// verifies the prids of imported private friendship pages after all the bundles are imported,
+	/// so that public keys of connections imported in later bundles are used as well
+	fn verify_imported_prids(&self, payloads: &[ImportBundleRef]) -> DsnpGraphResult<()> {
+		let private_friendship_schema_id = self
+			.environment
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Friendship(PrivacyType::Private));
+		let imported: BTreeSet<_> = payloads
+			.iter()
+			.filter(|bundle| {
+				Some(bundle.schema_id) == private_friendship_schema_id &&
+					!bundle.pages.is_empty() &&
+					!bundle.key_pairs.is_empty()
+			})
+			.map(|bundle| (bundle.dsnp_user_id, bundle.schema_id))
+			.collect();
+		for (dsnp_user_id, schema_id) in imported {
+			self.user_map
+				.get(&dsnp_user_id)
+				.ok_or(DsnpGraphError::UserGraphNotImported(dsnp_user_id))?
+				.graph(&schema_id)
+				.ok_or_else(|| self.environment.invalid_schema_id(schema_id))?
+				.verify_prid_alignment()?;
+		}
+		Ok(())
+	}
+
+	/// imports the data of a single validated bundle into the graph of its user and returns the
+	/// repairs of its pages
+	fn import_user_bundle(
+		user_graph: &mut UserGraph,
+		bundle: &ImportBundleRef,
+		environment: &Environment,
+		shared_state_manager: &RwLock<SharedStateManager>,
+		page_cache: &Mutex<DecryptedPageCache>,
+		import_options: &ImportOptions,
+	) -> DsnpGraphResult<Vec<ConnectionRepair>> {
+		let ImportBundleRef { schema_id, pages, dsnp_keys, dsnp_user_id, key_pairs, chain_head } =
+			bundle;
+		let connection_type_option =
+			environment.get_config().get_connection_type_from_schema_id(*schema_id);
+
+		match dsnp_keys {
+			Some(dsnp_keys) => {
+				let mut shared_state_manager = shared_state_manager.write().map_err(|_| {
+					DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string())
+				})?;
+				shared_state_manager.import_dsnp_keys(&dsnp_keys)?;
+				shared_state_manager.set_keys_chain_head(*dsnp_user_id, chain_head.clone());
+			},
+			None => (),
+		};
+
+		// key managers may also resolve key pairs that are kept outside of the imported bundles
+		let include_secret_keys = {
+			let mut user_key_manager = user_graph
+				.user_key_manager
+				.write()
+				.map_err(|_| DsnpGraphError::FailedtoWriteLock(USER_KEY_MANAGER.to_string()))?;
+
+			user_key_manager.import_key_pairs(key_pairs.clone())?;
+			!key_pairs.is_empty() || !user_key_manager.get_all_resolved_keys().is_empty()
+		};
+
+		if pages.is_empty() {
+			// case where only keys are imported
+			return Ok(vec![])
+		}
+
+		user_graph.set_chain_head(*schema_id, chain_head.clone());
+		let dsnp_config = user_graph
+			.get_dsnp_config(*schema_id)
+			.ok_or_else(|| environment.invalid_schema_id(*schema_id))?;
+
+		let graph = user_graph
+			.graph_mut(&schema_id)
+			.ok_or_else(|| environment.invalid_schema_id(*schema_id))?;
+		if import_options.verify_content_hashes {
+			graph.verify_content_hashes(&dsnp_config, pages)?;
+		}
+		let merge_pages = import_options.merge_pages;
+		if !merge_pages {
+			graph.clear();
+		}
+
+		let connection_type =
+			connection_type_option.ok_or_else(|| environment.invalid_schema_id(*schema_id))?;
+
+		let mut repairs: Vec<ConnectionRepair> = vec![];
+		match connection_type.privacy_type() {
+			PrivacyType::Public => {
+				repairs = graph.import_public_borrowed(connection_type, pages, merge_pages)?;
+				if import_options.dedupe_within_pages {
+					repairs.extend(graph.remove_duplicates_within_pages());
+				}
+				user_graph.sync_updates(*schema_id);
+			},
+			PrivacyType::Private => {
+				// private keys are provided try to import the graph, otherwise only keep the
+				// metadata of the encrypted pages
+				if include_secret_keys {
+					repairs = graph.import_private_borrowed(
+						&dsnp_config,
+						connection_type,
+						pages,
+						Some(page_cache),
+						merge_pages,
+					)?;
+					if import_options.dedupe_within_pages {
+						repairs.extend(graph.remove_duplicates_within_pages());
+					}
+					user_graph.sync_updates(*schema_id);
+				} else {
+					graph.import_private_metadata(connection_type, pages, merge_pages)?;
+				}
+
+				// since it's a private friendship import provided PRIs
+				if connection_type == ConnectionType::Friendship(PrivacyType::Private) {
+					let mut shared_state_manager = shared_state_manager.write().map_err(|_| {
+						DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string())
+					})?;
+					match merge_pages {
+						true => shared_state_manager.merge_pri(*dsnp_user_id, pages)?,
+						false => shared_state_manager.import_pri(*dsnp_user_id, pages)?,
+					}
+				}
+			},
+		};
+		// keeping the repairs of each bundle in the order of page ids
+		repairs.sort_by_key(|repair| repair.repaired_page_id);
+		Ok(repairs)
+	}
+
+	/// commits the changes of a call if it succeeded or rolls back all uncommitted changes if it
+	/// failed, unless `disable_auto_commit` is set which leaves both to the caller
+	fn auto_commit<T>(
+		&mut self,
+		options: &Option<ActionOptions>,
+		result: DsnpGraphResult<T>,
+	) -> DsnpGraphResult<T> {
+		let disable_auto_commit = match options {
+			Some(ActionOptions { disable_auto_commit, .. }) => *disable_auto_commit,
+			None => false,
+		};
+		if !disable_auto_commit {
+			match result {
+				DsnpGraphResult::Ok(_) => self.commit(),
+				DsnpGraphResult::Err(_) => self.rollback(),
+			}
+		}
+		result
+	}
+
+	/// main updating logic
+	#[log_result_err(Level::Error)]
+	fn do_apply_actions(
+		&mut self,
+		actions: &[Action],
+		options: &Option<ActionOptions>,
+		on_progress: &mut dyn FnMut(f64),
+	) -> DsnpGraphResult<()> {
+		let (
+			ignore_existing_connections,
+			ignore_missing_connections,
+			disable_auto_commit,
+			collect_errors,
+			require_counterpart_keys,
+			max_batch_size,
+			timestamp_policy,
+		) = match options {
+			Some(options) => (
+				options.ignore_existing_connections,
+				options.ignore_missing_connections,
+				options.disable_auto_commit,
+				options.collect_errors,
+				options.require_counterpart_keys,
+				options.max_batch_size,
+				options.timestamp_policy,
+			),
+			None => (false, false, false, false, false, None, TimestampPolicy::default()),
+		};
+
+		if let Some(max_batch_size) = max_batch_size {
+			if actions.len() > max_batch_size.get() as usize {
+				return Err(DsnpGraphError::ActionBatchTooLarge(
+					actions.len(),
+					max_batch_size.get(),
+				));
+			}
+		}
+
+		// under `CallerProvided` the since value of the action is used, and connections without
+		// one get the time of the export
+		let since = match timestamp_policy {
+			TimestampPolicy::CallerProvided => None,
+			TimestampPolicy::Now => Some(self.time_provider.time_in_ksecs()),
+			TimestampPolicy::Zero => Some(0),
+		};
+
+		// without auto commit nothing reverts the chunks applied before a failing one, so all
+		// actions are validated before applying any of them
+		let validate_upfront = disable_auto_commit && !collect_errors;
+		if validate_upfront {
+			for action in actions {
+				self.validate_action(action, require_counterpart_keys)?;
+			}
+		}
+
+		let mut failed_actions = vec![];
+		for (chunk_index, chunk) in actions.chunks(ACTION_CHUNK_SIZE).enumerate() {
+			let offset = chunk_index * ACTION_CHUNK_SIZE;
+			if collect_errors {
+				// attempt all actions and report every failing one
+				for (index, action) in chunk.iter().enumerate() {
+					if let Err(e) =
+						self.validate_action(action, require_counterpart_keys).and_then(|_| {
+							self.do_timed_apply_action(
+								action,
+								ignore_existing_connections,
+								ignore_missing_connections,
+								since,
+							)
+						}) {
+						failed_actions.push((offset + index, e));
+					}
+				}
+			} else {
+				// pre validate all actions of the chunk
+				if !validate_upfront {
+					for action in chunk {
+						self.validate_action(action, require_counterpart_keys)?;
+					}
+				}
+
+				// apply actions
+				for action in chunk {
+					self.do_timed_apply_action(
+						action,
+						ignore_existing_connections,
+						ignore_missing_connections,
+						since,
+					)?;
+				}
+			}
+			on_progress((offset + chunk.len()) as f64 / actions.len() as f64);
+		}
+
+		if !failed_actions.is_empty() {
+			return Err(DsnpGraphError::ActionsFailed(failed_actions))
+		}
+		Ok(())
+	}
+
+	/// validates an action before applying it
+	fn validate_action(
+		&self,
+		action: &Action,
+		require_counterpart_keys: bool,
+	) -> DsnpGraphResult<()> {
+		action.validate()?;
+		if require_counterpart_keys {
+			self.verify_counterpart_keys(action)?;
+		}
+		Ok(())
+	}
+
+	/// verifies that the counterparts of a private friendship connect, connect mutual or migrate
+	/// action have imported public keys or provide them in the action, since their PRIds can not
+	/// be calculated otherwise. Both users of a connect mutual action are counterparts
+	fn verify_counterpart_keys(&self, action: &Action) -> DsnpGraphResult<()> {
+		let (counterparts, schema_id) = match action {
+			Action::Connect {
+				connection: Connection { dsnp_user_id, schema_id },
+				dsnp_keys: None,
+				..
+			} => (vec![*dsnp_user_id], *schema_id),
+			Action::ConnectMutual { user_a, user_b, schema_id, .. } =>
+				(vec![*user_a, *user_b], *schema_id),
+			Action::MigrateConnection { dsnp_user_id, to_schema_id, .. } =>
+				(vec![*dsnp_user_id], *to_schema_id),
+			_ => return Ok(()),
+		};
+		let connection_type =
+			self.environment.get_config().get_connection_type_from_schema_id(schema_id);
+		if connection_type != Some(ConnectionType::Friendship(PrivacyType::Private)) {
+			return Ok(())
+		}
+
+		let without_keys = self
+			.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.find_users_without_keys(counterparts);
+		match without_keys.first() {
+			None => Ok(()),
+			Some(dsnp_user_id) => Err(DsnpGraphError::NoPublicKeyFoundForUser(*dsnp_user_id)),
+		}
+	}
+
+	/// applies a single action and records its duration and resolution
+	fn do_timed_apply_action(
+		&mut self,
+		action: &Action,
+		ignore_existing_connections: bool,
+		ignore_missing_connections: bool,
+		since: Option<u64>,
+	) -> DsnpGraphResult<()> {
+		let start = self.start_timer();
+		let result = self.do_apply_action(
+			action,
+			ignore_existing_connections,
+			ignore_missing_connections,
+			since,
+		);
+		self.record_timing(action.owner_dsnp_user_id(), GraphOperation::ApplyAction, start);
+		if let Some(audit_log) = &self.audit_log {
+			audit_log.lock().unwrap_or_else(|e| e.into_inner()).record(
+				self.time_provider.now_in_secs(),
+				action,
+				&result,
+			);
+		}
+		result.map(|_| ())
+	}
+
+	/// applies a single action and returns whether it was applied or ignored, added connections
+	/// get `since` as their since value if provided by the policy, otherwise the one of the action
+	fn do_apply_action(
+		&mut self,
+		action: &Action,
+		ignore_existing_connections: bool,
+		ignore_missing_connections: bool,
+		since: Option<u64>,
+	) -> DsnpGraphResult<AuditResolution> {
+		match action {
+			Action::Connect {
+				connection: Connection { ref dsnp_user_id, ref schema_id },
+				dsnp_keys,
+				preferred_page_id,
+				since: action_since,
+				..
+			} => {
+				let owner_graph = self.get_or_create_user_graph(action.owner_dsnp_user_id())?;
+				if owner_graph.graph_has_connection(*schema_id, *dsnp_user_id, true) {
+					if ignore_existing_connections {
+						log::warn!(
+							"Ignoring add redundant connection {} -> {}",
+							redact_id(action.owner_dsnp_user_id()),
+							redact_id(*dsnp_user_id)
+						);
+						return Ok(AuditResolution::Ignored)
+					}
+
+					return Err(DsnpGraphError::ConnectionAlreadyExists(
+						action.owner_dsnp_user_id(),
+						*dsnp_user_id,
+					));
+				}
+				owner_graph.update_tracker_mut().register_update(
+					UpdateEvent::create_add(*dsnp_user_id, *schema_id),
+					ignore_existing_connections,
+				)?;
+				if let Some(page_id) = preferred_page_id {
+					owner_graph.update_tracker_mut().set_page_hint(
+						*schema_id,
+						*dsnp_user_id,
+						*page_id,
+					);
+				}
+				if let Some(since) = since.or(*action_since) {
+					owner_graph.update_tracker_mut().set_since_hint(
+						*schema_id,
+						*dsnp_user_id,
+						since,
+					);
+				}
+				if let Some(inner_keys) = dsnp_keys {
+					self.shared_state_manager
+						.write()
+						.map_err(|_| {
+							DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string())
+						})?
+						.import_dsnp_keys(inner_keys)?;
+				}
+			},
+			Action::Disconnect {
+				connection: Connection { ref dsnp_user_id, ref schema_id },
+				..
+			} => {
+				let owner_graph = self.get_or_create_user_graph(action.owner_dsnp_user_id())?;
+				if !owner_graph.graph_has_connection(*schema_id, *dsnp_user_id, true) {
+					if ignore_missing_connections {
+						log::warn!(
+							"Ignoring remove non-existent connection {} -> {}",
+							redact_id(action.owner_dsnp_user_id()),
+							redact_id(*dsnp_user_id)
+						);
+						return Ok(AuditResolution::Ignored)
+					}
+
+					return Err(DsnpGraphError::ConnectionDoesNotExist(
+						action.owner_dsnp_user_id(),
+						*dsnp_user_id,
+					));
+				}
+				owner_graph.update_tracker_mut().register_update(
+					UpdateEvent::create_remove(*dsnp_user_id, *schema_id),
+					ignore_missing_connections,
+				)?;
+			},
+			Action::AddGraphKey { new_public_key, .. } => {
+				self.shared_state_manager
+					.write()
+					.map_err(|_| {
+						DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string())
+					})?
+					.add_new_key(action.owner_dsnp_user_id(), new_public_key.clone())?;
+			},
+			Action::ConnectMutual { user_a, user_b, schema_id, since: action_since } =>
+				return self.do_apply_connect_mutual(
+					*user_a,
+					*user_b,
+					*schema_id,
+					ignore_existing_connections,
+					since.or(*action_since),
+				),
+			Action::MigrateConnection {
+				owner_dsnp_user_id,
+				from_schema_id,
+				to_schema_id,
+				dsnp_user_id,
+				since: action_since,
+			} =>
+				return self.do_apply_migrate_connection(
+					*owner_dsnp_user_id,
+					*from_schema_id,
+					*to_schema_id,
+					*dsnp_user_id,
+					ignore_existing_connections,
+					ignore_missing_connections,
+					since.or(*action_since),
+				),
+		}
+		Ok(AuditResolution::Applied)
+	}
+
+	/// registers the removal from the source graph and the addition to the target graph of a
+	/// `MigrateConnection` action, which requires the graphs to be of the same connection kind
+	/// with different privacy types
+	fn do_apply_migrate_connection(
+		&mut self,
+		owner: DsnpUserId,
+		from_schema_id: SchemaId,
+		to_schema_id: SchemaId,
+		dsnp_user_id: DsnpUserId,
+		ignore_existing_connections: bool,
+		ignore_missing_connections: bool,
+		since: Option<u64>,
+	) -> DsnpGraphResult<AuditResolution> {
+		let config = self.environment.get_config();
+		let from = config
+			.get_connection_type_from_schema_id(from_schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(from_schema_id))?;
+		let to = config
+			.get_connection_type_from_schema_id(to_schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(to_schema_id))?;
+		let same_kind = matches!(
+			(from, to),
+			(ConnectionType::Follow(_), ConnectionType::Follow(_)) |
+				(ConnectionType::Friendship(_), ConnectionType::Friendship(_))
+		);
+		if !same_kind || from.privacy_type() == to.privacy_type() {
+			return Err(DsnpGraphError::IncorrectConnectionType(format!(
+				"Can not migrate a connection from {} to {}",
+				from, to
+			)))
+		}
+
+		// check both graphs before registering any update to keep the action atomic
+		let owner_graph = self.get_or_create_user_graph(owner)?;
+		let remove = match owner_graph.graph_has_connection(from_schema_id, dsnp_user_id, true) {
+			true => true,
+			false if ignore_missing_connections => {
+				log::warn!(
+					"Ignoring migrate missing connection {} -> {}",
+					redact_id(owner),
+					redact_id(dsnp_user_id)
+				);
+				false
+			},
+			false => return Err(DsnpGraphError::ConnectionDoesNotExist(owner, dsnp_user_id)),
+		};
+		let add = match owner_graph.graph_has_connection(to_schema_id, dsnp_user_id, true) {
+			false => true,
+			true if ignore_existing_connections => {
+				log::warn!(
+					"Ignoring migrate redundant connection {} -> {}",
+					redact_id(owner),
+					redact_id(dsnp_user_id)
+				);
+				false
+			},
+			true => return Err(DsnpGraphError::ConnectionAlreadyExists(owner, dsnp_user_id)),
+		};
+
+		if !remove && !add {
+			return Ok(AuditResolution::Ignored)
+		}
+		if remove {
+			owner_graph.update_tracker_mut().register_update(
+				UpdateEvent::create_remove(dsnp_user_id, from_schema_id),
+				ignore_missing_connections,
+			)?;
+		}
+		if add {
+			owner_graph.update_tracker_mut().register_update(
+				UpdateEvent::create_add(dsnp_user_id, to_schema_id),
+				ignore_existing_connections,
+			)?;
+			if let Some(since) = since {
+				owner_graph
+					.update_tracker_mut()
+					.set_since_hint(to_schema_id, dsnp_user_id, since);
+			}
+		}
+		Ok(AuditResolution::Applied)
+	}
+
+	/// registers reciprocal friendship connections for both users of a `ConnectMutual` action.
+	/// Both users need to be imported, and for private friendships both need a resolved active key
+	/// pair since the PRIds of both sides are calculated on export
+	fn do_apply_connect_mutual(
+		&mut self,
+		user_a: DsnpUserId,
+		user_b: DsnpUserId,
+		schema_id: SchemaId,
+		ignore_existing_connections: bool,
+		since: Option<u64>,
+	) -> DsnpGraphResult<AuditResolution> {
+		let privacy_type =
+			match self.environment.get_config().get_connection_type_from_schema_id(schema_id) {
+				Some(ConnectionType::Friendship(privacy_type)) => privacy_type,
+				_ => return Err(self.environment.invalid_schema_id(schema_id)),
+			};
+
+		// check both sides before registering any update to keep the action atomic
+		let mut connections_to_add = vec![];
+		for (owner, dsnp_user_id) in [(user_a, user_b), (user_b, user_a)] {
+			let owner_graph =
+				self.user_map.get(&owner).ok_or(DsnpGraphError::UserGraphNotImported(owner))?;
+			if privacy_type == PrivacyType::Private &&
+				owner_graph
+					.user_key_manager
+					.read()
+					.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+					.get_resolved_active_key(owner)
+					.is_none()
+			{
+				return Err(DsnpGraphError::NoResolvedActiveKeyFound)
+			}
+			if owner_graph.graph_has_connection(schema_id, dsnp_user_id, true) {
+				if ignore_existing_connections {
+					log::warn!(
+						"Ignoring add redundant connection {} -> {}",
+						redact_id(owner),
+						redact_id(dsnp_user_id)
+					);
+					continue
+				}
+				return Err(DsnpGraphError::ConnectionAlreadyExists(owner, dsnp_user_id))
+			}
+			connections_to_add.push((owner, dsnp_user_id));
+		}
+
+		if connections_to_add.is_empty() {
+			return Ok(AuditResolution::Ignored)
+		}
+		for (owner, dsnp_user_id) in connections_to_add {
+			let update_tracker = self.user_graph_mut(&owner)?.update_tracker_mut();
+			update_tracker.register_update(
+				UpdateEvent::create_add(dsnp_user_id, schema_id),
+				ignore_existing_connections,
+			)?;
+			if let Some(since) = since {
+				update_tracker.set_since_hint(schema_id, dsnp_user_id, since);
+			}
+		}
+		Ok(AuditResolution::Applied)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		api::api_types::{
+			ChainHead, EncryptionMode, LintRule, NonceMode, PageDataRef, PageHash, PageMetadata,
+			ResolvedKeyPair, UpdateDetails,
+		},
+		dsnp::{
+			chain_adapter::ChainAdapter,
+			compression::CompressionFormat,
+			dsnp_configs::{
+				DsnpVersionConfig, KeyPairType, PublicKeyType, SecretKeyOperations, SecretKeyType,
+				SharedSecretKeyOperations,
+			},
+			dsnp_types::{DsnpInnerGraph, DsnpPrid, PrivateGraphChunk},
+			reader_writer::DsnpReader,
+		},
+		frequency::Frequency,
+		graph::key_manager::{
+			SharedUserKeyManager, UserKeyManager, UserKeyManagerFactory, UserKeyProvider,
+		},
+		util::{
+			builders::{ImportBundleBuilder, KeyDataBuilder},
+			time::TimeProvider,
+		},
+	};
+	use dryoc::{constants::CRYPTO_BOX_BEFORENMBYTES, keypair::PublicKey};
+	use dsnp_graph_config::{registry::EnvironmentRegistry, DsnpVersion};
+	use memory_stats::memory_stats;
+	use ntest::*;
+	use std::{
+		collections::{HashMap, HashSet},
+		num::NonZeroU32,
+		sync::atomic::{AtomicU64, Ordering},
+	};
+	use zeroize::Zeroizing;
+
+	#[test]
+	fn graph_contains_false() {
+		let state = GraphState::new(Environment::Mainnet);
+		assert!(!state.contains_user_graph(&0));
+	}
+
+	#[test]
+	fn graph_contains_true() {
+		let mut state = GraphState::new(Environment::Mainnet);
+		let _ = state.get_or_create_user_graph(0);
+		assert!(state.contains_user_graph(&0));
+	}
+
+	#[test]
+	fn graph_len() {
+		let mut state = GraphState::new(Environment::Mainnet);
+		let _ = state.get_or_create_user_graph(0);
+		assert_eq!(state.len(), 1);
+		let _ = state.get_or_create_user_graph(1);
+		assert_eq!(state.len(), 2);
+	}
+
+	#[test]
+	fn add_user_success() {
+		let mut state = GraphState::new(Environment::Mainnet);
+		let res = state.get_or_create_user_graph(0);
+		assert!(res.is_ok());
+	}
+
+	#[test]
+	fn remove_user_success() {
+		let mut state = GraphState::new(Environment::Mainnet);
+		let _ = state.get_or_create_user_graph(0);
+		let _ = state.get_or_create_user_graph(1);
+		state.remove_user_graph(&0);
+		assert_eq!(state.len(), 1);
+		assert!(!state.contains_user_graph(&0));
+		assert!(state.contains_user_graph(&1));
+	}
+
+	#[test]
+	fn remove_nonexistent_user_noop() {
+		let mut state = GraphState::new(Environment::Mainnet);
+		let _ = state.get_or_create_user_graph(0);
+		let _ = state.get_or_create_user_graph(1);
+		state.remove_user_graph(&99);
+		assert_eq!(state.user_map.len(), 2);
+	}
+
+	#[test]
+	fn import_user_data_should_import_keys_and_data_for_public_follow_graph() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let connections = vec![(2, 0), (3, 0), (4, 0), (5, 0)];
+		let input = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair.clone()])
+			.with_page(1, &connections, &vec![], 1000)
+			.build();
+
+		// act
+		let res = state.import_users_data(&vec![input]);
+
+		// assert
+		assert!(res.is_ok());
+
+		let public_manager = state.shared_state_manager.read().unwrap();
+		let keys = public_manager.get_imported_keys(dsnp_user_id);
+		assert_eq!(keys.len(), 1);
+
+		let res = state.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false);
+		assert!(res.is_ok());
+		let res_set: HashSet<_> = res.unwrap().iter().copied().collect();
+		let mapped: HashSet<_> = connections
+			.into_iter()
+			.map(|(c, s)| DsnpGraphEdge { user_id: c, since: s })
+			.collect();
+		assert_eq!(res_set, mapped);
+	}
+
+	#[test]
+	fn import_users_data_borrowed_should_import_borrowed_pages() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let connections = vec![(2, 0), (3, 0), (4, 0), (5, 0)];
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &connections, &vec![], 1000)
+			.build();
+		let content: Vec<u8> = bundle.pages[0].content.clone();
+		let borrowed = ImportBundleRef {
+			dsnp_user_id,
+			schema_id,
+			key_pairs: vec![],
+			dsnp_keys: None,
+			pages: vec![PageDataRef { page_id: 1, content: &content, content_hash: 1000 }],
+			chain_head: None,
+		};
+		let mut invalid = borrowed.clone();
+		invalid.pages[0].content_hash = 0;
+
+		// act
+		let res = state.import_users_data_borrowed(&[borrowed]);
+
+		// assert
+		assert!(res.is_ok());
+		let res = state.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false);
+		let res_set: HashSet<_> = res.expect("should work").iter().copied().collect();
+		let mapped: HashSet<_> = connections
+			.into_iter()
+			.map(|(c, s)| DsnpGraphEdge { user_id: c, since: s })
+			.collect();
+		assert_eq!(res_set, mapped);
+		assert!(matches!(
+			state.import_users_data_borrowed(&[invalid]),
+			Err(DsnpGraphError::InvalidInput(_))
+		));
+	}
+
+	#[test]
+	fn import_user_data_should_import_keys_and_data_for_private_follow_graph() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 1 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let connections = vec![(2, 0), (3, 0), (4, 0), (5, 0)];
+		let input = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &connections, &vec![], 100)
+			.build();
+
+		// act
+		let res = state.import_users_data(&vec![input]);
+
+		// assert
+		assert!(res.is_ok());
+
+		let public_manager = state.shared_state_manager.read().unwrap();
+		let keys = public_manager.get_imported_keys(dsnp_user_id);
+		assert_eq!(keys.len(), 1);
+
+		let res = state.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false);
+		assert!(res.is_ok());
+		let res_set: HashSet<_> = res.unwrap().iter().copied().collect();
+		let mapped: HashSet<_> = connections
+			.into_iter()
+			.map(|(c, s)| DsnpGraphEdge { user_id: c, since: s })
+			.collect();
+		assert_eq!(res_set, mapped);
+	}
+
+	#[test]
+	fn import_user_data_should_keep_decrypted_pages_until_commit_or_rollback() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 1 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let input = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair.clone()])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.with_page(2, &vec![(4, 0)], &vec![], 200)
+			.build();
+		let other_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(StackKeyPair::gen()), key_id: 2 };
+		let wrong_key_input = ImportBundleBuilder::new(env, dsnp_user_id + 1, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(other_key)
+			.with_page(1, &vec![(2, 0)], &vec![], 100)
+			.build();
+		let cached = |state: &GraphState| state.page_cache.lock().unwrap().len();
+
+		// act
+		let imported = state.do_import_users_data(&[ImportBundleRef::from(&input)]);
+		let cached_before_commit = cached(&state);
+		state.commit();
+		let cached_after_commit = cached(&state);
+		let reimported = state.import_users_data(&vec![input.clone()]);
+		let failed = state.import_users_data(&vec![wrong_key_input]);
+
+		// assert
+		assert!(imported.is_ok());
+		assert_eq!(cached_before_commit, 2);
+		assert_eq!(cached_after_commit, 0);
+		assert!(reimported.is_ok());
+		assert_eq!(
+			state
+				.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false)
+				.expect("should get connections")
+				.len(),
+			3
+		);
+		assert!(failed.is_err());
+		assert_eq!(cached(&state), 0);
+
+		state.set_decrypted_page_cache_capacity(1);
+		state
+			.do_import_users_data(&[ImportBundleRef::from(&input)])
+			.expect("should import");
+		assert_eq!(cached(&state), 1);
+		state.remove_user_graph(&dsnp_user_id);
+		assert_eq!(cached(&state), 0);
+	}
+
+	#[test]
+	fn import_user_data_without_key_pairs_should_only_import_private_page_metadata() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 1 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let builder = || {
+			ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+				.with_encryption_key(resolved_key.clone())
+				.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+				.with_page(3, &vec![(4, 0)], &vec![], 300)
+		};
+		let metadata_only = builder().build();
+		let with_keys = builder().with_key_pairs(&vec![keypair]).build();
+
+		// act
+		state.import_users_data(&vec![metadata_only.clone()]).expect("should import");
+		let metadata_stats = state.get_user_graph_stats(&dsnp_user_id, &schema_id);
+		state.import_users_data(&vec![with_keys]).expect("should import");
+		let decrypted_stats = state.get_user_graph_stats(&dsnp_user_id, &schema_id);
+
+		// assert
+		let metadata_stats = metadata_stats.expect("should work");
+		assert!(metadata_stats.is_metadata_only());
+		assert_eq!(metadata_stats.connections, 0);
+		assert_eq!(
+			metadata_stats.encrypted_pages,
+			metadata_only
+				.pages
+				.iter()
+				.map(|p| PageMetadata::from(PageDataRef::from(p)))
+				.collect::<Vec<_>>()
+		);
+		assert_eq!(
+			metadata_stats.page_hashes.into_iter().collect::<Vec<_>>(),
+			vec![(1, 100), (3, 300)]
+		);
+
+		let decrypted_stats = decrypted_stats.expect("should work");
+		assert!(!decrypted_stats.is_metadata_only());
+		assert_eq!(decrypted_stats.decrypted_pages, 2);
+		assert_eq!(decrypted_stats.connections, 3);
+		assert!(decrypted_stats.encrypted_pages.is_empty());
+		assert_eq!(
+			decrypted_stats.page_hashes.into_iter().collect::<Vec<_>>(),
+			vec![(1, 100), (3, 300)]
+		);
+	}
+
+	#[test]
+	fn is_user_graph_writable_should_require_secret_keys_for_private_graphs() {
+		// arrange
+		let env = Environment::Mainnet;
+		let private_schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let public_schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 1 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let (read_only_user, writable_user) = (123, 124);
+		let builder = |dsnp_user_id| {
+			ImportBundleBuilder::new(env.clone(), dsnp_user_id, private_schema_id)
+				.with_encryption_key(resolved_key.clone())
+				.with_page(1, &vec![(2, 0)], &vec![], 100)
+		};
+		let read_only = builder(read_only_user).build();
+		let writable = builder(writable_user).with_key_pairs(&vec![keypair]).build();
+		state.import_users_data(&vec![read_only, writable]).expect("should import");
+
+		// act
+		let read_only_private = state.is_user_graph_writable(&read_only_user, &private_schema_id);
+		let read_only_public = state.is_user_graph_writable(&read_only_user, &public_schema_id);
+		let writable_private = state.is_user_graph_writable(&writable_user, &private_schema_id);
+		let not_imported = state.is_user_graph_writable(&1000, &public_schema_id);
+		let unknown_schema = state.is_user_graph_writable(&writable_user, &1000);
+
+		// assert
+		assert!(!read_only_private);
+		assert!(read_only_public);
+		assert!(writable_private);
+		assert!(!not_imported);
+		assert!(!unknown_schema);
+	}
+
+	#[test]
+	fn get_page_key_usage_should_return_used_key_for_each_imported_page() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 0 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let input = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(2, &vec![(4, 0), (5, 0)], &vec![], 100)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![input]).expect("should import");
+
+		// act
+		let usage = state.get_page_key_usage(&dsnp_user_id, &schema_id);
+
+		// assert
+		assert_eq!(usage.expect("should work"), vec![(1, 0), (2, 0)]);
+		assert!(matches!(
+			state.get_page_key_usage(&999, &schema_id),
+			Err(DsnpGraphError::UserGraphNotImported(999))
+		));
+	}
+
+	#[test]
+	fn get_page_key_usage_should_report_new_key_after_rotation() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		state.set_decrypted_page_cache_capacity(10);
+		let old_key_raw = StackKeyPair::gen();
+		let new_key_raw = StackKeyPair::gen();
+		let to_graph_key_pair = |key_pair: &StackKeyPair| GraphKeyPair {
+			secret_key: key_pair.secret_key.to_vec(),
+			public_key: key_pair.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let original = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![to_graph_key_pair(&old_key_raw), to_graph_key_pair(&new_key_raw)])
+			.with_encryption_key(ResolvedKeyPair {
+				key_pair: KeyPairType::Version1_0(old_key_raw),
+				key_id: 0,
+			})
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![original.clone()]).expect("should import");
+		let before = state.get_page_key_usage(&dsnp_user_id, &schema_id).expect("should work");
+
+		// act
+		let rotated = state.force_recalculate_graphs(&dsnp_user_id).expect("should recalculate");
+		state
+			.import_users_data(&vec![ImportBundleBuilder::build_from(&original, &rotated)])
+			.expect("should import");
+		let after = state.get_page_key_usage(&dsnp_user_id, &schema_id).expect("should work");
+
+		// assert
+		assert_eq!(before, vec![(1, 0)]);
+		assert_eq!(after, vec![(1, 1)]);
+	}
+
+	#[test]
+	fn get_page_key_usage_should_fail_fast_for_graphs_without_encryption() {
+		// arrange
+		let env = Environment::Mainnet;
+		let public_schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let state = GraphState::new(env);
+
+		// act
+		let result = state.get_page_key_usage(&999, &public_schema_id);
+
+		// assert
+		assert!(matches!(
+			result,
+			Err(DsnpGraphError::UnsupportedOperation(
+				_,
+				Capability::Encryption,
+				ConnectionType::Follow(PrivacyType::Public)
+			))
+		));
+	}
+
+	#[test]
+	fn prune_connections_should_stage_removal_of_old_connections() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let now = state.time_provider().time_in_ksecs();
+		let dsnp_user_id = 123;
+		let input = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, now), (4, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![input]).expect("should import");
+
+		// act
+		let pruned = state.prune_connections(&dsnp_user_id, &schema_id, 30, &None);
+		let pruned_again = state.prune_connections(&dsnp_user_id, &schema_id, 30, &None);
+
+		// assert
+		assert_eq!(pruned.expect("should work"), vec![2, 4]);
+		assert_eq!(pruned_again.expect("should work"), Vec::<DsnpUserId>::new());
+		let connections: Vec<_> = state
+			.get_connections_for_user_graph(&dsnp_user_id, &schema_id, true)
+			.expect("should work")
+			.iter()
+			.map(|c| c.user_id)
+			.collect();
+		assert_eq!(connections, vec![3]);
+		assert!(matches!(
+			state.prune_connections(&999, &schema_id, 30, &None),
+			Err(DsnpGraphError::UserGraphNotImported(999))
+		));
+	}
+
+	#[test]
+	fn prune_connections_with_disabled_auto_commit_should_keep_uncommitted_changes() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let now = state.time_provider().time_in_ksecs();
+		let dsnp_user_id = 123;
+		let input = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, now)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![input]).expect("should import");
+		let options = Some(ActionOptions { disable_auto_commit: true, ..Default::default() });
+		let connect = Action::Connect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 5, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		};
+		state.apply_actions(&vec![connect], &options).expect("should apply");
+
+		// act
+		let failed = state.prune_connections(&dsnp_user_id, &9_999, 30, &options);
+		let pending_after_failure = state.pending_adds(&dsnp_user_id, &schema_id);
+		let pruned = state.prune_connections(&dsnp_user_id, &schema_id, 30, &options);
+		state.rollback();
+
+		// assert
+		assert!(failed.is_err());
+		assert_eq!(pending_after_failure.expect("should work"), vec![5]);
+		assert_eq!(pruned.expect("should work"), vec![2]);
+		assert_eq!(
+			state.pending_removes(&dsnp_user_id, &schema_id).expect("should work"),
+			Vec::<DsnpUserId>::new()
+		);
+		assert_eq!(
+			state.pending_adds(&dsnp_user_id, &schema_id).expect("should work"),
+			Vec::<DsnpUserId>::new()
+		);
+	}
+
+	#[test]
+	fn import_edge_list_should_stage_connections_with_their_since_values() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let input = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 10)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![input]).expect("should import");
+		let capacity = state.remaining_capacity(&dsnp_user_id, &schema_id).expect("should work");
+		let edges = vec![(2, 20), (3, 30), (4, 40), (3, 50)];
+
+		// act
+		let imported = state.import_edge_list(&dsnp_user_id, &schema_id, edges.into_iter(), &None);
+		let over_capacity = state.import_edge_list(
+			&dsnp_user_id,
+			&schema_id,
+			(1_000..1_000 + capacity.remaining_connections as DsnpUserId).map(|id| (id, 0)),
+			&None,
+		);
+
+		// assert
+		assert_eq!(imported.expect("should work"), 2);
+		assert!(matches!(over_capacity, Err(DsnpGraphError::GraphCapacityExceeded(_, _, 2))));
+		assert_eq!(state.pending_adds(&dsnp_user_id, &schema_id).expect("should work"), vec![3, 4]);
+		let updates = state.export_user_graph_updates(&dsnp_user_id).expect("should export");
+		let Update::PersistPage { payload, .. } = &updates[0] else {
+			panic!("should persist page")
+		};
+		let mut connections = Frequency::read_public_graph(payload).expect("should read");
+		connections.sort_by_key(|c| c.user_id);
+		assert_eq!(
+			connections,
+			vec![
+				DsnpGraphEdge { user_id: 2, since: 10 },
+				DsnpGraphEdge { user_id: 3, since: 30 },
+				DsnpGraphEdge { user_id: 4, since: 40 },
+			]
+		);
+	}
+
+	#[test]
+	fn import_edge_list_with_disabled_auto_commit_should_keep_uncommitted_changes() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env);
+		let dsnp_user_id = 123;
+		let options = Some(ActionOptions { disable_auto_commit: true, ..Default::default() });
+		let connect = Action::Connect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 2, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		};
+		state.apply_actions(&vec![connect], &options).expect("should apply");
+
+		// act
+		let failed =
+			state.import_edge_list(&dsnp_user_id, &9_999, vec![(3, 0)].into_iter(), &options);
+		let pending_after_failure = state.pending_adds(&dsnp_user_id, &schema_id);
+		let imported =
+			state.import_edge_list(&dsnp_user_id, &schema_id, vec![(3, 0)].into_iter(), &options);
+		let pending_after_import = state.pending_adds(&dsnp_user_id, &schema_id);
+		state.rollback();
+
+		// assert
+		assert!(failed.is_err());
+		assert_eq!(pending_after_failure.expect("should work"), vec![2]);
+		assert_eq!(imported.expect("should work"), 1);
+		assert_eq!(pending_after_import.expect("should work"), vec![2, 3]);
+		assert!(!state.contains_user_graph(&dsnp_user_id));
+	}
+
+	#[test]
+	fn apply_actions_should_set_since_of_added_connections_using_timestamp_policy() {
+		// arrange
+		#[derive(Debug)]
+		struct AdjustableTimeProvider(AtomicU64);
+
+		impl TimeProvider for AdjustableTimeProvider {
+			fn now_in_secs(&self) -> u64 {
+				self.0.load(Ordering::SeqCst)
+			}
+		}
+
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let time_provider = Arc::new(AdjustableTimeProvider(AtomicU64::new(5_000)));
+		let mut state = GraphState::with_time_provider(env.clone(), time_provider.clone());
+		let dsnp_user_id = 123;
+		let connect = |id, since| Action::Connect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: id, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since,
+		};
+		let options =
+			|timestamp_policy| Some(ActionOptions { timestamp_policy, ..ActionOptions::default() });
+
+		// act
+		state
+			.apply_actions(&vec![connect(2, Some(11))], &options(TimestampPolicy::Now))
+			.expect("should apply");
+		state
+			.apply_actions(&vec![connect(3, None)], &options(TimestampPolicy::Zero))
+			.expect("should apply");
+		state
+			.apply_actions(
+				&vec![connect(4, None), connect(6, Some(11))],
+				&options(TimestampPolicy::CallerProvided),
+			)
+			.expect("should apply");
+		state
+			.import_edge_list(&dsnp_user_id, &schema_id, vec![(5, 7)].into_iter(), &None)
+			.expect("should work");
+		time_provider.0.store(9_000, Ordering::SeqCst);
+		let updates = state.export_user_graph_updates(&dsnp_user_id).expect("should export");
+
+		// assert
+		let Update::PersistPage { payload, .. } = &updates[0] else {
+			panic!("should persist page")
+		};
+		let mut connections = Frequency::read_public_graph(payload).expect("should read");
+		connections.sort_by_key(|c| c.user_id);
+		assert_eq!(
+			connections,
+			vec![
+				DsnpGraphEdge { user_id: 2, since: 5 },
+				DsnpGraphEdge { user_id: 3, since: 0 },
+				DsnpGraphEdge { user_id: 4, since: 9 },
+				DsnpGraphEdge { user_id: 5, since: 7 },
+				DsnpGraphEdge { user_id: 6, since: 11 },
+			]
+		);
+	}
+
+	#[test]
+	fn get_connection_placements_should_report_pages_of_added_connections() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let input = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![input]).expect("should import");
+		let actions = vec![
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id,
+				connection: Connection { dsnp_user_id: 4, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: Some(7),
+				since: None,
+			},
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id,
+				connection: Connection { dsnp_user_id: 5, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+		];
+		state.apply_actions(&actions, &None).expect("should apply");
+
+		// act
+		let placements = state.get_connection_placements(&dsnp_user_id);
+
+		// assert
+		assert_eq!(
+			placements.expect("should work"),
+			vec![
+				ConnectionPlacement {
+					dsnp_user_id: 4,
+					schema_id,
+					page_id: 7,
+					preferred_page_id: Some(7)
+				},
+				// pages already being updated are filled first by the normal packing
+				ConnectionPlacement {
+					dsnp_user_id: 5,
+					schema_id,
+					page_id: 7,
+					preferred_page_id: None
+				},
+			]
+		);
+		assert!(matches!(
+			state.get_connection_placements(&999),
+			Err(DsnpGraphError::UserGraphNotImported(999))
+		));
+	}
+
+	#[test]
+	fn export_graph_removal_should_delete_all_imported_pages_of_user() {
+		// arrange
+		let env = Environment::Mainnet;
+		let follow_schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let private_schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 0 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let follows = ImportBundleBuilder::new(env.clone(), dsnp_user_id, follow_schema_id)
+			.with_page(2, &vec![(4, 0)], &vec![], 200)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		let private_follows = ImportBundleBuilder::new(env, dsnp_user_id, private_schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(0, &vec![(5, 0)], &vec![], 300)
+			.build();
+		state.import_users_data(&vec![follows, private_follows]).expect("should import");
+		let action = Action::Connect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 6, schema_id: follow_schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		};
+		state.apply_actions(&vec![action], &None).expect("should apply");
+
+		// act
+		let updates = state.export_graph_removal(&dsnp_user_id);
+
+		// assert
+		let mut expected = vec![
+			Update::DeletePage {
+				owner_dsnp_user_id: dsnp_user_id,
+				schema_id: follow_schema_id,
+				page_id: 1,
+				prev_hash: 100,
+				chain_head: None,
+			},
+			Update::DeletePage {
+				owner_dsnp_user_id: dsnp_user_id,
+				schema_id: follow_schema_id,
+				page_id: 2,
+				prev_hash: 200,
+				chain_head: None,
+			},
+			Update::DeletePage {
+				owner_dsnp_user_id: dsnp_user_id,
+				schema_id: private_schema_id,
+				page_id: 0,
+				prev_hash: 300,
+				chain_head: None,
+			},
+		];
+		if private_schema_id < follow_schema_id {
+			expected.rotate_right(1);
+		}
+		assert_eq!(updates.expect("should work"), expected);
+		assert!(matches!(
+			state.export_graph_removal(&999),
+			Err(DsnpGraphError::UserGraphNotImported(999))
+		));
+	}
+
+	#[test]
+	fn verify_roundtrip_should_report_consistent_graph_with_pending_updates() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 0 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.with_page(2, &vec![(4, 0)], &vec![], 200)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+		let actions = vec![
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id,
+				connection: Connection { dsnp_user_id: 5, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+			Action::Disconnect {
+				owner_dsnp_user_id: dsnp_user_id,
+				connection: Connection { dsnp_user_id: 4, schema_id },
+			},
+		];
+		state.apply_actions(&actions, &None).expect("should apply");
+
+		// act
+		let report = state.verify_roundtrip(&dsnp_user_id, &schema_id);
+
+		// assert
+		let report = report.expect("should work");
+		assert!(report.is_consistent());
+		assert_eq!(report.dsnp_user_id, dsnp_user_id);
+		assert_eq!(report.schema_id, schema_id);
+		assert_eq!(report.expected_connections, 3);
+		// the added connection reuses the page that had a connection removed
+		assert_eq!(report.exported_pages, 2);
+		assert!(matches!(
+			state.verify_roundtrip(&999, &schema_id),
+			Err(DsnpGraphError::UserGraphNotImported(999))
+		));
+	}
+
+	#[test]
+	fn export_updates_with_deterministic_nonce_mode_should_be_idempotent() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 0 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+		let actions = vec![Action::Disconnect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 3, schema_id },
+		}];
+		state.apply_actions(&actions, &None).expect("should apply");
+		let random = state.export_user_graph_updates(&dsnp_user_id).expect("should export");
+		state.set_export_options(ExportOptions {
+			nonce_mode: NonceMode::Deterministic,
+			..ExportOptions::default()
+		});
+
+		// act
+		let first = state.export_user_graph_updates(&dsnp_user_id).expect("should export");
+		let retried = state.export_user_graph_updates(&dsnp_user_id).expect("should export");
+		let recalculated = state.force_recalculate_graphs(&dsnp_user_id).expect("should export");
+		let recalculated_retried =
+			state.force_recalculate_graphs(&dsnp_user_id).expect("should export");
+
+		// assert
+		assert_eq!(state.export_options().nonce_mode, NonceMode::Deterministic);
+		assert_eq!(state.page_overflow_policy(), PageOverflowPolicy::Error);
+		assert_eq!(first.len(), 1);
+		assert_eq!(first, retried);
+		assert_ne!(first, random);
+		assert_eq!(recalculated, recalculated_retried);
+		// different content of the same page never reuses the derived nonce
+		assert_ne!(first, recalculated);
+		let Update::PersistPage { payload, .. } = &first[0] else { panic!("should persist page") };
+		let chunk = Frequency::read_private_graph(
+			payload,
+			&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			&KeyPairType::Version1_0(key_pair_raw).into(),
+		)
+		.expect("should decrypt");
+		assert_eq!(chunk.inner_graph, vec![DsnpGraphEdge { user_id: 2, since: 0 }]);
+	}
+
+	#[test]
+	fn export_updates_with_multi_recipient_mode_should_be_decryptable_by_any_recipient() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let to_graph_key_pair = |key_pair: &StackKeyPair| GraphKeyPair {
+			secret_key: key_pair.secret_key.to_vec(),
+			public_key: key_pair.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let recovery_key_pair = StackKeyPair::gen();
+		let own_key_pair = StackKeyPair::gen();
+		let dsnp_user_id = 123;
+		// the recovery key is published first so the own key stays the active one
+		let bundle = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![
+				to_graph_key_pair(&recovery_key_pair),
+				to_graph_key_pair(&own_key_pair),
+			])
+			.with_encryption_key(ResolvedKeyPair {
+				key_pair: KeyPairType::Version1_0(own_key_pair.clone()),
+				key_id: 1,
+			})
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		let mut state = GraphState::new(env.clone());
+		state.import_users_data(&vec![bundle.clone()]).expect("should import");
+		let actions = vec![Action::Disconnect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 3, schema_id },
+		}];
+		state.apply_actions(&actions, &None).expect("should apply");
+		let import_with_recovery_key = |updates: &Vec<Update>| {
+			let mut recovered = ImportBundleBuilder::build_from(&bundle, updates);
+			recovered.key_pairs = vec![to_graph_key_pair(&recovery_key_pair)];
+			let mut recovery_state = GraphState::new(env.clone());
+			recovery_state.import_users_data(&vec![recovered])?;
+			recovery_state.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false)
+		};
+
+		// act
+		let single = state.export_user_graph_updates(&dsnp_user_id).expect("should export");
+		state.set_export_options(ExportOptions {
+			encryption_mode: EncryptionMode::MultiRecipientV1,
+			..ExportOptions::default()
+		});
+		let no_recipients = state.export_user_graph_updates(&dsnp_user_id);
+		let unknown_key = state.set_additional_recipient_keys(&dsnp_user_id, &[5]);
+		state.set_additional_recipient_keys(&dsnp_user_id, &[0]).expect("should set");
+		let multi = state.export_user_graph_updates(&dsnp_user_id).expect("should export");
+		state.set_export_options(ExportOptions {
+			encryption_mode: EncryptionMode::MultiRecipientV1,
+			nonce_mode: NonceMode::Deterministic,
+			..ExportOptions::default()
+		});
+		let deterministic = state.export_user_graph_updates(&dsnp_user_id);
+
+		// assert
+		assert!(matches!(no_recipients, Err(DsnpGraphError::EncryptionError(_))));
+		assert!(matches!(unknown_key, Err(DsnpGraphError::ImportedKeyNotFound(123, _))));
+		assert!(matches!(
+			import_with_recovery_key(&single),
+			Err(DsnpGraphError::UnableToDecryptGraphChunkWithAnyKey)
+		));
+		let connections = import_with_recovery_key(&multi).expect("should decrypt");
+		assert_eq!(connections, vec![DsnpGraphEdge { user_id: 2, since: 0 }]);
+		let Update::PersistPage { payload, .. } = &multi[0] else { panic!("should persist page") };
+		let chunk = Frequency::read_private_graph(
+			payload,
+			&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			&KeyPairType::Version1_0(own_key_pair).into(),
+		)
+		.expect("should decrypt");
+		assert_eq!(chunk.key_id, 1);
+		assert!(matches!(deterministic, Err(DsnpGraphError::EncryptionError(_))));
+	}
+
+	#[test]
+	fn state_hash_should_match_for_same_state_regardless_of_import_order() {
+		// arrange
+		let env = Environment::Mainnet;
+		let public_schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let private_schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 0 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let private_bundle = ImportBundleBuilder::new(env.clone(), dsnp_user_id, private_schema_id)
+			.with_key_pairs(&vec![keypair.clone()])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		let public_bundle = ImportBundleBuilder::new(env.clone(), dsnp_user_id, public_schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_page(1, &vec![(4, 0)], &vec![], 100)
+			.with_page(2, &vec![(5, 0)], &vec![], 200)
+			.build();
+		let actions = vec![Action::Disconnect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 3, schema_id: private_schema_id },
+		}];
+		let mut state = GraphState::new(env.clone());
+		let mut other = GraphState::new(env);
+		state
+			.import_users_data(&vec![private_bundle.clone(), public_bundle.clone()])
+			.expect("should import");
+		other
+			.import_users_data(&vec![public_bundle, private_bundle])
+			.expect("should import");
+		state.apply_actions(&actions, &None).expect("should apply");
+		other.apply_actions(&actions, &None).expect("should apply");
+
+		// act
+		let hash = state.state_hash().expect("should hash");
+		let other_hash = other.state_hash().expect("should hash");
+		other
+			.apply_actions(
+				&vec![Action::Disconnect {
+					owner_dsnp_user_id: dsnp_user_id,
+					connection: Connection { dsnp_user_id: 4, schema_id: public_schema_id },
+				}],
+				&None,
+			)
+			.expect("should apply");
+
+		// assert
+		assert_eq!(hash, other_hash);
+		assert_eq!(hash, state.state_hash().expect("should hash"));
+		assert_ne!(hash, other.state_hash().expect("should hash"));
+	}
+
+	#[test]
+	fn deep_clone_should_create_independent_graph_state() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 0 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+		let pending_action = vec![Action::Disconnect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 3, schema_id },
+		}];
+		let options = Some(ActionOptions { disable_auto_commit: true, ..Default::default() });
+		state.apply_actions(&pending_action, &options).expect("should apply");
+
+		// act
+		let mut cloned = state.deep_clone().expect("should clone");
+		cloned.rollback();
+		let cloned_actions = vec![
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id,
+				connection: Connection { dsnp_user_id: 4, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+			Action::AddGraphKey {
+				owner_dsnp_user_id: dsnp_user_id,
+				new_public_key: StackKeyPair::gen().public_key.to_vec(),
+			},
+		];
+		cloned.apply_actions(&cloned_actions, &None).expect("should apply");
+		let cloned_updates =
+			cloned.export_user_graph_updates(&dsnp_user_id).expect("should export");
+
+		// assert
+		let connected_ids = |state: &GraphState| -> Vec<DsnpUserId> {
+			let mut ids: Vec<_> = state
+				.get_connections_for_user_graph(&dsnp_user_id, &schema_id, true)
+				.expect("should get connections")
+				.iter()
+				.map(|edge| edge.user_id)
+				.collect();
+			ids.sort();
+			ids
+		};
+		assert_eq!(connected_ids(&state), vec![2]);
+		assert_eq!(connected_ids(&cloned), vec![2, 3, 4]);
+		assert_eq!(cloned_updates.len(), 2);
+		let Some(Update::PersistPage { payload, .. }) =
+			cloned_updates.iter().find(|u| matches!(u, Update::PersistPage { .. }))
+		else {
+			panic!("should persist page")
+		};
+		let chunk = Frequency::read_private_graph(
+			payload,
+			&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			&KeyPairType::Version1_0(key_pair_raw).into(),
+		)
+		.expect("should decrypt");
+		assert_eq!(chunk.inner_graph.len(), 3);
+		let updates = state.export_user_graph_updates(&dsnp_user_id).expect("should export");
+		assert_eq!(updates.len(), 1);
+		assert!(matches!(updates[0], Update::PersistPage { .. }));
+	}
+
+	#[test]
+	fn freeze_should_create_snapshot_shared_across_threads() {
+		// arrange
+		fn assert_send_sync<T: Send + Sync>() {}
+		assert_send_sync::<ReadonlyGraphState>();
+
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+		let connect = Action::Connect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 4, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		};
+		state.apply_actions(&vec![connect], &None).expect("should apply");
+		let uncommitted = vec![Action::Disconnect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 2, schema_id },
+		}];
+		let options = Some(ActionOptions { disable_auto_commit: true, ..Default::default() });
+		state.apply_actions(&uncommitted, &options).expect("should apply");
+
+		// act
+		let snapshot = state.freeze().expect("should freeze");
+		state.commit();
+		state.remove_user_graph(&dsnp_user_id);
+		let connections: Vec<Vec<DsnpUserId>> = std::thread::scope(|scope| {
+			let handles: Vec<_> = (0..4)
+				.map(|_| {
+					let snapshot = snapshot.clone();
+					scope.spawn(move || {
+						let mut ids: Vec<_> = snapshot
+							.get_connections_for_user_graph(&dsnp_user_id, &schema_id, true)
+							.expect("should get connections")
+							.iter()
+							.map(|edge| edge.user_id)
+							.collect();
+						ids.sort();
+						ids
+					})
+				})
+				.collect();
+			handles.into_iter().map(|h| h.join().expect("should join")).collect()
+		});
+
+		// assert
+		assert!(!state.contains_user_graph(&dsnp_user_id));
+		assert!(snapshot.contains_user_graph(&dsnp_user_id));
+		assert_eq!(connections, vec![vec![2, 3, 4]; 4]);
+		assert_eq!(snapshot.pending_adds(&dsnp_user_id, &schema_id).expect("should work"), vec![4]);
+		assert_eq!(
+			snapshot.pending_removes(&dsnp_user_id, &schema_id).expect("should work"),
+			Vec::<DsnpUserId>::new()
+		);
+	}
+
+	#[test]
+	fn freeze_should_share_committed_user_graphs_until_they_change() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let bundles: Vec<_> = [123, 456]
+			.iter()
+			.map(|dsnp_user_id| {
+				ImportBundleBuilder::new(env.clone(), *dsnp_user_id, schema_id)
+					.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+					.build()
+			})
+			.collect();
+		state.import_users_data(&bundles).expect("should import");
+		let connect = Action::Connect {
+			owner_dsnp_user_id: 123,
+			connection: Connection { dsnp_user_id: 4, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		};
+		let shared = |snapshot: &ReadonlyGraphState, state: &GraphState, user_id| {
+			Arc::ptr_eq(&snapshot.user_graphs[&user_id], &state.user_map.inner()[&user_id])
+		};
+
+		// act
+		let snapshot = state.freeze().expect("should freeze");
+		let shared_after_freeze = [shared(&snapshot, &state, 123), shared(&snapshot, &state, 456)];
+		state.apply_actions(&vec![connect], &None).expect("should apply");
+
+		// assert
+		assert_eq!(shared_after_freeze, [true, true]);
+		assert!(!shared(&snapshot, &state, 123));
+		assert!(shared(&snapshot, &state, 456));
+		assert_eq!(
+			snapshot.pending_adds(&123, &schema_id).expect("should work"),
+			Vec::<DsnpUserId>::new()
+		);
+		assert_eq!(state.pending_adds(&123, &schema_id).expect("should work"), vec![4]);
+	}
+
+	#[test]
+	fn freeze_should_leave_key_pairs_out_of_the_snapshot() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 1 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let input = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![input]).expect("should import");
+
+		// act
+		let snapshot = state.freeze().expect("should freeze");
+
+		// assert
+		let snapshot_key_manager = snapshot.user_graphs[&dsnp_user_id].user_key_manager.clone();
+		let state_key_manager = state.user_map.inner()[&dsnp_user_id].user_key_manager.clone();
+		assert!(!Arc::ptr_eq(&snapshot_key_manager, &state_key_manager));
+		assert!(!snapshot_key_manager.read().unwrap().holds_key_pairs());
+		assert!(snapshot_key_manager.read().unwrap().get_all_resolved_keys().is_empty());
+		assert_eq!(snapshot_key_manager.read().unwrap().export_key_pairs().unwrap(), vec![]);
+		assert!(state_key_manager.read().unwrap().holds_key_pairs());
+		let mut connections: Vec<_> = snapshot
+			.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false)
+			.expect("should get connections")
+			.iter()
+			.map(|edge| edge.user_id)
+			.collect();
+		connections.sort();
+		assert_eq!(connections, vec![2, 3]);
+	}
+
+	#[test]
+	fn take_metrics_should_report_timings_of_instrumented_operations() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		let actions = vec![
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id,
+				connection: Connection { dsnp_user_id: 4, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+			Action::Disconnect {
+				owner_dsnp_user_id: 456,
+				connection: Connection { dsnp_user_id: 2, schema_id },
+			},
+		];
+		let options =
+			Some(ActionOptions { ignore_missing_connections: true, ..Default::default() });
+		assert_eq!(state.take_metrics(), None);
+		state.enable_instrumentation();
+
+		// act
+		state.import_users_data(&vec![bundle]).expect("should import");
+		state.apply_actions(&actions, &options).expect("should apply");
+		state.export_updates().expect("should export");
+		let metrics = state.take_metrics();
+
+		// assert
+		let metrics = metrics.expect("should be enabled");
+		assert_eq!(metrics.operations[&GraphOperation::Import].count, 1);
+		assert_eq!(metrics.operations[&GraphOperation::ApplyAction].count, 2);
+		assert_eq!(metrics.operations[&GraphOperation::Export].count, 2);
+		assert_eq!(metrics.users[&dsnp_user_id].len(), 3);
+		assert_eq!(metrics.users[&456][&GraphOperation::ApplyAction].count, 1);
+		let apply = metrics.operations[&GraphOperation::ApplyAction];
+		assert!(apply.min_micros <= apply.p50_micros && apply.p99_micros <= apply.max_micros);
+		assert_eq!(state.take_metrics(), Some(GraphMetrics::default()));
+		state.disable_instrumentation();
+		assert_eq!(state.take_metrics(), None);
+	}
+
+	#[test]
+	fn assert_fresh_should_fail_for_states_older_than_max_age() {
+		// arrange
+		#[derive(Debug)]
+		struct AdjustableTimeProvider(AtomicU64);
+
+		impl TimeProvider for AdjustableTimeProvider {
+			fn now_in_secs(&self) -> u64 {
+				self.0.load(Ordering::SeqCst)
+			}
+		}
+
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let time_provider = Arc::new(AdjustableTimeProvider(AtomicU64::new(1_000)));
+		let mut state = GraphState::with_time_provider(env.clone(), time_provider.clone());
+		time_provider.0.store(1_050, Ordering::SeqCst);
+		let bundle = ImportBundleBuilder::new(env, 123, schema_id)
+			.with_page(1, &vec![(2, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+
+		// act
+		let fresh = state.assert_fresh(Duration::from_secs(100));
+		time_provider.0.store(1_101, Ordering::SeqCst);
+		let stale = state.assert_fresh(Duration::from_secs(100));
+
+		// assert
+		assert_eq!(state.created_at(), 1_000);
+		assert_eq!(state.last_imported_at(), Some(1_050));
+		assert!(fresh.is_ok());
+		assert!(matches!(stale, Err(DsnpGraphError::StaleGraphState(101, 100))));
+	}
+
+	#[test]
+	fn drain_audit_log_should_return_resolved_actions() {
+		// arrange
+		#[derive(Debug)]
+		struct FixedTimeProvider(u64);
+
+		impl TimeProvider for FixedTimeProvider {
+			fn now_in_secs(&self) -> u64 {
+				self.0
+			}
+		}
+
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state =
+			GraphState::with_time_provider(env.clone(), Arc::new(FixedTimeProvider(1_000)));
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+		let connect = |id: DsnpUserId| Action::Connect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: id, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		};
+		let options =
+			Some(ActionOptions { ignore_existing_connections: true, ..Default::default() });
+		assert!(state.drain_audit_log().is_none());
+		state.enable_audit_log();
+
+		// act
+		state.apply_actions(&[connect(3), connect(2)], &options).expect("should apply");
+		let failed = state.apply_actions(&[connect(4), connect(3)], &None);
+		let records = state.drain_audit_log().expect("should be enabled");
+
+		// assert
+		assert!(failed.is_err());
+		let resolutions: Vec<_> = records.iter().map(|r| r.resolution).collect();
+		assert_eq!(
+			resolutions,
+			vec![
+				AuditResolution::Applied,
+				AuditResolution::Ignored,
+				AuditResolution::RolledBack,
+				AuditResolution::Failed
+			]
+		);
+		assert!(records.iter().all(|r| r.timestamp_secs == 1_000));
+		assert!(records[3].error.is_some());
+		assert!(matches!(
+			records[2].action,
+			Action::Connect { connection: Connection { dsnp_user_id: 4, .. }, .. }
+		));
+		assert_eq!(state.drain_audit_log().map(|r| r.len()), Some(0));
+		state.disable_audit_log();
+		assert!(state.drain_audit_log().is_none());
+	}
+
+	#[derive(Debug, Default)]
+	struct RecordingKeyManagerFactory {
+		created: Mutex<HashMap<DsnpUserId, Arc<RwLock<UserKeyManager>>>>,
+	}
+
+	impl UserKeyManagerFactory for RecordingKeyManagerFactory {
+		fn create(
+			&self,
+			dsnp_user_id: DsnpUserId,
+			shared_state_manager: Arc<RwLock<SharedStateManager>>,
+		) -> SharedUserKeyManager {
+			let key_manager =
+				Arc::new(RwLock::new(UserKeyManager::new(dsnp_user_id, shared_state_manager)));
+			self.created.lock().unwrap().insert(dsnp_user_id, key_manager.clone());
+			key_manager
+		}
+	}
+
+	/// Key pair that is only accessible through its operations, such as one kept inside an HSM
+	#[derive(Debug)]
+	struct OpaqueKeyPair(StackKeyPair);
+
+	impl SecretKeyOperations for OpaqueKeyPair {
+		fn public_key(&self) -> PublicKey {
+			self.0.public_key()
+		}
+
+		fn open_sealed_box(&self, sealed_data: &[u8]) -> DsnpGraphResult<Vec<u8>> {
+			self.0.open_sealed_box(sealed_data)
+		}
+
+		fn derive_shared_key(
+			&self,
+			public_key: &PublicKey,
+		) -> DsnpGraphResult<Zeroizing<[u8; CRYPTO_BOX_BEFORENMBYTES]>> {
+			self.0.derive_shared_key(public_key)
+		}
+
+		fn derive_key_material(
+			&self,
+			salt: &[u8],
+			info: &[u8],
+			output: &mut [u8],
+		) -> DsnpGraphResult<()> {
+			self.0.derive_key_material(salt, info, output)
+		}
+	}
+
+	#[derive(Debug)]
+	struct OpaqueKeyManagerFactory {
+		key_pair: SharedSecretKeyOperations,
+	}
+
+	impl UserKeyManagerFactory for OpaqueKeyManagerFactory {
+		fn create(
+			&self,
+			dsnp_user_id: DsnpUserId,
+			shared_state_manager: Arc<RwLock<SharedStateManager>>,
+		) -> SharedUserKeyManager {
+			Arc::new(RwLock::new(UserKeyManager::with_secret_key_operations(
+				dsnp_user_id,
+				shared_state_manager,
+				vec![self.key_pair.clone()],
+			)))
+		}
+	}
+
+	#[derive(Debug, Default)]
+	struct RecordingChainAdapter {
+		calls: Mutex<HashMap<&'static str, usize>>,
+	}
+
+	impl RecordingChainAdapter {
+		fn record(&self, call: &'static str) {
+			*self.calls.lock().unwrap().entry(call).or_default() += 1;
+		}
+
+		fn count(&self, call: &'static str) -> usize {
+			self.calls.lock().unwrap().get(call).copied().unwrap_or_default()
+		}
+	}
+
+	impl ChainAdapter for RecordingChainAdapter {
+		fn read_key(&self, data: &[u8]) -> DsnpGraphResult<DsnpPublicKey> {
+			self.record("read_key");
+			Frequency.read_key(data)
+		}
+
+		fn write_key(&self, key: &DsnpPublicKey) -> DsnpGraphResult<Vec<u8>> {
+			self.record("write_key");
+			Frequency.write_key(key)
+		}
+
+		fn read_public_page(
+			&self,
+			data: &[u8],
+		) -> DsnpGraphResult<(DsnpInnerGraph, CompressionFormat)> {
+			self.record("read_public_page");
+			Frequency.read_public_page(data)
+		}
+
+		fn write_public_page(&self, inner: &DsnpInnerGraph) -> DsnpGraphResult<Vec<u8>> {
+			self.record("write_public_page");
+			Frequency.write_public_page(inner)
+		}
+
+		fn write_inner_graph(&self, inner: &DsnpInnerGraph) -> DsnpGraphResult<Vec<u8>> {
+			self.record("write_inner_graph");
+			Frequency.write_inner_graph(inner)
+		}
+
+		fn read_private_page_key_id(&self, data: &[u8]) -> DsnpGraphResult<u64> {
+			self.record("read_private_page_key_id");
+			Frequency.read_private_page_key_id(data)
+		}
+
+		fn read_private_page(
+			&self,
+			data: &[u8],
+			dsnp_version_config: &DsnpVersionConfig,
+			decryption_input: &SecretKeyType,
+		) -> DsnpGraphResult<(PrivateGraphChunk, CompressionFormat)> {
+			self.record("read_private_page");
+			Frequency.read_private_page(data, dsnp_version_config, decryption_input)
+		}
+
+		fn write_private_page(
+			&self,
+			graph: &PrivateGraphChunk,
+			dsnp_version_config: &DsnpVersionConfig,
+			encryption_input: &PublicKeyType,
+		) -> DsnpGraphResult<Vec<u8>> {
+			self.record("write_private_page");
+			Frequency.write_private_page(graph, dsnp_version_config, encryption_input)
+		}
+
+		fn write_private_page_deterministic(
+			&self,
+			graph: &PrivateGraphChunk,
+			dsnp_version_config: &DsnpVersionConfig,
+			key_pair: &KeyPairType,
+			context: &[u8],
+		) -> DsnpGraphResult<Vec<u8>> {
+			self.record("write_private_page_deterministic");
+			Frequency.write_private_page_deterministic(
+				graph,
+				dsnp_version_config,
+				key_pair,
+				context,
+			)
+		}
+
+		fn write_private_page_multi(
+			&self,
+			graph: &PrivateGraphChunk,
+			dsnp_version_config: &DsnpVersionConfig,
+			encryption_inputs: &[PublicKeyType],
+		) -> DsnpGraphResult<Vec<u8>> {
+			self.record("write_private_page_multi");
+			Frequency.write_private_page_multi(graph, dsnp_version_config, encryption_inputs)
+		}
+
+		fn page_hash(&self, content: &[u8], dsnp_version_config: &DsnpVersionConfig) -> PageHash {
+			self.record("page_hash");
+			Frequency.page_hash(content, dsnp_version_config)
+		}
 	}
 
 	#[test]
-	fn graph_len() {
-		let mut state = GraphState::new(Environment::Mainnet);
-		let _ = state.get_or_create_user_graph(0);
-		assert_eq!(state.len(), 1);
-		let _ = state.get_or_create_user_graph(1);
-		assert_eq!(state.len(), 2);
+	fn graph_state_builder_should_use_provided_chain_adapter() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let adapter = Arc::new(RecordingChainAdapter::default());
+		let mut state =
+			GraphStateBuilder::new(env.clone()).with_chain_adapter(adapter.clone()).build();
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 0 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		let actions = vec![Action::Disconnect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 3, schema_id },
+		}];
+
+		// act
+		state.import_users_data(&vec![bundle]).expect("should import");
+		state.apply_actions(&actions, &None).expect("should apply");
+		let updates = state.export_user_graph_updates(&dsnp_user_id).expect("should export");
+
+		// assert
+		assert_eq!(updates.len(), 1);
+		assert_eq!(adapter.count("read_key"), 1);
+		assert_eq!(adapter.count("read_private_page_key_id"), 1);
+		assert_eq!(adapter.count("read_private_page"), 1);
+		assert_eq!(adapter.count("write_private_page"), 1);
+		assert_eq!(adapter.count("write_inner_graph"), 1);
+		assert_eq!(adapter.count("read_public_page"), 0);
+		assert!(Arc::ptr_eq(state.chain_adapter(), &(adapter as SharedChainAdapter)));
+	}
+
+	#[test]
+	fn key_bundle_should_migrate_key_pairs_to_another_graph_state() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 1 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let input = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		let mut state = GraphState::new(env.clone());
+		state.import_users_data(&vec![input]).expect("should import");
+		let mut other_device = GraphState::new(env);
+
+		// act
+		let bundle = state.export_key_bundle(&dsnp_user_id, "passphrase").expect("should export");
+		let wrong_user =
+			other_device.import_key_bundle(&(dsnp_user_id + 1), &bundle, "passphrase", &None);
+		let imported = other_device.import_key_bundle(&dsnp_user_id, &bundle, "passphrase", &None);
+
+		// assert
+		assert!(matches!(wrong_user, Err(DsnpGraphError::InvalidKeyBundle(_))));
+		assert!(imported.is_ok());
+		let key_pairs = |state: &GraphState| {
+			let user_graph = state.user_map.get(&dsnp_user_id).unwrap();
+			let key_pairs = user_graph.user_key_manager.read().unwrap().export_key_pairs();
+			key_pairs.unwrap()
+		};
+		assert_eq!(key_pairs(&other_device), key_pairs(&state));
+		assert!(matches!(
+			state.export_key_bundle(&999, "passphrase"),
+			Err(DsnpGraphError::UserGraphNotImported(999))
+		));
+	}
+
+	#[test]
+	fn import_key_bundle_without_auto_commit_should_not_commit_other_staged_changes() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let key_pair_raw = StackKeyPair::gen();
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let bundle = seal_key_bundle(1, &vec![keypair], "passphrase", KeyBundleLimits::default())
+			.expect("should seal");
+		let mut state = GraphState::new(env);
+		let options = Some(ActionOptions { disable_auto_commit: true, ..ActionOptions::default() });
+		let action = Action::Connect {
+			owner_dsnp_user_id: 2,
+			connection: Connection { dsnp_user_id: 3, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		};
+		state.apply_actions(&vec![action], &options).expect("should apply");
+
+		// act
+		let imported = state.import_key_bundle(&1, &bundle, "passphrase", &options);
+		state.rollback();
+
+		// assert
+		assert!(imported.is_ok());
+		assert!(!state.contains_user_graph(&1));
+		assert!(!state.contains_user_graph(&2));
+	}
+
+	#[test]
+	fn remove_imported_keypair_should_report_pages_which_remaining_keys_can_not_decrypt() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let key_pairs: Vec<_> = (0..2)
+			.map(|_| {
+				let key_pair_raw = StackKeyPair::gen();
+				GraphKeyPair {
+					secret_key: key_pair_raw.secret_key.to_vec(),
+					public_key: key_pair_raw.public_key.to_vec(),
+					key_type: GraphKeyType::X25519,
+				}
+			})
+			.collect();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: key_pairs[0].clone().try_into().unwrap(), key_id: 0 };
+		let dsnp_user_id = 123;
+		let input = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+			.with_key_pairs(&key_pairs)
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		let mut state = GraphState::new(env);
+		state.import_users_data(&vec![input]).expect("should import");
+
+		// act
+		let active_removed =
+			state.remove_imported_keypair(&dsnp_user_id, &key_pairs[1].public_key, &None);
+		let missing = state.remove_imported_keypair(&dsnp_user_id, &key_pairs[1].public_key, &None);
+		let decrypting_removed =
+			state.remove_imported_keypair(&dsnp_user_id, &key_pairs[0].public_key, &None);
+
+		// assert
+		let active_removed = active_removed.expect("should remove");
+		assert!(active_removed.active_key_removed);
+		assert!(active_removed.undecryptable_pages.is_empty());
+		assert!(matches!(missing, Err(DsnpGraphError::InvalidInput(_))));
+		let decrypting_removed = decrypting_removed.expect("should remove");
+		assert!(!decrypting_removed.is_safe());
+		assert_eq!(decrypting_removed.undecryptable_pages, BTreeMap::from([(schema_id, vec![1])]));
+		assert!(matches!(
+			state.remove_imported_keypair(&404, &key_pairs[0].public_key, &None),
+			Err(DsnpGraphError::UserGraphNotImported(404))
+		));
+	}
+
+	#[test]
+	fn remove_imported_keypair_without_auto_commit_should_be_reverted_by_rollback() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let key_pair_raw = StackKeyPair::gen();
+		let key_pair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let resolved_key =
+			ResolvedKeyPair { key_pair: key_pair.clone().try_into().unwrap(), key_id: 0 };
+		let dsnp_user_id = 123;
+		let input = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![key_pair.clone()])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		let mut state = GraphState::new(env);
+		state.import_users_data(&vec![input]).expect("should import");
+		let options = Some(ActionOptions { disable_auto_commit: true, ..ActionOptions::default() });
+
+		// act
+		let report = state
+			.remove_imported_keypair(&dsnp_user_id, &key_pair.public_key, &options)
+			.expect("should remove");
+		state.rollback();
+
+		// assert
+		assert_eq!(report.undecryptable_pages, BTreeMap::from([(schema_id, vec![1])]));
+		assert!(state.is_user_graph_writable(&dsnp_user_id, &schema_id));
+		let user_graph = state.user_map.get(&dsnp_user_id).expect("should exist");
+		assert_eq!(user_graph.user_key_manager.read().unwrap().get_all_resolved_keys().len(), 1);
+	}
+
+	#[test]
+	fn graph_state_try_new_should_accept_configs_with_page_capacities() {
+		// arrange
+		let mut config = Environment::Mainnet.get_config().clone();
+		config.schema_map.clear();
+
+		// act
+		let mainnet = GraphState::try_new(Environment::Mainnet);
+		let without_schemas = GraphState::try_new(Environment::Dev(config));
+
+		// assert
+		assert!(mainnet.is_ok());
+		assert!(without_schemas.is_ok());
+	}
+
+	#[test]
+	fn graph_state_try_new_should_require_page_capacity_of_schemas_without_capacity() {
+		// arrange
+		let mut config = Environment::Mainnet.get_config().clone();
+		config.max_graph_page_size_bytes = 32;
+		let mut with_capacities = config.clone();
+		for schema_config in with_capacities.schema_map.values_mut() {
+			schema_config.page_capacity = NonZeroU32::new(1);
+		}
+
+		// act
+		let without_capacities = GraphState::try_new(Environment::Dev(config));
+		let with_capacities = GraphState::try_new(Environment::Dev(with_capacities));
+
+		// assert
+		assert!(matches!(without_capacities, Err(DsnpGraphError::PageCapacityNotConfigured(_))));
+		assert!(with_capacities.is_ok());
+	}
+
+	#[test]
+	fn graph_states_of_different_environments_should_be_isolated_and_tracked() {
+		// arrange
+		let name = "graph-state-multi-environment-test";
+		let mut config = Environment::Mainnet.get_config().clone();
+		config.max_graph_page_size_bytes *= 4;
+		EnvironmentRegistry::register(name, config).expect("should register");
+		let custom = EnvironmentRegistry::environment(name).expect("should exist");
+		let schema_id = Environment::Mainnet
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let dsnp_user_id = 123;
+		let mut mainnet_state = GraphState::try_new(Environment::Mainnet).expect("should create");
+		let mut custom_state = GraphState::try_new(custom.clone()).expect("should create");
+		for (state, env) in
+			[(&mut mainnet_state, Environment::Mainnet), (&mut custom_state, custom)]
+		{
+			let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+				.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+				.build();
+			state.import_users_data(&vec![bundle]).expect("should import");
+		}
+		let custom_copy = custom_state.deep_clone().expect("should clone");
+
+		// act
+		let mainnet_report = mainnet_state.remaining_capacity(&dsnp_user_id, &schema_id);
+		let custom_report = custom_state.remaining_capacity(&dsnp_user_id, &schema_id);
+		let in_use = GraphState::environments_in_use();
+		drop(custom_state);
+		drop(custom_copy);
+
+		// assert
+		let mainnet_report = mainnet_report.expect("should work");
+		let custom_report = custom_report.expect("should work");
+		assert!(custom_report.remaining_connections > 3 * mainnet_report.remaining_connections);
+		assert_eq!(in_use.get(name), Some(&2));
+		assert!(in_use.contains_key(Environment::Mainnet.name()));
+		assert_eq!(GraphState::environments_in_use().get(name), None);
+	}
+
+	#[test]
+	fn import_users_data_should_import_bundles_of_multiple_users_and_rollback_on_failure() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let users: Vec<DsnpUserId> = (100..120).collect();
+		let mut bundles: Vec<_> = users
+			.iter()
+			.map(|dsnp_user_id| {
+				let key_pair_raw = StackKeyPair::gen();
+				let keypair = GraphKeyPair {
+					secret_key: key_pair_raw.secret_key.to_vec(),
+					public_key: key_pair_raw.public_key.to_vec(),
+					key_type: GraphKeyType::X25519,
+				};
+				ImportBundleBuilder::new(env.clone(), *dsnp_user_id, schema_id)
+					.with_key_pairs(&vec![keypair])
+					.with_encryption_key(ResolvedKeyPair {
+						key_pair: KeyPairType::Version1_0(key_pair_raw),
+						key_id: 0,
+					})
+					.with_page(
+						1,
+						&vec![(*dsnp_user_id + 1, 0), (*dsnp_user_id + 2, 0)],
+						&vec![],
+						100,
+					)
+					.build()
+			})
+			.collect();
+		let mut invalid_bundle = bundles[0].clone();
+		invalid_bundle.dsnp_user_id = 200;
+		invalid_bundle.pages[0].content = vec![1, 2, 3];
+
+		// act
+		let result = state.import_users_data(&bundles);
+		bundles.push(invalid_bundle);
+		let mut failed_state = GraphState::new(env);
+		let failed_result = failed_state.import_users_data(&bundles);
+
+		// assert
+		assert!(result.is_ok());
+		for dsnp_user_id in users {
+			let connections = state
+				.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false)
+				.expect("should get connections");
+			assert_eq!(
+				connections.iter().map(|c| c.user_id).collect::<HashSet<_>>(),
+				HashSet::from([dsnp_user_id + 1, dsnp_user_id + 2])
+			);
+		}
+		assert!(failed_result.is_err());
+		assert_eq!(failed_state.len(), 0);
+	}
+
+	#[test]
+	fn graph_state_builder_should_use_provided_key_manager_factory() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let factory = Arc::new(RecordingKeyManagerFactory::default());
+		let mut state = GraphStateBuilder::new(env.clone())
+			.with_key_manager_factory(factory.clone())
+			.build();
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 0 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+
+		// act
+		let result = state.import_users_data(&vec![bundle]);
+
+		// assert
+		assert!(result.is_ok());
+		let created = factory.created.lock().unwrap();
+		assert_eq!(created.keys().copied().collect::<Vec<_>>(), vec![dsnp_user_id]);
+		let key_manager = created[&dsnp_user_id].read().unwrap();
+		assert_eq!(key_manager.get_imported_keys().len(), 1);
+		assert!(key_manager.get_resolved_active_key(dsnp_user_id).is_some());
+		let connections = state
+			.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false)
+			.expect("should decrypt using the provided key manager");
+		assert_eq!(connections.len(), 2);
 	}
 
 	#[test]
-	fn add_user_success() {
-		let mut state = GraphState::new(Environment::Mainnet);
-		let res = state.get_or_create_user_graph(0);
-		assert!(res.is_ok());
+	fn graph_state_should_use_secret_keys_only_through_their_operations() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let key_pair_raw = StackKeyPair::gen();
+		let key_pair: SharedSecretKeyOperations = Arc::new(OpaqueKeyPair(key_pair_raw.clone()));
+		let mut state = GraphStateBuilder::new(env.clone())
+			.with_key_manager_factory(Arc::new(OpaqueKeyManagerFactory {
+				key_pair: key_pair.clone(),
+			}))
+			.build();
+		let dsnp_user_id = 123;
+		let mut bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![GraphKeyPair {
+				secret_key: key_pair_raw.secret_key.to_vec(),
+				public_key: key_pair_raw.public_key.to_vec(),
+				key_type: GraphKeyType::X25519,
+			}])
+			.with_encryption_key(ResolvedKeyPair {
+				key_pair: KeyPairType::Version1_0(key_pair_raw),
+				key_id: 0,
+			})
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		bundle.key_pairs = vec![];
+		let connect = Action::Connect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 4, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		};
+
+		// act
+		let imported = state.import_users_data(&vec![bundle]);
+		let connections = state.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false);
+		let applied = state.apply_actions(&vec![connect], &None);
+		let updates = state.export_user_graph_updates(&dsnp_user_id);
+
+		// assert
+		assert!(imported.is_ok());
+		assert_eq!(connections.expect("should decrypt").len(), 2);
+		assert!(applied.is_ok());
+		let updates = updates.expect("should encrypt");
+		let Update::PersistPage { payload, .. } = &updates[0] else {
+			panic!("should persist page")
+		};
+		let decrypted = Frequency::read_private_graph(
+			payload,
+			&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			&SecretKeyType::ExternalVersion1_0(key_pair),
+		)
+		.expect("should decrypt");
+		assert_eq!(
+			decrypted.inner_graph.iter().map(|c| c.user_id).collect::<HashSet<_>>(),
+			HashSet::from([2, 3, 4])
+		);
 	}
 
 	#[test]
-	fn remove_user_success() {
-		let mut state = GraphState::new(Environment::Mainnet);
-		let _ = state.get_or_create_user_graph(0);
-		let _ = state.get_or_create_user_graph(1);
-		state.remove_user_graph(&0);
-		assert_eq!(state.len(), 1);
-		assert!(!state.contains_user_graph(&0));
-		assert!(state.contains_user_graph(&1));
+	fn lint_user_graph_should_report_spec_violations() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let future = state.time_provider().time_in_ksecs() + 1000;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.with_page(2, &vec![(5, 0), (4, future), (3, 0)], &vec![], 200)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+
+		// act
+		let report = state.lint_user_graph(&dsnp_user_id);
+
+		// assert
+		let report = report.expect("should work");
+		let rules: Vec<_> = report.issues.iter().map(|i| (i.page_id, i.rule)).collect();
+		assert_eq!(
+			rules,
+			vec![
+				(2, LintRule::FutureTimestamp),
+				(2, LintRule::DuplicateConnection),
+				(2, LintRule::UnsortedConnections),
+			]
+		);
+		assert!(report.has_errors());
+		assert_eq!(report.errors().len(), 2);
+		assert_eq!(report.warnings().len(), 1);
+		assert!(matches!(
+			state.lint_user_graph(&999),
+			Err(DsnpGraphError::UserGraphNotImported(999))
+		));
 	}
 
 	#[test]
-	fn remove_nonexistent_user_noop() {
-		let mut state = GraphState::new(Environment::Mainnet);
-		let _ = state.get_or_create_user_graph(0);
-		let _ = state.get_or_create_user_graph(1);
-		state.remove_user_graph(&99);
-		assert_eq!(state.user_map.len(), 2);
+	fn import_users_data_with_report_should_report_and_export_repaired_pages() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.with_page(2, &vec![(3, 0), (4, 0)], &vec![], 200)
+			.build();
+		let clean_bundle = ImportBundleBuilder::new(env, 456, schema_id)
+			.with_page(1, &vec![(2, 0)], &vec![], 300)
+			.build();
+
+		// act
+		let report = state.import_users_data_with_report(&vec![bundle, clean_bundle]);
+
+		// assert
+		let report = report.expect("should import");
+		assert!(!report.is_clean());
+		assert_eq!(
+			report.repairs,
+			vec![ConnectionRepair {
+				dsnp_user_id,
+				schema_id,
+				connection_id: 3,
+				kept_page_id: 1,
+				repaired_page_id: 2,
+			}]
+		);
+		let updates = state.export_updates().expect("should export");
+		assert_eq!(updates.len(), 1);
+		assert!(matches!(
+			updates[0],
+			Update::PersistPage { owner_dsnp_user_id: 123, page_id: 2, prev_hash: 200, .. }
+		));
 	}
 
 	#[test]
-	fn import_user_data_should_import_keys_and_data_for_public_follow_graph() {
+	fn import_users_data_with_verify_content_hashes_should_reject_mismatched_pages() {
 		// arrange
 		let env = Environment::Mainnet;
 		let schema_id = env
 			.get_config()
 			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
 			.expect("should exist");
+		let dsnp_version_config = DsnpVersionConfig::new(DsnpVersion::Version1_0);
+		let mut corrupted = ImportBundleBuilder::new(env.clone(), 123, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 0)
+			.build();
+		let mut bundle = corrupted.clone();
+		bundle.pages[0].content_hash =
+			Frequency::calculate_page_hash(&bundle.pages[0].content, &dsnp_version_config);
+		corrupted.pages[0].content_hash = bundle.pages[0].content_hash.wrapping_add(1);
 		let mut state = GraphState::new(env.clone());
-		let key_pair_raw = StackKeyPair::gen();
-		let keypair = GraphKeyPair {
-			secret_key: key_pair_raw.secret_key.to_vec(),
-			public_key: key_pair_raw.public_key.to_vec(),
-			key_type: GraphKeyType::X25519,
-		};
+		let mut verifying_state = GraphState::new(env);
+		verifying_state.set_import_options(ImportOptions {
+			verify_content_hashes: true,
+			..ImportOptions::default()
+		});
+
+		// act
+		let unverified = state.import_users_data(&vec![corrupted.clone()]);
+		let rejected = verifying_state.import_users_data(&vec![corrupted]);
+		let verified = verifying_state.import_users_data(&vec![bundle.clone()]);
+
+		// assert
+		assert!(unverified.is_ok());
+		assert!(matches!(
+			rejected,
+			Err(DsnpGraphError::PageContentHashMismatch(1, imported, calculated))
+				if calculated == bundle.pages[0].content_hash && imported == calculated.wrapping_add(1)
+		));
+		assert!(verified.is_ok());
+		assert!(verifying_state.contains_user_graph(&123));
+	}
+
+	#[test]
+	fn import_users_data_with_max_dsnp_user_id_should_reject_ids_that_are_not_issued() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let issued = ImportBundleBuilder::new(env.clone(), 100, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		let not_issued = ImportBundleBuilder::new(env.clone(), 101, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		let mut state = GraphState::new(env);
+		state.set_import_options(ImportOptions {
+			max_dsnp_user_id: Some(100),
+			..ImportOptions::default()
+		});
+
+		// act
+		let rejected = state.import_users_data(&vec![issued.clone(), not_issued]);
+		let imported = state.import_users_data(&vec![issued]);
+
+		// assert
+		assert!(matches!(rejected, Err(DsnpGraphError::InvalidDsnpUserId(101))));
+		assert!(imported.is_ok());
+		assert!(state.contains_user_graph(&100));
+		assert!(!state.contains_user_graph(&101));
+	}
+
+	#[test]
+	fn import_users_data_with_merge_pages_should_only_replace_provided_pages() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
 		let dsnp_user_id = 123;
-		let connections = vec![(2, 0), (3, 0), (4, 0), (5, 0)];
-		let input = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
-			.with_key_pairs(&vec![keypair.clone()])
-			.with_page(1, &connections, &vec![], 1000)
+		let bundle = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.with_page(2, &vec![(4, 0), (5, 0)], &vec![], 200)
 			.build();
+		let changed_pages = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+			.with_page(2, &vec![(5, 0), (6, 0)], &vec![], 300)
+			.with_page(3, &vec![(3, 0), (7, 0)], &vec![], 400)
+			.build();
+		let mut state = GraphState::new(env);
+		state.import_users_data(&vec![bundle]).expect("should import");
+		state.set_import_options(ImportOptions { merge_pages: true, ..ImportOptions::default() });
 
 		// act
-		let res = state.import_users_data(&vec![input]);
+		let report = state.import_users_data_with_report(&vec![changed_pages]);
 
 		// assert
-		assert!(res.is_ok());
+		let report = report.expect("should import");
+		assert_eq!(report.repairs.len(), 1);
+		assert_eq!(report.repairs[0].connection_id, 3);
+		assert_eq!(report.repairs[0].repaired_page_id, 3);
+		let mut connections: Vec<_> = state
+			.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false)
+			.expect("should work")
+			.iter()
+			.map(|c| c.user_id)
+			.collect();
+		connections.sort();
+		assert_eq!(connections, vec![2, 3, 5, 6, 7]);
+		let stats = state.get_user_graph_stats(&dsnp_user_id, &schema_id).expect("should work");
+		assert_eq!(stats.page_hashes, BTreeMap::from([(1, 100), (2, 300), (3, 400)]));
+		let updates = state.export_user_graph_updates(&dsnp_user_id).expect("should export");
+		assert!(matches!(updates[..], [Update::PersistPage { page_id: 3, .. }]));
+	}
 
-		let public_manager = state.shared_state_manager.read().unwrap();
-		let keys = public_manager.get_imported_keys(dsnp_user_id);
-		assert_eq!(keys.len(), 1);
+	#[test]
+	fn import_users_data_with_dedupe_within_pages_should_report_and_export_repaired_pages() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0), (2, 0)], &vec![], 100)
+			.with_page(2, &vec![(3, 0), (4, 0)], &vec![], 200)
+			.build();
+		let mut state = GraphState::new(env.clone());
+		let mut dedupe_state = GraphState::new(env);
+		dedupe_state.set_import_options(ImportOptions {
+			dedupe_within_pages: true,
+			..ImportOptions::default()
+		});
 
-		let res = state.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false);
-		assert!(res.is_ok());
-		let res_set: HashSet<_> = res.unwrap().iter().copied().collect();
-		let mapped: HashSet<_> = connections
+		// act
+		let report = state.import_users_data_with_report(&vec![bundle.clone()]);
+		let dedupe_report = dedupe_state.import_users_data_with_report(&vec![bundle]);
+
+		// assert
+		let report = report.expect("should import");
+		assert_eq!((report.within_page_repairs(), report.across_page_repairs()), (0, 1));
+		let dedupe_report = dedupe_report.expect("should import");
+		assert_eq!(
+			dedupe_report.repairs,
+			vec![
+				ConnectionRepair {
+					dsnp_user_id,
+					schema_id,
+					connection_id: 2,
+					kept_page_id: 1,
+					repaired_page_id: 1,
+				},
+				ConnectionRepair {
+					dsnp_user_id,
+					schema_id,
+					connection_id: 3,
+					kept_page_id: 1,
+					repaired_page_id: 2,
+				},
+			]
+		);
+		assert_eq!(
+			(dedupe_report.within_page_repairs(), dedupe_report.across_page_repairs()),
+			(1, 1)
+		);
+		let connections = dedupe_state
+			.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false)
+			.expect("should get connections");
+		assert_eq!(connections.len(), 3);
+		let updates = dedupe_state.export_updates().expect("should export");
+		assert_eq!(updates.len(), 2);
+		assert!(matches!(
+			updates[0],
+			Update::PersistPage { owner_dsnp_user_id: 123, page_id: 1, prev_hash: 100, .. }
+		));
+	}
+
+	#[test]
+	fn remaining_capacity_should_report_capacity_including_pending_updates() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+		let before = state.remaining_capacity(&dsnp_user_id, &schema_id).expect("should work");
+		let actions = vec![Action::Connect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 5, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		}];
+		state.apply_actions(&actions, &None).expect("should apply");
+
+		// act
+		let report = state.remaining_capacity(&dsnp_user_id, &schema_id);
+
+		// assert
+		let report = report.expect("should work");
+		assert_eq!(report.used_pages, 1);
+		assert_eq!(report.connections, 2);
+		assert_eq!(report.pending_additions, 1);
+		assert_eq!(report.remaining_connections, before.remaining_connections - 1);
+		assert!(report.full_page_ids.is_empty());
+		assert!(!report.is_over_capacity());
+		assert_eq!(state.page_overflow_policy(), PageOverflowPolicy::Error);
+		assert!(matches!(
+			state.remaining_capacity(&999, &schema_id),
+			Err(DsnpGraphError::UserGraphNotImported(999))
+		));
+	}
+
+	#[test]
+	fn changes_since_should_return_connection_changes_of_imported_pages() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let synced = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.with_page(2, &vec![(4, 0)], &vec![], 200)
+			.build();
+		let current = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (5, 0)], &vec![], 101)
+			.with_page(3, &vec![(4, 0), (6, 0)], &vec![], 300)
+			.build();
+		state.import_users_data(&vec![synced]).expect("should import");
+		let page_hashes: Vec<(PageId, PageHash)> = state
+			.get_user_graph_stats(&dsnp_user_id, &schema_id)
+			.expect("should get stats")
+			.page_hashes
 			.into_iter()
-			.map(|(c, s)| DsnpGraphEdge { user_id: c, since: s })
 			.collect();
-		assert_eq!(res_set, mapped);
+		state.import_users_data(&vec![current]).expect("should import");
+
+		// act
+		let changes = state.changes_since(&dsnp_user_id, &schema_id, &page_hashes);
+		let unknown_changes = state.changes_since(&dsnp_user_id, &schema_id, &[(1, 99)]);
+
+		// assert
+		let changes = changes.expect("should report changes");
+		assert_eq!(changes.added, vec![5, 6]);
+		assert_eq!(changes.removed, vec![3]);
+		assert_eq!(changes.changed_pages, vec![1, 2, 3]);
+		assert!(changes.is_complete());
+		assert_eq!(changes.page_hashes, BTreeMap::from([(1, 101), (3, 300)]));
+		let unknown_changes = unknown_changes.expect("should report changes");
+		assert_eq!(unknown_changes.added, vec![4, 6]);
+		assert!(unknown_changes.removed.is_empty());
+		assert_eq!(unknown_changes.changed_pages, vec![1, 3]);
+		assert_eq!(unknown_changes.unresolved_pages, vec![1]);
+		assert!(matches!(
+			state.changes_since(&dsnp_user_id, &9999, &page_hashes),
+			Err(DsnpGraphError::InvalidSchemaId(9999, _))
+		));
 	}
 
 	#[test]
-	fn import_user_data_should_import_keys_and_data_for_private_follow_graph() {
+	fn pending_adds_and_removes_should_return_not_exported_connections() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+		let actions = vec![
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id,
+				connection: Connection { dsnp_user_id: 5, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+			Action::Disconnect {
+				owner_dsnp_user_id: dsnp_user_id,
+				connection: Connection { dsnp_user_id: 2, schema_id },
+			},
+		];
+		state.apply_actions(&actions, &None).expect("should apply");
+
+		// act
+		let adds = state.pending_adds(&dsnp_user_id, &schema_id);
+		let removes = state.pending_removes(&dsnp_user_id, &schema_id);
+
+		// assert
+		assert_eq!(adds.expect("should work"), vec![5]);
+		assert_eq!(removes.expect("should work"), vec![2]);
+		assert!(matches!(
+			state.pending_adds(&999, &schema_id),
+			Err(DsnpGraphError::UserGraphNotImported(999))
+		));
+		assert!(matches!(
+			state.pending_removes(&dsnp_user_id, &9999),
+			Err(DsnpGraphError::InvalidSchemaId(9999, _))
+		));
+	}
+
+	#[test]
+	fn export_updates_with_details_should_pair_updates_with_connection_changes() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+		let actions = vec![
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id,
+				connection: Connection { dsnp_user_id: 5, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+			Action::Disconnect {
+				owner_dsnp_user_id: dsnp_user_id,
+				connection: Connection { dsnp_user_id: 2, schema_id },
+			},
+			Action::AddGraphKey {
+				owner_dsnp_user_id: dsnp_user_id,
+				new_public_key: StackKeyPair::gen().public_key.to_vec(),
+			},
+		];
+		state.apply_actions(&actions, &None).expect("should apply");
+
+		// act
+		let updates = state.export_updates_with_details();
+
+		// assert
+		let updates = updates.expect("should export");
+		assert_eq!(
+			updates.iter().map(|u| u.update.clone()).collect::<Vec<_>>(),
+			state.export_updates().expect("should export")
+		);
+		assert_eq!(updates.len(), 2);
+		assert!(matches!(updates[0].update, Update::AddKey { .. }));
+		assert_eq!(updates[0].details, None);
+		assert!(matches!(updates[1].update, Update::PersistPage { page_id: 1, .. }));
+		assert_eq!(
+			updates[1].details,
+			Some(UpdateDetails {
+				added_connections: vec![5],
+				removed_connections: vec![2],
+				connection_count: 2,
+				..updates[1].details.clone().unwrap_or_default()
+			})
+		);
+	}
+
+	#[test]
+	fn exported_updates_should_carry_imported_chain_head() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id = 123;
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.with_chain_head(1_000, &[7u8; 32])
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+		let actions = vec![Action::Connect {
+			owner_dsnp_user_id: dsnp_user_id,
+			connection: Connection { dsnp_user_id: 5, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		}];
+		state.apply_actions(&actions, &None).expect("should apply");
+
+		// act
+		let updates = state.export_user_graph_updates(&dsnp_user_id);
+		let removal = state.export_graph_removal(&dsnp_user_id);
+
+		// assert
+		let expected = ChainHead { block_number: 1_000, block_hash: vec![7u8; 32] };
+		let updates = updates.expect("should export");
+		assert_eq!(updates.len(), 1);
+		assert_eq!(updates[0].chain_head(), Some(&expected));
+		let removal = removal.expect("should export");
+		assert!(!removal.is_empty());
+		assert!(removal.iter().all(|u| u.chain_head() == Some(&expected)));
+	}
+
+	#[test]
+	fn memory_footprint_should_account_for_imported_graphs_and_pending_updates() {
 		// arrange
 		let env = Environment::Mainnet;
 		let schema_id = env
 			.get_config()
-			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
 			.expect("should exist");
 		let mut state = GraphState::new(env.clone());
-		let key_pair_raw = StackKeyPair::gen();
-		let resolved_key =
-			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 1 };
-		let keypair = GraphKeyPair {
-			secret_key: key_pair_raw.secret_key.to_vec(),
-			public_key: key_pair_raw.public_key.to_vec(),
-			key_type: GraphKeyType::X25519,
-		};
-		let dsnp_user_id = 123;
-		let connections = vec![(2, 0), (3, 0), (4, 0), (5, 0)];
-		let input = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
-			.with_key_pairs(&vec![keypair])
-			.with_encryption_key(resolved_key)
-			.with_page(1, &connections, &vec![], 100)
+		let empty_report = state.memory_footprint().expect("should work");
+		let input_1 = ImportBundleBuilder::new(env.clone(), 1, schema_id)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
 			.build();
+		let input_2 = ImportBundleBuilder::new(env, 2, schema_id)
+			.with_page(1, &(10..100).map(|id| (id, 0)).collect::<Vec<_>>(), &vec![], 100)
+			.build();
+		state.import_users_data(&vec![input_1, input_2]).expect("should import");
+		let imported_report = state.memory_footprint().expect("should work");
+		let actions: Vec<_> = (100..110)
+			.map(|id| Action::Connect {
+				owner_dsnp_user_id: 1,
+				connection: Connection { dsnp_user_id: id, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			})
+			.collect();
 
 		// act
-		let res = state.import_users_data(&vec![input]);
+		state.apply_actions(&actions, &None).expect("should apply");
+		let report = state.memory_footprint().expect("should work");
 
 		// assert
-		assert!(res.is_ok());
-
-		let public_manager = state.shared_state_manager.read().unwrap();
-		let keys = public_manager.get_imported_keys(dsnp_user_id);
-		assert_eq!(keys.len(), 1);
-
-		let res = state.get_connections_for_user_graph(&dsnp_user_id, &schema_id, false);
-		assert!(res.is_ok());
-		let res_set: HashSet<_> = res.unwrap().iter().copied().collect();
-		let mapped: HashSet<_> = connections
-			.into_iter()
-			.map(|(c, s)| DsnpGraphEdge { user_id: c, since: s })
-			.collect();
-		assert_eq!(res_set, mapped);
+		assert!(empty_report.user_graph_bytes.is_empty());
+		assert_eq!(imported_report.user_graph_bytes.len(), 2);
+		assert_eq!(report.user_graph_bytes, imported_report.user_graph_bytes);
+		let (user_1, user_1_bytes) = report.user_graph_bytes[0];
+		let (user_2, user_2_bytes) = report.user_graph_bytes[1];
+		assert_eq!((user_1, user_2), (1, 2));
+		assert!(user_2_bytes > user_1_bytes);
+		assert!(report.pending_update_bytes > imported_report.pending_update_bytes);
+		assert!(report.total_bytes() > imported_report.total_bytes());
+		assert!(imported_report.total_bytes() > empty_report.total_bytes());
 	}
 
 	#[test]
@@ -773,6 +5434,8 @@ mod test {
 				owner_dsnp_user_id: dsnp_user_id,
 				connection: Connection { dsnp_user_id: id, schema_id },
 				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
 			})
 			.collect();
 		let mem_usage = memory_stats().unwrap();
@@ -784,6 +5447,10 @@ mod test {
 				ignore_existing_connections: true,
 				ignore_missing_connections: false,
 				disable_auto_commit: false,
+				collect_errors: false,
+				require_counterpart_keys: false,
+				max_batch_size: None,
+				timestamp_policy: TimestampPolicy::default(),
 			}),
 		);
 
@@ -822,6 +5489,152 @@ mod test {
 		assert_eq!(before_export_set, after_reimport_set);
 	}
 
+	#[test]
+	fn estimate_key_rotation_impact_should_count_private_pages_without_changing_state() {
+		// arrange
+		let env = Environment::Mainnet;
+		let private_schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let public_schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 0 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let private_bundle = ImportBundleBuilder::new(env.clone(), dsnp_user_id, private_schema_id)
+			.with_key_pairs(&vec![keypair.clone()])
+			.with_encryption_key(resolved_key)
+			.with_page(1, &vec![(2, 0), (3, 0)], &vec![], 100)
+			.with_page(2, &vec![(4, 0)], &vec![], 100)
+			.build();
+		let public_bundle = ImportBundleBuilder::new(env, dsnp_user_id, public_schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_page(1, &vec![(5, 0)], &vec![], 100)
+			.build();
+		state
+			.import_users_data(&vec![private_bundle, public_bundle])
+			.expect("should import");
+		let public_keys = state.get_public_keys(&dsnp_user_id).expect("should get keys");
+		let recalculated = state.force_recalculate_graphs(&dsnp_user_id).expect("should export");
+
+		// act
+		let impact = state.estimate_key_rotation_impact(&dsnp_user_id).expect("should estimate");
+
+		// assert
+		let private_bytes: usize = recalculated
+			.iter()
+			.filter_map(|u| match u {
+				Update::PersistPage { schema_id, payload, .. }
+					if *schema_id == private_schema_id =>
+					Some(payload.len()),
+				_ => None,
+			})
+			.sum();
+		assert_eq!(impact.pages, 2);
+		assert_eq!(impact.bytes, private_bytes);
+		assert!(impact.users_missing_keys.is_empty());
+		assert_eq!(state.get_public_keys(&dsnp_user_id).expect("should get keys"), public_keys);
+		assert!(matches!(
+			state.estimate_key_rotation_impact(&404),
+			Err(DsnpGraphError::UserGraphNotImported(404))
+		));
+	}
+
+	#[test]
+	fn estimate_key_rotation_impact_should_report_friendship_connections_without_keys() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Friendship(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env.clone());
+		let key_pair_raw = StackKeyPair::gen();
+		let resolved_key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(key_pair_raw.clone()), key_id: 0 };
+		let keypair = GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		};
+		let dsnp_user_id = 123;
+		let since = state.time_provider().time_in_ksecs();
+		let bundle = ImportBundleBuilder::new(env, dsnp_user_id, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.with_page(
+				1,
+				&vec![(3, since), (2, since), (4, since)],
+				&vec![
+					DsnpPrid::new(&[1, 2, 3, 4, 5, 6, 7, 8]),
+					DsnpPrid::new(&[2, 2, 3, 4, 5, 6, 7, 8]),
+					DsnpPrid::new(&[3, 2, 3, 4, 5, 6, 7, 8]),
+				],
+				100,
+			)
+			.build();
+		state.import_users_data(&vec![bundle]).expect("should import");
+		state
+			.import_public_keys(
+				&4,
+				vec![DsnpPublicKey {
+					key: StackKeyPair::gen().public_key.to_vec(),
+					key_id: Some(0),
+				}],
+			)
+			.expect("should import keys");
+
+		// act
+		let impact = state.estimate_key_rotation_impact(&dsnp_user_id).expect("should estimate");
+
+		// assert
+		assert_eq!(impact.pages, 1);
+		assert!(impact.bytes > 0);
+		assert_eq!(impact.users_missing_keys, vec![2, 3]);
+		assert!(state.get_public_keys(&2).expect("should get keys").is_empty());
+		assert!(state.force_recalculate_graphs(&dsnp_user_id).is_err());
+	}
+
+	#[test]
+	fn import_public_keys_should_not_commit_or_rollback_changes_of_other_users() {
+		// arrange
+		let mut state = GraphState::new(Environment::Mainnet);
+		let options = Some(ActionOptions { disable_auto_commit: true, ..Default::default() });
+		let key_add_action = Action::AddGraphKey {
+			owner_dsnp_user_id: 1,
+			new_public_key: StackKeyPair::gen().public_key.to_vec(),
+		};
+		state.apply_actions(&vec![key_add_action], &options).expect("should apply");
+		let public_key =
+			DsnpPublicKey { key: StackKeyPair::gen().public_key.to_vec(), key_id: Some(0) };
+
+		// act
+		let failed = state
+			.import_public_keys(&3, vec![DsnpPublicKey { key_id: None, ..public_key.clone() }]);
+		let staged_after_failure = state.get_key_page_stats(&1).expect("should get stats");
+		state
+			.import_public_keys(&2, vec![public_key.clone()])
+			.expect("should import keys");
+		state.rollback();
+
+		// assert
+		assert!(failed.is_err());
+		assert_eq!(staged_after_failure.key_count, 1);
+		assert_eq!(state.get_key_page_stats(&1).expect("should get stats").key_count, 0);
+		assert_eq!(state.get_public_keys(&2).expect("should get keys"), vec![public_key]);
+		assert!(state.get_public_keys(&3).expect("should get keys").is_empty());
+	}
+
 	#[test]
 	fn import_user_data_without_private_keys_should_add_prids_for_private_friendship_graph() {
 		// arrange
@@ -921,16 +5734,20 @@ mod test {
 				keys_hash: 0,
 				dsnp_user_id: owner_dsnp_user_id,
 			}),
+			preferred_page_id: None,
+			since: None,
 		};
 		let connect_action_2 = Action::Connect {
 			owner_dsnp_user_id,
 			connection: Connection { dsnp_user_id: 2, schema_id },
 			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
 		};
 
 		let key_add_action = Action::AddGraphKey {
 			owner_dsnp_user_id,
-			new_public_key: b"27893788291911998228288282".to_vec(),
+			new_public_key: StackKeyPair::gen().public_key.to_vec(),
 		};
 		let mut state = GraphState::new(env);
 
@@ -944,8 +5761,277 @@ mod test {
 
 		// assert
 		assert_eq!(state.user_map.len(), 0);
-		let updates = state.shared_state_manager.write().unwrap().export_new_key_updates();
+		let updates = state.shared_state_manager.write().unwrap().export_new_key_updates(u32::MAX);
 		assert!(updates.is_ok());
 		assert_eq!(updates.unwrap().len(), 0);
 	}
+
+	#[test]
+	fn apply_actions_and_export_should_return_updates_of_applied_actions() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env);
+		let actions = vec![Action::Connect {
+			owner_dsnp_user_id: 1,
+			connection: Connection { dsnp_user_id: 2, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		}];
+
+		// act
+		let updates = state.apply_actions_and_export(&actions, &None);
+
+		// assert
+		let updates = updates.expect("should apply and export");
+		assert_eq!(updates.len(), 1);
+		assert!(matches!(updates[0], Update::PersistPage { owner_dsnp_user_id: 1, .. }));
+		assert_eq!(updates, state.export_updates().expect("should export"));
+	}
+
+	#[test]
+	fn apply_actions_with_progress_should_report_progress_of_each_chunk() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env);
+		let actions: Vec<Action> = (2..=2_501)
+			.map(|dsnp_user_id| Action::Connect {
+				owner_dsnp_user_id: 1,
+				connection: Connection { dsnp_user_id, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			})
+			.collect();
+		let mut progress = vec![];
+
+		// act
+		let result = state
+			.apply_actions_with_progress(&actions, &None, &mut |fraction| progress.push(fraction));
+
+		// assert
+		assert!(result.is_ok());
+		assert_eq!(progress, vec![0.4, 0.8, 1.0]);
+		assert_eq!(
+			state
+				.get_connections_for_user_graph(&1, &schema_id, true)
+				.expect("should get connections")
+				.len(),
+			actions.len()
+		);
+	}
+
+	#[test]
+	fn apply_actions_without_auto_commit_should_validate_all_chunks_before_applying() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env);
+		let mut actions: Vec<Action> = (2..=2_501)
+			.map(|dsnp_user_id| Action::Connect {
+				owner_dsnp_user_id: 1,
+				connection: Connection { dsnp_user_id, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			})
+			.collect();
+		actions.push(Action::Connect {
+			owner_dsnp_user_id: 1,
+			connection: Connection { dsnp_user_id: 0, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		});
+		let options = Some(ActionOptions { disable_auto_commit: true, ..ActionOptions::default() });
+		let mut progress = vec![];
+
+		// act
+		let result = state.apply_actions_with_progress(&actions, &options, &mut |fraction| {
+			progress.push(fraction)
+		});
+
+		// assert
+		assert!(matches!(result, Err(DsnpGraphError::InvalidDsnpUserId(0))));
+		assert!(progress.is_empty());
+		assert!(!state.contains_user_graph(&1));
+	}
+
+	#[test]
+	fn apply_actions_with_progress_should_keep_failing_action_indices_across_chunks() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env);
+		let mut actions: Vec<Action> = (2..=1_501)
+			.map(|dsnp_user_id| Action::Connect {
+				owner_dsnp_user_id: 1,
+				connection: Connection { dsnp_user_id, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			})
+			.collect();
+		actions[1_200] = actions[0].clone();
+		let options = Some(ActionOptions { collect_errors: true, ..Default::default() });
+
+		// act
+		let result = state.apply_actions_with_progress(&actions, &options, &mut |_| {});
+
+		// assert
+		assert!(matches!(
+			result,
+			Err(DsnpGraphError::ActionsFailed(failed))
+				if failed.len() == 1 && failed[0].0 == 1_200
+		));
+		assert_eq!(state.user_map.len(), 0);
+	}
+
+	#[test]
+	fn apply_actions_exceeding_max_batch_size_should_fail_without_applying_any_action() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env);
+		let actions: Vec<Action> = (2..=4)
+			.map(|dsnp_user_id| Action::Connect {
+				owner_dsnp_user_id: 1,
+				connection: Connection { dsnp_user_id, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			})
+			.collect();
+		let options = Some(ActionOptions {
+			max_batch_size: NonZeroU32::new(2),
+			disable_auto_commit: true,
+			..Default::default()
+		});
+		let mut progress = vec![];
+
+		// act
+		let result = state.apply_actions_with_progress(&actions, &options, &mut |fraction| {
+			progress.push(fraction)
+		});
+
+		// assert
+		assert!(matches!(result, Err(DsnpGraphError::ActionBatchTooLarge(3, 2))));
+		assert!(progress.is_empty());
+		assert_eq!(state.user_map.len(), 0);
+		assert!(state.apply_actions(&actions[..2], &options).is_ok());
+	}
+
+	#[test]
+	fn apply_actions_and_export_with_export_failure_should_rollback_every_action() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+			.expect("should exist");
+		let mut state = GraphState::new(env);
+		// no encryption key is imported for the owner so the private page can not be exported
+		let actions = vec![Action::Connect {
+			owner_dsnp_user_id: 1,
+			connection: Connection { dsnp_user_id: 2, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		}];
+
+		// act
+		let updates = state.apply_actions_and_export(&actions, &None);
+
+		// assert
+		assert!(matches!(updates, Err(DsnpGraphError::NoResolvedActiveKeyFound)));
+		assert_eq!(state.user_map.len(), 0);
+	}
+
+	#[test]
+	fn api_add_graph_key_with_malformed_public_key_should_fail() {
+		// arrange
+		let mut state = GraphState::new(Environment::Mainnet);
+		let key_add_action = Action::AddGraphKey {
+			owner_dsnp_user_id: 1,
+			new_public_key: b"27893788291911998228288282".to_vec(),
+		};
+
+		// act
+		let res = state.apply_actions(&vec![key_add_action], &None);
+
+		// assert
+		assert!(matches!(res, Err(DsnpGraphError::InvalidPublicKey)));
+		let updates = state.shared_state_manager.write().unwrap().export_new_key_updates(u32::MAX);
+		assert_eq!(updates.unwrap().len(), 0);
+	}
+
+	#[test]
+	fn api_get_next_key_id_should_match_pending_key() {
+		// arrange
+		let mut state = GraphState::new(Environment::Mainnet);
+		let key_add_action = Action::AddGraphKey {
+			owner_dsnp_user_id: 1,
+			new_public_key: StackKeyPair::gen().public_key.to_vec(),
+		};
+
+		// act
+		let before = state.get_next_key_id(&1);
+		state.apply_actions(&vec![key_add_action], &None).expect("should apply");
+
+		// assert
+		assert_eq!(before.unwrap(), 0);
+		assert_eq!(state.get_next_key_id(&1).unwrap(), 0);
+	}
+
+	#[test]
+	fn preview_add_key_should_return_exported_update_without_staging_key() {
+		// arrange
+		let mut state = GraphState::new(Environment::Mainnet);
+		let public_key = StackKeyPair::gen().public_key.to_vec();
+		let hash_before = state.state_hash().expect("should hash");
+
+		// act
+		let preview = state.preview_add_key(&1, public_key.clone()).expect("should preview");
+
+		// assert
+		assert_eq!(state.state_hash().expect("should hash"), hash_before);
+		assert_eq!(state.get_key_page_stats(&1).expect("should get stats").key_count, 0);
+		assert_eq!(preview.key_id, 0);
+		assert_eq!(preview.used_bytes, preview.item_bytes);
+		let key_add_action =
+			Action::AddGraphKey { owner_dsnp_user_id: 1, new_public_key: public_key };
+		state.apply_actions(&vec![key_add_action], &None).expect("should apply");
+		let stats = state.get_key_page_stats(&1).expect("should get stats");
+		assert_eq!((stats.used_bytes, stats.max_bytes), (preview.used_bytes, preview.max_bytes));
+		assert_eq!(state.export_updates().expect("should export"), vec![preview.update]);
+	}
+
+	#[test]
+	fn preview_add_key_with_malformed_public_key_should_fail() {
+		// arrange
+		let state = GraphState::new(Environment::Mainnet);
+
+		// act
+		let res = state.preview_add_key(&1, b"27893788291911998228288282".to_vec());
+
+		// assert
+		assert!(matches!(res, Err(DsnpGraphError::InvalidPublicKey)));
+	}
 }
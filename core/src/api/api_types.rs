@@ -1,11 +1,24 @@
 //! Different structs and types used in API
-use crate::dsnp::{dsnp_configs::KeyPairType, dsnp_types::DsnpUserId};
+use crate::{
+	dsnp::{
+		dsnp_configs::{validate_public_key, KeyPairType},
+		dsnp_types::DsnpUserId,
+	},
+	frequency::reader_writer::{PageHasher, XxHash32},
+	util::{
+		key_format::{
+			decode_base58, decode_base64_url, encode_base58, encode_base64_url,
+			BASE58_BTC_MULTIBASE_PREFIX, X25519_PUB_MULTICODEC,
+		},
+		state_hash::{StateHasher, STATE_HASH_BYTES},
+	},
+};
 use dsnp_graph_config::{
 	errors::{
 		DsnpGraphError::{
 			InvalidDsnpUserId, InvalidInput, InvalidPublicKey, InvalidSchemaId, InvalidSecretKey,
 		},
-		DsnpGraphResult,
+		DsnpGraphResult, SchemaIdContext,
 	},
 	GraphKeyType, InputValidation, SchemaId,
 };
@@ -13,7 +26,12 @@ pub use dsnp_graph_config::{ConnectionType, PageId, PrivacyType};
 use log::Level;
 use log_result_proc_macro::log_result_err;
 use serde::{Deserialize, Serialize};
-use std::{cmp::Ordering, collections::HashSet, fmt::Debug};
+use std::{
+	cmp::Ordering,
+	collections::{BTreeMap, HashSet},
+	fmt::Debug,
+	num::NonZeroU32,
+};
 
 /// Page Hash type
 pub type PageHash = u32;
@@ -27,6 +45,7 @@ pub struct PageData {
 
 	/// raw content of page data
 	#[serde(rename = "content")]
+	#[cfg_attr(feature = "serde", serde(with = "crate::util::serde_base64"))]
 	pub content: Vec<u8>,
 
 	/// hash value of content
@@ -36,6 +55,33 @@ pub struct PageData {
 
 /// implementing input validation for Page Data
 impl InputValidation for PageData {
+	#[log_result_err(Level::Info)]
+	fn validate(&self) -> DsnpGraphResult<()> {
+		PageDataRef::from(self).validate()
+	}
+}
+
+/// Borrowed raw page of Graph data, which allows importing pages without copying their content
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PageDataRef<'a> {
+	/// Id of the page
+	pub page_id: PageId,
+
+	/// raw content of page data
+	pub content: &'a [u8],
+
+	/// hash value of content
+	pub content_hash: PageHash,
+}
+
+impl<'a> From<&'a PageData> for PageDataRef<'a> {
+	fn from(PageData { page_id, content, content_hash }: &'a PageData) -> Self {
+		Self { page_id: *page_id, content, content_hash: *content_hash }
+	}
+}
+
+/// implementing input validation for borrowed Page Data
+impl InputValidation for PageDataRef<'_> {
 	#[log_result_err(Level::Info)]
 	fn validate(&self) -> DsnpGraphResult<()> {
 		if self.content.len() > 0 && self.content_hash == PageHash::default() {
@@ -58,6 +104,7 @@ pub struct KeyData {
 
 	/// raw content of key data
 	#[serde(rename = "content")]
+	#[cfg_attr(feature = "serde", serde(with = "crate::util::serde_base64"))]
 	pub content: Vec<u8>,
 }
 
@@ -81,13 +128,93 @@ pub struct GraphKeyPair {
 
 	/// public key raw
 	#[serde(rename = "publicKey")]
+	#[cfg_attr(feature = "serde", serde(with = "crate::util::serde_base64"))]
 	pub public_key: Vec<u8>,
 
 	/// secret key raw
 	#[serde(rename = "secretKey")]
+	#[cfg_attr(feature = "serde", serde(with = "crate::util::serde_base64"))]
 	pub secret_key: Vec<u8>,
 }
 
+/// Public key in JSON Web Key format as defined in RFC 8037
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PublicKeyJwk {
+	/// key type which is `OKP` for X25519 keys
+	pub kty: String,
+
+	/// curve of the key which is `X25519` for X25519 keys
+	pub crv: String,
+
+	/// url safe base64 encoded raw public key
+	pub x: String,
+}
+
+impl GraphKeyPair {
+	/// exports the public key as raw bytes after validating it
+	pub fn public_key_to_raw(&self) -> DsnpGraphResult<Vec<u8>> {
+		validate_public_key(self.key_type.clone(), &self.public_key)?;
+		Ok(self.public_key.clone())
+	}
+
+	/// exports the public key as a JWK
+	pub fn public_key_to_jwk(&self) -> DsnpGraphResult<PublicKeyJwk> {
+		let raw = self.public_key_to_raw()?;
+		match self.key_type {
+			GraphKeyType::X25519 => Ok(PublicKeyJwk {
+				kty: "OKP".to_string(),
+				crv: "X25519".to_string(),
+				x: encode_base64_url(&raw),
+			}),
+		}
+	}
+
+	/// exports the public key as a base58btc multibase string of the multicodec prefixed key
+	pub fn public_key_to_multibase(&self) -> DsnpGraphResult<String> {
+		let raw = self.public_key_to_raw()?;
+		let prefixed = match self.key_type {
+			GraphKeyType::X25519 => [&X25519_PUB_MULTICODEC[..], &raw].concat(),
+		};
+		Ok(format!("{}{}", BASE58_BTC_MULTIBASE_PREFIX, encode_base58(&prefixed)))
+	}
+
+	/// imports a raw public key of the provided type after validating it
+	pub fn public_key_from_raw(key_type: GraphKeyType, raw: &[u8]) -> DsnpGraphResult<Vec<u8>> {
+		validate_public_key(key_type, raw)?;
+		Ok(raw.to_vec())
+	}
+
+	/// imports a public key from a JWK and returns its type and raw bytes
+	pub fn public_key_from_jwk(jwk: &PublicKeyJwk) -> DsnpGraphResult<(GraphKeyType, Vec<u8>)> {
+		let key_type = match (jwk.kty.as_str(), jwk.crv.as_str()) {
+			("OKP", "X25519") => GraphKeyType::X25519,
+			(kty, crv) =>
+				return Err(InvalidInput(format!(
+					"Unsupported JWK key type {} and curve {}",
+					kty, crv
+				))),
+		};
+		let raw = decode_base64_url(&jwk.x)
+			.ok_or(InvalidInput("JWK public key is not valid base64url".to_string()))?;
+		let raw = Self::public_key_from_raw(key_type.clone(), &raw)?;
+		Ok((key_type, raw))
+	}
+
+	/// imports a public key from a base58btc multibase string and returns its type and raw bytes
+	pub fn public_key_from_multibase(encoded: &str) -> DsnpGraphResult<(GraphKeyType, Vec<u8>)> {
+		let prefixed = encoded
+			.strip_prefix(BASE58_BTC_MULTIBASE_PREFIX)
+			.and_then(decode_base58)
+			.ok_or(InvalidInput("Public key is not a base58btc multibase string".to_string()))?;
+		let (key_type, raw) = match prefixed.strip_prefix(&X25519_PUB_MULTICODEC[..]) {
+			Some(raw) => (GraphKeyType::X25519, raw),
+			None => return Err(InvalidInput("Unsupported multicodec public key type".to_string())),
+		};
+		let raw = Self::public_key_from_raw(key_type.clone(), raw)?;
+		Ok((key_type, raw))
+	}
+}
+
 /// implementing input validation for import bundle
 impl InputValidation for GraphKeyPair {
 	#[log_result_err(Level::Info)]
@@ -108,7 +235,7 @@ pub struct ResolvedKeyPair {
 	/// Key identifier
 	pub key_id: u64,
 
-	/// Public key
+	/// Key pair, or the handle performing the operations of its secret key
 	pub key_pair: KeyPairType,
 }
 
@@ -134,17 +261,87 @@ pub struct ImportBundle {
 	/// Page data containing the social graph retrieved from chain
 	#[serde(rename = "pages")]
 	pub pages: Vec<PageData>,
+
+	/// optional block at which the pages and keys were read from chain, which is attached to every
+	/// exported update of the graph or key page so stale updates can be detected before submitting
+	#[serde(rename = "chainHead", default)]
+	pub chain_head: Option<ChainHead>,
+}
+
+/// Block of Frequency chain at which the imported data was read, which is attached to the exported
+/// updates so that updates computed against a stale chain head can be detected
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainHead {
+	/// block number
+	#[serde(rename = "blockNumber")]
+	pub block_number: u32,
+
+	/// block hash
+	#[serde(rename = "blockHash")]
+	#[cfg_attr(feature = "serde", serde(with = "crate::util::serde_base64"))]
+	pub block_hash: Vec<u8>,
 }
 
 /// implementing input validation for import bundle
 impl InputValidation for ImportBundle {
+	#[log_result_err(Level::Info)]
+	fn validate(&self) -> DsnpGraphResult<()> {
+		ImportBundleRef::from(self).validate()
+	}
+
+	fn dsnp_user_ids(&self) -> Vec<DsnpUserId> {
+		ImportBundleRef::from(self).dsnp_user_ids()
+	}
+}
+
+/// Version of `ImportBundle` which borrows the page contents instead of owning them. Used to
+/// import large graphs without copying the pages received from the host.
+#[derive(Debug, Clone)]
+pub struct ImportBundleRef<'a> {
+	/// graph owner dsnp user id
+	pub dsnp_user_id: DsnpUserId,
+
+	/// Schema id of imported data
+	pub schema_id: SchemaId,
+
+	/// key pairs associated with this graph which is used for encryption and PRI generation
+	pub key_pairs: Vec<GraphKeyPair>,
+
+	/// published dsnp keys associated with this dsnp user
+	pub dsnp_keys: Option<DsnpKeys>,
+
+	/// Borrowed page data containing the social graph retrieved from chain
+	pub pages: Vec<PageDataRef<'a>>,
+
+	/// optional block at which the pages and keys were read from chain
+	pub chain_head: Option<ChainHead>,
+}
+
+impl<'a> From<&'a ImportBundle> for ImportBundleRef<'a> {
+	fn from(bundle: &'a ImportBundle) -> Self {
+		Self {
+			dsnp_user_id: bundle.dsnp_user_id,
+			schema_id: bundle.schema_id,
+			key_pairs: bundle.key_pairs.clone(),
+			dsnp_keys: bundle.dsnp_keys.clone(),
+			pages: bundle.pages.iter().map(PageDataRef::from).collect(),
+			chain_head: bundle.chain_head.clone(),
+		}
+	}
+}
+
+/// implementing input validation for borrowed import bundle
+impl InputValidation for ImportBundleRef<'_> {
 	#[log_result_err(Level::Info)]
 	fn validate(&self) -> DsnpGraphResult<()> {
 		if self.dsnp_user_id == 0 {
 			return DsnpGraphResult::Err(InvalidDsnpUserId(self.dsnp_user_id));
 		}
 		if self.schema_id == 0 && self.pages.len() > 0 {
-			return DsnpGraphResult::Err(InvalidSchemaId(self.schema_id));
+			return DsnpGraphResult::Err(InvalidSchemaId(
+				self.schema_id,
+				SchemaIdContext::default(),
+			));
 		}
 
 		for k in &self.key_pairs {
@@ -169,6 +366,14 @@ impl InputValidation for ImportBundle {
 
 		Ok(())
 	}
+
+	fn dsnp_user_ids(&self) -> Vec<DsnpUserId> {
+		let mut ids = vec![self.dsnp_user_id];
+		if let Some(dsnp_keys) = &self.dsnp_keys {
+			ids.extend(dsnp_keys.dsnp_user_ids());
+		}
+		ids
+	}
 }
 
 /// Encapsulates a dsnp user and their associated graph public keys
@@ -216,6 +421,10 @@ impl InputValidation for DsnpKeys {
 
 		Ok(())
 	}
+
+	fn dsnp_user_ids(&self) -> Vec<DsnpUserId> {
+		vec![self.dsnp_user_id]
+	}
 }
 
 /// A connection representation in graph sdk
@@ -239,11 +448,697 @@ impl InputValidation for Connection {
 			return DsnpGraphResult::Err(InvalidDsnpUserId(self.dsnp_user_id));
 		}
 		if self.schema_id == 0 {
-			return DsnpGraphResult::Err(InvalidSchemaId(self.schema_id));
+			return DsnpGraphResult::Err(InvalidSchemaId(
+				self.schema_id,
+				SchemaIdContext::default(),
+			));
 		}
 
 		Ok(())
 	}
+
+	fn dsnp_user_ids(&self) -> Vec<DsnpUserId> {
+		vec![self.dsnp_user_id]
+	}
+}
+
+/// Key count and utilization of the key page of a user
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyPageStats {
+	/// number of imported and newly added keys
+	#[serde(rename = "keyCount")]
+	pub key_count: u32,
+
+	/// bytes used by all keys inside the itemized key page
+	#[serde(rename = "usedBytes")]
+	pub used_bytes: u32,
+
+	/// maximum allowed size of the key page
+	#[serde(rename = "maxBytes")]
+	pub max_bytes: u32,
+}
+
+/// Itemized actions that rewrite the key page of a user with only its unique keys
+///
+/// Frequency stores the keys as items of an itemized page where the id of a key is the index of
+/// its item, so deleting an item shifts the ids of all later keys. The deletions are sorted in
+/// descending order so they can be applied in a single call without affecting each other.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyPageCompaction {
+	/// owner of the key page
+	#[serde(rename = "ownerDsnpUserId")]
+	pub owner_dsnp_user_id: DsnpUserId,
+
+	/// previous hash value is used to avoid updating a stale key page
+	#[serde(rename = "prevHash")]
+	pub prev_hash: PageHash,
+
+	/// itemized indices of the redundant keys to delete, in descending order
+	#[serde(rename = "deleteIndices")]
+	pub delete_indices: Vec<u16>,
+
+	/// ids of the kept keys in their order after compaction, the new id of each key is its
+	/// position in this list
+	#[serde(rename = "remainingKeyIds")]
+	pub remaining_key_ids: Vec<u64>,
+
+	/// block at which the imported keys this compaction is based on were read
+	#[serde(rename = "chainHead", default, skip_serializing_if = "Option::is_none")]
+	pub chain_head: Option<ChainHead>,
+}
+
+/// Estimated memory used by a graph state, which can be used for admission control when running
+/// many graph states concurrently
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryReport {
+	/// estimated bytes used by each imported user graph and its key pairs, sorted by user id
+	#[serde(rename = "userGraphBytes")]
+	pub user_graph_bytes: Vec<(DsnpUserId, usize)>,
+
+	/// estimated bytes used by shared public keys and PRIds
+	#[serde(rename = "sharedStateBytes")]
+	pub shared_state_bytes: usize,
+
+	/// estimated bytes used by pending updates and newly added keys of all users
+	#[serde(rename = "pendingUpdateBytes")]
+	pub pending_update_bytes: usize,
+}
+
+impl MemoryReport {
+	/// returns the estimated bytes used by the whole graph state
+	pub fn total_bytes(&self) -> usize {
+		self.user_graph_bytes.iter().map(|(_, bytes)| bytes).sum::<usize>() +
+			self.shared_state_bytes +
+			self.pending_update_bytes
+	}
+}
+
+/// Operations timed by the instrumentation of a graph state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GraphOperation {
+	/// importing the data of a user graph
+	#[serde(rename = "import")]
+	Import,
+
+	/// exporting the updates of a user graph
+	#[serde(rename = "export")]
+	Export,
+
+	/// applying an action on a user graph
+	#[serde(rename = "applyAction")]
+	ApplyAction,
+}
+
+/// Aggregated durations of a timed operation in microseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct OperationTimings {
+	/// number of timed operations
+	pub count: usize,
+
+	/// sum of all durations
+	#[serde(rename = "totalMicros")]
+	pub total_micros: u64,
+
+	/// shortest duration
+	#[serde(rename = "minMicros")]
+	pub min_micros: u64,
+
+	/// longest duration
+	#[serde(rename = "maxMicros")]
+	pub max_micros: u64,
+
+	/// median duration
+	#[serde(rename = "p50Micros")]
+	pub p50_micros: u64,
+
+	/// 90th percentile duration
+	#[serde(rename = "p90Micros")]
+	pub p90_micros: u64,
+
+	/// 99th percentile duration
+	#[serde(rename = "p99Micros")]
+	pub p99_micros: u64,
+}
+
+/// Timings recorded by the instrumentation of a graph state since the last time they were taken
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct GraphMetrics {
+	/// timings of each operation across all users
+	pub operations: BTreeMap<GraphOperation, OperationTimings>,
+
+	/// timings of each operation per user
+	pub users: BTreeMap<DsnpUserId, BTreeMap<GraphOperation, OperationTimings>>,
+}
+
+/// Outcome of an action recorded in the audit log of a graph state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditResolution {
+	/// the action updated the graph and the update was committed
+	#[serde(rename = "applied")]
+	Applied,
+
+	/// the action was skipped due to `ActionOptions` and did not update the graph
+	#[serde(rename = "ignored")]
+	Ignored,
+
+	/// the action failed and did not update the graph
+	#[serde(rename = "failed")]
+	Failed,
+
+	/// the action updated the graph but the update was rolled back
+	#[serde(rename = "rolledBack")]
+	RolledBack,
+}
+
+/// Action applied on a graph state along with its outcome
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+	/// time the action was applied in seconds since EPOCH
+	#[serde(rename = "timestampSecs")]
+	pub timestamp_secs: u64,
+
+	/// applied action
+	pub action: Action,
+
+	/// outcome of the action
+	pub resolution: AuditResolution,
+
+	/// error of a failed action
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+/// Page a pending connection will be stored in once the updates are exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionPlacement {
+	/// dsnp user id of the added connection
+	#[serde(rename = "dsnpUserId")]
+	pub dsnp_user_id: DsnpUserId,
+
+	/// Schema id of the graph containing the connection
+	#[serde(rename = "schemaId")]
+	pub schema_id: SchemaId,
+
+	/// page id the connection landed in
+	#[serde(rename = "pageId")]
+	pub page_id: PageId,
+
+	/// page id requested by the `Connect` action if any
+	#[serde(rename = "preferredPageId")]
+	pub preferred_page_id: Option<PageId>,
+}
+
+impl ConnectionPlacement {
+	/// returns true if the connection landed in the preferred page
+	pub fn is_preferred_page_honored(&self) -> bool {
+		self.preferred_page_id == Some(self.page_id)
+	}
+}
+
+/// Result of exporting a graph and importing the exported pages back into a scratch state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundtripReport {
+	/// owner of the verified social graph
+	#[serde(rename = "dsnpUserId")]
+	pub dsnp_user_id: DsnpUserId,
+
+	/// Schema id of the verified graph
+	#[serde(rename = "schemaId")]
+	pub schema_id: SchemaId,
+
+	/// number of non-empty pages in the exported graph
+	#[serde(rename = "exportedPages")]
+	pub exported_pages: usize,
+
+	/// number of connections expected after applying the pending updates
+	#[serde(rename = "expectedConnections")]
+	pub expected_connections: usize,
+
+	/// expected connections which are not found after re-importing the exported pages
+	#[serde(rename = "missingConnections")]
+	pub missing_connections: Vec<DsnpUserId>,
+
+	/// connections found after re-importing the exported pages which were not expected
+	#[serde(rename = "unexpectedConnections")]
+	pub unexpected_connections: Vec<DsnpUserId>,
+}
+
+impl RoundtripReport {
+	/// returns true if the re-imported connections match the expected ones
+	pub fn is_consistent(&self) -> bool {
+		self.missing_connections.is_empty() && self.unexpected_connections.is_empty()
+	}
+}
+
+/// Estimated impact of publishing a new key for a user, which requires rewriting all the pages of
+/// the private graphs of the user using the new key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationImpact {
+	/// number of private graph pages which would be rewritten
+	#[serde(rename = "pages")]
+	pub pages: usize,
+
+	/// total size of the payloads of the rewritten pages in bytes
+	#[serde(rename = "bytes")]
+	pub bytes: usize,
+
+	/// private friendship connections without any published key, whose PRIds can not be
+	/// calculated until they publish one
+	#[serde(rename = "usersMissingKeys")]
+	pub users_missing_keys: Vec<DsnpUserId>,
+}
+
+/// Result of removing an imported key pair of a user, revalidating that the remaining key pairs
+/// can still decrypt the imported private pages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRemovalReport {
+	/// owner of the removed key pair
+	#[serde(rename = "dsnpUserId")]
+	pub dsnp_user_id: DsnpUserId,
+
+	/// true if the removed key pair belonged to the active key of the user, which is required to
+	/// encrypt exported private pages
+	#[serde(rename = "activeKeyRemoved")]
+	pub active_key_removed: bool,
+
+	/// imported private pages of each schema which none of the remaining key pairs can decrypt,
+	/// sorted by page id
+	#[serde(rename = "undecryptablePages")]
+	pub undecryptable_pages: BTreeMap<SchemaId, Vec<PageId>>,
+}
+
+impl KeyRemovalReport {
+	/// returns true if the remaining key pairs can still decrypt and export all imported pages
+	pub fn is_safe(&self) -> bool {
+		!self.active_key_removed && self.undecryptable_pages.is_empty()
+	}
+}
+
+/// A connection which was imported on more than one page of a graph and removed from all of them
+/// except the page with the lowest id, or imported more than once within the same page and removed
+/// from it except its first occurrence when `ImportOptions::dedupe_within_pages` is set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionRepair {
+	/// owner of the social graph
+	#[serde(rename = "dsnpUserId")]
+	pub dsnp_user_id: DsnpUserId,
+
+	/// Schema id of the graph
+	#[serde(rename = "schemaId")]
+	pub schema_id: SchemaId,
+
+	/// the duplicated connection
+	#[serde(rename = "connectionId")]
+	pub connection_id: DsnpUserId,
+
+	/// page which keeps the connection, which is the repaired page itself for duplicates within a
+	/// single page
+	#[serde(rename = "keptPageId")]
+	pub kept_page_id: PageId,
+
+	/// page which the connection was removed from, which is rewritten on the next export
+	#[serde(rename = "repairedPageId")]
+	pub repaired_page_id: PageId,
+}
+
+/// Result of importing user data, listing the repairs applied to inconsistent chain data
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportReport {
+	/// duplicated connections that were removed from the imported pages, in the order of the
+	/// imported bundles and page ids
+	#[serde(rename = "repairs")]
+	pub repairs: Vec<ConnectionRepair>,
+}
+
+impl ImportReport {
+	/// returns true if the imported data did not need any repairs
+	pub fn is_clean(&self) -> bool {
+		self.repairs.is_empty()
+	}
+
+	/// returns the number of duplicated connections removed from within a single page
+	pub fn within_page_repairs(&self) -> usize {
+		self.repairs
+			.iter()
+			.filter(|repair| repair.kept_page_id == repair.repaired_page_id)
+			.count()
+	}
+
+	/// returns the number of duplicated connections removed since they were kept on another page
+	pub fn across_page_repairs(&self) -> usize {
+		self.repairs.len() - self.within_page_repairs()
+	}
+}
+
+/// Severity of a graph lint issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LintSeverity {
+	/// content is valid but not written the way the SDK would write it
+	Warning,
+
+	/// content violates the DSNP spec or the chain limits
+	Error,
+}
+
+/// Spec level rules checked when linting a graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LintRule {
+	/// a connection is stored more than once in the graph
+	DuplicateConnection,
+
+	/// connections of a page are not sorted by their dsnp user id
+	UnsortedConnections,
+
+	/// PRIds are stored in a page of a graph other than private friendship
+	UnexpectedPrids,
+
+	/// number of PRIds of a private friendship page does not match its connections
+	MissingPrids,
+
+	/// `since` timestamp of a connection is in the future
+	FutureTimestamp,
+
+	/// serialized page is larger than the maximum graph page size allowed on chain
+	PageTooLarge,
+
+	/// serialized page is larger than the configured maximum update payload size
+	PageExceedsUpdatePayload,
+}
+
+impl LintRule {
+	/// returns the severity of issues violating this rule
+	pub fn severity(&self) -> LintSeverity {
+		match self {
+			LintRule::UnsortedConnections | LintRule::PageExceedsUpdatePayload =>
+				LintSeverity::Warning,
+			_ => LintSeverity::Error,
+		}
+	}
+}
+
+/// A single violation of a lint rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintIssue {
+	/// Schema id of the graph containing the issue
+	#[serde(rename = "schemaId")]
+	pub schema_id: SchemaId,
+
+	/// page containing the issue
+	#[serde(rename = "pageId")]
+	pub page_id: PageId,
+
+	/// violated rule
+	#[serde(rename = "rule")]
+	pub rule: LintRule,
+
+	/// severity of the issue
+	#[serde(rename = "severity")]
+	pub severity: LintSeverity,
+
+	/// human readable description of the issue
+	#[serde(rename = "message")]
+	pub message: String,
+}
+
+impl LintIssue {
+	/// creates a new issue with the severity of the violated rule
+	pub fn new(schema_id: SchemaId, page_id: PageId, rule: LintRule, message: String) -> Self {
+		Self { schema_id, page_id, rule, severity: rule.severity(), message }
+	}
+}
+
+/// Result of linting the imported graphs of a user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintReport {
+	/// owner of the linted social graphs
+	#[serde(rename = "dsnpUserId")]
+	pub dsnp_user_id: DsnpUserId,
+
+	/// found issues sorted by schema id and page id
+	#[serde(rename = "issues")]
+	pub issues: Vec<LintIssue>,
+}
+
+impl LintReport {
+	/// returns the issues which violate the DSNP spec or the chain limits
+	pub fn errors(&self) -> Vec<&LintIssue> {
+		self.issues.iter().filter(|i| i.severity == LintSeverity::Error).collect()
+	}
+
+	/// returns the issues which are valid but not written the way the SDK would write them
+	pub fn warnings(&self) -> Vec<&LintIssue> {
+		self.issues.iter().filter(|i| i.severity == LintSeverity::Warning).collect()
+	}
+
+	/// returns true if any of the issues is an error
+	pub fn has_errors(&self) -> bool {
+		self.issues.iter().any(|i| i.severity == LintSeverity::Error)
+	}
+}
+
+/// Behavior when added connections do not fit into the pages allowed for a graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PageOverflowPolicy {
+	/// fails the export with `GraphCapacityExceeded` error
+	#[default]
+	Error,
+
+	/// exports the connections that fit and leaves the overflowing ones out of the exported
+	/// pages. They stay pending so they can be exported after some capacity is freed.
+	SkipOverflow,
+}
+
+/// Strategy used to generate the encryption randomness of exported private pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NonceMode {
+	/// uses a new random ephemeral key and nonce for every export, so exporting the same page
+	/// twice results in different payloads
+	#[default]
+	Random,
+
+	/// derives the ephemeral key and nonce via HKDF from the secret key, key id, page id,
+	/// page content hash and the exported content. Retrying the same export results in the same
+	/// payloads which makes retries idempotent.
+	///
+	/// Only use it when idempotent retries are required since identical content exported on top
+	/// of the same page version becomes linkable by its payload.
+	Deterministic,
+}
+
+/// Encryption used for exported private pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EncryptionMode {
+	/// seals the pages to the active key of the user
+	#[default]
+	SingleRecipient,
+
+	/// seals the pages to the active key and the additional recipient keys of the user, so they
+	/// can be decrypted by any of them, such as a recovery key. Only supports `NonceMode::Random`
+	/// and fails with `EncryptionError` if no additional recipient keys are set
+	MultiRecipientV1,
+}
+
+/// Options used when exporting the updates of a graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ExportOptions {
+	/// behavior when added connections do not fit into the pages allowed for a graph
+	#[serde(rename = "pageOverflowPolicy")]
+	pub page_overflow_policy: PageOverflowPolicy,
+
+	/// strategy used to generate the encryption randomness of private pages
+	#[serde(rename = "nonceMode")]
+	pub nonce_mode: NonceMode,
+
+	/// recipients that exported private pages are sealed to
+	#[serde(rename = "encryptionMode", default)]
+	pub encryption_mode: EncryptionMode,
+}
+
+/// Options used when importing the pages of a graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ImportOptions {
+	/// verifies each imported PRId of private friendship graphs against the connection at the same
+	/// position when the secret key of the page and the public key of the connection are available,
+	/// failing the import with `PridsMisaligned` which lists the mismatched indexes of the page
+	#[serde(rename = "strictPridValidation")]
+	pub strict_prid_validation: bool,
+
+	/// removes connections which are stored more than once within the same imported page,
+	/// keeping their first occurrence, and reports them as repairs of the import
+	#[serde(rename = "dedupeWithinPages", default)]
+	pub dedupe_within_pages: bool,
+
+	/// recalculates the hash of each imported page using the hashing rules of the chain adapter
+	/// and fails the import with `PageContentHashMismatch` if it differs from `content_hash`,
+	/// which detects pages corrupted in transit instead of trusting the hash until it is used as
+	/// the `prev_hash` of an exported update
+	#[serde(rename = "verifyContentHashes", default)]
+	pub verify_content_hashes: bool,
+
+	/// highest MSA id issued on chain, which fails importing bundles of users or keys with a
+	/// higher dsnp user id using `InvalidDsnpUserId`, rejecting malformed indexer data
+	#[serde(rename = "maxDsnpUserId", default)]
+	pub max_dsnp_user_id: Option<DsnpUserId>,
+
+	/// only replaces the pages with the provided page ids and keeps the other imported pages of
+	/// the graph, so indexers can import only the pages changed by chain events. Deleted pages
+	/// still require importing all the pages of the graph
+	#[serde(rename = "mergePages", default)]
+	pub merge_pages: bool,
+}
+
+/// Estimated capacity of a graph which can be used to check if added connections would fit
+/// before applying them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapacityReport {
+	/// owner of the social graph
+	#[serde(rename = "dsnpUserId")]
+	pub dsnp_user_id: DsnpUserId,
+
+	/// Schema id of the graph
+	#[serde(rename = "schemaId")]
+	pub schema_id: SchemaId,
+
+	/// maximum number of pages allowed for the graph
+	#[serde(rename = "maxPages")]
+	pub max_pages: usize,
+
+	/// number of imported pages, including the private pages which could not be decrypted
+	#[serde(rename = "usedPages")]
+	pub used_pages: usize,
+
+	/// imported pages which have reached the estimated page capacity, including the private
+	/// pages which could not be decrypted since their connections are unknown
+	#[serde(rename = "fullPageIds")]
+	pub full_page_ids: Vec<PageId>,
+
+	/// number of imported connections
+	#[serde(rename = "connections")]
+	pub connections: usize,
+
+	/// number of connections pending to be added
+	#[serde(rename = "pendingAdditions")]
+	pub pending_additions: usize,
+
+	/// number of connections pending to be removed
+	#[serde(rename = "pendingRemovals")]
+	pub pending_removals: usize,
+
+	/// estimated number of connections that can still be added after applying pending updates
+	#[serde(rename = "remainingConnections")]
+	pub remaining_connections: usize,
+
+	/// estimated number of pending added connections that would not fit into the graph
+	#[serde(rename = "connectionsOver")]
+	pub connections_over: usize,
+}
+
+impl CapacityReport {
+	/// returns true if pending added connections are expected to overflow the graph
+	pub fn is_over_capacity(&self) -> bool {
+		self.connections_over > 0
+	}
+}
+
+/// Metadata of an imported page which could not be decrypted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageMetadata {
+	/// Id of the page
+	#[serde(rename = "pageId")]
+	pub page_id: PageId,
+
+	/// size of the encrypted page content in bytes
+	#[serde(rename = "contentSize")]
+	pub content_size: usize,
+
+	/// hash value of content
+	#[serde(rename = "contentHash")]
+	pub content_hash: PageHash,
+}
+
+impl<'a> From<PageDataRef<'a>> for PageMetadata {
+	fn from(PageDataRef { page_id, content, content_hash }: PageDataRef<'a>) -> Self {
+		Self { page_id, content_size: content.len(), content_hash }
+	}
+}
+
+/// Statistics of an imported graph, which are also available for private graphs imported without
+/// their secret keys
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserGraphStats {
+	/// owner of the social graph
+	#[serde(rename = "dsnpUserId")]
+	pub dsnp_user_id: DsnpUserId,
+
+	/// Schema id of the graph
+	#[serde(rename = "schemaId")]
+	pub schema_id: SchemaId,
+
+	/// number of imported and decrypted pages
+	#[serde(rename = "decryptedPages")]
+	pub decrypted_pages: usize,
+
+	/// number of imported connections
+	#[serde(rename = "connections")]
+	pub connections: usize,
+
+	/// content hash of every imported page, including the encrypted ones, which can be compared
+	/// against the chain to detect stale pages
+	#[serde(rename = "pageHashes")]
+	pub page_hashes: BTreeMap<PageId, PageHash>,
+
+	/// pages which were imported without the secret keys to decrypt them, sorted by page id
+	#[serde(rename = "encryptedPages")]
+	pub encrypted_pages: Vec<PageMetadata>,
+}
+
+impl UserGraphStats {
+	/// returns true if only the metadata of the pages is imported since keys are not provided
+	pub fn is_metadata_only(&self) -> bool {
+		self.decrypted_pages == 0 && !self.encrypted_pages.is_empty()
+	}
+}
+
+/// Connections added and removed by the imported pages of a graph since an older sync, identified
+/// by the content hashes of its pages
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangesReport {
+	/// owner of the social graph
+	#[serde(rename = "dsnpUserId")]
+	pub dsnp_user_id: DsnpUserId,
+
+	/// Schema id of the graph
+	#[serde(rename = "schemaId")]
+	pub schema_id: SchemaId,
+
+	/// connections which were added since the sync, sorted by dsnp user id
+	#[serde(rename = "added")]
+	pub added: Vec<DsnpUserId>,
+
+	/// connections which were removed since the sync, sorted by dsnp user id
+	#[serde(rename = "removed")]
+	pub removed: Vec<DsnpUserId>,
+
+	/// pages whose content hash changed, including created and deleted pages, sorted by page id
+	#[serde(rename = "changedPages")]
+	pub changed_pages: Vec<PageId>,
+
+	/// changed pages whose connections are unknown for the synced or the current content hash,
+	/// such as pages which were not imported in this session, which have to be read again
+	#[serde(rename = "unresolvedPages")]
+	pub unresolved_pages: Vec<PageId>,
+
+	/// current content hash of every imported page, which can be stored as the next sync
+	#[serde(rename = "pageHashes")]
+	pub page_hashes: BTreeMap<PageId, PageHash>,
+}
+
+impl ChangesReport {
+	/// returns true if the connection changes of every changed page are known
+	pub fn is_complete(&self) -> bool {
+		self.unresolved_pages.is_empty()
+	}
 }
 
 /// Different kind of actions that can be applied to the graph
@@ -263,6 +1158,16 @@ pub enum Action {
 		/// optional keys to import for the connection. Mostly useful for private friendships.
 		#[serde(rename = "dsnpKeys")]
 		dsnp_keys: Option<DsnpKeys>,
+
+		/// optional page to place the connection into. Honored if the page has enough capacity,
+		/// otherwise the connection is placed using the normal packing.
+		#[serde(rename = "preferredPageId", default)]
+		preferred_page_id: Option<PageId>,
+
+		/// optional since value of the connection in ksecs since EPOCH, only honoured under
+		/// `TimestampPolicy::CallerProvided`
+		#[serde(rename = "since", default)]
+		since: Option<u64>,
 	},
 
 	/// an action that defines removing an existing connection from social graph
@@ -284,8 +1189,56 @@ pub enum Action {
 
 		/// public key
 		#[serde(rename = "newPublicKey")]
+		#[cfg_attr(feature = "serde", serde(with = "crate::util::serde_base64"))]
 		new_public_key: Vec<u8>,
 	},
+
+	/// an action that defines adding a friendship between two users owned by the same wallet,
+	/// updating both of their social graphs. Both users need to be imported, with their key pairs
+	/// for private friendships
+	ConnectMutual {
+		/// first user of the friendship, considered as the owner of this action
+		#[serde(rename = "userA")]
+		user_a: DsnpUserId,
+
+		/// second user of the friendship
+		#[serde(rename = "userB")]
+		user_b: DsnpUserId,
+
+		/// Schema id of the friendship graph
+		#[serde(rename = "schemaId")]
+		schema_id: SchemaId,
+
+		/// optional since value of the friendship in ksecs since EPOCH, only honoured under
+		/// `TimestampPolicy::CallerProvided`
+		#[serde(rename = "since", default)]
+		since: Option<u64>,
+	},
+
+	/// an action that defines moving an existing connection to the graph of the same connection
+	/// kind with the other privacy type, such as from a public to a private follow graph
+	MigrateConnection {
+		/// owner of the social graph
+		#[serde(rename = "ownerDsnpUserId")]
+		owner_dsnp_user_id: DsnpUserId,
+
+		/// Schema id of the graph the connection is removed from
+		#[serde(rename = "fromSchemaId")]
+		from_schema_id: SchemaId,
+
+		/// Schema id of the graph the connection is added to
+		#[serde(rename = "toSchemaId")]
+		to_schema_id: SchemaId,
+
+		/// user of the migrated connection
+		#[serde(rename = "dsnpUserId")]
+		dsnp_user_id: DsnpUserId,
+
+		/// optional since value of the migrated connection in ksecs since EPOCH, only honoured
+		/// under `TimestampPolicy::CallerProvided`
+		#[serde(rename = "since", default)]
+		since: Option<u64>,
+	},
 }
 
 impl Action {
@@ -294,6 +1247,8 @@ impl Action {
 			Action::Connect { owner_dsnp_user_id, .. } => owner_dsnp_user_id,
 			Action::Disconnect { owner_dsnp_user_id, .. } => owner_dsnp_user_id,
 			Action::AddGraphKey { owner_dsnp_user_id, .. } => owner_dsnp_user_id,
+			Action::ConnectMutual { user_a, .. } => user_a,
+			Action::MigrateConnection { owner_dsnp_user_id, .. } => owner_dsnp_user_id,
 		}
 	}
 }
@@ -313,6 +1268,41 @@ pub struct ActionOptions {
 	/// calling apply_actions
 	#[serde(rename = "disableAutoCommit")]
 	pub disable_auto_commit: bool,
+
+	/// attempt all actions and return every failing action index and error
+	/// instead of stopping on the first failure
+	#[serde(rename = "collectErrors", default)]
+	pub collect_errors: bool,
+
+	/// fail private friendship connect actions upfront if the public keys of the counterpart are
+	/// neither imported nor provided in the action, instead of failing later on export. Both users
+	/// of a connect mutual action are checked
+	#[serde(rename = "requireCounterpartKeys", default)]
+	pub require_counterpart_keys: bool,
+
+	/// fail with `ActionBatchTooLarge` before applying any action if more actions are provided
+	#[serde(rename = "maxBatchSize", default)]
+	pub max_batch_size: Option<NonZeroU32>,
+
+	/// policy choosing the since value of the connections added by the actions
+	#[serde(rename = "timestampPolicy", default)]
+	pub timestamp_policy: TimestampPolicy,
+}
+
+/// Policy choosing the since value stored with connections added by actions
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimestampPolicy {
+	/// keep the since values provided by the caller, in the add actions or through
+	/// `import_edge_list`, and use the time of the export for connections without one
+	#[default]
+	CallerProvided = 0,
+
+	/// use the time at which the actions are applied
+	Now = 1,
+
+	/// use zero, which does not reveal when the connections were made
+	Zero = 2,
 }
 
 /// implementing input validation for Action
@@ -334,10 +1324,26 @@ impl InputValidation for Action {
 			Action::Disconnect { connection, .. } => {
 				connection.validate()?;
 			},
-			Action::AddGraphKey { new_public_key, .. } =>
-				if new_public_key.is_empty() {
-					return DsnpGraphResult::Err(InvalidPublicKey);
-				},
+			Action::AddGraphKey { new_public_key, .. } => {
+				validate_public_key(GraphKeyType::X25519, new_public_key)?;
+			},
+			Action::ConnectMutual { user_a, user_b, schema_id, .. } => {
+				Connection { dsnp_user_id: *user_b, schema_id: *schema_id }.validate()?;
+				if user_a == user_b {
+					return DsnpGraphResult::Err(InvalidDsnpUserId(*user_b));
+				}
+			},
+			Action::MigrateConnection { from_schema_id, to_schema_id, dsnp_user_id, .. } => {
+				for schema_id in [from_schema_id, to_schema_id] {
+					Connection { dsnp_user_id: *dsnp_user_id, schema_id: *schema_id }.validate()?;
+				}
+				if from_schema_id == to_schema_id {
+					return DsnpGraphResult::Err(InvalidSchemaId(
+						*to_schema_id,
+						SchemaIdContext::default(),
+					));
+				}
+			},
 		}
 
 		Ok(())
@@ -346,6 +1352,11 @@ impl InputValidation for Action {
 
 /// Output of graph sdk that defines the different updates that needs to be applied to chain
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+	feature = "serde",
+	derive(Serialize, Deserialize),
+	serde(tag = "type", rename_all_fields = "camelCase")
+)]
 pub enum Update {
 	/// A `PersistPage` type is used to upsert a page on the chain with latest changes
 	PersistPage {
@@ -362,7 +1373,12 @@ pub enum Update {
 		prev_hash: PageHash,
 
 		/// social graph page data
+		#[cfg_attr(feature = "serde", serde(with = "crate::util::serde_base64"))]
 		payload: Vec<u8>,
+
+		/// block at which the imported data this update is based on was read
+		#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+		chain_head: Option<ChainHead>,
 	},
 
 	/// A `DeletePage` type is used to remove a page from the chain
@@ -378,6 +1394,10 @@ pub enum Update {
 
 		/// previous hash value is used to avoid updating a stale state
 		prev_hash: PageHash,
+
+		/// block at which the imported data this update is based on was read
+		#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+		chain_head: Option<ChainHead>,
 	},
 
 	/// A `AddKey` type is used to add a new key to chain
@@ -389,10 +1409,18 @@ pub enum Update {
 		prev_hash: PageHash,
 
 		/// social graph page data
+		#[cfg_attr(feature = "serde", serde(with = "crate::util::serde_base64"))]
 		payload: Vec<u8>,
+
+		/// block at which the imported data this update is based on was read
+		#[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+		chain_head: Option<ChainHead>,
 	},
 }
 
+/// Deterministic key of an exported `Update` which submitters can use to dedupe retries
+pub type IdempotencyKey = [u8; STATE_HASH_BYTES];
+
 /// converts a `PageData` type to `Update` type
 impl From<(PageData, DsnpUserId, SchemaId)> for Update {
 	fn from((page_data, owner_dsnp_user_id, schema_id): (PageData, DsnpUserId, SchemaId)) -> Self {
@@ -403,17 +1431,151 @@ impl From<(PageData, DsnpUserId, SchemaId)> for Update {
 				page_id: page_data.page_id,
 				prev_hash: page_data.content_hash,
 				payload: page_data.content.clone(),
+				chain_head: None,
 			},
 			true => Update::DeletePage {
 				owner_dsnp_user_id,
 				schema_id,
 				page_id: page_data.page_id,
 				prev_hash: page_data.content_hash,
+				chain_head: None,
 			},
 		}
 	}
 }
 
+impl Update {
+	/// returns the block at which the imported data this update is based on was read
+	pub fn chain_head(&self) -> Option<&ChainHead> {
+		match self {
+			Update::PersistPage { chain_head, .. } |
+			Update::DeletePage { chain_head, .. } |
+			Update::AddKey { chain_head, .. } => chain_head.as_ref(),
+		}
+	}
+
+	/// returns a key calculated from the type, owner, schema id, page id, previous hash and the
+	/// content hash of the new payload of this update
+	///
+	/// Re-exporting the same changes results in the same key, so submitters can dedupe retries
+	/// after a partially failed submission. Private pages are only re-exported with the same
+	/// payload when `NonceMode::Deterministic` is used.
+	pub fn idempotency_key(&self) -> IdempotencyKey {
+		let (kind, owner_dsnp_user_id, schema_id, page_id, prev_hash, payload) = match self {
+			Update::PersistPage {
+				owner_dsnp_user_id,
+				schema_id,
+				page_id,
+				prev_hash,
+				payload,
+				..
+			} => (0, owner_dsnp_user_id, Some(schema_id), Some(page_id), prev_hash, &payload[..]),
+			Update::DeletePage { owner_dsnp_user_id, schema_id, page_id, prev_hash, .. } =>
+				(1, owner_dsnp_user_id, Some(schema_id), Some(page_id), prev_hash, &[][..]),
+			Update::AddKey { owner_dsnp_user_id, prev_hash, payload, .. } =>
+				(2, owner_dsnp_user_id, None, None, prev_hash, &payload[..]),
+		};
+		let mut hasher = StateHasher::new();
+		hasher.write_u64(kind);
+		hasher.write_u64(*owner_dsnp_user_id);
+		hasher.write_optional_u64(schema_id.map(|id| *id as u64));
+		hasher.write_optional_u64(page_id.map(|id| *id as u64));
+		hasher.write_u64(*prev_hash as u64);
+		hasher.write_u64(XxHash32.hash(payload) as u64);
+		hasher.finish()
+	}
+
+	/// sets the block at which the imported data this update is based on was read
+	pub fn with_chain_head(mut self, head: Option<ChainHead>) -> Self {
+		match &mut self {
+			Update::PersistPage { chain_head, .. } |
+			Update::DeletePage { chain_head, .. } |
+			Update::AddKey { chain_head, .. } => *chain_head = head,
+		}
+		self
+	}
+}
+
+/// Connection changes contained in an exported page update
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct UpdateDetails {
+	/// connections added to the page
+	#[serde(rename = "addedConnections")]
+	pub added_connections: Vec<DsnpUserId>,
+
+	/// connections removed from the page
+	#[serde(rename = "removedConnections")]
+	pub removed_connections: Vec<DsnpUserId>,
+
+	/// number of connections on the page after applying the update
+	#[serde(rename = "connectionCount")]
+	pub connection_count: usize,
+
+	/// size of the serialized connections of the page before compression in bytes
+	#[serde(rename = "uncompressedSize")]
+	pub uncompressed_size: usize,
+
+	/// size of the page payload in bytes as it will be stored on chain, which is compressed and
+	/// for private pages encrypted, `0` for removed pages
+	#[serde(rename = "payloadSize")]
+	pub payload_size: usize,
+
+	/// maximum allowed size of a page payload in bytes for the environment
+	#[serde(rename = "maxPayloadSize")]
+	pub max_payload_size: usize,
+}
+
+impl UpdateDetails {
+	/// ratio of the payload size to the uncompressed size, including the encryption overhead of
+	/// private pages, `None` for empty pages
+	pub fn compression_ratio(&self) -> Option<f64> {
+		match self.uncompressed_size {
+			0 => None,
+			uncompressed_size => Some(self.payload_size as f64 / uncompressed_size as f64),
+		}
+	}
+
+	/// ratio of the payload size to the maximum allowed payload size, which shows how close the
+	/// page is to the page size limit of the chain
+	pub fn fill_ratio(&self) -> f64 {
+		match self.max_payload_size {
+			0 => 0.0,
+			max_payload_size => self.payload_size as f64 / max_payload_size as f64,
+		}
+	}
+}
+
+/// An exported update paired with the connection changes it contains
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UpdateWithDetails {
+	/// update to be applied to chain
+	pub update: Update,
+
+	/// connection changes of the page, `None` for key updates
+	pub details: Option<UpdateDetails>,
+}
+
+/// AddKey update which would be exported for a key, without the key being staged
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PreviewedUpdate {
+	/// update which would be applied to chain
+	pub update: Update,
+
+	/// key id which would be assigned to the key
+	pub key_id: u64,
+
+	/// bytes the key would add to the key page, including the itemized header
+	pub item_bytes: u32,
+
+	/// used bytes of the key page after adding the key
+	pub used_bytes: u32,
+
+	/// maximum allowed size of the key page
+	pub max_bytes: u32,
+}
+
 impl PartialOrd for KeyData {
 	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
 		Some(self.cmp(other))
@@ -429,6 +1591,7 @@ impl Ord for KeyData {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use dryoc::keypair::StackKeyPair;
 
 	#[test]
 	fn key_data_should_be_ordered_by_index_asc() {
@@ -442,6 +1605,68 @@ mod tests {
 		assert_eq!(arr, vec![a, b, c]);
 	}
 
+	#[test]
+	fn graph_key_pair_public_key_should_roundtrip_through_all_formats() {
+		// arrange
+		let key_pair = StackKeyPair::gen();
+		let graph_key_pair = GraphKeyPair {
+			key_type: GraphKeyType::X25519,
+			public_key: key_pair.public_key.to_vec(),
+			secret_key: key_pair.secret_key.to_vec(),
+		};
+
+		// act
+		let raw = graph_key_pair.public_key_to_raw().expect("should export raw");
+		let jwk = graph_key_pair.public_key_to_jwk().expect("should export jwk");
+		let multibase = graph_key_pair.public_key_to_multibase().expect("should export multibase");
+
+		// assert
+		assert_eq!(raw, graph_key_pair.public_key);
+		assert_eq!((jwk.kty.as_str(), jwk.crv.as_str()), ("OKP", "X25519"));
+		assert!(multibase.starts_with("z6LS"));
+		assert_eq!(
+			GraphKeyPair::public_key_from_jwk(&jwk).unwrap(),
+			(GraphKeyType::X25519, raw.clone())
+		);
+		assert_eq!(
+			GraphKeyPair::public_key_from_multibase(&multibase).unwrap(),
+			(GraphKeyType::X25519, raw.clone())
+		);
+		assert_eq!(GraphKeyPair::public_key_from_raw(GraphKeyType::X25519, &raw).unwrap(), raw);
+	}
+
+	#[test]
+	fn graph_key_pair_public_key_import_with_invalid_input_should_fail() {
+		let jwk = |kty: &str, crv: &str, x: &str| PublicKeyJwk {
+			kty: kty.to_string(),
+			crv: crv.to_string(),
+			x: x.to_string(),
+		};
+		let valid_x = encode_base64_url(&StackKeyPair::gen().public_key);
+
+		assert!(matches!(
+			GraphKeyPair::public_key_from_jwk(&jwk("EC", "P-256", &valid_x)),
+			Err(InvalidInput(_))
+		));
+		assert!(matches!(
+			GraphKeyPair::public_key_from_jwk(&jwk("OKP", "X25519", "not base64!")),
+			Err(InvalidInput(_))
+		));
+		assert!(matches!(
+			GraphKeyPair::public_key_from_jwk(&jwk("OKP", "X25519", "AQID")),
+			Err(InvalidPublicKey)
+		));
+		assert!(matches!(GraphKeyPair::public_key_from_multibase("m7AEBAg"), Err(InvalidInput(_))));
+		assert!(matches!(
+			GraphKeyPair::public_key_from_multibase(&format!("z{}", encode_base58(&[0xed, 0x01]))),
+			Err(InvalidInput(_))
+		));
+		assert!(matches!(
+			GraphKeyPair::public_key_from_raw(GraphKeyType::X25519, &[0u8; 32]),
+			Err(InvalidPublicKey)
+		));
+	}
+
 	#[test]
 	fn update_from_page_data_should_create_correct_update_types() {
 		// arrange
@@ -463,4 +1688,152 @@ mod tests {
 		assert!(matches!(persist_update, Update::PersistPage { .. }));
 		assert!(matches!(delete_update, Update::DeletePage { .. }));
 	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn import_bundle_and_action_should_roundtrip_through_json_with_base64_bytes() {
+		// arrange
+		let bundle = ImportBundle {
+			dsnp_user_id: 1,
+			schema_id: 2,
+			key_pairs: vec![GraphKeyPair {
+				key_type: GraphKeyType::X25519,
+				public_key: vec![1, 2, 3],
+				secret_key: vec![4, 5, 6],
+			}],
+			dsnp_keys: Some(DsnpKeys {
+				dsnp_user_id: 1,
+				keys_hash: 10,
+				keys: vec![KeyData { index: 0, content: vec![0xfb, 0xff] }],
+			}),
+			pages: vec![PageData { page_id: 3, content: vec![7, 8, 9], content_hash: 20 }],
+			chain_head: Some(ChainHead { block_number: 11, block_hash: vec![1, 2, 3] }),
+		};
+		let action = Action::AddGraphKey { owner_dsnp_user_id: 1, new_public_key: vec![1, 2, 3] };
+
+		// act
+		let bundle_json = serde_json::to_value(&bundle).unwrap();
+		let action_json = serde_json::to_value(&action).unwrap();
+
+		// assert
+		assert_eq!(bundle_json["keyPairs"][0]["secretKey"], "BAUG");
+		assert_eq!(bundle_json["dsnpKeys"]["keys"][0]["content"], "+/8=");
+		assert_eq!(bundle_json["pages"][0]["content"], "BwgJ");
+		assert_eq!(bundle_json["chainHead"]["blockHash"], "AQID");
+		assert_eq!(action_json["AddGraphKey"]["newPublicKey"], "AQID");
+		let bundle_back: ImportBundle = serde_json::from_value(bundle_json).unwrap();
+		assert_eq!(bundle_back.key_pairs, bundle.key_pairs);
+		assert_eq!(bundle_back.dsnp_keys, bundle.dsnp_keys);
+		assert_eq!(bundle_back.pages, bundle.pages);
+		assert_eq!(bundle_back.chain_head, bundle.chain_head);
+		let action_back: Action = serde_json::from_value(action_json).unwrap();
+		assert!(matches!(
+			action_back,
+			Action::AddGraphKey { new_public_key, .. } if new_public_key == vec![1, 2, 3]
+		));
+	}
+
+	#[test]
+	fn idempotency_key_should_depend_only_on_update_content() {
+		// arrange
+		let update = Update::AddKey {
+			owner_dsnp_user_id: 1,
+			prev_hash: 7,
+			payload: vec![4, 5, 6],
+			chain_head: None,
+		};
+		let retried = update
+			.clone()
+			.with_chain_head(Some(ChainHead { block_number: 11, block_hash: vec![4, 5, 6] }));
+		let other_payload = Update::AddKey {
+			owner_dsnp_user_id: 1,
+			prev_hash: 7,
+			payload: vec![4, 5, 7],
+			chain_head: None,
+		};
+		let other_prev_hash = Update::AddKey {
+			owner_dsnp_user_id: 1,
+			prev_hash: 8,
+			payload: vec![4, 5, 6],
+			chain_head: None,
+		};
+		let delete = Update::DeletePage {
+			owner_dsnp_user_id: 1,
+			schema_id: 2,
+			page_id: 5,
+			prev_hash: 7,
+			chain_head: None,
+		};
+		let other_page =
+			Update::from((PageData { page_id: 6, content: vec![], content_hash: 7 }, 1, 2));
+
+		// act
+		let key = update.idempotency_key();
+
+		// assert
+		assert_eq!(key, retried.idempotency_key());
+		assert_ne!(key, other_payload.idempotency_key());
+		assert_ne!(key, other_prev_hash.idempotency_key());
+		assert_ne!(delete.idempotency_key(), other_page.idempotency_key());
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn update_should_roundtrip_through_json_with_base64_payload() {
+		// arrange
+		let updates = vec![
+			Update::PersistPage {
+				owner_dsnp_user_id: 1,
+				schema_id: 2,
+				page_id: 3,
+				prev_hash: 4,
+				payload: vec![1, 2, 3],
+				chain_head: None,
+			},
+			Update::DeletePage {
+				owner_dsnp_user_id: 1,
+				schema_id: 2,
+				page_id: 5,
+				prev_hash: 6,
+				chain_head: None,
+			},
+			Update::AddKey {
+				owner_dsnp_user_id: 1,
+				prev_hash: 7,
+				payload: vec![4, 5, 6],
+				chain_head: Some(ChainHead { block_number: 11, block_hash: vec![4, 5, 6] }),
+			},
+		];
+
+		// act
+		let json = serde_json::to_value(&updates).unwrap();
+
+		// assert
+		assert_eq!(
+			json[0],
+			serde_json::json!({
+				"type": "PersistPage",
+				"ownerDsnpUserId": 1,
+				"schemaId": 2,
+				"pageId": 3,
+				"prevHash": 4,
+				"payload": "AQID",
+			})
+		);
+		assert_eq!(json[1]["type"], "DeletePage");
+		assert_eq!(json[2]["payload"], "BAUG");
+		assert_eq!(
+			json[2]["chainHead"],
+			serde_json::json!({ "blockNumber": 11, "blockHash": "BAUG" })
+		);
+		assert!(serde_json::from_value::<Vec<Update>>(serde_json::json!([{
+			"type": "AddKey",
+			"ownerDsnpUserId": 1,
+			"prevHash": 7,
+			"payload": "not base64!",
+		}]))
+		.is_err());
+		let updates_back: Vec<Update> = serde_json::from_value(json).unwrap();
+		assert_eq!(updates_back, updates);
+	}
 }
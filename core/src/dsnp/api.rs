@@ -0,0 +1,148 @@
+//! Low level functions to encrypt and decrypt a single private graph page without a `GraphState`,
+//! which can be used by backup and inspection tooling
+//!
+//! # Stability
+//! Signatures of this module only change in a new major version of the crate and the encrypted
+//! content follows the [DSNP](https://spec.dsnp.org/DSNP/Graph.html) private graph chunk format
+//! of the provided version, so pages encrypted here can be imported into a `GraphState` and the
+//! other way around.
+use crate::{
+	api::api_types::{GraphKeyPair, PageData},
+	dsnp::{
+		dsnp_configs::{validate_public_key, DsnpVersionConfig, KeyPairType, PublicKeyType},
+		dsnp_types::{DsnpPublicKey, PrivateGraphChunk},
+		reader_writer::{DsnpReader, DsnpWriter},
+	},
+	frequency::Frequency,
+};
+use dsnp_graph_config::{
+	errors::{DsnpGraphError, DsnpGraphResult},
+	DsnpVersion, GraphKeyType, InputValidation,
+};
+use log::Level;
+use log_result_proc_macro::log_result_err;
+
+/// decrypts the content of a private graph page using any of the key pairs
+///
+/// Fails with `InvalidInput` if the page is empty or no key pairs are provided, and with
+/// `UnableToDecryptGraphChunkWithAnyKey` if none of the key pairs can decrypt the page
+#[log_result_err(Level::Info)]
+pub fn decrypt_page(
+	page: &PageData,
+	key_pairs: &[GraphKeyPair],
+	version: DsnpVersion,
+) -> DsnpGraphResult<PrivateGraphChunk> {
+	page.validate()?;
+	if page.content.is_empty() {
+		return Err(DsnpGraphError::InvalidInput(format!("Page {} is empty", page.page_id)))
+	}
+	if key_pairs.is_empty() {
+		return Err(DsnpGraphError::InvalidInput("No key pairs are provided".to_string()))
+	}
+	let mut keys = vec![];
+	for key_pair in key_pairs {
+		key_pair.validate()?;
+		let key: KeyPairType = key_pair.clone().try_into()?;
+		keys.push(key);
+	}
+
+	let dsnp_version_config = DsnpVersionConfig::new(version);
+	keys.into_iter()
+		.find_map(|key| {
+			Frequency::read_private_graph(&page.content, &dsnp_version_config, &key.into()).ok()
+		})
+		.ok_or(DsnpGraphError::UnableToDecryptGraphChunkWithAnyKey)
+}
+
+/// encrypts a private graph chunk for the X25519 public key and returns the page content
+///
+/// Fails with `InvalidInput` if the chunk has PRIds which do not match its connections and with
+/// `InvalidPublicKey` if the public key is not a valid X25519 key
+#[log_result_err(Level::Info)]
+pub fn encrypt_page(
+	chunk: &PrivateGraphChunk,
+	public_key: &[u8],
+	version: DsnpVersion,
+) -> DsnpGraphResult<Vec<u8>> {
+	if !chunk.prids.is_empty() && chunk.prids.len() != chunk.inner_graph.len() {
+		return Err(DsnpGraphError::InvalidInput(format!(
+			"{} PRIds do not match {} connections",
+			chunk.prids.len(),
+			chunk.inner_graph.len()
+		)))
+	}
+	validate_public_key(GraphKeyType::X25519, public_key)?;
+	let public_key: PublicKeyType =
+		(&DsnpPublicKey { key: public_key.to_vec(), key_id: None }).try_into()?;
+
+	Frequency::write_private_graph(chunk, &DsnpVersionConfig::new(version), &public_key)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::dsnp::dsnp_types::{DsnpGraphEdge, DsnpPrid};
+	use dryoc::keypair::StackKeyPair;
+
+	fn graph_key_pair() -> GraphKeyPair {
+		GraphKeyPair::try_from(&KeyPairType::Version1_0(StackKeyPair::gen()))
+			.expect("should convert")
+	}
+
+	#[test]
+	fn encrypted_page_should_be_decrypted_by_any_of_the_key_pairs() {
+		// arrange
+		let (key_pair, other_key_pair) = (graph_key_pair(), graph_key_pair());
+		let chunk = PrivateGraphChunk {
+			key_id: 3,
+			prids: vec![DsnpPrid::new(&[1u8; 8]), DsnpPrid::new(&[2u8; 8])],
+			inner_graph: vec![
+				DsnpGraphEdge { user_id: 10, since: 100 },
+				DsnpGraphEdge { user_id: 11, since: 101 },
+			],
+		};
+
+		// act
+		let content = encrypt_page(&chunk, &key_pair.public_key, DsnpVersion::Version1_0);
+
+		// assert
+		let page =
+			PageData { page_id: 1, content: content.expect("should encrypt"), content_hash: 7 };
+		let decrypted =
+			decrypt_page(&page, &[other_key_pair.clone(), key_pair], DsnpVersion::Version1_0);
+		assert_eq!(decrypted.expect("should decrypt"), chunk);
+		assert!(matches!(
+			decrypt_page(&page, &[other_key_pair], DsnpVersion::Version1_0),
+			Err(DsnpGraphError::UnableToDecryptGraphChunkWithAnyKey)
+		));
+	}
+
+	#[test]
+	fn page_primitives_should_validate_inputs() {
+		// arrange
+		let key_pair = graph_key_pair();
+		let chunk = PrivateGraphChunk {
+			key_id: 0,
+			prids: vec![DsnpPrid::new(&[1u8; 8])],
+			inner_graph: vec![],
+		};
+		let empty_page = PageData { page_id: 1, content: vec![], content_hash: 0 };
+		let page = PageData { page_id: 1, content: vec![1, 2, 3], content_hash: 7 };
+
+		// act
+		let mismatched_prids = encrypt_page(&chunk, &key_pair.public_key, DsnpVersion::Version1_0);
+		let invalid_key = encrypt_page(
+			&PrivateGraphChunk { prids: vec![], ..chunk },
+			&[0u8; 31],
+			DsnpVersion::Version1_0,
+		);
+		let empty = decrypt_page(&empty_page, &[key_pair.clone()], DsnpVersion::Version1_0);
+		let without_keys = decrypt_page(&page, &[], DsnpVersion::Version1_0);
+
+		// assert
+		assert!(matches!(mismatched_prids, Err(DsnpGraphError::InvalidInput(_))));
+		assert!(matches!(invalid_key, Err(DsnpGraphError::InvalidPublicKey)));
+		assert!(matches!(empty, Err(DsnpGraphError::InvalidInput(_))));
+		assert!(matches!(without_keys, Err(DsnpGraphError::InvalidInput(_))));
+	}
+}
@@ -0,0 +1,86 @@
+//! Abstraction of the chain specific formats used to store graph pages and keys, which allows
+//! supporting other DSNP systems or future Frequency runtime versions by implementing
+//! `ChainAdapter`
+use crate::{
+	api::api_types::PageHash,
+	dsnp::{
+		compression::CompressionFormat,
+		dsnp_configs::{DsnpVersionConfig, KeyPairType, PublicKeyType, SecretKeyType},
+		dsnp_types::{DsnpInnerGraph, DsnpPublicKey, PrivateGraphChunk},
+	},
+	frequency::Frequency,
+};
+use dsnp_graph_config::errors::DsnpGraphResult;
+use std::{fmt::Debug, panic::RefUnwindSafe, sync::Arc};
+
+/// Shared reference to a chain adapter
+pub type SharedChainAdapter = Arc<dyn ChainAdapter>;
+
+/// Reads and writes the payloads of graph pages and key pages as they are stored on a chain and
+/// calculates their hashes
+///
+/// `Frequency` is the default implementation and hosts can provide their own one using
+/// `GraphStateBuilder::with_chain_adapter`.
+pub trait ChainAdapter: Debug + Send + Sync + RefUnwindSafe {
+	/// reads a public key from the payload of a key page item
+	fn read_key(&self, data: &[u8]) -> DsnpGraphResult<DsnpPublicKey>;
+
+	/// writes a public key as the payload of a key page item
+	fn write_key(&self, key: &DsnpPublicKey) -> DsnpGraphResult<Vec<u8>>;
+
+	/// reads the connections of a public graph page and the compression format it was stored with
+	fn read_public_page(&self, data: &[u8])
+		-> DsnpGraphResult<(DsnpInnerGraph, CompressionFormat)>;
+
+	/// writes the connections as the payload of a public graph page
+	fn write_public_page(&self, inner: &DsnpInnerGraph) -> DsnpGraphResult<Vec<u8>>;
+
+	/// serializes the connections of a graph page before they are compressed or encrypted
+	fn write_inner_graph(&self, inner: &DsnpInnerGraph) -> DsnpGraphResult<Vec<u8>>;
+
+	/// reads the id of the key a private graph page was encrypted with without decrypting it
+	fn read_private_page_key_id(&self, data: &[u8]) -> DsnpGraphResult<u64>;
+
+	/// decrypts a private graph page and returns it with the compression format it was stored with
+	fn read_private_page(
+		&self,
+		data: &[u8],
+		dsnp_version_config: &DsnpVersionConfig,
+		decryption_input: &SecretKeyType,
+	) -> DsnpGraphResult<(PrivateGraphChunk, CompressionFormat)>;
+
+	/// encrypts the graph as the payload of a private graph page
+	fn write_private_page(
+		&self,
+		graph: &PrivateGraphChunk,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_input: &PublicKeyType,
+	) -> DsnpGraphResult<Vec<u8>>;
+
+	/// encrypts the graph as the payload of a private graph page using deterministic encryption
+	/// for the key pair, where the same graph and context always result in the same payload
+	fn write_private_page_deterministic(
+		&self,
+		graph: &PrivateGraphChunk,
+		dsnp_version_config: &DsnpVersionConfig,
+		key_pair: &KeyPairType,
+		context: &[u8],
+	) -> DsnpGraphResult<Vec<u8>>;
+
+	/// encrypts the graph as the payload of a private graph page which can be decrypted by any of
+	/// the provided public keys
+	fn write_private_page_multi(
+		&self,
+		graph: &PrivateGraphChunk,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_inputs: &[PublicKeyType],
+	) -> DsnpGraphResult<Vec<u8>>;
+
+	/// calculates the hash of a page payload as it would be stored on chain
+	fn page_hash(&self, content: &[u8], dsnp_version_config: &DsnpVersionConfig) -> PageHash;
+}
+
+/// Returns the default `Frequency` chain adapter
+pub fn default_chain_adapter() -> SharedChainAdapter {
+	Arc::new(Frequency)
+}
@@ -3,8 +3,8 @@ use dsnp_graph_config::errors::{DsnpGraphError, DsnpGraphResult};
 use log::Level;
 use log_result_proc_macro::log_result_err;
 use miniz_oxide::{
-	deflate::{compress_to_vec, CompressionLevel},
-	inflate::decompress_to_vec,
+	deflate::{compress_to_vec, compress_to_vec_zlib, CompressionLevel},
+	inflate::{decompress_to_vec, decompress_to_vec_zlib},
 };
 
 /// Common trait for different compression algorithms
@@ -33,6 +33,81 @@ impl CompressionBehavior for DeflateCompression {
 	}
 }
 
+/// Legacy zlib wrapped Deflate Compression algorithm
+///
+/// Older graph pages (written on Rococo by a previous SDK iteration) wrapped the deflate stream
+/// in a zlib header and adler32 trailer. It is only supported for reading, new pages are always
+/// written using [DeflateCompression]
+pub struct LegacyZlibCompression;
+
+impl CompressionBehavior for LegacyZlibCompression {
+	#[log_result_err(Level::Info)]
+	fn compress(obj: &[u8]) -> DsnpGraphResult<Vec<u8>> {
+		Ok(compress_to_vec_zlib(obj, CompressionLevel::BestCompression as u8))
+	}
+
+	#[log_result_err(Level::Info)]
+	fn decompress(data: &[u8]) -> DsnpGraphResult<Vec<u8>> {
+		let val = decompress_to_vec_zlib(data)
+			.map_err(|e| DsnpGraphError::DecompressError(e.to_string()))?;
+		Ok(val)
+	}
+}
+
+/// Compression formats that pages might be stored with on chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+	/// Current raw deflate format
+	Deflate,
+	/// Legacy zlib wrapped deflate format
+	LegacyZlib,
+}
+
+impl CompressionFormat {
+	/// returns true if pages with this format should be rewritten using the current format
+	pub fn is_legacy(&self) -> bool {
+		*self != CompressionFormat::Deflate
+	}
+
+	/// Checks if the data starts with a valid zlib header (RFC 1950)
+	fn has_zlib_header(data: &[u8]) -> bool {
+		match data {
+			[cmf, flg, ..] =>
+				cmf & 0x0f == 8 && cmf >> 4 <= 7 && ((*cmf as u16) << 8 | *flg as u16) % 31 == 0,
+			_ => false,
+		}
+	}
+
+	/// Detects the format of the compressed data and decompresses it
+	///
+	/// Data carrying a zlib header is first tried as legacy format, otherwise the current format
+	/// is tried first. If the preferred format fails the other one is used as a fallback.
+	#[log_result_err(Level::Info)]
+	pub fn detect_and_decompress(data: &[u8]) -> DsnpGraphResult<(Vec<u8>, CompressionFormat)> {
+		let try_order = match Self::has_zlib_header(data) {
+			true => [CompressionFormat::LegacyZlib, CompressionFormat::Deflate],
+			false => [CompressionFormat::Deflate, CompressionFormat::LegacyZlib],
+		};
+
+		let mut last_error = None;
+		for format in try_order {
+			match format.decompress(data) {
+				Ok(decompressed) => return Ok((decompressed, format)),
+				Err(e) => last_error = Some(e),
+			}
+		}
+		Err(last_error.unwrap_or(DsnpGraphError::DecompressError("empty input".to_string())))
+	}
+
+	/// decompress the input using this format
+	fn decompress(&self, data: &[u8]) -> DsnpGraphResult<Vec<u8>> {
+		match self {
+			CompressionFormat::Deflate => DeflateCompression::decompress(data),
+			CompressionFormat::LegacyZlib => LegacyZlibCompression::decompress(data),
+		}
+	}
+}
+
 #[cfg(test)]
 mod test {
 	use super::*;
@@ -50,4 +125,35 @@ mod test {
 
 		assert_eq!(decompressed, data);
 	}
+
+	#[test]
+	fn detect_and_decompress_should_detect_current_format() {
+		let data = b"some graph data some graph data some graph data".to_vec();
+
+		let compressed = DeflateCompression::compress(&data).unwrap();
+		let (decompressed, format) = CompressionFormat::detect_and_decompress(&compressed).unwrap();
+
+		assert_eq!(decompressed, data);
+		assert_eq!(format, CompressionFormat::Deflate);
+		assert!(!format.is_legacy());
+	}
+
+	#[test]
+	fn detect_and_decompress_should_detect_legacy_format() {
+		let data = b"some graph data some graph data some graph data".to_vec();
+
+		let compressed = LegacyZlibCompression::compress(&data).unwrap();
+		let (decompressed, format) = CompressionFormat::detect_and_decompress(&compressed).unwrap();
+
+		assert_eq!(decompressed, data);
+		assert_eq!(format, CompressionFormat::LegacyZlib);
+		assert!(format.is_legacy());
+	}
+
+	#[test]
+	fn detect_and_decompress_with_invalid_input_should_fail() {
+		let result = CompressionFormat::detect_and_decompress(&[0xff, 0xff, 0xff, 0xff]);
+
+		assert!(result.is_err());
+	}
 }
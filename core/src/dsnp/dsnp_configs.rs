@@ -1,18 +1,32 @@
 //! DSNP version specific algorithms and Key types
+//!
+//! # Schema resolution
+//! Tooling that needs to read or write page payloads outside of a graph state, such as backup
+//! services, can resolve the parameters of a schema using [SchemaCodecConfig::resolve] and pass
+//! the contained [DsnpVersionConfig] to the readers and writers in `frequency`.
 use crate::{
 	api::api_types::GraphKeyPair,
 	dsnp::{
+		compression::CompressionFormat,
 		dsnp_types::DsnpPublicKey,
 		encryption::{EncryptionBehavior, SealBox},
 	},
+	util::hkdf::hkdf_sha512,
+};
+use dryoc::{
+	classic::crypto_box::{crypto_box_beforenm, crypto_box_seal_open},
+	constants::{CRYPTO_BOX_BEFORENMBYTES, CRYPTO_BOX_SEALBYTES},
+	keypair::{PublicKey, SecretKey, StackKeyPair},
+	types::ByteArray,
 };
-use dryoc::keypair::{PublicKey, SecretKey, StackKeyPair};
 use dsnp_graph_config::{
 	errors::{DsnpGraphError, DsnpGraphResult},
-	DsnpVersion, GraphKeyType,
+	ConnectionType, DsnpVersion, Environment, GraphKeyType, PrivacyType, SchemaId,
 };
 use log::Level;
 use log_result_proc_macro::log_result_err;
+use std::{fmt::Debug, panic::RefUnwindSafe, sync::Arc};
+use zeroize::{Zeroize, Zeroizing};
 
 /// Dsnp versions hardcoded configuration
 #[derive(Clone, PartialEq, Debug, Eq, Hash)]
@@ -21,6 +35,21 @@ pub enum DsnpVersionConfig {
 	Version1_0 { algorithm: SealBox },
 }
 
+/// Codec and cipher parameters of a schema resolved from an environment
+#[derive(Clone, PartialEq, Debug, Eq)]
+pub struct SchemaCodecConfig {
+	/// schema id the parameters are resolved for
+	pub schema_id: SchemaId,
+	/// dsnp version of the schema
+	pub dsnp_version: DsnpVersion,
+	/// connection type stored under the schema
+	pub connection_type: ConnectionType,
+	/// compression format used when writing pages
+	pub compression_format: CompressionFormat,
+	/// version specific configuration providing the encryption algorithm
+	pub version_config: DsnpVersionConfig,
+}
+
 /// Public key types for dsnp versions
 #[derive(Clone, PartialEq, Debug)]
 pub enum PublicKeyType {
@@ -29,17 +58,118 @@ pub enum PublicKeyType {
 }
 
 /// Keypair types for dsnp versions
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Debug)]
 pub enum KeyPairType {
 	/// Dsnp version 1.0
 	Version1_0(StackKeyPair),
+
+	/// Dsnp version 1.0 key pair whose secret key is only used through its operations
+	ExternalVersion1_0(SharedSecretKeyOperations),
 }
 
 /// Secret key types for dsnp versions
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Debug)]
 pub enum SecretKeyType {
 	/// Dsnp version 1.0
 	Version1_0(StackKeyPair),
+
+	/// Dsnp version 1.0 key pair whose secret key is only used through its operations
+	ExternalVersion1_0(SharedSecretKeyOperations),
+}
+
+/// Operations of a dsnp version 1.0 key pair which require its secret key
+///
+/// Every encryption, decryption and PRId calculation accesses secret keys through this trait. Key
+/// managers backed by secure enclaves or HSMs can implement it and resolve their key pairs as
+/// `KeyPairType::ExternalVersion1_0`, so the secret keys never have to leave them.
+pub trait SecretKeyOperations: Debug + Send + Sync + RefUnwindSafe {
+	/// returns the public key of the key pair
+	fn public_key(&self) -> PublicKey;
+
+	/// opens data sealed to the public key of the key pair using `crypto_box_seal`
+	fn open_sealed_box(&self, sealed_data: &[u8]) -> DsnpGraphResult<Vec<u8>>;
+
+	/// derives the shared key of the key pair and `public_key` the same way as
+	/// `crypto_box_beforenm`, which is used to calculate PRIds
+	fn derive_shared_key(
+		&self,
+		public_key: &PublicKey,
+	) -> DsnpGraphResult<Zeroizing<[u8; CRYPTO_BOX_BEFORENMBYTES]>>;
+
+	/// fills `output` using HKDF-SHA512 with the secret key as the input key material, which is
+	/// used to derive the ephemeral key pairs of deterministic sealing
+	fn derive_key_material(
+		&self,
+		salt: &[u8],
+		info: &[u8],
+		output: &mut [u8],
+	) -> DsnpGraphResult<()>;
+}
+
+/// Shared reference to the secret key operations of a key pair
+pub type SharedSecretKeyOperations = Arc<dyn SecretKeyOperations>;
+
+/// secret key operations of key pairs held in memory
+impl SecretKeyOperations for StackKeyPair {
+	fn public_key(&self) -> PublicKey {
+		self.public_key.clone()
+	}
+
+	fn open_sealed_box(&self, sealed_data: &[u8]) -> DsnpGraphResult<Vec<u8>> {
+		let mut plain = vec![0u8; sealed_data.len().saturating_sub(CRYPTO_BOX_SEALBYTES)];
+		crypto_box_seal_open(
+			plain.as_mut_slice(),
+			sealed_data,
+			self.public_key.as_array(),
+			self.secret_key.as_array(),
+		)
+		.map_err(|e| {
+			plain.zeroize();
+			DsnpGraphError::DecryptionError(e.to_string())
+		})?;
+		Ok(plain)
+	}
+
+	fn derive_shared_key(
+		&self,
+		public_key: &PublicKey,
+	) -> DsnpGraphResult<Zeroizing<[u8; CRYPTO_BOX_BEFORENMBYTES]>> {
+		Ok(Zeroizing::new(crypto_box_beforenm(public_key.as_array(), self.secret_key.as_array())))
+	}
+
+	fn derive_key_material(
+		&self,
+		salt: &[u8],
+		info: &[u8],
+		output: &mut [u8],
+	) -> DsnpGraphResult<()> {
+		hkdf_sha512(salt, self.secret_key.as_array(), info, output)
+			.ok_or(DsnpGraphError::EncryptionError("Invalid key material length".to_string()))
+	}
+}
+
+/// key pairs are equal if they hold the same key pair or share the same secret key operations
+impl PartialEq for KeyPairType {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(KeyPairType::Version1_0(a), KeyPairType::Version1_0(b)) => a == b,
+			(KeyPairType::ExternalVersion1_0(a), KeyPairType::ExternalVersion1_0(b)) =>
+				Arc::ptr_eq(a, b),
+			_ => false,
+		}
+	}
+}
+
+/// secret keys are equal if they hold the same key pair or share the same secret key operations
+impl PartialEq for SecretKeyType {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(SecretKeyType::Version1_0(a), SecretKeyType::Version1_0(b)) => a == b,
+			(SecretKeyType::ExternalVersion1_0(a), SecretKeyType::ExternalVersion1_0(b)) =>
+				Arc::ptr_eq(a, b),
+			_ => false,
+		}
+	}
 }
 
 impl DsnpVersionConfig {
@@ -50,6 +180,12 @@ impl DsnpVersionConfig {
 		}
 	}
 
+	/// creates a new `DsnpVersionConfig` for the dsnp version of a schema in the environment
+	#[log_result_err(Level::Info)]
+	pub fn from_schema_id(environment: &Environment, schema_id: SchemaId) -> DsnpGraphResult<Self> {
+		SchemaCodecConfig::resolve(environment, schema_id).map(|config| config.version_config)
+	}
+
 	/// returns the encryption/description algorithm associated with dsnp version
 	pub fn get_algorithm(&self) -> Box<dyn EncryptionBehavior> {
 		match self {
@@ -58,19 +194,64 @@ impl DsnpVersionConfig {
 	}
 }
 
+impl SchemaCodecConfig {
+	/// resolves the parameters of a schema using the config of the environment
+	#[log_result_err(Level::Info)]
+	pub fn resolve(environment: &Environment, schema_id: SchemaId) -> DsnpGraphResult<Self> {
+		let schema_config = environment
+			.get_config()
+			.schema_map
+			.get(&schema_id)
+			.ok_or_else(|| environment.invalid_schema_id(schema_id))?;
+		Ok(Self {
+			schema_id,
+			dsnp_version: schema_config.dsnp_version,
+			connection_type: schema_config.connection_type,
+			compression_format: CompressionFormat::Deflate,
+			version_config: DsnpVersionConfig::new(schema_config.dsnp_version),
+		})
+	}
+
+	/// returns true if pages of the schema are encrypted
+	pub fn is_encrypted(&self) -> bool {
+		self.connection_type.privacy_type() == PrivacyType::Private
+	}
+}
+
 impl KeyPairType {
 	/// returns raw bytes of the public key for specified dsnp version
 	pub fn get_public_key_raw(&self) -> Vec<u8> {
 		match self {
 			KeyPairType::Version1_0(k) => k.public_key.to_vec(),
+			KeyPairType::ExternalVersion1_0(operations) => operations.public_key().to_vec(),
+		}
+	}
+
+	/// returns the operations which use the secret key of the key pair
+	pub fn operations(&self) -> &dyn SecretKeyOperations {
+		match self {
+			KeyPairType::Version1_0(k) => k,
+			KeyPairType::ExternalVersion1_0(operations) => operations.as_ref(),
 		}
 	}
 
 	#[cfg(test)]
-	/// returns raw bytes of the secret key for specified dsnp version
-	pub fn get_secret_key_raw(&self) -> Vec<u8> {
+	/// returns raw bytes of the secret key for specified dsnp version, none for external key pairs
+	/// since their secret key is not accessible
+	pub fn get_secret_key_raw(&self) -> Option<Vec<u8>> {
+		match self {
+			KeyPairType::Version1_0(k) => Some(k.secret_key.to_vec()),
+			KeyPairType::ExternalVersion1_0(_) => None,
+		}
+	}
+}
+
+impl SecretKeyType {
+	/// returns the operations which use the secret key
+	pub fn operations(&self) -> &dyn SecretKeyOperations {
 		match self {
-			KeyPairType::Version1_0(k) => k.secret_key.to_vec(),
+			SecretKeyType::Version1_0(k) => k,
+			SecretKeyType::ExternalVersion1_0(operations) => operations.as_ref(),
 		}
 	}
 }
@@ -80,6 +261,8 @@ impl Into<PublicKeyType> for &'_ KeyPairType {
 	fn into(self) -> PublicKeyType {
 		match self {
 			KeyPairType::Version1_0(k) => PublicKeyType::Version1_0(k.public_key.clone()),
+			KeyPairType::ExternalVersion1_0(operations) =>
+				PublicKeyType::Version1_0(operations.public_key()),
 		}
 	}
 }
@@ -89,6 +272,8 @@ impl Into<SecretKeyType> for KeyPairType {
 	fn into(self) -> SecretKeyType {
 		match self {
 			KeyPairType::Version1_0(k) => SecretKeyType::Version1_0(k),
+			KeyPairType::ExternalVersion1_0(operations) =>
+				SecretKeyType::ExternalVersion1_0(operations),
 		}
 	}
 }
@@ -97,7 +282,8 @@ impl Into<SecretKeyType> for KeyPairType {
 impl Into<DsnpVersionConfig> for &SecretKeyType {
 	fn into(self) -> DsnpVersionConfig {
 		match self {
-			SecretKeyType::Version1_0(_) => DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			SecretKeyType::Version1_0(_) | SecretKeyType::ExternalVersion1_0(_) =>
+				DsnpVersionConfig::new(DsnpVersion::Version1_0),
 		}
 	}
 }
@@ -106,7 +292,8 @@ impl Into<DsnpVersionConfig> for &SecretKeyType {
 impl Into<DsnpVersionConfig> for &KeyPairType {
 	fn into(self) -> DsnpVersionConfig {
 		match self {
-			KeyPairType::Version1_0(_) => DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			KeyPairType::Version1_0(_) | KeyPairType::ExternalVersion1_0(_) =>
+				DsnpVersionConfig::new(DsnpVersion::Version1_0),
 		}
 	}
 }
@@ -159,3 +346,167 @@ impl TryInto<KeyPairType> for GraphKeyPair {
 		}
 	}
 }
+
+/// converts a `KeyPairType` into a `GraphKeyPair`, which fails for key pairs whose secret key is
+/// only used through its operations
+impl TryFrom<&KeyPairType> for GraphKeyPair {
+	type Error = DsnpGraphError;
+
+	fn try_from(key_pair: &KeyPairType) -> DsnpGraphResult<Self> {
+		match key_pair {
+			KeyPairType::Version1_0(k) => Ok(GraphKeyPair {
+				key_type: GraphKeyType::X25519,
+				public_key: k.public_key.to_vec(),
+				secret_key: k.secret_key.to_vec(),
+			}),
+			KeyPairType::ExternalVersion1_0(_) => Err(DsnpGraphError::InvalidInput(
+				"Secret key of an external key pair can not be exported".into(),
+			)),
+		}
+	}
+}
+
+/// X25519 public keys with a small order, which would result in a predictable shared secret
+/// (last byte is compared without its most significant bit)
+const X25519_SMALL_ORDER_POINTS: [[u8; 32]; 7] = [
+	// 0 (order 4)
+	[0x00; 32],
+	// 1 (order 1)
+	[
+		0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+		0x00, 0x00,
+	],
+	// order 8
+	[
+		0xe0, 0xeb, 0x7a, 0x7c, 0x3b, 0x41, 0xb8, 0xae, 0x16, 0x56, 0xe3, 0xfa, 0xf1, 0x9f, 0xc4,
+		0x6a, 0xda, 0x09, 0x8d, 0xeb, 0x9c, 0x32, 0xb1, 0xfd, 0x86, 0x62, 0x05, 0x16, 0x5f, 0x49,
+		0xb8, 0x00,
+	],
+	// order 8
+	[
+		0x5f, 0x9c, 0x95, 0xbc, 0xa3, 0x50, 0x8c, 0x24, 0xb1, 0xd0, 0xb1, 0x55, 0x9c, 0x83, 0xef,
+		0x5b, 0x04, 0x44, 0x5c, 0xc4, 0x58, 0x1c, 0x8e, 0x86, 0xd8, 0x22, 0x4e, 0xdd, 0xd0, 0x9f,
+		0x11, 0x57,
+	],
+	// p - 1 (order 2)
+	[
+		0xec, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+		0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+		0xff, 0x7f,
+	],
+	// p (order 4)
+	[
+		0xed, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+		0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+		0xff, 0x7f,
+	],
+	// p + 1 (order 1)
+	[
+		0xee, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+		0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+		0xff, 0x7f,
+	],
+];
+
+/// validates that `public_key` is a well-formed public key of `key_type` that is safe to publish
+#[log_result_err(Level::Info)]
+pub fn validate_public_key(key_type: GraphKeyType, public_key: &[u8]) -> DsnpGraphResult<()> {
+	match key_type {
+		GraphKeyType::X25519 => {
+			let key =
+				PublicKey::try_from(public_key).map_err(|_| DsnpGraphError::InvalidPublicKey)?;
+			let mut normalized = key.to_vec();
+			normalized[31] &= 0x7f;
+			if X25519_SMALL_ORDER_POINTS.iter().any(|point| point[..] == normalized[..]) {
+				return Err(DsnpGraphError::InvalidPublicKey)
+			}
+			Ok(())
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use dsnp_graph_config::{ConnectionType::Friendship, MAINNET_CONFIG};
+
+	#[test]
+	fn schema_codec_config_should_resolve_schema_parameters() {
+		let environment = Environment::Mainnet;
+		let schema_id = MAINNET_CONFIG
+			.get_schema_id_from_connection_type(Friendship(PrivacyType::Private))
+			.unwrap();
+
+		let config = SchemaCodecConfig::resolve(&environment, schema_id).unwrap();
+
+		assert_eq!(config.schema_id, schema_id);
+		assert_eq!(config.dsnp_version, DsnpVersion::Version1_0);
+		assert_eq!(config.connection_type, Friendship(PrivacyType::Private));
+		assert_eq!(config.compression_format, CompressionFormat::Deflate);
+		assert_eq!(config.version_config, DsnpVersionConfig::new(DsnpVersion::Version1_0));
+		assert!(config.is_encrypted());
+		assert_eq!(
+			DsnpVersionConfig::from_schema_id(&environment, schema_id).unwrap(),
+			config.version_config
+		);
+	}
+
+	#[test]
+	fn schema_codec_config_with_unknown_schema_should_fail() {
+		assert!(matches!(
+			SchemaCodecConfig::resolve(&Environment::Mainnet, 10_000),
+			Err(DsnpGraphError::InvalidSchemaId(10_000, _))
+		));
+		assert!(matches!(
+			DsnpVersionConfig::from_schema_id(&Environment::Mainnet, 10_000),
+			Err(DsnpGraphError::InvalidSchemaId(10_000, _))
+		));
+	}
+
+	#[test]
+	fn get_secret_key_raw_should_only_return_secret_keys_of_owned_key_pairs() {
+		let key_pair = StackKeyPair::gen();
+		let owned = KeyPairType::Version1_0(key_pair.clone());
+		let external = KeyPairType::ExternalVersion1_0(Arc::new(key_pair.clone()));
+
+		assert_eq!(owned.get_secret_key_raw(), Some(key_pair.secret_key.to_vec()));
+		assert_eq!(external.get_secret_key_raw(), None);
+	}
+
+	#[test]
+	fn validate_public_key_should_accept_generated_key() {
+		let key_pair = StackKeyPair::gen();
+
+		assert!(validate_public_key(GraphKeyType::X25519, &key_pair.public_key.to_vec()).is_ok());
+	}
+
+	#[test]
+	fn validate_public_key_should_reject_keys_with_invalid_length() {
+		let key_pair = StackKeyPair::gen();
+
+		for key in [
+			vec![],
+			b"27893788291911998228288282".to_vec(),
+			[key_pair.public_key.to_vec(), vec![1]].concat(),
+		] {
+			assert!(matches!(
+				validate_public_key(GraphKeyType::X25519, &key),
+				Err(DsnpGraphError::InvalidPublicKey)
+			));
+		}
+	}
+
+	#[test]
+	fn validate_public_key_should_reject_small_order_points() {
+		let mut with_high_bit = X25519_SMALL_ORDER_POINTS[2];
+		with_high_bit[31] |= 0x80;
+
+		for key in X25519_SMALL_ORDER_POINTS.iter().chain(std::iter::once(&with_high_bit)) {
+			assert!(matches!(
+				validate_public_key(GraphKeyType::X25519, key),
+				Err(DsnpGraphError::InvalidPublicKey)
+			));
+		}
+	}
+}
@@ -117,6 +117,16 @@ impl DsnpPrid {
 		assert_eq!(d.len(), PRID_LEN_IN_BYTES, "Prid size should be {} bytes", PRID_LEN_IN_BYTES);
 		Self { inner: d }
 	}
+
+	/// Getter for the raw bytes of this PRId
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.inner
+	}
+
+	/// Estimated number of bytes used by this PRId in memory
+	pub fn memory_footprint(&self) -> usize {
+		std::mem::size_of::<Self>() + self.inner.len()
+	}
 }
 
 /// Serialization of avro fixed type
@@ -1,19 +1,88 @@
 //! Definition and implementations to support encryption/decryption for private data
-use crate::dsnp::dsnp_configs::{PublicKeyType, SecretKeyType};
+//!
+//! # Deterministic sealing
+//! Sealed boxes use a new random ephemeral key pair for each encryption, so exporting the same
+//! page twice results in different payloads. `encrypt_deterministic` derives the ephemeral key
+//! pair using HKDF-SHA512 instead, which allows idempotent retries. Following rules keep it safe
+//! - the secret key of the recipient is the input key material, so only the owner of the key pair
+//! can reproduce the ephemeral secret key. This is why it only supports sealing to the own key pair.
+//! - the digest of the plain data is part of the derivation info, so different plain data never
+//! reuses the same ephemeral key pair and nonce, which would leak the xor of the plain data
+//! - the context, such as key id, page id and previous content hash, separates the pages so
+//! identical pages are not linkable by their encrypted payloads
+//!
+//! The only thing leaked compared to random sealing is that exporting identical content for the
+//! same context results in identical payloads.
+//!
+//! # Multi-recipient sealing
+//! `encrypt_multi` allows more than one key pair, such as a recovery key, to decrypt the same
+//! data. The plain data is encrypted once using a random content key, and the content key is sealed
+//! to each of the recipients. The encrypted data is laid out as
+//! - `MULTI_RECIPIENT_MAGIC` followed by the format version and the number of recipients
+//! - a sealed content key for each recipient
+//! - the nonce and the encrypted plain data
+//!
+//! `decrypt` detects the layout by its prefix and tries the secret key against every sealed
+//! content key, falling back to a single recipient sealed box otherwise.
+use crate::dsnp::dsnp_configs::{KeyPairType, PublicKeyType, SecretKeyType};
 use dryoc::{
-	classic::crypto_box::{crypto_box_seal, crypto_box_seal_open},
-	constants::CRYPTO_BOX_SEALBYTES,
+	classic::{
+		crypto_box::{crypto_box_easy, crypto_box_seal, crypto_box_seed_keypair},
+		crypto_generichash::{
+			crypto_generichash_final, crypto_generichash_init, crypto_generichash_update,
+		},
+		crypto_hash::crypto_hash_sha512,
+		crypto_secretbox::{
+			crypto_secretbox_easy, crypto_secretbox_keygen, crypto_secretbox_open_easy, Key, Nonce,
+		},
+	},
+	constants::{
+		CRYPTO_BOX_NONCEBYTES, CRYPTO_BOX_PUBLICKEYBYTES, CRYPTO_BOX_SEALBYTES,
+		CRYPTO_BOX_SEEDBYTES, CRYPTO_HASH_SHA512_BYTES, CRYPTO_SECRETBOX_KEYBYTES,
+		CRYPTO_SECRETBOX_MACBYTES, CRYPTO_SECRETBOX_NONCEBYTES,
+	},
 	dryocbox::ByteArray,
+	rng::copy_randombytes,
 };
 use dsnp_graph_config::errors::{DsnpGraphError, DsnpGraphResult};
 use log::Level;
 use log_result_proc_macro::log_result_err;
+use zeroize::Zeroize;
+
+/// Domain separation salt of the derived ephemeral key pairs
+const DETERMINISTIC_SEALING_SALT: &[u8] = b"dsnp-graph-sdk/deterministic-sealing/v1";
+
+/// Prefix of data sealed to multiple recipients
+pub const MULTI_RECIPIENT_MAGIC: &[u8] = b"DSMR";
+
+/// Version of the multi-recipient layout
+const MULTI_RECIPIENT_VERSION: u8 = 1;
+
+/// Size of the content key sealed to a single recipient
+const SEALED_CONTENT_KEY_BYTES: usize = CRYPTO_SECRETBOX_KEYBYTES + CRYPTO_BOX_SEALBYTES;
 
 /// Common trait for different encryption algorithms
 pub trait EncryptionBehavior {
 	/// encrypt the plain_data
 	fn encrypt(&self, plain_data: &[u8], input: &PublicKeyType) -> DsnpGraphResult<Vec<u8>>;
 
+	/// encrypt the plain_data for the public key of the key pair, deriving the randomness from
+	/// the secret key, the context and the plain data, so the same input always results in the
+	/// same encrypted data
+	fn encrypt_deterministic(
+		&self,
+		plain_data: &[u8],
+		key_pair: &KeyPairType,
+		context: &[u8],
+	) -> DsnpGraphResult<Vec<u8>>;
+
+	/// encrypt the plain_data once so it can be decrypted by any of the provided public keys
+	fn encrypt_multi(
+		&self,
+		plain_data: &[u8],
+		inputs: &[PublicKeyType],
+	) -> DsnpGraphResult<Vec<u8>>;
+
 	/// decrypt the encrypted_data
 	fn decrypt(&self, encrypted_data: &[u8], input: &SecretKeyType) -> DsnpGraphResult<Vec<u8>>;
 }
@@ -36,30 +105,138 @@ impl EncryptionBehavior for SealBox {
 		}
 	}
 
+	#[log_result_err(Level::Info)]
+	fn encrypt_deterministic(
+		&self,
+		plain_data: &[u8],
+		key_pair: &KeyPairType,
+		context: &[u8],
+	) -> DsnpGraphResult<Vec<u8>> {
+		let operations = key_pair.operations();
+		let public_key = operations.public_key();
+		let mut plain_digest = [0u8; CRYPTO_HASH_SHA512_BYTES];
+		crypto_hash_sha512(&mut plain_digest, plain_data);
+		let mut info = context.to_vec();
+		info.extend_from_slice(&plain_digest);
+		let mut seed = [0u8; CRYPTO_BOX_SEEDBYTES];
+		let derived = operations.derive_key_material(DETERMINISTIC_SEALING_SALT, &info, &mut seed);
+		if derived.is_err() {
+			seed.zeroize();
+		}
+		derived?;
+		let (ephemeral_public, mut ephemeral_secret) = crypto_box_seed_keypair(&seed);
+		seed.zeroize();
+
+		// same nonce derivation as sealed boxes, so it can be opened using `decrypt`
+		let mut nonce = [0u8; CRYPTO_BOX_NONCEBYTES];
+		let mut state = crypto_generichash_init(None, CRYPTO_BOX_NONCEBYTES)
+			.map_err(|e| DsnpGraphError::EncryptionError(e.to_string()))?;
+		crypto_generichash_update(&mut state, &ephemeral_public);
+		crypto_generichash_update(&mut state, public_key.as_array());
+		crypto_generichash_final(state, &mut nonce)
+			.map_err(|e| DsnpGraphError::EncryptionError(e.to_string()))?;
+
+		let mut encrypted = vec![0u8; plain_data.len().saturating_add(CRYPTO_BOX_SEALBYTES)];
+		encrypted[..CRYPTO_BOX_PUBLICKEYBYTES].copy_from_slice(&ephemeral_public);
+		let result = crypto_box_easy(
+			&mut encrypted[CRYPTO_BOX_PUBLICKEYBYTES..],
+			plain_data,
+			&nonce,
+			public_key.as_array(),
+			&ephemeral_secret,
+		);
+		ephemeral_secret.zeroize();
+		result.map_err(|e| DsnpGraphError::EncryptionError(e.to_string()))?;
+		Ok(encrypted)
+	}
+
+	#[log_result_err(Level::Info)]
+	fn encrypt_multi(
+		&self,
+		plain_data: &[u8],
+		inputs: &[PublicKeyType],
+	) -> DsnpGraphResult<Vec<u8>> {
+		let recipient_count = u8::try_from(inputs.len()).ok().filter(|count| *count > 0).ok_or(
+			DsnpGraphError::EncryptionError(format!(
+				"Unsupported number of recipients: {}",
+				inputs.len()
+			)),
+		)?;
+		let mut content_key: Key = crypto_secretbox_keygen();
+		let mut encrypted = MULTI_RECIPIENT_MAGIC.to_vec();
+		encrypted.extend_from_slice(&[MULTI_RECIPIENT_VERSION, recipient_count]);
+		for input in inputs {
+			let sealed_key = self.encrypt(&content_key, input);
+			if sealed_key.is_err() {
+				content_key.zeroize();
+			}
+			encrypted.extend_from_slice(&sealed_key?);
+		}
+
+		let mut nonce: Nonce = [0u8; CRYPTO_SECRETBOX_NONCEBYTES];
+		copy_randombytes(&mut nonce);
+		let mut ciphertext = vec![0u8; plain_data.len() + CRYPTO_SECRETBOX_MACBYTES];
+		let result = crypto_secretbox_easy(&mut ciphertext, plain_data, &nonce, &content_key);
+		content_key.zeroize();
+		result.map_err(|e| DsnpGraphError::EncryptionError(e.to_string()))?;
+		encrypted.extend_from_slice(&nonce);
+		encrypted.extend_from_slice(&ciphertext);
+		Ok(encrypted)
+	}
+
 	#[log_result_err(Level::Info)]
 	fn decrypt(&self, encrypted_data: &[u8], input: &SecretKeyType) -> DsnpGraphResult<Vec<u8>> {
-		match input {
-			SecretKeyType::Version1_0(key) => {
-				let mut plain =
-					vec![0u8; encrypted_data.len().saturating_sub(CRYPTO_BOX_SEALBYTES)];
-				crypto_box_seal_open(
-					plain.as_mut_slice(),
-					encrypted_data,
-					key.public_key.as_array(),
-					key.secret_key.as_array(),
-				)
-				.map_err(|e| DsnpGraphError::DecryptionError(e.to_string()))?;
-				Ok(plain)
-			},
+		if let Some(plain) = self.decrypt_multi(encrypted_data, input) {
+			return Ok(plain)
+		}
+		input.operations().open_sealed_box(encrypted_data)
+	}
+}
+
+impl SealBox {
+	/// opens data sealed to multiple recipients using any sealed content key that the secret key
+	/// can open, returns None if the data is not in the multi-recipient layout or can not be opened
+	fn decrypt_multi(&self, encrypted_data: &[u8], input: &SecretKeyType) -> Option<Vec<u8>> {
+		let header_len = MULTI_RECIPIENT_MAGIC.len() + 2;
+		let header = encrypted_data.get(..header_len)?;
+		if !header.starts_with(MULTI_RECIPIENT_MAGIC) ||
+			header[header_len - 2] != MULTI_RECIPIENT_VERSION
+		{
+			return None
+		}
+		let keys_len = header[header_len - 1] as usize * SEALED_CONTENT_KEY_BYTES;
+		let sealed_keys = encrypted_data.get(header_len..header_len + keys_len)?;
+		let body = encrypted_data.get(header_len + keys_len..)?;
+		if body.len() < CRYPTO_SECRETBOX_NONCEBYTES + CRYPTO_SECRETBOX_MACBYTES {
+			return None
 		}
+		let (nonce, ciphertext) = body.split_at(CRYPTO_SECRETBOX_NONCEBYTES);
+		let nonce: &Nonce = nonce.try_into().ok()?;
+
+		sealed_keys.chunks_exact(SEALED_CONTENT_KEY_BYTES).find_map(|sealed_key| {
+			let mut opened = input.operations().open_sealed_box(sealed_key).ok()?;
+			let mut content_key: Key = [0u8; CRYPTO_SECRETBOX_KEYBYTES];
+			let copied = opened.len() == CRYPTO_SECRETBOX_KEYBYTES;
+			if copied {
+				content_key.copy_from_slice(&opened);
+			}
+			opened.zeroize();
+			if !copied {
+				return None
+			}
+			let mut plain = vec![0u8; ciphertext.len() - CRYPTO_SECRETBOX_MACBYTES];
+			let result = crypto_secretbox_open_easy(&mut plain, ciphertext, nonce, &content_key);
+			content_key.zeroize();
+			result.ok().map(|_| plain)
+		})
 	}
 }
 
 #[cfg(test)]
 mod test {
 	use super::*;
-	use crate::dsnp::dsnp_configs::KeyPairType;
 	use dryoc::keypair::StackKeyPair;
+	use std::collections::HashSet;
 
 	#[test]
 	fn sealbox_should_encrypt_and_decrypt_successfully() {
@@ -75,6 +252,74 @@ mod test {
 		assert_eq!(decrypted, plain_data);
 	}
 
+	#[test]
+	fn sealbox_multi_recipient_encryption_should_be_decryptable_by_each_recipient() {
+		let plain_data = vec![83, 98, 0, 10, 234, 88, 23, 54, 23, 23, 109, 198, 111, 70, 2, 89];
+		let recipients: Vec<_> =
+			(0..3).map(|_| KeyPairType::Version1_0(StackKeyPair::gen())).collect();
+		let other_key_pair = KeyPairType::Version1_0(StackKeyPair::gen());
+		let public_keys: Vec<PublicKeyType> = recipients.iter().map(|k| k.into()).collect();
+
+		let encrypted = SealBox.encrypt_multi(&plain_data, &public_keys).unwrap();
+		let no_recipients = SealBox.encrypt_multi(&plain_data, &[]);
+
+		assert!(encrypted.starts_with(MULTI_RECIPIENT_MAGIC));
+		assert_eq!(
+			encrypted.len(),
+			MULTI_RECIPIENT_MAGIC.len() +
+				2 + 3 * SEALED_CONTENT_KEY_BYTES +
+				CRYPTO_SECRETBOX_NONCEBYTES +
+				plain_data.len() +
+				CRYPTO_SECRETBOX_MACBYTES
+		);
+		for recipient in recipients {
+			assert_eq!(SealBox.decrypt(&encrypted, &recipient.into()).unwrap(), plain_data);
+		}
+		assert!(SealBox.decrypt(&encrypted, &other_key_pair.into()).is_err());
+		assert!(matches!(no_recipients, Err(DsnpGraphError::EncryptionError(_))));
+	}
+
+	#[test]
+	fn sealbox_deterministic_encryption_should_be_idempotent_and_decryptable() {
+		let plain_data = vec![83, 98, 0, 10, 234, 88, 23, 54, 23, 23, 109, 198, 111, 70, 2, 89];
+		let key_pair = KeyPairType::Version1_0(StackKeyPair::gen());
+
+		let encrypted = SealBox.encrypt_deterministic(&plain_data, &key_pair, b"context").unwrap();
+		let retried = SealBox.encrypt_deterministic(&plain_data, &key_pair, b"context").unwrap();
+		let decrypted = SealBox.decrypt(&encrypted, &key_pair.into()).unwrap();
+
+		assert_eq!(encrypted, retried);
+		assert_eq!(encrypted.len(), plain_data.len() + CRYPTO_BOX_SEALBYTES);
+		assert_eq!(decrypted, plain_data);
+	}
+
+	#[test]
+	fn sealbox_deterministic_encryption_should_not_reuse_ephemeral_keys() {
+		let plain_data = vec![83, 98, 0, 10, 234, 88, 23, 54, 23, 23, 109, 198, 111, 70, 2, 89];
+		let mut other_plain_data = plain_data.clone();
+		other_plain_data[0] = !other_plain_data[0];
+		let key_pair = KeyPairType::Version1_0(StackKeyPair::gen());
+		let other_key_pair = KeyPairType::Version1_0(StackKeyPair::gen());
+		let ephemeral_key = |encrypted: &Vec<u8>| encrypted[..CRYPTO_BOX_PUBLICKEYBYTES].to_vec();
+
+		let encrypted = SealBox.encrypt_deterministic(&plain_data, &key_pair, b"page 1").unwrap();
+		let other_plain =
+			SealBox.encrypt_deterministic(&other_plain_data, &key_pair, b"page 1").unwrap();
+		let other_context =
+			SealBox.encrypt_deterministic(&plain_data, &key_pair, b"page 2").unwrap();
+		let other_key =
+			SealBox.encrypt_deterministic(&plain_data, &other_key_pair, b"page 1").unwrap();
+		let random = SealBox.encrypt(&plain_data, &(&key_pair).into()).unwrap();
+
+		let ephemeral_keys: HashSet<_> =
+			[&encrypted, &other_plain, &other_context, &other_key, &random]
+				.into_iter()
+				.map(ephemeral_key)
+				.collect();
+		assert_eq!(ephemeral_keys.len(), 5);
+		assert!(SealBox.decrypt(&encrypted, &other_key_pair.into()).is_err());
+	}
+
 	#[test]
 	fn sealbox_decrypting_corrupted_data_should_fail() {
 		let plain_data = vec![83, 98, 0, 10, 234, 88, 23, 54, 23, 23, 109, 198, 111, 70, 2, 89];
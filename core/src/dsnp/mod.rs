@@ -1,4 +1,6 @@
 //! Defines and implements [DSNP](https://spec.dsnp.org/DSNP/Graph.html) specification of social graph.
+pub mod api;
+pub mod chain_adapter;
 pub mod compression;
 pub mod dsnp_configs;
 pub mod dsnp_types;
@@ -4,10 +4,7 @@ use crate::dsnp::{
 	dsnp_types::{DsnpPrid, DsnpUserId},
 };
 use dryoc::{
-	classic::{
-		crypto_box::crypto_box_beforenm,
-		crypto_secretbox::{crypto_secretbox_detached, Nonce},
-	},
+	classic::crypto_secretbox::{crypto_secretbox_detached, Nonce},
 	constants::CRYPTO_SECRETBOX_MACBYTES,
 	kdf::{Key, StackKdf},
 	types::ByteArray,
@@ -16,7 +13,6 @@ use dsnp_graph_config::errors::{DsnpGraphError, DsnpGraphResult};
 use log::Level;
 use log_result_proc_macro::log_result_err;
 use std::ops::Deref;
-use zeroize::Zeroizing;
 
 const PRI_CONTEXT: &[u8] = b"PRIdCtx0";
 
@@ -80,12 +76,9 @@ impl PridProvider for DsnpPrid {
 		b_public_key: &PublicKeyType,
 	) -> DsnpGraphResult<Key> {
 		// calculate shared secret
-		let root_shared = match (a_secret_key, b_public_key) {
-			(SecretKeyType::Version1_0(a_pair), PublicKeyType::Version1_0(b_public)) =>
-				Zeroizing::new(crypto_box_beforenm(
-					b_public.as_array(),
-					a_pair.secret_key.as_array(),
-				)),
+		let root_shared = match b_public_key {
+			PublicKeyType::Version1_0(b_public) =>
+				a_secret_key.operations().derive_shared_key(b_public)?,
 		};
 
 		// // derive a new key form pri context
@@ -1,5 +1,5 @@
 use crate::dsnp::{
-	dsnp_configs::{DsnpVersionConfig, PublicKeyType, SecretKeyType},
+	dsnp_configs::{DsnpVersionConfig, KeyPairType, PublicKeyType, SecretKeyType},
 	dsnp_types::{DsnpInnerGraph, DsnpPublicKey, PrivateGraphChunk},
 };
 use dsnp_graph_config::errors::DsnpGraphResult;
@@ -30,4 +30,18 @@ pub trait DsnpWriter {
 		dsnp_version_config: &DsnpVersionConfig,
 		encryption_input: &PublicKeyType,
 	) -> DsnpGraphResult<Vec<u8>>;
+	/// write private graph to binary using deterministic encryption for the key pair, where the
+	/// same graph and context always result in the same binary
+	fn write_private_graph_deterministic(
+		graph: &PrivateGraphChunk,
+		dsnp_version_config: &DsnpVersionConfig,
+		key_pair: &KeyPairType,
+		context: &[u8],
+	) -> DsnpGraphResult<Vec<u8>>;
+	/// write private graph to binary which can be decrypted by any of the provided public keys
+	fn write_private_graph_multi(
+		graph: &PrivateGraphChunk,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_inputs: &[PublicKeyType],
+	) -> DsnpGraphResult<Vec<u8>>;
 }
@@ -2,4 +2,5 @@
 pub mod reader_writer;
 
 /// A utility to read/write data from and to Frequency chain specific implementation of DSNP
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct Frequency;
@@ -1,13 +1,17 @@
 use super::*;
-use crate::dsnp::{
-	compression::{CompressionBehavior, DeflateCompression},
-	dsnp_configs::{DsnpVersionConfig, PublicKeyType, SecretKeyType},
-	dsnp_types::{
-		DsnpInnerGraph, DsnpPublicKey, DsnpUserPrivateGraphChunk, DsnpUserPublicGraphChunk,
-		PrivateGraphChunk,
+use crate::{
+	api::api_types::PageHash,
+	dsnp::{
+		chain_adapter::ChainAdapter,
+		compression::{CompressionBehavior, CompressionFormat, DeflateCompression},
+		dsnp_configs::{DsnpVersionConfig, KeyPairType, PublicKeyType, SecretKeyType},
+		dsnp_types::{
+			DsnpInnerGraph, DsnpPublicKey, DsnpUserPrivateGraphChunk, DsnpUserPublicGraphChunk,
+			PrivateGraphChunk,
+		},
+		reader_writer::{DsnpReader, DsnpWriter},
+		schema::SchemaHandler,
 	},
-	reader_writer::{DsnpReader, DsnpWriter},
-	schema::SchemaHandler,
 };
 use dsnp_graph_config::errors::DsnpGraphResult;
 use log::Level;
@@ -21,9 +25,8 @@ impl DsnpReader for Frequency {
 	}
 
 	fn read_public_graph(data: &[u8]) -> DsnpGraphResult<DsnpInnerGraph> {
-		let chunk = SchemaHandler::read_public_graph_chunk(data)?;
-		let decompressed = DeflateCompression::decompress(&chunk.compressed_public_graph)?;
-		SchemaHandler::read_inner_graph(&decompressed)
+		let (inner_graph, _) = Frequency::read_public_graph_with_format(data)?;
+		Ok(inner_graph)
 	}
 
 	fn read_private_graph(
@@ -31,16 +34,43 @@ impl DsnpReader for Frequency {
 		dsnp_version_config: &DsnpVersionConfig,
 		decryption_input: &SecretKeyType,
 	) -> DsnpGraphResult<PrivateGraphChunk> {
+		let (chunk, _) =
+			Frequency::read_private_graph_with_format(data, dsnp_version_config, decryption_input)?;
+		Ok(chunk)
+	}
+}
+
+impl Frequency {
+	/// reading public graph from binary and detecting the compression format it was stored with
+	pub fn read_public_graph_with_format(
+		data: &[u8],
+	) -> DsnpGraphResult<(DsnpInnerGraph, CompressionFormat)> {
+		let chunk = SchemaHandler::read_public_graph_chunk(data)?;
+		let (decompressed, format) =
+			CompressionFormat::detect_and_decompress(&chunk.compressed_public_graph)?;
+		Ok((SchemaHandler::read_inner_graph(&decompressed)?, format))
+	}
+
+	/// reading private graph from binary and detecting the compression format it was stored with
+	pub fn read_private_graph_with_format(
+		data: &[u8],
+		dsnp_version_config: &DsnpVersionConfig,
+		decryption_input: &SecretKeyType,
+	) -> DsnpGraphResult<(PrivateGraphChunk, CompressionFormat)> {
 		let chunk = SchemaHandler::read_private_graph_chunk(data)?;
 		let decrypted_compressed = dsnp_version_config
 			.get_algorithm()
 			.decrypt(&chunk.encrypted_compressed_private_graph, decryption_input)?;
-		let decompressed = DeflateCompression::decompress(&decrypted_compressed)?;
-		Ok(PrivateGraphChunk {
-			prids: chunk.prids,
-			key_id: chunk.key_id,
-			inner_graph: SchemaHandler::read_inner_graph(&decompressed)?,
-		})
+		let (decompressed, format) =
+			CompressionFormat::detect_and_decompress(&decrypted_compressed)?;
+		Ok((
+			PrivateGraphChunk {
+				prids: chunk.prids,
+				key_id: chunk.key_id,
+				inner_graph: SchemaHandler::read_inner_graph(&decompressed)?,
+			},
+			format,
+		))
 	}
 }
 
@@ -74,12 +104,220 @@ impl DsnpWriter for Frequency {
 			encrypted_compressed_private_graph: encrypted_compressed,
 		})
 	}
+
+	fn write_private_graph_deterministic(
+		graph: &PrivateGraphChunk,
+		dsnp_version_config: &DsnpVersionConfig,
+		key_pair: &KeyPairType,
+		context: &[u8],
+	) -> DsnpGraphResult<Vec<u8>> {
+		let inner_serialized = SchemaHandler::write_inner_graph(&graph.inner_graph)?;
+		let compressed_inner = DeflateCompression::compress(&inner_serialized)?;
+		let encrypted_compressed = dsnp_version_config.get_algorithm().encrypt_deterministic(
+			&compressed_inner,
+			key_pair,
+			context,
+		)?;
+		SchemaHandler::write_private_graph_chunk(&DsnpUserPrivateGraphChunk {
+			key_id: graph.key_id,
+			prids: graph.prids.to_owned(),
+			encrypted_compressed_private_graph: encrypted_compressed,
+		})
+	}
+
+	fn write_private_graph_multi(
+		graph: &PrivateGraphChunk,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_inputs: &[PublicKeyType],
+	) -> DsnpGraphResult<Vec<u8>> {
+		let inner_serialized = SchemaHandler::write_inner_graph(&graph.inner_graph)?;
+		let compressed_inner = DeflateCompression::compress(&inner_serialized)?;
+		let encrypted_compressed = dsnp_version_config
+			.get_algorithm()
+			.encrypt_multi(&compressed_inner, encryption_inputs)?;
+		SchemaHandler::write_private_graph_chunk(&DsnpUserPrivateGraphChunk {
+			key_id: graph.key_id,
+			prids: graph.prids.to_owned(),
+			encrypted_compressed_private_graph: encrypted_compressed,
+		})
+	}
+}
+
+/// Hash algorithm used by the chain to calculate the `PageHash` of stored page contents
+pub trait PageHasher {
+	/// calculates the hash of the page content as stored on chain
+	fn hash(&self, content: &[u8]) -> PageHash;
+}
+
+/// XxHash32 with seed 0 which is the page hash used by Frequency stateful storage
+#[derive(Clone, PartialEq, Debug, Eq, Hash)]
+pub struct XxHash32;
+
+impl XxHash32 {
+	const PRIME_1: u32 = 0x9E3779B1;
+	const PRIME_2: u32 = 0x85EBCA77;
+	const PRIME_3: u32 = 0xC2B2AE3D;
+	const PRIME_4: u32 = 0x27D4EB2F;
+	const PRIME_5: u32 = 0x165667B1;
+
+	/// calculates XxHash32 of the input using the provided seed
+	pub fn hash_with_seed(input: &[u8], seed: u32) -> u32 {
+		let read_u32 = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+		let round = |acc: u32, lane: u32| {
+			acc.wrapping_add(lane.wrapping_mul(Self::PRIME_2))
+				.rotate_left(13)
+				.wrapping_mul(Self::PRIME_1)
+		};
+
+		let mut stripes = input.chunks_exact(16);
+		let mut hash = match input.len() >= 16 {
+			true => {
+				let mut acc = [
+					seed.wrapping_add(Self::PRIME_1).wrapping_add(Self::PRIME_2),
+					seed.wrapping_add(Self::PRIME_2),
+					seed,
+					seed.wrapping_sub(Self::PRIME_1),
+				];
+				for stripe in stripes.by_ref() {
+					for (i, lane) in stripe.chunks_exact(4).enumerate() {
+						acc[i] = round(acc[i], read_u32(lane));
+					}
+				}
+				acc[0]
+					.rotate_left(1)
+					.wrapping_add(acc[1].rotate_left(7))
+					.wrapping_add(acc[2].rotate_left(12))
+					.wrapping_add(acc[3].rotate_left(18))
+			},
+			false => seed.wrapping_add(Self::PRIME_5),
+		};
+		hash = hash.wrapping_add(input.len() as u32);
+
+		let mut words = stripes.remainder().chunks_exact(4);
+		for word in words.by_ref() {
+			hash = hash
+				.wrapping_add(read_u32(word).wrapping_mul(Self::PRIME_3))
+				.rotate_left(17)
+				.wrapping_mul(Self::PRIME_4);
+		}
+		for byte in words.remainder() {
+			hash = hash
+				.wrapping_add((*byte as u32).wrapping_mul(Self::PRIME_5))
+				.rotate_left(11)
+				.wrapping_mul(Self::PRIME_1);
+		}
+
+		hash ^= hash >> 15;
+		hash = hash.wrapping_mul(Self::PRIME_2);
+		hash ^= hash >> 13;
+		hash = hash.wrapping_mul(Self::PRIME_3);
+		hash ^ (hash >> 16)
+	}
+}
+
+impl PageHasher for XxHash32 {
+	fn hash(&self, content: &[u8]) -> PageHash {
+		// empty pages do not exist on chain and are represented with the default hash
+		match content.is_empty() {
+			true => PageHash::default(),
+			false => Self::hash_with_seed(content, 0),
+		}
+	}
+}
+
+impl Frequency {
+	/// returns the page hash algorithm used on chain for the dsnp version
+	pub fn get_page_hasher(dsnp_version_config: &DsnpVersionConfig) -> Box<dyn PageHasher> {
+		match dsnp_version_config {
+			DsnpVersionConfig::Version1_0 { .. } => Box::new(XxHash32),
+		}
+	}
+
+	/// calculates the hash of the page content as it would be stored on chain
+	pub fn calculate_page_hash(
+		content: &[u8],
+		dsnp_version_config: &DsnpVersionConfig,
+	) -> PageHash {
+		Self::get_page_hasher(dsnp_version_config).hash(content)
+	}
+}
+
+/// the default chain adapter storing pages and keys in the Frequency formats
+impl ChainAdapter for Frequency {
+	fn read_key(&self, data: &[u8]) -> DsnpGraphResult<DsnpPublicKey> {
+		Frequency::read_public_key(data)
+	}
+
+	fn write_key(&self, key: &DsnpPublicKey) -> DsnpGraphResult<Vec<u8>> {
+		Frequency::write_public_key(key)
+	}
+
+	fn read_public_page(
+		&self,
+		data: &[u8],
+	) -> DsnpGraphResult<(DsnpInnerGraph, CompressionFormat)> {
+		Frequency::read_public_graph_with_format(data)
+	}
+
+	fn write_public_page(&self, inner: &DsnpInnerGraph) -> DsnpGraphResult<Vec<u8>> {
+		Frequency::write_public_graph(inner)
+	}
+
+	fn write_inner_graph(&self, inner: &DsnpInnerGraph) -> DsnpGraphResult<Vec<u8>> {
+		SchemaHandler::write_inner_graph(inner)
+	}
+
+	fn read_private_page_key_id(&self, data: &[u8]) -> DsnpGraphResult<u64> {
+		Ok(SchemaHandler::read_private_graph_chunk(data)?.key_id)
+	}
+
+	fn read_private_page(
+		&self,
+		data: &[u8],
+		dsnp_version_config: &DsnpVersionConfig,
+		decryption_input: &SecretKeyType,
+	) -> DsnpGraphResult<(PrivateGraphChunk, CompressionFormat)> {
+		Frequency::read_private_graph_with_format(data, dsnp_version_config, decryption_input)
+	}
+
+	fn write_private_page(
+		&self,
+		graph: &PrivateGraphChunk,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_input: &PublicKeyType,
+	) -> DsnpGraphResult<Vec<u8>> {
+		Frequency::write_private_graph(graph, dsnp_version_config, encryption_input)
+	}
+
+	fn write_private_page_deterministic(
+		&self,
+		graph: &PrivateGraphChunk,
+		dsnp_version_config: &DsnpVersionConfig,
+		key_pair: &KeyPairType,
+		context: &[u8],
+	) -> DsnpGraphResult<Vec<u8>> {
+		Frequency::write_private_graph_deterministic(graph, dsnp_version_config, key_pair, context)
+	}
+
+	fn write_private_page_multi(
+		&self,
+		graph: &PrivateGraphChunk,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_inputs: &[PublicKeyType],
+	) -> DsnpGraphResult<Vec<u8>> {
+		Frequency::write_private_graph_multi(graph, dsnp_version_config, encryption_inputs)
+	}
+
+	fn page_hash(&self, content: &[u8], dsnp_version_config: &DsnpVersionConfig) -> PageHash {
+		Frequency::calculate_page_hash(content, dsnp_version_config)
+	}
 }
 
 #[cfg(test)]
 mod test {
 	use super::*;
 	use crate::dsnp::{
+		compression::LegacyZlibCompression,
 		dsnp_configs::KeyPairType,
 		dsnp_types::{DsnpGraphEdge, DsnpPrid},
 		encryption::SealBox,
@@ -179,6 +417,64 @@ mod test {
 		assert!(deserialized.is_err());
 	}
 
+	#[test]
+	fn public_graph_read_using_legacy_compression_should_detect_format() {
+		let inner_graph: DsnpInnerGraph = vec![
+			DsnpGraphEdge { user_id: 7, since: 12638718 },
+			DsnpGraphEdge { user_id: 167282, since: 28638718 },
+		];
+		let serialized = SchemaHandler::write_public_graph_chunk(&DsnpUserPublicGraphChunk {
+			compressed_public_graph: LegacyZlibCompression::compress(
+				&SchemaHandler::write_inner_graph(&inner_graph).unwrap(),
+			)
+			.unwrap(),
+		})
+		.unwrap();
+
+		let (deserialized, format) = Frequency::read_public_graph_with_format(&serialized)
+			.expect("deserialization should work");
+
+		assert_eq!(deserialized, inner_graph);
+		assert_eq!(format, CompressionFormat::LegacyZlib);
+	}
+
+	#[test]
+	fn private_graph_read_using_legacy_compression_should_detect_format() {
+		let private_graph = PrivateGraphChunk {
+			inner_graph: vec![
+				DsnpGraphEdge { user_id: 7, since: 12638718 },
+				DsnpGraphEdge { user_id: 167282, since: 28638718 },
+			],
+			key_id: 26783,
+			prids: vec![DsnpPrid::new(27737272u64.to_le_bytes().as_slice())],
+		};
+		let key_pair = KeyPairType::Version1_0(StackKeyPair::gen());
+		let dsnp_version_config = DsnpVersionConfig::Version1_0 { algorithm: SealBox };
+		let compressed = LegacyZlibCompression::compress(
+			&SchemaHandler::write_inner_graph(&private_graph.inner_graph).unwrap(),
+		)
+		.unwrap();
+		let serialized = SchemaHandler::write_private_graph_chunk(&DsnpUserPrivateGraphChunk {
+			key_id: private_graph.key_id,
+			prids: private_graph.prids.clone(),
+			encrypted_compressed_private_graph: dsnp_version_config
+				.get_algorithm()
+				.encrypt(&compressed, &(&key_pair).into())
+				.unwrap(),
+		})
+		.unwrap();
+
+		let (deserialized, format) = Frequency::read_private_graph_with_format(
+			&serialized,
+			&dsnp_version_config,
+			&key_pair.into(),
+		)
+		.expect("deserialization should work");
+
+		assert_eq!(deserialized, private_graph);
+		assert_eq!(format, CompressionFormat::LegacyZlib);
+	}
+
 	#[test]
 	fn check_average_size_of_graph_page() {
 		let mut rng = rand::thread_rng();
@@ -211,4 +507,30 @@ mod test {
 		assert_eq!((public_serialized.len() - 1) / page_size + 1, 2);
 		assert_eq!((private_serialized.len() - 1) / page_size + 1, 3);
 	}
+
+	#[test]
+	fn xx_hash_32_should_match_reference_vectors() {
+		assert_eq!(XxHash32::hash_with_seed(b"", 0), 0x02CC5D05);
+		assert_eq!(XxHash32::hash_with_seed(b"a", 0), 0x550D7456);
+		assert_eq!(XxHash32::hash_with_seed(b"abc", 0), 0x32D153FF);
+		assert_eq!(
+			XxHash32::hash_with_seed(b"Nobody inspects the spammish repetition", 0),
+			0xE2293B2F
+		);
+	}
+
+	#[test]
+	fn calculate_page_hash_for_version_1_0_should_use_xx_hash_32() {
+		let dsnp_version_config = DsnpVersionConfig::Version1_0 { algorithm: SealBox };
+
+		assert_eq!(Frequency::calculate_page_hash(&[], &dsnp_version_config), PageHash::default());
+		assert_eq!(Frequency::calculate_page_hash(b"abc", &dsnp_version_config), 0x32D153FF);
+		assert_eq!(
+			Frequency::calculate_page_hash(
+				b"Nobody inspects the spammish repetition",
+				&dsnp_version_config
+			),
+			0xE2293B2F
+		);
+	}
 }
@@ -1,28 +1,34 @@
 #![allow(dead_code)]
 use crate::{
 	api::api_types::*,
-	dsnp::{dsnp_configs::DsnpVersionConfig, dsnp_types::*},
+	dsnp::{
+		chain_adapter::{default_chain_adapter, SharedChainAdapter},
+		dsnp_configs::{DsnpVersionConfig, PublicKeyType},
+		dsnp_types::*,
+	},
 	graph::{
-		key_manager::{UserKeyManagerBase, USER_KEY_MANAGER},
-		page::{PrivatePageDataProvider, PublicPageDataProvider, RemovedPageDataProvider},
-		page_capacities::PAGE_CAPACITY_MAP,
+		key_manager::{SharedUserKeyManager, USER_KEY_MANAGER},
+		page::RemovedPageDataProvider,
+		page_cache::DecryptedPageCache,
 		updates::UpdateEvent,
 	},
 	util::{
-		time::duration_days_since,
+		page_capacity::environment_page_capacities,
+		state_hash::StateHasher,
+		time::{system_time_provider, SharedTimeProvider},
 		transactional_hashmap::{Transactional, TransactionalHashMap},
 	},
 };
 use dsnp_graph_config::{
 	errors::{DsnpGraphError, DsnpGraphResult},
-	Environment, SchemaId,
+	Capability, Config, ConnectionType, Environment, SchemaId,
 };
 use log::Level;
 use log_result_proc_macro::log_result_err;
 use std::{
-	collections::{BTreeMap, HashMap, HashSet},
+	collections::{BTreeMap, BTreeSet, HashMap, HashSet},
 	iter::Peekable,
-	sync::{Arc, RwLock},
+	sync::Mutex,
 };
 
 use super::page::GraphPage;
@@ -36,6 +42,18 @@ pub enum PageFullnessMode {
 	Aggressive,
 }
 
+/// Options used to estimate the payload size of pages while packing them, the encryption mode is
+/// overridden to multi-recipient when additional recipients are set since those payloads are never
+/// smaller than single recipient ones, so the estimate holds for all encryption modes
+const PAYLOAD_ESTIMATE_OPTIONS: ExportOptions = ExportOptions {
+	page_overflow_policy: PageOverflowPolicy::Error,
+	nonce_mode: NonceMode::Random,
+	encryption_mode: EncryptionMode::SingleRecipient,
+};
+
+/// Number of imported versions of each page kept to report the changes since an older version
+const PAGE_HISTORY_LENGTH: usize = 3;
+
 /// Graph structure to hold pages of connections of a single type
 #[derive(Debug, Clone)]
 pub struct Graph {
@@ -43,7 +61,23 @@ pub struct Graph {
 	user_id: DsnpUserId,
 	schema_id: SchemaId,
 	pages: PageMap,
-	user_key_manager: Arc<RwLock<dyn UserKeyManagerBase + 'static + Send + Sync>>,
+	/// page id of each connection, kept in sync with `pages` to avoid scanning them on lookups
+	connection_index: TransactionalHashMap<DsnpUserId, PageId>,
+	/// metadata of private pages imported without the secret keys to decrypt them
+	encrypted_pages: TransactionalHashMap<PageId, PageMetadata>,
+	/// connections removed from imported pages since they were imported on another page as well,
+	/// these pages are rewritten on export
+	repaired_pages: TransactionalHashMap<PageId, Vec<DsnpUserId>>,
+	/// connections of the last imported versions of each page by their content hash, oldest first,
+	/// which are kept when the pages are cleared to import newer versions
+	page_history: TransactionalHashMap<PageId, Vec<(PageHash, Vec<DsnpUserId>)>>,
+	user_key_manager: SharedUserKeyManager,
+	time_provider: SharedTimeProvider,
+	/// reads and writes the chain specific payloads of the pages
+	chain_adapter: SharedChainAdapter,
+	/// public keys that private pages are sealed to in addition to the active key when
+	/// `EncryptionMode::MultiRecipientV1` is used
+	additional_recipients: Vec<PublicKeyType>,
 }
 
 impl PartialEq for Graph {
@@ -64,10 +98,18 @@ impl Transactional for Graph {
 			}
 		}
 		self.pages.commit();
+		self.connection_index.commit();
+		self.encrypted_pages.commit();
+		self.repaired_pages.commit();
+		self.page_history.commit();
 	}
 
 	fn rollback(&mut self) {
 		self.pages.rollback();
+		self.connection_index.rollback();
+		self.encrypted_pages.rollback();
+		self.repaired_pages.rollback();
+		self.page_history.rollback();
 		let page_ids: Vec<_> = self.pages.inner().keys().copied().collect();
 		for pid in page_ids {
 			if let Some(g) = self.pages.get_mut(&pid) {
@@ -79,16 +121,69 @@ impl Transactional for Graph {
 
 impl Graph {
 	/// Create a new, empty Graph
-	pub fn new<E>(
+	pub fn new(
+		environment: Environment,
+		user_id: DsnpUserId,
+		schema_id: SchemaId,
+		user_key_manager: SharedUserKeyManager,
+	) -> Self {
+		Self::with_time_provider(
+			environment,
+			user_id,
+			schema_id,
+			user_key_manager,
+			system_time_provider(),
+		)
+	}
+
+	/// Create a new, empty Graph using provided time source
+	pub fn with_time_provider(
 		environment: Environment,
 		user_id: DsnpUserId,
 		schema_id: SchemaId,
-		user_key_manager: Arc<RwLock<E>>,
-	) -> Self
-	where
-		E: UserKeyManagerBase + 'static + Send + Sync,
-	{
-		Self { environment, user_id, schema_id, pages: PageMap::new(), user_key_manager }
+		user_key_manager: SharedUserKeyManager,
+		time_provider: SharedTimeProvider,
+	) -> Self {
+		Self {
+			environment,
+			user_id,
+			schema_id,
+			pages: PageMap::new(),
+			connection_index: TransactionalHashMap::new(),
+			encrypted_pages: TransactionalHashMap::new(),
+			repaired_pages: TransactionalHashMap::new(),
+			page_history: TransactionalHashMap::new(),
+			user_key_manager,
+			time_provider,
+			chain_adapter: default_chain_adapter(),
+			additional_recipients: vec![],
+		}
+	}
+
+	/// Getter for the time source of this graph
+	pub fn time_provider(&self) -> &SharedTimeProvider {
+		&self.time_provider
+	}
+
+	/// Getter for the chain adapter of this graph
+	pub fn chain_adapter(&self) -> &SharedChainAdapter {
+		&self.chain_adapter
+	}
+
+	/// Setter for the chain adapter used to read and write the payloads of the pages
+	pub fn set_chain_adapter(&mut self, chain_adapter: SharedChainAdapter) {
+		self.chain_adapter = chain_adapter;
+	}
+
+	/// Getter for the public keys that private pages are sealed to in addition to the active key
+	pub fn additional_recipients(&self) -> &Vec<PublicKeyType> {
+		&self.additional_recipients
+	}
+
+	/// Setter for the public keys that private pages are sealed to in addition to the active key
+	/// when exported using `EncryptionMode::MultiRecipientV1`
+	pub fn set_additional_recipients(&mut self, additional_recipients: Vec<PublicKeyType>) {
+		self.additional_recipients = additional_recipients;
 	}
 
 	/// Get total number of connections in graph
@@ -105,11 +200,17 @@ impl Graph {
 	#[cfg(test)]
 	pub fn set_pages(&mut self, pages: PageMap) {
 		self.pages = pages;
+		self.rebuild_connection_index();
+	}
+
+	/// Setter for UserKeyManager in Graph
+	pub fn set_user_key_manager(&mut self, user_key_manager: SharedUserKeyManager) {
+		self.user_key_manager = user_key_manager;
 	}
 
 	/// Getter for UserKeyManager in Graph
 	#[cfg(test)]
-	pub fn get_user_key_mgr(&self) -> Arc<RwLock<dyn UserKeyManagerBase + 'static + Send + Sync>> {
+	pub fn get_user_key_mgr(&self) -> SharedUserKeyManager {
 		self.user_key_manager.clone()
 	}
 
@@ -123,15 +224,18 @@ impl Graph {
 			.inner()
 			.keys()
 			.cloned()
+			.chain(self.encrypted_pages.inner().keys().cloned())
 			.chain(updated_pages.keys().cloned())
 			.collect::<HashSet<PageId>>();
-		(0..=(self.environment.get_config().max_page_id as PageId))
-			.find(|&pid| !existing_pages.contains(&pid))
+		(0..=self.environment.get_config().max_page_id).find(|&pid| !existing_pages.contains(&pid))
 	}
 
 	/// Remove all pages from this graph
 	pub fn clear(&mut self) {
 		self.pages.clear();
+		self.connection_index.clear();
+		self.encrypted_pages.clear();
+		self.repaired_pages.clear();
 	}
 
 	/// Get connection type of this graph
@@ -142,6 +246,20 @@ impl Graph {
 			.expect("Connection type should exist!")
 	}
 
+	/// fails with `UnsupportedOperation` if the connection type of the graph does not support the
+	/// capability required by the operation
+	fn require_capability(&self, capability: Capability, operation: &str) -> DsnpGraphResult<()> {
+		let connection_type = self.get_connection_type();
+		if !connection_type.supports(capability) {
+			return Err(DsnpGraphError::UnsupportedOperation(
+				operation.to_string(),
+				capability,
+				connection_type,
+			))
+		}
+		Ok(())
+	}
+
 	/// Get schema id of this graph
 	pub fn get_schema_id(&self) -> SchemaId {
 		self.schema_id
@@ -153,12 +271,45 @@ impl Graph {
 	}
 
 	/// Import bundle of pages as a Public Graph
-	#[log_result_err(Level::Info)]
 	pub fn import_public(
 		&mut self,
 		connection_type: ConnectionType,
 		pages: &Vec<PageData>,
 	) -> DsnpGraphResult<()> {
+		let pages: Vec<PageDataRef> = pages.iter().map(PageDataRef::from).collect();
+		self.import_public_borrowed(connection_type, &pages, false).map(|_| ())
+	}
+
+	/// Verifies that the content hash of each page matches the hash of its content as calculated
+	/// by the chain adapter
+	pub fn verify_content_hashes(
+		&self,
+		dsnp_version_config: &DsnpVersionConfig,
+		pages: &[PageDataRef],
+	) -> DsnpGraphResult<()> {
+		for page in pages {
+			let calculated = self.chain_adapter.page_hash(page.content, dsnp_version_config);
+			if calculated != page.content_hash {
+				return Err(DsnpGraphError::PageContentHashMismatch(
+					page.page_id,
+					page.content_hash,
+					calculated,
+				))
+			}
+		}
+		Ok(())
+	}
+
+	/// Import bundle of borrowed pages as a Public Graph, returning the repairs of connections
+	/// which were imported on more than one page. With `merge_pages` only the provided pages are
+	/// replaced and the other pages of the graph are kept.
+	#[log_result_err(Level::Info)]
+	pub fn import_public_borrowed(
+		&mut self,
+		connection_type: ConnectionType,
+		pages: &[PageDataRef],
+		merge_pages: bool,
+	) -> DsnpGraphResult<Vec<ConnectionRepair>> {
 		if connection_type != self.get_connection_type() {
 			return Err(DsnpGraphError::IncorrectConnectionType(format!(
 				"Expected {:?} but got {:?}",
@@ -169,10 +320,10 @@ impl Graph {
 		let max_page_id = self.environment.get_config().max_page_id;
 		let mut page_map = HashMap::new();
 		for page in pages.iter() {
-			if page.page_id > max_page_id as PageId {
+			if page.page_id > max_page_id {
 				return Err(DsnpGraphError::InvalidPageId(page.page_id))
 			}
-			match GraphPage::try_from(page) {
+			match GraphPage::from_public_page(*page, self.chain_adapter.as_ref()) {
 				Err(e) => return Err(DsnpGraphError::from(e)),
 				Ok(p) => {
 					page_map.insert(page.page_id, p);
@@ -180,22 +331,36 @@ impl Graph {
 			};
 		}
 
-		self.pages.clear();
-		for (page_id, page) in page_map {
-			self.pages.insert(page_id, page);
-		}
-
-		Ok(())
+		self.set_imported_pages(page_map, merge_pages)
 	}
 
 	/// Import bundle of pages as a Private Graph
-	#[log_result_err(Level::Info)]
 	pub fn import_private(
 		&mut self,
 		dsnp_version_config: &DsnpVersionConfig,
 		connection_type: ConnectionType,
 		pages: &[PageData],
 	) -> DsnpGraphResult<()> {
+		let pages: Vec<PageDataRef> = pages.iter().map(PageDataRef::from).collect();
+		self.import_private_borrowed(dsnp_version_config, connection_type, &pages, None, false)
+			.map(|_| ())
+	}
+
+	/// Import bundle of borrowed pages as a Private Graph, reusing the pages of the cache that
+	/// were decrypted from the same content, and returns the repairs of connections which were
+	/// imported on more than one page. With `merge_pages` only the provided pages are replaced.
+	///
+	/// The cache is only locked while looking up and storing pages, so graphs of different users
+	/// can decrypt their pages at the same time.
+	#[log_result_err(Level::Info)]
+	pub fn import_private_borrowed(
+		&mut self,
+		dsnp_version_config: &DsnpVersionConfig,
+		connection_type: ConnectionType,
+		pages: &[PageDataRef],
+		page_cache: Option<&Mutex<DecryptedPageCache>>,
+		merge_pages: bool,
+	) -> DsnpGraphResult<Vec<ConnectionRepair>> {
 		if connection_type != self.get_connection_type() {
 			return Err(DsnpGraphError::IncorrectConnectionType(format!(
 				"Expected {:?} but got {:?}",
@@ -212,26 +377,407 @@ impl Graph {
 			.get_all_resolved_keys();
 		let mut page_map = HashMap::new();
 		for page in pages.iter() {
-			if page.page_id > max_page_id as PageId {
+			if page.page_id > max_page_id {
 				return Err(DsnpGraphError::InvalidPageId(page.page_id))
 			}
-			match GraphPage::try_from((page, dsnp_version_config, &keys)) {
-				Err(e) => return Err(DsnpGraphError::from(e)),
-				Ok(p) => {
-					p.verify_prid_len(self.get_connection_type())?;
-					page_map.insert(page.page_id, p);
+			let lock_cache = || {
+				page_cache
+					.map(|cache| cache.lock().unwrap_or_else(|e| e.into_inner()))
+					.filter(|cache| cache.is_enabled())
+			};
+			let cached = lock_cache().map(|mut cache| {
+				let cache_key = (self.user_id, self.schema_id, page.page_id);
+				let digest = DecryptedPageCache::digest(page.content);
+				let cached_page = cache.get(&cache_key, page.content_hash, &digest, &keys);
+				(cache_key, digest, cached_page)
+			});
+			let p = match cached {
+				Some((_, _, Some(p))) => p,
+				Some((cache_key, digest, None)) => {
+					let p = GraphPage::from_private_page(
+						*page,
+						dsnp_version_config,
+						&keys,
+						self.chain_adapter.as_ref(),
+					)?;
+					if let Some(mut cache) = lock_cache() {
+						cache.insert(cache_key, digest, &p, &keys);
+					}
+					p
 				},
+				None => GraphPage::from_private_page(
+					*page,
+					dsnp_version_config,
+					&keys,
+					self.chain_adapter.as_ref(),
+				)?,
 			};
+			p.verify_prid_len(self.get_connection_type())?;
+			page_map.insert(page.page_id, p);
+		}
+
+		self.set_imported_pages(page_map, merge_pages)
+	}
+
+	/// Replaces the pages of this graph with the imported pages after removing connections which
+	/// are imported on more than one page from all of them except the page with the lowest id.
+	/// With `merge_pages` only the imported page ids are replaced, and the kept pages take part in
+	/// removing the connections imported on more than one page.
+	fn set_imported_pages(
+		&mut self,
+		mut page_map: HashMap<PageId, GraphPage>,
+		merge_pages: bool,
+	) -> DsnpGraphResult<Vec<ConnectionRepair>> {
+		let mut page_ids: Vec<PageId> = page_map.keys().copied().collect();
+		page_ids.sort();
+		for page_id in page_ids.iter() {
+			let page = &page_map[page_id];
+			self.record_page_version(
+				*page_id,
+				page.content_hash(),
+				page.connections().iter().map(|c| c.user_id).collect(),
+			);
+		}
+		if merge_pages {
+			for page_id in page_ids.iter() {
+				self.encrypted_pages.remove(page_id);
+				self.repaired_pages.remove(page_id);
+			}
+			for (page_id, page) in self.pages.inner() {
+				page_map.entry(*page_id).or_insert_with(|| page.clone());
+			}
+			page_ids = page_map.keys().copied().collect();
+			page_ids.sort();
+		} else {
+			self.encrypted_pages.clear();
+			self.repaired_pages.clear();
+		}
+		let mut kept_pages: HashMap<DsnpUserId, PageId> = HashMap::new();
+		let mut repairs = vec![];
+		for page_id in page_ids {
+			let page = page_map.get_mut(&page_id).expect("page should exist");
+			let mut duplicates: Vec<DsnpUserId> = vec![];
+			for connection in page.connections() {
+				match kept_pages.get(&connection.user_id) {
+					// duplicates within a single page are left as they are
+					Some(kept_page_id) if *kept_page_id == page_id => {},
+					Some(kept_page_id) => {
+						duplicates.push(connection.user_id);
+						repairs.push(ConnectionRepair {
+							dsnp_user_id: self.user_id,
+							schema_id: self.schema_id,
+							connection_id: connection.user_id,
+							kept_page_id: *kept_page_id,
+							repaired_page_id: page_id,
+						});
+					},
+					None => {
+						kept_pages.insert(connection.user_id, page_id);
+					},
+				}
+			}
+			for connection_id in duplicates {
+				page.remove_connection_and_prid(&connection_id)?;
+			}
 		}
 
 		self.pages.clear();
 		for (page_id, page) in page_map {
 			self.pages.insert(page_id, page);
 		}
+		for repair in repairs.iter() {
+			self.repaired_pages
+				.entry(repair.repaired_page_id)
+				.or_default()
+				.push(repair.connection_id);
+		}
+		self.rebuild_connection_index();
+
+		Ok(repairs)
+	}
+
+	/// Removes connections which are imported more than once within the same page, keeping their
+	/// first occurrence, and returns the repairs. The repaired pages are rewritten on export.
+	pub fn remove_duplicates_within_pages(&mut self) -> Vec<ConnectionRepair> {
+		let mut page_ids: Vec<PageId> = self.pages.inner().keys().copied().collect();
+		page_ids.sort();
+		let mut repairs = vec![];
+		for page_id in page_ids {
+			let page = self.pages.get_mut(&page_id).expect("page should exist");
+			for connection_id in page.remove_duplicate_connections() {
+				self.repaired_pages.entry(page_id).or_default().push(connection_id);
+				repairs.push(ConnectionRepair {
+					dsnp_user_id: self.user_id,
+					schema_id: self.schema_id,
+					connection_id,
+					kept_page_id: page_id,
+					repaired_page_id: page_id,
+				});
+			}
+		}
+		repairs
+	}
+
+	/// Returns the connections removed from each imported page since they were imported on
+	/// another page as well, sorted by page id
+	pub fn get_repaired_pages(&self) -> Vec<(PageId, Vec<DsnpUserId>)> {
+		let mut repaired_pages: Vec<(PageId, Vec<DsnpUserId>)> = self
+			.repaired_pages
+			.inner()
+			.iter()
+			.map(|(page_id, connections)| (*page_id, connections.clone()))
+			.collect();
+		repaired_pages.sort();
+		repaired_pages
+	}
+
+	/// Import only the metadata of a bundle of borrowed private pages, used when the secret keys
+	/// to decrypt them are not available. The recorded pages are not overwritten by new pages.
+	/// With `merge_pages` only the provided page ids are replaced.
+	#[log_result_err(Level::Info)]
+	pub fn import_private_metadata(
+		&mut self,
+		connection_type: ConnectionType,
+		pages: &[PageDataRef],
+		merge_pages: bool,
+	) -> DsnpGraphResult<()> {
+		if connection_type != self.get_connection_type() ||
+			connection_type.privacy_type() != PrivacyType::Private
+		{
+			return Err(DsnpGraphError::IncorrectConnectionType(format!(
+				"Expected {:?} but got {:?}",
+				self.get_connection_type(),
+				connection_type
+			)))
+		}
+
+		let max_page_id = self.environment.get_config().max_page_id;
+		if let Some(page) = pages.iter().find(|page| page.page_id > max_page_id) {
+			return Err(DsnpGraphError::InvalidPageId(page.page_id))
+		}
+
+		if merge_pages {
+			for page in pages.iter() {
+				self.pages.remove(&page.page_id);
+				self.repaired_pages.remove(&page.page_id);
+			}
+			self.rebuild_connection_index();
+		} else {
+			self.clear();
+		}
+		for page in pages.iter() {
+			self.encrypted_pages.insert(page.page_id, PageMetadata::from(*page));
+		}
 
 		Ok(())
 	}
 
+	/// Returns the statistics of this graph, including the pages imported without their keys
+	pub fn stats(&self) -> UserGraphStats {
+		let mut encrypted_pages: Vec<PageMetadata> =
+			self.encrypted_pages.inner().values().copied().collect();
+		encrypted_pages.sort_by_key(|page| page.page_id);
+		let page_hashes = self
+			.pages
+			.inner()
+			.iter()
+			.map(|(page_id, page)| (*page_id, page.content_hash()))
+			.chain(encrypted_pages.iter().map(|page| (page.page_id, page.content_hash)))
+			.collect();
+
+		UserGraphStats {
+			dsnp_user_id: self.user_id,
+			schema_id: self.schema_id,
+			decrypted_pages: self.pages.len(),
+			connections: self.len(),
+			page_hashes,
+			encrypted_pages,
+		}
+	}
+
+	/// Records the connections of an imported version of a page, dropping the oldest versions
+	fn record_page_version(
+		&mut self,
+		page_id: PageId,
+		content_hash: PageHash,
+		connections: Vec<DsnpUserId>,
+	) {
+		let versions = self.page_history.entry(page_id).or_default();
+		versions.retain(|(hash, _)| *hash != content_hash);
+		versions.push((content_hash, connections));
+		if versions.len() > PAGE_HISTORY_LENGTH {
+			versions.remove(0);
+		}
+	}
+
+	/// Returns the connections of an imported version of a page
+	fn page_version(&self, page_id: PageId, content_hash: PageHash) -> Option<&[DsnpUserId]> {
+		self.page_history
+			.get(&page_id)?
+			.iter()
+			.find(|(hash, _)| *hash == content_hash)
+			.map(|(_, connections)| connections.as_slice())
+	}
+
+	/// Reports the connections added and removed by the currently imported pages since the
+	/// imported versions of the pages with the provided content hashes. Pending changes are
+	/// ignored, and changed pages whose connections are unknown on either side are reported as
+	/// unresolved.
+	pub fn changes_since(&self, page_hashes: &[(PageId, PageHash)]) -> ChangesReport {
+		let previous: BTreeMap<PageId, PageHash> = page_hashes.iter().copied().collect();
+		let current = self.stats().page_hashes;
+		let changed_pages: Vec<PageId> = previous
+			.keys()
+			.chain(current.keys())
+			.copied()
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.filter(|page_id| previous.get(page_id) != current.get(page_id))
+			.collect();
+
+		let mut removed = BTreeSet::new();
+		let mut added = BTreeSet::new();
+		let mut unresolved_pages = vec![];
+		for page_id in changed_pages.iter() {
+			let previous_connections = match previous.get(page_id) {
+				Some(hash) => self.page_version(*page_id, *hash),
+				None => Some(&[][..]),
+			};
+			let current_connections = match current.get(page_id) {
+				Some(hash) if self.pages.inner().contains_key(page_id) =>
+					self.page_version(*page_id, *hash),
+				Some(_) => None,
+				None => Some(&[][..]),
+			};
+			match (previous_connections, current_connections) {
+				(Some(previous_connections), Some(current_connections)) => {
+					removed.extend(previous_connections.iter().copied());
+					added.extend(current_connections.iter().copied());
+				},
+				_ => unresolved_pages.push(*page_id),
+			}
+		}
+		let moved: Vec<DsnpUserId> = added.intersection(&removed).copied().collect();
+		for connection in moved {
+			added.remove(&connection);
+			removed.remove(&connection);
+		}
+
+		ChangesReport {
+			dsnp_user_id: self.user_id,
+			schema_id: self.schema_id,
+			added: added.into_iter().collect(),
+			removed: removed.into_iter().collect(),
+			changed_pages,
+			unresolved_pages,
+			page_hashes: current,
+		}
+	}
+
+	/// Rebuilds the index of connections to their pages from the current pages
+	fn rebuild_connection_index(&mut self) {
+		self.connection_index.clear();
+		for (page_id, page) in self.pages.inner() {
+			for connection in page.connections() {
+				self.connection_index.insert(connection.user_id, *page_id);
+			}
+		}
+	}
+
+	/// Returns the id of the key used to decrypt each imported private page, sorted by page id
+	pub fn get_page_key_usage(&self) -> Vec<(PageId, u64)> {
+		let mut usage: Vec<_> = self
+			.pages
+			.inner()
+			.values()
+			.filter_map(|page| page.key_id().map(|key_id| (page.page_id(), key_id)))
+			.collect();
+		usage.sort();
+		usage
+	}
+
+	/// Estimated number of bytes used by this graph and its pages in memory
+	pub fn memory_footprint(&self) -> usize {
+		std::mem::size_of::<Self>() +
+			self.pages
+				.inner()
+				.values()
+				.map(|page| std::mem::size_of::<PageId>() + page.memory_footprint())
+				.sum::<usize>() +
+			self.connection_index.len() * std::mem::size_of::<(DsnpUserId, PageId)>() +
+			self.encrypted_pages.len() * std::mem::size_of::<(PageId, PageMetadata)>() +
+			self.repaired_pages
+				.inner()
+				.values()
+				.map(|ids| {
+					std::mem::size_of::<PageId>() + ids.len() * std::mem::size_of::<DsnpUserId>()
+				})
+				.sum::<usize>() +
+			self.page_history
+				.inner()
+				.values()
+				.flatten()
+				.map(|(_, ids)| {
+					std::mem::size_of::<(PageHash, Vec<DsnpUserId>)>() +
+						ids.len() * std::mem::size_of::<DsnpUserId>()
+				})
+				.sum::<usize>()
+	}
+
+	/// feeds the decrypted pages, undecrypted pages and repaired pages of this graph into the
+	/// hasher, each ordered by page id
+	pub fn hash_into(&self, hasher: &mut StateHasher) {
+		let pages: BTreeMap<_, _> = self.pages.inner().iter().collect();
+		hasher.write_u64(pages.len() as u64);
+		for page in pages.values() {
+			page.hash_into(hasher);
+		}
+
+		let encrypted_pages: BTreeMap<_, _> = self.encrypted_pages.inner().iter().collect();
+		hasher.write_u64(encrypted_pages.len() as u64);
+		for metadata in encrypted_pages.values() {
+			hasher.write_u64(metadata.page_id as u64);
+			hasher.write_u64(metadata.content_hash as u64);
+			hasher.write_u64(metadata.content_size as u64);
+		}
+
+		let repaired_pages: BTreeMap<_, _> = self.repaired_pages.inner().iter().collect();
+		hasher.write_u64(repaired_pages.len() as u64);
+		for (page_id, removed) in repaired_pages {
+			hasher.write_u64(*page_id as u64);
+			hasher.write_u64(removed.len() as u64);
+			for dsnp_user_id in removed {
+				hasher.write_u64(*dsnp_user_id);
+			}
+		}
+	}
+
+	/// Returns the ids of connections whose `since` timestamp is more than `older_than_days` ago,
+	/// sorted in ascending order
+	pub fn get_connections_older_than(&self, older_than_days: u64) -> Vec<DsnpUserId> {
+		let mut ids: Vec<_> = self
+			.pages
+			.inner()
+			.values()
+			.flat_map(|page| page.connections())
+			.filter(|c| self.time_provider.duration_days_since(c.since) > older_than_days)
+			.map(|c| c.user_id)
+			.collect();
+		ids.sort();
+		ids
+	}
+
+	/// Returns true if any of the imported pages was stored with a legacy format
+	pub fn has_pages_needing_format_upgrade(&self) -> bool {
+		self.pages.inner().values().any(|page| page.needs_format_upgrade())
+	}
+
+	/// Returns true if any page should be rewritten on export, either to upgrade its legacy format
+	/// or to persist the repair of duplicated connections
+	pub fn has_pages_needing_rewrite(&self) -> bool {
+		self.has_pages_needing_format_upgrade() || self.repaired_pages.len() > 0
+	}
+
 	/// Calculate updates to be sent to the network
 	#[log_result_err(Level::Info)]
 	pub fn calculate_updates(
@@ -239,6 +785,33 @@ impl Graph {
 		dsnp_version_config: &DsnpVersionConfig,
 		updates: &Vec<UpdateEvent>,
 	) -> DsnpGraphResult<Vec<Update>> {
+		let (updates, _) = self.calculate_updates_with_placements(
+			dsnp_version_config,
+			updates,
+			&HashMap::new(),
+			&HashMap::new(),
+			ExportOptions::default(),
+		)?;
+		Ok(updates.into_iter().map(|u| u.update).collect())
+	}
+
+	/// Calculate updates to be sent to the network, placing added connections into their
+	/// preferred pages when capacity allows, and returns the page each added connection landed in
+	/// alongside the connection changes of each updated page
+	///
+	/// added connections use their value in `since_hints` as since, or the current time otherwise
+	///
+	/// `export_options` determines what happens to added connections that do not fit into any
+	/// of the allowed pages and how private pages are encrypted
+	#[log_result_err(Level::Info)]
+	pub fn calculate_updates_with_placements(
+		&self,
+		dsnp_version_config: &DsnpVersionConfig,
+		updates: &Vec<UpdateEvent>,
+		page_hints: &HashMap<DsnpUserId, PageId>,
+		since_hints: &HashMap<DsnpUserId, u64>,
+		export_options: ExportOptions,
+	) -> DsnpGraphResult<(Vec<UpdateWithDetails>, Vec<ConnectionPlacement>)> {
 		let encryption_key = match self.get_connection_type().privacy_type() {
 			PrivacyType::Public => None,
 			PrivacyType::Private => self
@@ -277,7 +850,12 @@ impl Graph {
 			.inner()
 			.iter()
 			.filter_map(|(page_id, page)| {
-				if pages_with_removals.contains(page_id) {
+				// pages stored with a legacy format are rewritten using the current one and
+				// repaired pages are rewritten without their duplicated connections
+				if pages_with_removals.contains(page_id) ||
+					page.needs_format_upgrade() ||
+					self.repaired_pages.get(page_id).is_some()
+				{
 					let mut updated_page = page.clone();
 					updated_page.remove_connections(&ids_to_remove);
 					return Some((*page_id, updated_page))
@@ -287,9 +865,28 @@ impl Graph {
 			})
 			.collect();
 
+		// Place connections with a preferred page first; the ones that don't fit are packed normally
+		let mut ids_to_pack: Vec<DsnpUserId> = Vec::new();
+		for id in ids_to_add.iter() {
+			let placed = match page_hints.get(id) {
+				Some(page_id) => self.try_add_connection_to_preferred_page(
+					&mut updated_pages,
+					*page_id,
+					id,
+					since_hints.get(id).copied(),
+					dsnp_version_config,
+					&encryption_key,
+				)?,
+				None => false,
+			};
+			if !placed {
+				ids_to_pack.push(*id);
+			}
+		}
+
 		// Now try to add new connections into pages already being updated
 		// Note: these pages have already been cloned, so we don't clone them again
-		let mut add_iter = ids_to_add.iter().cloned().peekable();
+		let mut add_iter = ids_to_pack.iter().cloned().peekable();
 		'fullness_mode_loop: for aggressive in
 			vec![PageFullnessMode::Trivial, PageFullnessMode::Aggressive]
 		{
@@ -298,9 +895,10 @@ impl Graph {
 					page,
 					&mut add_iter,
 					aggressive,
+					since_hints,
 					dsnp_version_config,
 					&encryption_key,
-				);
+				)?;
 
 				if let None = add_iter.peek() {
 					break 'fullness_mode_loop
@@ -333,9 +931,10 @@ impl Graph {
 				&mut current_page,
 				&mut add_iter,
 				PageFullnessMode::Aggressive,
+				since_hints,
 				dsnp_version_config,
 				&encryption_key,
-			);
+			)?;
 
 			if page_modified {
 				updated_pages.insert(current_page.page_id(), current_page);
@@ -351,76 +950,266 @@ impl Graph {
 		while let Some(_) = add_iter.peek() {
 			let mut new_page = match self.get_next_available_page_id(&updated_pages) {
 				Some(next_page_id) =>
-					Ok(GraphPage::new(self.get_connection_type().privacy_type(), next_page_id)),
-				None => Err(DsnpGraphError::GraphIsFull),
-			}?;
+					GraphPage::new(self.get_connection_type().privacy_type(), next_page_id),
+				None => match export_options.page_overflow_policy {
+					PageOverflowPolicy::SkipOverflow => break,
+					PageOverflowPolicy::Error =>
+						return Err(self.capacity_exceeded_error(&updated_pages, add_iter.count())),
+				},
+			};
 
-			if self.add_to_page_until_full(
+			if !self.add_to_page_until_full(
 				&mut new_page,
 				&mut add_iter,
 				PageFullnessMode::Aggressive,
+				since_hints,
 				dsnp_version_config,
 				&encryption_key,
-			) {
-				updated_pages.insert(new_page.page_id(), new_page);
+			)? {
+				// the next connection does not fit even into an empty page
+				let id_to_add = *add_iter.peek().ok_or(DsnpGraphError::ConnectionNotFound)?;
+				new_page.add_connection_since(&id_to_add, 0)?;
+				return Err(self.payload_too_large_error(
+					&new_page,
+					&ids_to_add,
+					dsnp_version_config,
+					&encryption_key,
+				));
 			}
+			updated_pages.insert(new_page.page_id(), new_page);
 		}
 
-		self.pages_to_updates(&mut updated_pages, encryption_key, dsnp_version_config, &ids_to_add)
+		// Make sure none of the updated pages exceed the maximum allowed payload size
+		self.split_oversized_pages(
+			&mut updated_pages,
+			&ids_to_add,
+			dsnp_version_config,
+			&encryption_key,
+			export_options.page_overflow_policy,
+		)?;
+
+		let placements = self.get_connection_placements(&updated_pages, &ids_to_add, page_hints);
+		// verifying PRIds may remove connections, so it happens before calculating the details
+		if self.get_connection_type() == ConnectionType::Friendship(PrivacyType::Private) {
+			let encryption_key =
+				encryption_key.as_ref().ok_or(DsnpGraphError::NoResolvedActiveKeyFound)?;
+			for page in updated_pages.values_mut() {
+				self.apply_prids(page, &ids_to_add, encryption_key)?;
+			}
+		}
+		let mut details = self.get_update_details(&updated_pages)?;
+		let updates = self
+			.pages_to_updates(
+				&mut updated_pages,
+				encryption_key,
+				dsnp_version_config,
+				export_options,
+			)?
+			.into_iter()
+			.map(|update| {
+				let details = match &update {
+					Update::PersistPage { page_id, payload, .. } => details
+						.remove(page_id)
+						.map(|details| UpdateDetails { payload_size: payload.len(), ..details }),
+					Update::DeletePage { page_id, .. } => details.remove(page_id),
+					Update::AddKey { .. } => None,
+				};
+				UpdateWithDetails { update, details }
+			})
+			.collect();
+		Ok((updates, placements))
 	}
 
-	/// Function to add as many connections as possible to a page
-	fn add_to_page_until_full(
+	/// Creates the error returned when a page holding a single connection exceeds the maximum
+	/// update payload size
+	fn payload_too_large_error(
 		&self,
-		page: &mut GraphPage,
-		add_iter: &mut Peekable<impl Iterator<Item = u64>>,
-		fullness_mode: PageFullnessMode,
+		page: &GraphPage,
+		ids_to_add: &Vec<DsnpUserId>,
 		dsnp_version_config: &DsnpVersionConfig,
 		encryption_key: &Option<ResolvedKeyPair>,
-	) -> bool {
-		let mut page_modified = false;
-		while let Some(id_to_add) = add_iter.peek() {
-			if let Ok(_) = self.try_add_connection_to_page(
-				page,
-				id_to_add,
-				fullness_mode,
-				dsnp_version_config,
-				encryption_key,
-			) {
-				page_modified = true;
-				let _ = add_iter.next(); // TODO: prefer advance_by(1) once that stabilizes
-			} else {
-				break
-			}
-		}
+	) -> DsnpGraphError {
+		let payload_size =
+			match self.page_to_payload(page, ids_to_add, dsnp_version_config, encryption_key) {
+				Ok(payload) => payload.content.len(),
+				Err(e) => return e,
+			};
+		DsnpGraphError::UpdatePayloadTooLarge(
+			page.page_id(),
+			payload_size,
+			self.environment.get_config().get_max_graph_page_payload_bytes(),
+		)
+	}
 
-		page_modified
+	/// Creates the error returned when connections do not fit into any of the allowed pages
+	fn capacity_exceeded_error(
+		&self,
+		updated_pages: &BTreeMap<PageId, GraphPage>,
+		connections_over: usize,
+	) -> DsnpGraphError {
+		let full_page_ids: BTreeSet<PageId> =
+			self.pages.inner().keys().chain(updated_pages.keys()).copied().collect();
+		DsnpGraphError::GraphCapacityExceeded(
+			self.schema_id,
+			full_page_ids.into_iter().collect(),
+			connections_over,
+		)
 	}
 
-	/// Function to take a vec of updated & removed pages, and return a vec
-	/// of Update payloads.
-	#[log_result_err(Level::Info)]
-	fn pages_to_updates(
+	/// Function to add a connection to its preferred page if the page has enough capacity,
+	/// returns false if the page is full and fails for any other error
+	fn try_add_connection_to_preferred_page(
 		&self,
 		updated_pages: &mut BTreeMap<PageId, GraphPage>,
-		encryption_key: Option<ResolvedKeyPair>,
+		page_id: PageId,
+		connection_id: &DsnpUserId,
+		since: Option<u64>,
 		dsnp_version_config: &DsnpVersionConfig,
-		ids_to_add: &Vec<DsnpUserId>,
-	) -> DsnpGraphResult<Vec<Update>> {
-		// If any pages now empty, remove from updates & add to the remove list
-		let mut removed_pages: Vec<PageData> = Vec::new();
-		updated_pages.retain(|_, page| {
-			if page.is_empty() {
-				removed_pages.push(page.to_removed_page_data());
-				return false
-			}
-			true
-		});
+		encryption_key: &Option<ResolvedKeyPair>,
+	) -> DsnpGraphResult<bool> {
+		if page_id > self.environment.get_config().max_page_id {
+			return Ok(false)
+		}
+
+		let mut page = match updated_pages.get(&page_id).or(self.pages.get(&page_id)) {
+			Some(page) => page.clone(),
+			None => GraphPage::new(self.get_connection_type().privacy_type(), page_id),
+		};
+		match self.try_add_connection_to_page_since(
+			&mut page,
+			connection_id,
+			since,
+			PageFullnessMode::Aggressive,
+			dsnp_version_config,
+			encryption_key,
+		) {
+			Ok(_) => {
+				updated_pages.insert(page_id, page);
+				Ok(true)
+			},
+			Err(e) if e.is_page_full() => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+
+	/// Function to find the pages that added connections landed in
+	fn get_connection_placements(
+		&self,
+		updated_pages: &BTreeMap<PageId, GraphPage>,
+		ids_to_add: &Vec<DsnpUserId>,
+		page_hints: &HashMap<DsnpUserId, PageId>,
+	) -> Vec<ConnectionPlacement> {
+		let ids_to_add: HashSet<_> = ids_to_add.iter().collect();
+		let mut placements: Vec<ConnectionPlacement> = updated_pages
+			.iter()
+			.filter(|(_, page)| page.may_contain_any(ids_to_add.iter().copied()))
+			.flat_map(|(page_id, page)| {
+				page.connections().iter().filter(|c| ids_to_add.contains(&c.user_id)).map(|c| {
+					ConnectionPlacement {
+						dsnp_user_id: c.user_id,
+						schema_id: self.schema_id,
+						page_id: *page_id,
+						preferred_page_id: page_hints.get(&c.user_id).copied(),
+					}
+				})
+			})
+			.collect();
+		placements.sort_by_key(|p| p.dsnp_user_id);
+		placements
+	}
+
+	/// Function to find the connections added to and removed from each updated page and the size
+	/// of its serialized connections, the payload size is set once the page is exported
+	fn get_update_details(
+		&self,
+		updated_pages: &BTreeMap<PageId, GraphPage>,
+	) -> DsnpGraphResult<HashMap<PageId, UpdateDetails>> {
+		let max_payload_size =
+			self.environment.get_config().get_max_graph_page_payload_bytes() as usize;
+		updated_pages
+			.iter()
+			.map(|(page_id, page)| {
+				let previous: BTreeSet<DsnpUserId> = match self.pages.get(page_id) {
+					Some(previous_page) =>
+						previous_page.connections().iter().map(|c| c.user_id).collect(),
+					None => BTreeSet::new(),
+				};
+				let current: BTreeSet<DsnpUserId> =
+					page.connections().iter().map(|c| c.user_id).collect();
+				let uncompressed_size = match page.is_empty() {
+					true => 0,
+					false => self.chain_adapter.write_inner_graph(page.connections())?.len(),
+				};
+				let details = UpdateDetails {
+					added_connections: current.difference(&previous).copied().collect(),
+					removed_connections: previous.difference(&current).copied().collect(),
+					connection_count: current.len(),
+					uncompressed_size,
+					payload_size: 0,
+					max_payload_size,
+				};
+				Ok((*page_id, details))
+			})
+			.collect()
+	}
+
+	/// Function to add as many connections as possible to a page, returns true if the page was
+	/// modified and fails for any error other than the page being full
+	fn add_to_page_until_full(
+		&self,
+		page: &mut GraphPage,
+		add_iter: &mut Peekable<impl Iterator<Item = u64>>,
+		fullness_mode: PageFullnessMode,
+		since_hints: &HashMap<DsnpUserId, u64>,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_key: &Option<ResolvedKeyPair>,
+	) -> DsnpGraphResult<bool> {
+		let mut page_modified = false;
+		while let Some(id_to_add) = add_iter.peek() {
+			match self.try_add_connection_to_page_since(
+				page,
+				id_to_add,
+				since_hints.get(id_to_add).copied(),
+				fullness_mode,
+				dsnp_version_config,
+				encryption_key,
+			) {
+				Ok(_) => {
+					page_modified = true;
+					let _ = add_iter.next(); // TODO: prefer advance_by(1) once that stabilizes
+				},
+				Err(e) if e.is_page_full() => break,
+				Err(e) => return Err(e),
+			}
+		}
+
+		Ok(page_modified)
+	}
+
+	/// Function to take a vec of updated & removed pages, and return a vec
+	/// of Update payloads.
+	#[log_result_err(Level::Info)]
+	fn pages_to_updates(
+		&self,
+		updated_pages: &mut BTreeMap<PageId, GraphPage>,
+		encryption_key: Option<ResolvedKeyPair>,
+		dsnp_version_config: &DsnpVersionConfig,
+		export_options: ExportOptions,
+	) -> DsnpGraphResult<Vec<Update>> {
+		// If any pages now empty, remove from updates & add to the remove list
+		let mut removed_pages: Vec<PageData> = Vec::new();
+		updated_pages.retain(|_, page| {
+			if page.is_empty() {
+				removed_pages.push(page.to_removed_page_data());
+				return false
+			}
+			true
+		});
 
 		let updated_blobs: DsnpGraphResult<Vec<PageData>> = match self.get_connection_type() {
 			ConnectionType::Follow(PrivacyType::Public) |
 			ConnectionType::Friendship(PrivacyType::Public) =>
-				updated_pages.values().map(|page| page.to_public_page_data()).collect(),
+				updated_pages.values().map(|page| self.to_public_page(page)).collect(),
 			ConnectionType::Follow(PrivacyType::Private) => {
 				let encryption_key =
 					encryption_key.ok_or(DsnpGraphError::NoResolvedActiveKeyFound)?;
@@ -428,19 +1217,28 @@ impl Graph {
 					.iter_mut()
 					.map(|(_, page)| {
 						page.clear_prids();
-						page.to_private_page_data(dsnp_version_config, &encryption_key)
+						self.to_private_page(
+							page,
+							dsnp_version_config,
+							&encryption_key,
+							export_options,
+						)
 					})
 					.collect()
 			},
+			// PRIds of private friendship pages are already applied
 			ConnectionType::Friendship(PrivacyType::Private) => {
 				let encryption_key =
 					encryption_key.ok_or(DsnpGraphError::NoResolvedActiveKeyFound)?;
 				updated_pages
-					.iter_mut()
-					.map(|(_, page)| {
-						let mut updated_page = page.clone();
-						self.apply_prids(&mut updated_page, &ids_to_add, &encryption_key)?;
-						updated_page.to_private_page_data(dsnp_version_config, &encryption_key)
+					.values()
+					.map(|page| {
+						self.to_private_page(
+							page,
+							dsnp_version_config,
+							&encryption_key,
+							export_options,
+						)
 					})
 					.collect()
 			},
@@ -460,6 +1258,7 @@ impl Graph {
 	pub fn force_recalculate(
 		&self,
 		dsnp_version_config: &DsnpVersionConfig,
+		export_options: ExportOptions,
 	) -> DsnpGraphResult<Vec<Update>> {
 		// get latest encryption key
 		let encryption_key = match self.get_connection_type().privacy_type() {
@@ -479,14 +1278,19 @@ impl Graph {
 				true => Ok(page.to_removed_page_data()),
 				false => match self.get_connection_type() {
 					ConnectionType::Follow(PrivacyType::Public) |
-					ConnectionType::Friendship(PrivacyType::Public) => page.to_public_page_data(),
+					ConnectionType::Friendship(PrivacyType::Public) => self.to_public_page(page),
 					ConnectionType::Follow(PrivacyType::Private) => {
 						let encryption_key = encryption_key
 							.clone()
 							.ok_or(DsnpGraphError::NoResolvedActiveKeyFound)?;
 						let mut updated_page = page.clone();
 						updated_page.clear_prids();
-						updated_page.to_private_page_data(dsnp_version_config, &encryption_key)
+						self.to_private_page(
+							&updated_page,
+							dsnp_version_config,
+							&encryption_key,
+							export_options,
+						)
 					},
 					ConnectionType::Friendship(PrivacyType::Private) => {
 						let encryption_key = encryption_key
@@ -494,7 +1298,12 @@ impl Graph {
 							.ok_or(DsnpGraphError::NoResolvedActiveKeyFound)?;
 						let mut updated_page = page.clone();
 						self.apply_prids(&mut updated_page, &vec![], &encryption_key)?;
-						updated_page.to_private_page_data(dsnp_version_config, &encryption_key)
+						self.to_private_page(
+							&updated_page,
+							dsnp_version_config,
+							&encryption_key,
+							export_options,
+						)
 					},
 				},
 			};
@@ -509,6 +1318,207 @@ impl Graph {
 		Ok(mapped)
 	}
 
+	/// Returns `DeletePage` updates for all imported pages, sorted by page id, which removes the
+	/// whole graph from the chain
+	pub fn export_removal(&self) -> Vec<Update> {
+		let mut pages: Vec<&GraphPage> = self.pages.inner().values().collect();
+		pages.sort_by_key(|page| page.page_id());
+		pages
+			.into_iter()
+			.map(|page| Update::from((page.to_removed_page_data(), self.user_id, self.schema_id)))
+			.collect()
+	}
+
+	/// Exports all pages with the pending updates applied and imports them back into a scratch
+	/// graph, reporting any connection that did not survive the round-trip. The pages are exported
+	/// the same way `calculate_updates_with_placements` does, using the provided hints and options
+	#[log_result_err(Level::Info)]
+	pub fn verify_roundtrip(
+		&self,
+		dsnp_version_config: &DsnpVersionConfig,
+		updates: &Vec<UpdateEvent>,
+		page_hints: &HashMap<DsnpUserId, PageId>,
+		since_hints: &HashMap<DsnpUserId, u64>,
+		export_options: ExportOptions,
+	) -> DsnpGraphResult<RoundtripReport> {
+		// pages with pending changes override the recalculated version of the same page
+		let mut exported: BTreeMap<PageId, Option<Vec<u8>>> = BTreeMap::new();
+		let recalculated = self.force_recalculate(dsnp_version_config, export_options)?;
+		let (pending, _) = self.calculate_updates_with_placements(
+			dsnp_version_config,
+			updates,
+			page_hints,
+			since_hints,
+			export_options,
+		)?;
+		let pending = pending.into_iter().map(|u| u.update);
+		for update in recalculated.into_iter().chain(pending) {
+			match update {
+				Update::PersistPage { page_id, payload, .. } => {
+					exported.insert(page_id, Some(payload));
+				},
+				Update::DeletePage { page_id, .. } => {
+					exported.insert(page_id, None);
+				},
+				Update::AddKey { .. } => {},
+			}
+		}
+		let pages: Vec<PageData> = exported
+			.into_iter()
+			.filter_map(|(page_id, payload)| payload.map(|content| (page_id, content)))
+			.map(|(page_id, content)| PageData {
+				page_id,
+				content_hash: self.chain_adapter.page_hash(&content, dsnp_version_config),
+				content,
+			})
+			.collect();
+
+		let mut scratch = self.clone();
+		scratch.clear();
+		match self.get_connection_type().privacy_type() {
+			PrivacyType::Public => scratch.import_public(self.get_connection_type(), &pages)?,
+			PrivacyType::Private =>
+				scratch.import_private(dsnp_version_config, self.get_connection_type(), &pages)?,
+		};
+
+		let mut expected: BTreeSet<DsnpUserId> =
+			self.connection_index.inner().keys().copied().collect();
+		for event in updates {
+			match event {
+				UpdateEvent::Add { dsnp_user_id, .. } => expected.insert(*dsnp_user_id),
+				UpdateEvent::Remove { dsnp_user_id, .. } => expected.remove(dsnp_user_id),
+			};
+		}
+		let actual: BTreeSet<DsnpUserId> =
+			scratch.connection_index.inner().keys().copied().collect();
+
+		Ok(RoundtripReport {
+			dsnp_user_id: self.user_id,
+			schema_id: self.schema_id,
+			exported_pages: pages.len(),
+			expected_connections: expected.len(),
+			missing_connections: expected.difference(&actual).copied().collect(),
+			unexpected_connections: actual.difference(&expected).copied().collect(),
+		})
+	}
+
+	/// Checks the imported pages against the spec level rules of DSNP and the chain limits,
+	/// returning the found issues sorted by page id
+	#[log_result_err(Level::Info)]
+	pub fn lint(&self, dsnp_version_config: &DsnpVersionConfig) -> DsnpGraphResult<Vec<LintIssue>> {
+		let connection_type = self.get_connection_type();
+		let config = self.environment.get_config();
+		let now = self.time_provider.time_in_ksecs();
+		let encryption_key = match connection_type.privacy_type() {
+			PrivacyType::Public => None,
+			PrivacyType::Private => self
+				.user_key_manager
+				.read()
+				.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+				.get_resolved_active_key(self.user_id),
+		};
+
+		let sorted_pages: BTreeMap<&PageId, &GraphPage> = self.pages.inner().iter().collect();
+		let mut stored_in: HashMap<DsnpUserId, PageId> = HashMap::new();
+		let mut issues = vec![];
+		for (page_id, page) in sorted_pages {
+			let issue = |rule: LintRule, message: String| {
+				LintIssue::new(self.schema_id, *page_id, rule, message)
+			};
+
+			for edge in page.connections() {
+				if let Some(other_page_id) = stored_in.insert(edge.user_id, *page_id) {
+					issues.push(issue(
+						LintRule::DuplicateConnection,
+						format!(
+							"connection {} is also stored in page {}",
+							edge.user_id, other_page_id
+						),
+					));
+				}
+				if edge.since > now {
+					issues.push(issue(
+						LintRule::FutureTimestamp,
+						format!("connection {} has a since timestamp in the future", edge.user_id),
+					));
+				}
+			}
+			// duplicates are already removed from the repaired pages on import
+			for connection_id in self.repaired_pages.get(page_id).into_iter().flatten() {
+				issues.push(issue(
+					LintRule::DuplicateConnection,
+					format!(
+						"connection {} is also stored in a lower page or earlier in this page and removed on export",
+						connection_id
+					),
+				));
+			}
+
+			if page.connections().windows(2).any(|pair| pair[0].user_id > pair[1].user_id) {
+				issues.push(issue(
+					LintRule::UnsortedConnections,
+					"connections are not sorted by dsnp user id".to_string(),
+				));
+			}
+
+			match connection_type {
+				ConnectionType::Friendship(PrivacyType::Private) =>
+					if page.prids().len() != page.connections().len() {
+						issues.push(issue(
+							LintRule::MissingPrids,
+							format!(
+								"page has {} prids for {} connections",
+								page.prids().len(),
+								page.connections().len()
+							),
+						));
+					},
+				_ =>
+					if !page.prids().is_empty() {
+						issues.push(issue(
+							LintRule::UnexpectedPrids,
+							format!("page of {:?} graph has prids", connection_type),
+						));
+					},
+			}
+
+			// private pages can only be measured if there is an active key to encrypt them with
+			let page_data = match (connection_type.privacy_type(), &encryption_key) {
+				(PrivacyType::Public, _) => Some(self.to_public_page(page)?),
+				(PrivacyType::Private, Some(key)) => Some(self.to_private_page(
+					page,
+					dsnp_version_config,
+					key,
+					ExportOptions::default(),
+				)?),
+				(PrivacyType::Private, None) => None,
+			};
+			if let Some(page_data) = page_data {
+				let size = page_data.content.len();
+				if size > config.max_graph_page_size_bytes as usize {
+					issues.push(issue(
+						LintRule::PageTooLarge,
+						format!(
+							"page size {} exceeds the maximum of {} bytes",
+							size, config.max_graph_page_size_bytes
+						),
+					));
+				} else if size > config.get_max_graph_page_payload_bytes() as usize {
+					issues.push(issue(
+						LintRule::PageExceedsUpdatePayload,
+						format!(
+							"page size {} exceeds the maximum update payload of {} bytes",
+							size,
+							config.get_max_graph_page_payload_bytes()
+						),
+					));
+				}
+			}
+		}
+
+		Ok(issues)
+	}
+
 	/// Create a new Page in the Graph, with the given PageId.
 	///
 	/// Error on duplicate PageId.
@@ -525,13 +1535,14 @@ impl Graph {
 			return Err(DsnpGraphError::NewPageForExistingPageId)
 		}
 
-		self.pages.insert(
-			*page_id,
-			match page {
-				Some(page) => page,
-				None => GraphPage::new(self.get_connection_type().privacy_type(), *page_id),
-			},
-		);
+		let page = match page {
+			Some(page) => page,
+			None => GraphPage::new(self.get_connection_type().privacy_type(), *page_id),
+		};
+		for connection in page.connections() {
+			self.connection_index.insert(connection.user_id, *page_id);
+		}
+		self.pages.insert(*page_id, page);
 		match self.get_page_mut(page_id) {
 			Some(page) => Ok(page),
 			None => Err(DsnpGraphError::FailedToRetrieveGraphPage),
@@ -544,36 +1555,26 @@ impl Graph {
 	}
 
 	/// Retrieve a mutable reference to the page with the given PageId
-	pub fn get_page_mut(&mut self, page_id: &PageId) -> Option<&mut GraphPage> {
+	/// Note: connections should only be changed through the graph to keep the index in sync
+	fn get_page_mut(&mut self, page_id: &PageId) -> Option<&mut GraphPage> {
 		self.pages.get_mut(page_id)
 	}
 
 	/// Boolean function to indicate if a connection is present in the graph
 	pub fn has_connection(&self, dsnp_id: &DsnpUserId) -> bool {
-		self.pages.inner().iter().any(|(_, page)| page.contains(dsnp_id))
+		self.connection_index.inner().contains_key(dsnp_id)
 	}
 
 	/// Return the PageId in which the given connection resides, if found.
 	pub fn find_connection(&self, dsnp_id: &DsnpUserId) -> Option<PageId> {
-		for (id, page) in self.pages.inner().iter() {
-			if page.contains(dsnp_id) {
-				return Some(*id)
-			}
-		}
-
-		None
+		self.connection_index.get(dsnp_id).copied()
 	}
 
 	/// Return all PageIds containing any of the connections in the list
 	pub fn find_connections(&self, ids: &Vec<DsnpUserId>) -> Vec<PageId> {
-		self.pages
-			.inner()
-			.iter()
-			.filter_map(|(page_id, page)| match page.contains_any(ids) {
-				true => Some(*page_id),
-				false => None,
-			})
-			.collect()
+		let page_ids: BTreeSet<PageId> =
+			ids.iter().filter_map(|id| self.find_connection(id)).collect();
+		page_ids.into_iter().collect()
 	}
 
 	/// Add a connection to the specified page.
@@ -596,10 +1597,13 @@ impl Graph {
 				GraphPage::new(self.get_connection_type().privacy_type(), *page_id),
 			);
 		}
+		let now = self.time_provider.time_in_ksecs();
 		match self.get_page_mut(page_id) {
-			Some(page) => page.add_connection(connection_id),
+			Some(page) => page.add_connection_since(connection_id, now),
 			None => Err(DsnpGraphError::FailedToRetrieveGraphPage),
-		}
+		}?;
+		self.connection_index.insert(*connection_id, *page_id);
+		Ok(())
 	}
 
 	/// Remove a connection from the graph.
@@ -614,7 +1618,10 @@ impl Graph {
 		if let Some(page_id) = self.find_connection(connection_id) {
 			return match self.get_page_mut(&page_id) {
 				Some(page) => match page.remove_connection(connection_id) {
-					Ok(()) => Ok(Some(page_id)),
+					Ok(()) => {
+						self.connection_index.remove(connection_id);
+						Ok(Some(page_id))
+					},
 					Err(e) => Err(e),
 				},
 				None => Err(DsnpGraphError::FailedToRetrieveGraphPage),
@@ -625,25 +1632,135 @@ impl Graph {
 		Ok(None)
 	}
 
+	/// returns the public page payload of a page, sorting its connections unless the legacy order
+	/// is configured
+	fn to_public_page(&self, page: &GraphPage) -> DsnpGraphResult<PageData> {
+		page.to_public_page(
+			self.chain_adapter.as_ref(),
+			self.environment.get_config().legacy_public_page_order,
+		)
+	}
+
+	/// returns the private page payload of a page, sealing it to the additional recipients as well
+	/// if multi-recipient encryption is selected, which fails if no additional recipients are set
+	fn to_private_page(
+		&self,
+		page: &GraphPage,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_key: &ResolvedKeyPair,
+		export_options: ExportOptions,
+	) -> DsnpGraphResult<PageData> {
+		let additional_recipients = match export_options.encryption_mode {
+			EncryptionMode::SingleRecipient => None,
+			EncryptionMode::MultiRecipientV1 if self.additional_recipients.is_empty() =>
+				return Err(DsnpGraphError::EncryptionError(
+					"No additional recipients are set for multi-recipient encryption".to_string(),
+				)),
+			EncryptionMode::MultiRecipientV1 => Some(self.additional_recipients.as_slice()),
+		};
+		page.to_private_page(
+			dsnp_version_config,
+			encryption_key,
+			export_options.nonce_mode,
+			additional_recipients,
+			self.chain_adapter.as_ref(),
+		)
+	}
+
 	/// returns one sided friendship connections
 	#[log_result_err(Level::Info)]
 	pub fn get_one_sided_friendships(&self) -> DsnpGraphResult<Vec<DsnpGraphEdge>> {
-		if self.get_connection_type() != ConnectionType::Friendship(PrivacyType::Private) {
-			return Err(DsnpGraphError::CallToPrivateFriendsInPublicGraph)
-		}
+		self.require_capability(Capability::Prids, "get_one_sided_friendships")?;
 
-		let mut result = vec![];
-		for c in self.pages.inner().values().flat_map(|g| g.connections()) {
-			if !self
-				.user_key_manager
-				.read()
-				.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
-				.verify_connection(c.user_id)?
+		let connections: Vec<DsnpGraphEdge> =
+			self.pages.inner().values().flat_map(|g| g.connections()).copied().collect();
+		self.filter_one_sided_friendships(connections)
+	}
+
+	/// returns the provided connections whose counterparts do not have the reverse friendship,
+	/// which allows checking connections that are not imported yet such as pending ones
+	pub fn filter_one_sided_friendships(
+		&self,
+		connections: Vec<DsnpGraphEdge>,
+	) -> DsnpGraphResult<Vec<DsnpGraphEdge>> {
+		let ids: Vec<DsnpUserId> = connections.iter().map(|c| c.user_id).collect();
+		let verified = self
+			.user_key_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+			.verify_connections(&ids)?;
+
+		Ok(connections
+			.into_iter()
+			.zip(verified)
+			.filter(|(_, verified)| !verified)
+			.map(|(c, _)| c)
+			.collect())
+	}
+
+	/// returns the ids of the decrypted pages whose key pair is not resolved anymore, such as the
+	/// ones decrypted by a removed key pair, sorted by page id
+	#[log_result_err(Level::Info)]
+	pub fn get_pages_without_resolved_keys(&self) -> DsnpGraphResult<Vec<PageId>> {
+		let user_key_manager = self
+			.user_key_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?;
+		let mut page_ids: Vec<PageId> = self
+			.pages
+			.inner()
+			.iter()
+			.filter(|(_, page)| {
+				page.key_id()
+					.map_or(false, |key_id| user_key_manager.get_resolved_key(key_id).is_none())
+			})
+			.map(|(page_id, _)| *page_id)
+			.collect();
+		page_ids.sort();
+		Ok(page_ids)
+	}
+
+	/// verifies that each imported prid matches the connection at the same position, skipping
+	/// pages without their resolved key pair and connections without any imported public key.
+	/// Prids are recalculated using all the imported public keys of each connection, so pages
+	/// written before a connection rotated its key are still considered aligned
+	#[log_result_err(Level::Info)]
+	pub fn verify_prid_alignment(&self) -> DsnpGraphResult<()> {
+		self.require_capability(Capability::Prids, "verify_prid_alignment")?;
+
+		let user_key_manager = self
+			.user_key_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?;
+		let mut page_ids: Vec<PageId> = self.pages.inner().keys().copied().collect();
+		page_ids.sort();
+		for page_id in page_ids {
+			let page = self.pages.get(&page_id).ok_or(DsnpGraphError::InvalidPageId(page_id))?;
+			let resolved_key =
+				match page.key_id().and_then(|key_id| user_key_manager.get_resolved_key(key_id)) {
+					Some(resolved_key) => resolved_key,
+					None => continue,
+				};
+
+			let mut mismatched = vec![];
+			for (index, (connection, prid)) in
+				page.connections().iter().zip(page.prids().iter()).enumerate()
 			{
-				result.push(*c)
+				match user_key_manager.calculate_prids(
+					self.user_id,
+					connection.user_id,
+					resolved_key.key_pair.clone().into(),
+				) {
+					Ok(expected) if !expected.contains(prid) => mismatched.push(index),
+					Ok(_) | Err(DsnpGraphError::NoPublicKeyFoundForUser(_)) => (),
+					Err(e) => return Err(e),
+				}
+			}
+			if !mismatched.is_empty() {
+				return Err(DsnpGraphError::PridsMisaligned(page_id, mismatched))
 			}
 		}
-		Ok(result)
+		Ok(())
 	}
 
 	/// verifies prids for friendship from other party and calculates for own side
@@ -654,32 +1771,31 @@ impl Graph {
 		ids_to_add: &Vec<DsnpUserId>,
 		encryption_key: &ResolvedKeyPair,
 	) -> DsnpGraphResult<()> {
-		if self.get_connection_type() != ConnectionType::Friendship(PrivacyType::Private) {
-			return Err(DsnpGraphError::CallToPridsInPublicGraph)
-		}
+		self.require_capability(Capability::Prids, "apply_prids")?;
 
 		// verify connection existence based on prid
 		let max_allowed_stale_days =
 			self.environment.get_config().sdk_max_stale_friendship_days as u64;
-		for c in updated_page
+		// This timestamp condition is eliminating PRID checks for 2 categories of connections
+		// 1. Connections that are just added but not included in `ids_to_add` list
+		// 2. Connections that are added less than `max_allowed_stale_days` since we need some time for the other
+		// side of the connection to act on it
+		let ids_to_verify: Vec<DsnpUserId> = updated_page
 			.connections()
-			.clone()
 			.iter()
 			.filter(|c| !ids_to_add.contains(&c.user_id))
-		{
-			// This timestamp condition is eliminating PRID checks for 2 categories of connections
-			// 1. Connections that are just added but not included in `ids_to_add` list
-			// 2. Connections that are added less than `max_allowed_stale_days` since we need some time for the other
-			// side of the connection to act on it
-			if duration_days_since(c.since) > max_allowed_stale_days &&
-				!self
-					.user_key_manager
-					.read()
-					.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
-					.verify_connection(c.user_id)?
-			{
+			.filter(|c| self.time_provider.duration_days_since(c.since) > max_allowed_stale_days)
+			.map(|c| c.user_id)
+			.collect();
+		let verified = self
+			.user_key_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+			.verify_connections(&ids_to_verify)?;
+		for (id, verified) in ids_to_verify.iter().zip(verified) {
+			if !verified {
 				// connection is removed from the other side
-				updated_page.remove_connection(&c.user_id)?;
+				updated_page.remove_connection(id)?;
 			}
 		}
 
@@ -709,77 +1825,320 @@ impl Graph {
 		dsnp_version_config: &DsnpVersionConfig,
 		encryption_key: &Option<ResolvedKeyPair>,
 	) -> DsnpGraphResult<()> {
-		let connection_type = self.get_connection_type();
-		let max_connections_per_page =
-			*PAGE_CAPACITY_MAP.get(&connection_type).unwrap_or_else(|| {
-				let mut capacities: Vec<&usize> = PAGE_CAPACITY_MAP.values().collect();
-				capacities.sort();
-				capacities.first().unwrap() // default: return smallest capacity value
-			});
+		self.try_add_connection_to_page_since(
+			page,
+			connection_id,
+			None,
+			mode,
+			dsnp_version_config,
+			encryption_key,
+		)
+	}
+
+	/// Same as `try_add_connection_to_page` using the provided since value instead of the
+	/// current time if there is one
+	fn try_add_connection_to_page_since(
+		&self,
+		page: &mut GraphPage,
+		connection_id: &DsnpUserId,
+		since: Option<u64>,
+		mode: PageFullnessMode,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_key: &Option<ResolvedKeyPair>,
+	) -> DsnpGraphResult<()> {
+		let max_connections_per_page = self.max_connections_per_page()?;
 
 		// Regardless of whether we're in aggressive mode, if the page is trivially non-full,
-		// just try and add the connection
-		if page.connections().len() < max_connections_per_page {
-			return page.add_connection(connection_id)
+		// just try and add the connection. Page capacities are calculated based on graph page
+		// size, so this shortcut is not valid if a lower update payload limit is configured.
+		let config = self.environment.get_config();
+		let max_page_size = config.get_max_graph_page_payload_bytes() as usize;
+		let is_payload_limited = max_page_size < config.max_graph_page_size_bytes as usize;
+		let now = since.unwrap_or_else(|| self.time_provider.time_in_ksecs());
+		if page.connections().len() < max_connections_per_page && !is_payload_limited {
+			return page.add_connection_since(connection_id, now)
 		} else if mode == PageFullnessMode::Trivial {
 			return Err(DsnpGraphError::PageTriviallyFull)
 		}
 
-		let max_page_size = self.environment.get_config().max_graph_page_size_bytes as usize;
 		let mut temp_page = page.clone();
-		let _ = temp_page.add_connection(connection_id)?;
+		let _ = temp_page.add_connection_since(connection_id, now)?;
+
+		let blob = self.page_to_payload(
+			&temp_page,
+			&vec![*connection_id],
+			dsnp_version_config,
+			encryption_key,
+		)?;
+		if blob.content.len() > max_page_size {
+			Err(DsnpGraphError::PageAggressivelyFull)
+		} else {
+			page.add_connection_since(connection_id, now)
+		}
+	}
 
-		let page_blob = match connection_type {
+	/// Number of connections that trivially fit into a page of this graph in its environment
+	fn max_connections_per_page(&self) -> DsnpGraphResult<usize> {
+		let config = self.environment.get_config();
+		schema_page_capacity(config, &environment_page_capacities(config), self.schema_id)
+	}
+
+	/// Estimates the remaining capacity of this graph after applying the pending updates
+	///
+	/// Page capacities are benchmarked against the graph page size, so they are scaled down if a
+	/// lower update payload limit is configured. Private pages which could not be decrypted are
+	/// counted as full since their connections are unknown.
+	pub fn remaining_capacity(
+		&self,
+		updates: &Vec<UpdateEvent>,
+	) -> DsnpGraphResult<CapacityReport> {
+		let config = self.environment.get_config();
+		let max_connections_per_page = self.max_connections_per_page()? *
+			config.get_max_graph_page_payload_bytes() as usize /
+			config.max_graph_page_size_bytes as usize;
+		let max_pages = config.max_page_id as usize + 1;
+		let full_page_ids: Vec<PageId> = self
+			.pages
+			.inner()
+			.iter()
+			.filter(|(_, page)| page.connections().len() >= max_connections_per_page)
+			.map(|(page_id, _)| *page_id)
+			.chain(self.encrypted_pages.inner().keys().copied())
+			.collect::<BTreeSet<PageId>>()
+			.into_iter()
+			.collect();
+		let (pending_additions, pending_removals) =
+			updates.iter().fold((0, 0), |(adds, removes), event| match event {
+				UpdateEvent::Add { .. } => (adds + 1, removes),
+				UpdateEvent::Remove { .. } => (adds, removes + 1),
+			});
+
+		let connections = self.len();
+		let total_capacity =
+			max_pages.saturating_sub(self.encrypted_pages.len()) * max_connections_per_page;
+		let expected_connections =
+			(connections + pending_additions).saturating_sub(pending_removals);
+		Ok(CapacityReport {
+			dsnp_user_id: self.user_id,
+			schema_id: self.schema_id,
+			max_pages,
+			used_pages: self.pages.len() + self.encrypted_pages.len(),
+			full_page_ids,
+			connections,
+			pending_additions,
+			pending_removals,
+			remaining_connections: total_capacity.saturating_sub(expected_connections),
+			connections_over: expected_connections.saturating_sub(total_capacity),
+		})
+	}
+
+	/// Serializes a page into the payload that would be exported for it
+	fn page_to_payload(
+		&self,
+		page: &GraphPage,
+		ids_to_add: &Vec<DsnpUserId>,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_key: &Option<ResolvedKeyPair>,
+	) -> DsnpGraphResult<PageData> {
+		let estimate_options = match self.additional_recipients.is_empty() {
+			true => PAYLOAD_ESTIMATE_OPTIONS,
+			false => ExportOptions {
+				encryption_mode: EncryptionMode::MultiRecipientV1,
+				..PAYLOAD_ESTIMATE_OPTIONS
+			},
+		};
+		match self.get_connection_type() {
 			ConnectionType::Follow(PrivacyType::Public) |
-			ConnectionType::Friendship(PrivacyType::Public) => temp_page.to_public_page_data(),
+			ConnectionType::Friendship(PrivacyType::Public) => self.to_public_page(page),
 			ConnectionType::Follow(PrivacyType::Private) => {
 				let encryption_key =
 					encryption_key.as_ref().ok_or(DsnpGraphError::NoResolvedActiveKeyFound)?;
+				let mut temp_page = page.clone();
 				temp_page.clear_prids();
-				temp_page.to_private_page_data(dsnp_version_config, &encryption_key)
+				self.to_private_page(
+					&temp_page,
+					dsnp_version_config,
+					&encryption_key,
+					estimate_options,
+				)
 			},
 			ConnectionType::Friendship(PrivacyType::Private) => {
 				let encryption_key =
 					encryption_key.as_ref().ok_or(DsnpGraphError::NoResolvedActiveKeyFound)?;
-				self.apply_prids(&mut temp_page, &vec![*connection_id], &encryption_key)
-					.expect("Error applying prids to page");
-				temp_page.to_private_page_data(dsnp_version_config, &encryption_key)
+				let mut temp_page = page.clone();
+				self.apply_prids(&mut temp_page, ids_to_add, &encryption_key)?;
+				self.to_private_page(
+					&temp_page,
+					dsnp_version_config,
+					&encryption_key,
+					estimate_options,
+				)
 			},
-		};
-
-		match page_blob {
-			Ok(blob) =>
-				if blob.content.len() > max_page_size {
-					Err(DsnpGraphError::PageAggressivelyFull)
-				} else {
-					return page.add_connection(connection_id)
-				},
-			Err(e) => Err(e),
 		}
 	}
-}
-
-/// Macro to get an iterator to all connections across all GraphPages
-/// within a Graph.
-#[macro_export]
-macro_rules! iter_graph_connections {
-	( $x:expr ) => {{
-		$x.pages()
-			.inner()
-			.values()
-			.flat_map(|p| p.connections().iter().cloned())
-			.collect::<Vec<DsnpGraphEdge>>()
-			.iter()
-	}};
-}
 
-#[cfg(test)]
-mod test {
-	use super::*;
+	/// Splits any updated page whose payload exceeds the maximum allowed payload size by moving
+	/// the overflowing connections into new pages
+	#[log_result_err(Level::Info)]
+	fn split_oversized_pages(
+		&self,
+		updated_pages: &mut BTreeMap<PageId, GraphPage>,
+		ids_to_add: &Vec<DsnpUserId>,
+		dsnp_version_config: &DsnpVersionConfig,
+		encryption_key: &Option<ResolvedKeyPair>,
+		overflow_policy: PageOverflowPolicy,
+	) -> DsnpGraphResult<()> {
+		let config = self.environment.get_config();
+		let max_page_size = config.get_max_graph_page_payload_bytes() as usize;
+		if max_page_size >= config.max_graph_page_size_bytes as usize {
+			// packing already guarantees the graph page size limit
+			return Ok(())
+		}
+
+		let mut overflow: Vec<DsnpGraphEdge> = vec![];
+		for page in updated_pages.values_mut() {
+			while !page.is_empty() &&
+				self.page_to_payload(page, ids_to_add, dsnp_version_config, encryption_key)?
+					.content
+					.len() > max_page_size
+			{
+				if page.connections().len() == 1 {
+					return Err(self.payload_too_large_error(
+						page,
+						ids_to_add,
+						dsnp_version_config,
+						encryption_key,
+					));
+				}
+				let edge = *page.connections().last().ok_or(DsnpGraphError::ConnectionNotFound)?;
+				page.remove_connection(&edge.user_id)?;
+				overflow.push(edge);
+			}
+		}
+
+		// keep the original order of moved connections
+		overflow.reverse();
+		let mut overflow_iter = overflow.into_iter().peekable();
+		while overflow_iter.peek().is_some() {
+			let mut new_page = match self.get_next_available_page_id(updated_pages) {
+				Some(next_page_id) =>
+					GraphPage::new(self.get_connection_type().privacy_type(), next_page_id),
+				None => {
+					// only newly added connections can be left out, existing ones would get lost
+					let remaining: Vec<DsnpGraphEdge> = overflow_iter.collect();
+					if overflow_policy == PageOverflowPolicy::SkipOverflow &&
+						remaining.iter().all(|edge| ids_to_add.contains(&edge.user_id))
+					{
+						break
+					}
+					return Err(self.capacity_exceeded_error(updated_pages, remaining.len()))
+				},
+			};
+
+			while let Some(edge) = overflow_iter.peek() {
+				let mut temp_page = new_page.clone();
+				temp_page.add_connection_since(&edge.user_id, edge.since)?;
+				if self
+					.page_to_payload(&temp_page, ids_to_add, dsnp_version_config, encryption_key)?
+					.content
+					.len() > max_page_size
+				{
+					if new_page.is_empty() {
+						return Err(self.payload_too_large_error(
+							&temp_page,
+							ids_to_add,
+							dsnp_version_config,
+							encryption_key,
+						));
+					}
+					break
+				}
+				new_page = temp_page;
+				let _ = overflow_iter.next();
+			}
+			updated_pages.insert(new_page.page_id(), new_page);
+		}
+
+		Ok(())
+	}
+}
+
+/// Returns the benchmarked page capacity of the connection type from the capacity map
+fn page_capacity(
+	capacities: &HashMap<ConnectionType, usize>,
+	connection_type: ConnectionType,
+) -> DsnpGraphResult<usize> {
+	capacities
+		.get(&connection_type)
+		.copied()
+		.ok_or(DsnpGraphError::PageCapacityNotConfigured(connection_type))
+}
+
+/// Returns the page capacity of a schema, preferring the capacity configured in its schema config
+/// over the capacity of its connection type from the capacity map
+fn schema_page_capacity(
+	config: &Config,
+	capacities: &HashMap<ConnectionType, usize>,
+	schema_id: SchemaId,
+) -> DsnpGraphResult<usize> {
+	if let Some(capacity) = config.get_page_capacity_from_schema_id(schema_id) {
+		return Ok(capacity.get() as usize)
+	}
+	let connection_type = config
+		.get_connection_type_from_schema_id(schema_id)
+		.ok_or_else(|| config.invalid_schema_id(schema_id))?;
+	page_capacity(capacities, connection_type)
+}
+
+/// Checks that a page capacity exists for every graph schema of the config, either configured in
+/// its schema config or for its connection type in the capacity map
+fn validate_capacities_with(
+	config: &Config,
+	capacities: &HashMap<ConnectionType, usize>,
+) -> DsnpGraphResult<()> {
+	let mut schema_ids: Vec<&SchemaId> = config.schema_map.keys().collect();
+	schema_ids.sort();
+	for schema_id in schema_ids {
+		schema_page_capacity(config, capacities, *schema_id)?;
+	}
+	Ok(())
+}
+
+/// Checks that the config is consistent with its page capacities, so that adding connections to
+/// a graph of any configured schema never fails due to a missing capacity
+pub fn validate_page_capacities(config: &Config) -> DsnpGraphResult<()> {
+	validate_capacities_with(config, &environment_page_capacities(config))
+}
+
+/// Macro to get an iterator to all connections across all GraphPages
+/// within a Graph.
+#[macro_export]
+macro_rules! iter_graph_connections {
+	( $x:expr ) => {{
+		$x.pages()
+			.inner()
+			.values()
+			.flat_map(|p| p.connections().iter().cloned())
+			.collect::<Vec<DsnpGraphEdge>>()
+			.iter()
+	}};
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
 	use crate::{
-		dsnp::dsnp_configs::KeyPairType,
+		dsnp::{
+			compression::{CompressionBehavior, LegacyZlibCompression},
+			dsnp_configs::KeyPairType,
+			reader_writer::DsnpReader,
+			schema::SchemaHandler,
+		},
+		frequency::Frequency,
 		graph::{
 			key_manager::{UserKeyManager, UserKeyProvider},
+			page::PublicPageDataProvider,
+			page_capacities::PAGE_CAPACITY_MAP,
 			shared_state_manager::{PublicKeyProvider, SharedStateManager},
 		},
 		tests::{
@@ -790,13 +2149,23 @@ mod test {
 			},
 			mocks::MockUserKeyManager,
 		},
-		util::builders::{GraphPageBuilder, KeyDataBuilder, PageDataBuilder},
+		util::{
+			builders::{GraphPageBuilder, KeyDataBuilder, PageDataBuilder},
+			page_packing::{verify_page_plan, PackingLimits, PageConnections, PagePlan},
+			time::TimeProvider,
+		},
 	};
 	use dryoc::keypair::StackKeyPair;
 	use dsnp_graph_config::{DsnpVersion, GraphKeyType, ALL_CONNECTION_TYPES};
 	use ntest::*;
 	#[allow(unused_imports)]
 	use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
+	use rand::{rngs::StdRng, Rng, SeedableRng};
+	use std::{
+		collections::HashSet,
+		num::NonZeroU32,
+		sync::{Arc, RwLock},
+	};
 
 	#[test]
 	fn new_graph_is_empty() {
@@ -863,7 +2232,7 @@ mod test {
 			.get_config()
 			.get_schema_id_from_connection_type(CONN_TYPE)
 			.expect("should exist");
-		let pages: PageMap = (0..=environment.get_config().max_page_id as PageId)
+		let pages: PageMap = (0..=environment.get_config().max_page_id)
 			.map(|page_id: PageId| (page_id, GraphPage::new(PRIV_TYPE, page_id)))
 			.collect();
 		let graph = Graph {
@@ -871,10 +2240,17 @@ mod test {
 			schema_id, // doesn't matter which type
 			user_id,
 			pages,
+			connection_index: TransactionalHashMap::new(),
+			encrypted_pages: TransactionalHashMap::new(),
+			repaired_pages: TransactionalHashMap::new(),
+			page_history: TransactionalHashMap::new(),
 			user_key_manager: Arc::new(RwLock::new(UserKeyManager::new(
 				user_id,
 				Arc::new(RwLock::new(SharedStateManager::new())),
 			))),
+			time_provider: system_time_provider(),
+			chain_adapter: default_chain_adapter(),
+			additional_recipients: vec![],
 		};
 
 		assert_eq!(graph.get_next_available_page_id(&BTreeMap::default()), None);
@@ -890,7 +2266,7 @@ mod test {
 			.get_config()
 			.get_schema_id_from_connection_type(CONN_TYPE)
 			.expect("should exist");
-		let mut pages: PageMap = (0..environment.get_config().max_page_id as PageId)
+		let mut pages: PageMap = (0..environment.get_config().max_page_id)
 			.map(|page_id: PageId| (page_id, GraphPage::new(PRIV_TYPE, page_id)))
 			.collect();
 		pages.remove(&8);
@@ -899,10 +2275,17 @@ mod test {
 			schema_id, // doesn't matter which type
 			user_id,
 			pages,
+			connection_index: TransactionalHashMap::new(),
+			encrypted_pages: TransactionalHashMap::new(),
+			repaired_pages: TransactionalHashMap::new(),
+			page_history: TransactionalHashMap::new(),
 			user_key_manager: Arc::new(RwLock::new(UserKeyManager::new(
 				user_id,
 				Arc::new(RwLock::new(SharedStateManager::new())),
 			))),
+			time_provider: system_time_provider(),
+			chain_adapter: default_chain_adapter(),
+			additional_recipients: vec![],
 		};
 
 		assert_eq!(graph.get_next_available_page_id(&BTreeMap::default()), Some(8));
@@ -918,7 +2301,7 @@ mod test {
 			.get_config()
 			.get_schema_id_from_connection_type(CONN_TYPE)
 			.expect("should exist");
-		let mut updated_pages: BTreeMap<_, _> = (0..environment.get_config().max_page_id as PageId)
+		let mut updated_pages: BTreeMap<_, _> = (0..environment.get_config().max_page_id)
 			.map(|page_id: PageId| (page_id, GraphPage::new(PRIV_TYPE, page_id)))
 			.collect();
 		updated_pages.remove(&8);
@@ -927,15 +2310,50 @@ mod test {
 			schema_id, // doesn't matter which type
 			user_id,
 			pages: PageMap::new(),
+			connection_index: TransactionalHashMap::new(),
+			encrypted_pages: TransactionalHashMap::new(),
+			repaired_pages: TransactionalHashMap::new(),
+			page_history: TransactionalHashMap::new(),
 			user_key_manager: Arc::new(RwLock::new(UserKeyManager::new(
 				user_id,
 				Arc::new(RwLock::new(SharedStateManager::new())),
 			))),
+			time_provider: system_time_provider(),
+			chain_adapter: default_chain_adapter(),
+			additional_recipients: vec![],
 		};
 
 		assert_eq!(graph.get_next_available_page_id(&updated_pages), Some(8));
 	}
 
+	#[test]
+	fn get_next_available_page_should_skip_encrypted_pages() {
+		let environment = Environment::Mainnet;
+		let user_id = 3;
+		let connection_type = ConnectionType::Follow(PrivacyType::Private);
+		let schema_id = environment
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let user_key_manager = Arc::new(RwLock::new(UserKeyManager::new(
+			user_id,
+			Arc::new(RwLock::new(SharedStateManager::new())),
+		)));
+		let mut graph = Graph::new(environment, user_id, schema_id, user_key_manager);
+		let content = vec![1u8; 50];
+		let pages: Vec<_> = (0..2)
+			.map(|page_id| PageDataRef { page_id, content: &content, content_hash: 10 })
+			.collect();
+
+		graph
+			.import_private_metadata(connection_type, &pages, false)
+			.expect("should import");
+
+		assert_eq!(graph.get_next_available_page_id(&BTreeMap::default()), Some(2));
+		assert_eq!(graph.stats().encrypted_pages.len(), 2);
+		assert!(graph.stats().is_metadata_only());
+	}
+
 	#[test]
 	fn clear_removes_all_pages() {
 		let mut graph = create_test_graph(None);
@@ -974,6 +2392,104 @@ mod test {
 		assert_eq!(orig_connections, imported_connections);
 	}
 
+	#[test]
+	fn import_public_with_legacy_format_should_upgrade_pages_on_calculate_updates() {
+		let environment = Environment::Mainnet;
+		let user_id = 3;
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+		let schema_id = environment
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let mut graph = Graph::new(
+			environment,
+			user_id,
+			schema_id,
+			Arc::new(RwLock::new(UserKeyManager::new(
+				user_id,
+				Arc::new(RwLock::new(SharedStateManager::new())),
+			))),
+		);
+		let legacy_content = SchemaHandler::write_public_graph_chunk(&DsnpUserPublicGraphChunk {
+			compressed_public_graph: LegacyZlibCompression::compress(
+				&SchemaHandler::write_inner_graph(&INNER_TEST_DATA.to_vec()).unwrap(),
+			)
+			.unwrap(),
+		})
+		.unwrap();
+		let pages = vec![PageData { content_hash: 0, page_id: 0, content: legacy_content }];
+
+		graph.import_public(connection_type, &pages).expect("should import");
+		assert!(graph.has_pages_needing_format_upgrade());
+
+		let updates = graph
+			.calculate_updates(&DsnpVersionConfig::new(DsnpVersion::Version1_0), &vec![])
+			.expect("should calculate");
+
+		assert_eq!(updates.len(), 1);
+		graph
+			.import_public(connection_type, &updates_to_page(&updates))
+			.expect("should import");
+		assert!(!graph.has_pages_needing_format_upgrade());
+		let imported_connections: HashSet<DsnpUserId> =
+			iter_graph_connections!(graph).map(|edge| edge.user_id).collect();
+		assert_eq!(
+			imported_connections,
+			INNER_TEST_DATA.iter().map(|edge| edge.user_id).collect::<HashSet<_>>()
+		);
+	}
+
+	#[test]
+	fn import_public_with_duplicate_connections_should_keep_lowest_page_and_rewrite_others() {
+		// arrange
+		let environment = Environment::Mainnet;
+		let user_id = 3;
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+		let schema_id = environment
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let mut graph = Graph::new(
+			environment,
+			user_id,
+			schema_id,
+			Arc::new(RwLock::new(UserKeyManager::new(
+				user_id,
+				Arc::new(RwLock::new(SharedStateManager::new())),
+			))),
+		);
+		let pages = PageDataBuilder::new(connection_type)
+			.with_page(2, &vec![(1, 10), (5, 11)], &vec![], 100)
+			.with_page(1, &vec![(1, 10), (2, 12)], &vec![], 101)
+			.with_page(3, &vec![(4, 13)], &vec![], 102)
+			.build();
+		let pages: Vec<PageDataRef> = pages.iter().map(PageDataRef::from).collect();
+
+		// act
+		let repairs = graph.import_public_borrowed(connection_type, &pages, false);
+
+		// assert
+		assert_eq!(
+			repairs.expect("should import"),
+			vec![ConnectionRepair {
+				dsnp_user_id: user_id,
+				schema_id,
+				connection_id: 1,
+				kept_page_id: 1,
+				repaired_page_id: 2,
+			}]
+		);
+		assert_eq!(graph.len(), 4);
+		assert_eq!(graph.find_connection(&1), Some(1));
+		assert_eq!(graph.get_repaired_pages(), vec![(2, vec![1])]);
+		assert!(graph.has_pages_needing_rewrite());
+		let updates = graph
+			.calculate_updates(&DsnpVersionConfig::new(DsnpVersion::Version1_0), &vec![])
+			.expect("should calculate");
+		assert_eq!(updates.len(), 1);
+		assert!(matches!(updates[0], Update::PersistPage { page_id: 2, prev_hash: 100, .. }));
+	}
+
 	#[test]
 	fn import_private_follow_gets_correct_data() {
 		let connection_type = ConnectionType::Follow(PrivacyType::Private);
@@ -1058,168 +2574,896 @@ mod test {
 	}
 
 	#[test]
-	fn has_connection_returns_false_for_missing_connection() {
-		let graph = create_test_graph(None);
-
-		assert_eq!(graph.has_connection(&99), false);
-	}
+	fn has_connection_returns_false_for_missing_connection() {
+		let graph = create_test_graph(None);
+
+		assert_eq!(graph.has_connection(&99), false);
+	}
+
+	#[test]
+	fn has_connection_returns_true_for_present_connection() {
+		let graph = create_test_graph(None);
+
+		assert_eq!(graph.has_connection(&1), true);
+	}
+
+	#[test]
+	fn find_connection_returns_none_for_nonexistent_connection() {
+		let graph = create_test_graph(None);
+
+		assert_eq!(graph.find_connection(&99), None);
+	}
+
+	#[test]
+	fn find_connections_returns_pageid_of_existing_connection() {
+		let graph = create_test_graph(None);
+
+		assert_eq!(graph.find_connection(&1), Some(0));
+	}
+
+	#[test]
+	fn find_connections_returns_vec_of_pageids() {
+		let graph = create_test_graph(None);
+
+		let mut v = graph.find_connections(&vec![1, 5, 24]);
+		v.sort();
+		assert_eq!(v, vec![0, 1, 4]);
+	}
+
+	#[test]
+	fn add_connection_duplicate_connection_errors() {
+		let mut graph = create_test_graph(None);
+
+		assert_eq!(graph.add_connection_to_page(&4, &0).is_err(), true);
+	}
+
+	#[test]
+	fn add_connection_to_nonexistent_page_adds_new_page() {
+		let mut graph = create_test_graph(None);
+		let page_to_add: PageId = 99;
+
+		assert_eq!(graph.pages().inner().contains_key(&page_to_add), false);
+		let _ = graph.add_connection_to_page(&page_to_add, &12345);
+		assert_eq!(graph.pages().inner().contains_key(&page_to_add), true);
+	}
+
+	#[test]
+	fn add_connection_succeeds() {
+		let mut graph = create_test_graph(None);
+
+		let _ = graph.add_connection_to_page(&4, &99);
+		assert_eq!(graph.find_connection(&99), Some(4));
+	}
+
+	#[test]
+	fn add_connection_should_use_injected_time_provider() {
+		#[derive(Debug)]
+		struct FixedTimeProvider(u64);
+
+		impl TimeProvider for FixedTimeProvider {
+			fn now_in_secs(&self) -> u64 {
+				self.0
+			}
+		}
+
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut graph = Graph::with_time_provider(
+			env,
+			1,
+			schema_id,
+			Arc::new(RwLock::new(MockUserKeyManager::new())),
+			Arc::new(FixedTimeProvider(5_000_000)),
+		);
+
+		graph.add_connection_to_page(&0, &99).expect("should add");
+
+		let added: Vec<_> = graph.pages().inner().values().flat_map(|p| p.connections()).collect();
+		assert_eq!(added, vec![&DsnpGraphEdge { user_id: 99, since: 5_000 }]);
+	}
+
+	#[test]
+	fn remove_connection_returns_none_for_not_found() {
+		let mut graph = create_test_graph(None);
+
+		let result = graph.remove_connection(&99);
+		assert_eq!(result.unwrap().is_none(), true);
+	}
+
+	#[test]
+	fn remove_connection_returns_pageid_of_removed_connection() {
+		let mut graph = create_test_graph(None);
+
+		let result = graph.remove_connection(&5);
+		assert_eq!(result.unwrap(), Some(1));
+	}
+
+	#[test]
+	fn connection_index_should_be_kept_in_sync_with_transactions() {
+		// arrange
+		let mut graph = create_test_graph(None);
+		graph.commit();
+
+		// act
+		graph.add_connection_to_page(&2, &100).expect("should add");
+		graph.remove_connection(&5).expect("should remove");
+		let before_rollback = (graph.find_connection(&100), graph.find_connection(&5));
+		graph.rollback();
+
+		// assert
+		assert_eq!(before_rollback, (Some(2), None));
+		assert_eq!(graph.find_connection(&100), None);
+		assert_eq!(graph.find_connection(&5), Some(1));
+		assert_eq!(graph.find_connections(&vec![5, 1, 99]), vec![0, 1]);
+	}
+
+	#[test]
+	fn connection_index_should_be_rebuilt_on_import() {
+		// arrange
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+		let mut graph = create_test_graph(Some(connection_type));
+		let pages = GraphPageBuilder::new(connection_type)
+			.with_page(3, &vec![(100, 0), (101, 0)], &vec![], 0)
+			.build();
+		let page_data: Vec<_> =
+			pages.iter().map(|p| p.to_public_page_data().expect("should write")).collect();
+
+		// act
+		graph.import_public(connection_type, &page_data).expect("should import");
+
+		// assert
+		assert_eq!(graph.find_connection(&100), Some(3));
+		assert_eq!(graph.find_connection(&101), Some(3));
+		assert!(!graph.has_connection(&5));
+	}
+
+	#[test]
+	fn graph_iterator_should_iterate_over_all_connections() {
+		let graph = create_test_graph(None);
+		let mut test_connections: Vec<DsnpUserId> = (0..25).map(|i| i as DsnpUserId).collect();
+		test_connections.sort();
+
+		let mut graph_connections: Vec<DsnpUserId> =
+			iter_graph_connections!(graph).map(|edge| edge.user_id).collect();
+		graph_connections.sort();
+		assert_eq!(test_connections, graph_connections);
+	}
+
+	fn updates_to_page(updates: &[Update]) -> Vec<PageData> {
+		updates
+			.iter()
+			.filter_map(|u| match u {
+				Update::PersistPage { page_id, payload, .. } =>
+					Some(PageData { page_id: *page_id, content_hash: 0, content: payload.clone() }),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test]
+	#[timeout(5000)] // let's make sure this terminates successfully
+	fn calculate_updates_public_existing_pages_succeeds() {
+		// arrange
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+		let ids_per_page = 5;
+		let user_id = 3;
+		let mut curr_id = 1u64;
+		let mut page_builder = GraphPageBuilder::new(connection_type);
+		for i in 0..5 {
+			let ids: Vec<(DsnpUserId, u64)> =
+				(curr_id..(curr_id + ids_per_page)).map(|id| (id, 0)).collect();
+			page_builder = page_builder.with_page(i, &ids, &vec![], 0);
+			curr_id += ids_per_page;
+		}
+
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let mut graph = Graph::new(
+			env,
+			user_id,
+			schema_id,
+			Arc::new(RwLock::new(UserKeyManager::new(
+				user_id,
+				Arc::new(RwLock::new(SharedStateManager::new())),
+			))),
+		);
+		for p in page_builder.build() {
+			let _ = graph.create_page(&p.page_id(), Some(p)).expect("should create page!");
+		}
+		let updates = vec![
+			UpdateEvent::create_remove(1, graph.schema_id),
+			UpdateEvent::create_remove(1 + ids_per_page * 2, graph.schema_id),
+			UpdateEvent::create_add(curr_id + 1, graph.schema_id),
+			UpdateEvent::create_add(curr_id + 2, graph.schema_id),
+		];
+
+		// act
+		let updates =
+			graph.calculate_updates(&DsnpVersionConfig::new(DsnpVersion::Version1_0), &updates);
+
+		// assert
+		assert!(updates.is_ok());
+		let updates = updates.unwrap();
+
+		assert_eq!(updates.len(), 2);
+		graph
+			.import_public(connection_type, &updates_to_page(&updates))
+			.expect("should import");
+
+		let removed_connection_1 = graph.find_connection(&1);
+		let removed_connection_2 = graph.find_connection(&(1 + ids_per_page * 2));
+		assert!(removed_connection_1.is_none());
+		assert!(removed_connection_2.is_none());
+
+		let added_connection_1 = graph.find_connection(&(curr_id + 1));
+		let added_connection_2 = graph.find_connection(&(curr_id + 2));
+		assert_eq!(added_connection_1, Some(0));
+		assert_eq!(added_connection_2, Some(0));
+	}
+
+	#[test]
+	fn calculate_updates_with_placements_should_honor_preferred_pages_when_possible() {
+		// arrange
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+		let user_id = 3;
+		let env = Environment::Mainnet;
+		let max_page_id = env.get_config().max_page_id;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let mut graph = Graph::new(
+			env,
+			user_id,
+			schema_id,
+			Arc::new(RwLock::new(UserKeyManager::new(
+				user_id,
+				Arc::new(RwLock::new(SharedStateManager::new())),
+			))),
+		);
+		let page_builder = GraphPageBuilder::new(connection_type)
+			.with_page(0, &vec![(1, 0), (2, 0)], &vec![], 0)
+			.with_page(1, &vec![(3, 0), (4, 0)], &vec![], 0);
+		for p in page_builder.build() {
+			let _ = graph.create_page(&p.page_id(), Some(p)).expect("should create page!");
+		}
+		let updates = vec![
+			UpdateEvent::create_add(10, schema_id),
+			UpdateEvent::create_add(11, schema_id),
+			UpdateEvent::create_add(12, schema_id),
+			UpdateEvent::create_add(13, schema_id),
+		];
+		let page_hints = HashMap::from([(10, 1), (11, 5), (12, max_page_id + 1)]);
+
+		// act
+		let (updates, placements) = graph
+			.calculate_updates_with_placements(
+				&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+				&updates,
+				&page_hints,
+				&HashMap::new(),
+				ExportOptions::default(),
+			)
+			.expect("should calculate updates");
+		let updates: Vec<_> = updates.into_iter().map(|u| u.update).collect();
+
+		// assert
+		assert_eq!(
+			placements,
+			vec![
+				ConnectionPlacement {
+					dsnp_user_id: 10,
+					schema_id,
+					page_id: 1,
+					preferred_page_id: Some(1)
+				},
+				ConnectionPlacement {
+					dsnp_user_id: 11,
+					schema_id,
+					page_id: 5,
+					preferred_page_id: Some(5)
+				},
+				ConnectionPlacement {
+					dsnp_user_id: 12,
+					schema_id,
+					page_id: 1,
+					preferred_page_id: Some(max_page_id + 1)
+				},
+				ConnectionPlacement {
+					dsnp_user_id: 13,
+					schema_id,
+					page_id: 1,
+					preferred_page_id: None
+				},
+			]
+		);
+		assert!(!placements[2].is_preferred_page_honored());
+		graph
+			.import_public(connection_type, &updates_to_page(&updates))
+			.expect("should import");
+		assert_eq!(graph.find_connection(&11), Some(5));
+		assert_eq!(graph.find_connection(&13), Some(1));
+	}
+
+	#[test]
+	fn calculate_updates_should_split_pages_exceeding_max_update_payload_size() {
+		// arrange
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+		let max_update_payload_bytes = 200;
+		let user_id = 3;
+		let mut config = Environment::Mainnet.get_config().clone();
+		config.max_update_payload_bytes = Some(max_update_payload_bytes);
+		let env = Environment::Dev(config);
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let ids: Vec<(DsnpUserId, u64)> = (1..=80).map(|id| (id, id * 1_000_003)).collect();
+		let page = GraphPageBuilder::new(connection_type)
+			.with_page(0, &ids, &vec![], 0)
+			.build()
+			.pop()
+			.expect("should exist");
+		assert!(
+			page.to_public_page_data().unwrap().content.len() > max_update_payload_bytes as usize
+		);
+		let mut graph =
+			Graph::new(env, user_id, schema_id, Arc::new(RwLock::new(MockUserKeyManager::new())));
+		graph.create_page(&0, Some(page)).expect("should create page!");
+		let mut updates = vec![UpdateEvent::create_remove(1, schema_id)];
+		updates.extend((100..120).map(|id| UpdateEvent::create_add(id, schema_id)));
+
+		// act
+		let updates =
+			graph.calculate_updates(&DsnpVersionConfig::new(DsnpVersion::Version1_0), &updates);
+
+		// assert
+		assert!(updates.is_ok());
+		let pages = updates_to_page(&updates.unwrap());
+		assert!(pages.len() > 1);
+		assert!(pages.iter().all(|p| p.content.len() <= max_update_payload_bytes as usize));
+		graph.import_public(connection_type, &pages).expect("should import");
+		assert_eq!(graph.len(), 80 - 1 + 20);
+		assert_eq!(graph.find_connection(&1), None);
+		assert!(graph.find_connection(&2).is_some());
+		assert!(graph.find_connection(&119).is_some());
+	}
+
+	#[test]
+	fn calculate_updates_with_single_connection_exceeding_max_update_payload_should_fail() {
+		// arrange
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+		let mut config = Environment::Mainnet.get_config().clone();
+		config.max_update_payload_bytes = Some(5);
+		let env = Environment::Dev(config);
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let empty_graph =
+			Graph::new(env.clone(), 3, schema_id, Arc::new(RwLock::new(MockUserKeyManager::new())));
+		let mut imported_graph =
+			Graph::new(env, 3, schema_id, Arc::new(RwLock::new(MockUserKeyManager::new())));
+		let page = GraphPageBuilder::new(connection_type)
+			.with_page(0, &vec![(1, 0)], &vec![], 0)
+			.build()
+			.pop()
+			.expect("should exist");
+		imported_graph.create_page(&0, Some(page)).expect("should create page!");
+		let dsnp_version_config = DsnpVersionConfig::new(DsnpVersion::Version1_0);
+
+		// act
+		let added = empty_graph
+			.calculate_updates(&dsnp_version_config, &vec![UpdateEvent::create_add(2, schema_id)]);
+		let added_to_imported = imported_graph
+			.calculate_updates(&dsnp_version_config, &vec![UpdateEvent::create_add(2, schema_id)]);
+
+		// assert
+		assert!(
+			matches!(added, Err(DsnpGraphError::UpdatePayloadTooLarge(0, size, 5)) if size > 5)
+		);
+		assert!(matches!(
+			added_to_imported,
+			Err(DsnpGraphError::UpdatePayloadTooLarge(1, size, 5)) if size > 5
+		));
+	}
+
+	fn create_single_page_graph(ids: &Vec<(DsnpUserId, u64)>) -> Graph {
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+		let mut config = Environment::Mainnet.get_config().clone();
+		config.max_page_id = 0;
+		config.max_update_payload_bytes = Some(200);
+		let env = Environment::Dev(config);
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let page = GraphPageBuilder::new(connection_type)
+			.with_page(0, ids, &vec![], 0)
+			.build()
+			.pop()
+			.expect("should exist");
+		let mut graph =
+			Graph::new(env, 3, schema_id, Arc::new(RwLock::new(MockUserKeyManager::new())));
+		graph.create_page(&0, Some(page)).expect("should create page!");
+		graph
+	}
+
+	#[test]
+	fn calculate_updates_exceeding_max_page_id_should_fail_with_capacity_error() {
+		// arrange
+		let graph = create_single_page_graph(&vec![(1, 0), (2, 0)]);
+		let schema_id = graph.get_schema_id();
+		let updates: Vec<_> =
+			(1..=100).map(|i| UpdateEvent::create_add(i * 1_000_003, schema_id)).collect();
 
-	#[test]
-	fn has_connection_returns_true_for_present_connection() {
-		let graph = create_test_graph(None);
+		// act
+		let result = graph.calculate_updates_with_placements(
+			&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			&updates,
+			&HashMap::new(),
+			&HashMap::new(),
+			ExportOptions::default(),
+		);
 
-		assert_eq!(graph.has_connection(&1), true);
+		// assert
+		match result {
+			Err(DsnpGraphError::GraphCapacityExceeded(error_schema_id, full_pages, over)) => {
+				assert_eq!(error_schema_id, schema_id);
+				assert_eq!(full_pages, vec![0]);
+				assert!(over > 0 && over < 100);
+			},
+			_ => panic!("expected GraphCapacityExceeded error"),
+		}
 	}
 
 	#[test]
-	fn find_connection_returns_none_for_nonexistent_connection() {
-		let graph = create_test_graph(None);
-
-		assert_eq!(graph.find_connection(&99), None);
-	}
+	fn calculate_updates_with_skip_overflow_policy_should_export_connections_that_fit() {
+		// arrange
+		let mut graph = create_single_page_graph(&vec![(1, 0), (2, 0)]);
+		let schema_id = graph.get_schema_id();
+		let updates: Vec<_> =
+			(1..=100).map(|i| UpdateEvent::create_add(i * 1_000_003, schema_id)).collect();
 
-	#[test]
-	fn find_connections_returns_pageid_of_existing_connection() {
-		let graph = create_test_graph(None);
+		// act
+		let result = graph.calculate_updates_with_placements(
+			&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			&updates,
+			&HashMap::new(),
+			&HashMap::new(),
+			ExportOptions {
+				page_overflow_policy: PageOverflowPolicy::SkipOverflow,
+				..ExportOptions::default()
+			},
+		);
 
-		assert_eq!(graph.find_connection(&1), Some(0));
+		// assert
+		assert!(result.is_ok());
+		let (updates, placements) = result.unwrap();
+		let updates: Vec<_> = updates.into_iter().map(|u| u.update).collect();
+		let pages = updates_to_page(&updates);
+		assert_eq!(pages.len(), 1);
+		assert!(!placements.is_empty() && placements.len() < 100);
+		assert!(placements.iter().all(|p| p.page_id == 0));
+		graph
+			.import_public(ConnectionType::Follow(PrivacyType::Public), &pages)
+			.expect("should import");
+		assert_eq!(graph.len(), 2 + placements.len());
+		assert!(graph.find_connection(&1).is_some());
+		assert!(graph.find_connection(&(100 * 1_000_003)).is_none());
 	}
 
 	#[test]
-	fn find_connections_returns_vec_of_pageids() {
-		let graph = create_test_graph(None);
+	fn verify_roundtrip_should_use_provided_export_options() {
+		// arrange
+		let graph = create_single_page_graph(&vec![(1, 0), (2, 0)]);
+		let schema_id = graph.get_schema_id();
+		let updates: Vec<_> =
+			(1..=100).map(|i| UpdateEvent::create_add(i * 1_000_003, schema_id)).collect();
+		let dsnp_version_config = DsnpVersionConfig::new(DsnpVersion::Version1_0);
+		let skip_overflow = ExportOptions {
+			page_overflow_policy: PageOverflowPolicy::SkipOverflow,
+			..ExportOptions::default()
+		};
 
-		let mut v = graph.find_connections(&vec![1, 5, 24]);
-		v.sort();
-		assert_eq!(v, vec![0, 1, 4]);
+		// act
+		let default_result = graph.verify_roundtrip(
+			&dsnp_version_config,
+			&updates,
+			&HashMap::new(),
+			&HashMap::new(),
+			ExportOptions::default(),
+		);
+		let skip_result = graph.verify_roundtrip(
+			&dsnp_version_config,
+			&updates,
+			&HashMap::new(),
+			&HashMap::new(),
+			skip_overflow,
+		);
+
+		// assert
+		assert!(matches!(default_result, Err(DsnpGraphError::GraphCapacityExceeded(..))));
+		let report = skip_result.expect("should verify");
+		assert_eq!(report.expected_connections, 102);
+		assert!(!report.missing_connections.is_empty());
+		assert!(report.missing_connections.contains(&(100 * 1_000_003)));
+		assert!(report.unexpected_connections.is_empty());
 	}
 
 	#[test]
-	fn add_connection_duplicate_connection_errors() {
-		let mut graph = create_test_graph(None);
+	fn calculate_updates_with_placements_should_report_connection_changes_of_each_page() {
+		// arrange
+		let graph = create_single_page_graph(&vec![(1, 0), (2, 0)]);
+		let schema_id = graph.get_schema_id();
+		let updates = vec![
+			UpdateEvent::create_remove(1, schema_id),
+			UpdateEvent::create_add(4, schema_id),
+			UpdateEvent::create_add(3, schema_id),
+		];
+		let removals = vec![
+			UpdateEvent::create_remove(1, schema_id),
+			UpdateEvent::create_remove(2, schema_id),
+		];
 
-		assert_eq!(graph.add_connection_to_page(&4, &0).is_err(), true);
+		// act
+		let (updates, _) = graph
+			.calculate_updates_with_placements(
+				&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+				&updates,
+				&HashMap::new(),
+				&HashMap::new(),
+				ExportOptions::default(),
+			)
+			.expect("should calculate updates");
+		let (removals, _) = graph
+			.calculate_updates_with_placements(
+				&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+				&removals,
+				&HashMap::new(),
+				&HashMap::new(),
+				ExportOptions::default(),
+			)
+			.expect("should calculate updates");
+
+		// assert
+		assert_eq!(updates.len(), 1);
+		assert!(matches!(updates[0].update, Update::PersistPage { page_id: 0, .. }));
+		assert_eq!(
+			updates[0].details,
+			Some(UpdateDetails {
+				added_connections: vec![3, 4],
+				removed_connections: vec![1],
+				connection_count: 3,
+				..updates[0].details.clone().unwrap_or_default()
+			})
+		);
+		assert_eq!(removals.len(), 1);
+		assert!(matches!(removals[0].update, Update::DeletePage { page_id: 0, .. }));
+		assert_eq!(
+			removals[0].details,
+			Some(UpdateDetails {
+				added_connections: vec![],
+				removed_connections: vec![1, 2],
+				connection_count: 0,
+				uncompressed_size: 0,
+				payload_size: 0,
+				max_payload_size: removals[0].details.as_ref().map_or(0, |d| d.max_payload_size),
+			})
+		);
 	}
 
 	#[test]
-	fn add_connection_to_nonexistent_page_adds_new_page() {
-		let mut graph = create_test_graph(None);
-		let page_to_add: PageId = 99;
+	fn calculate_updates_with_placements_should_report_connections_removed_by_prids() {
+		// arrange
+		let connection_type = ConnectionType::Friendship(PrivacyType::Private);
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let dsnp_user_id = 1000;
+		let key_pair = KeyPairType::Version1_0(StackKeyPair::gen());
+		let mut key_manager = MockUserKeyManager::new();
+		key_manager.register_verifications(&vec![(1, Some(false)), (2, Some(true))]);
+		key_manager
+			.register_key(dsnp_user_id, &ResolvedKeyPair { key_id: 1, key_pair: key_pair.clone() });
+		let mut graph =
+			Graph::new(env, dsnp_user_id, schema_id, Arc::new(RwLock::new(key_manager)));
+		for p in GraphPageBuilder::new(connection_type)
+			.with_page(
+				1,
+				&vec![(1, 0), (2, 0)],
+				&vec![DsnpPrid::new(&[0, 1, 2, 3, 4, 5, 6, 7]); 2],
+				0,
+			)
+			.build()
+		{
+			let _ = graph.create_page(&p.page_id(), Some(p)).expect("should create page!");
+		}
+		let updates = vec![UpdateEvent::create_add(3, schema_id)];
 
-		assert_eq!(graph.pages().inner().contains_key(&page_to_add), false);
-		let _ = graph.add_connection_to_page(&page_to_add, &12345);
-		assert_eq!(graph.pages().inner().contains_key(&page_to_add), true);
+		// act
+		let (updates, _) = graph
+			.calculate_updates_with_placements(
+				&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+				&updates,
+				&HashMap::new(),
+				&HashMap::new(),
+				ExportOptions::default(),
+			)
+			.expect("should calculate updates");
+
+		// assert
+		assert_eq!(updates.len(), 1);
+		let details = updates[0].details.clone().expect("should have details");
+		assert_eq!(details.added_connections, vec![3]);
+		assert_eq!(details.removed_connections, vec![1]);
+		assert_eq!(details.connection_count, 2);
+		let Update::PersistPage { payload, .. } = &updates[0].update else {
+			panic!("expected a persisted page")
+		};
+		let chunk = Frequency::read_private_graph(
+			payload,
+			&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			&key_pair.into(),
+		)
+		.expect("should read");
+		assert_eq!(
+			details.uncompressed_size,
+			SchemaHandler::write_inner_graph(&chunk.inner_graph)
+				.expect("should serialize")
+				.len()
+		);
+		assert_eq!(details.payload_size, payload.len());
 	}
 
 	#[test]
-	fn add_connection_succeeds() {
-		let mut graph = create_test_graph(None);
+	fn calculate_updates_with_placements_should_report_page_sizes() {
+		// arrange
+		let graph = create_single_page_graph(&vec![(1, 0), (2, 0)]);
+		let schema_id = graph.get_schema_id();
+		let updates = vec![UpdateEvent::create_add(3, schema_id)];
+		let max_payload_size =
+			graph.environment.get_config().get_max_graph_page_payload_bytes() as usize;
 
-		let _ = graph.add_connection_to_page(&4, &99);
-		assert_eq!(graph.find_connection(&99), Some(4));
+		// act
+		let (updates, _) = graph
+			.calculate_updates_with_placements(
+				&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+				&updates,
+				&HashMap::new(),
+				&HashMap::new(),
+				ExportOptions::default(),
+			)
+			.expect("should calculate updates");
+
+		// assert
+		assert_eq!(updates.len(), 1);
+		let details = updates[0].details.clone().expect("should have details");
+		let payload = match &updates[0].update {
+			Update::PersistPage { payload, .. } => payload,
+			_ => panic!("expected a persisted page"),
+		};
+		let (connections, _) =
+			Frequency::read_public_graph_with_format(payload).expect("should read");
+		assert_eq!(
+			details.uncompressed_size,
+			SchemaHandler::write_inner_graph(&connections).expect("should serialize").len()
+		);
+		assert_eq!(details.payload_size, payload.len());
+		assert_eq!(details.max_payload_size, max_payload_size);
+		assert_eq!(
+			details.compression_ratio(),
+			Some(payload.len() as f64 / details.uncompressed_size as f64)
+		);
+		assert!(details.fill_ratio() > 0.0 && details.fill_ratio() < 1.0);
 	}
 
 	#[test]
-	fn remove_connection_returns_none_for_not_found() {
-		let mut graph = create_test_graph(None);
+	fn changes_since_should_only_resolve_last_imported_page_versions() {
+		// arrange
+		let (mut graph, _, _) =
+			create_empty_test_graph(None, Some(ConnectionType::Follow(PrivacyType::Public)));
+		for content_hash in 0..=PAGE_HISTORY_LENGTH as PageHash {
+			graph.record_page_version(0, content_hash, vec![content_hash as DsnpUserId + 1]);
+		}
 
-		let result = graph.remove_connection(&99);
-		assert_eq!(result.unwrap().is_none(), true);
+		// act
+		let evicted = graph.changes_since(&[(0, 0)]);
+		let kept = graph.changes_since(&[(0, 1)]);
+
+		// assert
+		assert_eq!(evicted.changed_pages, vec![0]);
+		assert_eq!(evicted.unresolved_pages, vec![0]);
+		assert!(evicted.removed.is_empty());
+		assert!(kept.is_complete());
+		assert_eq!(kept.removed, vec![2]);
 	}
 
 	#[test]
-	fn remove_connection_returns_pageid_of_removed_connection() {
-		let mut graph = create_test_graph(None);
+	fn calculate_updates_with_placements_should_satisfy_packing_invariants() {
+		// inputs are generated from fixed seeds instead of using proptest, which is not a
+		// dependency of this crate, so a failing seed is reproduced by running the test again
+		let max_payload_size =
+			get_env_and_config().0.get_config().get_max_graph_page_payload_bytes();
+		for seed in 0..20 {
+			// arrange
+			let mut rng = StdRng::seed_from_u64(seed);
+			let (mut graph, _, _) =
+				create_empty_test_graph(None, Some(ConnectionType::Follow(PrivacyType::Public)));
+			let schema_id = graph.get_schema_id();
+			let mut next_id: DsnpUserId = 1;
+			for page_id in 0..rng.gen_range(1..5) {
+				for _ in 0..rng.gen_range(1..60) {
+					graph.add_connection_to_page(&page_id, &next_id).expect("should add");
+					next_id += 1;
+				}
+			}
+			let existing: PageConnections = graph
+				.pages()
+				.inner()
+				.iter()
+				.map(|(page_id, page)| {
+					(*page_id, page.connections().iter().map(|c| c.user_id).collect())
+				})
+				.collect();
+			let removes: Vec<DsnpUserId> =
+				(0..rng.gen_range(0..40)).map(|_| rng.gen_range(1..next_id)).collect();
+			let adds: Vec<DsnpUserId> = (next_id..next_id + rng.gen_range(0..200)).collect();
+			let limits = PackingLimits {
+				max_connections_per_page: usize::MAX,
+				max_page_id: graph.environment.get_config().max_page_id,
+			};
+			let events: Vec<UpdateEvent> = removes
+				.iter()
+				.collect::<HashSet<_>>()
+				.into_iter()
+				.map(|id| UpdateEvent::create_remove(*id, schema_id))
+				.chain(adds.iter().map(|id| UpdateEvent::create_add(*id, schema_id)))
+				.collect();
 
-		let result = graph.remove_connection(&5);
-		assert_eq!(result.unwrap(), Some(1));
+			// act
+			let (updates, _) = graph
+				.calculate_updates_with_placements(
+					&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+					&events,
+					&HashMap::new(),
+					&HashMap::new(),
+					ExportOptions::default(),
+				)
+				.expect("should calculate updates");
+
+			// assert
+			let mut plan = PagePlan { pages: existing.clone(), ..PagePlan::default() };
+			for update in &updates {
+				match &update.update {
+					Update::PersistPage { page_id, payload, .. } => {
+						assert!(payload.len() <= max_payload_size as usize, "seed {}", seed);
+						let (connections, _) =
+							Frequency::read_public_graph_with_format(payload).expect("should read");
+						plan.pages
+							.insert(*page_id, connections.iter().map(|c| c.user_id).collect());
+						plan.updated_pages.insert(*page_id);
+					},
+					Update::DeletePage { page_id, .. } => {
+						plan.pages.remove(page_id);
+						plan.updated_pages.insert(*page_id);
+					},
+					_ => panic!("unexpected update"),
+				}
+			}
+			assert_eq!(
+				verify_page_plan(&existing, &adds, &removes, &plan, limits),
+				Ok(()),
+				"seed {}",
+				seed
+			);
+		}
 	}
 
 	#[test]
-	fn graph_iterator_should_iterate_over_all_connections() {
-		let graph = create_test_graph(None);
-		let mut test_connections: Vec<DsnpUserId> = (0..25).map(|i| i as DsnpUserId).collect();
-		test_connections.sort();
+	fn remaining_capacity_should_report_expected_capacity() {
+		// arrange
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+		let max_connections = *PAGE_CAPACITY_MAP.get(&connection_type).unwrap();
+		let ids: Vec<(DsnpUserId, u64)> =
+			(1..=max_connections as DsnpUserId).map(|id| (id, 0)).collect();
+		let mut config = Environment::Mainnet.get_config().clone();
+		config.max_page_id = 0;
+		let env = Environment::Dev(config);
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let page = GraphPageBuilder::new(connection_type)
+			.with_page(0, &ids, &vec![], 0)
+			.build()
+			.pop()
+			.expect("should exist");
+		let mut graph =
+			Graph::new(env, 3, schema_id, Arc::new(RwLock::new(MockUserKeyManager::new())));
+		graph.create_page(&0, Some(page)).expect("should create page!");
+		let updates = vec![
+			UpdateEvent::create_remove(1, schema_id),
+			UpdateEvent::create_add(10_000, schema_id),
+			UpdateEvent::create_add(10_001, schema_id),
+		];
 
-		let mut graph_connections: Vec<DsnpUserId> =
-			iter_graph_connections!(graph).map(|edge| edge.user_id).collect();
-		graph_connections.sort();
-		assert_eq!(test_connections, graph_connections);
-	}
+		// act
+		let empty_report = graph.remaining_capacity(&vec![]).unwrap();
+		let report = graph.remaining_capacity(&updates).unwrap();
 
-	fn updates_to_page(updates: &[Update]) -> Vec<PageData> {
-		updates
-			.iter()
-			.filter_map(|u| match u {
-				Update::PersistPage { page_id, payload, .. } =>
-					Some(PageData { page_id: *page_id, content_hash: 0, content: payload.clone() }),
-				_ => None,
-			})
-			.collect()
+		// assert
+		assert_eq!(empty_report.max_pages, 1);
+		assert_eq!(empty_report.used_pages, 1);
+		assert_eq!(empty_report.full_page_ids, vec![0]);
+		assert_eq!(empty_report.connections, max_connections);
+		assert_eq!(empty_report.remaining_connections, 0);
+		assert!(!empty_report.is_over_capacity());
+		assert_eq!(report.pending_additions, 2);
+		assert_eq!(report.pending_removals, 1);
+		assert_eq!(report.connections_over, 1);
+		assert!(report.is_over_capacity());
 	}
 
 	#[test]
-	#[timeout(5000)] // let's make sure this terminates successfully
-	fn calculate_updates_public_existing_pages_succeeds() {
+	fn remaining_capacity_should_scale_page_capacity_by_max_update_payload_bytes() {
 		// arrange
 		let connection_type = ConnectionType::Follow(PrivacyType::Public);
-		let ids_per_page = 5;
-		let user_id = 3;
-		let mut curr_id = 1u64;
-		let mut page_builder = GraphPageBuilder::new(connection_type);
-		for i in 0..5 {
-			let ids: Vec<(DsnpUserId, u64)> =
-				(curr_id..(curr_id + ids_per_page)).map(|id| (id, 0)).collect();
-			page_builder = page_builder.with_page(i, &ids, &vec![], 0);
-			curr_id += ids_per_page;
-		}
-
-		let env = Environment::Mainnet;
+		let max_connections = *PAGE_CAPACITY_MAP.get(&connection_type).unwrap();
+		let mut config = Environment::Mainnet.get_config().clone();
+		config.max_update_payload_bytes = Some(config.max_graph_page_size_bytes / 2);
+		let max_pages = config.max_page_id as usize + 1;
+		let env = Environment::Dev(config);
 		let schema_id = env
 			.get_config()
 			.get_schema_id_from_connection_type(connection_type)
 			.expect("should exist");
-		let mut graph = Graph::new(
-			env,
-			user_id,
-			schema_id,
-			Arc::new(RwLock::new(UserKeyManager::new(
-				user_id,
-				Arc::new(RwLock::new(SharedStateManager::new())),
-			))),
-		);
-		for p in page_builder.build() {
-			let _ = graph.create_page(&p.page_id(), Some(p)).expect("should create page!");
-		}
-		let updates = vec![
-			UpdateEvent::create_remove(1, graph.schema_id),
-			UpdateEvent::create_remove(1 + ids_per_page * 2, graph.schema_id),
-			UpdateEvent::create_add(curr_id + 1, graph.schema_id),
-			UpdateEvent::create_add(curr_id + 2, graph.schema_id),
-		];
+		let graph = Graph::new(env, 3, schema_id, Arc::new(RwLock::new(MockUserKeyManager::new())));
 
 		// act
-		let updates =
-			graph.calculate_updates(&DsnpVersionConfig::new(DsnpVersion::Version1_0), &updates);
+		let report = graph.remaining_capacity(&vec![]).unwrap();
 
 		// assert
-		assert!(updates.is_ok());
-		let updates = updates.unwrap();
+		assert_eq!(report.remaining_connections, max_pages * (max_connections / 2));
+	}
 
-		assert_eq!(updates.len(), 2);
+	#[test]
+	fn remaining_capacity_should_count_undecrypted_pages_as_full() {
+		// arrange
+		let env = Environment::Mainnet;
+		let connection_type = ConnectionType::Follow(PrivacyType::Private);
+		let max_connections = *PAGE_CAPACITY_MAP.get(&connection_type).unwrap();
+		let max_pages = env.get_config().max_page_id as usize + 1;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(connection_type)
+			.expect("should exist");
+		let mut graph =
+			Graph::new(env, 3, schema_id, Arc::new(RwLock::new(MockUserKeyManager::new())));
+		let content = vec![0u8; 100];
+		let pages = vec![
+			PageDataRef { page_id: 2, content: &content, content_hash: 1 },
+			PageDataRef { page_id: 5, content: &content, content_hash: 2 },
+		];
 		graph
-			.import_public(connection_type, &updates_to_page(&updates))
-			.expect("should import");
+			.import_private_metadata(connection_type, &pages, false)
+			.expect("should import metadata");
 
-		let removed_connection_1 = graph.find_connection(&1);
-		let removed_connection_2 = graph.find_connection(&(1 + ids_per_page * 2));
-		assert!(removed_connection_1.is_none());
-		assert!(removed_connection_2.is_none());
+		// act
+		let report = graph.remaining_capacity(&vec![]).unwrap();
 
-		let added_connection_1 = graph.find_connection(&(curr_id + 1));
-		let added_connection_2 = graph.find_connection(&(curr_id + 2));
-		assert_eq!(added_connection_1, Some(0));
-		assert_eq!(added_connection_2, Some(0));
+		// assert
+		assert_eq!(report.used_pages, 2);
+		assert_eq!(report.full_page_ids, vec![2, 5]);
+		assert_eq!(report.remaining_connections, (max_pages - 2) * max_connections);
 	}
 
 	/// Helper for testing calculating updates when all existing pages are
@@ -1538,8 +3782,14 @@ mod test {
 			);
 
 			// assert
-			assert!(one_sided.is_err());
-			assert!(prids.is_err());
+			assert!(matches!(
+				one_sided,
+				Err(DsnpGraphError::UnsupportedOperation(_, Capability::Prids, c)) if c == connection_type
+			));
+			assert!(matches!(
+				prids,
+				Err(DsnpGraphError::UnsupportedOperation(_, Capability::Prids, c)) if c == connection_type
+			));
 		}
 	}
 
@@ -1771,7 +4021,10 @@ mod test {
 			let _ = graph.create_page(&(i as PageId), Some(p));
 		}
 		// act
-		let updates = graph.force_recalculate(&DsnpVersionConfig::new(DsnpVersion::Version1_0));
+		let updates = graph.force_recalculate(
+			&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			ExportOptions::default(),
+		);
 
 		// assert
 		assert!(updates.is_ok());
@@ -1801,7 +4054,10 @@ mod test {
 			let _ = graph.create_page(&(i as PageId), Some(p));
 		}
 		// act
-		let updates = graph.force_recalculate(&DsnpVersionConfig::new(DsnpVersion::Version1_0));
+		let updates = graph.force_recalculate(
+			&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			ExportOptions::default(),
+		);
 
 		// assert
 		assert!(updates.is_ok());
@@ -1835,7 +4091,10 @@ mod test {
 			let _ = graph.create_page(&(i as PageId), Some(p));
 		}
 		// act
-		let updates = graph.force_recalculate(&DsnpVersionConfig::new(DsnpVersion::Version1_0));
+		let updates = graph.force_recalculate(
+			&DsnpVersionConfig::new(DsnpVersion::Version1_0),
+			ExportOptions::default(),
+		);
 
 		// assert
 		assert!(updates.is_ok());
@@ -1843,4 +4102,62 @@ mod test {
 		assert_eq!(updates.len(), 1);
 		assert!(matches!(updates.get(0).unwrap(), Update::PersistPage { .. }));
 	}
+
+	#[test]
+	fn validate_page_capacities_should_reject_connection_types_without_capacity() {
+		// arrange
+		let config = Environment::Mainnet.get_config().clone();
+		let mut capacities = PAGE_CAPACITY_MAP.clone();
+		capacities.remove(&ConnectionType::Friendship(PrivacyType::Private));
+
+		// act
+		let configured = validate_page_capacities(&config);
+		let missing = validate_capacities_with(&config, &capacities);
+
+		// assert
+		assert!(configured.is_ok());
+		assert!(matches!(
+			missing,
+			Err(DsnpGraphError::PageCapacityNotConfigured(ConnectionType::Friendship(
+				PrivacyType::Private
+			)))
+		));
+	}
+
+	#[test]
+	fn validate_page_capacities_should_accept_schemas_with_configured_capacity() {
+		// arrange
+		let mut config = Environment::Mainnet.get_config().clone();
+		let connection_type = ConnectionType::Friendship(PrivacyType::Private);
+		let schema_id = config.get_schema_id_from_connection_type(connection_type).unwrap();
+		let mut capacities = PAGE_CAPACITY_MAP.clone();
+		capacities.remove(&connection_type);
+		config.schema_map.get_mut(&schema_id).unwrap().page_capacity = NonZeroU32::new(3);
+
+		// act
+		let validated = validate_capacities_with(&config, &capacities);
+		let capacity = schema_page_capacity(&config, &capacities, schema_id);
+
+		// assert
+		assert!(validated.is_ok());
+		assert_eq!(capacity.unwrap(), 3);
+	}
+
+	#[test]
+	fn page_capacity_should_fail_for_missing_connection_type() {
+		// arrange
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+		let capacities = HashMap::from([(connection_type, 10)]);
+
+		// act
+		let configured = page_capacity(&capacities, connection_type);
+		let missing = page_capacity(&capacities, ConnectionType::Follow(PrivacyType::Private));
+
+		// assert
+		assert_eq!(configured.unwrap(), 10);
+		let error = missing.unwrap_err();
+		assert!(matches!(error, DsnpGraphError::PageCapacityNotConfigured(_)));
+		assert!(!error.is_page_full());
+		assert!(DsnpGraphError::PageAggressivelyFull.is_page_full());
+	}
 }
@@ -1,20 +1,23 @@
 use crate::{
-	api::api_types::{GraphKeyPair, PageData, ResolvedKeyPair},
+	api::api_types::{GraphKeyPair, PageDataRef, ResolvedKeyPair},
 	dsnp::{
-		dsnp_configs::{KeyPairType, SecretKeyType},
+		dsnp_configs::{KeyPairType, SecretKeyType, SharedSecretKeyOperations},
 		dsnp_types::{DsnpPrid, DsnpUserId},
-		pseudo_relationship_identifier::PridProvider,
 	},
 	graph::shared_state_manager::{
-		PriProvider, PublicKeyProvider, SharedStateManager, SHARED_STATE_MANAGER,
+		PriProvider, PublicKeyProvider, PublicKeySnapshot, SharedStateManager, SHARED_STATE_MANAGER,
+	},
+	util::{
+		snapshot::SnapshotCell, transactional_hashmap::Transactional,
+		transactional_vec::TransactionalVec,
 	},
-	util::{transactional_hashmap::Transactional, transactional_vec::TransactionalVec},
 };
 use dsnp_graph_config::errors::{DsnpGraphError, DsnpGraphResult};
 use log::Level;
 use log_result_proc_macro::log_result_err;
 use std::{
 	fmt::Debug,
+	panic::RefUnwindSafe,
 	sync::{Arc, RwLock},
 };
 
@@ -22,6 +25,10 @@ use std::{
 pub const USER_KEY_MANAGER: &str = "UserKeyManager";
 
 /// Common trait that manages public and private keys for each user
+///
+/// Resolved key pairs are only used through their `SecretKeyOperations`, so providers backed by
+/// secure enclaves or HSMs can resolve them as `KeyPairType::ExternalVersion1_0` handles which
+/// perform the decryption and key derivations without exposing the secret keys.
 pub trait UserKeyProvider {
 	/// imports key pairs into a provider
 	/// will overwrite any existing imported keys for the user
@@ -35,25 +42,105 @@ pub trait UserKeyProvider {
 
 	/// returns the active key for a a user to used for encryption
 	fn get_resolved_active_key(&self, dsnp_user_id: DsnpUserId) -> Option<ResolvedKeyPair>;
+
+	/// returns the imported key pairs so they can be backed up, providers that do not allow
+	/// secret keys to leave them such as HSMs can keep this default which fails
+	fn export_key_pairs(&self) -> DsnpGraphResult<Vec<GraphKeyPair>> {
+		Err(DsnpGraphError::InvalidInput(
+			"Key pairs of this key manager can not be exported".into(),
+		))
+	}
+
+	/// removes the imported key pair matching the public key, providers that do not allow
+	/// removing individual keys can keep this default which fails
+	fn remove_key_pair(&mut self, _public_key: &[u8]) -> DsnpGraphResult<()> {
+		Err(DsnpGraphError::InvalidInput("Key pairs of this key manager can not be removed".into()))
+	}
+
+	/// returns whether the provider holds any key pairs, providers that can not tell can keep this
+	/// default which assumes they do
+	fn holds_key_pairs(&self) -> bool {
+		true
+	}
 }
 
 pub trait ConnectionVerifier {
 	fn verify_connection(&self, from: DsnpUserId) -> DsnpGraphResult<bool>;
+
+	/// verifies multiple connections at once, returning the result in the same order
+	fn verify_connections(&self, from: &[DsnpUserId]) -> DsnpGraphResult<Vec<bool>>;
 }
 
 /// a combining trait that provides all functionalities required by user key manager
-pub trait UserKeyManagerBase: UserKeyProvider + PriProvider + ConnectionVerifier + Debug {}
+pub trait UserKeyManagerBase:
+	UserKeyProvider + PriProvider + ConnectionVerifier + Transactional + Debug
+{
+	/// estimated number of bytes used by this key manager in memory
+	fn memory_footprint(&self) -> usize;
+
+	/// creates an independent copy of this key manager, including its uncommitted changes, which
+	/// uses the provided shared state instead of the current one
+	fn clone_with_shared_state(
+		&self,
+		shared_state_manager: Arc<RwLock<SharedStateManager>>,
+	) -> SharedUserKeyManager;
+}
+
+/// Shared reference to the key manager of a user
+pub type SharedUserKeyManager = Arc<RwLock<dyn UserKeyManagerBase + 'static + Send + Sync>>;
+
+/// Creates the key manager of each user graph
+///
+/// Integrators can provide their own implementation to use key managers backed by secure
+/// enclaves or HSMs instead of the default in memory `UserKeyManager`, or create the default one
+/// using `UserKeyManager::with_secret_key_operations` to resolve externally held key pairs.
+pub trait UserKeyManagerFactory: Debug + Send + Sync + RefUnwindSafe {
+	/// creates the key manager of `dsnp_user_id`
+	fn create(
+		&self,
+		dsnp_user_id: DsnpUserId,
+		shared_state_manager: Arc<RwLock<SharedStateManager>>,
+	) -> SharedUserKeyManager;
+}
+
+/// Shared reference to a key manager factory
+pub type SharedUserKeyManagerFactory = Arc<dyn UserKeyManagerFactory>;
+
+/// Default factory which creates in memory `UserKeyManager`s
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DefaultUserKeyManagerFactory;
+
+impl UserKeyManagerFactory for DefaultUserKeyManagerFactory {
+	fn create(
+		&self,
+		dsnp_user_id: DsnpUserId,
+		shared_state_manager: Arc<RwLock<SharedStateManager>>,
+	) -> SharedUserKeyManager {
+		Arc::new(RwLock::new(UserKeyManager::new(dsnp_user_id, shared_state_manager)))
+	}
+}
+
+/// Returns the default key manager factory
+pub fn default_user_key_manager_factory() -> SharedUserKeyManagerFactory {
+	Arc::new(DefaultUserKeyManagerFactory)
+}
 
 #[derive(Debug)]
 pub struct UserKeyManager {
 	/// keeps a reference to the shared instance of shared public keys and PRIDs
 	shared_state_manager: Arc<RwLock<SharedStateManager>>,
 
+	/// committed public keys and PRIds used to verify connections without locking the shared state
+	committed: Arc<SnapshotCell<PublicKeySnapshot>>,
+
 	/// current user dsnp id that this key manager belongs to
 	dsnp_user_id: DsnpUserId,
 
 	/// key pairs associated with this user
 	keys: TransactionalVec<KeyPairType>,
+
+	/// key pairs whose secret keys are only used through their operations, kept across imports
+	external_keys: Vec<KeyPairType>,
 }
 
 impl UserKeyProvider for UserKeyManager {
@@ -70,6 +157,26 @@ impl UserKeyProvider for UserKeyManager {
 		Ok(())
 	}
 
+	fn export_key_pairs(&self) -> DsnpGraphResult<Vec<GraphKeyPair>> {
+		self.keys.inner().iter().map(GraphKeyPair::try_from).collect()
+	}
+
+	#[log_result_err(Level::Info)]
+	fn remove_key_pair(&mut self, public_key: &[u8]) -> DsnpGraphResult<()> {
+		if !self.keys.inner().iter().any(|k| k.get_public_key_raw() == public_key) {
+			return Err(DsnpGraphError::InvalidInput(
+				"No imported key pair matches the public key".into(),
+			))
+		}
+
+		self.keys.retain(|k| k.get_public_key_raw() != public_key);
+		Ok(())
+	}
+
+	fn holds_key_pairs(&self) -> bool {
+		!self.keys.inner().is_empty() || !self.external_keys.is_empty()
+	}
+
 	fn get_resolved_key(&self, key_id: u64) -> Option<ResolvedKeyPair> {
 		if let Some(dsnp) = self
 			.shared_state_manager
@@ -77,11 +184,7 @@ impl UserKeyProvider for UserKeyManager {
 			.unwrap()
 			.get_key_by_id(self.dsnp_user_id, key_id)
 		{
-			if let Some(key_pair) =
-				self.keys.inner().iter().find(|&k| k.get_public_key_raw() == dsnp.key)
-			{
-				return Some(ResolvedKeyPair { key_id, key_pair: key_pair.clone() })
-			}
+			return self.resolve_key_pair(key_id, &dsnp.key)
 		}
 		None
 	}
@@ -111,13 +214,29 @@ impl UserKeyProvider for UserKeyManager {
 
 impl PriProvider for UserKeyManager {
 	#[log_result_err(Level::Info)]
-	fn import_pri(&mut self, dsnp_user_id: DsnpUserId, pages: &[PageData]) -> DsnpGraphResult<()> {
+	fn import_pri(
+		&mut self,
+		dsnp_user_id: DsnpUserId,
+		pages: &[PageDataRef],
+	) -> DsnpGraphResult<()> {
 		self.shared_state_manager
 			.write()
 			.map_err(|_| DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string()))?
 			.import_pri(dsnp_user_id, pages)
 	}
 
+	#[log_result_err(Level::Info)]
+	fn merge_pri(
+		&mut self,
+		dsnp_user_id: DsnpUserId,
+		pages: &[PageDataRef],
+	) -> DsnpGraphResult<()> {
+		self.shared_state_manager
+			.write()
+			.map_err(|_| DsnpGraphError::FailedtoWriteLock(SHARED_STATE_MANAGER.to_string()))?
+			.merge_pri(dsnp_user_id, pages)
+	}
+
 	fn contains(&self, dsnp_user_id: DsnpUserId, prid: DsnpPrid) -> bool {
 		self.shared_state_manager.read().unwrap().contains(dsnp_user_id, prid)
 	}
@@ -134,43 +253,67 @@ impl PriProvider for UserKeyManager {
 			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
 			.calculate_prid(from, to, from_secret)
 	}
+
+	#[log_result_err(Level::Info)]
+	fn calculate_prids(
+		&self,
+		from: DsnpUserId,
+		to: DsnpUserId,
+		from_secret: SecretKeyType,
+	) -> DsnpGraphResult<Vec<DsnpPrid>> {
+		self.shared_state_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
+			.calculate_prids(from, to, from_secret)
+	}
 }
 
 impl ConnectionVerifier for UserKeyManager {
 	#[log_result_err(Level::Info)]
 	fn verify_connection(&self, from: DsnpUserId) -> DsnpGraphResult<bool> {
-		let from_public_keys: Vec<_> = self
-			.shared_state_manager
-			.read()
-			.map_err(|_| DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string()))?
-			.get_prid_associated_public_keys(from)?;
-		let to_resolved_keys = self.get_all_resolved_keys();
+		let snapshot = self.committed.load();
+		let to_resolved_keys = self.get_committed_resolved_keys(&snapshot);
+		snapshot.verify_connection(self.dsnp_user_id, &to_resolved_keys, from)
+	}
 
-		for public in from_public_keys {
-			for private in to_resolved_keys.iter().rev() {
-				let prid = DsnpPrid::create_prid(
-					from,
-					self.dsnp_user_id,
-					&private.key_pair.clone().into(),
-					&public,
-				)?;
-				if self
-					.shared_state_manager
-					.read()
-					.map_err(|_| {
-						DsnpGraphError::FailedtoReadLock(SHARED_STATE_MANAGER.to_string())
-					})?
-					.contains(from, prid)
-				{
-					return Ok(true)
-				}
-			}
-		}
-		Ok(false)
+	#[log_result_err(Level::Info)]
+	fn verify_connections(&self, from: &[DsnpUserId]) -> DsnpGraphResult<Vec<bool>> {
+		let snapshot = self.committed.load();
+		let to_resolved_keys = self.get_committed_resolved_keys(&snapshot);
+		let verified =
+			snapshot.verify_connections_batch(self.dsnp_user_id, &to_resolved_keys, from);
+
+		from.iter()
+			.zip(verified)
+			.map(|(from, verified)| match verified {
+				Some(verified) => Ok(verified),
+				// verifying again to surface the underlying error
+				None => snapshot.verify_connection(self.dsnp_user_id, &to_resolved_keys, *from),
+			})
+			.collect()
 	}
 }
 
-impl UserKeyManagerBase for UserKeyManager {}
+impl UserKeyManagerBase for UserKeyManager {
+	fn memory_footprint(&self) -> usize {
+		std::mem::size_of::<Self>() +
+			(self.keys.len() + self.external_keys.len()) * std::mem::size_of::<KeyPairType>()
+	}
+
+	fn clone_with_shared_state(
+		&self,
+		shared_state_manager: Arc<RwLock<SharedStateManager>>,
+	) -> SharedUserKeyManager {
+		let committed = shared_state_manager.read().unwrap().committed_snapshot();
+		Arc::new(RwLock::new(Self {
+			shared_state_manager,
+			committed,
+			dsnp_user_id: self.dsnp_user_id,
+			keys: self.keys.clone(),
+			external_keys: self.external_keys.clone(),
+		}))
+	}
+}
 
 impl Transactional for UserKeyManager {
 	fn commit(&mut self) {
@@ -188,13 +331,52 @@ impl UserKeyManager {
 		dsnp_user_id: DsnpUserId,
 		public_key_manager: Arc<RwLock<SharedStateManager>>,
 	) -> Self {
+		let committed = public_key_manager.read().unwrap().committed_snapshot();
 		Self {
 			shared_state_manager: public_key_manager,
+			committed,
 			dsnp_user_id,
 			keys: TransactionalVec::new(),
+			external_keys: vec![],
 		}
 	}
 
+	/// creates a new instance of `UserKeyManager` which also resolves the provided key pairs, such
+	/// as key pairs kept inside an HSM, without ever accessing their secret keys
+	pub fn with_secret_key_operations(
+		dsnp_user_id: DsnpUserId,
+		public_key_manager: Arc<RwLock<SharedStateManager>>,
+		key_pairs: Vec<SharedSecretKeyOperations>,
+	) -> Self {
+		Self {
+			external_keys: key_pairs.into_iter().map(KeyPairType::ExternalVersion1_0).collect(),
+			..Self::new(dsnp_user_id, public_key_manager)
+		}
+	}
+
+	/// returns the key pair matching the public key of an imported key
+	fn resolve_key_pair(&self, key_id: u64, public_key: &[u8]) -> Option<ResolvedKeyPair> {
+		self.keys
+			.inner()
+			.iter()
+			.chain(self.external_keys.iter())
+			.find(|&k| k.get_public_key_raw() == public_key)
+			.map(|key_pair| ResolvedKeyPair { key_id, key_pair: key_pair.clone() })
+	}
+
+	/// returns the key pairs matching the committed keys of this user in the snapshot
+	fn get_committed_resolved_keys(&self, snapshot: &PublicKeySnapshot) -> Vec<ResolvedKeyPair> {
+		snapshot
+			.get_imported_keys(self.dsnp_user_id)
+			.iter()
+			.filter_map(|dsnp| dsnp.key_id)
+			.filter_map(|key_id| {
+				let dsnp = snapshot.get_key_by_id(self.dsnp_user_id, key_id)?;
+				self.resolve_key_pair(key_id, &dsnp.key)
+			})
+			.collect()
+	}
+
 	#[cfg(test)]
 	pub fn get_imported_keys(&self) -> &Vec<KeyPairType> {
 		self.keys.inner()
@@ -206,7 +388,10 @@ mod tests {
 	use super::*;
 	use crate::{
 		api::api_types::{DsnpKeys, KeyData},
-		dsnp::{dsnp_types::DsnpPublicKey, reader_writer::DsnpWriter},
+		dsnp::{
+			dsnp_types::DsnpPublicKey, pseudo_relationship_identifier::PridProvider,
+			reader_writer::DsnpWriter,
+		},
 		frequency::Frequency,
 	};
 	use dryoc::keypair::StackKeyPair;
@@ -252,4 +437,90 @@ mod tests {
 		let resolved_active = user_key_manager.get_resolved_active_key(dsnp_user_id);
 		assert_eq!(resolved_active, Some(ResolvedKeyPair { key_id: id1, key_pair: key_pair_type }));
 	}
+
+	#[test]
+	fn user_key_manager_should_verify_committed_connections_without_locking_shared_state() {
+		// arrange
+		let (owner, from) = (2, 3);
+		let shared_state_manager = Arc::new(RwLock::new(SharedStateManager::new()));
+		let mut user_key_manager = UserKeyManager::new(owner, shared_state_manager.clone());
+		let owner_key_pair = StackKeyPair::gen();
+		let from_key_pair = KeyPairType::Version1_0(StackKeyPair::gen());
+		user_key_manager
+			.import_key_pairs(vec![GraphKeyPair {
+				secret_key: owner_key_pair.secret_key.to_vec(),
+				public_key: owner_key_pair.public_key.to_vec(),
+				key_type: GraphKeyType::X25519,
+			}])
+			.expect("should import");
+		let prid = DsnpPrid::create_prid(
+			from,
+			owner,
+			&from_key_pair.clone().into(),
+			&(&KeyPairType::Version1_0(owner_key_pair.clone())).into(),
+		)
+		.expect("should create prid");
+		{
+			let mut manager = shared_state_manager.write().unwrap();
+			manager
+				.import_keys_test(
+					owner,
+					&[DsnpPublicKey { key: owner_key_pair.public_key.to_vec(), key_id: Some(0) }],
+					0,
+				)
+				.expect("should import");
+			manager
+				.import_keys_test(
+					from,
+					&[DsnpPublicKey { key: from_key_pair.get_public_key_raw(), key_id: Some(0) }],
+					0,
+				)
+				.expect("should import");
+			manager.import_prids_test(from, &[prid], 0).expect("should import");
+		}
+		let before_commit = user_key_manager.verify_connections(&[from]);
+		shared_state_manager.write().unwrap().commit();
+
+		// act
+		let write_lock = shared_state_manager.write().unwrap();
+		let verified = user_key_manager.verify_connections(&[from]);
+		drop(write_lock);
+
+		// assert
+		assert!(
+			matches!(before_commit, Err(DsnpGraphError::NoPrisImportedForUser(id)) if id == from)
+		);
+		assert_eq!(verified.expect("should verify"), vec![true]);
+	}
+
+	#[test]
+	fn user_key_manager_should_remove_key_pair_and_rollback_removal() {
+		// arrange
+		let shared_state_manager = Arc::new(RwLock::new(SharedStateManager::new()));
+		let mut user_key_manager = UserKeyManager::new(2, shared_state_manager);
+		let key_pairs: Vec<_> = (0..2)
+			.map(|_| {
+				let key_pair = StackKeyPair::gen();
+				GraphKeyPair {
+					secret_key: key_pair.secret_key.to_vec(),
+					public_key: key_pair.public_key.to_vec(),
+					key_type: GraphKeyType::X25519,
+				}
+			})
+			.collect();
+		user_key_manager.import_key_pairs(key_pairs.clone()).expect("should import");
+		user_key_manager.commit();
+
+		// act
+		let removed = user_key_manager.remove_key_pair(&key_pairs[0].public_key);
+		let missing = user_key_manager.remove_key_pair(&key_pairs[0].public_key);
+
+		// assert
+		assert!(removed.is_ok());
+		assert!(matches!(missing, Err(DsnpGraphError::InvalidInput(_))));
+		assert_eq!(user_key_manager.export_key_pairs().unwrap(), vec![key_pairs[1].clone()]);
+
+		user_key_manager.rollback();
+		assert_eq!(user_key_manager.export_key_pairs().unwrap(), key_pairs);
+	}
 }
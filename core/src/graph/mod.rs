@@ -2,6 +2,7 @@
 pub mod graph;
 pub mod key_manager;
 pub mod page;
+pub mod page_cache;
 pub mod page_capacities;
 pub mod shared_state_manager;
 pub mod updates;
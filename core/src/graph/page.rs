@@ -4,15 +4,19 @@ use dsnp_graph_config::errors::{DsnpGraphError, DsnpGraphResult};
 
 use crate::{
 	dsnp::{
-		dsnp_configs::DsnpVersionConfig,
-		reader_writer::{DsnpReader, DsnpWriter},
-		schema::SchemaHandler,
+		chain_adapter::ChainAdapter,
+		compression::CompressionFormat,
+		dsnp_configs::{DsnpVersionConfig, PublicKeyType},
 	},
 	frequency::Frequency,
-	util::{transactional_hashmap::Transactional, transactional_vec::TransactionalVec},
+	util::{
+		bloom_filter::ConnectionBloomFilter, state_hash::StateHasher,
+		transactional_hashmap::Transactional, transactional_vec::TransactionalVec,
+	},
 };
 use log::Level;
 use log_result_proc_macro::log_result_err;
+use std::collections::HashSet;
 
 /// A traits that returns a removed page binary payload according to the DSNP Graph schema
 pub trait RemovedPageDataProvider {
@@ -42,25 +46,33 @@ pub struct GraphPage {
 	privacy_type: PrivacyType,
 	/// Current content hash of page as retrieved from chain
 	content_hash: u32,
+	/// Id of the key used to decrypt this page on import (only for private pages)
+	key_id: Option<u64>,
+	/// Compression format the page was stored with on chain
+	compression_format: CompressionFormat,
 	/// List of PRIds
 	prids: TransactionalVec<DsnpPrid>,
 	/// List of connections
 	connections: TransactionalVec<DsnpGraphEdge>,
+	/// Bloom filter of the current connections, used to skip membership scans of this page
+	filter: ConnectionBloomFilter,
 }
 
 /// Conversion for Public Graph
 impl TryFrom<&PageData> for GraphPage {
 	type Error = DsnpGraphError;
 
-	#[log_result_err(Level::Info)]
-	fn try_from(PageData { content_hash, content, page_id }: &PageData) -> DsnpGraphResult<Self> {
-		Ok(Self {
-			page_id: *page_id,
-			privacy_type: PrivacyType::Public,
-			content_hash: *content_hash,
-			prids: TransactionalVec::new(),
-			connections: TransactionalVec::from(Frequency::read_public_graph(&content)?),
-		})
+	fn try_from(page_data: &PageData) -> DsnpGraphResult<Self> {
+		GraphPage::try_from(PageDataRef::from(page_data))
+	}
+}
+
+/// Conversion for borrowed Public Graph
+impl TryFrom<PageDataRef<'_>> for GraphPage {
+	type Error = DsnpGraphError;
+
+	fn try_from(page_data: PageDataRef<'_>) -> DsnpGraphResult<Self> {
+		GraphPage::from_public_page(page_data, &Frequency)
 	}
 }
 
@@ -68,27 +80,107 @@ impl TryFrom<&PageData> for GraphPage {
 impl TryFrom<(&PageData, &DsnpVersionConfig, &Vec<ResolvedKeyPair>)> for GraphPage {
 	type Error = DsnpGraphError;
 
-	#[log_result_err(Level::Info)]
 	fn try_from(
-		(PageData { content_hash, content, page_id }, dsnp_version_config, keys): (
+		(page_data, dsnp_version_config, keys): (
 			&PageData,
 			&DsnpVersionConfig,
 			&Vec<ResolvedKeyPair>,
 		),
 	) -> DsnpGraphResult<Self> {
-		let mut private_graph_chunk: Option<PrivateGraphChunk> = None;
+		GraphPage::try_from((PageDataRef::from(page_data), dsnp_version_config, keys))
+	}
+}
+
+/// Conversion for borrowed Private Graph
+impl TryFrom<(PageDataRef<'_>, &DsnpVersionConfig, &Vec<ResolvedKeyPair>)> for GraphPage {
+	type Error = DsnpGraphError;
+
+	fn try_from(
+		(page_data, dsnp_version_config, keys): (
+			PageDataRef<'_>,
+			&DsnpVersionConfig,
+			&Vec<ResolvedKeyPair>,
+		),
+	) -> DsnpGraphResult<Self> {
+		GraphPage::from_private_page(page_data, dsnp_version_config, keys, &Frequency)
+	}
+}
+
+impl RemovedPageDataProvider for GraphPage {
+	fn to_removed_page_data(&self) -> PageData {
+		PageData { content_hash: self.content_hash, page_id: self.page_id, content: Vec::new() }
+	}
+}
+
+impl PublicPageDataProvider for GraphPage {
+	fn to_public_page_data(&self) -> DsnpGraphResult<PageData> {
+		self.to_public_page(&Frequency, false)
+	}
+}
+
+impl PrivatePageDataProvider for GraphPage {
+	fn to_private_page_data(
+		&self,
+		dsnp_version_config: &DsnpVersionConfig,
+		key: &ResolvedKeyPair,
+	) -> DsnpGraphResult<PageData> {
+		self.to_private_page_data_with_nonce_mode(dsnp_version_config, key, NonceMode::Random)
+	}
+}
+
+impl GraphPage {
+	/// returns the private page binary payload using the provided nonce mode
+	///
+	/// In `Deterministic` mode the encryption randomness is bound to the key id, page id and
+	/// the content hash of the page, so retrying the same export results in the same payload
+	pub fn to_private_page_data_with_nonce_mode(
+		&self,
+		dsnp_version_config: &DsnpVersionConfig,
+		key: &ResolvedKeyPair,
+		nonce_mode: NonceMode,
+	) -> DsnpGraphResult<PageData> {
+		self.to_private_page(dsnp_version_config, key, nonce_mode, None, &Frequency)
+	}
+
+	/// reads a public page using the payload format of the chain adapter
+	#[log_result_err(Level::Info)]
+	pub fn from_public_page(
+		PageDataRef { content_hash, content, page_id }: PageDataRef<'_>,
+		chain_adapter: &dyn ChainAdapter,
+	) -> DsnpGraphResult<Self> {
+		let (connections, compression_format) = chain_adapter.read_public_page(content)?;
+		Ok(Self {
+			page_id,
+			privacy_type: PrivacyType::Public,
+			content_hash,
+			key_id: None,
+			compression_format,
+			prids: TransactionalVec::new(),
+			filter: ConnectionBloomFilter::from_ids(connections.iter().map(|c| &c.user_id)),
+			connections: TransactionalVec::from(connections),
+		})
+	}
+
+	/// decrypts a private page using the payload format of the chain adapter
+	#[log_result_err(Level::Info)]
+	pub fn from_private_page(
+		PageDataRef { content_hash, content, page_id }: PageDataRef<'_>,
+		dsnp_version_config: &DsnpVersionConfig,
+		keys: &Vec<ResolvedKeyPair>,
+		chain_adapter: &dyn ChainAdapter,
+	) -> DsnpGraphResult<Self> {
+		let mut private_graph_chunk: Option<((PrivateGraphChunk, CompressionFormat), u64)> = None;
 
 		// read key_id from page
-		let DsnpUserPrivateGraphChunk { key_id, .. } =
-			SchemaHandler::read_private_graph_chunk(&content)?;
+		let key_id = chain_adapter.read_private_page_key_id(content)?;
 
 		// First try the key that was indicated in the page
 		if let Some(indicated_key) = keys.iter().find(|k| k.key_id == key_id) {
 			let secret_key = indicated_key.key_pair.clone().into();
 			if let Ok(chunk) =
-				Frequency::read_private_graph(&content, &dsnp_version_config, &secret_key)
+				chain_adapter.read_private_page(content, dsnp_version_config, &secret_key)
 			{
-				private_graph_chunk = Some(chunk);
+				private_graph_chunk = Some((chunk, indicated_key.key_id));
 			}
 		}
 
@@ -97,9 +189,9 @@ impl TryFrom<(&PageData, &DsnpVersionConfig, &Vec<ResolvedKeyPair>)> for GraphPa
 			for other_key in keys.iter().filter(|k| k.key_id != key_id) {
 				let secret_key = other_key.key_pair.clone().into();
 				if let Ok(chunk) =
-					Frequency::read_private_graph(&content, &dsnp_version_config, &secret_key)
+					chain_adapter.read_private_page(content, dsnp_version_config, &secret_key)
 				{
-					private_graph_chunk = Some(chunk);
+					private_graph_chunk = Some((chunk, other_key.key_id));
 					break
 				}
 			}
@@ -107,62 +199,106 @@ impl TryFrom<(&PageData, &DsnpVersionConfig, &Vec<ResolvedKeyPair>)> for GraphPa
 
 		match private_graph_chunk {
 			None => Err(DsnpGraphError::UnableToDecryptGraphChunkWithAnyKey),
-			Some(chunk) => Ok(GraphPage {
-				page_id: *page_id,
+			Some(((chunk, compression_format), used_key_id)) => Ok(GraphPage {
+				page_id,
 				privacy_type: PrivacyType::Private,
-				content_hash: *content_hash,
+				content_hash,
+				key_id: Some(used_key_id),
+				compression_format,
 				prids: TransactionalVec::from(chunk.prids),
+				filter: ConnectionBloomFilter::from_ids(
+					chunk.inner_graph.iter().map(|c| &c.user_id),
+				),
 				connections: TransactionalVec::from(chunk.inner_graph),
 			}),
 		}
 	}
-}
-
-impl RemovedPageDataProvider for GraphPage {
-	fn to_removed_page_data(&self) -> PageData {
-		PageData { content_hash: self.content_hash, page_id: self.page_id, content: Vec::new() }
-	}
-}
 
-impl PublicPageDataProvider for GraphPage {
+	/// returns the public page binary payload using the format of the chain adapter
+	///
+	/// connections are written in their canonical order sorted by dsnp user id and then by their
+	/// since value, so the same logical page always results in the same payload regardless of the
+	/// order the connections were added in. `legacy_order` writes them in their stored order
 	#[log_result_err(Level::Info)]
-	fn to_public_page_data(&self) -> DsnpGraphResult<PageData> {
+	pub fn to_public_page(
+		&self,
+		chain_adapter: &dyn ChainAdapter,
+		legacy_order: bool,
+	) -> DsnpGraphResult<PageData> {
 		if self.privacy_type != PrivacyType::Public {
 			return Err(DsnpGraphError::IncompatiblePrivacyTypeForBlobExport)
 		}
 
-		Ok(PageData {
-			content_hash: self.content_hash,
-			page_id: self.page_id,
-			content: Frequency::write_public_graph(self.connections())?,
-		})
+		let content = match legacy_order {
+			true => chain_adapter.write_public_page(self.connections())?,
+			false => chain_adapter.write_public_page(&self.canonical_connections())?,
+		};
+		Ok(PageData { content_hash: self.content_hash, page_id: self.page_id, content })
 	}
-}
 
-impl PrivatePageDataProvider for GraphPage {
+	/// returns the connections sorted by dsnp user id and then by their since value
+	pub fn canonical_connections(&self) -> Vec<DsnpGraphEdge> {
+		let mut connections = self.connections().clone();
+		connections.sort_by_key(|c| (c.user_id, c.since));
+		connections
+	}
+
+	/// returns the private page binary payload using the provided nonce mode and the format of
+	/// the chain adapter
+	///
+	/// If `additional_recipients` are provided, the page is sealed to the key and all of them
+	/// using multi-recipient encryption, which requires `NonceMode::Random`
 	#[log_result_err(Level::Info)]
-	fn to_private_page_data(
+	pub fn to_private_page(
 		&self,
 		dsnp_version_config: &DsnpVersionConfig,
 		key: &ResolvedKeyPair,
+		nonce_mode: NonceMode,
+		additional_recipients: Option<&[PublicKeyType]>,
+		chain_adapter: &dyn ChainAdapter,
 	) -> DsnpGraphResult<PageData> {
 		if self.privacy_type != PrivacyType::Private {
 			return Err(DsnpGraphError::IncompatiblePrivacyTypeForBlobExport)
 		}
 
-		Ok(PageData {
-			page_id: self.page_id,
-			content_hash: self.content_hash,
-			content: Frequency::write_private_graph(
-				&PrivateGraphChunk {
-					prids: self.prids.inner().clone(),
-					inner_graph: self.connections.inner().clone(),
-					key_id: key.clone().key_id,
-				},
+		let chunk = PrivateGraphChunk {
+			prids: self.prids.inner().clone(),
+			inner_graph: self.connections.inner().clone(),
+			key_id: key.key_id,
+		};
+		let content = match (nonce_mode, additional_recipients) {
+			(NonceMode::Random, Some(additional_recipients)) => {
+				let mut recipients: Vec<PublicKeyType> = vec![(&key.key_pair).into()];
+				for recipient in additional_recipients {
+					if !recipients.contains(recipient) {
+						recipients.push(recipient.clone());
+					}
+				}
+				chain_adapter.write_private_page_multi(&chunk, dsnp_version_config, &recipients)?
+			},
+			(NonceMode::Deterministic, Some(_)) =>
+				return Err(DsnpGraphError::EncryptionError(
+					"Deterministic nonces are not supported for multiple recipients".to_string(),
+				)),
+			(NonceMode::Random, None) => chain_adapter.write_private_page(
+				&chunk,
 				dsnp_version_config,
 				&(&key.key_pair).into(),
 			)?,
-		})
+			(NonceMode::Deterministic, None) => {
+				let mut context = key.key_id.to_le_bytes().to_vec();
+				context.extend_from_slice(&self.page_id.to_le_bytes());
+				context.extend_from_slice(&self.content_hash.to_le_bytes());
+				chain_adapter.write_private_page_deterministic(
+					&chunk,
+					dsnp_version_config,
+					&key.key_pair,
+					&context,
+				)?
+			},
+		};
+
+		Ok(PageData { page_id: self.page_id, content_hash: self.content_hash, content })
 	}
 }
 
@@ -176,6 +312,7 @@ impl Transactional for GraphPage {
 	fn rollback(&mut self) {
 		self.prids.rollback();
 		self.connections.rollback();
+		self.refresh_filter();
 	}
 }
 
@@ -186,8 +323,11 @@ impl GraphPage {
 			page_id,
 			privacy_type,
 			content_hash: 0,
+			key_id: None,
+			compression_format: CompressionFormat::Deflate,
 			prids: TransactionalVec::<DsnpPrid>::new(),
 			connections: TransactionalVec::<DsnpGraphEdge>::new(),
+			filter: ConnectionBloomFilter::default(),
 		}
 	}
 
@@ -205,6 +345,7 @@ impl GraphPage {
 	pub fn set_connections(&mut self, connections: Vec<DsnpGraphEdge>) {
 		self.connections.clear();
 		self.connections.extend_from_slice(&connections);
+		self.refresh_filter();
 	}
 
 	/// Getter for the content hash
@@ -217,6 +358,26 @@ impl GraphPage {
 		self.content_hash = content_hash;
 	}
 
+	/// Getter for the id of the key used to decrypt this page on import
+	pub fn key_id(&self) -> Option<u64> {
+		self.key_id
+	}
+
+	/// Setter for the id of the key used to decrypt this page on import
+	pub fn set_key_id(&mut self, key_id: Option<u64>) {
+		self.key_id = key_id;
+	}
+
+	/// Getter for the compression format the page was stored with on chain
+	pub fn compression_format(&self) -> CompressionFormat {
+		self.compression_format
+	}
+
+	/// Returns true if the page was stored with a legacy format and should be rewritten on export
+	pub fn needs_format_upgrade(&self) -> bool {
+		self.compression_format.is_legacy()
+	}
+
 	/// Get page id
 	pub fn page_id(&self) -> PageId {
 		self.page_id
@@ -224,16 +385,59 @@ impl GraphPage {
 
 	/// Tester to check if the page contains a connection to a particular DsnpUserId
 	pub fn contains(&self, connection_id: &DsnpUserId) -> bool {
-		self.connections.inner().iter().any(|c| c.user_id == *connection_id)
+		self.filter.may_contain(connection_id) &&
+			self.connections.inner().iter().any(|c| c.user_id == *connection_id)
 	}
 
 	/// Checks if any of the users contains in this pages connections
 	pub fn contains_any(&self, connections: &Vec<DsnpUserId>) -> bool {
-		self.connections
-			.inner()
-			.iter()
-			.map(|c| c.user_id)
-			.any(|id| connections.contains(&id))
+		self.filter.may_contain_any(connections) &&
+			self.connections
+				.inner()
+				.iter()
+				.map(|c| c.user_id)
+				.any(|id| connections.contains(&id))
+	}
+
+	/// Returns false if none of the users are in this pages connections, without scanning them
+	pub fn may_contain_any<'a>(
+		&self,
+		connections: impl IntoIterator<Item = &'a DsnpUserId>,
+	) -> bool {
+		self.filter.may_contain_any(connections)
+	}
+
+	/// rebuilds the bloom filter from the current connections, which is needed after removals
+	/// since a bloom filter can not forget ids
+	fn refresh_filter(&mut self) {
+		self.filter =
+			ConnectionBloomFilter::from_ids(self.connections.inner().iter().map(|c| &c.user_id));
+	}
+
+	/// Estimated number of bytes used by this page in memory
+	pub fn memory_footprint(&self) -> usize {
+		std::mem::size_of::<Self>() +
+			self.filter.memory_footprint() +
+			self.connections.len() * std::mem::size_of::<DsnpGraphEdge>() +
+			self.prids.inner().iter().map(|prid| prid.memory_footprint()).sum::<usize>()
+	}
+
+	/// feeds the decrypted content of this page into the hasher, keeping the order of its
+	/// connections and PRIds
+	pub fn hash_into(&self, hasher: &mut StateHasher) {
+		hasher.write_u64(self.page_id as u64);
+		hasher.write_u64(self.content_hash as u64);
+		hasher.write_optional_u64(self.key_id);
+		hasher.write_u64(self.compression_format.is_legacy() as u64);
+		hasher.write_u64(self.connections.len() as u64);
+		for connection in self.connections.inner() {
+			hasher.write_u64(connection.user_id);
+			hasher.write_u64(connection.since);
+		}
+		hasher.write_u64(self.prids.len() as u64);
+		for prid in self.prids.inner() {
+			hasher.write_bytes(prid.as_bytes());
+		}
 	}
 
 	/// Function to test if the page is empty
@@ -244,12 +448,25 @@ impl GraphPage {
 	/// Add a connection to the page. Fail if the connection is already present.
 	#[log_result_err(Level::Info)]
 	pub fn add_connection(&mut self, connection_id: &DsnpUserId) -> DsnpGraphResult<()> {
+		self.add_connection_since(connection_id, time_in_ksecs())
+	}
+
+	/// Add a connection to the page with provided `since` timestamp. Fail if the connection is already present.
+	#[log_result_err(Level::Info)]
+	pub fn add_connection_since(
+		&mut self,
+		connection_id: &DsnpUserId,
+		since: u64,
+	) -> DsnpGraphResult<()> {
 		if self.contains(connection_id) {
 			return Err(DsnpGraphError::DuplicateConnectionDetected)
 		}
 
-		self.connections
-			.push(DsnpGraphEdge { user_id: *connection_id, since: time_in_ksecs() });
+		self.connections.push(DsnpGraphEdge { user_id: *connection_id, since });
+		match self.filter.needs_resize(self.connections.len()) {
+			true => self.refresh_filter(),
+			false => self.filter.insert(connection_id),
+		}
 		Ok(())
 	}
 
@@ -261,12 +478,89 @@ impl GraphPage {
 		}
 
 		self.connections.retain(|c| c.user_id != *connection_id);
+		self.refresh_filter();
+		Ok(())
+	}
+
+	/// Remove a connection from the page along with its PRId, if the PRIds are aligned with the
+	/// connections. Error if connection not found in page.
+	#[log_result_err(Level::Info)]
+	pub fn remove_connection_and_prid(
+		&mut self,
+		connection_id: &DsnpUserId,
+	) -> DsnpGraphResult<()> {
+		if !self.contains(connection_id) {
+			return Err(DsnpGraphError::ConnectionNotFound)
+		}
+
+		if self.prids.len() == self.connections.len() {
+			let prids: Vec<DsnpPrid> = self
+				.prids
+				.inner()
+				.iter()
+				.zip(self.connections.inner().iter())
+				.filter(|(_, c)| c.user_id != *connection_id)
+				.map(|(prid, _)| prid.clone())
+				.collect();
+			self.prids.clear();
+			self.prids.extend_from_slice(&prids);
+		}
+		self.connections.retain(|c| c.user_id != *connection_id);
+		self.refresh_filter();
 		Ok(())
 	}
 
 	/// Remove all connections in the list from the page. It is not an error if none of the connections are present.
+	/// Removes connections which are stored more than once in the page, keeping their first
+	/// occurrence along with its aligned prid, and returns the ids of the removed duplicates
+	pub fn remove_duplicate_connections(&mut self) -> Vec<DsnpUserId> {
+		let mut seen = HashSet::new();
+		let keep: Vec<bool> =
+			self.connections.inner().iter().map(|c| seen.insert(c.user_id)).collect();
+		if keep.iter().all(|keep| *keep) {
+			return vec![]
+		}
+
+		let duplicates: Vec<DsnpUserId> = self
+			.connections
+			.inner()
+			.iter()
+			.zip(keep.iter())
+			.filter(|(_, keep)| !**keep)
+			.map(|(c, _)| c.user_id)
+			.collect();
+		if self.prids.len() == self.connections.len() {
+			let prids: Vec<DsnpPrid> = self
+				.prids
+				.inner()
+				.iter()
+				.zip(keep.iter())
+				.filter(|(_, keep)| **keep)
+				.map(|(prid, _)| prid.clone())
+				.collect();
+			self.prids.clear();
+			self.prids.extend_from_slice(&prids);
+		}
+		let connections: Vec<DsnpGraphEdge> = self
+			.connections
+			.inner()
+			.iter()
+			.zip(keep.iter())
+			.filter(|(_, keep)| **keep)
+			.map(|(c, _)| *c)
+			.collect();
+		self.connections.clear();
+		self.connections.extend_from_slice(&connections);
+		self.refresh_filter();
+		duplicates
+	}
+
 	pub fn remove_connections(&mut self, ids: &Vec<DsnpUserId>) {
+		if !self.may_contain_any(ids) {
+			return
+		}
 		self.connections.retain(|c| !ids.contains(&c.user_id));
+		self.refresh_filter();
 	}
 
 	/// Refresh PRIds based on latest
@@ -413,6 +707,39 @@ mod test {
 		assert_eq!(page.contains(&id_to_remove), false);
 	}
 
+	#[test]
+	fn remove_connection_and_prid_removes_aligned_prid() {
+		let (ids, mut page) = create_test_ids_and_page();
+		let prids: Vec<DsnpPrid> =
+			ids.iter().map(|(id, _)| DsnpPrid::new(&id.to_le_bytes())).collect();
+		page.set_prids(prids.clone()).expect("should set prids");
+
+		assert_eq!(page.remove_connection_and_prid(&2).is_ok(), true);
+		assert_eq!(page.contains(&2), false);
+		assert_eq!(page.prids(), &vec![prids[0].clone(), prids[2].clone()]);
+		assert!(matches!(
+			page.remove_connection_and_prid(&2),
+			Err(DsnpGraphError::ConnectionNotFound)
+		));
+	}
+
+	#[test]
+	fn remove_duplicate_connections_keeps_first_occurrence_and_aligned_prid() {
+		let mut page = GraphPage::new(PrivacyType::Private, 1);
+		let ids: Vec<DsnpUserId> = vec![2, 3, 2, 4, 3];
+		page.set_connections(
+			ids.iter().map(|id| DsnpGraphEdge { user_id: *id, since: 0 }).collect(),
+		);
+		let prids: Vec<DsnpPrid> =
+			(0..ids.len() as u64).map(|i| DsnpPrid::new(&i.to_le_bytes())).collect();
+		page.set_prids(prids.clone()).expect("should set prids");
+
+		assert_eq!(page.remove_duplicate_connections(), vec![2, 3]);
+		assert_eq!(page.connections().iter().map(|c| c.user_id).collect::<Vec<_>>(), vec![2, 3, 4]);
+		assert_eq!(page.prids(), &vec![prids[0].clone(), prids[1].clone(), prids[3].clone()]);
+		assert!(page.remove_duplicate_connections().is_empty());
+	}
+
 	#[test]
 	fn remove_list_of_connections_removes_matching_connections() {
 		let (ids, mut page) = create_test_ids_and_page();
@@ -455,7 +782,10 @@ mod test {
 			page_id,
 			privacy_type,
 			content_hash,
+			key_id: None,
+			compression_format: CompressionFormat::Deflate,
 			prids: TransactionalVec::new(),
+			filter: ConnectionBloomFilter::from_ids(connections.iter().map(|(c, _)| c)),
 			connections: TransactionalVec::from(
 				connections
 					.iter()
@@ -490,7 +820,10 @@ mod test {
 			page_id,
 			privacy_type,
 			content_hash,
+			key_id: Some(1),
+			compression_format: CompressionFormat::Deflate,
 			prids: TransactionalVec::new(),
+			filter: ConnectionBloomFilter::from_ids(connections.iter().map(|(c, _)| c)),
 			connections: TransactionalVec::from(
 				connections
 					.iter()
@@ -527,7 +860,10 @@ mod test {
 			page_id,
 			privacy_type,
 			content_hash,
+			key_id: Some(1),
+			compression_format: CompressionFormat::Deflate,
 			prids: TransactionalVec::from(prids),
+			filter: ConnectionBloomFilter::from_ids(connections.iter().map(|(c, _)| c)),
 			connections: TransactionalVec::from(
 				connections
 					.iter()
@@ -570,7 +906,10 @@ mod test {
 			page_id,
 			privacy_type,
 			content_hash,
+			key_id: Some(2),
+			compression_format: CompressionFormat::Deflate,
 			prids: TransactionalVec::from(prids),
+			filter: ConnectionBloomFilter::from_ids(connections.iter().map(|(c, _)| c)),
 			connections: TransactionalVec::from(
 				connections
 					.iter()
@@ -621,7 +960,10 @@ mod test {
 			page_id: 1,
 			privacy_type: PrivacyType::Private,
 			content_hash: 10,
+			key_id: None,
+			compression_format: CompressionFormat::Deflate,
 			prids: TransactionalVec::from(vec![DsnpPrid::from(vec![1u8, 2, 3, 4, 5, 6, 7, 8])]),
+			filter: ConnectionBloomFilter::from_ids([70].iter()),
 			connections: TransactionalVec::from(vec![DsnpGraphEdge { user_id: 70, since: 2873 }]),
 		};
 		let expected = PageData { page_id: 1, content: vec![], content_hash: 10 };
@@ -647,7 +989,10 @@ mod test {
 			page_id,
 			privacy_type,
 			content_hash,
+			key_id: None,
+			compression_format: CompressionFormat::Deflate,
 			prids: TransactionalVec::new(),
+			filter: ConnectionBloomFilter::from_ids(connections.iter().map(|(c, _)| c)),
 			connections: TransactionalVec::from(
 				connections
 					.iter()
@@ -665,6 +1010,28 @@ mod test {
 		assert_eq!(&public, page_data.get(0).unwrap());
 	}
 
+	#[test]
+	fn public_page_export_should_sort_connections_unless_legacy_order_is_used() {
+		// arrange
+		let mut unordered = GraphPage::new(Public, 1);
+		let mut ordered = GraphPage::new(Public, 1);
+		for (id, since) in [(3, 10), (1, 20), (2, 5), (4, 10)] {
+			unordered.add_connection_since(&id, since).expect("should add");
+		}
+		for (id, since) in [(1, 20), (2, 5), (3, 10), (4, 10)] {
+			ordered.add_connection_since(&id, since).expect("should add");
+		}
+
+		// act
+		let canonical = unordered.to_public_page_data().expect("should write");
+		let legacy = unordered.to_public_page(&Frequency, true).expect("should write");
+
+		// assert
+		assert_eq!(canonical, ordered.to_public_page_data().expect("should write"));
+		assert_eq!(legacy.content, Frequency.write_public_page(unordered.connections()).unwrap());
+		assert_ne!(legacy, canonical);
+	}
+
 	#[test]
 	fn private_page_data_provider_should_return_private_page_as_expected() {
 		// arrange
@@ -725,8 +1092,11 @@ mod test {
 			page_id: 1,
 			privacy_type: PrivacyType::Private,
 			content_hash: 10,
+			key_id: None,
+			compression_format: CompressionFormat::Deflate,
 			prids: TransactionalVec::from(vec![prid.clone()]),
 			connections: TransactionalVec::from(vec![connection]),
+			filter: ConnectionBloomFilter::from_ids([connection.user_id].iter()),
 		};
 		page.add_connection(&10).expect("should add");
 		page.set_prids(vec![prid.clone(), DsnpPrid::from(vec![10u8, 20, 30, 40, 50, 60, 70, 80])])
@@ -738,5 +1108,30 @@ mod test {
 		// assert
 		assert_eq!(page.prids.inner(), &vec![prid]);
 		assert_eq!(page.connections.inner(), &vec![connection]);
+		assert!(!page.contains(&10));
+		#[cfg(not(feature = "disable-page-filter"))]
+		assert!(!page.may_contain_any(&[10]));
+	}
+
+	#[test]
+	fn graph_page_bloom_filter_should_follow_added_and_removed_connections() {
+		// arrange
+		let mut page = GraphPage::new(PrivacyType::Public, 1);
+		let mut other = GraphPage::new(PrivacyType::Public, 1);
+		for id in 1..=100 {
+			page.add_connection_since(&id, 0).expect("should add");
+		}
+		for id in 51..=100 {
+			other.add_connection_since(&id, 0).expect("should add");
+		}
+
+		// act
+		page.remove_connections(&(1..=50).collect());
+
+		// assert
+		assert!(page.may_contain_any(&[75]));
+		assert!(!page.contains_any(&vec![10, 20, 30]));
+		assert!(page.add_connection_since(&75, 0).is_err());
+		assert_eq!(page.filter, other.filter);
 	}
 }
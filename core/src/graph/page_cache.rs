@@ -0,0 +1,286 @@
+#![allow(dead_code)]
+//! Bounded cache of decrypted private pages used to avoid decrypting the same page content again
+use crate::{
+	api::api_types::{PageId, ResolvedKeyPair},
+	dsnp::dsnp_types::DsnpUserId,
+	graph::page::GraphPage,
+};
+use dryoc::classic::crypto_generichash::crypto_generichash;
+use dsnp_graph_config::SchemaId;
+use std::collections::{BTreeMap, HashMap};
+
+/// Default number of decrypted pages kept by a `GraphState`
+pub const DEFAULT_DECRYPTED_PAGE_CACHE_SIZE: usize = 256;
+
+/// Size of the digest of the encrypted page content in bytes
+const CONTENT_DIGEST_BYTES: usize = 32;
+
+/// Digest of the encrypted content of a page
+pub type ContentDigest = [u8; CONTENT_DIGEST_BYTES];
+
+/// Identifies the cached page of a graph
+pub type PageCacheKey = (DsnpUserId, SchemaId, PageId);
+
+/// Decrypted page along with the encrypted content and key it was decrypted from
+#[derive(Debug, Clone)]
+struct CachedPage {
+	/// content hash of the encrypted page as retrieved from chain
+	content_hash: u32,
+	/// digest of the encrypted content
+	digest: ContentDigest,
+	/// key used to decrypt the page
+	key: ResolvedKeyPair,
+	/// decrypted page
+	page: GraphPage,
+	/// value of the use counter when this page was last used
+	last_used: u64,
+}
+
+/// Keeps the most recently used decrypted pages until the changes are committed or rolled back, so
+/// importing the same encrypted content again in the same call reuses the plaintext instead of
+/// decrypting and decompressing it.
+///
+/// A cached page is only returned if the encrypted content is exactly the same and the key that
+/// decrypted it is still one of the keys provided on lookup.
+#[derive(Debug, Clone)]
+pub struct DecryptedPageCache {
+	/// maximum number of cached pages, zero disables the cache
+	capacity: usize,
+	/// cached page of each graph page
+	entries: HashMap<PageCacheKey, CachedPage>,
+	/// keys of the cached pages ordered by their last use
+	recency: BTreeMap<u64, PageCacheKey>,
+	/// incremented on every use of a cached page
+	use_counter: u64,
+}
+
+impl Default for DecryptedPageCache {
+	fn default() -> Self {
+		Self::new(DEFAULT_DECRYPTED_PAGE_CACHE_SIZE)
+	}
+}
+
+impl DecryptedPageCache {
+	/// creates an empty cache keeping at most `capacity` pages
+	pub fn new(capacity: usize) -> Self {
+		Self { capacity, entries: HashMap::new(), recency: BTreeMap::new(), use_counter: 0 }
+	}
+
+	/// returns the maximum number of cached pages
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// changes the maximum number of cached pages, evicting the least recently used ones
+	pub fn set_capacity(&mut self, capacity: usize) {
+		self.capacity = capacity;
+		self.evict();
+	}
+
+	/// returns true if caching is enabled
+	pub fn is_enabled(&self) -> bool {
+		self.capacity > 0
+	}
+
+	/// returns the number of cached pages
+	pub fn len(&self) -> usize {
+		self.entries.len()
+	}
+
+	/// returns true if no pages are cached
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	/// calculates the digest of the encrypted content of a page
+	pub fn digest(content: &[u8]) -> ContentDigest {
+		let mut digest = [0u8; CONTENT_DIGEST_BYTES];
+		// can only fail for invalid output or key lengths which are constant here
+		let _ = crypto_generichash(&mut digest, content, None);
+		digest
+	}
+
+	/// returns the cached decrypted page if it was decrypted from the same content using one of
+	/// the provided keys
+	pub fn get(
+		&mut self,
+		key: &PageCacheKey,
+		content_hash: u32,
+		digest: &ContentDigest,
+		keys: &[ResolvedKeyPair],
+	) -> Option<GraphPage> {
+		self.use_counter += 1;
+		let use_counter = self.use_counter;
+		let cached = self.entries.get_mut(key)?;
+		if cached.content_hash != content_hash ||
+			cached.digest != *digest ||
+			!keys.contains(&cached.key)
+		{
+			return None
+		}
+
+		self.recency.remove(&cached.last_used);
+		self.recency.insert(use_counter, *key);
+		cached.last_used = use_counter;
+		Some(cached.page.clone())
+	}
+
+	/// caches a page decrypted from the content with the given digest using one of the keys,
+	/// replacing any page previously cached for the same graph page
+	pub fn insert(
+		&mut self,
+		key: PageCacheKey,
+		digest: ContentDigest,
+		page: &GraphPage,
+		keys: &[ResolvedKeyPair],
+	) {
+		if !self.is_enabled() {
+			return
+		}
+		let used_key = match keys.iter().find(|k| Some(k.key_id) == page.key_id()) {
+			Some(used_key) => used_key.clone(),
+			None => return,
+		};
+
+		self.use_counter += 1;
+		let cached = CachedPage {
+			content_hash: page.content_hash(),
+			digest,
+			key: used_key,
+			page: page.clone(),
+			last_used: self.use_counter,
+		};
+		if let Some(previous) = self.entries.insert(key, cached) {
+			self.recency.remove(&previous.last_used);
+		}
+		self.recency.insert(self.use_counter, key);
+		self.evict();
+	}
+
+	/// removes the cached pages of a user
+	pub fn remove_user(&mut self, user_id: DsnpUserId) {
+		self.entries.retain(|(cached_user_id, ..), _| *cached_user_id != user_id);
+		self.recency.retain(|_, (cached_user_id, ..)| *cached_user_id != user_id);
+	}
+
+	/// removes the cached pages of a user which were decrypted using the key pair with the public
+	/// key
+	pub fn remove_key_pair(&mut self, user_id: DsnpUserId, public_key: &[u8]) {
+		self.entries.retain(|(cached_user_id, ..), cached| {
+			*cached_user_id != user_id || cached.key.key_pair.get_public_key_raw() != public_key
+		});
+		let entries = &self.entries;
+		self.recency.retain(|_, key| entries.contains_key(key));
+	}
+
+	/// removes all cached pages
+	pub fn clear(&mut self) {
+		self.entries.clear();
+		self.recency.clear();
+	}
+
+	/// removes the least recently used pages until the cache fits its capacity
+	fn evict(&mut self) {
+		while self.entries.len() > self.capacity {
+			match self.recency.pop_first() {
+				Some((_, key)) => {
+					self.entries.remove(&key);
+				},
+				None => break,
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::dsnp::dsnp_configs::KeyPairType;
+	use dryoc::keypair::StackKeyPair;
+	use dsnp_graph_config::PrivacyType;
+
+	fn decrypted_page(page_id: PageId, content_hash: u32, key_id: u64) -> GraphPage {
+		let mut page = GraphPage::new(PrivacyType::Private, page_id);
+		page.set_content_hash(content_hash);
+		page.set_key_id(Some(key_id));
+		page.add_connection(&(page_id as DsnpUserId + 100)).unwrap();
+		page
+	}
+
+	fn resolved_key(key_id: u64) -> ResolvedKeyPair {
+		ResolvedKeyPair { key_id, key_pair: KeyPairType::Version1_0(StackKeyPair::gen()) }
+	}
+
+	#[test]
+	fn get_should_only_return_pages_of_same_content_and_available_keys() {
+		// arrange
+		let mut cache = DecryptedPageCache::new(4);
+		let keys = vec![resolved_key(1)];
+		let digest = DecryptedPageCache::digest(b"encrypted page");
+		let page = decrypted_page(1, 10, 1);
+		cache.insert((1, 8, 1), digest, &page, &keys);
+
+		// act
+		let cached = cache.get(&(1, 8, 1), 10, &digest, &keys);
+		let other_hash = cache.get(&(1, 8, 1), 11, &digest, &keys);
+		let other_content =
+			cache.get(&(1, 8, 1), 10, &DecryptedPageCache::digest(b"other page"), &keys);
+		let other_keys = cache.get(&(1, 8, 1), 10, &digest, &[resolved_key(1)]);
+		let other_user = cache.get(&(2, 8, 1), 10, &digest, &keys);
+
+		// assert
+		assert_eq!(cached, Some(page));
+		assert_eq!(other_hash, None);
+		assert_eq!(other_content, None);
+		assert_eq!(other_keys, None);
+		assert_eq!(other_user, None);
+	}
+
+	#[test]
+	fn insert_should_evict_least_recently_used_pages() {
+		// arrange
+		let mut cache = DecryptedPageCache::new(2);
+		let keys = vec![resolved_key(1)];
+		let digest = DecryptedPageCache::digest(b"encrypted page");
+		for page_id in 1..=2 {
+			cache.insert((1, 8, page_id), digest, &decrypted_page(page_id, 10, 1), &keys);
+		}
+		assert!(cache.get(&(1, 8, 1), 10, &digest, &keys).is_some());
+
+		// act
+		cache.insert((1, 8, 3), digest, &decrypted_page(3, 10, 1), &keys);
+
+		// assert
+		assert_eq!(cache.len(), 2);
+		assert!(cache.get(&(1, 8, 1), 10, &digest, &keys).is_some());
+		assert!(cache.get(&(1, 8, 2), 10, &digest, &keys).is_none());
+		assert!(cache.get(&(1, 8, 3), 10, &digest, &keys).is_some());
+
+		cache.remove_user(1);
+		assert!(cache.is_empty());
+
+		cache.set_capacity(0);
+		cache.insert((1, 8, 1), digest, &decrypted_page(1, 10, 1), &keys);
+		assert!(cache.is_empty());
+	}
+
+	#[test]
+	fn remove_key_pair_should_only_evict_pages_of_user_decrypted_by_the_key() {
+		// arrange
+		let mut cache = DecryptedPageCache::new(4);
+		let keys = vec![resolved_key(1), resolved_key(2)];
+		let digest = DecryptedPageCache::digest(b"encrypted page");
+		cache.insert((1, 8, 1), digest, &decrypted_page(1, 10, 1), &keys);
+		cache.insert((1, 8, 2), digest, &decrypted_page(2, 10, 2), &keys);
+		cache.insert((2, 8, 1), digest, &decrypted_page(1, 10, 1), &keys);
+
+		// act
+		cache.remove_key_pair(1, &keys[0].key_pair.get_public_key_raw());
+
+		// assert
+		assert_eq!(cache.len(), 2);
+		assert!(cache.get(&(1, 8, 1), 10, &digest, &keys).is_none());
+		assert!(cache.get(&(1, 8, 2), 10, &digest, &keys).is_some());
+		assert!(cache.get(&(2, 8, 1), 10, &digest, &keys).is_some());
+	}
+}
@@ -1,27 +1,51 @@
 use crate::{
-	api::api_types::{DsnpKeys, PageData, PageHash, Update},
+	api::api_types::{
+		ChainHead, DsnpKeys, KeyPageCompaction, PageDataRef, PageHash, PreviewedUpdate,
+		ResolvedKeyPair, Update,
+	},
 	dsnp::{
+		chain_adapter::{default_chain_adapter, SharedChainAdapter},
 		dsnp_configs::{PublicKeyType, SecretKeyType},
 		dsnp_types::{DsnpPrid, DsnpPublicKey, DsnpUserId},
 		pseudo_relationship_identifier::PridProvider,
-		reader_writer::{DsnpReader, DsnpWriter},
 		schema::SchemaHandler,
 	},
-	frequency::Frequency,
-	util::transactional_hashmap::{Transactional, TransactionalHashMap},
+	util::{
+		snapshot::SnapshotCell,
+		state_hash::StateHasher,
+		transactional_hashmap::{Transactional, TransactionalHashMap},
+	},
+};
+use dsnp_graph_config::{
+	errors::{DsnpGraphError, DsnpGraphResult},
+	PageId,
 };
-use dsnp_graph_config::errors::{DsnpGraphError, DsnpGraphResult};
 use log::Level;
 use log_result_proc_macro::log_result_err;
-use std::collections::HashSet;
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	sync::Arc,
+};
 
 /// Constant used in errors
 pub const SHARED_STATE_MANAGER: &str = "SharedStateManager";
 
+/// Size of the header prepended to each item stored in an itemized page on chain
+const ITEMIZED_ITEM_HEADER_BYTES: usize = 2;
+
 /// A trait that defines all the functionality that a pri manager should implement.
 pub trait PriProvider {
 	/// imports pri for a user and replaces the older ones if exists
-	fn import_pri(&mut self, dsnp_user_id: DsnpUserId, pages: &[PageData]) -> DsnpGraphResult<()>;
+	fn import_pri(
+		&mut self,
+		dsnp_user_id: DsnpUserId,
+		pages: &[PageDataRef],
+	) -> DsnpGraphResult<()>;
+
+	/// imports pri of the provided pages of a user, replacing the ones imported from the same page
+	/// ids and keeping the ones of other pages
+	fn merge_pri(&mut self, dsnp_user_id: DsnpUserId, pages: &[PageDataRef])
+		-> DsnpGraphResult<()>;
 
 	/// checks if a pri exist for a specific user
 	fn contains(&self, dsnp_user_id: DsnpUserId, prid: DsnpPrid) -> bool;
@@ -32,6 +56,17 @@ pub trait PriProvider {
 		to: DsnpUserId,
 		from_secret: SecretKeyType,
 	) -> DsnpGraphResult<DsnpPrid>;
+
+	/// calculates the prids of `from` for each imported public key of `to`, so the prids written
+	/// using an older key of `to` can be recognized while `to` is rotating its keys
+	fn calculate_prids(
+		&self,
+		from: DsnpUserId,
+		to: DsnpUserId,
+		from_secret: SecretKeyType,
+	) -> DsnpGraphResult<Vec<DsnpPrid>> {
+		Ok(vec![self.calculate_prid(from, to, from_secret)?])
+	}
 }
 
 /// A trait that defines all the functionality that a public key provider need to implement.
@@ -40,17 +75,28 @@ pub trait PublicKeyProvider {
 	/// will overwrite any existing imported keys for the user and remove any new added keys
 	fn import_dsnp_keys(&mut self, keys: &DsnpKeys) -> DsnpGraphResult<()>;
 
+	/// imports public keys of a user without their key page, each key should have its key id set
+	/// will overwrite any existing imported keys for the user and remove any new added keys
+	fn import_public_keys(
+		&mut self,
+		dsnp_user_id: DsnpUserId,
+		keys: &[DsnpPublicKey],
+	) -> DsnpGraphResult<()>;
+
 	/// adds a new public key to the provider
 	fn add_new_key(&mut self, dsnp_user_id: DsnpUserId, public_key: Vec<u8>)
 		-> DsnpGraphResult<()>;
 
-	/// exports added new keys to be submitted to chain
-	fn export_new_key_updates(&self) -> DsnpGraphResult<Vec<Update>>;
+	/// exports added new keys to be submitted to chain, failing with `KeyPageSizeExceeded` if the
+	/// key page of a user would exceed `max_key_page_size_bytes` after adding the new key
+	fn export_new_key_updates(&self, max_key_page_size_bytes: u32) -> DsnpGraphResult<Vec<Update>>;
 
-	/// exports added new keys to be submitted to chain for a specific user
+	/// exports added new keys to be submitted to chain for a specific user, failing with
+	/// `KeyPageSizeExceeded` if the key page would exceed `max_key_page_size_bytes`
 	fn export_new_key_updates_for_user(
 		&self,
 		dsnp_user_id: &DsnpUserId,
+		max_key_page_size_bytes: u32,
 	) -> DsnpGraphResult<Vec<Update>>;
 
 	/// get imported keys
@@ -73,7 +119,21 @@ pub trait PublicKeyProvider {
 	fn find_users_without_keys(&self, dsnp_user_ids: Vec<DsnpUserId>) -> Vec<DsnpUserId>;
 }
 
-#[derive(Debug, Eq, PartialEq)]
+/// Immutable view of the committed public keys and PRIds
+///
+/// A new snapshot is published on each commit of the `SharedStateManager`, which allows verifying
+/// PRIds without waiting on imports that hold the shared state lock. Changes are only visible
+/// after they are committed.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct PublicKeySnapshot {
+	/// keys are stored sorted by index
+	dsnp_user_to_keys: HashMap<DsnpUserId, Arc<Vec<DsnpPublicKey>>>,
+
+	/// prids are stored with key_id
+	dsnp_user_to_pris: HashMap<DsnpUserId, Arc<Vec<(DsnpPrid, u64)>>>,
+}
+
+#[derive(Debug)]
 pub struct SharedStateManager {
 	/// keys are stored sorted by index
 	dsnp_user_to_keys: TransactionalHashMap<DsnpUserId, (Vec<DsnpPublicKey>, PageHash)>,
@@ -83,18 +143,200 @@ pub struct SharedStateManager {
 
 	/// prids are stored with key_id
 	dsnp_user_to_pris: TransactionalHashMap<DsnpUserId, Vec<(DsnpPrid, u64)>>,
+
+	/// number of prids imported from each page in the order they are stored, which allows
+	/// replacing the prids of single pages
+	pri_page_lengths: TransactionalHashMap<DsnpUserId, Vec<(PageId, usize)>>,
+
+	/// block at which the imported keys of each user were read from chain
+	keys_chain_heads: TransactionalHashMap<DsnpUserId, ChainHead>,
+
+	/// chain payloads of imported and added keys by their key bytes, which avoids serializing all
+	/// the keys of a user again on each export
+	serialized_keys: TransactionalHashMap<Vec<u8>, Vec<u8>>,
+
+	/// committed keys and prids which can be read without locking the shared state
+	committed: Arc<SnapshotCell<PublicKeySnapshot>>,
+
+	/// reads and writes the chain specific payloads of the keys
+	chain_adapter: SharedChainAdapter,
+}
+
+impl PartialEq for SharedStateManager {
+	fn eq(&self, other: &Self) -> bool {
+		self.dsnp_user_to_keys == other.dsnp_user_to_keys &&
+			self.new_keys == other.new_keys &&
+			self.dsnp_user_to_pris == other.dsnp_user_to_pris &&
+			self.pri_page_lengths == other.pri_page_lengths &&
+			self.keys_chain_heads == other.keys_chain_heads &&
+			self.committed == other.committed
+	}
+}
+
+impl Eq for SharedStateManager {}
+
+impl PublicKeySnapshot {
+	/// returns the committed keys of a user
+	pub fn get_imported_keys(&self, dsnp_user_id: DsnpUserId) -> &[DsnpPublicKey] {
+		self.dsnp_user_to_keys.get(&dsnp_user_id).map_or(&[], |keys| &keys[..])
+	}
+
+	/// returns a committed key by its id
+	pub fn get_key_by_id(&self, dsnp_user_id: DsnpUserId, key_id: u64) -> Option<&DsnpPublicKey> {
+		// get the first key by that id as specified in the spec
+		self.get_imported_keys(dsnp_user_id).iter().find(|k| k.key_id == Some(key_id))
+	}
+
+	/// checks if a committed prid exist for a specific user
+	pub fn contains(&self, dsnp_user_id: DsnpUserId, prid: DsnpPrid) -> bool {
+		self.dsnp_user_to_pris
+			.get(&dsnp_user_id)
+			.map_or(false, |prids| prids.iter().any(|(p, _)| p == &prid))
+	}
+
+	/// returns all the imported pri keys for a user
+	#[log_result_err(Level::Info)]
+	pub fn get_prid_associated_public_keys(
+		&self,
+		dsnp_user_id: DsnpUserId,
+	) -> DsnpGraphResult<Vec<PublicKeyType>> {
+		// get imported prids for user
+		let prids = self
+			.dsnp_user_to_pris
+			.get(&dsnp_user_id)
+			.ok_or(DsnpGraphError::NoPrisImportedForUser(dsnp_user_id))?;
+
+		// find all unique key_id used in prid calculations
+		let key_ids: HashSet<_> = prids.iter().map(|(_, key_id)| *key_id).collect();
+
+		// map key_id to their associated imported public keys
+		let public_keys: DsnpGraphResult<Vec<_>> = key_ids
+			.iter()
+			.map(|id| {
+				self.get_key_by_id(dsnp_user_id, *id)
+					.ok_or(DsnpGraphError::ImportedKeyNotFound(dsnp_user_id, id.to_string()))
+			})
+			.collect();
+
+		// map DsnpPublicKeys to PublicKeyType
+		public_keys?
+			.iter()
+			.map(|&p| {
+				let mapped: DsnpGraphResult<PublicKeyType> = p.try_into();
+				mapped
+			})
+			.collect()
+	}
+
+	/// verifies that `from` has a connection to `owner` by checking the PRIds imported for `from`
+	/// against the ones calculated using the secret keys of `owner`
+	#[log_result_err(Level::Info)]
+	pub fn verify_connection(
+		&self,
+		owner: DsnpUserId,
+		owner_keys: &[ResolvedKeyPair],
+		from: DsnpUserId,
+	) -> DsnpGraphResult<bool> {
+		Ok(self.find_verifying_key_id(owner, owner_keys, from)?.is_some())
+	}
+
+	/// returns the id of the public key of `from` which verifies its connection to `owner`, or
+	/// `None` if the connection is not verified. All the imported public keys of `from` are tried
+	/// in key id order, so connections stay verified while `from` is rotating its keys
+	#[log_result_err(Level::Info)]
+	pub fn find_verifying_key_id(
+		&self,
+		owner: DsnpUserId,
+		owner_keys: &[ResolvedKeyPair],
+		from: DsnpUserId,
+	) -> DsnpGraphResult<Option<u64>> {
+		let prids = self
+			.dsnp_user_to_pris
+			.get(&from)
+			.ok_or(DsnpGraphError::NoPrisImportedForUser(from))?;
+
+		let mut from_public_keys: Vec<_> = self
+			.get_imported_keys(from)
+			.iter()
+			.filter_map(|key| key.key_id.map(|key_id| (key_id, key)))
+			.collect();
+		if from_public_keys.is_empty() {
+			let key_id = prids.iter().map(|(_, key_id)| *key_id).min().unwrap_or_default();
+			return Err(DsnpGraphError::ImportedKeyNotFound(from, key_id.to_string()))
+		}
+		// stable sort keeps the first key of duplicated ids first as specified in the spec
+		from_public_keys.sort_by_key(|(key_id, _)| *key_id);
+
+		for (key_id, key) in from_public_keys {
+			let public: PublicKeyType = key.try_into()?;
+			for private in owner_keys.iter().rev() {
+				let prid =
+					DsnpPrid::create_prid(from, owner, &private.key_pair.clone().into(), &public)?;
+				if self.contains(from, prid) {
+					return Ok(Some(key_id))
+				}
+			}
+		}
+		Ok(None)
+	}
+
+	/// verifies the connections of multiple users to `owner`, returning `None` for the users that
+	/// can not be verified, such as the ones without imported PRIds or public keys
+	pub fn verify_connections_batch(
+		&self,
+		owner: DsnpUserId,
+		owner_keys: &[ResolvedKeyPair],
+		from: &[DsnpUserId],
+	) -> Vec<Option<bool>> {
+		from.iter()
+			.map(|from| self.verify_connection(owner, owner_keys, *from).ok())
+			.collect()
+	}
+
+	/// returns the ids of the public keys verifying the connections of multiple users to `owner`,
+	/// returning `None` for the users that are not verified or can not be verified
+	pub fn find_verifying_key_ids_batch(
+		&self,
+		owner: DsnpUserId,
+		owner_keys: &[ResolvedKeyPair],
+		from: &[DsnpUserId],
+	) -> Vec<Option<u64>> {
+		from.iter()
+			.map(|from| self.find_verifying_key_id(owner, owner_keys, *from).ok().flatten())
+			.collect()
+	}
 }
 
 impl PriProvider for SharedStateManager {
 	#[log_result_err(Level::Info)]
-	fn import_pri(&mut self, dsnp_user_id: DsnpUserId, pages: &[PageData]) -> DsnpGraphResult<()> {
-		let mut prids = vec![];
-		for p in pages {
-			let chunk = SchemaHandler::read_private_graph_chunk(&p.content[..])?;
-			let mapped: Vec<_> = chunk.prids.iter().map(|p| (p.clone(), chunk.key_id)).collect();
-			prids.extend_from_slice(&mapped);
+	fn import_pri(
+		&mut self,
+		dsnp_user_id: DsnpUserId,
+		pages: &[PageDataRef],
+	) -> DsnpGraphResult<()> {
+		let pri_pages = Self::read_pri_pages(pages)?;
+		self.set_pri_pages(dsnp_user_id, pri_pages);
+		Ok(())
+	}
+
+	#[log_result_err(Level::Info)]
+	fn merge_pri(
+		&mut self,
+		dsnp_user_id: DsnpUserId,
+		pages: &[PageDataRef],
+	) -> DsnpGraphResult<()> {
+		let mut pri_pages: BTreeMap<PageId, Vec<(DsnpPrid, u64)>> = BTreeMap::new();
+		if let (Some(prids), Some(lengths)) =
+			(self.dsnp_user_to_pris.get(&dsnp_user_id), self.pri_page_lengths.get(&dsnp_user_id))
+		{
+			let mut offset = 0;
+			for (page_id, len) in lengths {
+				pri_pages.insert(*page_id, prids[offset..offset + len].to_vec());
+				offset += len;
+			}
 		}
-		self.dsnp_user_to_pris.insert(dsnp_user_id, prids);
+		pri_pages.extend(Self::read_pri_pages(pages)?);
+		self.set_pri_pages(dsnp_user_id, pri_pages.into_iter().collect());
 		Ok(())
 	}
 
@@ -120,6 +362,26 @@ impl PriProvider for SharedStateManager {
 		let prid = DsnpPrid::create_prid(from, to, &from_secret, &to_public_key)?;
 		Ok(prid)
 	}
+
+	#[log_result_err(Level::Info)]
+	fn calculate_prids(
+		&self,
+		from: DsnpUserId,
+		to: DsnpUserId,
+		from_secret: SecretKeyType,
+	) -> DsnpGraphResult<Vec<DsnpPrid>> {
+		let to_public_keys = self.get_imported_keys(to);
+		if to_public_keys.is_empty() {
+			return Err(DsnpGraphError::NoPublicKeyFoundForUser(to))
+		}
+		to_public_keys
+			.into_iter()
+			.map(|key| {
+				let to_public_key: PublicKeyType = key.try_into()?;
+				DsnpPrid::create_prid(from, to, &from_secret, &to_public_key)
+			})
+			.collect()
+	}
 }
 
 impl PublicKeyProvider for SharedStateManager {
@@ -127,6 +389,7 @@ impl PublicKeyProvider for SharedStateManager {
 	/// sorting indices since ids might not be unique but indices definitely should be
 	#[log_result_err(Level::Info)]
 	fn import_dsnp_keys(&mut self, keys: &DsnpKeys) -> DsnpGraphResult<()> {
+		self.remove_serialized_keys(keys.dsnp_user_id);
 		self.dsnp_user_to_keys.remove(&keys.dsnp_user_id);
 		self.new_keys.remove(&keys.dsnp_user_id);
 
@@ -137,12 +400,13 @@ impl PublicKeyProvider for SharedStateManager {
 		let mut dsnp_keys = vec![];
 		for key in sorted_keys {
 			let mut k =
-				Frequency::read_public_key(&key.content).map_err(|e| DsnpGraphError::from(e))?;
+				self.chain_adapter.read_key(&key.content).map_err(|e| DsnpGraphError::from(e))?;
 
 			// make sure it can deserialize correctly
 			let _: PublicKeyType = (&k).try_into()?;
 			// key id is the itemized index of the key stored in Frequency
 			k.key_id = Some(key.index.into());
+			self.cache_serialized_key(&k)?;
 			dsnp_keys.push(k);
 		}
 
@@ -150,6 +414,49 @@ impl PublicKeyProvider for SharedStateManager {
 		Ok(())
 	}
 
+	/// importing public keys that are retrieved without their key page, since the page hash is not
+	/// known a zero hash is stored which would fail any new key export for the user on chain
+	#[log_result_err(Level::Info)]
+	fn import_public_keys(
+		&mut self,
+		dsnp_user_id: DsnpUserId,
+		keys: &[DsnpPublicKey],
+	) -> DsnpGraphResult<()> {
+		let mut sorted_keys = keys.to_vec();
+		for key in &sorted_keys {
+			if key.key_id.is_none() {
+				return Err(DsnpGraphError::InvalidInput(format!(
+					"Public key {:?} of {} does not have a key id",
+					key.key, dsnp_user_id
+				)))
+			}
+			// make sure it can deserialize correctly
+			let _: PublicKeyType = key.try_into()?;
+		}
+		// sorting by key id in ascending mode
+		sorted_keys.sort_by_key(|k| k.key_id);
+		if sorted_keys.windows(2).any(|w| w[0].key_id == w[1].key_id) {
+			return Err(DsnpGraphError::InvalidInput(format!(
+				"Duplicate key ids in public keys of {}",
+				dsnp_user_id
+			)))
+		}
+		// serializing before changing anything, so a failing import leaves no staged changes
+		let payloads = sorted_keys
+			.iter()
+			.map(|key| self.serialized_key(key))
+			.collect::<DsnpGraphResult<Vec<_>>>()?;
+
+		self.remove_serialized_keys(dsnp_user_id);
+		for (key, payload) in sorted_keys.iter().zip(payloads) {
+			self.serialized_keys.insert(key.key.clone(), payload);
+		}
+		self.new_keys.remove(&dsnp_user_id);
+		self.keys_chain_heads.remove(&dsnp_user_id);
+		self.dsnp_user_to_keys.insert(dsnp_user_id, (sorted_keys, 0));
+		Ok(())
+	}
+
 	#[log_result_err(Level::Info)]
 	fn add_new_key(
 		&mut self,
@@ -165,7 +472,7 @@ impl PublicKeyProvider for SharedStateManager {
 			DsnpPublicKey { key: public_key, key_id: Some(self.get_next_key_id(dsnp_user_id)) };
 
 		// making sure it is serializable before adding
-		let _ = Frequency::write_public_key(&new_key).map_err(|e| DsnpGraphError::from(e))?;
+		self.cache_serialized_key(&new_key)?;
 
 		// only one new key is allowed to be added to a dsnp_user_id at a time
 		self.new_keys.insert(dsnp_user_id, new_key.clone());
@@ -174,18 +481,10 @@ impl PublicKeyProvider for SharedStateManager {
 	}
 
 	#[log_result_err(Level::Info)]
-	fn export_new_key_updates(&self) -> DsnpGraphResult<Vec<Update>> {
+	fn export_new_key_updates(&self, max_key_page_size_bytes: u32) -> DsnpGraphResult<Vec<Update>> {
 		let mut result = vec![];
 		for (dsnp_user_id, key) in self.new_keys.inner() {
-			let prev_hash = self
-				.dsnp_user_to_keys
-				.get(&dsnp_user_id)
-				.map_or(PageHash::default(), |(_, hash)| *hash);
-			result.push(Update::AddKey {
-				owner_dsnp_user_id: *dsnp_user_id,
-				prev_hash,
-				payload: Frequency::write_public_key(key)?,
-			});
+			result.push(self.create_add_key_update(*dsnp_user_id, key, max_key_page_size_bytes)?);
 		}
 		Ok(result)
 	}
@@ -194,6 +493,7 @@ impl PublicKeyProvider for SharedStateManager {
 	fn export_new_key_updates_for_user(
 		&self,
 		dsnp_user_id: &DsnpUserId,
+		max_key_page_size_bytes: u32,
 	) -> DsnpGraphResult<Vec<Update>> {
 		let mut result = vec![];
 		for (dsnp_user_id, key) in self
@@ -202,15 +502,7 @@ impl PublicKeyProvider for SharedStateManager {
 			.iter()
 			.filter(|(key_dsnp_id, _)| *key_dsnp_id == dsnp_user_id)
 		{
-			let prev_hash = self
-				.dsnp_user_to_keys
-				.get(&dsnp_user_id)
-				.map_or(PageHash::default(), |(_, hash)| *hash);
-			result.push(Update::AddKey {
-				owner_dsnp_user_id: *dsnp_user_id,
-				prev_hash,
-				payload: Frequency::write_public_key(key)?,
-			});
+			result.push(self.create_add_key_update(*dsnp_user_id, key, max_key_page_size_bytes)?);
 		}
 		Ok(result)
 	}
@@ -268,60 +560,150 @@ impl PublicKeyProvider for SharedStateManager {
 
 impl Transactional for SharedStateManager {
 	fn commit(&mut self) {
+		self.publish_snapshot();
 		self.dsnp_user_to_keys.commit();
 		self.new_keys.commit();
 		self.dsnp_user_to_pris.commit();
+		self.pri_page_lengths.commit();
+		self.keys_chain_heads.commit();
+		self.serialized_keys.commit();
 	}
 
 	fn rollback(&mut self) {
 		self.dsnp_user_to_keys.rollback();
 		self.new_keys.rollback();
 		self.dsnp_user_to_pris.rollback();
+		self.pri_page_lengths.rollback();
+		self.keys_chain_heads.rollback();
+		self.serialized_keys.rollback();
+	}
+}
+
+/// clones are independent from each other, including their published snapshots
+impl Clone for SharedStateManager {
+	fn clone(&self) -> Self {
+		Self {
+			dsnp_user_to_keys: self.dsnp_user_to_keys.clone(),
+			new_keys: self.new_keys.clone(),
+			dsnp_user_to_pris: self.dsnp_user_to_pris.clone(),
+			pri_page_lengths: self.pri_page_lengths.clone(),
+			keys_chain_heads: self.keys_chain_heads.clone(),
+			serialized_keys: self.serialized_keys.clone(),
+			committed: Arc::new(SnapshotCell::new(self.committed.load())),
+			chain_adapter: self.chain_adapter.clone(),
+		}
 	}
 }
 
 impl SharedStateManager {
+	/// reads the prids of each private friendship page along with the id of their key
+	fn read_pri_pages(
+		pages: &[PageDataRef],
+	) -> DsnpGraphResult<Vec<(PageId, Vec<(DsnpPrid, u64)>)>> {
+		pages
+			.iter()
+			.map(|p| {
+				let chunk = SchemaHandler::read_private_graph_chunk(p.content)?;
+				let prids = chunk.prids.iter().map(|prid| (prid.clone(), chunk.key_id)).collect();
+				Ok((p.page_id, prids))
+			})
+			.collect()
+	}
+
+	/// replaces the prids of a user with the prids of the pages in the provided order
+	fn set_pri_pages(
+		&mut self,
+		dsnp_user_id: DsnpUserId,
+		pri_pages: Vec<(PageId, Vec<(DsnpPrid, u64)>)>,
+	) {
+		let lengths = pri_pages.iter().map(|(page_id, prids)| (*page_id, prids.len())).collect();
+		let prids = pri_pages.into_iter().flat_map(|(_, prids)| prids).collect();
+		self.dsnp_user_to_pris.insert(dsnp_user_id, prids);
+		self.pri_page_lengths.insert(dsnp_user_id, lengths);
+	}
+
 	/// creates a new instance of `SharedStateManager`
 	pub fn new() -> Self {
+		Self::with_chain_adapter(default_chain_adapter())
+	}
+
+	/// creates a new instance of `SharedStateManager` reading and writing the keys using the
+	/// provided chain adapter
+	pub fn with_chain_adapter(chain_adapter: SharedChainAdapter) -> Self {
 		Self {
 			new_keys: TransactionalHashMap::new(),
 			dsnp_user_to_keys: TransactionalHashMap::new(),
 			dsnp_user_to_pris: TransactionalHashMap::new(),
+			pri_page_lengths: TransactionalHashMap::new(),
+			keys_chain_heads: TransactionalHashMap::new(),
+			serialized_keys: TransactionalHashMap::new(),
+			committed: Arc::new(SnapshotCell::new(Arc::new(PublicKeySnapshot::default()))),
+			chain_adapter,
 		}
 	}
 
-	/// returns all the imported pri keys for a user
-	#[log_result_err(Level::Info)]
-	pub fn get_prid_associated_public_keys(
-		&self,
-		dsnp_user_id: DsnpUserId,
-	) -> DsnpGraphResult<Vec<PublicKeyType>> {
-		// get imported prids for user
-		let prids = self
-			.dsnp_user_to_pris
-			.get(&dsnp_user_id)
-			.ok_or(DsnpGraphError::NoPrisImportedForUser(dsnp_user_id))?;
-
-		// find all unique key_id used in prid calculations
-		let key_ids: HashSet<_> = prids.iter().map(|(_, key_id)| *key_id).collect();
+	/// returns the cell where committed keys and prids are published, which can be kept and read
+	/// without locking this manager
+	pub fn committed_snapshot(&self) -> Arc<SnapshotCell<PublicKeySnapshot>> {
+		self.committed.clone()
+	}
 
-		// map key_id to their associated imported public keys
-		let public_keys: DsnpGraphResult<Vec<_>> = key_ids
-			.iter()
-			.map(|id| {
-				self.get_key_by_id(dsnp_user_id, *id)
-					.ok_or(DsnpGraphError::ImportedKeyNotFound(dsnp_user_id, id.to_string()))
-			})
+	/// commits the imported keys of a single user without committing the staged changes of other
+	/// users, which are left to the transaction that staged them
+	pub fn commit_user_keys(&mut self, dsnp_user_id: DsnpUserId) {
+		self.publish_users(&[dsnp_user_id], &[]);
+		let key_bytes: Vec<_> = self
+			.get_imported_keys(dsnp_user_id)
+			.into_iter()
+			.map(|key| key.key.clone())
 			.collect();
+		for key in key_bytes {
+			self.serialized_keys.commit_key(&key);
+		}
+		self.dsnp_user_to_keys.commit_key(&dsnp_user_id);
+		self.new_keys.commit_key(&dsnp_user_id);
+		self.keys_chain_heads.commit_key(&dsnp_user_id);
+	}
 
-		// map DsnpPublicKeys to PublicKeyType
-		public_keys?
-			.iter()
-			.map(|&p| {
-				let mapped: DsnpGraphResult<PublicKeyType> = p.try_into();
-				mapped
-			})
-			.collect()
+	/// publishes a new snapshot containing the keys and prids of the users changed since the last
+	/// commit, reusing the unchanged ones from the current snapshot
+	fn publish_snapshot(&self) {
+		let changed_keys: Vec<_> = self.dsnp_user_to_keys.changed_keys().copied().collect();
+		let changed_pris: Vec<_> = self.dsnp_user_to_pris.changed_keys().copied().collect();
+		self.publish_users(&changed_keys, &changed_pris);
+	}
+
+	/// publishes a new snapshot with the current keys and prids of the provided users
+	fn publish_users(&self, changed_keys: &[DsnpUserId], changed_pris: &[DsnpUserId]) {
+		if changed_keys.is_empty() && changed_pris.is_empty() {
+			return
+		}
+
+		let mut snapshot = (*self.committed.load()).clone();
+		for dsnp_user_id in changed_keys.iter().copied() {
+			match self.dsnp_user_to_keys.get(&dsnp_user_id) {
+				Some((keys, _)) =>
+					snapshot.dsnp_user_to_keys.insert(dsnp_user_id, Arc::new(keys.clone())),
+				None => snapshot.dsnp_user_to_keys.remove(&dsnp_user_id),
+			};
+		}
+		for dsnp_user_id in changed_pris.iter().copied() {
+			match self.dsnp_user_to_pris.get(&dsnp_user_id) {
+				Some(prids) =>
+					snapshot.dsnp_user_to_pris.insert(dsnp_user_id, Arc::new(prids.clone())),
+				None => snapshot.dsnp_user_to_pris.remove(&dsnp_user_id),
+			};
+		}
+		self.committed.store(Arc::new(snapshot));
+	}
+
+	/// sets the block at which the imported keys of a user were read from chain, which gets
+	/// attached to the exported AddKey updates of the user
+	pub fn set_keys_chain_head(&mut self, dsnp_user_id: DsnpUserId, chain_head: Option<ChainHead>) {
+		match chain_head {
+			Some(chain_head) => self.keys_chain_heads.insert(dsnp_user_id, chain_head),
+			None => self.keys_chain_heads.remove(&dsnp_user_id),
+		};
 	}
 
 	/// returns all the public keys for a user
@@ -332,14 +714,281 @@ impl SharedStateManager {
 		}
 	}
 
-	/// get the next key id for a user
-	fn get_next_key_id(&self, dsnp_user_id: DsnpUserId) -> u64 {
+	/// returns the number of keys and the used bytes of the key page for a user, including any
+	/// newly added key which is not published yet
+	pub fn get_key_page_usage(&self, dsnp_user_id: &DsnpUserId) -> DsnpGraphResult<(u32, u32)> {
+		let mut key_count = 0;
+		let mut used_bytes = 0;
+		for key in self
+			.get_imported_keys(*dsnp_user_id)
+			.into_iter()
+			.chain(self.new_keys.get(dsnp_user_id))
+		{
+			key_count += 1;
+			used_bytes += self.serialized_key_len(key)? + ITEMIZED_ITEM_HEADER_BYTES;
+		}
+		Ok((key_count, used_bytes as u32))
+	}
+
+	/// returns the itemized deletions which remove the duplicated keys of a user, keeping the last
+	/// occurrence of each key so the active key stays the last one. Returns `None` if the key page
+	/// has no duplicates
+	pub fn get_key_page_compaction(
+		&self,
+		dsnp_user_id: &DsnpUserId,
+	) -> DsnpGraphResult<Option<KeyPageCompaction>> {
+		if self.new_keys.get(dsnp_user_id).is_some() {
+			// both would be applied on top of the same key page hash
+			return Err(DsnpGraphError::InvalidInput(format!(
+				"User {} has a new key which should be exported before compacting the key page",
+				dsnp_user_id
+			)))
+		}
+		let (keys, prev_hash) = match self.dsnp_user_to_keys.get(dsnp_user_id) {
+			Some(imported) => imported,
+			None => return Ok(None),
+		};
+
+		let mut seen = HashSet::new();
+		let mut delete_indices = vec![];
+		let mut remaining_key_ids = vec![];
+		for key in keys.iter().rev() {
+			let key_id = key.key_id.ok_or_else(|| {
+				DsnpGraphError::InvalidInput(format!("Key of {} has no key id", dsnp_user_id))
+			})?;
+			match seen.insert(&key.key) {
+				true => remaining_key_ids.push(key_id),
+				false => delete_indices.push(u16::try_from(key_id).map_err(|_| {
+					DsnpGraphError::InvalidInput(format!(
+						"Key id {} of {} is not an itemized index",
+						key_id, dsnp_user_id
+					))
+				})?),
+			}
+		}
+		if delete_indices.is_empty() {
+			return Ok(None)
+		}
+
+		remaining_key_ids.reverse();
+		Ok(Some(KeyPageCompaction {
+			owner_dsnp_user_id: *dsnp_user_id,
+			prev_hash: *prev_hash,
+			delete_indices,
+			remaining_key_ids,
+			chain_head: self.keys_chain_heads.get(dsnp_user_id).cloned(),
+		}))
+	}
+
+	/// estimated number of bytes used by imported public keys and PRIds in memory
+	pub fn memory_footprint(&self) -> usize {
+		let keys: usize = self
+			.dsnp_user_to_keys
+			.inner()
+			.values()
+			.map(|(keys, _)| {
+				std::mem::size_of::<(DsnpUserId, (Vec<DsnpPublicKey>, PageHash))>() +
+					keys.iter()
+						.map(|key| std::mem::size_of::<DsnpPublicKey>() + key.key.len())
+						.sum::<usize>()
+			})
+			.sum();
+		let prids: usize = self
+			.dsnp_user_to_pris
+			.inner()
+			.values()
+			.map(|prids| {
+				std::mem::size_of::<(DsnpUserId, Vec<(DsnpPrid, u64)>)>() +
+					prids
+						.iter()
+						.map(|(prid, _)| prid.memory_footprint() + std::mem::size_of::<u64>())
+						.sum::<usize>()
+			})
+			.sum();
+		let serialized_keys: usize = self
+			.serialized_keys
+			.inner()
+			.iter()
+			.map(|(key, payload)| {
+				std::mem::size_of::<(Vec<u8>, Vec<u8>)>() + key.len() + payload.len()
+			})
+			.sum();
+		std::mem::size_of::<Self>() + keys + prids + serialized_keys
+	}
+
+	/// feeds the published keys, newly added keys and imported PRIds into the hasher, each
+	/// ordered by user id
+	pub fn hash_into(&self, hasher: &mut StateHasher) {
+		let hash_key = |hasher: &mut StateHasher, key: &DsnpPublicKey| {
+			hasher.write_optional_u64(key.key_id);
+			hasher.write_bytes(&key.key);
+		};
+
+		let keys: BTreeMap<_, _> = self.dsnp_user_to_keys.inner().iter().collect();
+		hasher.write_u64(keys.len() as u64);
+		for (dsnp_user_id, (keys, keys_hash)) in keys {
+			hasher.write_u64(*dsnp_user_id);
+			hasher.write_u64(*keys_hash as u64);
+			hasher.write_u64(keys.len() as u64);
+			for key in keys {
+				hash_key(hasher, key);
+			}
+		}
+
+		let new_keys: BTreeMap<_, _> = self.new_keys.inner().iter().collect();
+		hasher.write_u64(new_keys.len() as u64);
+		for (dsnp_user_id, key) in new_keys {
+			hasher.write_u64(*dsnp_user_id);
+			hash_key(hasher, key);
+		}
+
+		let prids: BTreeMap<_, _> = self.dsnp_user_to_pris.inner().iter().collect();
+		hasher.write_u64(prids.len() as u64);
+		for (dsnp_user_id, prids) in prids {
+			hasher.write_u64(*dsnp_user_id);
+			hasher.write_u64(prids.len() as u64);
+			for (prid, key_id) in prids {
+				hasher.write_bytes(prid.as_bytes());
+				hasher.write_u64(*key_id);
+			}
+		}
+	}
+
+	/// estimated number of bytes used by newly added keys which are not published yet
+	pub fn new_keys_memory_footprint(&self) -> usize {
+		self.new_keys
+			.inner()
+			.values()
+			.map(|key| std::mem::size_of::<(DsnpUserId, DsnpPublicKey)>() + key.key.len())
+			.sum()
+	}
+
+	/// returns the key id that the next published key for a user will be assigned on chain
+	///
+	/// key ids are the itemized indices of the keys, so the next key is appended right after the
+	/// highest imported index, or at index 0 if the user has no imported keys
+	pub fn get_next_key_id(&self, dsnp_user_id: DsnpUserId) -> u64 {
 		self.get_imported_keys(dsnp_user_id)
 			.iter()
 			.filter_map(|key| key.key_id)
 			.max()
-			.unwrap_or(u64::default()) +
-			1
+			.map_or(0, |max_key_id| max_key_id + 1)
+	}
+
+	/// serializes a key as its chain payload and caches it until the key is replaced
+	fn cache_serialized_key(&mut self, key: &DsnpPublicKey) -> DsnpGraphResult<()> {
+		if self.serialized_keys.get(&key.key).is_none() {
+			let payload = self.chain_adapter.write_key(key).map_err(|e| DsnpGraphError::from(e))?;
+			self.serialized_keys.insert(key.key.clone(), payload);
+		}
+		Ok(())
+	}
+
+	/// removes the cached payloads of the imported and added keys of a user which are replaced
+	fn remove_serialized_keys(&mut self, dsnp_user_id: DsnpUserId) {
+		let keys: Vec<_> = self
+			.get_imported_keys(dsnp_user_id)
+			.into_iter()
+			.chain(self.new_keys.get(&dsnp_user_id))
+			.map(|key| key.key.clone())
+			.collect();
+		for key in keys {
+			self.serialized_keys.remove(&key);
+		}
+	}
+
+	/// returns the chain payload of a key, serializing it only if it is not cached
+	fn serialized_key(&self, key: &DsnpPublicKey) -> DsnpGraphResult<Vec<u8>> {
+		match self.serialized_keys.get(&key.key) {
+			Some(payload) => Ok(payload.clone()),
+			None => self.chain_adapter.write_key(key),
+		}
+	}
+
+	/// returns the size of the chain payload of a key, serializing it only if it is not cached
+	fn serialized_key_len(&self, key: &DsnpPublicKey) -> DsnpGraphResult<usize> {
+		match self.serialized_keys.get(&key.key) {
+			Some(payload) => Ok(payload.len()),
+			None => Ok(self.chain_adapter.write_key(key)?.len()),
+		}
+	}
+
+	/// creates the AddKey update for a newly added key, assigning it the next key id
+	///
+	/// the chain rejects AddKey updates that make the key page larger than the maximum allowed
+	/// size, so those fail here instead of producing a payload that can not be submitted
+	fn create_add_key_update(
+		&self,
+		dsnp_user_id: DsnpUserId,
+		key: &DsnpPublicKey,
+		max_key_page_size_bytes: u32,
+	) -> DsnpGraphResult<Update> {
+		let (_, used_bytes) = self.get_key_page_usage(&dsnp_user_id)?;
+		if used_bytes > max_key_page_size_bytes {
+			return Err(DsnpGraphError::KeyPageSizeExceeded(
+				dsnp_user_id,
+				used_bytes,
+				max_key_page_size_bytes,
+			))
+		}
+		let prev_hash = self
+			.dsnp_user_to_keys
+			.get(&dsnp_user_id)
+			.map_or(PageHash::default(), |(_, hash)| *hash);
+		let key = DsnpPublicKey {
+			key: key.key.clone(),
+			key_id: Some(self.get_next_key_id(dsnp_user_id)),
+		};
+		Ok(Update::AddKey {
+			owner_dsnp_user_id: dsnp_user_id,
+			prev_hash,
+			payload: self.serialized_key(&key)?,
+			chain_head: self.keys_chain_heads.get(&dsnp_user_id).cloned(),
+		})
+	}
+
+	/// returns the AddKey update which would be exported if the key was added, without staging the
+	/// key. The previewed key replaces any staged new key of the user, the same way adding it would
+	pub fn preview_add_key_update(
+		&self,
+		dsnp_user_id: DsnpUserId,
+		public_key: Vec<u8>,
+		max_key_page_size_bytes: u32,
+	) -> DsnpGraphResult<PreviewedUpdate> {
+		if self.get_key_by_public_key(dsnp_user_id, public_key.clone()).is_some() {
+			return Err(DsnpGraphError::PublicKeyAlreadyExists(format!("{:?}", public_key)))
+		}
+		let key_id = self.get_next_key_id(dsnp_user_id);
+		let payload =
+			self.serialized_key(&DsnpPublicKey { key: public_key, key_id: Some(key_id) })?;
+		let item_bytes = (payload.len() + ITEMIZED_ITEM_HEADER_BYTES) as u32;
+		let mut used_bytes = item_bytes;
+		for key in self.get_imported_keys(dsnp_user_id) {
+			used_bytes += (self.serialized_key_len(key)? + ITEMIZED_ITEM_HEADER_BYTES) as u32;
+		}
+		if used_bytes > max_key_page_size_bytes {
+			return Err(DsnpGraphError::KeyPageSizeExceeded(
+				dsnp_user_id,
+				used_bytes,
+				max_key_page_size_bytes,
+			));
+		}
+		let prev_hash = self
+			.dsnp_user_to_keys
+			.get(&dsnp_user_id)
+			.map_or(PageHash::default(), |(_, hash)| *hash);
+		Ok(PreviewedUpdate {
+			update: Update::AddKey {
+				owner_dsnp_user_id: dsnp_user_id,
+				prev_hash,
+				payload,
+				chain_head: self.keys_chain_heads.get(&dsnp_user_id).cloned(),
+			},
+			key_id,
+			item_bytes,
+			used_bytes,
+			max_bytes: max_key_page_size_bytes,
+		})
 	}
 
 	#[cfg(test)]
@@ -368,6 +1017,7 @@ impl SharedStateManager {
 		key_id: u64,
 	) -> DsnpGraphResult<()> {
 		self.dsnp_user_to_pris.remove(&dsnp_user_id);
+		self.pri_page_lengths.remove(&dsnp_user_id);
 
 		let mapped: Vec<_> = prids.iter().map(|p| (p.clone(), key_id)).collect();
 		self.dsnp_user_to_pris.insert(dsnp_user_id, mapped);
@@ -380,7 +1030,8 @@ mod tests {
 	use super::*;
 	use crate::{
 		api::api_types::{KeyData, ResolvedKeyPair},
-		dsnp::dsnp_configs::KeyPairType,
+		dsnp::{dsnp_configs::KeyPairType, reader_writer::DsnpWriter},
+		frequency::Frequency,
 		util::builders::PageDataBuilder,
 	};
 	use dryoc::keypair::StackKeyPair;
@@ -414,7 +1065,8 @@ mod tests {
 		let non_existing_user_id = 10;
 
 		// act
-		let res = manager.import_pri(dsnp_user_id, &pages);
+		let res = manager
+			.import_pri(dsnp_user_id, &pages.iter().map(PageDataRef::from).collect::<Vec<_>>());
 
 		// assert
 		assert!(res.is_ok());
@@ -428,6 +1080,83 @@ mod tests {
 		assert!(!manager.contains(dsnp_user_id, non_existing_prid));
 	}
 
+	#[test]
+	fn pri_provider_merge_should_only_replace_prids_of_provided_pages() {
+		// arrange
+		let mut manager = SharedStateManager::new();
+		let prids: Vec<_> = (1u8..=4).map(|i| DsnpPrid::new(&[i, 2, 3, 4, 5, 6, 7, 8])).collect();
+		let key_id = 2;
+		let key =
+			ResolvedKeyPair { key_pair: KeyPairType::Version1_0(StackKeyPair::gen()), key_id };
+		let pages = PageDataBuilder::new(Friendship(PrivacyType::Private))
+			.with_page(1, &vec![(1, 0)], &vec![prids[0].clone()], 0)
+			.with_page(2, &vec![(2, 0)], &vec![prids[1].clone()], 0)
+			.with_encryption_key(key.clone())
+			.build();
+		let changed_pages = PageDataBuilder::new(Friendship(PrivacyType::Private))
+			.with_page(2, &vec![(3, 0)], &vec![prids[2].clone()], 0)
+			.with_page(3, &vec![(4, 0)], &vec![prids[3].clone()], 0)
+			.with_encryption_key(key)
+			.build();
+		let dsnp_user_id = 23;
+		manager
+			.import_pri(dsnp_user_id, &pages.iter().map(PageDataRef::from).collect::<Vec<_>>())
+			.expect("should import");
+
+		// act
+		let res = manager.merge_pri(
+			dsnp_user_id,
+			&changed_pages.iter().map(PageDataRef::from).collect::<Vec<_>>(),
+		);
+
+		// assert
+		assert!(res.is_ok());
+		assert_eq!(
+			manager.dsnp_user_to_pris.get(&dsnp_user_id),
+			Some(&vec![
+				(prids[0].clone(), key_id),
+				(prids[2].clone(), key_id),
+				(prids[3].clone(), key_id)
+			])
+		);
+		assert!(!manager.contains(dsnp_user_id, prids[1].clone()));
+	}
+
+	#[test]
+	fn shared_state_manager_should_publish_snapshot_only_on_commit() {
+		// arrange
+		let mut manager = SharedStateManager::new();
+		let committed = manager.committed_snapshot();
+		let dsnp_user_id = 23;
+		let key = DsnpPublicKey { key: StackKeyPair::gen().public_key.to_vec(), key_id: Some(1) };
+		let prid = DsnpPrid::new(&[1u8, 2, 3, 4, 5, 6, 7, 8]);
+		manager
+			.import_keys_test(dsnp_user_id, &[key.clone()], 0)
+			.expect("should import");
+		manager
+			.import_prids_test(dsnp_user_id, &[prid.clone()], 1)
+			.expect("should import");
+		let before_commit = committed.load();
+
+		// act
+		manager.commit();
+		let after_commit = committed.load();
+		manager.import_keys_test(dsnp_user_id, &[], 0).expect("should import");
+		manager.rollback();
+		let cloned = manager.clone();
+		manager.import_keys_test(dsnp_user_id, &[], 0).expect("should import");
+		manager.commit();
+
+		// assert
+		assert_eq!(before_commit.get_imported_keys(dsnp_user_id), &[]);
+		assert!(!before_commit.contains(dsnp_user_id, prid.clone()));
+		assert_eq!(after_commit.get_imported_keys(dsnp_user_id), &[key.clone()]);
+		assert_eq!(after_commit.get_key_by_id(dsnp_user_id, 1), Some(&key));
+		assert!(after_commit.contains(dsnp_user_id, prid));
+		assert_eq!(committed.load().get_imported_keys(dsnp_user_id), &[]);
+		assert_eq!(cloned.committed_snapshot().load(), after_commit);
+	}
+
 	#[test]
 	fn pri_provider_import_should_replace_previous_prids() {
 		// arrange
@@ -442,7 +1171,9 @@ mod tests {
 			})
 			.build();
 		let dsnp_user_id = 23;
-		manager.import_pri(dsnp_user_id, &pages).expect("should work");
+		manager
+			.import_pri(dsnp_user_id, &pages.iter().map(PageDataRef::from).collect::<Vec<_>>())
+			.expect("should work");
 		let new_prid = DsnpPrid::new(&[10u8, 20, 30, 40, 50, 60, 70, 80]);
 		let new_pages = PageDataBuilder::new(Friendship(PrivacyType::Private))
 			.with_page(1, &vec![(1, 0)], &vec![new_prid.clone()], 0)
@@ -453,7 +1184,8 @@ mod tests {
 			.build();
 
 		// act
-		let res = manager.import_pri(dsnp_user_id, &new_pages);
+		let res = manager
+			.import_pri(dsnp_user_id, &new_pages.iter().map(PageDataRef::from).collect::<Vec<_>>());
 
 		// assert
 		assert!(res.is_ok());
@@ -516,6 +1248,46 @@ mod tests {
 		assert_eq!(key_manager.get_active_key(dsnp_user_id), Some(&key1));
 	}
 
+	#[test]
+	fn shared_state_manager_should_import_public_keys_without_key_page() {
+		// arrange
+		let dsnp_user_id = 23;
+		let key1 = DsnpPublicKey { key_id: Some(2), key: vec![1u8; 32] };
+		let key2 = DsnpPublicKey { key_id: Some(1), key: vec![2u8; 32] };
+		let mut key_manager = SharedStateManager::new();
+		key_manager
+			.add_new_key(dsnp_user_id, vec![3u8; 32])
+			.expect("should add new key");
+
+		// act
+		let res = key_manager.import_public_keys(dsnp_user_id, &[key1.clone(), key2.clone()]);
+
+		// assert
+		assert!(res.is_ok());
+		assert_eq!(key_manager.get_imported_keys(dsnp_user_id), vec![&key2, &key1]);
+		assert_eq!(key_manager.get_active_key(dsnp_user_id), Some(&key1));
+		assert_eq!(key_manager.new_keys.get(&dsnp_user_id), None);
+	}
+
+	#[test]
+	fn shared_state_manager_import_public_keys_with_missing_or_duplicate_ids_should_fail() {
+		// arrange
+		let dsnp_user_id = 23;
+		let key1 = DsnpPublicKey { key_id: Some(1), key: vec![1u8; 32] };
+		let key2 = DsnpPublicKey { key_id: Some(1), key: vec![2u8; 32] };
+		let key3 = DsnpPublicKey { key_id: None, key: vec![3u8; 32] };
+		let mut key_manager = SharedStateManager::new();
+
+		// act
+		let duplicate = key_manager.import_public_keys(dsnp_user_id, &[key1.clone(), key2]);
+		let missing = key_manager.import_public_keys(dsnp_user_id, &[key1, key3]);
+
+		// assert
+		assert!(matches!(duplicate, Err(DsnpGraphError::InvalidInput(_))));
+		assert!(matches!(missing, Err(DsnpGraphError::InvalidInput(_))));
+		assert!(key_manager.get_imported_keys(dsnp_user_id).is_empty());
+	}
+
 	#[test]
 	fn shared_state_manager_add_new_key_should_store_a_key_with_increased_id_and_export_as_update()
 	{
@@ -547,9 +1319,9 @@ mod tests {
 		assert!(res.is_ok());
 		let active_key = key_manager.get_active_key(dsnp_user_id);
 		assert_eq!(active_key, Some(&DsnpPublicKey { key_id: Some(2), key: key2.key }));
-		let export = key_manager.export_new_key_updates().expect("should work");
+		let export = key_manager.export_new_key_updates(u32::MAX).expect("should work");
 		let export_user = key_manager
-			.export_new_key_updates_for_user(&dsnp_user_id)
+			.export_new_key_updates_for_user(&dsnp_user_id, u32::MAX)
 			.expect("key export for specific user should work");
 		assert_eq!(
 			export,
@@ -557,6 +1329,7 @@ mod tests {
 				payload: Frequency::write_public_key(&expected_added_key).expect("should write"),
 				owner_dsnp_user_id: dsnp_user_id,
 				prev_hash: keys_hash,
+				chain_head: None,
 			}]
 		);
 		assert_eq!(
@@ -566,11 +1339,160 @@ mod tests {
 		assert_eq!(key_manager.get_imported_keys(dsnp_user_id).len(), 2);
 
 		let export_other_user = key_manager
-			.export_new_key_updates_for_user(&dsnp_user_id_2)
+			.export_new_key_updates_for_user(&dsnp_user_id_2, u32::MAX)
 			.expect("key export for another user should work");
 		assert_eq!(export_other_user.len(), 0, "should have exported 0 keys for other user");
 	}
 
+	#[test]
+	fn shared_state_manager_get_key_page_usage_should_include_imported_and_new_keys() {
+		// arrange
+		let mut key_manager = SharedStateManager::new();
+		let dsnp_user_id = 23;
+		let key1 = DsnpPublicKey { key_id: Some(0), key: vec![1u8; 32] };
+		let serialized1 = Frequency::write_public_key(&key1).expect("should serialize");
+		let item_size = (serialized1.len() + ITEMIZED_ITEM_HEADER_BYTES) as u32;
+		let keys =
+			create_dsnp_keys(dsnp_user_id, 10, vec![KeyData { index: 0, content: serialized1 }]);
+		key_manager.import_dsnp_keys(&keys).expect("should import");
+
+		// act
+		let imported_usage = key_manager.get_key_page_usage(&dsnp_user_id);
+		key_manager.add_new_key(dsnp_user_id, vec![2u8; 32]).expect("should add");
+		let staged_usage = key_manager.get_key_page_usage(&dsnp_user_id);
+
+		// assert
+		assert_eq!(imported_usage.unwrap(), (1, item_size));
+		assert_eq!(staged_usage.unwrap(), (2, 2 * item_size));
+		assert_eq!(key_manager.get_key_page_usage(&1).unwrap(), (0, 0));
+	}
+
+	#[test]
+	fn shared_state_manager_export_new_keys_exceeding_key_page_size_should_fail() {
+		// arrange
+		let mut key_manager = SharedStateManager::new();
+		let dsnp_user_id = 23;
+		let key1 = DsnpPublicKey { key_id: Some(0), key: vec![1u8; 32] };
+		let serialized1 = Frequency::write_public_key(&key1).expect("should serialize");
+		let item_size = (serialized1.len() + ITEMIZED_ITEM_HEADER_BYTES) as u32;
+		let keys =
+			create_dsnp_keys(dsnp_user_id, 10, vec![KeyData { index: 0, content: serialized1 }]);
+		key_manager.import_dsnp_keys(&keys).expect("should import");
+		key_manager.add_new_key(dsnp_user_id, vec![2u8; 32]).expect("should add");
+
+		// act
+		let exceeding = key_manager.export_new_key_updates(2 * item_size - 1);
+		let exceeding_user =
+			key_manager.export_new_key_updates_for_user(&dsnp_user_id, 2 * item_size - 1);
+		let fitting = key_manager.export_new_key_updates(2 * item_size);
+
+		// assert
+		assert!(matches!(
+			exceeding,
+			Err(DsnpGraphError::KeyPageSizeExceeded(id, required, max))
+				if id == dsnp_user_id && required == 2 * item_size && max == 2 * item_size - 1
+		));
+		assert!(matches!(exceeding_user, Err(DsnpGraphError::KeyPageSizeExceeded(..))));
+		assert_eq!(fitting.expect("should export").len(), 1);
+	}
+
+	#[test]
+	fn shared_state_manager_get_next_key_id_should_follow_highest_imported_index() {
+		// arrange
+		let mut key_manager = SharedStateManager::new();
+		let dsnp_user_id = 23;
+		let key1 = DsnpPublicKey { key_id: None, key: vec![1u8; 32] };
+		let serialized1 = Frequency::write_public_key(&key1).expect("should serialize");
+		let keys =
+			create_dsnp_keys(dsnp_user_id, 10, vec![KeyData { index: 3, content: serialized1 }]);
+
+		// act
+		let without_keys = key_manager.get_next_key_id(dsnp_user_id);
+		key_manager.import_dsnp_keys(&keys).expect("should import");
+		key_manager.add_new_key(dsnp_user_id, vec![2u8; 32]).expect("should add");
+
+		// assert
+		assert_eq!(without_keys, 0);
+		assert_eq!(key_manager.get_next_key_id(dsnp_user_id), 4);
+		assert_eq!(key_manager.new_keys.get(&dsnp_user_id).unwrap().key_id, Some(4));
+	}
+
+	#[test]
+	fn shared_state_manager_should_verify_connection_using_historical_key_of_counterpart() {
+		// arrange
+		let (owner, from) = (2, 3);
+		let owner_key_pair = KeyPairType::Version1_0(StackKeyPair::gen());
+		let old_from_key_pair = KeyPairType::Version1_0(StackKeyPair::gen());
+		let new_from_key_pair = KeyPairType::Version1_0(StackKeyPair::gen());
+		let owner_keys = vec![ResolvedKeyPair { key_id: 0, key_pair: owner_key_pair.clone() }];
+		let prid = DsnpPrid::create_prid(
+			from,
+			owner,
+			&old_from_key_pair.clone().into(),
+			&(&owner_key_pair).into(),
+		)
+		.expect("should create prid");
+		let mut manager = SharedStateManager::new();
+		manager
+			.import_keys_test(
+				from,
+				&[
+					DsnpPublicKey { key: new_from_key_pair.get_public_key_raw(), key_id: Some(1) },
+					DsnpPublicKey { key: old_from_key_pair.get_public_key_raw(), key_id: Some(0) },
+				],
+				0,
+			)
+			.expect("should import");
+		// prids are stored under the latest key id while they are calculated using the older one
+		manager.import_prids_test(from, &[prid], 1).expect("should import");
+		manager.commit();
+		let snapshot = manager.committed_snapshot().load();
+
+		// act
+		let key_id = snapshot.find_verifying_key_id(owner, &owner_keys, from);
+		let verified = snapshot.verify_connection(owner, &owner_keys, from);
+		let unrelated = snapshot.find_verifying_key_ids_batch(owner + 10, &owner_keys, &[from]);
+
+		// assert
+		assert_eq!(key_id.expect("should verify"), Some(0));
+		assert!(verified.expect("should verify"));
+		assert_eq!(unrelated, vec![None]);
+	}
+
+	#[test]
+	fn shared_state_manager_should_cache_serialized_keys_transactionally() {
+		// arrange
+		let dsnp_user_id = 2;
+		let key1 = DsnpPublicKey { key_id: None, key: vec![1u8; 32] };
+		let serialized1 = Frequency::write_public_key(&key1).expect("should serialize");
+		let keys =
+			create_dsnp_keys(dsnp_user_id, 233, vec![KeyData { index: 1, content: serialized1 }]);
+		let new_key = DsnpPublicKey { key_id: None, key: vec![2u8; 32] };
+		let mut key_manager = SharedStateManager::new();
+		key_manager.import_dsnp_keys(&keys).expect("should work");
+		key_manager.commit();
+
+		// act
+		key_manager.add_new_key(dsnp_user_id, new_key.key.clone()).expect("should add");
+		let updates = key_manager.export_new_key_updates(u32::MAX).expect("should export");
+		key_manager.rollback();
+
+		// assert
+		let expected_payload = Frequency::write_public_key(&new_key).expect("should serialize");
+		assert!(matches!(
+			updates.as_slice(),
+			[Update::AddKey { payload, .. }] if payload == &expected_payload
+		));
+		assert!(key_manager.serialized_keys.get(&key1.key).is_some());
+		assert!(key_manager.serialized_keys.get(&new_key.key).is_none());
+
+		// replacing the imported keys drops the cached payloads of the old ones
+		key_manager
+			.import_dsnp_keys(&create_dsnp_keys(dsnp_user_id, 234, vec![]))
+			.expect("should work");
+		assert!(key_manager.serialized_keys.get(&key1.key).is_none());
+	}
+
 	#[test]
 	fn shared_state_manager_add_new_key_should_fail_if_already_exists() {
 		// arrange
@@ -622,4 +1544,45 @@ mod tests {
 		// assert
 		assert_eq!(res, Some(&key1));
 	}
+
+	#[test]
+	fn shared_state_manager_key_page_compaction_should_delete_all_but_last_duplicate() {
+		// arrange
+		let dsnp_user_id = 2;
+		let contents = [b"A", b"B", b"A", b"C", b"B"].map(|suffix| {
+			let mut key = b"2176781278128718123343246543087".to_vec();
+			key.extend_from_slice(suffix);
+			DsnpPublicKey { key_id: None, key }
+		});
+		let key_data = contents
+			.iter()
+			.enumerate()
+			.map(|(index, key)| KeyData {
+				index: index as u16,
+				content: Frequency::write_public_key(key).expect("should serialize"),
+			})
+			.collect();
+		let mut key_manager = SharedStateManager::new();
+		key_manager
+			.import_dsnp_keys(&create_dsnp_keys(dsnp_user_id, 233, key_data))
+			.expect("should import");
+		let unique = create_dsnp_keys(3, 10, vec![]);
+		key_manager.import_dsnp_keys(&unique).expect("should import");
+
+		// act
+		let compaction = key_manager.get_key_page_compaction(&dsnp_user_id);
+		let without_duplicates = key_manager.get_key_page_compaction(&3);
+		key_manager
+			.add_new_key(dsnp_user_id, b"21767812781287181233432465430870".to_vec())
+			.unwrap();
+		let with_new_key = key_manager.get_key_page_compaction(&dsnp_user_id);
+
+		// assert
+		let compaction = compaction.expect("should work").expect("should have duplicates");
+		assert_eq!(compaction.prev_hash, 233);
+		assert_eq!(compaction.delete_indices, vec![1, 0]);
+		assert_eq!(compaction.remaining_key_ids, vec![2, 3, 4]);
+		assert!(matches!(without_duplicates, Ok(None)));
+		assert!(matches!(with_new_key, Err(DsnpGraphError::InvalidInput(_))));
+	}
 }
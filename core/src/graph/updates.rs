@@ -1,8 +1,13 @@
 #![allow(dead_code)]
 use crate::{
+	api::api_types::PageId,
 	dsnp::dsnp_types::DsnpUserId,
 	graph::updates::UpdateEvent::{Add, Remove},
-	util::transactional_hashmap::{Transactional, TransactionalHashMap},
+	util::{
+		log_redaction::redact_id,
+		state_hash::StateHasher,
+		transactional_hashmap::{Transactional, TransactionalHashMap},
+	},
 };
 use dsnp_graph_config::{
 	errors::{DsnpGraphError, DsnpGraphResult},
@@ -10,7 +15,10 @@ use dsnp_graph_config::{
 };
 use log::Level;
 use log_result_proc_macro::log_result_err;
-use std::{cmp::Ordering, collections::HashSet};
+use std::{
+	cmp::Ordering,
+	collections::{BTreeMap, HashMap, HashSet},
+};
 
 /// Update event for a schema
 #[derive(Clone, PartialEq, Ord, Eq, PartialOrd, Debug)]
@@ -26,23 +34,35 @@ pub enum UpdateEvent {
 pub struct UpdateTracker {
 	/// map of schema id to update events
 	updates: TransactionalHashMap<SchemaId, Vec<UpdateEvent>>,
+	/// map of pending added connections to their preferred page id
+	page_hints: TransactionalHashMap<(SchemaId, DsnpUserId), PageId>,
+	/// map of pending added connections to their since value, if not added at export time
+	since_hints: TransactionalHashMap<(SchemaId, DsnpUserId), u64>,
 }
 
 /// implementing transactional trait for update tracker
 impl Transactional for UpdateTracker {
 	fn commit(&mut self) {
 		self.updates.commit();
+		self.page_hints.commit();
+		self.since_hints.commit();
 	}
 
 	fn rollback(&mut self) {
 		self.updates.rollback();
+		self.page_hints.rollback();
+		self.since_hints.rollback();
 	}
 }
 
 impl UpdateTracker {
 	/// creates a new update tracker
 	pub fn new() -> Self {
-		Self { updates: TransactionalHashMap::new() }
+		Self {
+			updates: TransactionalHashMap::new(),
+			page_hints: TransactionalHashMap::new(),
+			since_hints: TransactionalHashMap::new(),
+		}
 	}
 
 	/// registers an update event
@@ -57,10 +77,16 @@ impl UpdateTracker {
 				true => {
 					match event {
 						UpdateEvent::Add { dsnp_user_id, schema_id } => {
-							log::warn!("Ignore duplicate Add event: id={dsnp_user_id}, schema_id={schema_id}");
+							log::warn!(
+								"Ignore duplicate Add event: id={}, schema_id={schema_id}",
+								redact_id(dsnp_user_id)
+							);
 						},
 						UpdateEvent::Remove { dsnp_user_id, schema_id } => {
-							log::warn!("Ignore duplicate Remove event: id={dsnp_user_id}, schema_id={schema_id}");
+							log::warn!(
+								"Ignore duplicate Remove event: id={}, schema_id={schema_id}",
+								redact_id(dsnp_user_id)
+							);
 						},
 					};
 					Ok(())
@@ -97,6 +123,98 @@ impl UpdateTracker {
 		Ok(())
 	}
 
+	/// sets the preferred page of a pending added connection, ignored if there is no pending add
+	pub fn set_page_hint(
+		&mut self,
+		schema_id: SchemaId,
+		dsnp_user_id: DsnpUserId,
+		page_id: PageId,
+	) {
+		if self.contains(&UpdateEvent::create_add(dsnp_user_id, schema_id)) {
+			self.page_hints.insert((schema_id, dsnp_user_id), page_id);
+		}
+	}
+
+	/// sets the since value of a pending added connection, ignored if there is no pending add
+	pub fn set_since_hint(&mut self, schema_id: SchemaId, dsnp_user_id: DsnpUserId, since: u64) {
+		if self.contains(&UpdateEvent::create_add(dsnp_user_id, schema_id)) {
+			self.since_hints.insert((schema_id, dsnp_user_id), since);
+		}
+	}
+
+	/// returns since values of pending added connections for the schema id
+	pub fn get_since_hints_for_schema_id(&self, schema_id: SchemaId) -> HashMap<DsnpUserId, u64> {
+		self.since_hints
+			.inner()
+			.iter()
+			.filter(|((s, _), _)| *s == schema_id)
+			.map(|((_, dsnp_user_id), since)| (*dsnp_user_id, *since))
+			.collect()
+	}
+
+	/// returns preferred pages of pending added connections for the schema id
+	pub fn get_page_hints_for_schema_id(&self, schema_id: SchemaId) -> HashMap<DsnpUserId, PageId> {
+		self.page_hints
+			.inner()
+			.iter()
+			.filter(|((s, _), _)| *s == schema_id)
+			.map(|((_, dsnp_user_id), page_id)| (*dsnp_user_id, *page_id))
+			.collect()
+	}
+
+	/// estimated number of bytes used by pending update events and hints in memory
+	pub fn memory_footprint(&self) -> usize {
+		let events: usize = self
+			.updates
+			.inner()
+			.values()
+			.map(|events| {
+				std::mem::size_of::<(SchemaId, Vec<UpdateEvent>)>() +
+					events.len() * std::mem::size_of::<UpdateEvent>()
+			})
+			.sum();
+		let page_hints =
+			self.page_hints.len() * std::mem::size_of::<((SchemaId, DsnpUserId), PageId)>();
+		let since_hints =
+			self.since_hints.len() * std::mem::size_of::<((SchemaId, DsnpUserId), u64)>();
+		std::mem::size_of::<Self>() + events + page_hints + since_hints
+	}
+
+	/// feeds the pending update events by schema id in registration order, followed by the page
+	/// and since hints sorted by schema and user id, into the hasher
+	pub fn hash_into(&self, hasher: &mut StateHasher) {
+		let updates: BTreeMap<_, _> = self.updates.inner().iter().collect();
+		hasher.write_u64(updates.len() as u64);
+		for (schema_id, events) in updates {
+			hasher.write_u64(*schema_id as u64);
+			hasher.write_u64(events.len() as u64);
+			for event in events {
+				let (kind, dsnp_user_id) = match event {
+					Add { dsnp_user_id, .. } => (0, dsnp_user_id),
+					Remove { dsnp_user_id, .. } => (1, dsnp_user_id),
+				};
+				hasher.write_u64(kind);
+				hasher.write_u64(*dsnp_user_id);
+			}
+		}
+
+		let page_hints: BTreeMap<_, _> = self.page_hints.inner().iter().collect();
+		hasher.write_u64(page_hints.len() as u64);
+		for ((schema_id, dsnp_user_id), page_id) in page_hints {
+			hasher.write_u64(*schema_id as u64);
+			hasher.write_u64(*dsnp_user_id);
+			hasher.write_u64(*page_id as u64);
+		}
+
+		let since_hints: BTreeMap<_, _> = self.since_hints.inner().iter().collect();
+		hasher.write_u64(since_hints.len() as u64);
+		for ((schema_id, dsnp_user_id), since) in since_hints {
+			hasher.write_u64(*schema_id as u64);
+			hasher.write_u64(*dsnp_user_id);
+			hasher.write_u64(*since);
+		}
+	}
+
 	/// returns true if there are any updates
 	pub fn has_updates(&self) -> bool {
 		self.updates.inner().iter().any(|(_, v)| !v.is_empty())
@@ -107,6 +225,16 @@ impl UpdateTracker {
 		self.updates.inner().get(&schema_id)
 	}
 
+	/// returns the users of pending added connections for the schema id, in registration order
+	pub fn pending_adds(&self, schema_id: SchemaId) -> Vec<DsnpUserId> {
+		self.filter_updates(schema_id, |e| matches!(e, Add { .. }))
+	}
+
+	/// returns the users of pending removed connections for the schema id, in registration order
+	pub fn pending_removes(&self, schema_id: SchemaId) -> Vec<DsnpUserId> {
+		self.filter_updates(schema_id, |e| matches!(e, Remove { .. }))
+	}
+
 	/// returns true if there are any updates for the schema id
 	pub fn contains(&self, event: &UpdateEvent) -> bool {
 		match self.updates.inner().get(event.get_schema_id()) {
@@ -142,8 +270,31 @@ impl UpdateTracker {
 		}
 	}
 
+	/// returns the users of the update events for the schema id which match the predicate
+	fn filter_updates(
+		&self,
+		schema_id: SchemaId,
+		predicate: impl Fn(&UpdateEvent) -> bool,
+	) -> Vec<DsnpUserId> {
+		self.get_updates_for_schema_id(schema_id)
+			.map(|events| {
+				events
+					.iter()
+					.filter(|e| predicate(e))
+					.map(|e| match e {
+						Add { dsnp_user_id, .. } | Remove { dsnp_user_id, .. } => *dsnp_user_id,
+					})
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
 	/// removes the update event
 	fn remove(&mut self, event: &UpdateEvent) {
+		if let Add { dsnp_user_id, schema_id } = event {
+			self.page_hints.remove(&(*schema_id, *dsnp_user_id));
+			self.since_hints.remove(&(*schema_id, *dsnp_user_id));
+		}
 		if let Some(arr) = self.updates.get(event.get_schema_id()) {
 			let mut updates = arr.clone();
 			updates.retain(|e| e.ne(event));
@@ -319,4 +470,50 @@ mod test {
 		let schema_1_events = tracker.updates.get(&schema_1).unwrap();
 		assert_eq!(schema_1_events.as_slice(), &events[..2]);
 	}
+
+	#[test]
+	fn tracker_page_hints_should_be_removed_with_cancelled_add_events() {
+		// arrange
+		let mut tracker = UpdateTracker::new();
+		let schema_id = 4;
+		tracker.register_update(UpdateEvent::create_add(1, schema_id), false).unwrap();
+		tracker.register_update(UpdateEvent::create_add(2, schema_id), false).unwrap();
+		tracker.set_page_hint(schema_id, 1, 5);
+		tracker.set_page_hint(schema_id, 2, 6);
+		tracker.set_page_hint(schema_id, 3, 7);
+
+		// act
+		tracker
+			.register_update(UpdateEvent::create_remove(1, schema_id), false)
+			.unwrap();
+
+		// assert
+		assert_eq!(tracker.get_page_hints_for_schema_id(schema_id), HashMap::from([(2, 6)]));
+		assert!(tracker.get_page_hints_for_schema_id(schema_id + 1).is_empty());
+	}
+
+	#[test]
+	fn tracker_pending_adds_and_removes_should_be_scoped_to_schema_id() {
+		// arrange
+		let mut tracker = UpdateTracker::new();
+		let schema_1 = 4;
+		let schema_2 = 5;
+		let events = vec![
+			UpdateEvent::create_add(3, schema_1),
+			UpdateEvent::create_remove(2, schema_1),
+			UpdateEvent::create_add(1, schema_1),
+			UpdateEvent::create_remove(7, schema_2),
+		];
+		tracker.register_updates(events, false).unwrap();
+
+		// act
+		tracker.register_update(UpdateEvent::create_remove(1, schema_1), false).unwrap();
+
+		// assert
+		assert_eq!(tracker.pending_adds(schema_1), vec![3]);
+		assert_eq!(tracker.pending_removes(schema_1), vec![2]);
+		assert!(tracker.pending_adds(schema_2).is_empty());
+		assert_eq!(tracker.pending_removes(schema_2), vec![7]);
+		assert!(tracker.pending_removes(schema_2 + 1).is_empty());
+	}
 }
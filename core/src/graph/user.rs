@@ -5,17 +5,24 @@ use dsnp_graph_config::{
 	Environment, SchemaId,
 };
 use std::{
-	collections::HashSet,
+	collections::{BTreeMap, HashSet},
+	convert::Infallible,
 	sync::{Arc, RwLock},
 };
 
 use crate::{
-	dsnp::dsnp_configs::DsnpVersionConfig,
+	dsnp::{
+		chain_adapter::SharedChainAdapter,
+		dsnp_configs::{DsnpVersionConfig, PublicKeyType},
+	},
 	graph::{
-		key_manager::UserKeyManager, shared_state_manager::SharedStateManager, updates::UpdateEvent,
+		key_manager::{SharedUserKeyManager, UserKeyManager, USER_KEY_MANAGER},
+		shared_state_manager::SharedStateManager,
+		updates::UpdateEvent,
 	},
 	util::{
-		time::time_in_ksecs,
+		state_hash::StateHasher,
+		time::{system_time_provider, SharedTimeProvider},
 		transactional_hashmap::{Transactional, TransactionalHashMap},
 	},
 };
@@ -33,7 +40,10 @@ pub struct UserGraph {
 	environment: Environment,
 	graphs: GraphMap,
 	update_tracker: UpdateTracker,
-	pub user_key_manager: Arc<RwLock<UserKeyManager>>,
+	pub user_key_manager: SharedUserKeyManager,
+	time_provider: SharedTimeProvider,
+	/// block at which the imported pages of each graph were read from chain
+	chain_heads: TransactionalHashMap<SchemaId, ChainHead>,
 }
 
 impl Transactional for UserGraph {
@@ -46,18 +56,12 @@ impl Transactional for UserGraph {
 		}
 		self.graphs.commit();
 		self.update_tracker.commit();
+		self.chain_heads.commit();
 		self.user_key_manager.write().unwrap().commit();
 	}
 
 	fn rollback(&mut self) {
-		self.graphs.rollback();
-		let ids: Vec<_> = self.graphs.inner().keys().copied().collect();
-		for gid in ids {
-			if let Some(g) = self.graphs.get_mut(&gid) {
-				g.rollback();
-			}
-		}
-		self.update_tracker.rollback();
+		self.rollback_graphs();
 		self.user_key_manager.write().unwrap().rollback();
 	}
 }
@@ -68,9 +72,29 @@ impl UserGraph {
 		user_id: &DsnpUserId,
 		environment: &Environment,
 		shared_state_manager: Arc<RwLock<SharedStateManager>>,
+	) -> Self {
+		Self::with_time_provider(user_id, environment, shared_state_manager, system_time_provider())
+	}
+
+	/// Create a new, empty UserGraph using provided time source
+	pub fn with_time_provider(
+		user_id: &DsnpUserId,
+		environment: &Environment,
+		shared_state_manager: Arc<RwLock<SharedStateManager>>,
+		time_provider: SharedTimeProvider,
 	) -> Self {
 		let user_key_manager =
 			Arc::new(RwLock::new(UserKeyManager::new(*user_id, shared_state_manager)));
+		Self::with_key_manager(user_id, environment, user_key_manager, time_provider)
+	}
+
+	/// Create a new, empty UserGraph using provided key manager and time source
+	pub fn with_key_manager(
+		user_id: &DsnpUserId,
+		environment: &Environment,
+		user_key_manager: SharedUserKeyManager,
+		time_provider: SharedTimeProvider,
+	) -> Self {
 		let graphs: GraphMap = environment
 			.get_config()
 			.schema_map
@@ -78,7 +102,13 @@ impl UserGraph {
 			.map(|schema_id| {
 				(
 					*schema_id,
-					Graph::new(environment.clone(), *user_id, *schema_id, user_key_manager.clone()),
+					Graph::with_time_provider(
+						environment.clone(),
+						*user_id,
+						*schema_id,
+						user_key_manager.clone(),
+						time_provider.clone(),
+					),
 				)
 			})
 			.collect();
@@ -88,6 +118,8 @@ impl UserGraph {
 			user_key_manager,
 			update_tracker: UpdateTracker::new(),
 			environment: environment.clone(),
+			time_provider,
+			chain_heads: TransactionalHashMap::new(),
 		}
 	}
 
@@ -96,6 +128,121 @@ impl UserGraph {
 		&self.graphs
 	}
 
+	/// Estimated number of bytes used by imported graphs and key pairs of this user in memory,
+	/// excluding pending updates
+	pub fn memory_footprint(&self) -> DsnpGraphResult<usize> {
+		let key_manager_bytes = self
+			.user_key_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+			.memory_footprint();
+		let graph_bytes: usize = self
+			.graphs
+			.inner()
+			.values()
+			.map(|graph| std::mem::size_of::<SchemaId>() + graph.memory_footprint())
+			.sum();
+		Ok(std::mem::size_of::<Self>() + key_manager_bytes + graph_bytes)
+	}
+
+	/// feeds the public keys of the imported key pairs by key id, the graphs by schema id and the
+	/// pending updates of this user into the hasher
+	pub fn hash_into(&self, hasher: &mut StateHasher) -> DsnpGraphResult<()> {
+		let mut keys = self
+			.user_key_manager
+			.read()
+			.map_err(|_| DsnpGraphError::FailedtoReadLock(USER_KEY_MANAGER.to_string()))?
+			.get_all_resolved_keys();
+		keys.sort_by_key(|key| key.key_id);
+		hasher.write_u64(keys.len() as u64);
+		for key in keys {
+			hasher.write_u64(key.key_id);
+			hasher.write_bytes(&key.key_pair.get_public_key_raw());
+		}
+
+		let graphs: BTreeMap<_, _> = self.graphs.inner().iter().collect();
+		hasher.write_u64(graphs.len() as u64);
+		for (schema_id, graph) in graphs {
+			hasher.write_u64(*schema_id as u64);
+			graph.hash_into(hasher);
+		}
+
+		self.update_tracker.hash_into(hasher);
+		Ok(())
+	}
+
+	/// Uses the provided chain adapter to read and write the pages of all graphs of this user
+	pub fn with_chain_adapter(mut self, chain_adapter: SharedChainAdapter) -> Self {
+		let _ = self.graphs.try_for_each_value_mut(|_, graph| -> Result<(), Infallible> {
+			graph.set_chain_adapter(chain_adapter.clone());
+			Ok(())
+		});
+		self
+	}
+
+	/// Seals the private pages of all graphs of this user to the provided public keys in addition to
+	/// the active key, when exported using `EncryptionMode::MultiRecipientV1`
+	pub fn set_additional_recipients(&mut self, additional_recipients: Vec<PublicKeyType>) {
+		let _ = self.graphs.try_for_each_value_mut(|_, graph| -> Result<(), Infallible> {
+			graph.set_additional_recipients(additional_recipients.clone());
+			Ok(())
+		});
+	}
+
+	/// reverts the uncommitted changes of the graphs without touching the key manager
+	fn rollback_graphs(&mut self) {
+		self.graphs.rollback();
+		let ids: Vec<_> = self.graphs.inner().keys().copied().collect();
+		for gid in ids {
+			if let Some(g) = self.graphs.get_mut(&gid) {
+				g.rollback();
+			}
+		}
+		self.update_tracker.rollback();
+		self.chain_heads.rollback();
+	}
+
+	/// Creates a copy of the committed graphs of this user graph, which shares the key manager of
+	/// this one and leaves its uncommitted changes in place
+	pub fn committed_copy(&self) -> Self {
+		let mut copy = self.clone();
+		copy.rollback_graphs();
+		copy
+	}
+
+	/// Creates a copy of this user graph, including its uncommitted changes, which uses the
+	/// provided key manager in all of its graphs
+	pub fn clone_with_key_manager(&self, user_key_manager: SharedUserKeyManager) -> Self {
+		let mut graphs = self.graphs.clone();
+		let _ = graphs.try_for_each_value_mut(|_, graph| -> Result<(), Infallible> {
+			graph.set_user_key_manager(user_key_manager.clone());
+			Ok(())
+		});
+		Self {
+			environment: self.environment.clone(),
+			graphs,
+			update_tracker: self.update_tracker.clone(),
+			user_key_manager,
+			time_provider: self.time_provider.clone(),
+			chain_heads: self.chain_heads.clone(),
+		}
+	}
+
+	/// Sets the block at which the imported pages of the graph with the specified schema_id were
+	/// read from chain, which gets attached to the exported updates of the graph
+	pub fn set_chain_head(&mut self, schema_id: SchemaId, chain_head: Option<ChainHead>) {
+		match chain_head {
+			Some(chain_head) => self.chain_heads.insert(schema_id, chain_head),
+			None => self.chain_heads.remove(&schema_id),
+		};
+	}
+
+	/// Returns the block at which the imported pages of the graph with the specified schema_id
+	/// were read from chain
+	pub fn chain_head(&self, schema_id: SchemaId) -> Option<&ChainHead> {
+		self.chain_heads.get(&schema_id)
+	}
+
 	/// Getter for UpdateTracker
 	pub fn update_tracker(&self) -> &UpdateTracker {
 		&self.update_tracker
@@ -141,33 +288,203 @@ impl UserGraph {
 
 	/// Calculate pending updates for all graphs for this user
 	#[log_result_err(Level::Info)]
-	pub fn calculate_updates(&self) -> DsnpGraphResult<Vec<Update>> {
-		let mut result: Vec<Update> = Vec::new();
-		for (schema_id, graph) in self.graphs.inner().iter() {
-			if let Some(updates) = self.update_tracker.get_updates_for_schema_id(*schema_id) {
-				let dsnp_version_config = self
-					.get_dsnp_config(*schema_id)
-					.ok_or(DsnpGraphError::UnsupportedSchema(*schema_id))?;
+	pub fn calculate_updates(&self, export_options: ExportOptions) -> DsnpGraphResult<Vec<Update>> {
+		let (updates, _) = self.calculate_updates_with_placements(export_options)?;
+		Ok(updates.into_iter().map(|u| u.update).collect())
+	}
+
+	/// Calculate pending updates for all graphs for this user alongside the pages that added
+	/// connections landed in and the connection changes of each updated page
+	#[log_result_err(Level::Info)]
+	pub fn calculate_updates_with_placements(
+		&self,
+		export_options: ExportOptions,
+	) -> DsnpGraphResult<(Vec<UpdateWithDetails>, Vec<ConnectionPlacement>)> {
+		self.calculate_updates_of_schemas(export_options, |_| true)
+	}
 
-				let graph_data = graph.calculate_updates(&dsnp_version_config, &updates)?;
-				result.extend(graph_data.into_iter());
+	/// Calculate pending updates for the graphs of this user with the provided schema ids
+	pub fn calculate_updates_for_schemas(
+		&self,
+		export_options: ExportOptions,
+		schema_ids: &[SchemaId],
+	) -> DsnpGraphResult<Vec<Update>> {
+		let (updates, _) = self.calculate_updates_of_schemas(export_options, |schema_id| {
+			schema_ids.contains(schema_id)
+		})?;
+		Ok(updates.into_iter().map(|u| u.update).collect())
+	}
+
+	/// Calculate pending updates and placements for the graphs of this user which are included
+	fn calculate_updates_of_schemas(
+		&self,
+		export_options: ExportOptions,
+		include: impl Fn(&SchemaId) -> bool,
+	) -> DsnpGraphResult<(Vec<UpdateWithDetails>, Vec<ConnectionPlacement>)> {
+		let mut result: Vec<UpdateWithDetails> = Vec::new();
+		let mut placements: Vec<ConnectionPlacement> = Vec::new();
+		let no_updates = vec![];
+		for (schema_id, graph) in self.graphs.inner().iter().filter(|(s, _)| include(s)) {
+			// graphs with legacy formatted or repaired pages are exported even without pending
+			// updates so that they get rewritten
+			let updates = match self.update_tracker.get_updates_for_schema_id(*schema_id) {
+				Some(updates) => updates,
+				None if graph.has_pages_needing_rewrite() => &no_updates,
+				None => continue,
 			};
+			let dsnp_version_config = self
+				.get_dsnp_config(*schema_id)
+				.ok_or(DsnpGraphError::UnsupportedSchema(*schema_id))?;
+
+			let page_hints = self.update_tracker.get_page_hints_for_schema_id(*schema_id);
+			let since_hints = self.update_tracker.get_since_hints_for_schema_id(*schema_id);
+			let (graph_data, graph_placements) = graph.calculate_updates_with_placements(
+				&dsnp_version_config,
+				&updates,
+				&page_hints,
+				&since_hints,
+				export_options,
+			)?;
+			let chain_head = self.chain_heads.get(schema_id);
+			result.extend(graph_data.into_iter().map(|u| UpdateWithDetails {
+				update: u.update.with_chain_head(chain_head.cloned()),
+				details: u.details,
+			}));
+			placements.extend(graph_placements.into_iter());
 		}
 
-		Ok(result)
+		Ok((result, placements))
+	}
+
+	/// Calculates `DeletePage` updates for all imported pages of every graph of this user
+	pub fn calculate_graph_removal(&self) -> Vec<Update> {
+		let mut schema_ids: Vec<_> = self.graphs.inner().keys().copied().collect();
+		schema_ids.sort();
+		schema_ids
+			.iter()
+			.filter_map(|schema_id| {
+				Some((self.graphs.get(schema_id)?, self.chain_heads.get(schema_id)))
+			})
+			.flat_map(|(graph, chain_head)| {
+				graph
+					.export_removal()
+					.into_iter()
+					.map(move |u| u.with_chain_head(chain_head.cloned()))
+			})
+			.collect()
+	}
+
+	/// Exports the graph with the specified schema_id using the export options and the page and
+	/// since hints of the pending updates, and imports it back into a scratch graph to verify that
+	/// all the connections survive the round-trip
+	#[log_result_err(Level::Info)]
+	pub fn verify_roundtrip(
+		&self,
+		schema_id: SchemaId,
+		export_options: ExportOptions,
+	) -> DsnpGraphResult<RoundtripReport> {
+		let graph = self
+			.graph(&schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(schema_id))?;
+		let dsnp_version_config = self
+			.get_dsnp_config(schema_id)
+			.ok_or(DsnpGraphError::UnsupportedSchema(schema_id))?;
+		let no_updates = vec![];
+		let updates =
+			self.update_tracker.get_updates_for_schema_id(schema_id).unwrap_or(&no_updates);
+
+		let page_hints = self.update_tracker.get_page_hints_for_schema_id(schema_id);
+		let since_hints = self.update_tracker.get_since_hints_for_schema_id(schema_id);
+		graph.verify_roundtrip(
+			&dsnp_version_config,
+			updates,
+			&page_hints,
+			&since_hints,
+			export_options,
+		)
+	}
+
+	/// Estimates the remaining capacity of the graph with the specified schema_id after applying
+	/// the pending updates
+	pub fn remaining_capacity(&self, schema_id: SchemaId) -> DsnpGraphResult<CapacityReport> {
+		let graph = self
+			.graph(&schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(schema_id))?;
+		let no_updates = vec![];
+		let updates =
+			self.update_tracker.get_updates_for_schema_id(schema_id).unwrap_or(&no_updates);
+
+		graph.remaining_capacity(updates)
+	}
+
+	/// Returns the users of pending added connections of the graph with the specified schema_id
+	pub fn pending_adds(&self, schema_id: SchemaId) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		self.graph(&schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(schema_id))?;
+
+		Ok(self.update_tracker.pending_adds(schema_id))
+	}
+
+	/// Returns the users of pending removed connections of the graph with the specified schema_id
+	pub fn pending_removes(&self, schema_id: SchemaId) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		self.graph(&schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(schema_id))?;
+
+		Ok(self.update_tracker.pending_removes(schema_id))
+	}
+
+	/// Returns the statistics of the graph with the specified schema_id
+	pub fn stats(&self, schema_id: SchemaId) -> DsnpGraphResult<UserGraphStats> {
+		let graph = self
+			.graph(&schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(schema_id))?;
+
+		Ok(graph.stats())
+	}
+
+	/// Returns the connection changes of the graph with the specified schema_id since the pages
+	/// with the provided content hashes were imported
+	pub fn changes_since(
+		&self,
+		schema_id: SchemaId,
+		page_hashes: &[(PageId, PageHash)],
+	) -> DsnpGraphResult<ChangesReport> {
+		let graph = self
+			.graph(&schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(schema_id))?;
+
+		Ok(graph.changes_since(page_hashes))
+	}
+
+	/// Lints all imported graphs of this user, sorted by schema id
+	#[log_result_err(Level::Info)]
+	pub fn lint(&self) -> DsnpGraphResult<Vec<LintIssue>> {
+		let graphs: BTreeMap<&SchemaId, &Graph> = self.graphs.inner().iter().collect();
+		let mut issues = vec![];
+		for (schema_id, graph) in graphs {
+			let dsnp_version_config = self
+				.get_dsnp_config(*schema_id)
+				.ok_or(DsnpGraphError::UnsupportedSchema(*schema_id))?;
+			issues.extend(graph.lint(&dsnp_version_config)?);
+		}
+		Ok(issues)
 	}
 
 	// force calculates all imported graphs which will use the latest encryption key
 	#[log_result_err(Level::Info)]
-	pub fn force_calculate_graphs(&self) -> DsnpGraphResult<Vec<Update>> {
+	pub fn force_calculate_graphs(
+		&self,
+		export_options: ExportOptions,
+	) -> DsnpGraphResult<Vec<Update>> {
 		let mut result = vec![];
 		for (schema_id, graph) in self.graphs.inner().iter() {
 			let dsnp_version_config = self
 				.get_dsnp_config(*schema_id)
 				.ok_or(DsnpGraphError::UnsupportedSchema(*schema_id))?;
 
-			let updates = graph.force_recalculate(&dsnp_version_config)?;
-			result.extend(updates);
+			let updates = graph.force_recalculate(&dsnp_version_config, export_options)?;
+			let chain_head = self.chain_heads.get(schema_id);
+			result.extend(updates.into_iter().map(|u| u.with_chain_head(chain_head.cloned())));
 		}
 
 		Ok(result)
@@ -194,6 +511,83 @@ impl UserGraph {
 		false
 	}
 
+	/// Returns the friendships of the specified schema_id which are not reciprocated by their
+	/// counterparts. Pending added and removed connections are applied if `include_pending` is set
+	pub fn get_one_sided_friendships(
+		&self,
+		schema_id: SchemaId,
+		include_pending: bool,
+	) -> DsnpGraphResult<Vec<DsnpGraphEdge>> {
+		let graph = self
+			.graph(&schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(schema_id))?;
+		if !include_pending {
+			return graph.get_one_sided_friendships();
+		}
+		let mut one_sided =
+			graph.filter_one_sided_friendships(self.get_all_connections_of(schema_id, true))?;
+		one_sided.sort_by_key(|edge| edge.user_id);
+		Ok(one_sided)
+	}
+
+	/// Stages removal of all imported connections of the specified schema_id which were added more
+	/// than `older_than_days` ago, and returns the ids of the pruned connections
+	pub fn prune_connections(
+		&mut self,
+		schema_id: SchemaId,
+		older_than_days: u64,
+	) -> DsnpGraphResult<Vec<DsnpUserId>> {
+		let graph = self
+			.graph(&schema_id)
+			.ok_or_else(|| self.environment.invalid_schema_id(schema_id))?;
+		let pruned: Vec<_> = graph
+			.get_connections_older_than(older_than_days)
+			.into_iter()
+			.filter(|id| !self.update_tracker.contains(&UpdateEvent::create_remove(*id, schema_id)))
+			.collect();
+
+		self.update_tracker.register_updates(
+			pruned.iter().map(|id| UpdateEvent::create_remove(*id, schema_id)).collect(),
+			false,
+		)?;
+		Ok(pruned)
+	}
+
+	/// Stages added connections of the specified schema_id using their `since` values, and returns
+	/// the number of staged connections. Existing, pending and repeated connections are skipped,
+	/// and nothing is staged if the remaining connections would not fit into the graph
+	pub fn import_edge_list(
+		&mut self,
+		schema_id: SchemaId,
+		edges: impl Iterator<Item = (DsnpUserId, u64)>,
+	) -> DsnpGraphResult<usize> {
+		let capacity = self.remaining_capacity(schema_id)?;
+		let mut seen = HashSet::new();
+		let new_edges: Vec<_> = edges
+			.filter(|(dsnp_user_id, _)| seen.insert(*dsnp_user_id))
+			.filter(|(dsnp_user_id, _)| !self.graph_has_connection(schema_id, *dsnp_user_id, true))
+			.collect();
+		if new_edges.len() > capacity.remaining_connections {
+			return Err(DsnpGraphError::GraphCapacityExceeded(
+				schema_id,
+				capacity.full_page_ids,
+				new_edges.len() - capacity.remaining_connections,
+			))
+		}
+
+		self.update_tracker.register_updates(
+			new_edges
+				.iter()
+				.map(|(dsnp_user_id, _)| UpdateEvent::create_add(*dsnp_user_id, schema_id))
+				.collect(),
+			false,
+		)?;
+		for (dsnp_user_id, since) in &new_edges {
+			self.update_tracker.set_since_hint(schema_id, *dsnp_user_id, *since);
+		}
+		Ok(new_edges.len())
+	}
+
 	/// Get all the connections for the specified schema_id
 	pub fn get_all_connections_of(
 		&self,
@@ -211,6 +605,8 @@ impl UserGraph {
 			.collect();
 
 		if apply_pending {
+			let now = self.time_provider.time_in_ksecs();
+			let since_hints = self.update_tracker.get_since_hints_for_schema_id(schema_id);
 			self.update_tracker
 				.get_updates_for_schema_id(schema_id)
 				.unwrap_or(&Vec::<UpdateEvent>::new())
@@ -218,16 +614,11 @@ impl UserGraph {
 				.cloned()
 				.for_each(|event| match event {
 					UpdateEvent::Add { dsnp_user_id, .. } => {
-						connections.insert(DsnpGraphEdge {
-							user_id: dsnp_user_id,
-							since: time_in_ksecs(),
-						});
+						let since = since_hints.get(&dsnp_user_id).copied().unwrap_or(now);
+						connections.insert(DsnpGraphEdge { user_id: dsnp_user_id, since });
 					},
 					UpdateEvent::Remove { dsnp_user_id, .. } => {
-						connections.remove(&DsnpGraphEdge {
-							user_id: dsnp_user_id,
-							since: time_in_ksecs(),
-						});
+						connections.remove(&DsnpGraphEdge { user_id: dsnp_user_id, since: now });
 					},
 				});
 		}
@@ -237,18 +628,14 @@ impl UserGraph {
 
 	/// get dsnp config for a schema id
 	pub fn get_dsnp_config(&self, schema_id: SchemaId) -> Option<DsnpVersionConfig> {
-		let config = self.environment.get_config();
-		if let Some(dsnp_version) = config.get_dsnp_version_from_schema_id(schema_id) {
-			return Some(DsnpVersionConfig::new(dsnp_version));
-		}
-		None
+		DsnpVersionConfig::from_schema_id(&self.environment, schema_id).ok()
 	}
 }
 
 #[cfg(test)]
 mod test {
 	use super::*;
-	use crate::{graph::key_manager::UserKeyProvider, iter_graph_connections, tests::helpers::*};
+	use crate::{iter_graph_connections, tests::helpers::*};
 	use dryoc::keypair::StackKeyPair;
 	use dsnp_graph_config::{GraphKeyType, ALL_CONNECTION_TYPES};
 
@@ -359,8 +746,12 @@ mod test {
 		// arrange
 		let env = Environment::Mainnet;
 		let graph = create_test_graph(None);
+		let user_key_manager = Arc::new(RwLock::new(UserKeyManager::new(
+			1,
+			Arc::new(RwLock::new(SharedStateManager::new())),
+		)));
 		let mut user_graph =
-			UserGraph::new(&1, &env, Arc::new(RwLock::new(SharedStateManager::new())));
+			UserGraph::with_key_manager(&1, &env, user_key_manager.clone(), system_time_provider());
 		let schema_id = env
 			.get_config()
 			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
@@ -393,6 +784,6 @@ mod test {
 		let graph = user_graph.graph(&schema_id).unwrap();
 		assert!(graph.find_connection(&connection_dsnp).is_none());
 		assert!(!user_graph.update_tracker.has_updates());
-		assert_eq!(user_graph.user_key_manager.read().unwrap().get_imported_keys().len(), 0);
+		assert_eq!(user_key_manager.read().unwrap().get_imported_keys().len(), 0);
 	}
 }
@@ -15,6 +15,8 @@ mod benches;
 pub mod dsnp;
 pub mod frequency;
 mod graph;
+#[cfg(feature = "recommendations")]
+pub mod recommendations;
 #[cfg(test)]
 mod tests;
 pub mod util;
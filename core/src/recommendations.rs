@@ -0,0 +1,165 @@
+//! "People you may know" recommendations calculated over the locally imported graphs
+//!
+//! Candidates are users that are connected to the direct connections of a user without being
+//! connected to the user itself. Each mutual connection contributes to the score of a candidate
+//! based on how recently it was established.
+use crate::{
+	api::api::{GraphAPI, GraphState},
+	dsnp::dsnp_types::DsnpUserId,
+};
+use dsnp_graph_config::{errors::DsnpGraphResult, SchemaId};
+use std::collections::{HashMap, HashSet};
+
+/// Number of days after which the weight of a mutual connection is halved
+pub const RECENCY_HALF_WEIGHT_DAYS: f64 = 30.0;
+
+/// A recommended connection for a user
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recommendation {
+	/// recommended dsnp user
+	pub dsnp_user_id: DsnpUserId,
+
+	/// number of direct connections of the user that are connected to the recommended user
+	pub mutual_connections: u32,
+
+	/// sum of recency weights of all mutual connections
+	pub score: f64,
+}
+
+/// Returns the top `top_n` recommended connections for `user_id` in the graph of `schema_id`.
+///
+/// Only graphs of the direct connections that are imported into `state` are considered and
+/// pending changes are included.
+pub fn recommend_connections(
+	state: &GraphState,
+	user_id: &DsnpUserId,
+	schema_id: &SchemaId,
+	top_n: usize,
+) -> DsnpGraphResult<Vec<Recommendation>> {
+	let direct: HashSet<DsnpUserId> = state
+		.get_connections_for_user_graph(user_id, schema_id, true)?
+		.into_iter()
+		.map(|edge| edge.user_id)
+		.collect();
+
+	let time_provider = state.time_provider();
+	let mut candidates: HashMap<DsnpUserId, (u32, f64)> = HashMap::new();
+	for mutual in direct.iter().filter(|id| state.contains_user_graph(id)) {
+		for edge in state.get_connections_for_user_graph(mutual, schema_id, true)? {
+			if edge.user_id == *user_id || direct.contains(&edge.user_id) {
+				continue
+			}
+			let age_days = time_provider.duration_days_since(edge.since) as f64;
+			let entry = candidates.entry(edge.user_id).or_default();
+			entry.0 += 1;
+			entry.1 += 1.0 / (1.0 + age_days / RECENCY_HALF_WEIGHT_DAYS);
+		}
+	}
+
+	let mut recommendations: Vec<_> = candidates
+		.into_iter()
+		.map(|(dsnp_user_id, (mutual_connections, score))| Recommendation {
+			dsnp_user_id,
+			mutual_connections,
+			score,
+		})
+		.collect();
+	recommendations.sort_by(|a, b| {
+		b.score
+			.total_cmp(&a.score)
+			.then(b.mutual_connections.cmp(&a.mutual_connections))
+			.then(a.dsnp_user_id.cmp(&b.dsnp_user_id))
+	});
+	recommendations.truncate(top_n);
+	Ok(recommendations)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		api::api_types::{Action, Connection},
+		util::time::{TimeProvider, SECS_PER_DAY},
+	};
+	use dsnp_graph_config::{ConnectionType, Environment, PrivacyType};
+	use std::sync::Arc;
+
+	const NOW_SECS: u64 = 1_000 * SECS_PER_DAY;
+
+	#[derive(Debug)]
+	struct FixedTimeProvider;
+
+	impl TimeProvider for FixedTimeProvider {
+		fn now_in_secs(&self) -> u64 {
+			NOW_SECS
+		}
+	}
+
+	fn connect(
+		owner_dsnp_user_id: DsnpUserId,
+		dsnp_user_id: DsnpUserId,
+		schema_id: SchemaId,
+	) -> Action {
+		Action::Connect {
+			owner_dsnp_user_id,
+			connection: Connection { dsnp_user_id, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		}
+	}
+
+	#[test]
+	fn recommend_connections_should_rank_candidates_by_mutual_connections() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::with_time_provider(env, Arc::new(FixedTimeProvider));
+		let actions = vec![
+			connect(1, 2, schema_id),
+			connect(1, 3, schema_id),
+			connect(2, 1, schema_id),
+			connect(2, 4, schema_id),
+			connect(2, 5, schema_id),
+			connect(3, 4, schema_id),
+			connect(3, 2, schema_id),
+		];
+		state.apply_actions(&actions, &None).expect("should apply");
+
+		// act
+		let recommendations = recommend_connections(&state, &1, &schema_id, 10);
+
+		// assert
+		let recommendations = recommendations.unwrap();
+		assert_eq!(recommendations.iter().map(|r| r.dsnp_user_id).collect::<Vec<_>>(), vec![4, 5]);
+		assert_eq!(recommendations[0].mutual_connections, 2);
+		assert_eq!(recommendations[0].score, 2.0);
+		assert_eq!(recommendations[1].mutual_connections, 1);
+	}
+
+	#[test]
+	fn recommend_connections_should_limit_results_to_top_n() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Public))
+			.expect("should exist");
+		let mut state = GraphState::new(env);
+		let mut actions = vec![connect(1, 2, schema_id)];
+		actions.extend((10..20).map(|id| connect(2, id, schema_id)));
+		state.apply_actions(&actions, &None).expect("should apply");
+
+		// act
+		let recommendations = recommend_connections(&state, &1, &schema_id, 3);
+
+		// assert
+		assert_eq!(
+			recommendations.unwrap().iter().map(|r| r.dsnp_user_id).collect::<Vec<_>>(),
+			vec![10, 11, 12]
+		);
+	}
+}
@@ -92,7 +92,7 @@ pub fn create_empty_test_graph(
 		.import_key_pairs(vec![GraphKeyPair {
 			key_type: GraphKeyType::X25519,
 			public_key: key.key_pair.get_public_key_raw(),
-			secret_key: key.key_pair.get_secret_key_raw(),
+			secret_key: key.key_pair.get_secret_key_raw().expect("should have a secret key"),
 		}])
 		.expect("should import user keys");
 	let graph = Graph::new(
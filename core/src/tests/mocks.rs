@@ -1,16 +1,23 @@
 use crate::{
-	api::api_types::{GraphKeyPair, PageData, ResolvedKeyPair},
+	api::api_types::{GraphKeyPair, PageDataRef, ResolvedKeyPair},
 	dsnp::{
 		dsnp_configs::SecretKeyType,
 		dsnp_types::{DsnpPrid, DsnpUserId},
 	},
 	graph::{
-		key_manager::{ConnectionVerifier, UserKeyManagerBase, UserKeyProvider},
-		shared_state_manager::PriProvider,
+		key_manager::{
+			ConnectionVerifier, SharedUserKeyManager, UserKeyManagerBase, UserKeyProvider,
+		},
+		shared_state_manager::{PriProvider, SharedStateManager},
 	},
+	util::transactional_hashmap::Transactional,
 };
 use dsnp_graph_config::errors::{DsnpGraphError, DsnpGraphResult};
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+	collections::HashMap,
+	fmt::Debug,
+	sync::{Arc, RwLock},
+};
 
 #[derive(Debug)]
 pub struct MockUserKeyManager {
@@ -45,6 +52,10 @@ impl ConnectionVerifier for MockUserKeyManager {
 			None => Err(DsnpGraphError::Unknown(anyhow::anyhow!("User not registered!"))),
 		}
 	}
+
+	fn verify_connections(&self, from: &[DsnpUserId]) -> DsnpGraphResult<Vec<bool>> {
+		from.iter().map(|from| self.verify_connection(*from)).collect()
+	}
 }
 
 impl UserKeyProvider for MockUserKeyManager {
@@ -72,7 +83,15 @@ impl PriProvider for MockUserKeyManager {
 	fn import_pri(
 		&mut self,
 		_dsnp_user_id: DsnpUserId,
-		_pages: &[PageData],
+		_pages: &[PageDataRef],
+	) -> DsnpGraphResult<()> {
+		Ok(())
+	}
+
+	fn merge_pri(
+		&mut self,
+		_dsnp_user_id: DsnpUserId,
+		_pages: &[PageDataRef],
 	) -> DsnpGraphResult<()> {
 		Ok(())
 	}
@@ -91,4 +110,24 @@ impl PriProvider for MockUserKeyManager {
 	}
 }
 
-impl UserKeyManagerBase for MockUserKeyManager {}
+impl Transactional for MockUserKeyManager {
+	fn commit(&mut self) {}
+
+	fn rollback(&mut self) {}
+}
+
+impl UserKeyManagerBase for MockUserKeyManager {
+	fn memory_footprint(&self) -> usize {
+		std::mem::size_of::<Self>()
+	}
+
+	fn clone_with_shared_state(
+		&self,
+		_shared_state_manager: Arc<RwLock<SharedStateManager>>,
+	) -> SharedUserKeyManager {
+		Arc::new(RwLock::new(Self {
+			verifications: self.verifications.clone(),
+			key_pairs: self.key_pairs.clone(),
+		}))
+	}
+}
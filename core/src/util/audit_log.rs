@@ -0,0 +1,118 @@
+//! Opt-in log of the actions applied on a graph state which can be persisted by hosts
+use crate::{
+	api::api_types::{Action, AuditRecord, AuditResolution},
+	util::transactional_hashmap::Transactional,
+};
+use dsnp_graph_config::errors::DsnpGraphResult;
+
+/// Records applied actions and resolves them once they are committed or rolled back
+#[derive(Debug, Default)]
+pub struct AuditLog {
+	/// records of the actions which are not committed or rolled back yet
+	pending: Vec<AuditRecord>,
+
+	/// records of the committed or rolled back actions in the order they were applied
+	records: Vec<AuditRecord>,
+}
+
+impl AuditLog {
+	/// records the result of applying an action
+	pub fn record(
+		&mut self,
+		timestamp_secs: u64,
+		action: &Action,
+		result: &DsnpGraphResult<AuditResolution>,
+	) {
+		let (resolution, error) = match result {
+			Ok(resolution) => (*resolution, None),
+			Err(e) => (AuditResolution::Failed, Some(e.to_string())),
+		};
+		self.pending.push(AuditRecord {
+			timestamp_secs,
+			action: action.clone(),
+			resolution,
+			error,
+		});
+	}
+
+	/// returns true if no actions are recorded
+	pub fn is_empty(&self) -> bool {
+		self.pending.is_empty() && self.records.is_empty()
+	}
+
+	/// returns the records of the committed or rolled back actions and clears them, actions which
+	/// are not committed or rolled back yet are kept
+	pub fn drain(&mut self) -> Vec<AuditRecord> {
+		std::mem::take(&mut self.records)
+	}
+}
+
+impl Transactional for AuditLog {
+	fn commit(&mut self) {
+		self.records.append(&mut self.pending);
+	}
+
+	fn rollback(&mut self) {
+		for mut record in self.pending.drain(..) {
+			if record.resolution == AuditResolution::Applied {
+				record.resolution = AuditResolution::RolledBack;
+			}
+			self.records.push(record);
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::api::api_types::Connection;
+	use dsnp_graph_config::errors::DsnpGraphError;
+
+	fn connect(dsnp_user_id: u64) -> Action {
+		Action::Connect {
+			owner_dsnp_user_id: 1,
+			connection: Connection { dsnp_user_id, schema_id: 1 },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		}
+	}
+
+	#[test]
+	fn drain_should_only_return_committed_or_rolled_back_records() {
+		// arrange
+		let mut audit_log = AuditLog::default();
+		audit_log.record(10, &connect(2), &Ok(AuditResolution::Applied));
+		audit_log.commit();
+		audit_log.record(11, &connect(3), &Ok(AuditResolution::Applied));
+		audit_log.record(11, &connect(2), &Ok(AuditResolution::Ignored));
+		audit_log.record(12, &connect(2), &Err(DsnpGraphError::ConnectionAlreadyExists(1, 2)));
+		audit_log.rollback();
+		audit_log.record(13, &connect(4), &Ok(AuditResolution::Applied));
+
+		// act
+		let records = audit_log.drain();
+
+		// assert
+		let resolutions: Vec<_> = records.iter().map(|r| r.resolution).collect();
+		assert_eq!(
+			resolutions,
+			vec![
+				AuditResolution::Applied,
+				AuditResolution::RolledBack,
+				AuditResolution::Ignored,
+				AuditResolution::Failed
+			]
+		);
+		let timestamps: Vec<_> = records.iter().map(|r| r.timestamp_secs).collect();
+		assert_eq!(timestamps, vec![10, 11, 11, 12]);
+		assert!(records[3].error.is_some());
+		assert!(records[..3].iter().all(|r| r.error.is_none()));
+		assert!(audit_log.drain().is_empty());
+		assert!(!audit_log.is_empty());
+
+		audit_log.commit();
+		assert_eq!(audit_log.drain().len(), 1);
+		assert!(audit_log.is_empty());
+	}
+}
@@ -0,0 +1,163 @@
+//! A small bloom filter over the connections of a graph page, which allows membership checks to
+//! skip pages that certainly do not contain any of the searched users without scanning them
+//!
+//! The filter only has false positives, so a positive answer still needs to be confirmed against
+//! the connections of the page. Its size is derived from the number of connections only, which
+//! makes the filter of two pages with the same connections identical regardless of the order the
+//! connections were added and removed in.
+use crate::dsnp::dsnp_types::DsnpUserId;
+
+/// Number of bits reserved for each connection, resulting in about 1% false positives
+const BITS_PER_CONNECTION: usize = 10;
+
+/// Number of bits of the filter of an empty or small page
+const MIN_FILTER_BITS: usize = 256;
+
+/// Number of bits set for each connection
+const HASH_COUNT: u64 = 4;
+
+/// The `disable-page-filter` feature keeps every filter empty and answering that any id may be
+/// contained, so benchmarks can compare maintaining the filters against always scanning pages
+const FILTER_ENABLED: bool = !cfg!(feature = "disable-page-filter");
+
+/// Bloom filter of the dsnp user ids of a graph page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionBloomFilter {
+	/// bit array whose length is a power of two
+	words: Vec<u64>,
+}
+
+impl Default for ConnectionBloomFilter {
+	fn default() -> Self {
+		Self::with_capacity(0)
+	}
+}
+
+impl ConnectionBloomFilter {
+	/// creates an empty filter sized for the number of connections
+	pub fn with_capacity(connections: usize) -> Self {
+		if !FILTER_ENABLED {
+			return Self { words: vec![] }
+		}
+		Self { words: vec![0; Self::required_bits(connections) / u64::BITS as usize] }
+	}
+
+	/// creates a filter containing all the ids
+	pub fn from_ids<'a>(ids: impl ExactSizeIterator<Item = &'a DsnpUserId>) -> Self {
+		let mut filter = Self::with_capacity(ids.len());
+		if !FILTER_ENABLED {
+			return filter
+		}
+		for id in ids {
+			filter.insert(id);
+		}
+		filter
+	}
+
+	/// returns true if the filter is too small to hold the number of connections
+	pub fn needs_resize(&self, connections: usize) -> bool {
+		FILTER_ENABLED && Self::required_bits(connections) > self.bit_len()
+	}
+
+	/// adds the id to the filter
+	pub fn insert(&mut self, id: &DsnpUserId) {
+		if !FILTER_ENABLED {
+			return
+		}
+		for bit in self.bit_indices(id) {
+			self.words[bit / u64::BITS as usize] |= 1 << (bit % u64::BITS as usize);
+		}
+	}
+
+	/// returns false if the id is certainly not in the filter
+	pub fn may_contain(&self, id: &DsnpUserId) -> bool {
+		!FILTER_ENABLED ||
+			self.bit_indices(id).all(|bit| {
+				self.words[bit / u64::BITS as usize] & (1 << (bit % u64::BITS as usize)) != 0
+			})
+	}
+
+	/// returns false if none of the ids are in the filter
+	pub fn may_contain_any<'a>(&self, ids: impl IntoIterator<Item = &'a DsnpUserId>) -> bool {
+		ids.into_iter().any(|id| self.may_contain(id))
+	}
+
+	/// number of bits of the filter
+	pub fn bit_len(&self) -> usize {
+		self.words.len() * u64::BITS as usize
+	}
+
+	/// Estimated number of bytes used by the filter in memory
+	pub fn memory_footprint(&self) -> usize {
+		std::mem::size_of::<Self>() + self.words.len() * std::mem::size_of::<u64>()
+	}
+
+	fn required_bits(connections: usize) -> usize {
+		(connections * BITS_PER_CONNECTION).next_power_of_two().max(MIN_FILTER_BITS)
+	}
+
+	/// double hashing of the id, using the mixing function of splitmix64 to spread sequential
+	/// ids over the whole filter
+	fn bit_indices(&self, id: &DsnpUserId) -> impl Iterator<Item = usize> {
+		let mask = self.bit_len() as u64 - 1;
+		let first = mix(*id);
+		let second = mix(first) | 1;
+		(0..HASH_COUNT).map(move |i| (first.wrapping_add(i.wrapping_mul(second)) & mask) as usize)
+	}
+}
+
+fn mix(value: u64) -> u64 {
+	let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+	z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+	z ^ (z >> 31)
+}
+
+#[cfg(all(test, not(feature = "disable-page-filter")))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn bloom_filter_should_contain_all_inserted_ids() {
+		// arrange
+		let ids: Vec<DsnpUserId> = (1..=1_000).collect();
+
+		// act
+		let filter = ConnectionBloomFilter::from_ids(ids.iter());
+
+		// assert
+		assert!(ids.iter().all(|id| filter.may_contain(id)));
+		assert!(filter.may_contain_any(&[5_000, 10]));
+		assert!(!filter.needs_resize(ids.len()));
+		assert!(filter.needs_resize(ids.len() * 2));
+	}
+
+	#[test]
+	fn bloom_filter_should_have_a_low_false_positive_rate() {
+		// arrange
+		let ids: Vec<DsnpUserId> = (1..=1_000).collect();
+		let filter = ConnectionBloomFilter::from_ids(ids.iter());
+
+		// act
+		let false_positives =
+			(1_000_000..1_010_000).filter(|id: &DsnpUserId| filter.may_contain(id)).count();
+
+		// assert
+		assert!(false_positives < 300, "false positives {}", false_positives);
+	}
+
+	#[test]
+	fn bloom_filters_of_the_same_ids_should_be_equal() {
+		// arrange
+		let mut filter = ConnectionBloomFilter::with_capacity(2);
+		filter.insert(&2);
+		filter.insert(&1);
+
+		// act
+		let other = ConnectionBloomFilter::from_ids([1, 2].iter());
+
+		// assert
+		assert_eq!(filter, other);
+		assert!(!ConnectionBloomFilter::default().may_contain(&1));
+	}
+}
@@ -1,34 +1,49 @@
-//! Builders used mostly in integration and unit tests
+//! Builders of the api types
+//!
+//! `KeyDataBuilder` and `DsnpKeysBuilder` are a stable API to construct validated keys, such as
+//! wallets building `DsnpKeys` from the itemized responses of the chain, while the page and bundle
+//! builders are used mostly in integration and unit tests.
 use crate::{
 	api::api_types::{
-		DsnpKeys, GraphKeyPair, ImportBundle, KeyData, PageData, PageId, ResolvedKeyPair, Update,
+		ChainHead, DsnpKeys, GraphKeyPair, ImportBundle, KeyData, PageData, PageHash, PageId,
+		ResolvedKeyPair, Update,
 	},
 	dsnp::{
-		dsnp_configs::{DsnpVersionConfig, KeyPairType},
+		chain_adapter::{default_chain_adapter, ChainAdapter, SharedChainAdapter},
+		dsnp_configs::{DsnpVersionConfig, KeyPairType, PublicKeyType},
 		dsnp_types::{DsnpGraphEdge, DsnpPrid, DsnpPublicKey, DsnpUserId},
 		reader_writer::DsnpWriter,
 	},
 	frequency::Frequency,
-	graph::page::{GraphPage, PrivatePageDataProvider, PublicPageDataProvider},
+	graph::page::{GraphPage, PrivatePageDataProvider},
 };
 use dryoc::keypair::StackKeyPair;
-use dsnp_graph_config::{ConnectionType, Environment, GraphKeyType, PrivacyType, SchemaId};
+use dsnp_graph_config::{
+	errors::{DsnpGraphError, DsnpGraphResult},
+	ConnectionType, Environment, GraphKeyType, InputValidation, PrivacyType, SchemaId,
+};
 use std::collections::BTreeMap;
 
+/// Builds the key data of an itemized key page from key pairs, where each key is stored at the
+/// index matching its position
+#[derive(Debug, Clone, Default)]
 pub struct KeyDataBuilder {
 	key_pairs: Vec<GraphKeyPair>,
 }
 
 impl KeyDataBuilder {
+	/// creates a builder without any keys
 	pub fn new() -> Self {
 		KeyDataBuilder { key_pairs: vec![] }
 	}
 
+	/// appends the public keys of the key pairs
 	pub fn with_key_pairs(mut self, key_pairs: &[GraphKeyPair]) -> Self {
 		self.key_pairs.extend_from_slice(key_pairs);
 		self
 	}
 
+	/// appends a newly generated key pair
 	pub fn with_generated_key(mut self) -> Self {
 		let raw_key_pair = StackKeyPair::gen();
 		self.key_pairs.extend_from_slice(&vec![GraphKeyPair {
@@ -39,10 +54,20 @@ impl KeyDataBuilder {
 		self
 	}
 
+	/// returns the appended key pairs
 	pub fn get_key_pairs(&self) -> &Vec<GraphKeyPair> {
 		&self.key_pairs
 	}
 
+	/// builds the key data after checking that each key pair is a valid key of its type
+	pub fn try_build(self) -> DsnpGraphResult<Vec<KeyData>> {
+		for pair in self.key_pairs.iter() {
+			let _: KeyPairType = pair.clone().try_into()?;
+		}
+		Ok(self.build())
+	}
+
+	/// builds the key data without validating the key pairs
 	pub fn build(self) -> Vec<KeyData> {
 		self.key_pairs
 			.iter()
@@ -59,6 +84,83 @@ impl KeyDataBuilder {
 	}
 }
 
+/// Builds validated `DsnpKeys` of a user from the items of their key page as read from chain
+///
+/// Checks that the user id is valid, the keys hash is set when there are keys, each item has a
+/// unique index and each content is a parsable and valid public key.
+#[derive(Debug, Clone)]
+pub struct DsnpKeysBuilder {
+	dsnp_user_id: DsnpUserId,
+	keys_hash: PageHash,
+	keys: Vec<KeyData>,
+	chain_adapter: SharedChainAdapter,
+}
+
+impl DsnpKeysBuilder {
+	/// creates a builder for the keys of a user with the content hash of their key page
+	pub fn new(dsnp_user_id: DsnpUserId, keys_hash: PageHash) -> Self {
+		Self { dsnp_user_id, keys_hash, keys: vec![], chain_adapter: default_chain_adapter() }
+	}
+
+	/// uses the provided chain adapter to parse and write key contents instead of `Frequency`
+	pub fn with_chain_adapter(mut self, chain_adapter: SharedChainAdapter) -> Self {
+		self.chain_adapter = chain_adapter;
+		self
+	}
+
+	/// appends an item of the key page with its raw content as stored on chain
+	pub fn with_item(mut self, index: u16, content: &[u8]) -> Self {
+		self.keys.push(KeyData { index, content: content.to_vec() });
+		self
+	}
+
+	/// appends the key data items
+	pub fn with_key_data(mut self, keys: &[KeyData]) -> Self {
+		self.keys.extend_from_slice(keys);
+		self
+	}
+
+	/// appends a raw public key at the index, which is written in the format of the chain
+	pub fn with_public_key(mut self, index: u16, public_key: &[u8]) -> DsnpGraphResult<Self> {
+		let content = self
+			.chain_adapter
+			.write_key(&DsnpPublicKey { key: public_key.to_vec(), key_id: Some(index.into()) })?;
+		self.keys.push(KeyData { index, content });
+		Ok(self)
+	}
+
+	/// builds the keys sorted by index after validating them
+	pub fn build(self) -> DsnpGraphResult<DsnpKeys> {
+		let mut keys = self.keys;
+		keys.sort_by_key(|key| key.index);
+		let dsnp_keys =
+			DsnpKeys { dsnp_user_id: self.dsnp_user_id, keys_hash: self.keys_hash, keys };
+		dsnp_keys.validate()?;
+		for key in dsnp_keys.keys.iter() {
+			parse_key_data(key, self.chain_adapter.as_ref())?;
+		}
+		Ok(dsnp_keys)
+	}
+}
+
+/// parses the content of key data into a valid public key with its key id set to its index
+pub fn parse_key_data(
+	key: &KeyData,
+	chain_adapter: &dyn ChainAdapter,
+) -> DsnpGraphResult<DsnpPublicKey> {
+	let invalid = |reason: String| {
+		DsnpGraphError::InvalidInput(format!("key at index {} {}", key.index, reason))
+	};
+	key.validate()?;
+	let mut public_key = chain_adapter
+		.read_key(&key.content)
+		.map_err(|e| invalid(format!("is not parsable: {}", e)))?;
+	let _: PublicKeyType =
+		(&public_key).try_into().map_err(|e| invalid(format!("is not valid: {}", e)))?;
+	public_key.key_id = Some(key.index.into());
+	Ok(public_key)
+}
+
 pub struct GraphPageBuilder {
 	connection_type: ConnectionType,
 	// using BTreeMap to keep the pages sorted
@@ -145,6 +247,8 @@ impl PageDataBuilder {
 		self
 	}
 
+	/// builds the page payloads, writing the connections of public pages in the order they are
+	/// provided so unsorted pages can be built as well
 	pub fn build(self) -> Vec<PageData> {
 		let dsnp_config: DsnpVersionConfig = (&self.resolved_key.key_pair).into();
 		self.page_builder
@@ -152,7 +256,7 @@ impl PageDataBuilder {
 			.iter()
 			.map(|page| match self.connection_type.privacy_type() {
 				PrivacyType::Public =>
-					page.to_public_page_data().expect("should write public page"),
+					page.to_public_page(&Frequency, true).expect("should write public page"),
 				PrivacyType::Private => page
 					.to_private_page_data(&dsnp_config, &self.resolved_key)
 					.expect("should write private page"),
@@ -168,7 +272,7 @@ impl PageDataBuilder {
 			.map(|page| match self.connection_type.privacy_type() {
 				PrivacyType::Public => (
 					page.connections().len(),
-					page.to_public_page_data().expect("should write public page"),
+					page.to_public_page(&Frequency, true).expect("should write public page"),
 				),
 				PrivacyType::Private => (
 					page.connections().len(),
@@ -186,6 +290,7 @@ pub struct ImportBundleBuilder {
 	schema_id: SchemaId,
 	key_builder: KeyDataBuilder,
 	page_data_builder: PageDataBuilder,
+	chain_head: Option<ChainHead>,
 }
 
 impl ImportBundleBuilder {
@@ -200,6 +305,7 @@ impl ImportBundleBuilder {
 			schema_id,
 			key_builder: KeyDataBuilder::new(),
 			page_data_builder: PageDataBuilder::new(connection_type),
+			chain_head: None,
 		}
 	}
 
@@ -225,6 +331,11 @@ impl ImportBundleBuilder {
 		self
 	}
 
+	pub fn with_chain_head(mut self, block_number: u32, block_hash: &[u8]) -> Self {
+		self.chain_head = Some(ChainHead { block_number, block_hash: block_hash.to_vec() });
+		self
+	}
+
 	pub fn build(self) -> ImportBundle {
 		let key_pairs = self.key_builder.get_key_pairs().clone();
 		let pages: Vec<PageData> = self.page_data_builder.build();
@@ -243,6 +354,7 @@ impl ImportBundleBuilder {
 			schema_id: self.schema_id,
 			key_pairs,
 			pages,
+			chain_head: self.chain_head,
 		}
 	}
 
@@ -256,6 +368,7 @@ impl ImportBundleBuilder {
 					owner_dsnp_user_id,
 					payload,
 					prev_hash,
+					..
 				} => {
 					if *owner_dsnp_user_id != new_bundle.dsnp_user_id ||
 						*schema_id != new_bundle.schema_id
@@ -276,7 +389,9 @@ impl ImportBundleBuilder {
 						},
 					}
 				},
-				Update::DeletePage { page_id, prev_hash, schema_id, owner_dsnp_user_id } => {
+				Update::DeletePage {
+					page_id, prev_hash, schema_id, owner_dsnp_user_id, ..
+				} => {
 					if *owner_dsnp_user_id != new_bundle.dsnp_user_id ||
 						*schema_id != new_bundle.schema_id
 					{
@@ -291,7 +406,7 @@ impl ImportBundleBuilder {
 					assert_eq!(old_page.content_hash, *prev_hash);
 					new_bundle.pages.remove(ind);
 				},
-				Update::AddKey { prev_hash, payload, owner_dsnp_user_id } => {
+				Update::AddKey { prev_hash, payload, owner_dsnp_user_id, .. } => {
 					if *owner_dsnp_user_id != new_bundle.dsnp_user_id {
 						continue
 					}
@@ -323,3 +438,62 @@ impl ImportBundleBuilder {
 		new_bundle
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn dsnp_keys_builder_should_build_sorted_and_parsable_keys() {
+		// arrange
+		let key_data = KeyDataBuilder::new()
+			.with_generated_key()
+			.with_generated_key()
+			.try_build()
+			.expect("should build");
+		let public_key = StackKeyPair::gen().public_key.to_vec();
+
+		// act
+		let dsnp_keys = DsnpKeysBuilder::new(10, 100)
+			.with_item(key_data[1].index, &key_data[1].content)
+			.with_item(key_data[0].index, &key_data[0].content)
+			.with_public_key(2, &public_key)
+			.expect("should write")
+			.build();
+
+		// assert
+		let dsnp_keys = dsnp_keys.expect("should build");
+		assert_eq!(dsnp_keys.keys.iter().map(|k| k.index).collect::<Vec<_>>(), vec![0, 1, 2]);
+		let parsed = parse_key_data(&dsnp_keys.keys[2], &Frequency).expect("should parse");
+		assert_eq!(parsed, DsnpPublicKey { key: public_key, key_id: Some(2) });
+	}
+
+	#[test]
+	fn dsnp_keys_builder_should_reject_invalid_keys() {
+		// arrange
+		let key_data = KeyDataBuilder::new().with_generated_key().build();
+		let builder = DsnpKeysBuilder::new(10, 100).with_key_data(&key_data);
+
+		// act
+		let duplicated_index = builder.clone().with_item(0, &key_data[0].content).build();
+		let unparsable = builder.clone().with_item(1, &[1, 2, 3]).build();
+		let invalid_key =
+			builder.clone().with_public_key(1, &[1, 2, 3]).expect("should write").build();
+		let missing_hash = DsnpKeysBuilder::new(10, 0).with_key_data(&key_data).build();
+		let invalid_pair = KeyDataBuilder::new()
+			.with_key_pairs(&[GraphKeyPair {
+				key_type: GraphKeyType::X25519,
+				public_key: vec![1, 2, 3],
+				secret_key: vec![4, 5, 6],
+			}])
+			.try_build();
+
+		// assert
+		assert!(builder.build().is_ok());
+		assert!(matches!(duplicated_index, Err(DsnpGraphError::InvalidInput(_))));
+		assert!(matches!(unparsable, Err(DsnpGraphError::InvalidInput(_))));
+		assert!(matches!(invalid_key, Err(DsnpGraphError::InvalidInput(_))));
+		assert!(matches!(missing_hash, Err(DsnpGraphError::InvalidInput(_))));
+		assert!(invalid_pair.is_err());
+	}
+}
@@ -0,0 +1,146 @@
+//! Helpers to keep persisted graph state encrypted at rest
+use dryoc::{
+	constants::{
+		CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES,
+		CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES,
+		CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_KEYBYTES,
+	},
+	dryocstream::{DryocStream, Header, Key, Tag},
+	types::NewByteArray,
+};
+use dsnp_graph_config::errors::{DsnpGraphError, DsnpGraphResult};
+use log::Level;
+use log_result_proc_macro::log_result_err;
+use std::{fs, path::Path};
+
+/// Encrypts and decrypts serialized state using XChaCha20-Poly1305 with a caller provided key,
+/// so that graph data never gets persisted in plaintext.
+///
+/// The encrypted payload is the stream header followed by a single authenticated message.
+pub struct EncryptedStateStore {
+	/// Secret key used for encryption, zeroed out on drop
+	key: Key,
+}
+
+impl EncryptedStateStore {
+	/// Size of the secret key in bytes
+	pub const KEY_LEN: usize = CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_KEYBYTES;
+
+	/// Creates a new store using the provided secret key which should be `KEY_LEN` bytes
+	pub fn new(key: &[u8]) -> DsnpGraphResult<Self> {
+		let key = Key::try_from(key).map_err(|_| {
+			DsnpGraphError::InvalidInput(format!(
+				"Invalid state encryption key length, expected {} bytes",
+				Self::KEY_LEN
+			))
+		})?;
+		Ok(Self { key })
+	}
+
+	/// Generates a new random secret key
+	pub fn generate_key() -> Vec<u8> {
+		Key::gen().to_vec()
+	}
+
+	/// Encrypts the serialized state
+	#[log_result_err(Level::Info)]
+	pub fn encrypt(&self, plain_data: &[u8]) -> DsnpGraphResult<Vec<u8>> {
+		let (mut stream, header): (_, Header) = DryocStream::init_push(&self.key);
+		let encrypted = stream
+			.push_to_vec(&plain_data, None, Tag::FINAL)
+			.map_err(|e| DsnpGraphError::EncryptionError(e.to_string()))?;
+
+		let mut result = header.to_vec();
+		result.extend_from_slice(&encrypted);
+		Ok(result)
+	}
+
+	/// Decrypts the state previously encrypted using the same key
+	#[log_result_err(Level::Info)]
+	pub fn decrypt(&self, encrypted_data: &[u8]) -> DsnpGraphResult<Vec<u8>> {
+		if encrypted_data.len() <
+			CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES +
+				CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_ABYTES
+		{
+			return Err(DsnpGraphError::DecryptionError("Encrypted state is too short".to_string()))
+		}
+
+		let (header, encrypted) =
+			encrypted_data.split_at(CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES);
+		let header =
+			Header::try_from(header).map_err(|e| DsnpGraphError::DecryptionError(e.to_string()))?;
+		let mut stream = DryocStream::init_pull(&self.key, &header);
+		let (plain_data, tag) = stream
+			.pull_to_vec(&encrypted, None)
+			.map_err(|e| DsnpGraphError::DecryptionError(e.to_string()))?;
+		if tag != Tag::FINAL {
+			return Err(DsnpGraphError::DecryptionError("Encrypted state is truncated".to_string()))
+		}
+		Ok(plain_data)
+	}
+
+	/// Encrypts the serialized state and writes it into the file at `path`
+	#[log_result_err(Level::Info)]
+	pub fn save(&self, path: impl AsRef<Path>, plain_data: &[u8]) -> DsnpGraphResult<()> {
+		let encrypted = self.encrypt(plain_data)?;
+		fs::write(path, encrypted).map_err(|e| DsnpGraphError::Unknown(e.into()))
+	}
+
+	/// Reads the file at `path` and decrypts the serialized state
+	#[log_result_err(Level::Info)]
+	pub fn load(&self, path: impl AsRef<Path>) -> DsnpGraphResult<Vec<u8>> {
+		let encrypted = fs::read(path).map_err(|e| DsnpGraphError::Unknown(e.into()))?;
+		self.decrypt(&encrypted)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn encrypted_state_store_should_encrypt_and_decrypt() {
+		let store = EncryptedStateStore::new(&EncryptedStateStore::generate_key()).unwrap();
+		let data = b"serialized graph state".to_vec();
+
+		let encrypted = store.encrypt(&data).unwrap();
+		let decrypted = store.decrypt(&encrypted).unwrap();
+
+		assert_ne!(encrypted, data);
+		assert_eq!(decrypted, data);
+	}
+
+	#[test]
+	fn encrypted_state_store_with_wrong_key_should_fail() {
+		let store = EncryptedStateStore::new(&EncryptedStateStore::generate_key()).unwrap();
+		let other_store = EncryptedStateStore::new(&EncryptedStateStore::generate_key()).unwrap();
+
+		let encrypted = store.encrypt(b"serialized graph state").unwrap();
+
+		assert!(matches!(other_store.decrypt(&encrypted), Err(DsnpGraphError::DecryptionError(_))));
+		assert!(matches!(store.decrypt(&encrypted[..10]), Err(DsnpGraphError::DecryptionError(_))));
+	}
+
+	#[test]
+	fn encrypted_state_store_with_invalid_key_length_should_fail() {
+		assert!(matches!(
+			EncryptedStateStore::new(&[1u8; 16]),
+			Err(DsnpGraphError::InvalidInput(_))
+		));
+	}
+
+	#[test]
+	fn encrypted_state_store_should_save_and_load_files() {
+		let store = EncryptedStateStore::new(&EncryptedStateStore::generate_key()).unwrap();
+		let path = std::env::temp_dir().join("encrypted_state_store_test.bin");
+		let data = b"serialized graph state".to_vec();
+
+		store.save(&path, &data).unwrap();
+		let on_disk = fs::read(&path).unwrap();
+		let loaded = store.load(&path).unwrap();
+		let _ = fs::remove_file(&path);
+
+		assert!(!on_disk.windows(data.len()).any(|w| w == data.as_slice()));
+		assert_eq!(loaded, data);
+	}
+}
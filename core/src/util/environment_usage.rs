@@ -0,0 +1,74 @@
+//! Tracks the environments of the graph states alive in this process
+use dsnp_graph_config::Environment;
+use lazy_static::lazy_static;
+use std::{collections::BTreeMap, sync::Mutex};
+
+lazy_static! {
+	/// Number of alive graph states by environment name
+	static ref ENVIRONMENTS_IN_USE: Mutex<BTreeMap<String, usize>> = Mutex::new(BTreeMap::new());
+}
+
+/// Marks an environment as in use for as long as this guard is alive
+#[derive(Debug)]
+pub struct EnvironmentUsage {
+	name: String,
+}
+
+impl EnvironmentUsage {
+	/// starts using the environment
+	pub fn new(environment: &Environment) -> Self {
+		let name = environment.name().to_string();
+		// the counters are always consistent, so a poisoned lock can still be used
+		*ENVIRONMENTS_IN_USE
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.entry(name.clone())
+			.or_default() += 1;
+		Self { name }
+	}
+}
+
+impl Drop for EnvironmentUsage {
+	fn drop(&mut self) {
+		let mut environments = ENVIRONMENTS_IN_USE.lock().unwrap_or_else(|e| e.into_inner());
+		if let Some(count) = environments.get_mut(&self.name) {
+			*count -= 1;
+			if *count == 0 {
+				environments.remove(&self.name);
+			}
+		}
+	}
+}
+
+/// Returns the number of alive graph states by the name of their environment
+pub fn environments_in_use() -> BTreeMap<String, usize> {
+	ENVIRONMENTS_IN_USE.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use dsnp_graph_config::registry::EnvironmentRegistry;
+
+	#[test]
+	fn environment_usage_should_count_alive_guards() {
+		// arrange
+		let name = "environment-usage-test";
+		EnvironmentRegistry::register(name, Environment::Mainnet.get_config().clone())
+			.expect("should register");
+		let environment = EnvironmentRegistry::environment(name).expect("should exist");
+
+		// act
+		let first = EnvironmentUsage::new(&environment);
+		let second = EnvironmentUsage::new(&environment);
+		let both = environments_in_use();
+		drop(first);
+		let one = environments_in_use();
+		drop(second);
+
+		// assert
+		assert_eq!(both.get(name), Some(&2));
+		assert_eq!(one.get(name), Some(&1));
+		assert_eq!(environments_in_use().get(name), None);
+	}
+}
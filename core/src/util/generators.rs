@@ -0,0 +1,134 @@
+//! Generators of synthetic graph data used by benchmarks and downstream load tests
+use crate::{
+	api::api_types::{Action, Connection, GraphKeyPair, ImportBundle, PageId, ResolvedKeyPair},
+	dsnp::{
+		dsnp_configs::KeyPairType,
+		dsnp_types::{DsnpPrid, DsnpUserId},
+	},
+	graph::page_capacities::PAGE_CAPACITY_MAP,
+	util::{builders::ImportBundleBuilder, time::time_in_ksecs},
+};
+use dryoc::keypair::StackKeyPair;
+use dsnp_graph_config::{ConnectionType, Environment, GraphKeyType, PrivacyType, SchemaId};
+use rand::{thread_rng, Rng};
+
+/// Smallest page capacity, used if the connection type does not have a calculated capacity
+const DEFAULT_PAGE_CAPACITY: usize = 49;
+
+/// Generates a new random graph key pair
+pub fn generate_key_pair() -> GraphKeyPair {
+	let key_pair = StackKeyPair::gen();
+	GraphKeyPair {
+		key_type: GraphKeyType::X25519,
+		secret_key: key_pair.secret_key.to_vec(),
+		public_key: key_pair.public_key.to_vec(),
+	}
+}
+
+/// Generates `count` connections with consecutive ids starting from `start_id`, all
+/// created at current time
+pub fn generate_connections(start_id: DsnpUserId, count: usize) -> Vec<(DsnpUserId, u64)> {
+	let since = time_in_ksecs();
+	(start_id..start_id + count as u64).map(|id| (id, since)).collect()
+}
+
+/// Generates an import bundle containing provided connections split into pages of maximum
+/// capacity for the connection type of the schema.
+///
+/// `key_pair` is required for private graphs and is used as the only imported key
+pub fn generate_import_bundle(
+	env: &Environment,
+	dsnp_user_id: DsnpUserId,
+	schema_id: SchemaId,
+	connections: &[(DsnpUserId, u64)],
+	key_pair: Option<&GraphKeyPair>,
+) -> ImportBundle {
+	let connection_type = env
+		.get_config()
+		.get_connection_type_from_schema_id(schema_id)
+		.unwrap_or(ConnectionType::Follow(PrivacyType::Public));
+	let capacity = *PAGE_CAPACITY_MAP.get(&connection_type).unwrap_or(&DEFAULT_PAGE_CAPACITY);
+
+	let mut builder = ImportBundleBuilder::new(env.clone(), dsnp_user_id, schema_id);
+	if let Some(key_pair) = key_pair {
+		let resolved_key_pair: KeyPairType =
+			key_pair.clone().try_into().expect("generated key pair should be valid");
+		builder = builder
+			.with_key_pairs(&[key_pair.clone()])
+			.with_encryption_key(ResolvedKeyPair { key_id: 0, key_pair: resolved_key_pair });
+	}
+
+	let mut rng = thread_rng();
+	for (page_id, chunk) in connections.chunks(capacity).enumerate() {
+		let prids: Vec<DsnpPrid> = match connection_type {
+			ConnectionType::Friendship(PrivacyType::Private) =>
+				chunk.iter().map(|_| DsnpPrid::new(&rng.gen::<[u8; 8]>())).collect(),
+			_ => vec![],
+		};
+		builder = builder.with_page(page_id as PageId, chunk, &prids, page_id as u32 + 1);
+	}
+	builder.build()
+}
+
+/// Generates key only import bundles, publishing a new random key for each of the users
+pub fn generate_public_key_bundles(
+	env: &Environment,
+	dsnp_user_ids: &[DsnpUserId],
+) -> Vec<ImportBundle> {
+	let schema_id = env.get_config().graph_public_key_schema_id;
+	dsnp_user_ids
+		.iter()
+		.map(|dsnp_user_id| {
+			ImportBundleBuilder::new(env.clone(), *dsnp_user_id, schema_id)
+				.with_key_pairs(&[generate_key_pair()])
+				.build()
+		})
+		.collect()
+}
+
+/// Generates `Connect` actions from owner to each of the provided users
+pub fn generate_connect_actions(
+	owner_dsnp_user_id: DsnpUserId,
+	schema_id: SchemaId,
+	dsnp_user_ids: &[DsnpUserId],
+) -> Vec<Action> {
+	dsnp_user_ids
+		.iter()
+		.map(|dsnp_user_id| Action::Connect {
+			owner_dsnp_user_id,
+			connection: Connection { dsnp_user_id: *dsnp_user_id, schema_id },
+			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::api::api::{GraphAPI, GraphState};
+
+	#[test]
+	fn generated_private_friendship_bundle_should_be_importable() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = env
+			.get_config()
+			.get_schema_id_from_connection_type(ConnectionType::Friendship(PrivacyType::Private))
+			.expect("should exist");
+		let key_pair = generate_key_pair();
+		let connections = generate_connections(100, 120);
+		let bundle = generate_import_bundle(&env, 1, schema_id, &connections, Some(&key_pair));
+		let mut state = GraphState::new(env);
+
+		// act
+		let res = state.import_users_data(&vec![bundle.clone()]);
+
+		// assert
+		assert!(res.is_ok());
+		assert_eq!(bundle.pages.len(), 3);
+		let imported = state.get_connections_for_user_graph(&1, &schema_id, false).unwrap();
+		assert_eq!(imported.len(), connections.len());
+	}
+}
@@ -0,0 +1,102 @@
+//! HMAC and HKDF (RFC 5869) using SHA-512, built on top of the hash function provided by dryoc
+//!
+//! These are implemented here rather than pulled in from the `hmac` and `hkdf` crates to avoid
+//! adding a second SHA-512 implementation next to the one dryoc already uses for sealing. Every
+//! buffer derived from the key material is wrapped in `Zeroizing` so it is wiped once dropped.
+use dryoc::{
+	classic::crypto_hash::{
+		crypto_hash_sha512, crypto_hash_sha512_final, crypto_hash_sha512_init,
+		crypto_hash_sha512_update, Digest,
+	},
+	constants::CRYPTO_HASH_SHA512_BYTES,
+};
+use zeroize::Zeroizing;
+
+/// Block size of SHA-512 in bytes
+const SHA512_BLOCK_BYTES: usize = 128;
+
+/// Output size of HMAC-SHA512 in bytes
+pub const HMAC_SHA512_BYTES: usize = CRYPTO_HASH_SHA512_BYTES;
+
+/// calculates HMAC-SHA512 of the data using the provided key
+pub fn hmac_sha512(key: &[u8], data: &[u8]) -> Zeroizing<[u8; HMAC_SHA512_BYTES]> {
+	let mut block_key = Zeroizing::new([0u8; SHA512_BLOCK_BYTES]);
+	if key.len() > SHA512_BLOCK_BYTES {
+		let mut hashed_key: Zeroizing<Digest> = Zeroizing::new([0u8; HMAC_SHA512_BYTES]);
+		crypto_hash_sha512(&mut hashed_key, key);
+		block_key[..HMAC_SHA512_BYTES].copy_from_slice(hashed_key.as_ref());
+	} else {
+		block_key[..key.len()].copy_from_slice(key);
+	}
+
+	let pad = |value: u8| Zeroizing::new(block_key.map(|b| b ^ value));
+	let mut inner = crypto_hash_sha512_init();
+	crypto_hash_sha512_update(&mut inner, pad(0x36).as_ref());
+	crypto_hash_sha512_update(&mut inner, data);
+	let mut inner_digest: Zeroizing<Digest> = Zeroizing::new([0u8; HMAC_SHA512_BYTES]);
+	crypto_hash_sha512_final(inner, &mut inner_digest);
+
+	let mut outer = crypto_hash_sha512_init();
+	crypto_hash_sha512_update(&mut outer, pad(0x5c).as_ref());
+	crypto_hash_sha512_update(&mut outer, inner_digest.as_ref());
+	let mut result: Zeroizing<Digest> = Zeroizing::new([0u8; HMAC_SHA512_BYTES]);
+	crypto_hash_sha512_final(outer, &mut result);
+	result
+}
+
+/// derives `output.len()` bytes of key material from the input key material using HKDF-SHA512,
+/// returns `None` if the requested length is more than `255 * HMAC_SHA512_BYTES`
+pub fn hkdf_sha512(salt: &[u8], ikm: &[u8], info: &[u8], output: &mut [u8]) -> Option<()> {
+	if output.len() > 255 * HMAC_SHA512_BYTES {
+		return None
+	}
+
+	// extract
+	let prk = hmac_sha512(salt, ikm);
+
+	// expand
+	let mut previous: Option<Zeroizing<[u8; HMAC_SHA512_BYTES]>> = None;
+	for (index, chunk) in output.chunks_mut(HMAC_SHA512_BYTES).enumerate() {
+		let mut data = Zeroizing::new(Vec::with_capacity(HMAC_SHA512_BYTES + info.len() + 1));
+		if let Some(previous) = &previous {
+			data.extend_from_slice(previous.as_ref());
+		}
+		data.extend_from_slice(info);
+		data.push(index as u8 + 1);
+		let block = hmac_sha512(prk.as_ref(), &data);
+		chunk.copy_from_slice(&block[..chunk.len()]);
+		previous = Some(block);
+	}
+	Some(())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn hmac_sha512_should_match_rfc_4231_vectors() {
+		let expected = hex::decode(
+			"164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+			 9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+		)
+		.unwrap();
+
+		assert_eq!(hmac_sha512(b"Jefe", b"what do ya want for nothing?").to_vec(), expected);
+	}
+
+	#[test]
+	fn hkdf_sha512_should_expand_with_chained_blocks() {
+		let mut output = [0u8; 100];
+		hkdf_sha512(b"salt", b"input key material", b"info", &mut output).unwrap();
+
+		let prk = hmac_sha512(b"salt", b"input key material");
+		let first = hmac_sha512(prk.as_ref(), b"info\x01");
+		let second = hmac_sha512(prk.as_ref(), &[&first[..], b"info\x02"].concat());
+		assert_eq!(output[..64], first[..]);
+		assert_eq!(output[64..], second[..36]);
+
+		let mut too_long = vec![0u8; 255 * HMAC_SHA512_BYTES + 1];
+		assert_eq!(hkdf_sha512(b"salt", b"ikm", b"info", &mut too_long), None);
+	}
+}
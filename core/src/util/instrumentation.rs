@@ -0,0 +1,118 @@
+//! Opt-in collector of operation durations which can be reported by hosts in their telemetry
+use crate::{
+	api::api_types::{GraphMetrics, GraphOperation, OperationTimings},
+	dsnp::dsnp_types::DsnpUserId,
+};
+use std::{collections::HashMap, time::Duration};
+
+/// Records the durations of timed operations per user
+#[derive(Debug, Default)]
+pub struct Instrumentation {
+	/// recorded durations of each operation of each user
+	samples: HashMap<(DsnpUserId, GraphOperation), Vec<Duration>>,
+}
+
+impl Instrumentation {
+	/// records the duration of an operation executed on the graph of a user
+	pub fn record(&mut self, user_id: DsnpUserId, operation: GraphOperation, duration: Duration) {
+		self.samples.entry((user_id, operation)).or_default().push(duration);
+	}
+
+	/// returns true if no durations are recorded
+	pub fn is_empty(&self) -> bool {
+		self.samples.is_empty()
+	}
+
+	/// aggregates the recorded durations and clears them
+	pub fn take_metrics(&mut self) -> GraphMetrics {
+		let mut metrics = GraphMetrics::default();
+		let mut all_samples: HashMap<GraphOperation, Vec<Duration>> = HashMap::new();
+		for ((user_id, operation), mut samples) in self.samples.drain() {
+			all_samples.entry(operation).or_default().extend_from_slice(&samples);
+			metrics
+				.users
+				.entry(user_id)
+				.or_default()
+				.insert(operation, summarize(&mut samples));
+		}
+		for (operation, mut samples) in all_samples {
+			metrics.operations.insert(operation, summarize(&mut samples));
+		}
+		metrics
+	}
+}
+
+/// calculates the timings of the durations using nearest rank percentiles
+fn summarize(samples: &mut [Duration]) -> OperationTimings {
+	if samples.is_empty() {
+		return OperationTimings::default()
+	}
+
+	samples.sort_unstable();
+	let micros = |duration: &Duration| u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+	let percentile = |p: usize| {
+		let rank = (samples.len() * p + 99) / 100;
+		micros(&samples[rank.saturating_sub(1)])
+	};
+	OperationTimings {
+		count: samples.len(),
+		total_micros: samples.iter().map(micros).fold(0u64, |a, b| a.saturating_add(b)),
+		min_micros: micros(&samples[0]),
+		max_micros: micros(&samples[samples.len() - 1]),
+		p50_micros: percentile(50),
+		p90_micros: percentile(90),
+		p99_micros: percentile(99),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn take_metrics_should_aggregate_percentiles_per_user_and_operation() {
+		// arrange
+		let mut instrumentation = Instrumentation::default();
+		for micros in 1..=100 {
+			instrumentation.record(1, GraphOperation::Import, Duration::from_micros(micros));
+		}
+		instrumentation.record(2, GraphOperation::Import, Duration::from_micros(1_000));
+		instrumentation.record(2, GraphOperation::Export, Duration::from_micros(7));
+
+		// act
+		let metrics = instrumentation.take_metrics();
+
+		// assert
+		let user_import = metrics.users[&1][&GraphOperation::Import];
+		assert_eq!(user_import.count, 100);
+		assert_eq!(user_import.total_micros, 5_050);
+		assert_eq!(user_import.min_micros, 1);
+		assert_eq!(user_import.max_micros, 100);
+		assert_eq!(user_import.p50_micros, 50);
+		assert_eq!(user_import.p90_micros, 90);
+		assert_eq!(user_import.p99_micros, 99);
+
+		let import = metrics.operations[&GraphOperation::Import];
+		assert_eq!(import.count, 101);
+		assert_eq!(import.max_micros, 1_000);
+		assert_eq!(import.p99_micros, 100);
+		assert_eq!(metrics.operations[&GraphOperation::Export].p50_micros, 7);
+		assert!(!metrics.users[&1].contains_key(&GraphOperation::Export));
+	}
+
+	#[test]
+	fn take_metrics_should_clear_recorded_durations() {
+		// arrange
+		let mut instrumentation = Instrumentation::default();
+		instrumentation.record(1, GraphOperation::ApplyAction, Duration::from_millis(1));
+
+		// act
+		let first = instrumentation.take_metrics();
+		let second = instrumentation.take_metrics();
+
+		// assert
+		assert_eq!(first.operations[&GraphOperation::ApplyAction].count, 1);
+		assert!(instrumentation.is_empty());
+		assert_eq!(second, GraphMetrics::default());
+	}
+}
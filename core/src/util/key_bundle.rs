@@ -0,0 +1,288 @@
+//! Passphrase protected bundles of graph key pairs used to migrate keys between devices
+//!
+//! A bundle is laid out as
+//! - version (1 byte)
+//! - argon2id operations and memory limits (4 bytes each, little endian)
+//! - argon2id salt
+//! - XChaCha20-Poly1305 stream header
+//! - encrypted content
+//!
+//! Changing the limits, salt or header results in a different key or nonce, so the content fails
+//! to authenticate instead of being decrypted with weakened parameters.
+//!
+//! The content is the dsnp user id followed by the key pairs, each one being its key type and
+//! the length prefixed public and secret keys.
+//!
+//! The key derived from the passphrase and the plaintext content hold secret material, so they
+//! are wrapped in `Zeroizing` and wiped once dropped.
+use crate::{api::api_types::GraphKeyPair, dsnp::dsnp_types::DsnpUserId};
+use dryoc::{
+	classic::crypto_pwhash::{crypto_pwhash, PasswordHashAlgorithm},
+	constants::{
+		CRYPTO_PWHASH_MEMLIMIT_INTERACTIVE, CRYPTO_PWHASH_MEMLIMIT_MIN,
+		CRYPTO_PWHASH_MEMLIMIT_MODERATE, CRYPTO_PWHASH_OPSLIMIT_INTERACTIVE,
+		CRYPTO_PWHASH_OPSLIMIT_MIN, CRYPTO_PWHASH_OPSLIMIT_MODERATE, CRYPTO_PWHASH_SALTBYTES,
+		CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES,
+		CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_KEYBYTES,
+	},
+	dryocstream::{DryocStream, Header, Key, Tag},
+	rng::randombytes_buf,
+};
+use dsnp_graph_config::{
+	errors::{DsnpGraphError, DsnpGraphResult},
+	GraphKeyType,
+};
+use zeroize::Zeroizing;
+
+/// Current version of the bundle layout
+pub const KEY_BUNDLE_VERSION: u8 = 1;
+
+/// Size of the bytes preceding the stream header
+const PARAMS_LEN: usize = 1 + 4 + 4 + CRYPTO_PWHASH_SALTBYTES;
+
+/// Size of the bytes preceding the encrypted content
+const PREFIX_LEN: usize = PARAMS_LEN + CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_HEADERBYTES;
+
+/// Argon2id limits used to derive the encryption key of a bundle from the passphrase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBundleLimits {
+	/// number of passes over the memory
+	pub opslimit: u32,
+	/// size of the memory in bytes
+	pub memlimit: u32,
+}
+
+impl Default for KeyBundleLimits {
+	fn default() -> Self {
+		Self {
+			opslimit: CRYPTO_PWHASH_OPSLIMIT_INTERACTIVE as u32,
+			memlimit: CRYPTO_PWHASH_MEMLIMIT_INTERACTIVE as u32,
+		}
+	}
+}
+
+impl KeyBundleLimits {
+	/// returns true if the limits are in the range accepted when opening a bundle, which prevents
+	/// crafted bundles from using excessive time or memory
+	fn is_supported(&self) -> bool {
+		(CRYPTO_PWHASH_OPSLIMIT_MIN..=CRYPTO_PWHASH_OPSLIMIT_MODERATE)
+			.contains(&(self.opslimit as u64)) &&
+			(CRYPTO_PWHASH_MEMLIMIT_MIN..=CRYPTO_PWHASH_MEMLIMIT_MODERATE)
+				.contains(&(self.memlimit as usize))
+	}
+}
+
+/// Encrypts the key pairs of a user into a bundle using a key derived from the passphrase
+pub fn seal_key_bundle(
+	dsnp_user_id: DsnpUserId,
+	key_pairs: &[GraphKeyPair],
+	passphrase: &str,
+	limits: KeyBundleLimits,
+) -> DsnpGraphResult<Vec<u8>> {
+	if !limits.is_supported() {
+		return Err(DsnpGraphError::InvalidInput("Unsupported key bundle limits".to_string()))
+	}
+
+	let mut bundle = vec![KEY_BUNDLE_VERSION];
+	bundle.extend_from_slice(&limits.opslimit.to_le_bytes());
+	bundle.extend_from_slice(&limits.memlimit.to_le_bytes());
+	bundle.extend_from_slice(&randombytes_buf(CRYPTO_PWHASH_SALTBYTES));
+
+	let key = derive_key(passphrase, &bundle[PARAMS_LEN - CRYPTO_PWHASH_SALTBYTES..], limits)?;
+	let (mut stream, header): (_, Header) = DryocStream::init_push(&key);
+	bundle.extend_from_slice(&header);
+
+	let content = Zeroizing::new(encode_content(dsnp_user_id, key_pairs)?);
+	let encrypted = stream
+		.push_to_vec(&*content, None, Tag::FINAL)
+		.map_err(|e| DsnpGraphError::EncryptionError(e.to_string()))?;
+	bundle.extend_from_slice(&encrypted);
+	Ok(bundle)
+}
+
+/// Decrypts a bundle created by `seal_key_bundle`, returning the user and its key pairs
+pub fn open_key_bundle(
+	bundle: &[u8],
+	passphrase: &str,
+) -> DsnpGraphResult<(DsnpUserId, Vec<GraphKeyPair>)> {
+	if bundle.len() < PREFIX_LEN {
+		return Err(DsnpGraphError::InvalidKeyBundle("bundle is too short".to_string()))
+	}
+	if bundle[0] != KEY_BUNDLE_VERSION {
+		return Err(DsnpGraphError::InvalidKeyBundle(format!("unsupported version {}", bundle[0])))
+	}
+	let limits = KeyBundleLimits {
+		opslimit: u32::from_le_bytes(bundle[1..5].try_into().unwrap()),
+		memlimit: u32::from_le_bytes(bundle[5..9].try_into().unwrap()),
+	};
+	if !limits.is_supported() {
+		return Err(DsnpGraphError::InvalidKeyBundle("unsupported limits".to_string()))
+	}
+
+	let key = derive_key(passphrase, &bundle[9..PARAMS_LEN], limits)?;
+	let header = Header::try_from(&bundle[PARAMS_LEN..PREFIX_LEN])
+		.map_err(|e| DsnpGraphError::InvalidKeyBundle(e.to_string()))?;
+	let mut stream = DryocStream::init_pull(&key, &header);
+	let (content, tag) = stream
+		.pull_to_vec(&bundle[PREFIX_LEN..].to_vec(), None)
+		.map_err(|e| DsnpGraphError::DecryptionError(e.to_string()))?;
+	let content = Zeroizing::new(content);
+	if tag != Tag::FINAL {
+		return Err(DsnpGraphError::DecryptionError("Key bundle is truncated".to_string()))
+	}
+	decode_content(&content)
+}
+
+/// derives the stream key from the passphrase using argon2id
+fn derive_key(passphrase: &str, salt: &[u8], limits: KeyBundleLimits) -> DsnpGraphResult<Key> {
+	if passphrase.is_empty() {
+		return Err(DsnpGraphError::InvalidInput("Key bundle passphrase is empty".to_string()))
+	}
+
+	let mut key = Zeroizing::new([0u8; CRYPTO_SECRETSTREAM_XCHACHA20POLY1305_KEYBYTES]);
+	crypto_pwhash(
+		&mut *key,
+		passphrase.as_bytes(),
+		salt,
+		limits.opslimit as u64,
+		limits.memlimit as usize,
+		PasswordHashAlgorithm::Argon2id13,
+	)
+	.map_err(|e| DsnpGraphError::KeyDerivationError(e.to_string()))?;
+	Ok(Key::from(&*key))
+}
+
+/// serializes the user and its key pairs
+fn encode_content(
+	dsnp_user_id: DsnpUserId,
+	key_pairs: &[GraphKeyPair],
+) -> DsnpGraphResult<Vec<u8>> {
+	let encode_len = |len: usize| {
+		u16::try_from(len)
+			.map(u16::to_le_bytes)
+			.map_err(|_| DsnpGraphError::InvalidInput("Key pairs are too large".to_string()))
+	};
+	let mut content = dsnp_user_id.to_le_bytes().to_vec();
+	content.extend_from_slice(&encode_len(key_pairs.len())?);
+	for key_pair in key_pairs {
+		content.push(match key_pair.key_type {
+			GraphKeyType::X25519 => 0,
+		});
+		for key in [&key_pair.public_key, &key_pair.secret_key] {
+			content.extend_from_slice(&encode_len(key.len())?);
+			content.extend_from_slice(key);
+		}
+	}
+	Ok(content)
+}
+
+/// deserializes the user and its key pairs
+fn decode_content(content: &[u8]) -> DsnpGraphResult<(DsnpUserId, Vec<GraphKeyPair>)> {
+	let mut remaining = content;
+	let mut take = |len: usize| -> DsnpGraphResult<&[u8]> {
+		if remaining.len() < len {
+			return Err(DsnpGraphError::InvalidKeyBundle("content is truncated".to_string()))
+		}
+		let (taken, rest) = remaining.split_at(len);
+		remaining = rest;
+		Ok(taken)
+	};
+
+	let dsnp_user_id = DsnpUserId::from_le_bytes(take(8)?.try_into().unwrap());
+	let count = u16::from_le_bytes(take(2)?.try_into().unwrap());
+	let mut key_pairs = Vec::with_capacity(count as usize);
+	for _ in 0..count {
+		let key_type = match take(1)?[0] {
+			0 => GraphKeyType::X25519,
+			other =>
+				return Err(DsnpGraphError::InvalidKeyBundle(format!("unknown key type {}", other))),
+		};
+		let public_len = u16::from_le_bytes(take(2)?.try_into().unwrap());
+		let public_key = take(public_len as usize)?.to_vec();
+		let secret_len = u16::from_le_bytes(take(2)?.try_into().unwrap());
+		let secret_key = take(secret_len as usize)?.to_vec();
+		key_pairs.push(GraphKeyPair { key_type, public_key, secret_key });
+	}
+	if !remaining.is_empty() {
+		return Err(DsnpGraphError::InvalidKeyBundle("content has trailing bytes".to_string()))
+	}
+	Ok((dsnp_user_id, key_pairs))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use dryoc::keypair::StackKeyPair;
+
+	const TEST_LIMITS: KeyBundleLimits = KeyBundleLimits {
+		opslimit: CRYPTO_PWHASH_OPSLIMIT_MIN as u32,
+		memlimit: CRYPTO_PWHASH_MEMLIMIT_MIN as u32,
+	};
+
+	fn key_pairs() -> Vec<GraphKeyPair> {
+		(0..2)
+			.map(|_| {
+				let key_pair = StackKeyPair::gen();
+				GraphKeyPair {
+					key_type: GraphKeyType::X25519,
+					public_key: key_pair.public_key.to_vec(),
+					secret_key: key_pair.secret_key.to_vec(),
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn key_bundle_should_seal_and_open() {
+		let key_pairs = key_pairs();
+
+		let bundle = seal_key_bundle(7, &key_pairs, "correct horse", TEST_LIMITS).unwrap();
+		let opened = open_key_bundle(&bundle, "correct horse").unwrap();
+
+		assert_eq!(bundle[0], KEY_BUNDLE_VERSION);
+		assert!(!bundle.windows(32).any(|w| w == key_pairs[0].secret_key.as_slice()));
+		assert_eq!(opened, (7, key_pairs));
+	}
+
+	#[test]
+	fn key_bundle_with_wrong_passphrase_or_tampered_header_should_fail() {
+		let bundle = seal_key_bundle(7, &key_pairs(), "correct horse", TEST_LIMITS).unwrap();
+		let mut tampered = bundle.clone();
+		tampered[PARAMS_LEN - 1] ^= 1;
+
+		assert!(matches!(
+			open_key_bundle(&bundle, "battery staple"),
+			Err(DsnpGraphError::DecryptionError(_))
+		));
+		assert!(matches!(
+			open_key_bundle(&tampered, "correct horse"),
+			Err(DsnpGraphError::DecryptionError(_))
+		));
+	}
+
+	#[test]
+	fn key_bundle_with_invalid_layout_should_fail() {
+		let bundle = seal_key_bundle(7, &key_pairs(), "correct horse", TEST_LIMITS).unwrap();
+		let mut unknown_version = bundle.clone();
+		unknown_version[0] = KEY_BUNDLE_VERSION + 1;
+		let mut excessive_memory = bundle.clone();
+		excessive_memory[5..9].copy_from_slice(&u32::MAX.to_le_bytes());
+
+		assert!(matches!(
+			open_key_bundle(&unknown_version, "correct horse"),
+			Err(DsnpGraphError::InvalidKeyBundle(_))
+		));
+		assert!(matches!(
+			open_key_bundle(&excessive_memory, "correct horse"),
+			Err(DsnpGraphError::InvalidKeyBundle(_))
+		));
+		assert!(matches!(
+			open_key_bundle(&bundle[..PREFIX_LEN - 1], "correct horse"),
+			Err(DsnpGraphError::InvalidKeyBundle(_))
+		));
+		assert!(matches!(
+			seal_key_bundle(7, &key_pairs(), "", TEST_LIMITS),
+			Err(DsnpGraphError::InvalidInput(_))
+		));
+	}
+}
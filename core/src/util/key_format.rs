@@ -0,0 +1,105 @@
+//! Encodings used to exchange graph public keys with other DSNP tooling
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+/// Multicodec prefix of a X25519 public key (`x25519-pub` encoded as an unsigned varint)
+pub const X25519_PUB_MULTICODEC: [u8; 2] = [0xec, 0x01];
+
+/// Multibase prefix of base58btc encoded strings
+pub const BASE58_BTC_MULTIBASE_PREFIX: char = 'z';
+
+/// Bitcoin base58 alphabet
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// encodes the input using the bitcoin base58 alphabet
+pub fn encode_base58(input: &[u8]) -> String {
+	let leading_zeros = input.iter().take_while(|b| **b == 0).count();
+	// little endian base58 digits of the input without the leading zeros
+	let mut digits: Vec<u8> = Vec::with_capacity(input.len() * 138 / 100 + 1);
+	for byte in &input[leading_zeros..] {
+		let mut carry = *byte as u32;
+		for digit in digits.iter_mut() {
+			carry += (*digit as u32) << 8;
+			*digit = (carry % 58) as u8;
+			carry /= 58;
+		}
+		while carry > 0 {
+			digits.push((carry % 58) as u8);
+			carry /= 58;
+		}
+	}
+
+	std::iter::repeat(BASE58_ALPHABET[0] as char)
+		.take(leading_zeros)
+		.chain(digits.iter().rev().map(|d| BASE58_ALPHABET[*d as usize] as char))
+		.collect()
+}
+
+/// decodes a bitcoin base58 encoded string, returns `None` if it contains invalid characters
+pub fn decode_base58(input: &str) -> Option<Vec<u8>> {
+	let leading_zeros = input.bytes().take_while(|c| *c == BASE58_ALPHABET[0]).count();
+	// little endian bytes of the input without the leading zeros
+	let mut bytes: Vec<u8> = Vec::with_capacity(input.len() * 733 / 1000 + 1);
+	for c in input.bytes().skip(leading_zeros) {
+		let mut carry = BASE58_ALPHABET.iter().position(|a| *a == c)? as u32;
+		for byte in bytes.iter_mut() {
+			carry += (*byte as u32) * 58;
+			*byte = (carry & 0xff) as u8;
+			carry >>= 8;
+		}
+		while carry > 0 {
+			bytes.push((carry & 0xff) as u8);
+			carry >>= 8;
+		}
+	}
+
+	Some(
+		std::iter::repeat(0u8)
+			.take(leading_zeros)
+			.chain(bytes.into_iter().rev())
+			.collect(),
+	)
+}
+
+/// encodes the input using url safe base64 without padding, as used in JWKs
+pub fn encode_base64_url(input: &[u8]) -> String {
+	URL_SAFE_NO_PAD.encode(input)
+}
+
+/// decodes an url safe base64 string without padding, returns `None` if it is malformed
+pub fn decode_base64_url(input: &str) -> Option<Vec<u8>> {
+	URL_SAFE_NO_PAD.decode(input).ok()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn base58_should_match_reference_vectors() {
+		let vectors: [(&[u8], &str); 4] = [
+			(b"", ""),
+			(&[0, 0, 1], "112"),
+			(b"Hello World!", "2NEpo7TZRRrLZSi2U"),
+			(&[0x00, 0x00, 0x28, 0x7f, 0xb4, 0xcd], "11233QC4"),
+		];
+
+		for (raw, encoded) in vectors {
+			assert_eq!(encode_base58(raw), encoded);
+			assert_eq!(decode_base58(encoded), Some(raw.to_vec()));
+		}
+	}
+
+	#[test]
+	fn decode_base58_with_invalid_characters_should_fail() {
+		assert_eq!(decode_base58("0OIl"), None);
+	}
+
+	#[test]
+	fn base64_url_should_roundtrip_without_padding() {
+		let encoded = encode_base64_url(&[0xfb, 0xff, 0x01]);
+
+		assert_eq!(encoded, "-_8B");
+		assert_eq!(decode_base64_url(&encoded), Some(vec![0xfb, 0xff, 0x01]));
+		assert_eq!(decode_base64_url("-_8B=="), None);
+	}
+}
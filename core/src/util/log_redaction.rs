@@ -0,0 +1,152 @@
+//! Redaction of dsnp user ids in log output
+//!
+//! Some deployments consider dsnp user ids personal data. The redaction mode is global for the
+//! process since all graph states share the same logger, and is applied to the errors logged by
+//! `log_result_err` and to the warnings of the SDK. Returned errors are never redacted.
+use crate::{dsnp::dsnp_types::DsnpUserId, util::state_hash::StateHasher};
+use dsnp_graph_config::errors::DsnpGraphError;
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::HashSet,
+	fmt::Display,
+	sync::atomic::{AtomicU8, Ordering},
+};
+
+/// Number of leading digits kept by `RedactionMode::Truncate`
+const TRUNCATED_DIGITS: usize = 3;
+
+/// Number of hash bytes written by `RedactionMode::Hash`
+const HASHED_ID_BYTES: usize = 4;
+
+/// Domain separation of hashed ids
+const HASHED_ID_CONTEXT: &[u8] = b"dsnp-graph-sdk/log-redaction";
+
+static REDACTION_MODE: AtomicU8 = AtomicU8::new(RedactionMode::None as u8);
+
+/// How dsnp user ids are written in log output
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RedactionMode {
+	/// ids are logged as they are
+	#[default]
+	None,
+
+	/// ids are replaced by a short hash such as `#1a2b3c4d`, which still allows correlating the
+	/// log lines of the same user
+	Hash,
+
+	/// only the first digits of ids are kept such as `123...`
+	Truncate,
+}
+
+/// sets the redaction mode used by all graph states of this process
+pub fn set_redaction_mode(mode: RedactionMode) {
+	REDACTION_MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+/// returns the redaction mode used by all graph states of this process
+pub fn redaction_mode() -> RedactionMode {
+	match REDACTION_MODE.load(Ordering::Relaxed) {
+		x if x == RedactionMode::Hash as u8 => RedactionMode::Hash,
+		x if x == RedactionMode::Truncate as u8 => RedactionMode::Truncate,
+		_ => RedactionMode::None,
+	}
+}
+
+/// formats a dsnp user id for log output using the current redaction mode
+pub fn redact_id(dsnp_user_id: DsnpUserId) -> String {
+	redact_id_with_mode(dsnp_user_id, redaction_mode())
+}
+
+/// formats an error for log output, redacting the dsnp user ids it carries
+pub fn redact_error(error: &DsnpGraphError) -> String {
+	let mode = redaction_mode();
+	if mode == RedactionMode::None {
+		return error.to_string()
+	}
+	redact_ids_in(error, &error.dsnp_user_ids().into_iter().collect(), mode)
+}
+
+fn redact_id_with_mode(dsnp_user_id: DsnpUserId, mode: RedactionMode) -> String {
+	match mode {
+		RedactionMode::None => dsnp_user_id.to_string(),
+		RedactionMode::Hash => {
+			let mut hasher = StateHasher::new();
+			hasher.write_bytes(HASHED_ID_CONTEXT);
+			hasher.write_u64(dsnp_user_id);
+			let hash = hasher.finish();
+			let hex: String =
+				hash[..HASHED_ID_BYTES].iter().map(|byte| format!("{:02x}", byte)).collect();
+			format!("#{}", hex)
+		},
+		RedactionMode::Truncate => {
+			let digits = dsnp_user_id.to_string();
+			match digits.len() > TRUNCATED_DIGITS {
+				true => format!("{}...", &digits[..TRUNCATED_DIGITS]),
+				false => "...".to_string(),
+			}
+		},
+	}
+}
+
+/// replaces each number of the message which is one of the ids, leaving other numbers such as
+/// page ids or sizes as they are
+fn redact_ids_in(message: &impl Display, ids: &HashSet<DsnpUserId>, mode: RedactionMode) -> String {
+	let message = message.to_string();
+	let mut redacted = String::with_capacity(message.len());
+	let mut digits = String::new();
+	let flush = |digits: &mut String, redacted: &mut String| {
+		match digits.parse::<DsnpUserId>() {
+			Ok(id) if ids.contains(&id) => redacted.push_str(&redact_id_with_mode(id, mode)),
+			_ => redacted.push_str(digits),
+		}
+		digits.clear();
+	};
+	for c in message.chars() {
+		match c.is_ascii_digit() {
+			true => digits.push(c),
+			false => {
+				flush(&mut digits, &mut redacted);
+				redacted.push(c);
+			},
+		}
+	}
+	flush(&mut digits, &mut redacted);
+	redacted
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn redact_ids_in_should_only_replace_ids_of_the_error() {
+		// arrange
+		let error = DsnpGraphError::KeyPageSizeExceeded(123456, 123, 4096);
+		let ids = error.dsnp_user_ids().into_iter().collect();
+
+		// act
+		let none = redact_ids_in(&error, &ids, RedactionMode::None);
+		let truncated = redact_ids_in(&error, &ids, RedactionMode::Truncate);
+		let hashed = redact_ids_in(&error, &ids, RedactionMode::Hash);
+
+		// assert
+		assert_eq!(none, error.to_string());
+		assert_eq!(
+			truncated,
+			"Key page of user 123... requires 123 bytes which exceeds the maximum of 4096 bytes"
+		);
+		let hashed_id = redact_id_with_mode(123456, RedactionMode::Hash);
+		assert_eq!(hashed_id.len(), 1 + 2 * HASHED_ID_BYTES);
+		assert_eq!(hashed_id, redact_id_with_mode(123456, RedactionMode::Hash));
+		assert_ne!(hashed_id, redact_id_with_mode(123457, RedactionMode::Hash));
+		assert_eq!(
+			hashed,
+			format!(
+				"Key page of user {} requires 123 bytes which exceeds the maximum of 4096 bytes",
+				hashed_id
+			)
+		);
+		assert_eq!(redact_id_with_mode(12, RedactionMode::Truncate), "...");
+	}
+}
@@ -1,5 +1,22 @@
 //! Implemented helpers and utilities
+pub mod audit_log;
+pub mod bloom_filter;
 pub mod builders;
+pub mod encrypted_store;
+pub mod environment_usage;
+#[cfg(feature = "benches")]
+pub mod generators;
+pub mod hkdf;
+pub mod instrumentation;
+pub mod key_bundle;
+pub mod key_format;
+pub mod log_redaction;
+pub mod page_capacity;
+pub mod page_packing;
+#[cfg(feature = "serde")]
+pub mod serde_base64;
+pub mod snapshot;
+pub mod state_hash;
 pub mod time;
 pub mod transactional_hashmap;
 pub mod transactional_vec;
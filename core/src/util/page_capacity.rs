@@ -0,0 +1,192 @@
+//! Helpers to calculate how many connections fit into a single graph page
+use crate::{
+	api::api_types::ResolvedKeyPair,
+	dsnp::{
+		dsnp_configs::{DsnpVersionConfig, KeyPairType},
+		dsnp_types::{DsnpGraphEdge, DsnpPrid},
+	},
+	graph::{
+		page::{GraphPage, PrivatePageDataProvider, PublicPageDataProvider},
+		page_capacities::PAGE_CAPACITY_MAP,
+	},
+};
+use dryoc::keypair::StackKeyPair;
+use dsnp_graph_config::{Config, ConnectionType, DsnpVersion, PrivacyType};
+use lazy_static::lazy_static;
+use std::{
+	collections::HashMap,
+	sync::{Arc, RwLock},
+};
+
+/// Seed of the pseudo random connection ids, kept constant so calculations are reproducible
+const CONNECTION_ID_SEED: u64 = 0x2545F4914F6CDD1D;
+
+/// Page size that `PAGE_CAPACITY_MAP` was benchmarked with
+pub const BENCHMARKED_PAGE_SIZE_BYTES: u32 = 1024;
+
+/// Connection types that capacities are calculated for
+const CAPACITY_CONNECTION_TYPES: [ConnectionType; 4] = [
+	ConnectionType::Follow(PrivacyType::Public),
+	ConnectionType::Follow(PrivacyType::Private),
+	ConnectionType::Friendship(PrivacyType::Public),
+	ConnectionType::Friendship(PrivacyType::Private),
+];
+
+lazy_static! {
+	/// Shared benchmarked page capacities
+	static ref BENCHMARKED_CAPACITIES: Arc<HashMap<ConnectionType, usize>> =
+		Arc::new(PAGE_CAPACITY_MAP.clone());
+	/// Calculated page capacities of environments with non benchmarked page sizes, by page size
+	static ref CALCULATED_CAPACITIES: RwLock<HashMap<u32, Arc<HashMap<ConnectionType, usize>>>> =
+		RwLock::new(HashMap::new());
+}
+
+/// Returns the page capacities of the environment with the provided config
+///
+/// Configs with the benchmarked page size use `PAGE_CAPACITY_MAP`, others use capacities which
+/// are calculated once per page size, so graph states of several environments can live in the
+/// same process. Connection types which do not fit into a single page have no capacity.
+pub fn environment_page_capacities(config: &Config) -> Arc<HashMap<ConnectionType, usize>> {
+	let page_size = config.max_graph_page_size_bytes;
+	if page_size == BENCHMARKED_PAGE_SIZE_BYTES {
+		return BENCHMARKED_CAPACITIES.clone()
+	}
+	// a poisoned cache only contains fully calculated capacities, so it is still safe to use
+	if let Some(capacities) =
+		CALCULATED_CAPACITIES.read().unwrap_or_else(|e| e.into_inner()).get(&page_size)
+	{
+		return capacities.clone()
+	}
+
+	let capacities: HashMap<ConnectionType, usize> = CAPACITY_CONNECTION_TYPES
+		.into_iter()
+		.map(|connection_type| (connection_type, calculate_page_capacity(connection_type, config)))
+		.filter(|(_, capacity)| *capacity > 0)
+		.collect();
+	CALCULATED_CAPACITIES
+		.write()
+		.unwrap_or_else(|e| e.into_inner())
+		.entry(page_size)
+		.or_insert_with(|| Arc::new(capacities))
+		.clone()
+}
+
+/// Calculates the number of connections of a connection type that fit into a single graph page
+/// of the provided config.
+///
+/// The calculation uses connection ids and timestamps that are hard to compress, so the result
+/// can be used as a soft limit for environments with non-default page sizes.
+pub fn calculate_page_capacity(connection_type: ConnectionType, config: &Config) -> usize {
+	let max_page_size = config.max_graph_page_size_bytes as usize;
+	let dsnp_version_config = config
+		.get_schema_id_from_connection_type(connection_type)
+		.and_then(|schema_id| config.get_dsnp_version_from_schema_id(schema_id))
+		.map_or(DsnpVersionConfig::new(DsnpVersion::Version1_0), DsnpVersionConfig::new);
+	let key = ResolvedKeyPair { key_id: 0, key_pair: KeyPairType::Version1_0(StackKeyPair::gen()) };
+	let fits = |count: usize| {
+		page_size(connection_type, count, &dsnp_version_config, &key)
+			.map_or(false, |size| size <= max_page_size)
+	};
+
+	// grow exponentially until the page does not fit anymore and then binary search the capacity
+	let mut low = 0;
+	let mut high = 1;
+	while fits(high) {
+		low = high;
+		high *= 2;
+	}
+	while high - low > 1 {
+		let mid = low + (high - low) / 2;
+		match fits(mid) {
+			true => low = mid,
+			false => high = mid,
+		}
+	}
+	low
+}
+
+/// Serialized size of a page containing `count` hard to compress connections
+fn page_size(
+	connection_type: ConnectionType,
+	count: usize,
+	dsnp_version_config: &DsnpVersionConfig,
+	key: &ResolvedKeyPair,
+) -> Option<usize> {
+	let mut next_id = CONNECTION_ID_SEED;
+	let mut next_random = || {
+		// xorshift64 keeps the sequence stable, so bigger pages are a superset of smaller ones
+		next_id ^= next_id << 13;
+		next_id ^= next_id >> 7;
+		next_id ^= next_id << 17;
+		next_id
+	};
+	let mut connections = vec![];
+	let mut prids = vec![];
+	for _ in 0..count {
+		let user_id = next_random() >> 2 | 0x4000000000000000;
+		connections.push(DsnpGraphEdge { user_id, since: user_id - 1 });
+		prids.push(DsnpPrid::new(&next_random().to_le_bytes()));
+	}
+
+	let mut page = GraphPage::new(connection_type.privacy_type(), 0);
+	page.set_connections(connections);
+	if connection_type == ConnectionType::Friendship(PrivacyType::Private) {
+		page.unchecked_set_prids(prids);
+	}
+
+	let page_data = match connection_type.privacy_type() {
+		PrivacyType::Public => page.to_public_page_data(),
+		PrivacyType::Private => page.to_private_page_data(dsnp_version_config, key),
+	};
+	page_data.ok().map(|page_data| page_data.content.len())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::graph::page_capacities::PAGE_CAPACITY_MAP;
+	use dsnp_graph_config::{Environment, ALL_CONNECTION_TYPES};
+
+	#[test]
+	fn calculate_page_capacity_should_be_close_to_benchmarked_capacities() {
+		let config = Environment::Mainnet.get_config();
+
+		for connection_type in ALL_CONNECTION_TYPES {
+			let capacity = calculate_page_capacity(connection_type, &config);
+			let benchmarked = *PAGE_CAPACITY_MAP.get(&connection_type).unwrap();
+
+			assert!(capacity.abs_diff(benchmarked) <= benchmarked / 20);
+		}
+	}
+
+	#[test]
+	fn calculate_page_capacity_should_grow_with_page_size() {
+		let mut config = Environment::Mainnet.get_config().clone();
+		let connection_type = ConnectionType::Follow(PrivacyType::Private);
+		let default_capacity = calculate_page_capacity(connection_type, &config);
+
+		config.max_graph_page_size_bytes *= 4;
+		let bigger_capacity = calculate_page_capacity(connection_type, &config);
+
+		assert!(bigger_capacity > default_capacity * 3);
+	}
+
+	#[test]
+	fn environment_page_capacities_should_be_scoped_by_page_size() {
+		// arrange
+		let mainnet = Environment::Mainnet.get_config().clone();
+		let mut bigger = mainnet.clone();
+		bigger.max_graph_page_size_bytes = 3 * BENCHMARKED_PAGE_SIZE_BYTES;
+		let connection_type = ConnectionType::Follow(PrivacyType::Public);
+
+		// act
+		let mainnet_capacities = environment_page_capacities(&mainnet);
+		let bigger_capacities = environment_page_capacities(&bigger);
+
+		// assert
+		assert_eq!(*mainnet_capacities, *PAGE_CAPACITY_MAP);
+		assert!(bigger_capacities[&connection_type] > mainnet_capacities[&connection_type] * 2);
+		assert!(Arc::ptr_eq(&bigger_capacities, &environment_page_capacities(&bigger)));
+		assert!(Arc::ptr_eq(&mainnet_capacities, &environment_page_capacities(&mainnet)));
+	}
+}
@@ -0,0 +1,162 @@
+//! Invariants every page plan resulting from exported graph updates has to satisfy
+//!
+//! `verify_page_plan` checks that updates keep every connection exactly once, only change the
+//! pages they update and respect the page limits. It is used to test the update calculation of
+//! graphs and can be used by integrators to audit the pages resulting from exported updates
+//! before submitting them on chain.
+use crate::dsnp::dsnp_types::DsnpUserId;
+use dsnp_graph_config::PageId;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Connections of each page by page id
+pub type PageConnections = BTreeMap<PageId, Vec<DsnpUserId>>;
+
+/// Limits that a page plan has to respect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackingLimits {
+	/// maximum number of connections in an updated page
+	pub max_connections_per_page: usize,
+
+	/// highest page id that can be used
+	pub max_page_id: PageId,
+}
+
+/// Resulting pages of packing added and removed connections into existing pages
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PagePlan {
+	/// connections of all pages after the update, updated pages without connections are deleted
+	/// and may be left out
+	pub pages: PageConnections,
+
+	/// ids of the pages which are changed by the update
+	pub updated_pages: BTreeSet<PageId>,
+
+	/// added connections which did not fit into any page
+	pub overflow: Vec<DsnpUserId>,
+}
+
+/// Invariant broken by a page plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackingViolation {
+	/// an existing or added connection is missing from the plan
+	ConnectionLost(DsnpUserId),
+
+	/// a connection is stored more than once
+	ConnectionDuplicated(DsnpUserId),
+
+	/// a connection is neither existing nor added, or was removed
+	UnexpectedConnection(DsnpUserId),
+
+	/// an overflowing connection was neither added nor kept
+	UnexpectedOverflow(DsnpUserId),
+
+	/// a page which is not marked as updated has different connections
+	UntrackedPageChange(PageId),
+
+	/// an updated page holds more connections than allowed
+	PageOverCapacity(PageId, usize),
+
+	/// a page id exceeds the maximum page id
+	PageIdOutOfRange(PageId),
+}
+
+/// Verifies that a page plan keeps every existing connection which is not removed and every
+/// added connection exactly once unless it is reported as overflow, that only updated pages
+/// changed and that updated pages respect the limits.
+pub fn verify_page_plan(
+	existing_pages: &PageConnections,
+	adds: &[DsnpUserId],
+	removes: &[DsnpUserId],
+	plan: &PagePlan,
+	limits: PackingLimits,
+) -> Result<(), PackingViolation> {
+	let removes: HashSet<DsnpUserId> = removes.iter().copied().collect();
+	let mut expected: BTreeSet<DsnpUserId> = existing_pages
+		.values()
+		.flatten()
+		.copied()
+		.filter(|id| !removes.contains(id))
+		.collect();
+	expected.extend(adds.iter().copied());
+	for id in &plan.overflow {
+		if !expected.remove(id) {
+			return Err(PackingViolation::UnexpectedOverflow(*id))
+		}
+	}
+
+	let mut seen = HashSet::new();
+	for (page_id, connections) in &plan.pages {
+		if *page_id > limits.max_page_id {
+			return Err(PackingViolation::PageIdOutOfRange(*page_id))
+		}
+		if !plan.updated_pages.contains(page_id) {
+			if existing_pages.get(page_id) != Some(connections) {
+				return Err(PackingViolation::UntrackedPageChange(*page_id))
+			}
+		} else if connections.len() > limits.max_connections_per_page {
+			return Err(PackingViolation::PageOverCapacity(*page_id, connections.len()))
+		}
+		for id in connections {
+			if !seen.insert(*id) {
+				return Err(PackingViolation::ConnectionDuplicated(*id))
+			}
+			if !expected.contains(id) {
+				return Err(PackingViolation::UnexpectedConnection(*id))
+			}
+		}
+	}
+	if let Some(page_id) = existing_pages
+		.keys()
+		.find(|id| !plan.pages.contains_key(id) && !plan.updated_pages.contains(id))
+	{
+		return Err(PackingViolation::UntrackedPageChange(*page_id))
+	}
+	match expected.into_iter().find(|id| !seen.contains(id)) {
+		Some(id) => Err(PackingViolation::ConnectionLost(id)),
+		None => Ok(()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn verify_page_plan_should_detect_broken_invariants() {
+		// arrange
+		let pages = PageConnections::from([(0, vec![1, 2]), (1, vec![3])]);
+		let limits = PackingLimits { max_connections_per_page: 2, max_page_id: 1 };
+		let plan = PagePlan {
+			pages: PageConnections::from([(0, vec![1, 4]), (1, vec![3])]),
+			updated_pages: BTreeSet::from([0]),
+			overflow: vec![],
+		};
+		let mut lost = plan.clone();
+		lost.pages.get_mut(&0).unwrap().clear();
+		let mut duplicated = plan.clone();
+		duplicated.pages.insert(0, vec![1, 1]);
+		let mut untracked = plan.clone();
+		untracked.updated_pages.clear();
+		let mut over_capacity = plan.clone();
+		over_capacity.pages.get_mut(&0).unwrap().extend([5, 6]);
+		let mut out_of_range = plan.clone();
+		out_of_range.pages.insert(2, vec![]);
+		let overflow = PagePlan { overflow: vec![4], ..PagePlan::default() };
+
+		// act
+		let verify = |plan: &PagePlan| verify_page_plan(&pages, &[4], &[2], plan, limits);
+
+		// assert
+		assert_eq!(verify(&plan), Ok(()));
+		assert_eq!(verify(&lost), Err(PackingViolation::ConnectionLost(1)));
+		assert_eq!(verify(&duplicated), Err(PackingViolation::ConnectionDuplicated(1)));
+		assert_eq!(verify(&untracked), Err(PackingViolation::UntrackedPageChange(0)));
+		assert_eq!(verify(&over_capacity), Err(PackingViolation::PageOverCapacity(0, 4)));
+		assert_eq!(verify(&out_of_range), Err(PackingViolation::PageIdOutOfRange(2)));
+		assert_eq!(
+			verify(&PagePlan { overflow: vec![2], ..plan.clone() }),
+			Err(PackingViolation::UnexpectedOverflow(2))
+		);
+		assert_eq!(verify(&overflow), Err(PackingViolation::UntrackedPageChange(0)));
+	}
+}
@@ -0,0 +1,23 @@
+//! Serde helpers that encode raw bytes as standard base64 strings in human readable formats such
+//! as JSON, and as plain bytes in binary formats
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+/// serializes the bytes as a base64 string if the format is human readable
+pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+	match serializer.is_human_readable() {
+		true => serializer.serialize_str(&STANDARD.encode(bytes)),
+		false => serializer.serialize_bytes(bytes),
+	}
+}
+
+/// deserializes the bytes from a base64 string if the format is human readable
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+	match deserializer.is_human_readable() {
+		true => {
+			let encoded = String::deserialize(deserializer)?;
+			STANDARD.decode(encoded).map_err(D::Error::custom)
+		},
+		false => serde_bytes::ByteBuf::deserialize(deserializer).map(|bytes| bytes.into_vec()),
+	}
+}
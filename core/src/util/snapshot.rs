@@ -0,0 +1,59 @@
+//! Cell holding an immutable snapshot which is replaced as a whole
+use std::sync::{Arc, RwLock};
+
+/// Holds an immutable snapshot which can be replaced while it is being read
+///
+/// Readers only hold the inner lock while cloning the `Arc` of the current snapshot and writers
+/// only while swapping it with an already built one, so reads never wait on building a snapshot
+/// or on any other lock that the writer might be holding.
+#[derive(Debug, Default)]
+pub struct SnapshotCell<T> {
+	/// currently published snapshot
+	current: RwLock<Arc<T>>,
+}
+
+impl<T> SnapshotCell<T> {
+	/// creates a new cell publishing `value`
+	pub fn new(value: Arc<T>) -> Self {
+		Self { current: RwLock::new(value) }
+	}
+
+	/// returns the currently published snapshot
+	pub fn load(&self) -> Arc<T> {
+		// the lock is never held while running any code that can panic
+		self.current.read().unwrap_or_else(|e| e.into_inner()).clone()
+	}
+
+	/// publishes a new snapshot, existing readers keep using the one they loaded
+	pub fn store(&self, value: Arc<T>) {
+		*self.current.write().unwrap_or_else(|e| e.into_inner()) = value;
+	}
+}
+
+impl<T: PartialEq> PartialEq for SnapshotCell<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.load() == other.load()
+	}
+}
+
+impl<T: Eq> Eq for SnapshotCell<T> {}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn snapshot_cell_store_should_not_affect_loaded_snapshots() {
+		// arrange
+		let cell = SnapshotCell::new(Arc::new(vec![1, 2]));
+		let loaded = cell.load();
+
+		// act
+		cell.store(Arc::new(vec![3]));
+
+		// assert
+		assert_eq!(*loaded, vec![1, 2]);
+		assert_eq!(*cell.load(), vec![3]);
+		assert_eq!(cell, SnapshotCell::new(Arc::new(vec![3])));
+	}
+}
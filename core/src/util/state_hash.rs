@@ -0,0 +1,97 @@
+//! Deterministic hashing of graph states, which allows services running the same imports and
+//! actions on different nodes to verify that they derived identical states before exporting
+//!
+//! # Canonical ordering
+//! The hash does not depend on the iteration order of any map or on the nonces of encrypted
+//! pages. Values are fed in the following order
+//! - the name of the environment
+//! - published keys, newly added keys and imported PRIds of the shared state, by user id
+//! - user graphs by user id, each with the public keys of its imported key pairs by key id, its
+//!   graphs by schema id and its pending updates by schema id
+//! - graphs with their decrypted pages, undecrypted pages and repaired pages by page id, where
+//!   connections and PRIds keep their order within each page
+//! - pending update events in registration order followed by the page hints sorted by key
+//!
+//! Integers are fed as little endian `u64` and byte strings are prefixed by their length, so the
+//! same state always produces the same hash regardless of the platform.
+use dryoc::classic::crypto_generichash::{
+	crypto_generichash_final, crypto_generichash_init, crypto_generichash_update, GenericHashState,
+};
+
+/// Number of bytes of a `StateHash`
+pub const STATE_HASH_BYTES: usize = 32;
+
+/// Blake2b digest of a graph state
+pub type StateHash = [u8; STATE_HASH_BYTES];
+
+/// Incrementally calculates a `StateHash`
+pub struct StateHasher {
+	state: GenericHashState,
+}
+
+impl Default for StateHasher {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl StateHasher {
+	/// creates a hasher without any fed value
+	pub fn new() -> Self {
+		// can only fail for invalid output or key lengths which are constant here
+		let state = crypto_generichash_init(None, STATE_HASH_BYTES)
+			.expect("state hash length should be valid");
+		Self { state }
+	}
+
+	/// feeds an integer
+	pub fn write_u64(&mut self, value: u64) {
+		crypto_generichash_update(&mut self.state, &value.to_le_bytes());
+	}
+
+	/// feeds an optional integer prefixed by whether it exists
+	pub fn write_optional_u64(&mut self, value: Option<u64>) {
+		self.write_u64(value.is_some() as u64);
+		if let Some(value) = value {
+			self.write_u64(value);
+		}
+	}
+
+	/// feeds a byte string prefixed by its length
+	pub fn write_bytes(&mut self, bytes: &[u8]) {
+		self.write_u64(bytes.len() as u64);
+		crypto_generichash_update(&mut self.state, bytes);
+	}
+
+	/// returns the hash of all fed values
+	pub fn finish(self) -> StateHash {
+		let mut hash = [0u8; STATE_HASH_BYTES];
+		// can only fail for an output length different than the initialized one
+		let _ = crypto_generichash_final(self.state, &mut hash);
+		hash
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn hash_of(values: &[&[u8]]) -> StateHash {
+		let mut hasher = StateHasher::new();
+		for value in values {
+			hasher.write_bytes(value);
+		}
+		hasher.finish()
+	}
+
+	#[test]
+	fn state_hasher_should_separate_fed_values() {
+		// act
+		let hash = hash_of(&[b"ab", b"c"]);
+
+		// assert
+		assert_eq!(hash, hash_of(&[b"ab", b"c"]));
+		assert_ne!(hash, hash_of(&[b"a", b"bc"]));
+		assert_ne!(hash, hash_of(&[b"abc"]));
+	}
+}
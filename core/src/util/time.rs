@@ -1,18 +1,59 @@
 //! Module that defines helpers to create or read timestamps
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{
+	fmt::Debug,
+	panic::RefUnwindSafe,
+	sync::Arc,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 pub const SECS_PER_DAY: u64 = 60 * 60 * 24;
 
+/// Shared reference to a time source
+pub type SharedTimeProvider = Arc<dyn TimeProvider>;
+
+/// Source of the current time used for `since` stamping and stale-friendship checks
+///
+/// Hosts can provide their own implementation to use a chain based timestamp instead of the
+/// local system clock.
+pub trait TimeProvider: Debug + Send + Sync + RefUnwindSafe {
+	/// Current timestamp from EPOCH in seconds
+	fn now_in_secs(&self) -> u64;
+
+	/// Current timestamp from EPOCH in seconds, rounded to the nearest 1000
+	fn time_in_ksecs(&self) -> u64 {
+		self.now_in_secs() / 1_000
+	}
+
+	/// Duration in days between now and provided timestamp from EPOCH
+	fn duration_days_since(&self, since_ksecs: u64) -> u64 {
+		let from_sec = since_ksecs.saturating_mul(1_000);
+		duration_sec_from_to(from_sec, self.now_in_secs()) / SECS_PER_DAY
+	}
+}
+
+/// Default time provider which reads the local system clock
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemTimeProvider;
+
+impl TimeProvider for SystemTimeProvider {
+	fn now_in_secs(&self) -> u64 {
+		SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+	}
+}
+
+/// Returns the default system time provider
+pub fn system_time_provider() -> SharedTimeProvider {
+	Arc::new(SystemTimeProvider)
+}
+
 /// Calculates current timestamp from EPOCH in seconds, rounded to the nearest 1000
 pub fn time_in_ksecs() -> u64 {
-	SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() / 1_000
+	SystemTimeProvider.time_in_ksecs()
 }
 
 /// Calculates duration in days between now and provided timestamp from EPOCH
 pub fn duration_days_since(since_ksecs: u64) -> u64 {
-	let from_sec = since_ksecs.saturating_mul(1_000);
-	let to_sec = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
-	duration_sec_from_to(from_sec, to_sec) / SECS_PER_DAY
+	SystemTimeProvider.duration_days_since(since_ksecs)
 }
 
 fn duration_sec_from_to(from_sec: u64, to_sec: u64) -> u64 {
@@ -38,4 +79,28 @@ mod tests {
 		// assert
 		assert_eq!(duration_days, days);
 	}
+
+	#[derive(Debug)]
+	struct FixedTimeProvider(u64);
+
+	impl TimeProvider for FixedTimeProvider {
+		fn now_in_secs(&self) -> u64 {
+			self.0
+		}
+	}
+
+	#[test]
+	fn custom_time_provider_should_be_used_for_calculations() {
+		// arrange
+		let provider = FixedTimeProvider(100 * SECS_PER_DAY);
+		let since_ksecs = 10 * SECS_PER_DAY / 1_000;
+
+		// act
+		let now_ksecs = provider.time_in_ksecs();
+		let duration_days = provider.duration_days_since(since_ksecs);
+
+		// assert
+		assert_eq!(now_ksecs, 100 * SECS_PER_DAY / 1_000);
+		assert_eq!(duration_days, 90);
+	}
 }
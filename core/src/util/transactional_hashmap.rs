@@ -1,16 +1,30 @@
 //! Implementation of transactional HashMap which tracks all changes before committing, and allows
 //! rollbacks
+//!
+//! Changes made using `insert`, `remove`, `entry` and `clear` are staged until `commit` and can
+//! be reverted using `rollback`. The committed values stay readable using `committed_get` and
+//! `committed_iter` while changes are staged, which can be used by registries such as the handle
+//! registries of the bridges to serve readers a stable view during a batch of mutations.
+//!
+//! Values changed in place using `get_mut`, `iter_mut` or `try_for_each_value_mut` and items added
+//! using `extend` are not tracked, so they are meant for values which track their own changes such
+//! as other transactional types, or for initializing the map.
 use std::{
 	borrow::Borrow,
 	collections::{hash_map::Entry, HashMap},
 	hash::Hash,
 };
 
+/// Defines a type whose changes can be committed or reverted
 pub trait Transactional {
+	/// keeps all the staged changes
 	fn commit(&mut self);
+
+	/// reverts all the staged changes since the last commit or rollback
 	fn rollback(&mut self);
 }
 
+/// HashMap which stages changes until they are committed or rolled back
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct TransactionalHashMap<K, V>
 where
@@ -26,10 +40,12 @@ where
 	K: Eq + Hash + Clone,
 	V: Clone,
 {
+	/// creates an empty map
 	pub fn new() -> Self {
 		Self { inner: HashMap::new(), overridden_originals: HashMap::new() }
 	}
 
+	/// creates an empty map with at least the specified capacity
 	#[inline]
 	pub fn with_capacity(capacity: usize) -> Self {
 		Self {
@@ -38,10 +54,12 @@ where
 		}
 	}
 
+	/// returns the current values including the staged changes
 	pub fn inner(&self) -> &HashMap<K, V> {
 		&self.inner
 	}
 
+	/// returns the entry of the key, where any change made using it is staged
 	#[inline]
 	pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
 		match self.inner.entry(key.clone()) {
@@ -61,15 +79,17 @@ where
 		}
 	}
 
+	/// inserts a value as a staged change and returns the previous value of the key
 	#[inline]
 	pub fn insert(&mut self, k: K, v: V) -> Option<V> {
-		let prev = self.inner.remove(&k);
+		let prev = self.inner.insert(k.clone(), v);
 		if !self.overridden_originals.contains_key(&k) {
-			self.overridden_originals.insert(k.clone(), prev);
+			self.overridden_originals.insert(k, prev.clone());
 		}
-		self.inner.insert(k, v)
+		prev
 	}
 
+	/// removes a value as a staged change and returns it
 	#[inline]
 	pub fn remove(&mut self, k: &K) -> Option<V> {
 		if let Some(v) = self.inner.remove(&k) {
@@ -82,6 +102,7 @@ where
 		None
 	}
 
+	/// removes all values as staged changes
 	#[inline]
 	pub fn clear(&mut self) {
 		for (key, value) in self.inner.drain() {
@@ -91,11 +112,13 @@ where
 		}
 	}
 
+	/// returns the number of current values including the staged changes
 	#[inline]
 	pub fn len(&self) -> usize {
 		self.inner.len()
 	}
 
+	/// returns the current value of the key including the staged changes
 	#[inline]
 	pub fn get<Q: ?Sized>(&self, k: &Q) -> Option<&V>
 	where
@@ -105,6 +128,8 @@ where
 		self.inner.get(k)
 	}
 
+	/// returns a mutable reference to the current value of the key, which is not tracked as a
+	/// change
 	#[inline]
 	pub fn get_mut<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
 	where
@@ -113,6 +138,89 @@ where
 	{
 		self.inner.get_mut(k)
 	}
+
+	/// returns mutable references to all the current values, which same as `get_mut` are not
+	/// tracked as changes
+	#[inline]
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = (&K, &mut V)> {
+		self.inner.iter_mut()
+	}
+
+	/// returns the keys which are changed since the last commit or rollback
+	pub fn changed_keys(&self) -> impl Iterator<Item = &K> {
+		self.overridden_originals.keys()
+	}
+
+	/// keeps the staged change of a single key while the changes of other keys stay staged
+	pub fn commit_key<Q: ?Sized>(&mut self, k: &Q)
+	where
+		K: Borrow<Q>,
+		Q: Hash + Eq,
+	{
+		self.overridden_originals.remove(k);
+	}
+
+	/// returns true if there are changes staged since the last commit or rollback
+	pub fn has_staged_changes(&self) -> bool {
+		!self.overridden_originals.is_empty()
+	}
+
+	/// returns the value of the key as of the last commit, ignoring the staged changes
+	pub fn committed_get<Q: ?Sized>(&self, k: &Q) -> Option<&V>
+	where
+		K: Borrow<Q>,
+		Q: Hash + Eq,
+	{
+		match self.overridden_originals.get(k) {
+			Some(original) => original.as_ref(),
+			None => self.inner.get(k),
+		}
+	}
+
+	/// iterates over the values as of the last commit, ignoring the staged changes, in arbitrary
+	/// order
+	pub fn committed_iter(&self) -> impl Iterator<Item = (&K, &V)> {
+		self.inner
+			.iter()
+			.filter(|(key, _)| !self.overridden_originals.contains_key(*key))
+			.chain(
+				self.overridden_originals
+					.iter()
+					.filter_map(|(key, original)| original.as_ref().map(|value| (key, value))),
+			)
+	}
+
+	/// returns the number of values as of the last commit
+	pub fn committed_len(&self) -> usize {
+		self.committed_iter().count()
+	}
+
+	/// applies `f` to all the current values and the originals kept for a rollback, without
+	/// tracking it as a change. Stops on the first error.
+	pub fn try_for_each_value_mut<E>(
+		&mut self,
+		mut f: impl FnMut(&K, &mut V) -> Result<(), E>,
+	) -> Result<(), E> {
+		for (key, value) in self.inner.iter_mut() {
+			f(key, value)?;
+		}
+		for (key, value) in self.overridden_originals.iter_mut() {
+			if let Some(value) = value {
+				f(key, value)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl<K, V> Default for TransactionalHashMap<K, V>
+where
+	K: Eq + Hash + Clone,
+	V: Clone,
+{
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 impl<K, V> FromIterator<(K, V)> for TransactionalHashMap<K, V>
@@ -162,8 +270,95 @@ where
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use rand::{rngs::StdRng, Rng, SeedableRng};
 	use std::collections::BTreeMap;
 
+	#[test]
+	fn transactional_hashmap_should_match_model_for_random_operations() {
+		for seed in 0..50 {
+			let mut rng = StdRng::seed_from_u64(seed);
+			let mut transactional: TransactionalHashMap<u8, u32> = TransactionalHashMap::new();
+			let mut current: HashMap<u8, u32> = HashMap::new();
+			let mut committed: HashMap<u8, u32> = HashMap::new();
+
+			for _ in 0..200 {
+				let key = rng.gen_range(0..16);
+				let value = rng.gen::<u32>();
+				match rng.gen_range(0..100) {
+					0..=39 => {
+						assert_eq!(transactional.insert(key, value), current.insert(key, value));
+					},
+					40..=64 => {
+						assert_eq!(transactional.remove(&key), current.remove(&key));
+					},
+					65..=79 => {
+						*transactional.entry(key).or_default() += 1;
+						*current.entry(key).or_default() += 1;
+					},
+					80..=81 => {
+						transactional.clear();
+						current.clear();
+					},
+					82..=91 => {
+						transactional.commit();
+						committed = current.clone();
+					},
+					92..=94 => {
+						transactional.commit_key(&key);
+						match current.get(&key) {
+							Some(value) => committed.insert(key, *value),
+							None => committed.remove(&key),
+						};
+					},
+					_ => {
+						transactional.rollback();
+						current = committed.clone();
+					},
+				}
+
+				// the committed view is stable while changes are staged
+				assert_eq!(transactional.inner(), &current, "seed {}", seed);
+				let snapshot: HashMap<u8, u32> =
+					transactional.committed_iter().map(|(k, v)| (*k, *v)).collect();
+				assert_eq!(snapshot, committed, "seed {}", seed);
+				assert_eq!(transactional.committed_len(), committed.len());
+				assert_eq!(transactional.committed_get(&key), committed.get(&key));
+				assert_eq!(
+					transactional.has_staged_changes(),
+					transactional.changed_keys().count() > 0
+				);
+			}
+
+			transactional.rollback();
+			assert_eq!(transactional.inner(), &committed);
+			assert!(!transactional.has_staged_changes());
+		}
+	}
+
+	#[test]
+	fn transactional_hashmap_try_for_each_value_mut_should_update_originals() {
+		let mut transactional = TransactionalHashMap::new();
+		transactional.insert(1, 10);
+		transactional.insert(2, 20);
+		transactional.commit();
+		transactional.insert(1, 11);
+		transactional.remove(&2);
+
+		transactional
+			.try_for_each_value_mut(|_, v| -> Result<(), ()> {
+				*v *= 2;
+				Ok(())
+			})
+			.unwrap();
+		let failed =
+			transactional.try_for_each_value_mut(|k, _| if *k == 1 { Err(*k) } else { Ok(()) });
+
+		assert_eq!(failed, Err(1));
+		assert_eq!(transactional.inner(), &HashMap::from([(1, 22)]));
+		transactional.rollback();
+		assert_eq!(transactional.inner(), &HashMap::from([(1, 20), (2, 40)]));
+	}
+
 	#[test]
 	fn transactional_hashmap_should_revert_the_state_as_before() {
 		let arr = [(1, 9), (2, 8), (3, 7), (4, 6), (5, 5), (6, 4), (7, 3), (8, 2), (9, 1)];
@@ -5,7 +5,7 @@ use dsnp_graph_config::{ConnectionType, Environment, PrivacyType};
 use dsnp_graph_core::{
 	api::{
 		api::{GraphAPI, GraphState},
-		api_types::{PageData, PageId},
+		api_types::PageData,
 	},
 	util::builders::ImportBundleBuilder,
 };
@@ -16,9 +16,12 @@ mod integration_tests {
 	use crate::common::create_new_keys;
 	use ctor::ctor;
 	use dryoc::keypair::StackKeyPair;
-	use dsnp_graph_config::GraphKeyType;
+	use dsnp_graph_config::{errors::DsnpGraphError, GraphKeyType};
 	use dsnp_graph_core::{
-		api::api_types::{Action, ActionOptions, Connection, DsnpKeys, GraphKeyPair, Update},
+		api::api_types::{
+			Action, ActionOptions, Connection, DsnpKeys, GraphKeyPair, ImportOptions,
+			TimestampPolicy, Update,
+		},
 		dsnp::{
 			dsnp_types::{DsnpGraphEdge, DsnpPrid, DsnpPublicKey, DsnpUserId},
 			pseudo_relationship_identifier::PridProvider,
@@ -112,7 +115,7 @@ mod integration_tests {
 			.with_page(1, &connections, &vec![], 0)
 			.build();
 		let mut bad_page = input.pages.remove(0);
-		bad_page.page_id = env.get_config().max_page_id as PageId + 1; // invalid page id
+		bad_page.page_id = env.get_config().max_page_id + 1; // invalid page id
 		input.pages.push(bad_page);
 
 		// act
@@ -360,6 +363,8 @@ mod integration_tests {
 				owner_dsnp_user_id: dsnp_user_id_1,
 				connection: Connection { dsnp_user_id: 10, schema_id },
 				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
 			},
 			Action::Disconnect {
 				owner_dsnp_user_id: dsnp_user_id_1,
@@ -404,6 +409,8 @@ mod integration_tests {
 				owner_dsnp_user_id: dsnp_user_id,
 				connection: Connection { dsnp_user_id: 10, schema_id },
 				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
 			},
 			Action::Disconnect {
 				owner_dsnp_user_id: dsnp_user_id,
@@ -511,7 +518,7 @@ mod integration_tests {
 		state.import_users_data(&vec![input1]).expect("should import!");
 
 		// act
-		let res = state.get_connections_without_keys();
+		let res = state.get_connections_without_keys(true);
 
 		// assert
 		assert!(res.is_ok());
@@ -520,6 +527,200 @@ mod integration_tests {
 		assert_eq!(res_set, mapped);
 	}
 
+	#[test]
+	fn api_get_connections_without_keys_page_should_return_sorted_pages_after_cursor() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let mut state = GraphState::new(env.clone());
+		let (_, resolved_key, keypair) = create_new_keys(0);
+		let connections: Vec<(DsnpUserId, u64)> = vec![(9, 1), (4, 2), (7, 3), (2, 4), (5, 5)];
+		let prids: Vec<_> =
+			connections.iter().map(|(id, _)| DsnpPrid::new(&id.to_le_bytes())).collect();
+		let input = ImportBundleBuilder::new(env, 1, schema_id)
+			.with_page(1, &connections, &prids, 100)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.build();
+		state.import_users_data(&vec![input]).expect("should import!");
+
+		// act
+		let first = state.get_connections_without_keys_page(true, None, Some(2));
+		let second = state.get_connections_without_keys_page(true, Some(4), Some(2));
+		let last = state.get_connections_without_keys_page(true, Some(7), Some(2));
+		let rest = state.get_connections_without_keys_page(true, Some(4), None);
+
+		// assert
+		assert_eq!(state.get_connections_without_keys(true).unwrap(), vec![2, 4, 5, 7, 9]);
+		assert_eq!(first.unwrap(), vec![2, 4]);
+		assert_eq!(second.unwrap(), vec![5, 7]);
+		assert_eq!(last.unwrap(), vec![9]);
+		assert_eq!(rest.unwrap(), vec![5, 7, 9]);
+		assert!(matches!(
+			state.get_connections_without_keys_page(true, None, Some(0)),
+			Err(DsnpGraphError::InvalidInput(_))
+		));
+	}
+
+	#[test]
+	fn api_import_public_keys_should_provide_keys_of_private_friendship_connections() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let mut state = GraphState::new(env.clone());
+		let (_, resolved_key, keypair) = create_new_keys(0);
+		let dsnp_user_id_1 = 1;
+		let connections_1: Vec<(DsnpUserId, u64)> = vec![(2, 1), (3, 2)];
+		let prids: Vec<_> =
+			connections_1.iter().map(|(id, _)| DsnpPrid::new(&id.to_le_bytes())).collect();
+		let input1 = ImportBundleBuilder::new(env, dsnp_user_id_1, schema_id)
+			.with_page(1, &connections_1, &prids, 100)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.build();
+		state.import_users_data(&vec![input1]).expect("should import!");
+		let (key_pair_raw, _, _) = create_new_keys(0);
+		let public_key = DsnpPublicKey { key: key_pair_raw.public_key.to_vec(), key_id: Some(3) };
+
+		// act
+		let res = state.import_public_keys(&2, vec![public_key.clone()]);
+
+		// assert
+		assert!(res.is_ok());
+		assert_eq!(state.get_public_keys(&2).expect("should work"), vec![public_key]);
+		assert_eq!(state.get_connections_without_keys(true).expect("should work"), vec![3]);
+		assert!(matches!(
+			state.import_public_keys(&3, vec![DsnpPublicKey { key: vec![1u8; 32], key_id: None }]),
+			Err(DsnpGraphError::InvalidInput(_))
+		));
+	}
+
+	#[test]
+	fn api_import_with_strict_prid_validation_should_report_misaligned_prids() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let dsnp_user_id_1 = 1;
+		let (_, _, keypair) = create_new_keys(0);
+		let (key_2, _, _) = create_new_keys(0);
+		let (key_3, _, _) = create_new_keys(0);
+		let (rotated_key_3, _, _) = create_new_keys(0);
+		let public_key = |key: &StackKeyPair, key_id: u64| DsnpPublicKey {
+			key: key.public_key.to_vec(),
+			key_id: Some(key_id),
+		};
+		let input1 = ImportBundleBuilder::new(env.clone(), dsnp_user_id_1, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.build();
+		let mut state = GraphState::new(env.clone());
+		state.import_users_data(&vec![input1.clone()]).expect("should import!");
+		state
+			.import_public_keys(&2, vec![public_key(&key_2, 0)])
+			.expect("should import!");
+		state
+			.import_public_keys(&3, vec![public_key(&key_3, 0)])
+			.expect("should import!");
+		let actions: Vec<_> = [2, 3]
+			.iter()
+			.map(|id| Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id_1,
+				connection: Connection { dsnp_user_id: *id, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			})
+			.collect();
+		state.apply_actions(&actions, &None).expect("should apply actions!");
+		let exports = state.export_updates().expect("should export!");
+		let input2 = ImportBundleBuilder::build_from(&input1, &exports);
+		let strict = ImportOptions { strict_prid_validation: true, ..ImportOptions::default() };
+		let mut aligned_state = GraphState::new(env.clone());
+		aligned_state.set_import_options(strict);
+		aligned_state
+			.import_public_keys(&2, vec![public_key(&key_2, 0)])
+			.expect("should import!");
+		aligned_state
+			.import_public_keys(&3, vec![public_key(&key_3, 0)])
+			.expect("should import!");
+		let mut rotated_state = GraphState::new(env.clone());
+		rotated_state.set_import_options(strict);
+		rotated_state
+			.import_public_keys(&2, vec![public_key(&key_2, 0)])
+			.expect("should import!");
+		rotated_state
+			.import_public_keys(&3, vec![public_key(&key_3, 0), public_key(&rotated_key_3, 1)])
+			.expect("should import!");
+		let mut misaligned_state = GraphState::new(env);
+		misaligned_state.set_import_options(strict);
+		misaligned_state
+			.import_public_keys(&2, vec![public_key(&key_2, 0)])
+			.expect("should import!");
+		misaligned_state
+			.import_public_keys(&3, vec![public_key(&rotated_key_3, 1)])
+			.expect("should import!");
+
+		// act
+		let aligned = aligned_state.import_users_data(&vec![input2.clone()]);
+		let rotated = rotated_state.import_users_data(&vec![input2.clone()]);
+		let misaligned = misaligned_state.import_users_data(&vec![input2]);
+
+		// assert
+		assert!(aligned.is_ok());
+		assert!(rotated.is_ok());
+		assert!(matches!(
+			misaligned,
+			Err(DsnpGraphError::PridsMisaligned(_, indexes)) if indexes == vec![1]
+		));
+		assert!(!misaligned_state.contains_user_graph(&dsnp_user_id_1));
+	}
+
+	#[test]
+	fn api_get_connections_without_keys_with_include_pending_should_reflect_pending_actions() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let mut state = GraphState::new(env.clone());
+		let (_, resolved_key, keypair) = create_new_keys(0);
+		let dsnp_user_id_1 = 1;
+		let connections_1: Vec<(DsnpUserId, u64)> = vec![(2, 1), (3, 2)];
+		let prids: Vec<_> =
+			connections_1.iter().map(|(id, _)| DsnpPrid::new(&id.to_le_bytes())).collect();
+		let input1 = ImportBundleBuilder::new(env, dsnp_user_id_1, schema_id)
+			.with_page(1, &connections_1, &prids, 100)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.build();
+		state.import_users_data(&vec![input1]).expect("should import!");
+		let actions = vec![
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id_1,
+				connection: Connection { dsnp_user_id: 10, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+			Action::Disconnect {
+				owner_dsnp_user_id: dsnp_user_id_1,
+				connection: Connection { dsnp_user_id: 3, schema_id },
+			},
+		];
+		state.apply_actions(&actions, &None).expect("should apply actions");
+
+		// act
+		let with_pending = state.get_connections_without_keys(true);
+		let without_pending = state.get_connections_without_keys(false);
+
+		// assert
+		let with_pending: HashSet<_> = with_pending.unwrap().into_iter().collect();
+		let without_pending: HashSet<_> = without_pending.unwrap().into_iter().collect();
+		assert_eq!(with_pending, HashSet::from([2, 10]));
+		assert_eq!(without_pending, HashSet::from([2, 3]));
+	}
+
 	#[test]
 	fn api_get_connections_without_keys_for_non_private_friendship_graph_should_be_empty() {
 		// arrange
@@ -541,7 +742,7 @@ mod integration_tests {
 			state.import_users_data(&vec![input1]).expect("should import!");
 
 			// act
-			let res = state.get_connections_without_keys();
+			let res = state.get_connections_without_keys(true);
 
 			// assert
 			assert!(res.is_ok());
@@ -561,6 +762,7 @@ mod integration_tests {
 		let (_, resolved_key_1, keypair_1) = create_new_keys(0);
 		let (_, resolved_key_2, keypair_2) = create_new_keys(1);
 		let (_, resolved_key_3, keypair_3) = create_new_keys(2);
+		let (_, _, keypair_4) = create_new_keys(3);
 		// --------- user 1 graph setup--------------
 		let connections_1: Vec<(DsnpUserId, u64)> = vec![(2, 0), (3, 0)];
 		let prids: Vec<_> = vec![
@@ -606,16 +808,88 @@ mod integration_tests {
 			.with_key_pairs(&vec![keypair_3])
 			.build();
 		input3.key_pairs = vec![];
-		state.import_users_data(&vec![input1, input2, input3]).expect("should import!");
+		// --------- user 4 graph setup--------------
+		let mut input4 = ImportBundleBuilder::new(env.clone(), 4, schema_id)
+			.with_page(1, &connections_2, &prids_2, 400)
+			.with_key_pairs(&vec![keypair_4])
+			.build();
+		input4.key_pairs = vec![];
+		state
+			.import_users_data(&vec![input1, input2, input3, input4])
+			.expect("should import!");
 
 		// act
-		let res = state.get_one_sided_private_friendship_connections(&dsnp_user_id_1);
+		let res = state.get_one_sided_private_friendship_connections(&dsnp_user_id_1, false);
 
 		// assert
 		assert!(res.is_ok());
 		let res_set: HashSet<_> = res.unwrap().iter().copied().collect();
 		let mapped: HashSet<_> = HashSet::from([DsnpGraphEdge { user_id: 2, since: 0 }]);
 		assert_eq!(res_set, mapped);
+
+		// pending actions should only be reflected when including pending actions
+		let actions = vec![
+			Action::Disconnect {
+				owner_dsnp_user_id: dsnp_user_id_1,
+				connection: Connection { dsnp_user_id: 2, schema_id },
+			},
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id_1,
+				connection: Connection { dsnp_user_id: 4, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+		];
+		state.apply_actions(&actions, &None).expect("should apply actions");
+		let res = state.get_one_sided_private_friendship_connections(&dsnp_user_id_1, true);
+		let ids: Vec<_> = res.unwrap().iter().map(|edge| edge.user_id).collect();
+		assert_eq!(ids, vec![4]);
+		let res = state.get_one_sided_private_friendship_connections(&dsnp_user_id_1, false);
+		assert_eq!(res.unwrap(), vec![DsnpGraphEdge { user_id: 2, since: 0 }]);
+	}
+
+	#[test]
+	fn api_verify_connections_should_return_verification_of_each_user() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id_1 = 1;
+		let (_, resolved_key_1, keypair_1) = create_new_keys(0);
+		let (_, _, keypair_2) = create_new_keys(1);
+		let (_, resolved_key_3, keypair_3) = create_new_keys(2);
+		let input1 = ImportBundleBuilder::new(env.clone(), dsnp_user_id_1, schema_id)
+			.with_key_pairs(&vec![keypair_1])
+			.with_encryption_key(resolved_key_1.clone())
+			.build();
+		let mut input2 = ImportBundleBuilder::new(env.clone(), 2, schema_id)
+			.with_page(1, &vec![(dsnp_user_id_1, 0)], &vec![DsnpPrid::from(vec![0u8; 8])], 200)
+			.with_key_pairs(&vec![keypair_2])
+			.build();
+		input2.key_pairs = vec![];
+		let prids_3 = vec![DsnpPrid::create_prid(
+			3,
+			dsnp_user_id_1,
+			&resolved_key_1.key_pair.clone().into(),
+			&(&resolved_key_3.key_pair).into(),
+		)
+		.unwrap()];
+		let mut input3 = ImportBundleBuilder::new(env.clone(), 3, schema_id)
+			.with_page(1, &vec![(dsnp_user_id_1, 0)], &prids_3, 300)
+			.with_key_pairs(&vec![keypair_3])
+			.build();
+		input3.key_pairs = vec![];
+		state.import_users_data(&vec![input1, input2, input3]).expect("should import!");
+
+		// act
+		let res = state.verify_connections(&dsnp_user_id_1, &vec![2, 3, 4]);
+		let not_imported = state.verify_connections(&5, &vec![2]);
+
+		// assert
+		assert_eq!(res.unwrap(), vec![Some(false), Some(true), None]);
+		assert!(matches!(not_imported, Err(DsnpGraphError::UserGraphNotImported(5))));
 	}
 
 	#[test]
@@ -639,6 +913,8 @@ mod integration_tests {
 				owner_dsnp_user_id: dsnp_user_id_1,
 				connection: Connection { dsnp_user_id: 10, schema_id },
 				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
 			},
 			Action::Disconnect {
 				owner_dsnp_user_id: dsnp_user_id_1,
@@ -676,6 +952,8 @@ mod integration_tests {
 			owner_dsnp_user_id: dsnp_user_id_1,
 			connection: Connection { dsnp_user_id: 5, schema_id },
 			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
 		}];
 
 		// act
@@ -702,11 +980,15 @@ mod integration_tests {
 				owner_dsnp_user_id: dsnp_user_id_1,
 				connection: Connection { dsnp_user_id: 5, schema_id }, // redundant connection
 				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
 			},
 			Action::Connect {
 				owner_dsnp_user_id: dsnp_user_id_1,
 				connection: Connection { dsnp_user_id: 10, schema_id },
 				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
 			},
 		];
 		let expected_connections = vec![(2, 1), (3, 2), (4, 3), (5, 4), (10, 5)];
@@ -718,6 +1000,10 @@ mod integration_tests {
 				ignore_existing_connections: true,
 				ignore_missing_connections: false,
 				disable_auto_commit: false,
+				collect_errors: false,
+				require_counterpart_keys: false,
+				max_batch_size: None,
+				timestamp_policy: TimestampPolicy::default(),
 			}),
 		);
 
@@ -787,6 +1073,10 @@ mod integration_tests {
 				ignore_existing_connections: false,
 				ignore_missing_connections: true,
 				disable_auto_commit: false,
+				collect_errors: false,
+				require_counterpart_keys: false,
+				max_batch_size: None,
+				timestamp_policy: TimestampPolicy::default(),
 			}),
 		);
 
@@ -817,6 +1107,8 @@ mod integration_tests {
 				owner_dsnp_user_id: dsnp_user_id_1,
 				connection: Connection { dsnp_user_id: 1000, schema_id },
 				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
 			},
 			Action::Disconnect {
 				owner_dsnp_user_id: dsnp_user_id_1,
@@ -835,6 +1127,158 @@ mod integration_tests {
 		assert!(!connections.iter().any(|e| e.user_id == 1000));
 	}
 
+	#[test]
+	fn api_apply_actions_with_collect_errors_should_return_all_failed_actions() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = get_schema_from(env.clone(), ConnectionType::Follow(PrivacyType::Public));
+		let mut state = GraphState::new(env.clone());
+		let dsnp_user_id_1 = 1;
+		let connections_1 = vec![(2, 1), (3, 2), (4, 3), (5, 4)];
+		let input1 = ImportBundleBuilder::new(env, dsnp_user_id_1, schema_id)
+			.with_page(1, &connections_1, &vec![], 1000)
+			.build();
+		state.import_users_data(&vec![input1]).expect("should import!");
+		let actions = vec![
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id_1,
+				connection: Connection { dsnp_user_id: 1000, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+			Action::Connect {
+				owner_dsnp_user_id: dsnp_user_id_1,
+				connection: Connection { dsnp_user_id: 2, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+			Action::Disconnect {
+				owner_dsnp_user_id: dsnp_user_id_1,
+				connection: Connection { dsnp_user_id: 10, schema_id },
+			},
+		];
+
+		// act
+		let res = state.apply_actions(
+			&actions,
+			&Some(ActionOptions { collect_errors: true, ..ActionOptions::default() }),
+		);
+
+		// assert
+		let failed_actions = match res {
+			Err(DsnpGraphError::ActionsFailed(failed)) => failed,
+			_ => panic!("should fail with all failed actions"),
+		};
+		let failed_indices: Vec<_> = failed_actions.iter().map(|(i, _)| *i).collect();
+		assert_eq!(failed_indices, vec![1, 2]);
+		assert!(matches!(failed_actions[0].1, DsnpGraphError::ConnectionAlreadyExists(1, 2)));
+		assert!(matches!(failed_actions[1].1, DsnpGraphError::ConnectionDoesNotExist(1, 10)));
+		let connections = state
+			.get_connections_for_user_graph(&dsnp_user_id_1, &schema_id, true)
+			.expect("should work");
+		assert!(!connections.iter().any(|e| e.user_id == 1000));
+	}
+
+	#[test]
+	fn api_apply_actions_with_require_counterpart_keys_should_fail_upfront_for_missing_keys() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let public_schema_id =
+			get_schema_from(env.clone(), ConnectionType::Follow(PrivacyType::Public));
+		let mut state = GraphState::new(env);
+		let (key_pair_raw, _, keypair) = create_new_keys(0);
+		state
+			.import_public_keys(
+				&3,
+				vec![DsnpPublicKey { key: key_pair_raw.public_key.to_vec(), key_id: Some(0) }],
+			)
+			.expect("should import");
+		let connect = |dsnp_user_id, schema_id, dsnp_keys| Action::Connect {
+			owner_dsnp_user_id: 1,
+			connection: Connection { dsnp_user_id, schema_id },
+			dsnp_keys,
+			preferred_page_id: None,
+			since: None,
+		};
+		let actions = vec![
+			connect(2, schema_id, None),
+			connect(3, schema_id, None),
+			connect(
+				4,
+				schema_id,
+				Some(DsnpKeys {
+					keys: KeyDataBuilder::new().with_key_pairs(&vec![keypair]).build(),
+					keys_hash: 1,
+					dsnp_user_id: 4,
+				}),
+			),
+			connect(5, public_schema_id, None),
+		];
+		let options = ActionOptions { require_counterpart_keys: true, ..ActionOptions::default() };
+
+		// act
+		let res = state.apply_actions(&actions, &Some(options.clone()));
+		let collected = state.apply_actions(
+			&actions,
+			&Some(ActionOptions { collect_errors: true, ..options.clone() }),
+		);
+
+		// assert
+		assert!(matches!(res, Err(DsnpGraphError::NoPublicKeyFoundForUser(2))));
+		let failed_actions = match collected {
+			Err(DsnpGraphError::ActionsFailed(failed)) => failed,
+			_ => panic!("should fail with all failed actions"),
+		};
+		assert_eq!(failed_actions.len(), 1);
+		assert!(matches!(failed_actions[0], (0, DsnpGraphError::NoPublicKeyFoundForUser(2))));
+		assert!(state.apply_actions(&actions[1..], &Some(options)).is_ok());
+		let connections: HashSet<_> = state
+			.get_connections_for_user_graph(&1, &schema_id, true)
+			.expect("should work")
+			.iter()
+			.map(|e| e.user_id)
+			.collect();
+		assert_eq!(connections, HashSet::from([3, 4]));
+	}
+
+	#[test]
+	fn api_apply_actions_with_require_counterpart_keys_should_check_both_users_of_connect_mutual() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let mut state = GraphState::new(env.clone());
+		let (_, resolved_key_1, keypair_1) = create_new_keys(0);
+		let input1 = ImportBundleBuilder::new(env.clone(), 1, schema_id)
+			.with_key_pairs(&vec![keypair_1])
+			.with_encryption_key(resolved_key_1)
+			.build();
+		let input2 = ImportBundleBuilder::new(env.clone(), 2, schema_id).build();
+		state.import_users_data(&vec![input1, input2]).expect("should import!");
+		let options =
+			Some(ActionOptions { require_counterpart_keys: true, ..ActionOptions::default() });
+
+		// act
+		let user_b_without_keys = state.apply_actions(
+			&vec![Action::ConnectMutual { user_a: 1, user_b: 2, schema_id, since: None }],
+			&options,
+		);
+		let user_a_without_keys = state.apply_actions(
+			&vec![Action::ConnectMutual { user_a: 2, user_b: 1, schema_id, since: None }],
+			&options,
+		);
+
+		// assert
+		assert!(matches!(user_b_without_keys, Err(DsnpGraphError::NoPublicKeyFoundForUser(2))));
+		assert!(matches!(user_a_without_keys, Err(DsnpGraphError::NoPublicKeyFoundForUser(2))));
+		let connections = state.get_connections_for_user_graph(&1, &schema_id, true).unwrap();
+		assert!(connections.is_empty());
+	}
+
 	#[test]
 	fn api_apply_actions_with_duplicate_connection_for_user_fails() {
 		// arrange
@@ -848,6 +1292,8 @@ mod integration_tests {
 			owner_dsnp_user_id,
 			connection: Connection { schema_id, dsnp_user_id: 1 },
 			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
 		};
 
 		let mut state = GraphState::new(env);
@@ -862,6 +1308,10 @@ mod integration_tests {
 					ignore_existing_connections: true,
 					ignore_missing_connections: false,
 					disable_auto_commit: false,
+					collect_errors: false,
+					require_counterpart_keys: false,
+					max_batch_size: None,
+					timestamp_policy: TimestampPolicy::default(),
 				})
 			)
 			.is_ok());
@@ -897,6 +1347,10 @@ mod integration_tests {
 					ignore_existing_connections: false,
 					ignore_missing_connections: true,
 					disable_auto_commit: false,
+					collect_errors: false,
+					require_counterpart_keys: false,
+					max_batch_size: None,
+					timestamp_policy: TimestampPolicy::default(),
 				}),
 			)
 			.is_ok());
@@ -947,6 +1401,8 @@ mod integration_tests {
 				owner_dsnp_user_id: dsnp_user_id_1,
 				connection: Connection { dsnp_user_id: 6, schema_id },
 				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
 			},
 			Action::Disconnect {
 				owner_dsnp_user_id: dsnp_user_id_1,
@@ -1010,6 +1466,8 @@ mod integration_tests {
 				owner_dsnp_user_id: dsnp_user_id_1,
 				connection: Connection { dsnp_user_id: 6, schema_id },
 				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
 			},
 			Action::Disconnect {
 				owner_dsnp_user_id: dsnp_user_id_1,
@@ -1125,6 +1583,8 @@ mod integration_tests {
 					keys_hash: 1,
 					dsnp_user_id: 4,
 				}),
+				preferred_page_id: None,
+				since: None,
 			},
 			Action::Disconnect {
 				owner_dsnp_user_id: dsnp_user_id_1,
@@ -1160,6 +1620,256 @@ mod integration_tests {
 		assert_eq!(new_connections, expected_connections);
 	}
 
+	#[test]
+	fn api_apply_actions_connect_mutual_should_export_updates_for_both_users() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let mut state = GraphState::new(env.clone());
+		let (_, resolved_key_1, keypair_1) = create_new_keys(0);
+		let (_, resolved_key_2, keypair_2) = create_new_keys(0);
+		let input1 = ImportBundleBuilder::new(env.clone(), 1, schema_id)
+			.with_key_pairs(&vec![keypair_1])
+			.with_encryption_key(resolved_key_1)
+			.build();
+		let input2 = ImportBundleBuilder::new(env.clone(), 2, schema_id)
+			.with_key_pairs(&vec![keypair_2])
+			.with_encryption_key(resolved_key_2)
+			.build();
+		state.import_users_data(&vec![input1, input2]).expect("should import!");
+		let actions = vec![Action::ConnectMutual { user_a: 1, user_b: 2, schema_id, since: None }];
+
+		// act
+		let res = state.apply_actions(&actions, &None);
+
+		// assert
+		assert!(res.is_ok());
+		for (owner, friend) in [(1, 2), (2, 1)] {
+			let connections =
+				state.get_connections_for_user_graph(&owner, &schema_id, true).unwrap();
+			assert_eq!(connections.iter().map(|e| e.user_id).collect::<Vec<_>>(), vec![friend]);
+		}
+		let owners: HashSet<DsnpUserId> = state
+			.export_updates()
+			.expect("should export")
+			.into_iter()
+			.filter_map(|update| match update {
+				Update::PersistPage { owner_dsnp_user_id, .. } => Some(owner_dsnp_user_id),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(owners, HashSet::from([1, 2]));
+	}
+
+	#[test]
+	fn api_apply_actions_connect_mutual_with_existing_connection_should_not_update_any_user() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let mut state = GraphState::new(env.clone());
+		let (_, resolved_key_1, keypair_1) = create_new_keys(0);
+		let (_, resolved_key_2, keypair_2) = create_new_keys(0);
+		let input1 = ImportBundleBuilder::new(env.clone(), 1, schema_id)
+			.with_key_pairs(&vec![keypair_1])
+			.with_encryption_key(resolved_key_1)
+			.build();
+		let input2 = ImportBundleBuilder::new(env.clone(), 2, schema_id)
+			.with_key_pairs(&vec![keypair_2])
+			.with_encryption_key(resolved_key_2)
+			.build();
+		state.import_users_data(&vec![input1, input2]).expect("should import!");
+		let actions = vec![
+			Action::Connect {
+				owner_dsnp_user_id: 2,
+				connection: Connection { dsnp_user_id: 1, schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+			Action::ConnectMutual { user_a: 1, user_b: 2, schema_id, since: None },
+		];
+		let options = Some(ActionOptions {
+			collect_errors: true,
+			disable_auto_commit: true,
+			..ActionOptions::default()
+		});
+
+		// act
+		let res = state.apply_actions(&actions, &options);
+
+		// assert
+		match res {
+			Err(DsnpGraphError::ActionsFailed(failed)) => {
+				assert_eq!(failed.len(), 1);
+				assert_eq!(failed[0].0, 1);
+				assert!(matches!(failed[0].1, DsnpGraphError::ConnectionAlreadyExists(2, 1)));
+			},
+			_ => panic!("expected connect mutual to fail"),
+		}
+		let connections = state.get_connections_for_user_graph(&1, &schema_id, true).unwrap();
+		assert!(connections.is_empty());
+	}
+
+	#[test]
+	fn api_apply_actions_connect_mutual_with_not_imported_user_should_fail() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let mut state = GraphState::new(env.clone());
+		let (_, resolved_key, keypair) = create_new_keys(0);
+		let input = ImportBundleBuilder::new(env.clone(), 1, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.build();
+		state.import_users_data(&vec![input]).expect("should import!");
+		let actions = vec![Action::ConnectMutual { user_a: 1, user_b: 2, schema_id, since: None }];
+
+		// act
+		let res = state.apply_actions(&actions, &None);
+
+		// assert
+		assert!(matches!(res, Err(DsnpGraphError::UserGraphNotImported(2))));
+		assert!(!state.contains_user_graph(&2));
+		let connections = state.get_connections_for_user_graph(&1, &schema_id, true).unwrap();
+		assert!(connections.is_empty());
+	}
+
+	#[test]
+	fn api_apply_actions_connect_mutual_private_friendship_without_key_pair_should_fail() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let mut state = GraphState::new(env.clone());
+		let (_, resolved_key, keypair) = create_new_keys(0);
+		let input1 = ImportBundleBuilder::new(env.clone(), 1, schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.build();
+		let input2 = ImportBundleBuilder::new(env.clone(), 2, schema_id).build();
+		state.import_users_data(&vec![input1, input2]).expect("should import!");
+		let actions = vec![Action::ConnectMutual { user_a: 1, user_b: 2, schema_id, since: None }];
+
+		// act
+		let res = state.apply_actions(&actions, &None);
+
+		// assert
+		assert!(matches!(res, Err(DsnpGraphError::NoResolvedActiveKeyFound)));
+		let connections = state.get_connections_for_user_graph(&1, &schema_id, true).unwrap();
+		assert!(connections.is_empty());
+	}
+
+	#[test]
+	fn api_apply_actions_connect_mutual_with_follow_schema_should_fail() {
+		// arrange
+		let env = Environment::Mainnet;
+		let schema_id = get_schema_from(env.clone(), ConnectionType::Follow(PrivacyType::Public));
+		let mut state = GraphState::new(env);
+		let actions = vec![Action::ConnectMutual { user_a: 1, user_b: 2, schema_id, since: None }];
+
+		// act
+		let res = state.apply_actions(&actions, &None);
+
+		// assert
+		assert!(matches!(res, Err(DsnpGraphError::InvalidSchemaId(id, _)) if id == schema_id));
+		assert_eq!(state.len(), 0);
+	}
+
+	#[test]
+	fn api_apply_actions_migrate_connection_should_move_connection_to_private_graph() {
+		// arrange
+		let env = Environment::Mainnet;
+		let public_schema_id =
+			get_schema_from(env.clone(), ConnectionType::Follow(PrivacyType::Public));
+		let private_schema_id =
+			get_schema_from(env.clone(), ConnectionType::Follow(PrivacyType::Private));
+		let mut state = GraphState::new(env.clone());
+		let (_, resolved_key, keypair) = create_new_keys(0);
+		let public_input = ImportBundleBuilder::new(env.clone(), 1, public_schema_id)
+			.with_page(1, &vec![(2, 1), (3, 2)], &vec![], 1)
+			.build();
+		let private_input = ImportBundleBuilder::new(env.clone(), 1, private_schema_id)
+			.with_key_pairs(&vec![keypair])
+			.with_encryption_key(resolved_key)
+			.build();
+		state
+			.import_users_data(&vec![public_input, private_input])
+			.expect("should import!");
+		let actions = vec![Action::MigrateConnection {
+			owner_dsnp_user_id: 1,
+			from_schema_id: public_schema_id,
+			to_schema_id: private_schema_id,
+			dsnp_user_id: 2,
+			since: None,
+		}];
+
+		// act
+		let res = state.apply_actions(&actions, &None);
+
+		// assert
+		assert!(res.is_ok());
+		let public_connections =
+			state.get_connections_for_user_graph(&1, &public_schema_id, true).unwrap();
+		let private_connections =
+			state.get_connections_for_user_graph(&1, &private_schema_id, true).unwrap();
+		assert_eq!(public_connections.iter().map(|e| e.user_id).collect::<Vec<_>>(), vec![3]);
+		assert_eq!(private_connections.iter().map(|e| e.user_id).collect::<Vec<_>>(), vec![2]);
+		let schemas: HashSet<_> = state
+			.export_updates()
+			.expect("should export")
+			.into_iter()
+			.filter_map(|update| match update {
+				Update::PersistPage { schema_id, .. } => Some(schema_id),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(schemas, HashSet::from([public_schema_id, private_schema_id]));
+	}
+
+	#[test]
+	fn api_apply_actions_migrate_connection_with_invalid_schemas_or_connection_should_fail() {
+		// arrange
+		let env = Environment::Mainnet;
+		let public_follow =
+			get_schema_from(env.clone(), ConnectionType::Follow(PrivacyType::Public));
+		let private_follow =
+			get_schema_from(env.clone(), ConnectionType::Follow(PrivacyType::Private));
+		let private_friendship =
+			get_schema_from(env.clone(), ConnectionType::Friendship(PrivacyType::Private));
+		let mut state = GraphState::new(env);
+		let migrate = |from_schema_id, to_schema_id| Action::MigrateConnection {
+			owner_dsnp_user_id: 1,
+			from_schema_id,
+			to_schema_id,
+			dsnp_user_id: 2,
+			since: None,
+		};
+		let options = Some(ActionOptions { collect_errors: true, ..ActionOptions::default() });
+
+		// act
+		let res = state.apply_actions(
+			&vec![
+				migrate(public_follow, private_friendship),
+				migrate(public_follow, private_follow),
+			],
+			&options,
+		);
+
+		// assert
+		match res {
+			Err(DsnpGraphError::ActionsFailed(failed)) => {
+				assert_eq!(failed.len(), 2);
+				assert!(matches!(failed[0].1, DsnpGraphError::IncorrectConnectionType(_)));
+				assert!(matches!(failed[1].1, DsnpGraphError::ConnectionDoesNotExist(1, 2)));
+			},
+			_ => panic!("expected migrate connection to fail"),
+		}
+		assert_eq!(state.len(), 0);
+	}
+
 	#[test]
 	fn api_export_updates_without_updates_to_graph_should_be_empty() {
 		// arrange
@@ -1205,6 +1915,8 @@ mod integration_tests {
 			owner_dsnp_user_id: dsnp_user_id_2,
 			connection: Connection { dsnp_user_id: 10, schema_id },
 			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
 		}];
 		state.apply_actions(&actions, &None).expect("Should apply actions!");
 
@@ -1229,6 +1941,50 @@ mod integration_tests {
 		assert_eq!(sorted_connections, mapped);
 	}
 
+	#[test]
+	fn api_export_updates_filtered_should_include_only_specified_schema_updates() {
+		// arrange
+		let env = Environment::Mainnet;
+		let public_schema_id =
+			get_schema_from(env.clone(), ConnectionType::Follow(PrivacyType::Public));
+		let private_schema_id =
+			get_schema_from(env.clone(), ConnectionType::Follow(PrivacyType::Private));
+		let key_schema_id = env.get_config().graph_public_key_schema_id;
+		let mut state = GraphState::new(env.clone());
+		let (_, _, keypair) = create_new_keys(0);
+		let actions = vec![
+			Action::Connect {
+				owner_dsnp_user_id: 1,
+				connection: Connection { dsnp_user_id: 10, schema_id: public_schema_id },
+				dsnp_keys: None,
+				preferred_page_id: None,
+				since: None,
+			},
+			Action::AddGraphKey { owner_dsnp_user_id: 2, new_public_key: keypair.public_key },
+		];
+		state.apply_actions(&actions, &None).expect("Should apply actions!");
+
+		// act
+		let public_updates = state.export_updates_filtered(&[public_schema_id]);
+		let key_updates = state.export_updates_filtered(&[key_schema_id]);
+		let private_updates = state.export_updates_filtered(&[private_schema_id]);
+		let all_updates = state.export_updates_filtered(&[public_schema_id, key_schema_id]);
+		let unknown = state.export_updates_filtered(&[9999]);
+
+		// assert
+		assert!(matches!(
+			public_updates.expect("should export").as_slice(),
+			[Update::PersistPage { owner_dsnp_user_id: 1, schema_id, .. }] if *schema_id == public_schema_id
+		));
+		assert!(matches!(
+			key_updates.expect("should export").as_slice(),
+			[Update::AddKey { owner_dsnp_user_id: 2, .. }]
+		));
+		assert!(private_updates.expect("should export").is_empty());
+		assert_eq!(all_updates.expect("should export").len(), 2);
+		assert!(matches!(unknown, Err(DsnpGraphError::InvalidSchemaId(9999, _))));
+	}
+
 	#[test]
 	fn api_export_updates_for_private_friendship_graph_without_imported_connection_keys_should_fail(
 	) {
@@ -1250,6 +2006,8 @@ mod integration_tests {
 			owner_dsnp_user_id: dsnp_user_id_1,
 			connection: Connection { dsnp_user_id: 4, schema_id },
 			dsnp_keys: None,
+			preferred_page_id: None,
+			since: None,
 		}];
 		state.apply_actions(&actions, &None).expect("Should apply actions!");
 
@@ -38,6 +38,7 @@ dependencies {
     // These dependencies are used only for junit tests
     testImplementation("org.junit.jupiter:junit-jupiter:5.9.1")
     testImplementation("io.github.hakky54:logcaptor:2.9.0")
+    testImplementation("com.google.code.gson:gson:2.10.1")
 
     implementation("org.slf4j:slf4j-api:2.0.7")
     api("com.google.protobuf:protobuf-java:3.23.0")
@@ -16,6 +16,7 @@ public class Configuration {
     private final Config inner;
     private final Environment environment;
     private final HashMap<ConnectionType, Integer> schemaIdMap = new HashMap<>();
+    private String customEnvironmentName;
 
     public Configuration(Config config) {
         this.inner = config;
@@ -49,6 +50,24 @@ public class Configuration {
         return this.environment;
     }
 
+    /**
+     * @return name of the registered custom environment, or null if this is not a custom environment
+     */
+    public String getCustomEnvironmentName() {
+        return this.customEnvironmentName;
+    }
+
+    /**
+     * Registers the config under the given name, so that graphs created with the returned
+     * configuration use the registered custom environment
+     */
+    public static Configuration registerCustom(String name, Config config) {
+        Native.registerEnvironmentConfig(name, config.toByteArray());
+        var configuration = new Configuration(config);
+        configuration.customEnvironmentName = name;
+        return configuration;
+    }
+
     public static Configuration getMainNet() throws InvalidProtocolBufferException {
         if(MAIN_NET_INSTANCE == null) {
             MAIN_NET_INSTANCE = new Configuration(EnvironmentType.MainNet);
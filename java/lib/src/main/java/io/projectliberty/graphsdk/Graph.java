@@ -13,7 +13,10 @@ public class Graph implements NativeHandleGuard.Owner {
 
     public Graph(Configuration configuration) throws BaseGraphSdkException {
         this.configuration = configuration;
-        this.unsafeHandle = Native.initializeGraphState(configuration.getEnvironment().toByteArray());
+        var customEnvironmentName = configuration.getCustomEnvironmentName();
+        this.unsafeHandle = customEnvironmentName != null
+                ? Native.initializeCustomGraphState(customEnvironmentName)
+                : Native.initializeGraphState(configuration.getEnvironment().toByteArray());
     }
 
     public void setUnsafeHandle(long unsafeHandle) {
@@ -26,6 +29,12 @@ public class Graph implements NativeHandleGuard.Owner {
         }
     }
 
+    public boolean isUserGraphWritable(long dsnpUserId, int schemaId) throws BaseGraphSdkException {
+        try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
+            return Native.isUserGraphWritable(guard.nativeHandle(), dsnpUserId, schemaId);
+        }
+    }
+
     public int getUsersLength() throws BaseGraphSdkException {
         try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
             return Native.getGraphUsersLength(guard.nativeHandle());
@@ -65,6 +74,20 @@ public class Graph implements NativeHandleGuard.Owner {
         }
     }
 
+    public void applyActions(Actions actions, ProgressListener listener) throws BaseGraphSdkException {
+        try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
+            Native.applyActionsWithProgress(guard.nativeHandle(), actions.toByteArray(), listener);
+        }
+    }
+
+    public List<Updates.Update> applyActionsAndExport(Actions actions)
+            throws BaseGraphSdkException, InvalidProtocolBufferException {
+        try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
+            var raw = Native.applyActionsAndExport(guard.nativeHandle(), actions.toByteArray());
+            return Updates.parseFrom(raw).getUpdateList();
+        }
+    }
+
     public void commit() throws BaseGraphSdkException {
         try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
             Native.commit(guard.nativeHandle());
@@ -85,6 +108,14 @@ public class Graph implements NativeHandleGuard.Owner {
         }
     }
 
+    public List<Updates.Update> exportGraphRemoval(long dsnpUserId)
+            throws BaseGraphSdkException, InvalidProtocolBufferException {
+        try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
+            var raw = Native.exportGraphRemoval(guard.nativeHandle(), dsnpUserId);
+            return Updates.parseFrom(raw).getUpdateList();
+        }
+    }
+
     public List<DsnpGraphEdges.DsnpGraphEdge> getConnections(long dsnpUserId, ConnectionType connectionType,
             boolean includePending) throws BaseGraphSdkException, InvalidProtocolBufferException {
         try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
@@ -94,18 +125,29 @@ public class Graph implements NativeHandleGuard.Owner {
         }
     }
 
-    public List<Long> getUsersWithoutImportedKeys() throws BaseGraphSdkException, InvalidProtocolBufferException {
+    public List<Long> getUsersWithoutImportedKeys(boolean includePending)
+            throws BaseGraphSdkException, InvalidProtocolBufferException {
+        try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
+            var raw = Native.getUsersWithoutKeys(guard.nativeHandle(), includePending);
+            return DsnpUsers.parseFrom(raw).getUserList();
+        }
+    }
+
+    public List<Long> getUsersWithoutImportedKeys(boolean includePending, Long cursor, int limit)
+            throws BaseGraphSdkException, InvalidProtocolBufferException {
         try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
-            var raw = Native.getUsersWithoutKeys(guard.nativeHandle());
+            var raw = Native.getUsersWithoutKeysPage(guard.nativeHandle(), includePending,
+                    cursor == null ? -1 : cursor, limit);
             return DsnpUsers.parseFrom(raw).getUserList();
         }
     }
 
     // TODO: add test
-    public List<DsnpGraphEdges.DsnpGraphEdge> getOneSidedPrivateFriendships(long dsnpUserId)
+    public List<DsnpGraphEdges.DsnpGraphEdge> getOneSidedPrivateFriendships(long dsnpUserId, boolean includePending)
             throws BaseGraphSdkException, InvalidProtocolBufferException {
         try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
-            var raw = Native.getOneSidedPrivateFriendshipConnections(guard.nativeHandle(), dsnpUserId);
+            var raw = Native.getOneSidedPrivateFriendshipConnections(guard.nativeHandle(), dsnpUserId,
+                    includePending);
             return DsnpGraphEdges.parseFrom(raw).getEdgeList();
         }
     }
@@ -118,6 +160,25 @@ public class Graph implements NativeHandleGuard.Owner {
         }
     }
 
+    public KeyPageStats getKeyPageStats(long dsnpUserId) throws BaseGraphSdkException {
+        try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
+            var raw = Native.getKeyPageStats(guard.nativeHandle(), dsnpUserId);
+            return new KeyPageStats(raw[0], raw[1], raw[2]);
+        }
+    }
+
+    public long getNextKeyId(long dsnpUserId) throws BaseGraphSdkException {
+        try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
+            return Native.getNextKeyId(guard.nativeHandle(), dsnpUserId);
+        }
+    }
+
+    public void setAdditionalRecipientKeys(long dsnpUserId, long[] keyIds) throws BaseGraphSdkException {
+        try (NativeHandleGuard guard = new NativeHandleGuard(this)) {
+            Native.setAdditionalRecipientKeys(guard.nativeHandle(), dsnpUserId, keyIds);
+        }
+    }
+
     public static List<DsnpPublicKeys.DsnpPublicKey> deserializeDsnpKeys(DsnpKeys keys)
             throws BaseGraphSdkException, InvalidProtocolBufferException {
         var raw = Native.deserializeDsnpKeys(keys.toByteArray());
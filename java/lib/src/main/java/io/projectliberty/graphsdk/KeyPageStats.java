@@ -0,0 +1,11 @@
+package io.projectliberty.graphsdk;
+
+/**
+ * Key count and utilization of the key page of a user
+ *
+ * @param keyCount  number of imported and newly added keys
+ * @param usedBytes bytes used by all keys inside the itemized key page
+ * @param maxBytes  maximum allowed size of the key page
+ */
+public record KeyPageStats(int keyCount, int usedBytes, int maxBytes) {
+}
@@ -102,12 +102,18 @@ public final class Native {
 
     public static native long initializeGraphState(byte[] environment);
 
+    public static native long initializeCustomGraphState(String name);
+
+    public static native void registerEnvironmentConfig(String name, byte[] config);
+
     public static native long freeGraphState(long stateHandle);
 
     public static native byte[] getConfig(byte[] environment);
 
     public static native boolean containsUserGraph(long stateHandle, long dsnpUserId);
 
+    public static native boolean isUserGraphWritable(long stateHandle, long dsnpUserId, int schemaId);
+
     public static native int getGraphUsersLength(long stateHandle);
 
     public static native void removeUserGraph(long stateHandle, long dsnpUserId);
@@ -120,21 +126,37 @@ public final class Native {
 
     public static native void applyActions(long stateHandle, byte[] actions);
 
+    public static native void applyActionsWithProgress(long stateHandle, byte[] actions, ProgressListener listener);
+
+    public static native byte[] applyActionsAndExport(long stateHandle, byte[] actions);
+
     public static native void commit(long stateHandle);
 
     public static native void rollback(long stateHandle);
 
     public static native byte[] forceCalculateGraphs(long stateHandle, long dsnpUserId);
 
+    public static native byte[] exportGraphRemoval(long stateHandle, long dsnpUserId);
+
     public static native byte[] getConnectionsForUserGraph(long stateHandle, long dsnpUserId, int schemaId,
             boolean includePending);
 
-    public static native byte[] getUsersWithoutKeys(long stateHandle);
+    public static native byte[] getUsersWithoutKeys(long stateHandle, boolean includePending);
 
-    public static native byte[] getOneSidedPrivateFriendshipConnections(long stateHandle, long dsnpUserId);
+    public static native byte[] getUsersWithoutKeysPage(long stateHandle, boolean includePending, long cursor,
+            int limit);
+
+    public static native byte[] getOneSidedPrivateFriendshipConnections(long stateHandle, long dsnpUserId,
+            boolean includePending);
 
     public static native byte[] getPublicKeys(long stateHandle, long dsnpUserId);
 
+    public static native int[] getKeyPageStats(long stateHandle, long dsnpUserId);
+
+    public static native long getNextKeyId(long stateHandle, long dsnpUserId);
+
+    public static native void setAdditionalRecipientKeys(long stateHandle, long dsnpUserId, long[] keyIds);
+
     public static native byte[] deserializeDsnpKeys(byte[] dsnpKeys);
 
     public static native byte[] generateKeyPair(long graph_key_type);
@@ -0,0 +1,14 @@
+package io.projectliberty.graphsdk;
+
+/**
+ * Receives the fraction of processed actions while applying a batch of actions
+ */
+@FunctionalInterface
+public interface ProgressListener {
+    /**
+     * Called on the applying thread after each processed chunk of actions
+     *
+     * @param fraction fraction of processed actions between 0 and 1
+     */
+    void onProgress(double fraction);
+}
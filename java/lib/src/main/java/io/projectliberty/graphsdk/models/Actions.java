@@ -65,6 +65,37 @@ private static final long serialVersionUID = 0L;
      * @return The disableAutoCommit.
      */
     boolean getDisableAutoCommit();
+
+    /**
+     * <code>.TimestampPolicy timestamp_policy = 4;</code>
+     * @return The enum numeric value on the wire for timestampPolicy.
+     */
+    int getTimestampPolicyValue();
+    /**
+     * <code>.TimestampPolicy timestamp_policy = 4;</code>
+     * @return The timestampPolicy.
+     */
+    io.projectliberty.graphsdk.models.TimestampPolicy getTimestampPolicy();
+
+    /**
+     * <pre>
+     * maximum number of actions in a call, 0 for no limit
+     * </pre>
+     *
+     * <code>uint32 max_batch_size = 5;</code>
+     * @return The maxBatchSize.
+     */
+    int getMaxBatchSize();
+
+    /**
+     * <pre>
+     * attempt all actions and fail with every failing action instead of stopping on the first one
+     * </pre>
+     *
+     * <code>bool collect_errors = 6;</code>
+     * @return The collectErrors.
+     */
+    boolean getCollectErrors();
   }
   /**
    * Protobuf type {@code Actions.ActionOptions}
@@ -79,6 +110,7 @@ private static final long serialVersionUID = 0L;
       super(builder);
     }
     private ActionOptions() {
+      timestampPolicy_ = 0;
     }
 
     @java.lang.Override
@@ -139,6 +171,54 @@ private static final long serialVersionUID = 0L;
       return disableAutoCommit_;
     }
 
+    public static final int TIMESTAMP_POLICY_FIELD_NUMBER = 4;
+    private int timestampPolicy_ = 0;
+    /**
+     * <code>.TimestampPolicy timestamp_policy = 4;</code>
+     * @return The enum numeric value on the wire for timestampPolicy.
+     */
+    @java.lang.Override public int getTimestampPolicyValue() {
+      return timestampPolicy_;
+    }
+    /**
+     * <code>.TimestampPolicy timestamp_policy = 4;</code>
+     * @return The timestampPolicy.
+     */
+    @java.lang.Override public io.projectliberty.graphsdk.models.TimestampPolicy getTimestampPolicy() {
+      io.projectliberty.graphsdk.models.TimestampPolicy result = io.projectliberty.graphsdk.models.TimestampPolicy.forNumber(timestampPolicy_);
+      return result == null ? io.projectliberty.graphsdk.models.TimestampPolicy.UNRECOGNIZED : result;
+    }
+
+    public static final int MAX_BATCH_SIZE_FIELD_NUMBER = 5;
+    private int maxBatchSize_ = 0;
+    /**
+     * <pre>
+     * maximum number of actions in a call, 0 for no limit
+     * </pre>
+     *
+     * <code>uint32 max_batch_size = 5;</code>
+     * @return The maxBatchSize.
+     */
+    @java.lang.Override
+    public int getMaxBatchSize() {
+      return maxBatchSize_;
+    }
+
+    public static final int COLLECT_ERRORS_FIELD_NUMBER = 6;
+    private boolean collectErrors_ = false;
+    /**
+     * <pre>
+     * attempt all actions and fail with every failing action instead of stopping on the first one
+     * </pre>
+     *
+     * <code>bool collect_errors = 6;</code>
+     * @return The collectErrors.
+     */
+    @java.lang.Override
+    public boolean getCollectErrors() {
+      return collectErrors_;
+    }
+
     private byte memoizedIsInitialized = -1;
     @java.lang.Override
     public final boolean isInitialized() {
@@ -162,6 +242,15 @@ private static final long serialVersionUID = 0L;
       if (disableAutoCommit_ != false) {
         output.writeBool(3, disableAutoCommit_);
       }
+      if (timestampPolicy_ != io.projectliberty.graphsdk.models.TimestampPolicy.CallerProvided.getNumber()) {
+        output.writeEnum(4, timestampPolicy_);
+      }
+      if (maxBatchSize_ != 0) {
+        output.writeUInt32(5, maxBatchSize_);
+      }
+      if (collectErrors_ != false) {
+        output.writeBool(6, collectErrors_);
+      }
       getUnknownFields().writeTo(output);
     }
 
@@ -183,6 +272,18 @@ private static final long serialVersionUID = 0L;
         size += com.google.protobuf.CodedOutputStream
           .computeBoolSize(3, disableAutoCommit_);
       }
+      if (timestampPolicy_ != io.projectliberty.graphsdk.models.TimestampPolicy.CallerProvided.getNumber()) {
+        size += com.google.protobuf.CodedOutputStream
+          .computeEnumSize(4, timestampPolicy_);
+      }
+      if (maxBatchSize_ != 0) {
+        size += com.google.protobuf.CodedOutputStream
+          .computeUInt32Size(5, maxBatchSize_);
+      }
+      if (collectErrors_ != false) {
+        size += com.google.protobuf.CodedOutputStream
+          .computeBoolSize(6, collectErrors_);
+      }
       size += getUnknownFields().getSerializedSize();
       memoizedSize = size;
       return size;
@@ -204,6 +305,11 @@ private static final long serialVersionUID = 0L;
           != other.getIgnoreMissingConnections()) return false;
       if (getDisableAutoCommit()
           != other.getDisableAutoCommit()) return false;
+      if (timestampPolicy_ != other.timestampPolicy_) return false;
+      if (getMaxBatchSize()
+          != other.getMaxBatchSize()) return false;
+      if (getCollectErrors()
+          != other.getCollectErrors()) return false;
       if (!getUnknownFields().equals(other.getUnknownFields())) return false;
       return true;
     }
@@ -224,6 +330,13 @@ private static final long serialVersionUID = 0L;
       hash = (37 * hash) + DISABLE_AUTO_COMMIT_FIELD_NUMBER;
       hash = (53 * hash) + com.google.protobuf.Internal.hashBoolean(
           getDisableAutoCommit());
+      hash = (37 * hash) + TIMESTAMP_POLICY_FIELD_NUMBER;
+      hash = (53 * hash) + timestampPolicy_;
+      hash = (37 * hash) + MAX_BATCH_SIZE_FIELD_NUMBER;
+      hash = (53 * hash) + getMaxBatchSize();
+      hash = (37 * hash) + COLLECT_ERRORS_FIELD_NUMBER;
+      hash = (53 * hash) + com.google.protobuf.Internal.hashBoolean(
+          getCollectErrors());
       hash = (29 * hash) + getUnknownFields().hashCode();
       memoizedHashCode = hash;
       return hash;
@@ -356,6 +469,9 @@ private static final long serialVersionUID = 0L;
         ignoreExistingConnections_ = false;
         ignoreMissingConnections_ = false;
         disableAutoCommit_ = false;
+        timestampPolicy_ = 0;
+        maxBatchSize_ = 0;
+        collectErrors_ = false;
         return this;
       }
 
@@ -398,6 +514,15 @@ private static final long serialVersionUID = 0L;
         if (((from_bitField0_ & 0x00000004) != 0)) {
           result.disableAutoCommit_ = disableAutoCommit_;
         }
+        if (((from_bitField0_ & 0x00000008) != 0)) {
+          result.timestampPolicy_ = timestampPolicy_;
+        }
+        if (((from_bitField0_ & 0x00000010) != 0)) {
+          result.maxBatchSize_ = maxBatchSize_;
+        }
+        if (((from_bitField0_ & 0x00000020) != 0)) {
+          result.collectErrors_ = collectErrors_;
+        }
       }
 
       @java.lang.Override
@@ -453,6 +578,15 @@ private static final long serialVersionUID = 0L;
         if (other.getDisableAutoCommit() != false) {
           setDisableAutoCommit(other.getDisableAutoCommit());
         }
+        if (other.timestampPolicy_ != 0) {
+          setTimestampPolicyValue(other.getTimestampPolicyValue());
+        }
+        if (other.getMaxBatchSize() != 0) {
+          setMaxBatchSize(other.getMaxBatchSize());
+        }
+        if (other.getCollectErrors() != false) {
+          setCollectErrors(other.getCollectErrors());
+        }
         this.mergeUnknownFields(other.getUnknownFields());
         onChanged();
         return this;
@@ -494,6 +628,21 @@ private static final long serialVersionUID = 0L;
                 bitField0_ |= 0x00000004;
                 break;
               } // case 24
+              case 32: {
+                timestampPolicy_ = input.readEnum();
+                bitField0_ |= 0x00000008;
+                break;
+              } // case 32
+              case 40: {
+                maxBatchSize_ = input.readUInt32();
+                bitField0_ |= 0x00000010;
+                break;
+              } // case 40
+              case 48: {
+                collectErrors_ = input.readBool();
+                bitField0_ |= 0x00000020;
+                break;
+              } // case 48
               default: {
                 if (!super.parseUnknownField(input, extensionRegistry, tag)) {
                   done = true; // was an endgroup tag
@@ -606,6 +755,147 @@ private static final long serialVersionUID = 0L;
         onChanged();
         return this;
       }
+
+      private int timestampPolicy_ = 0;
+      /**
+       * <code>.TimestampPolicy timestamp_policy = 4;</code>
+       * @return The enum numeric value on the wire for timestampPolicy.
+       */
+      @java.lang.Override public int getTimestampPolicyValue() {
+        return timestampPolicy_;
+      }
+      /**
+       * <code>.TimestampPolicy timestamp_policy = 4;</code>
+       * @param value The enum numeric value on the wire for timestampPolicy to set.
+       * @return This builder for chaining.
+       */
+      public Builder setTimestampPolicyValue(int value) {
+        timestampPolicy_ = value;
+        bitField0_ |= 0x00000008;
+        onChanged();
+        return this;
+      }
+      /**
+       * <code>.TimestampPolicy timestamp_policy = 4;</code>
+       * @return The timestampPolicy.
+       */
+      @java.lang.Override
+      public io.projectliberty.graphsdk.models.TimestampPolicy getTimestampPolicy() {
+        io.projectliberty.graphsdk.models.TimestampPolicy result = io.projectliberty.graphsdk.models.TimestampPolicy.forNumber(timestampPolicy_);
+        return result == null ? io.projectliberty.graphsdk.models.TimestampPolicy.UNRECOGNIZED : result;
+      }
+      /**
+       * <code>.TimestampPolicy timestamp_policy = 4;</code>
+       * @param value The timestampPolicy to set.
+       * @return This builder for chaining.
+       */
+      public Builder setTimestampPolicy(io.projectliberty.graphsdk.models.TimestampPolicy value) {
+        if (value == null) {
+          throw new NullPointerException();
+        }
+        bitField0_ |= 0x00000008;
+        timestampPolicy_ = value.getNumber();
+        onChanged();
+        return this;
+      }
+      /**
+       * <code>.TimestampPolicy timestamp_policy = 4;</code>
+       * @return This builder for chaining.
+       */
+      public Builder clearTimestampPolicy() {
+        bitField0_ = (bitField0_ & ~0x00000008);
+        timestampPolicy_ = 0;
+        onChanged();
+        return this;
+      }
+
+      private int maxBatchSize_ ;
+      /**
+       * <pre>
+       * maximum number of actions in a call, 0 for no limit
+       * </pre>
+       *
+       * <code>uint32 max_batch_size = 5;</code>
+       * @return The maxBatchSize.
+       */
+      @java.lang.Override
+      public int getMaxBatchSize() {
+        return maxBatchSize_;
+      }
+      /**
+       * <pre>
+       * maximum number of actions in a call, 0 for no limit
+       * </pre>
+       *
+       * <code>uint32 max_batch_size = 5;</code>
+       * @param value The maxBatchSize to set.
+       * @return This builder for chaining.
+       */
+      public Builder setMaxBatchSize(int value) {
+
+        maxBatchSize_ = value;
+        bitField0_ |= 0x00000010;
+        onChanged();
+        return this;
+      }
+      /**
+       * <pre>
+       * maximum number of actions in a call, 0 for no limit
+       * </pre>
+       *
+       * <code>uint32 max_batch_size = 5;</code>
+       * @return This builder for chaining.
+       */
+      public Builder clearMaxBatchSize() {
+        bitField0_ = (bitField0_ & ~0x00000010);
+        maxBatchSize_ = 0;
+        onChanged();
+        return this;
+      }
+
+      private boolean collectErrors_ ;
+      /**
+       * <pre>
+       * attempt all actions and fail with every failing action instead of stopping on the first one
+       * </pre>
+       *
+       * <code>bool collect_errors = 6;</code>
+       * @return The collectErrors.
+       */
+      @java.lang.Override
+      public boolean getCollectErrors() {
+        return collectErrors_;
+      }
+      /**
+       * <pre>
+       * attempt all actions and fail with every failing action instead of stopping on the first one
+       * </pre>
+       *
+       * <code>bool collect_errors = 6;</code>
+       * @param value The collectErrors to set.
+       * @return This builder for chaining.
+       */
+      public Builder setCollectErrors(boolean value) {
+
+        collectErrors_ = value;
+        bitField0_ |= 0x00000020;
+        onChanged();
+        return this;
+      }
+      /**
+       * <pre>
+       * attempt all actions and fail with every failing action instead of stopping on the first one
+       * </pre>
+       *
+       * <code>bool collect_errors = 6;</code>
+       * @return This builder for chaining.
+       */
+      public Builder clearCollectErrors() {
+        bitField0_ = (bitField0_ & ~0x00000020);
+        collectErrors_ = false;
+        onChanged();
+        return this;
+      }
       @java.lang.Override
       public final Builder setUnknownFields(
           final com.google.protobuf.UnknownFieldSet unknownFields) {
@@ -800,6 +1090,16 @@ private static final long serialVersionUID = 0L;
        * <code>optional .DsnpKeys dsnp_keys = 3;</code>
        */
       io.projectliberty.graphsdk.models.DsnpKeysOrBuilder getDsnpKeysOrBuilder();
+
+      /**
+       * <pre>
+       * since value of the connection, 0 if not provided
+       * </pre>
+       *
+       * <code>uint64 since = 4;</code>
+       * @return The since.
+       */
+      long getSince();
     }
     /**
      * Protobuf type {@code Actions.Action.ConnectAction}
@@ -905,6 +1205,21 @@ private static final long serialVersionUID = 0L;
         return dsnpKeys_ == null ? io.projectliberty.graphsdk.models.DsnpKeys.getDefaultInstance() : dsnpKeys_;
       }
 
+      public static final int SINCE_FIELD_NUMBER = 4;
+      private long since_ = 0L;
+      /**
+       * <pre>
+       * since value of the connection, 0 if not provided
+       * </pre>
+       *
+       * <code>uint64 since = 4;</code>
+       * @return The since.
+       */
+      @java.lang.Override
+      public long getSince() {
+        return since_;
+      }
+
       private byte memoizedIsInitialized = -1;
       @java.lang.Override
       public final boolean isInitialized() {
@@ -928,6 +1243,9 @@ private static final long serialVersionUID = 0L;
         if (((bitField0_ & 0x00000001) != 0)) {
           output.writeMessage(3, getDsnpKeys());
         }
+        if (since_ != 0L) {
+          output.writeUInt64(4, since_);
+        }
         getUnknownFields().writeTo(output);
       }
 
@@ -949,6 +1267,10 @@ private static final long serialVersionUID = 0L;
           size += com.google.protobuf.CodedOutputStream
             .computeMessageSize(3, getDsnpKeys());
         }
+        if (since_ != 0L) {
+          size += com.google.protobuf.CodedOutputStream
+            .computeUInt64Size(4, since_);
+        }
         size += getUnknownFields().getSerializedSize();
         memoizedSize = size;
         return size;
@@ -976,6 +1298,8 @@ private static final long serialVersionUID = 0L;
           if (!getDsnpKeys()
               .equals(other.getDsnpKeys())) return false;
         }
+        if (getSince()
+            != other.getSince()) return false;
         if (!getUnknownFields().equals(other.getUnknownFields())) return false;
         return true;
       }
@@ -998,6 +1322,9 @@ private static final long serialVersionUID = 0L;
           hash = (37 * hash) + DSNP_KEYS_FIELD_NUMBER;
           hash = (53 * hash) + getDsnpKeys().hashCode();
         }
+        hash = (37 * hash) + SINCE_FIELD_NUMBER;
+        hash = (53 * hash) + com.google.protobuf.Internal.hashLong(
+            getSince());
         hash = (29 * hash) + getUnknownFields().hashCode();
         memoizedHashCode = hash;
         return hash;
@@ -1145,6 +1472,7 @@ private static final long serialVersionUID = 0L;
             dsnpKeysBuilder_.dispose();
             dsnpKeysBuilder_ = null;
           }
+          since_ = 0L;
           return this;
         }
 
@@ -1193,6 +1521,9 @@ private static final long serialVersionUID = 0L;
                 : dsnpKeysBuilder_.build();
             to_bitField0_ |= 0x00000001;
           }
+          if (((from_bitField0_ & 0x00000008) != 0)) {
+            result.since_ = since_;
+          }
           result.bitField0_ |= to_bitField0_;
         }
 
@@ -1249,6 +1580,9 @@ private static final long serialVersionUID = 0L;
           if (other.hasDsnpKeys()) {
             mergeDsnpKeys(other.getDsnpKeys());
           }
+          if (other.getSince() != 0L) {
+            setSince(other.getSince());
+          }
           this.mergeUnknownFields(other.getUnknownFields());
           onChanged();
           return this;
@@ -1294,6 +1628,11 @@ private static final long serialVersionUID = 0L;
                   bitField0_ |= 0x00000004;
                   break;
                 } // case 26
+                case 32: {
+                  since_ = input.readUInt64();
+                  bitField0_ |= 0x00000008;
+                  break;
+                } // case 32
                 default: {
                   if (!super.parseUnknownField(input, extensionRegistry, tag)) {
                     done = true; // was an endgroup tag
@@ -1580,6 +1919,50 @@ private static final long serialVersionUID = 0L;
           }
           return dsnpKeysBuilder_;
         }
+
+        private long since_ ;
+        /**
+         * <pre>
+         * since value of the connection, 0 if not provided
+         * </pre>
+         *
+         * <code>uint64 since = 4;</code>
+         * @return The since.
+         */
+        @java.lang.Override
+        public long getSince() {
+          return since_;
+        }
+        /**
+         * <pre>
+         * since value of the connection, 0 if not provided
+         * </pre>
+         *
+         * <code>uint64 since = 4;</code>
+         * @param value The since to set.
+         * @return This builder for chaining.
+         */
+        public Builder setSince(long value) {
+          
+          since_ = value;
+          bitField0_ |= 0x00000008;
+          onChanged();
+          return this;
+        }
+        /**
+         * <pre>
+         * since value of the connection, 0 if not provided
+         * </pre>
+         *
+         * <code>uint64 since = 4;</code>
+         * @return This builder for chaining.
+         */
+        public Builder clearSince() {
+          bitField0_ = (bitField0_ & ~0x00000008);
+          since_ = 0L;
+          onChanged();
+          return this;
+        }
         @java.lang.Override
         public final Builder setUnknownFields(
             final com.google.protobuf.UnknownFieldSet unknownFields) {
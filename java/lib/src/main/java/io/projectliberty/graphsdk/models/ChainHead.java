@@ -0,0 +1,549 @@
+// Generated by the protocol buffer compiler.  DO NOT EDIT!
+// source: bridge/common/protos/input.proto
+
+package io.projectliberty.graphsdk.models;
+
+/**
+ * <pre>
+ * block of the chain at which the imported data was read
+ * </pre>
+ *
+ * Protobuf type {@code ChainHead}
+ */
+public final class ChainHead extends
+    com.google.protobuf.GeneratedMessageV3 implements
+    // @@protoc_insertion_point(message_implements:ChainHead)
+    ChainHeadOrBuilder {
+private static final long serialVersionUID = 0L;
+  // Use ChainHead.newBuilder() to construct.
+  private ChainHead(com.google.protobuf.GeneratedMessageV3.Builder<?> builder) {
+    super(builder);
+  }
+  private ChainHead() {
+    blockHash_ = com.google.protobuf.ByteString.EMPTY;
+  }
+
+  @java.lang.Override
+  @SuppressWarnings({"unused"})
+  protected java.lang.Object newInstance(
+      UnusedPrivateParameter unused) {
+    return new ChainHead();
+  }
+
+  @java.lang.Override
+  public final com.google.protobuf.UnknownFieldSet
+  getUnknownFields() {
+    return this.unknownFields;
+  }
+  public static final com.google.protobuf.Descriptors.Descriptor
+      getDescriptor() {
+    return io.projectliberty.graphsdk.models.Input.internal_static_ChainHead_descriptor;
+  }
+
+  @java.lang.Override
+  protected com.google.protobuf.GeneratedMessageV3.FieldAccessorTable
+      internalGetFieldAccessorTable() {
+    return io.projectliberty.graphsdk.models.Input.internal_static_ChainHead_fieldAccessorTable
+        .ensureFieldAccessorsInitialized(
+            io.projectliberty.graphsdk.models.ChainHead.class, io.projectliberty.graphsdk.models.ChainHead.Builder.class);
+  }
+
+  public static final int BLOCK_NUMBER_FIELD_NUMBER = 1;
+  private int blockNumber_ = 0;
+  /**
+   * <code>uint32 block_number = 1;</code>
+   * @return The blockNumber.
+   */
+  @java.lang.Override
+  public int getBlockNumber() {
+    return blockNumber_;
+  }
+
+  public static final int BLOCK_HASH_FIELD_NUMBER = 2;
+  private com.google.protobuf.ByteString blockHash_ = com.google.protobuf.ByteString.EMPTY;
+  /**
+   * <code>bytes block_hash = 2;</code>
+   * @return The blockHash.
+   */
+  @java.lang.Override
+  public com.google.protobuf.ByteString getBlockHash() {
+    return blockHash_;
+  }
+
+  private byte memoizedIsInitialized = -1;
+  @java.lang.Override
+  public final boolean isInitialized() {
+    byte isInitialized = memoizedIsInitialized;
+    if (isInitialized == 1) return true;
+    if (isInitialized == 0) return false;
+
+    memoizedIsInitialized = 1;
+    return true;
+  }
+
+  @java.lang.Override
+  public void writeTo(com.google.protobuf.CodedOutputStream output)
+                      throws java.io.IOException {
+    if (blockNumber_ != 0) {
+      output.writeUInt32(1, blockNumber_);
+    }
+    if (!blockHash_.isEmpty()) {
+      output.writeBytes(2, blockHash_);
+    }
+    getUnknownFields().writeTo(output);
+  }
+
+  @java.lang.Override
+  public int getSerializedSize() {
+    int size = memoizedSize;
+    if (size != -1) return size;
+
+    size = 0;
+    if (blockNumber_ != 0) {
+      size += com.google.protobuf.CodedOutputStream
+        .computeUInt32Size(1, blockNumber_);
+    }
+    if (!blockHash_.isEmpty()) {
+      size += com.google.protobuf.CodedOutputStream
+        .computeBytesSize(2, blockHash_);
+    }
+    size += getUnknownFields().getSerializedSize();
+    memoizedSize = size;
+    return size;
+  }
+
+  @java.lang.Override
+  public boolean equals(final java.lang.Object obj) {
+    if (obj == this) {
+     return true;
+    }
+    if (!(obj instanceof io.projectliberty.graphsdk.models.ChainHead)) {
+      return super.equals(obj);
+    }
+    io.projectliberty.graphsdk.models.ChainHead other = (io.projectliberty.graphsdk.models.ChainHead) obj;
+
+    if (getBlockNumber()
+        != other.getBlockNumber()) return false;
+    if (!getBlockHash()
+        .equals(other.getBlockHash())) return false;
+    if (!getUnknownFields().equals(other.getUnknownFields())) return false;
+    return true;
+  }
+
+  @java.lang.Override
+  public int hashCode() {
+    if (memoizedHashCode != 0) {
+      return memoizedHashCode;
+    }
+    int hash = 41;
+    hash = (19 * hash) + getDescriptor().hashCode();
+    hash = (37 * hash) + BLOCK_NUMBER_FIELD_NUMBER;
+    hash = (53 * hash) + getBlockNumber();
+    hash = (37 * hash) + BLOCK_HASH_FIELD_NUMBER;
+    hash = (53 * hash) + getBlockHash().hashCode();
+    hash = (29 * hash) + getUnknownFields().hashCode();
+    memoizedHashCode = hash;
+    return hash;
+  }
+
+  public static io.projectliberty.graphsdk.models.ChainHead parseFrom(
+      java.nio.ByteBuffer data)
+      throws com.google.protobuf.InvalidProtocolBufferException {
+    return PARSER.parseFrom(data);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseFrom(
+      java.nio.ByteBuffer data,
+      com.google.protobuf.ExtensionRegistryLite extensionRegistry)
+      throws com.google.protobuf.InvalidProtocolBufferException {
+    return PARSER.parseFrom(data, extensionRegistry);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseFrom(
+      com.google.protobuf.ByteString data)
+      throws com.google.protobuf.InvalidProtocolBufferException {
+    return PARSER.parseFrom(data);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseFrom(
+      com.google.protobuf.ByteString data,
+      com.google.protobuf.ExtensionRegistryLite extensionRegistry)
+      throws com.google.protobuf.InvalidProtocolBufferException {
+    return PARSER.parseFrom(data, extensionRegistry);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseFrom(byte[] data)
+      throws com.google.protobuf.InvalidProtocolBufferException {
+    return PARSER.parseFrom(data);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseFrom(
+      byte[] data,
+      com.google.protobuf.ExtensionRegistryLite extensionRegistry)
+      throws com.google.protobuf.InvalidProtocolBufferException {
+    return PARSER.parseFrom(data, extensionRegistry);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseFrom(java.io.InputStream input)
+      throws java.io.IOException {
+    return com.google.protobuf.GeneratedMessageV3
+        .parseWithIOException(PARSER, input);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseFrom(
+      java.io.InputStream input,
+      com.google.protobuf.ExtensionRegistryLite extensionRegistry)
+      throws java.io.IOException {
+    return com.google.protobuf.GeneratedMessageV3
+        .parseWithIOException(PARSER, input, extensionRegistry);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseDelimitedFrom(java.io.InputStream input)
+      throws java.io.IOException {
+    return com.google.protobuf.GeneratedMessageV3
+        .parseDelimitedWithIOException(PARSER, input);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseDelimitedFrom(
+      java.io.InputStream input,
+      com.google.protobuf.ExtensionRegistryLite extensionRegistry)
+      throws java.io.IOException {
+    return com.google.protobuf.GeneratedMessageV3
+        .parseDelimitedWithIOException(PARSER, input, extensionRegistry);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseFrom(
+      com.google.protobuf.CodedInputStream input)
+      throws java.io.IOException {
+    return com.google.protobuf.GeneratedMessageV3
+        .parseWithIOException(PARSER, input);
+  }
+  public static io.projectliberty.graphsdk.models.ChainHead parseFrom(
+      com.google.protobuf.CodedInputStream input,
+      com.google.protobuf.ExtensionRegistryLite extensionRegistry)
+      throws java.io.IOException {
+    return com.google.protobuf.GeneratedMessageV3
+        .parseWithIOException(PARSER, input, extensionRegistry);
+  }
+
+  @java.lang.Override
+  public Builder newBuilderForType() { return newBuilder(); }
+  public static Builder newBuilder() {
+    return DEFAULT_INSTANCE.toBuilder();
+  }
+  public static Builder newBuilder(io.projectliberty.graphsdk.models.ChainHead prototype) {
+    return DEFAULT_INSTANCE.toBuilder().mergeFrom(prototype);
+  }
+  @java.lang.Override
+  public Builder toBuilder() {
+    return this == DEFAULT_INSTANCE
+        ? new Builder() : new Builder().mergeFrom(this);
+  }
+
+  @java.lang.Override
+  protected Builder newBuilderForType(
+      com.google.protobuf.GeneratedMessageV3.BuilderParent parent) {
+    Builder builder = new Builder(parent);
+    return builder;
+  }
+  /**
+   * <pre>
+   * block of the chain at which the imported data was read
+   * </pre>
+   *
+   * Protobuf type {@code ChainHead}
+   */
+  public static final class Builder extends
+      com.google.protobuf.GeneratedMessageV3.Builder<Builder> implements
+      // @@protoc_insertion_point(builder_implements:ChainHead)
+      io.projectliberty.graphsdk.models.ChainHeadOrBuilder {
+    public static final com.google.protobuf.Descriptors.Descriptor
+        getDescriptor() {
+      return io.projectliberty.graphsdk.models.Input.internal_static_ChainHead_descriptor;
+    }
+
+    @java.lang.Override
+    protected com.google.protobuf.GeneratedMessageV3.FieldAccessorTable
+        internalGetFieldAccessorTable() {
+      return io.projectliberty.graphsdk.models.Input.internal_static_ChainHead_fieldAccessorTable
+          .ensureFieldAccessorsInitialized(
+              io.projectliberty.graphsdk.models.ChainHead.class, io.projectliberty.graphsdk.models.ChainHead.Builder.class);
+    }
+
+    // Construct using io.projectliberty.graphsdk.models.ChainHead.newBuilder()
+    private Builder() {
+
+    }
+
+    private Builder(
+        com.google.protobuf.GeneratedMessageV3.BuilderParent parent) {
+      super(parent);
+
+    }
+    @java.lang.Override
+    public Builder clear() {
+      super.clear();
+      bitField0_ = 0;
+      blockNumber_ = 0;
+      blockHash_ = com.google.protobuf.ByteString.EMPTY;
+      return this;
+    }
+
+    @java.lang.Override
+    public com.google.protobuf.Descriptors.Descriptor
+        getDescriptorForType() {
+      return io.projectliberty.graphsdk.models.Input.internal_static_ChainHead_descriptor;
+    }
+
+    @java.lang.Override
+    public io.projectliberty.graphsdk.models.ChainHead getDefaultInstanceForType() {
+      return io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance();
+    }
+
+    @java.lang.Override
+    public io.projectliberty.graphsdk.models.ChainHead build() {
+      io.projectliberty.graphsdk.models.ChainHead result = buildPartial();
+      if (!result.isInitialized()) {
+        throw newUninitializedMessageException(result);
+      }
+      return result;
+    }
+
+    @java.lang.Override
+    public io.projectliberty.graphsdk.models.ChainHead buildPartial() {
+      io.projectliberty.graphsdk.models.ChainHead result = new io.projectliberty.graphsdk.models.ChainHead(this);
+      if (bitField0_ != 0) { buildPartial0(result); }
+      onBuilt();
+      return result;
+    }
+
+    private void buildPartial0(io.projectliberty.graphsdk.models.ChainHead result) {
+      int from_bitField0_ = bitField0_;
+      if (((from_bitField0_ & 0x00000001) != 0)) {
+        result.blockNumber_ = blockNumber_;
+      }
+      if (((from_bitField0_ & 0x00000002) != 0)) {
+        result.blockHash_ = blockHash_;
+      }
+    }
+
+    @java.lang.Override
+    public Builder clone() {
+      return super.clone();
+    }
+    @java.lang.Override
+    public Builder setField(
+        com.google.protobuf.Descriptors.FieldDescriptor field,
+        java.lang.Object value) {
+      return super.setField(field, value);
+    }
+    @java.lang.Override
+    public Builder clearField(
+        com.google.protobuf.Descriptors.FieldDescriptor field) {
+      return super.clearField(field);
+    }
+    @java.lang.Override
+    public Builder clearOneof(
+        com.google.protobuf.Descriptors.OneofDescriptor oneof) {
+      return super.clearOneof(oneof);
+    }
+    @java.lang.Override
+    public Builder setRepeatedField(
+        com.google.protobuf.Descriptors.FieldDescriptor field,
+        int index, java.lang.Object value) {
+      return super.setRepeatedField(field, index, value);
+    }
+    @java.lang.Override
+    public Builder addRepeatedField(
+        com.google.protobuf.Descriptors.FieldDescriptor field,
+        java.lang.Object value) {
+      return super.addRepeatedField(field, value);
+    }
+    @java.lang.Override
+    public Builder mergeFrom(com.google.protobuf.Message other) {
+      if (other instanceof io.projectliberty.graphsdk.models.ChainHead) {
+        return mergeFrom((io.projectliberty.graphsdk.models.ChainHead)other);
+      } else {
+        super.mergeFrom(other);
+        return this;
+      }
+    }
+
+    public Builder mergeFrom(io.projectliberty.graphsdk.models.ChainHead other) {
+      if (other == io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance()) return this;
+      if (other.getBlockNumber() != 0) {
+        setBlockNumber(other.getBlockNumber());
+      }
+      if (other.getBlockHash() != com.google.protobuf.ByteString.EMPTY) {
+        setBlockHash(other.getBlockHash());
+      }
+      this.mergeUnknownFields(other.getUnknownFields());
+      onChanged();
+      return this;
+    }
+
+    @java.lang.Override
+    public final boolean isInitialized() {
+      return true;
+    }
+
+    @java.lang.Override
+    public Builder mergeFrom(
+        com.google.protobuf.CodedInputStream input,
+        com.google.protobuf.ExtensionRegistryLite extensionRegistry)
+        throws java.io.IOException {
+      if (extensionRegistry == null) {
+        throw new java.lang.NullPointerException();
+      }
+      try {
+        boolean done = false;
+        while (!done) {
+          int tag = input.readTag();
+          switch (tag) {
+            case 0:
+              done = true;
+              break;
+            case 8: {
+              blockNumber_ = input.readUInt32();
+              bitField0_ |= 0x00000001;
+              break;
+            } // case 8
+            case 18: {
+              blockHash_ = input.readBytes();
+              bitField0_ |= 0x00000002;
+              break;
+            } // case 18
+            default: {
+              if (!super.parseUnknownField(input, extensionRegistry, tag)) {
+                done = true; // was an endgroup tag
+              }
+              break;
+            } // default:
+          } // switch (tag)
+        } // while (!done)
+      } catch (com.google.protobuf.InvalidProtocolBufferException e) {
+        throw e.unwrapIOException();
+      } finally {
+        onChanged();
+      } // finally
+      return this;
+    }
+    private int bitField0_;
+
+    private int blockNumber_ ;
+    /**
+     * <code>uint32 block_number = 1;</code>
+     * @return The blockNumber.
+     */
+    @java.lang.Override
+    public int getBlockNumber() {
+      return blockNumber_;
+    }
+    /**
+     * <code>uint32 block_number = 1;</code>
+     * @param value The blockNumber to set.
+     * @return This builder for chaining.
+     */
+    public Builder setBlockNumber(int value) {
+      
+      blockNumber_ = value;
+      bitField0_ |= 0x00000001;
+      onChanged();
+      return this;
+    }
+    /**
+     * <code>uint32 block_number = 1;</code>
+     * @return This builder for chaining.
+     */
+    public Builder clearBlockNumber() {
+      bitField0_ = (bitField0_ & ~0x00000001);
+      blockNumber_ = 0;
+      onChanged();
+      return this;
+    }
+
+    private com.google.protobuf.ByteString blockHash_ = com.google.protobuf.ByteString.EMPTY;
+    /**
+     * <code>bytes block_hash = 2;</code>
+     * @return The blockHash.
+     */
+    @java.lang.Override
+    public com.google.protobuf.ByteString getBlockHash() {
+      return blockHash_;
+    }
+    /**
+     * <code>bytes block_hash = 2;</code>
+     * @param value The blockHash to set.
+     * @return This builder for chaining.
+     */
+    public Builder setBlockHash(com.google.protobuf.ByteString value) {
+      if (value == null) { throw new NullPointerException(); }
+      blockHash_ = value;
+      bitField0_ |= 0x00000002;
+      onChanged();
+      return this;
+    }
+    /**
+     * <code>bytes block_hash = 2;</code>
+     * @return This builder for chaining.
+     */
+    public Builder clearBlockHash() {
+      bitField0_ = (bitField0_ & ~0x00000002);
+      blockHash_ = getDefaultInstance().getBlockHash();
+      onChanged();
+      return this;
+    }
+    @java.lang.Override
+    public final Builder setUnknownFields(
+        final com.google.protobuf.UnknownFieldSet unknownFields) {
+      return super.setUnknownFields(unknownFields);
+    }
+
+    @java.lang.Override
+    public final Builder mergeUnknownFields(
+        final com.google.protobuf.UnknownFieldSet unknownFields) {
+      return super.mergeUnknownFields(unknownFields);
+    }
+
+
+    // @@protoc_insertion_point(builder_scope:ChainHead)
+  }
+
+  // @@protoc_insertion_point(class_scope:ChainHead)
+  private static final io.projectliberty.graphsdk.models.ChainHead DEFAULT_INSTANCE;
+  static {
+    DEFAULT_INSTANCE = new io.projectliberty.graphsdk.models.ChainHead();
+  }
+
+  public static io.projectliberty.graphsdk.models.ChainHead getDefaultInstance() {
+    return DEFAULT_INSTANCE;
+  }
+
+  private static final com.google.protobuf.Parser<ChainHead>
+      PARSER = new com.google.protobuf.AbstractParser<ChainHead>() {
+    @java.lang.Override
+    public ChainHead parsePartialFrom(
+        com.google.protobuf.CodedInputStream input,
+        com.google.protobuf.ExtensionRegistryLite extensionRegistry)
+        throws com.google.protobuf.InvalidProtocolBufferException {
+      Builder builder = newBuilder();
+      try {
+        builder.mergeFrom(input, extensionRegistry);
+      } catch (com.google.protobuf.InvalidProtocolBufferException e) {
+        throw e.setUnfinishedMessage(builder.buildPartial());
+      } catch (com.google.protobuf.UninitializedMessageException e) {
+        throw e.asInvalidProtocolBufferException().setUnfinishedMessage(builder.buildPartial());
+      } catch (java.io.IOException e) {
+        throw new com.google.protobuf.InvalidProtocolBufferException(e)
+            .setUnfinishedMessage(builder.buildPartial());
+      }
+      return builder.buildPartial();
+    }
+  };
+
+  public static com.google.protobuf.Parser<ChainHead> parser() {
+    return PARSER;
+  }
+
+  @java.lang.Override
+  public com.google.protobuf.Parser<ChainHead> getParserForType() {
+    return PARSER;
+  }
+
+  @java.lang.Override
+  public io.projectliberty.graphsdk.models.ChainHead getDefaultInstanceForType() {
+    return DEFAULT_INSTANCE;
+  }
+
+}
+
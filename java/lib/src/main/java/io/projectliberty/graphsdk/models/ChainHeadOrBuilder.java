@@ -0,0 +1,21 @@
+// Generated by the protocol buffer compiler.  DO NOT EDIT!
+// source: bridge/common/protos/input.proto
+
+package io.projectliberty.graphsdk.models;
+
+public interface ChainHeadOrBuilder extends
+    // @@protoc_insertion_point(interface_extends:ChainHead)
+    com.google.protobuf.MessageOrBuilder {
+
+  /**
+   * <code>uint32 block_number = 1;</code>
+   * @return The blockNumber.
+   */
+  int getBlockNumber();
+
+  /**
+   * <code>bytes block_hash = 2;</code>
+   * @return The blockHash.
+   */
+  com.google.protobuf.ByteString getBlockHash();
+}
@@ -248,6 +248,17 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue) {
     return graphPublicKeySchemaId_;
   }
 
+  public static final int MAX_UPDATE_PAYLOAD_BYTES_FIELD_NUMBER = 8;
+  private int maxUpdatePayloadBytes_ = 0;
+  /**
+   * <code>uint32 max_update_payload_bytes = 8;</code>
+   * @return The maxUpdatePayloadBytes.
+   */
+  @java.lang.Override
+  public int getMaxUpdatePayloadBytes() {
+    return maxUpdatePayloadBytes_;
+  }
+
   private byte memoizedIsInitialized = -1;
   @java.lang.Override
   public final boolean isInitialized() {
@@ -291,6 +302,9 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue) {
     if (graphPublicKeySchemaId_ != 0) {
       output.writeUInt32(7, graphPublicKeySchemaId_);
     }
+    if (maxUpdatePayloadBytes_ != 0) {
+      output.writeUInt32(8, maxUpdatePayloadBytes_);
+    }
     getUnknownFields().writeTo(output);
   }
 
@@ -342,6 +356,10 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue) {
       size += com.google.protobuf.CodedOutputStream
         .computeUInt32Size(7, graphPublicKeySchemaId_);
     }
+    if (maxUpdatePayloadBytes_ != 0) {
+      size += com.google.protobuf.CodedOutputStream
+        .computeUInt32Size(8, maxUpdatePayloadBytes_);
+    }
     size += getUnknownFields().getSerializedSize();
     memoizedSize = size;
     return size;
@@ -370,6 +388,8 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue) {
     if (!dsnpVersions_.equals(other.dsnpVersions_)) return false;
     if (getGraphPublicKeySchemaId()
         != other.getGraphPublicKeySchemaId()) return false;
+    if (getMaxUpdatePayloadBytes()
+        != other.getMaxUpdatePayloadBytes()) return false;
     if (!getUnknownFields().equals(other.getUnknownFields())) return false;
     return true;
   }
@@ -399,6 +419,8 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue) {
     }
     hash = (37 * hash) + GRAPH_PUBLIC_KEY_SCHEMA_ID_FIELD_NUMBER;
     hash = (53 * hash) + getGraphPublicKeySchemaId();
+    hash = (37 * hash) + MAX_UPDATE_PAYLOAD_BYTES_FIELD_NUMBER;
+    hash = (53 * hash) + getMaxUpdatePayloadBytes();
     hash = (29 * hash) + getUnknownFields().hashCode();
     memoizedHashCode = hash;
     return hash;
@@ -558,6 +580,7 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue) {
       dsnpVersions_ = java.util.Collections.emptyList();
       bitField0_ = (bitField0_ & ~0x00000020);
       graphPublicKeySchemaId_ = 0;
+      maxUpdatePayloadBytes_ = 0;
       return this;
     }
 
@@ -619,6 +642,9 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue) {
       if (((from_bitField0_ & 0x00000040) != 0)) {
         result.graphPublicKeySchemaId_ = graphPublicKeySchemaId_;
       }
+      if (((from_bitField0_ & 0x00000080) != 0)) {
+        result.maxUpdatePayloadBytes_ = maxUpdatePayloadBytes_;
+      }
     }
 
     @java.lang.Override
@@ -693,6 +719,9 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue) {
       if (other.getGraphPublicKeySchemaId() != 0) {
         setGraphPublicKeySchemaId(other.getGraphPublicKeySchemaId());
       }
+      if (other.getMaxUpdatePayloadBytes() != 0) {
+        setMaxUpdatePayloadBytes(other.getMaxUpdatePayloadBytes());
+      }
       this.mergeUnknownFields(other.getUnknownFields());
       onChanged();
       return this;
@@ -770,6 +799,11 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue) {
               bitField0_ |= 0x00000040;
               break;
             } // case 56
+            case 64: {
+              maxUpdatePayloadBytes_ = input.readUInt32();
+              bitField0_ |= 0x00000080;
+              break;
+            } // case 64
             default: {
               if (!super.parseUnknownField(input, extensionRegistry, tag)) {
                 done = true; // was an endgroup tag
@@ -1213,6 +1247,38 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue) {
       onChanged();
       return this;
     }
+
+    private int maxUpdatePayloadBytes_ ;
+    /**
+     * <code>uint32 max_update_payload_bytes = 8;</code>
+     * @return The maxUpdatePayloadBytes.
+     */
+    @java.lang.Override
+    public int getMaxUpdatePayloadBytes() {
+      return maxUpdatePayloadBytes_;
+    }
+    /**
+     * <code>uint32 max_update_payload_bytes = 8;</code>
+     * @param value The maxUpdatePayloadBytes to set.
+     * @return This builder for chaining.
+     */
+    public Builder setMaxUpdatePayloadBytes(int value) {
+      
+      maxUpdatePayloadBytes_ = value;
+      bitField0_ |= 0x00000080;
+      onChanged();
+      return this;
+    }
+    /**
+     * <code>uint32 max_update_payload_bytes = 8;</code>
+     * @return This builder for chaining.
+     */
+    public Builder clearMaxUpdatePayloadBytes() {
+      bitField0_ = (bitField0_ & ~0x00000080);
+      maxUpdatePayloadBytes_ = 0;
+      onChanged();
+      return this;
+    }
     @java.lang.Override
     public final Builder setUnknownFields(
         final com.google.protobuf.UnknownFieldSet unknownFields) {
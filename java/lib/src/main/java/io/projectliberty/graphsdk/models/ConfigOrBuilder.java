@@ -99,4 +99,10 @@ io.projectliberty.graphsdk.models.SchemaConfig defaultValue);
    * @return The graphPublicKeySchemaId.
    */
   int getGraphPublicKeySchemaId();
+
+  /**
+   * <code>uint32 max_update_payload_bytes = 8;</code>
+   * @return The maxUpdatePayloadBytes.
+   */
+  int getMaxUpdatePayloadBytes();
 }
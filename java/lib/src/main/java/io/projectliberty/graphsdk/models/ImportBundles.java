@@ -122,6 +122,21 @@ private static final long serialVersionUID = 0L;
      */
     io.projectliberty.graphsdk.models.PageDataOrBuilder getPagesOrBuilder(
         int index);
+
+    /**
+     * <code>optional .ChainHead chain_head = 6;</code>
+     * @return Whether the chainHead field is set.
+     */
+    boolean hasChainHead();
+    /**
+     * <code>optional .ChainHead chain_head = 6;</code>
+     * @return The chainHead.
+     */
+    io.projectliberty.graphsdk.models.ChainHead getChainHead();
+    /**
+     * <code>optional .ChainHead chain_head = 6;</code>
+     */
+    io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder();
   }
   /**
    * Protobuf type {@code ImportBundles.ImportBundle}
@@ -954,6 +969,32 @@ private static final long serialVersionUID = 0L;
       return pages_.get(index);
     }
 
+    public static final int CHAIN_HEAD_FIELD_NUMBER = 6;
+    private io.projectliberty.graphsdk.models.ChainHead chainHead_;
+    /**
+     * <code>optional .ChainHead chain_head = 6;</code>
+     * @return Whether the chainHead field is set.
+     */
+    @java.lang.Override
+    public boolean hasChainHead() {
+      return ((bitField0_ & 0x00000002) != 0);
+    }
+    /**
+     * <code>optional .ChainHead chain_head = 6;</code>
+     * @return The chainHead.
+     */
+    @java.lang.Override
+    public io.projectliberty.graphsdk.models.ChainHead getChainHead() {
+      return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+    }
+    /**
+     * <code>optional .ChainHead chain_head = 6;</code>
+     */
+    @java.lang.Override
+    public io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder() {
+      return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+    }
+
     private byte memoizedIsInitialized = -1;
     @java.lang.Override
     public final boolean isInitialized() {
@@ -983,6 +1024,9 @@ private static final long serialVersionUID = 0L;
       for (int i = 0; i < pages_.size(); i++) {
         output.writeMessage(5, pages_.get(i));
       }
+      if (((bitField0_ & 0x00000002) != 0)) {
+        output.writeMessage(6, getChainHead());
+      }
       getUnknownFields().writeTo(output);
     }
 
@@ -1012,6 +1056,10 @@ private static final long serialVersionUID = 0L;
         size += com.google.protobuf.CodedOutputStream
           .computeMessageSize(5, pages_.get(i));
       }
+      if (((bitField0_ & 0x00000002) != 0)) {
+        size += com.google.protobuf.CodedOutputStream
+          .computeMessageSize(6, getChainHead());
+      }
       size += getUnknownFields().getSerializedSize();
       memoizedSize = size;
       return size;
@@ -1040,6 +1088,11 @@ private static final long serialVersionUID = 0L;
       }
       if (!getPagesList()
           .equals(other.getPagesList())) return false;
+      if (hasChainHead() != other.hasChainHead()) return false;
+      if (hasChainHead()) {
+        if (!getChainHead()
+            .equals(other.getChainHead())) return false;
+      }
       if (!getUnknownFields().equals(other.getUnknownFields())) return false;
       return true;
     }
@@ -1068,6 +1121,10 @@ private static final long serialVersionUID = 0L;
         hash = (37 * hash) + PAGES_FIELD_NUMBER;
         hash = (53 * hash) + getPagesList().hashCode();
       }
+      if (hasChainHead()) {
+        hash = (37 * hash) + CHAIN_HEAD_FIELD_NUMBER;
+        hash = (53 * hash) + getChainHead().hashCode();
+      }
       hash = (29 * hash) + getUnknownFields().hashCode();
       memoizedHashCode = hash;
       return hash;
@@ -1199,6 +1256,7 @@ private static final long serialVersionUID = 0L;
           getKeyPairsFieldBuilder();
           getDsnpKeysFieldBuilder();
           getPagesFieldBuilder();
+          getChainHeadFieldBuilder();
         }
       }
       @java.lang.Override
@@ -1226,6 +1284,11 @@ private static final long serialVersionUID = 0L;
           pagesBuilder_.clear();
         }
         bitField0_ = (bitField0_ & ~0x00000010);
+        chainHead_ = null;
+        if (chainHeadBuilder_ != null) {
+          chainHeadBuilder_.dispose();
+          chainHeadBuilder_ = null;
+        }
         return this;
       }
 
@@ -1294,6 +1357,12 @@ private static final long serialVersionUID = 0L;
               : dsnpKeysBuilder_.build();
           to_bitField0_ |= 0x00000001;
         }
+        if (((from_bitField0_ & 0x00000020) != 0)) {
+          result.chainHead_ = chainHeadBuilder_ == null
+              ? chainHead_
+              : chainHeadBuilder_.build();
+          to_bitField0_ |= 0x00000002;
+        }
         result.bitField0_ |= to_bitField0_;
       }
 
@@ -1402,6 +1471,9 @@ private static final long serialVersionUID = 0L;
             }
           }
         }
+        if (other.hasChainHead()) {
+          mergeChainHead(other.getChainHead());
+        }
         this.mergeUnknownFields(other.getUnknownFields());
         onChanged();
         return this;
@@ -1471,6 +1543,13 @@ private static final long serialVersionUID = 0L;
                 }
                 break;
               } // case 42
+              case 50: {
+                input.readMessage(
+                    getChainHeadFieldBuilder().getBuilder(),
+                    extensionRegistry);
+                bitField0_ |= 0x00000020;
+                break;
+              } // case 50
               default: {
                 if (!super.parseUnknownField(input, extensionRegistry, tag)) {
                   done = true; // was an endgroup tag
@@ -2150,6 +2229,127 @@ private static final long serialVersionUID = 0L;
         }
         return pagesBuilder_;
       }
+
+      private io.projectliberty.graphsdk.models.ChainHead chainHead_;
+      private com.google.protobuf.SingleFieldBuilderV3<
+          io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder> chainHeadBuilder_;
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       * @return Whether the chainHead field is set.
+       */
+      public boolean hasChainHead() {
+        return ((bitField0_ & 0x00000020) != 0);
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       * @return The chainHead.
+       */
+      public io.projectliberty.graphsdk.models.ChainHead getChainHead() {
+        if (chainHeadBuilder_ == null) {
+          return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+        } else {
+          return chainHeadBuilder_.getMessage();
+        }
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       */
+      public Builder setChainHead(io.projectliberty.graphsdk.models.ChainHead value) {
+        if (chainHeadBuilder_ == null) {
+          if (value == null) {
+            throw new NullPointerException();
+          }
+          chainHead_ = value;
+        } else {
+          chainHeadBuilder_.setMessage(value);
+        }
+        bitField0_ |= 0x00000020;
+        onChanged();
+        return this;
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       */
+      public Builder setChainHead(
+          io.projectliberty.graphsdk.models.ChainHead.Builder builderForValue) {
+        if (chainHeadBuilder_ == null) {
+          chainHead_ = builderForValue.build();
+        } else {
+          chainHeadBuilder_.setMessage(builderForValue.build());
+        }
+        bitField0_ |= 0x00000020;
+        onChanged();
+        return this;
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       */
+      public Builder mergeChainHead(io.projectliberty.graphsdk.models.ChainHead value) {
+        if (chainHeadBuilder_ == null) {
+          if (((bitField0_ & 0x00000020) != 0) &&
+            chainHead_ != null &&
+            chainHead_ != io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance()) {
+            getChainHeadBuilder().mergeFrom(value);
+          } else {
+            chainHead_ = value;
+          }
+        } else {
+          chainHeadBuilder_.mergeFrom(value);
+        }
+        if (chainHead_ != null) {
+          bitField0_ |= 0x00000020;
+          onChanged();
+        }
+        return this;
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       */
+      public Builder clearChainHead() {
+        bitField0_ = (bitField0_ & ~0x00000020);
+        chainHead_ = null;
+        if (chainHeadBuilder_ != null) {
+          chainHeadBuilder_.dispose();
+          chainHeadBuilder_ = null;
+        }
+        onChanged();
+        return this;
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       */
+      public io.projectliberty.graphsdk.models.ChainHead.Builder getChainHeadBuilder() {
+        bitField0_ |= 0x00000020;
+        onChanged();
+        return getChainHeadFieldBuilder().getBuilder();
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       */
+      public io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder() {
+        if (chainHeadBuilder_ != null) {
+          return chainHeadBuilder_.getMessageOrBuilder();
+        } else {
+          return chainHead_ == null ?
+              io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+        }
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       */
+      private com.google.protobuf.SingleFieldBuilderV3<
+          io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder> 
+          getChainHeadFieldBuilder() {
+        if (chainHeadBuilder_ == null) {
+          chainHeadBuilder_ = new com.google.protobuf.SingleFieldBuilderV3<
+              io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder>(
+                  getChainHead(),
+                  getParentForChildren(),
+                  isClean());
+          chainHead_ = null;
+        }
+        return chainHeadBuilder_;
+      }
       @java.lang.Override
       public final Builder setUnknownFields(
           final com.google.protobuf.UnknownFieldSet unknownFields) {
@@ -79,6 +79,11 @@ public final class Input {
   static final 
     com.google.protobuf.GeneratedMessageV3.FieldAccessorTable
       internal_static_Actions_Action_AddGraphKey_fieldAccessorTable;
+  static final com.google.protobuf.Descriptors.Descriptor
+    internal_static_ChainHead_descriptor;
+  static final 
+    com.google.protobuf.GeneratedMessageV3.FieldAccessorTable
+      internal_static_ChainHead_fieldAccessorTable;
 
   public static com.google.protobuf.Descriptors.FileDescriptor
       getDescriptor() {
@@ -93,36 +98,43 @@ public final class Input {
       "\010PageData\022\017\n\007page_id\030\001 \001(\r\022\017\n\007content\030\002 " +
       "\001(\014\022\024\n\014content_hash\030\003 \001(\r\"K\n\010DsnpKeys\022\024\n" +
       "\014dsnp_user_id\030\001 \001(\004\022\021\n\tkeys_hash\030\002 \001(\r\022\026" +
-      "\n\004keys\030\003 \003(\0132\010.KeyData\"\330\002\n\rImportBundles" +
+      "\n\004keys\030\003 \003(\0132\010.KeyData\"\214\003\n\rImportBundles" +
       "\022,\n\007bundles\030\001 \003(\0132\033.ImportBundles.Import" +
-      "Bundle\032\230\002\n\014ImportBundle\022\024\n\014dsnp_user_id\030" +
+      "Bundle\032\314\002\n\014ImportBundle\022\024\n\014dsnp_user_id\030" +
       "\001 \001(\004\022\021\n\tschema_id\030\002 \001(\r\022;\n\tkey_pairs\030\003 " +
       "\003(\0132(.ImportBundles.ImportBundle.GraphKe" +
       "yPair\022!\n\tdsnp_keys\030\004 \001(\0132\t.DsnpKeysH\000\210\001\001" +
-      "\022\030\n\005pages\030\005 \003(\0132\t.PageData\032W\n\014GraphKeyPa" +
-      "ir\022\037\n\010key_type\030\001 \001(\0162\r.GraphKeyType\022\022\n\np" +
-      "ublic_key\030\002 \001(\014\022\022\n\nsecret_key\030\003 \001(\014B\014\n\n_" +
-      "dsnp_keys\"5\n\nConnection\022\024\n\014dsnp_user_id\030" +
-      "\001 \001(\004\022\021\n\tschema_id\030\002 \001(\r\"\262\005\n\007Actions\022 \n\007" +
-      "actions\030\001 \003(\0132\017.Actions.Action\022,\n\007option" +
-      "s\030\002 \001(\0132\026.Actions.ActionOptionsH\000\210\001\001\032u\n\r" +
-      "ActionOptions\022#\n\033ignore_existing_connect" +
-      "ions\030\001 \001(\010\022\"\n\032ignore_missing_connections" +
-      "\030\002 \001(\010\022\033\n\023disable_auto_commit\030\003 \001(\010\032\323\003\n\006" +
-      "Action\0227\n\016connect_action\030\001 \001(\0132\035.Actions" +
-      ".Action.ConnectActionH\000\022=\n\021disconnect_ac" +
-      "tion\030\002 \001(\0132 .Actions.Action.DisconnectAc" +
-      "tionH\000\0225\n\016add_key_action\030\003 \001(\0132\033.Actions" +
-      ".Action.AddGraphKeyH\000\032}\n\rConnectAction\022\032" +
-      "\n\022owner_dsnp_user_id\030\001 \001(\004\022\037\n\nconnection" +
-      "\030\002 \001(\0132\013.Connection\022!\n\tdsnp_keys\030\003 \001(\0132\t" +
-      ".DsnpKeysH\000\210\001\001B\014\n\n_dsnp_keys\032O\n\020Disconne" +
-      "ctAction\022\032\n\022owner_dsnp_user_id\030\001 \001(\004\022\037\n\n" +
-      "connection\030\002 \001(\0132\013.Connection\032A\n\013AddGrap" +
-      "hKey\022\032\n\022owner_dsnp_user_id\030\001 \001(\004\022\026\n\016new_" +
-      "public_key\030\002 \001(\014B\007\n\005innerB\n\n\010_options*\032\n" +
-      "\014GraphKeyType\022\n\n\006X25519\020\000B%\n!io.projectl" +
-      "iberty.graphsdk.modelsP\001b\006proto3"
+      "\022\030\n\005pages\030\005 \003(\0132\t.PageData\022#\n\nchain_head" +
+      "\030\006 \001(\0132\n.ChainHeadH\001\210\001\001\032W\n\014GraphKeyPair\022" +
+      "\037\n\010key_type\030\001 \001(\0162\r.GraphKeyType\022\022\n\npubl" +
+      "ic_key\030\002 \001(\014\022\022\n\nsecret_key\030\003 \001(\014B\014\n\n_dsn" +
+      "p_keysB\r\n\013_chain_head\"5\n\nConnection\022\024\n\014d" +
+      "snp_user_id\030\001 \001(\004\022\021\n\tschema_id\030\002 \001(\r\"\237\006\n" +
+      "\007Actions\022 \n\007actions\030\001 \003(\0132\017.Actions.Acti" +
+      "on\022,\n\007options\030\002 \001(\0132\026.Actions.ActionOpti" +
+      "onsH\000\210\001\001\032\321\001\n\rActionOptions\022#\n\033ignore_exi" +
+      "sting_connections\030\001 \001(\010\022\"\n\032ignore_missin" +
+      "g_connections\030\002 \001(\010\022\033\n\023disable_auto_comm" +
+      "it\030\003 \001(\010\022*\n\020timestamp_policy\030\004 \001(\0162\020.Tim" +
+      "estampPolicy\022\026\n\016max_batch_size\030\005 \001(\r\022\026\n\016" +
+      "collect_errors\030\006 \001(\010\032\343\003\n\006Action\0227\n\016conne" +
+      "ct_action\030\001 \001(\0132\035.Actions.Action.Connect" +
+      "ActionH\000\022=\n\021disconnect_action\030\002 \001(\0132 .Ac" +
+      "tions.Action.DisconnectActionH\000\0225\n\016add_k" +
+      "ey_action\030\003 \001(\0132\033.Actions.Action.AddGrap" +
+      "hKeyH\000\032\214\001\n\rConnectAction\022\032\n\022owner_dsnp_u" +
+      "ser_id\030\001 \001(\004\022\037\n\nconnection\030\002 \001(\0132\013.Conne" +
+      "ction\022!\n\tdsnp_keys\030\003 \001(\0132\t.DsnpKeysH\000\210\001\001" +
+      "\022\r\n\005since\030\004 \001(\004B\014\n\n_dsnp_keys\032O\n\020Disconn" +
+      "ectAction\022\032\n\022owner_dsnp_user_id\030\001 \001(\004\022\037\n" +
+      "\nconnection\030\002 \001(\0132\013.Connection\032A\n\013AddGra" +
+      "phKey\022\032\n\022owner_dsnp_user_id\030\001 \001(\004\022\026\n\016new" +
+      "_public_key\030\002 \001(\014B\007\n\005innerB\n\n\010_options\"5" +
+      "\n\tChainHead\022\024\n\014block_number\030\001 \001(\r\022\022\n\nblo" +
+      "ck_hash\030\002 \001(\014*\032\n\014GraphKeyType\022\n\n\006X25519\020" +
+      "\000*8\n\017TimestampPolicy\022\022\n\016CallerProvided\020\000" +
+      "\022\007\n\003Now\020\001\022\010\n\004Zero\020\002B%\n!io.projectliberty" +
+      ".graphsdk.modelsP\001b\006proto3"
     };
     descriptor = com.google.protobuf.Descriptors.FileDescriptor
       .internalBuildGeneratedFileFrom(descriptorData,
@@ -157,7 +169,7 @@ public final class Input {
     internal_static_ImportBundles_ImportBundle_fieldAccessorTable = new
       com.google.protobuf.GeneratedMessageV3.FieldAccessorTable(
         internal_static_ImportBundles_ImportBundle_descriptor,
-        new java.lang.String[] { "DsnpUserId", "SchemaId", "KeyPairs", "DsnpKeys", "Pages", "DsnpKeys", });
+        new java.lang.String[] { "DsnpUserId", "SchemaId", "KeyPairs", "DsnpKeys", "Pages", "ChainHead", "DsnpKeys", "ChainHead", });
     internal_static_ImportBundles_ImportBundle_GraphKeyPair_descriptor =
       internal_static_ImportBundles_ImportBundle_descriptor.getNestedTypes().get(0);
     internal_static_ImportBundles_ImportBundle_GraphKeyPair_fieldAccessorTable = new
@@ -181,7 +193,7 @@ public final class Input {
     internal_static_Actions_ActionOptions_fieldAccessorTable = new
       com.google.protobuf.GeneratedMessageV3.FieldAccessorTable(
         internal_static_Actions_ActionOptions_descriptor,
-        new java.lang.String[] { "IgnoreExistingConnections", "IgnoreMissingConnections", "DisableAutoCommit", });
+        new java.lang.String[] { "IgnoreExistingConnections", "IgnoreMissingConnections", "DisableAutoCommit", "TimestampPolicy", "MaxBatchSize", "CollectErrors", });
     internal_static_Actions_Action_descriptor =
       internal_static_Actions_descriptor.getNestedTypes().get(1);
     internal_static_Actions_Action_fieldAccessorTable = new
@@ -193,7 +205,7 @@ public final class Input {
     internal_static_Actions_Action_ConnectAction_fieldAccessorTable = new
       com.google.protobuf.GeneratedMessageV3.FieldAccessorTable(
         internal_static_Actions_Action_ConnectAction_descriptor,
-        new java.lang.String[] { "OwnerDsnpUserId", "Connection", "DsnpKeys", "DsnpKeys", });
+        new java.lang.String[] { "OwnerDsnpUserId", "Connection", "DsnpKeys", "Since", "DsnpKeys", });
     internal_static_Actions_Action_DisconnectAction_descriptor =
       internal_static_Actions_Action_descriptor.getNestedTypes().get(1);
     internal_static_Actions_Action_DisconnectAction_fieldAccessorTable = new
@@ -206,6 +218,12 @@ public final class Input {
       com.google.protobuf.GeneratedMessageV3.FieldAccessorTable(
         internal_static_Actions_Action_AddGraphKey_descriptor,
         new java.lang.String[] { "OwnerDsnpUserId", "NewPublicKey", });
+    internal_static_ChainHead_descriptor =
+      getDescriptor().getMessageTypes().get(6);
+    internal_static_ChainHead_fieldAccessorTable = new
+      com.google.protobuf.GeneratedMessageV3.FieldAccessorTable(
+        internal_static_ChainHead_descriptor,
+        new java.lang.String[] { "BlockNumber", "BlockHash", });
   }
 
   // @@protoc_insertion_point(outer_class_scope)
@@ -93,51 +93,58 @@ public final class Output {
       descriptor;
   static {
     java.lang.String[] descriptorData = {
-      "\n!bridge/common/protos/output.proto\"\\\n\014S" +
-      "chemaConfig\022\"\n\014dsnp_version\030\001 \001(\0162\014.Dsnp" +
-      "Version\022(\n\017connection_type\030\002 \001(\0162\017.Conne" +
-      "ctionType\"\276\002\n\006Config\022%\n\035sdk_max_stale_fr" +
-      "iendship_days\030\001 \001(\r\022!\n\031max_graph_page_si" +
-      "ze_bytes\030\002 \001(\r\022\023\n\013max_page_id\030\003 \001(\r\022\037\n\027m" +
-      "ax_key_page_size_bytes\030\004 \001(\r\022*\n\nschema_m" +
-      "ap\030\005 \003(\0132\026.Config.SchemaMapEntry\022#\n\rdsnp" +
-      "_versions\030\006 \003(\0162\014.DsnpVersion\022\"\n\032graph_p" +
-      "ublic_key_schema_id\030\007 \001(\r\032?\n\016SchemaMapEn" +
-      "try\022\013\n\003key\030\001 \001(\r\022\034\n\005value\030\002 \001(\0132\r.Schema" +
-      "Config:\0028\001\"b\n\013Environment\022*\n\020environment" +
-      "_type\030\001 \001(\0162\020.EnvironmentType\022\034\n\006config\030" +
-      "\002 \001(\0132\007.ConfigH\000\210\001\001B\t\n\007_config\"\211\004\n\007Updat" +
-      "es\022\037\n\006update\030\001 \003(\0132\017.Updates.Update\032\334\003\n\006" +
-      "Update\0224\n\007persist\030\001 \001(\0132!.Updates.Update" +
-      ".PersistPageUpdateH\000\0222\n\006delete\030\002 \001(\0132 .U" +
-      "pdates.Update.DeletePageUpdateH\000\022/\n\007add_" +
-      "key\030\003 \001(\0132\034.Updates.Update.AddKeyUpdateH" +
-      "\000\032w\n\021PersistPageUpdate\022\032\n\022owner_dsnp_use" +
-      "r_id\030\001 \001(\004\022\021\n\tschema_id\030\002 \001(\r\022\017\n\007page_id" +
-      "\030\003 \001(\r\022\021\n\tprev_hash\030\004 \001(\r\022\017\n\007payload\030\005 \001" +
-      "(\014\032e\n\020DeletePageUpdate\022\032\n\022owner_dsnp_use" +
-      "r_id\030\001 \001(\004\022\021\n\tschema_id\030\002 \001(\r\022\017\n\007page_id" +
-      "\030\003 \001(\r\022\021\n\tprev_hash\030\004 \001(\r\032N\n\014AddKeyUpdat" +
-      "e\022\032\n\022owner_dsnp_user_id\030\001 \001(\004\022\021\n\tprev_ha" +
-      "sh\030\002 \001(\r\022\017\n\007payload\030\003 \001(\014B\007\n\005inner\"n\n\016Ds" +
-      "npGraphEdges\022+\n\004edge\030\001 \003(\0132\035.DsnpGraphEd" +
-      "ges.DsnpGraphEdge\032/\n\rDsnpGraphEdge\022\017\n\007us" +
-      "er_id\030\001 \001(\004\022\r\n\005since\030\002 \001(\004\"\031\n\tDsnpUsers\022" +
-      "\014\n\004user\030\001 \003(\004\"q\n\016DsnpPublicKeys\0221\n\npubli" +
-      "c_key\030\001 \003(\0132\035.DsnpPublicKeys.DsnpPublicK" +
-      "ey\032,\n\rDsnpPublicKey\022\013\n\003key\030\001 \001(\014\022\016\n\006key_" +
-      "id\030\002 \001(\004*&\n\013PrivacyType\022\n\n\006Public\020\000\022\013\n\007P" +
-      "rivate\020\001*b\n\016ConnectionType\022\020\n\014FollowPubl" +
-      "ic\020\000\022\021\n\rFollowPrivate\020\001\022\024\n\020FriendshipPub" +
-      "lic\020\002\022\025\n\021FriendshipPrivate\020\003*\035\n\013DsnpVers" +
-      "ion\022\016\n\nVersion1_0\020\000*E\n\017EnvironmentType\022\013" +
-      "\n\007MainNet\020\000\022\020\n\014TestnetPaseo\020\001\022\n\n\006Rococo\020" +
-      "\002\022\007\n\003Dev\020\003B%\n!io.projectliberty.graphsdk" +
-      ".modelsP\001b\006proto3"
+      "\n!bridge/common/protos/output.proto\032 bri" +
+      "dge/common/protos/input.proto\"\\\n\014SchemaC" +
+      "onfig\022\"\n\014dsnp_version\030\001 \001(\0162\014.DsnpVersio" +
+      "n\022(\n\017connection_type\030\002 \001(\0162\017.ConnectionT" +
+      "ype\"\340\002\n\006Config\022%\n\035sdk_max_stale_friendsh" +
+      "ip_days\030\001 \001(\r\022!\n\031max_graph_page_size_byt" +
+      "es\030\002 \001(\r\022\023\n\013max_page_id\030\003 \001(\r\022\037\n\027max_key" +
+      "_page_size_bytes\030\004 \001(\r\022*\n\nschema_map\030\005 \003" +
+      "(\0132\026.Config.SchemaMapEntry\022#\n\rdsnp_versi" +
+      "ons\030\006 \003(\0162\014.DsnpVersion\022\"\n\032graph_public_" +
+      "key_schema_id\030\007 \001(\r\022 \n\030max_update_payloa" +
+      "d_bytes\030\010 \001(\r\032?\n\016SchemaMapEntry\022\013\n\003key\030\001" +
+      " \001(\r\022\034\n\005value\030\002 \001(\0132\r.SchemaConfig:\0028\001\"b" +
+      "\n\013Environment\022*\n\020environment_type\030\001 \001(\0162" +
+      "\020.EnvironmentType\022\034\n\006config\030\002 \001(\0132\007.Conf" +
+      "igH\000\210\001\001B\t\n\007_config\"\250\005\n\007Updates\022\037\n\006update" +
+      "\030\001 \003(\0132\017.Updates.Update\032\373\004\n\006Update\0224\n\007pe" +
+      "rsist\030\001 \001(\0132!.Updates.Update.PersistPage" +
+      "UpdateH\000\0222\n\006delete\030\002 \001(\0132 .Updates.Updat" +
+      "e.DeletePageUpdateH\000\022/\n\007add_key\030\003 \001(\0132\034." +
+      "Updates.Update.AddKeyUpdateH\000\032\253\001\n\021Persis" +
+      "tPageUpdate\022\032\n\022owner_dsnp_user_id\030\001 \001(\004\022" +
+      "\021\n\tschema_id\030\002 \001(\r\022\017\n\007page_id\030\003 \001(\r\022\021\n\tp" +
+      "rev_hash\030\004 \001(\r\022\017\n\007payload\030\005 \001(\014\022#\n\nchain" +
+      "_head\030\006 \001(\0132\n.ChainHeadH\000\210\001\001B\r\n\013_chain_h" +
+      "ead\032\231\001\n\020DeletePageUpdate\022\032\n\022owner_dsnp_u" +
+      "ser_id\030\001 \001(\004\022\021\n\tschema_id\030\002 \001(\r\022\017\n\007page_" +
+      "id\030\003 \001(\r\022\021\n\tprev_hash\030\004 \001(\r\022#\n\nchain_hea" +
+      "d\030\005 \001(\0132\n.ChainHeadH\000\210\001\001B\r\n\013_chain_head\032" +
+      "\202\001\n\014AddKeyUpdate\022\032\n\022owner_dsnp_user_id\030\001" +
+      " \001(\004\022\021\n\tprev_hash\030\002 \001(\r\022\017\n\007payload\030\003 \001(\014" +
+      "\022#\n\nchain_head\030\004 \001(\0132\n.ChainHeadH\000\210\001\001B\r\n" +
+      "\013_chain_headB\007\n\005inner\"n\n\016DsnpGraphEdges\022" +
+      "+\n\004edge\030\001 \003(\0132\035.DsnpGraphEdges.DsnpGraph" +
+      "Edge\032/\n\rDsnpGraphEdge\022\017\n\007user_id\030\001 \001(\004\022\r" +
+      "\n\005since\030\002 \001(\004\"\031\n\tDsnpUsers\022\014\n\004user\030\001 \003(\004" +
+      "\"q\n\016DsnpPublicKeys\0221\n\npublic_key\030\001 \003(\0132\035" +
+      ".DsnpPublicKeys.DsnpPublicKey\032,\n\rDsnpPub" +
+      "licKey\022\013\n\003key\030\001 \001(\014\022\016\n\006key_id\030\002 \001(\004*&\n\013P" +
+      "rivacyType\022\n\n\006Public\020\000\022\013\n\007Private\020\001*b\n\016C" +
+      "onnectionType\022\020\n\014FollowPublic\020\000\022\021\n\rFollo" +
+      "wPrivate\020\001\022\024\n\020FriendshipPublic\020\002\022\025\n\021Frie" +
+      "ndshipPrivate\020\003*\035\n\013DsnpVersion\022\016\n\nVersio" +
+      "n1_0\020\000*E\n\017EnvironmentType\022\013\n\007MainNet\020\000\022\020" +
+      "\n\014TestnetPaseo\020\001\022\n\n\006Rococo\020\002\022\007\n\003Dev\020\003B%\n" +
+      "!io.projectliberty.graphsdk.modelsP\001b\006pr" +
+      "oto3"
     };
     descriptor = com.google.protobuf.Descriptors.FileDescriptor
       .internalBuildGeneratedFileFrom(descriptorData,
         new com.google.protobuf.Descriptors.FileDescriptor[] {
+          io.projectliberty.graphsdk.models.Input.getDescriptor(),
         });
     internal_static_SchemaConfig_descriptor =
       getDescriptor().getMessageTypes().get(0);
@@ -150,7 +157,7 @@ public final class Output {
     internal_static_Config_fieldAccessorTable = new
       com.google.protobuf.GeneratedMessageV3.FieldAccessorTable(
         internal_static_Config_descriptor,
-        new java.lang.String[] { "SdkMaxStaleFriendshipDays", "MaxGraphPageSizeBytes", "MaxPageId", "MaxKeyPageSizeBytes", "SchemaMap", "DsnpVersions", "GraphPublicKeySchemaId", });
+        new java.lang.String[] { "SdkMaxStaleFriendshipDays", "MaxGraphPageSizeBytes", "MaxPageId", "MaxKeyPageSizeBytes", "SchemaMap", "DsnpVersions", "GraphPublicKeySchemaId", "MaxUpdatePayloadBytes", });
     internal_static_Config_SchemaMapEntry_descriptor =
       internal_static_Config_descriptor.getNestedTypes().get(0);
     internal_static_Config_SchemaMapEntry_fieldAccessorTable = new
@@ -180,19 +187,19 @@ public final class Output {
     internal_static_Updates_Update_PersistPageUpdate_fieldAccessorTable = new
       com.google.protobuf.GeneratedMessageV3.FieldAccessorTable(
         internal_static_Updates_Update_PersistPageUpdate_descriptor,
-        new java.lang.String[] { "OwnerDsnpUserId", "SchemaId", "PageId", "PrevHash", "Payload", });
+        new java.lang.String[] { "OwnerDsnpUserId", "SchemaId", "PageId", "PrevHash", "Payload", "ChainHead", "ChainHead", });
     internal_static_Updates_Update_DeletePageUpdate_descriptor =
       internal_static_Updates_Update_descriptor.getNestedTypes().get(1);
     internal_static_Updates_Update_DeletePageUpdate_fieldAccessorTable = new
       com.google.protobuf.GeneratedMessageV3.FieldAccessorTable(
         internal_static_Updates_Update_DeletePageUpdate_descriptor,
-        new java.lang.String[] { "OwnerDsnpUserId", "SchemaId", "PageId", "PrevHash", });
+        new java.lang.String[] { "OwnerDsnpUserId", "SchemaId", "PageId", "PrevHash", "ChainHead", "ChainHead", });
     internal_static_Updates_Update_AddKeyUpdate_descriptor =
       internal_static_Updates_Update_descriptor.getNestedTypes().get(2);
     internal_static_Updates_Update_AddKeyUpdate_fieldAccessorTable = new
       com.google.protobuf.GeneratedMessageV3.FieldAccessorTable(
         internal_static_Updates_Update_AddKeyUpdate_descriptor,
-        new java.lang.String[] { "OwnerDsnpUserId", "PrevHash", "Payload", });
+        new java.lang.String[] { "OwnerDsnpUserId", "PrevHash", "Payload", "ChainHead", "ChainHead", });
     internal_static_DsnpGraphEdges_descriptor =
       getDescriptor().getMessageTypes().get(4);
     internal_static_DsnpGraphEdges_fieldAccessorTable = new
@@ -223,6 +230,7 @@ public final class Output {
       com.google.protobuf.GeneratedMessageV3.FieldAccessorTable(
         internal_static_DsnpPublicKeys_DsnpPublicKey_descriptor,
         new java.lang.String[] { "Key", "KeyId", });
+    io.projectliberty.graphsdk.models.Input.getDescriptor();
   }
 
   // @@protoc_insertion_point(outer_class_scope)
@@ -0,0 +1,122 @@
+// Generated by the protocol buffer compiler.  DO NOT EDIT!
+// source: bridge/common/protos/input.proto
+
+package io.projectliberty.graphsdk.models;
+
+/**
+ * Protobuf enum {@code TimestampPolicy}
+ */
+public enum TimestampPolicy
+    implements com.google.protobuf.ProtocolMessageEnum {
+  /**
+   * <code>CallerProvided = 0;</code>
+   */
+  CallerProvided(0),
+  /**
+   * <code>Now = 1;</code>
+   */
+  Now(1),
+  /**
+   * <code>Zero = 2;</code>
+   */
+  Zero(2),
+  UNRECOGNIZED(-1),
+  ;
+
+  /**
+   * <code>CallerProvided = 0;</code>
+   */
+  public static final int CallerProvided_VALUE = 0;
+  /**
+   * <code>Now = 1;</code>
+   */
+  public static final int Now_VALUE = 1;
+  /**
+   * <code>Zero = 2;</code>
+   */
+  public static final int Zero_VALUE = 2;
+
+
+  public final int getNumber() {
+    if (this == UNRECOGNIZED) {
+      throw new java.lang.IllegalArgumentException(
+          "Can't get the number of an unknown enum value.");
+    }
+    return value;
+  }
+
+  /**
+   * @param value The numeric wire value of the corresponding enum entry.
+   * @return The enum associated with the given numeric wire value.
+   * @deprecated Use {@link #forNumber(int)} instead.
+   */
+  @java.lang.Deprecated
+  public static TimestampPolicy valueOf(int value) {
+    return forNumber(value);
+  }
+
+  /**
+   * @param value The numeric wire value of the corresponding enum entry.
+   * @return The enum associated with the given numeric wire value.
+   */
+  public static TimestampPolicy forNumber(int value) {
+    switch (value) {
+      case 0: return CallerProvided;
+      case 1: return Now;
+      case 2: return Zero;
+      default: return null;
+    }
+  }
+
+  public static com.google.protobuf.Internal.EnumLiteMap<TimestampPolicy>
+      internalGetValueMap() {
+    return internalValueMap;
+  }
+  private static final com.google.protobuf.Internal.EnumLiteMap<
+      TimestampPolicy> internalValueMap =
+        new com.google.protobuf.Internal.EnumLiteMap<TimestampPolicy>() {
+          public TimestampPolicy findValueByNumber(int number) {
+            return TimestampPolicy.forNumber(number);
+          }
+        };
+
+  public final com.google.protobuf.Descriptors.EnumValueDescriptor
+      getValueDescriptor() {
+    if (this == UNRECOGNIZED) {
+      throw new java.lang.IllegalStateException(
+          "Can't get the descriptor of an unrecognized enum value.");
+    }
+    return getDescriptor().getValues().get(ordinal());
+  }
+  public final com.google.protobuf.Descriptors.EnumDescriptor
+      getDescriptorForType() {
+    return getDescriptor();
+  }
+  public static final com.google.protobuf.Descriptors.EnumDescriptor
+      getDescriptor() {
+    return io.projectliberty.graphsdk.models.Input.getDescriptor().getEnumTypes().get(1);
+  }
+
+  private static final TimestampPolicy[] VALUES = values();
+
+  public static TimestampPolicy valueOf(
+      com.google.protobuf.Descriptors.EnumValueDescriptor desc) {
+    if (desc.getType() != getDescriptor()) {
+      throw new java.lang.IllegalArgumentException(
+        "EnumValueDescriptor is not for this type.");
+    }
+    if (desc.getIndex() == -1) {
+      return UNRECOGNIZED;
+    }
+    return VALUES[desc.getIndex()];
+  }
+
+  private final int value;
+
+  private TimestampPolicy(int value) {
+    this.value = value;
+  }
+
+  // @@protoc_insertion_point(enum_scope:TimestampPolicy)
+}
+
@@ -168,6 +168,21 @@ private static final long serialVersionUID = 0L;
        * @return The payload.
        */
       com.google.protobuf.ByteString getPayload();
+
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       * @return Whether the chainHead field is set.
+       */
+      boolean hasChainHead();
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       * @return The chainHead.
+       */
+      io.projectliberty.graphsdk.models.ChainHead getChainHead();
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       */
+      io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder();
     }
     /**
      * Protobuf type {@code Updates.Update.PersistPageUpdate}
@@ -210,6 +225,7 @@ private static final long serialVersionUID = 0L;
                 io.projectliberty.graphsdk.models.Updates.Update.PersistPageUpdate.class, io.projectliberty.graphsdk.models.Updates.Update.PersistPageUpdate.Builder.class);
       }
 
+      private int bitField0_;
       public static final int OWNER_DSNP_USER_ID_FIELD_NUMBER = 1;
       private long ownerDsnpUserId_ = 0L;
       /**
@@ -265,6 +281,32 @@ private static final long serialVersionUID = 0L;
         return payload_;
       }
 
+      public static final int CHAIN_HEAD_FIELD_NUMBER = 6;
+      private io.projectliberty.graphsdk.models.ChainHead chainHead_;
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       * @return Whether the chainHead field is set.
+       */
+      @java.lang.Override
+      public boolean hasChainHead() {
+        return ((bitField0_ & 0x00000001) != 0);
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       * @return The chainHead.
+       */
+      @java.lang.Override
+      public io.projectliberty.graphsdk.models.ChainHead getChainHead() {
+        return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 6;</code>
+       */
+      @java.lang.Override
+      public io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder() {
+        return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+      }
+
       private byte memoizedIsInitialized = -1;
       @java.lang.Override
       public final boolean isInitialized() {
@@ -294,6 +336,9 @@ private static final long serialVersionUID = 0L;
         if (!payload_.isEmpty()) {
           output.writeBytes(5, payload_);
         }
+        if (((bitField0_ & 0x00000001) != 0)) {
+          output.writeMessage(6, getChainHead());
+        }
         getUnknownFields().writeTo(output);
       }
 
@@ -323,6 +368,10 @@ private static final long serialVersionUID = 0L;
           size += com.google.protobuf.CodedOutputStream
             .computeBytesSize(5, payload_);
         }
+        if (((bitField0_ & 0x00000001) != 0)) {
+          size += com.google.protobuf.CodedOutputStream
+            .computeMessageSize(6, getChainHead());
+        }
         size += getUnknownFields().getSerializedSize();
         memoizedSize = size;
         return size;
@@ -348,6 +397,11 @@ private static final long serialVersionUID = 0L;
             != other.getPrevHash()) return false;
         if (!getPayload()
             .equals(other.getPayload())) return false;
+        if (hasChainHead() != other.hasChainHead()) return false;
+        if (hasChainHead()) {
+          if (!getChainHead()
+              .equals(other.getChainHead())) return false;
+        }
         if (!getUnknownFields().equals(other.getUnknownFields())) return false;
         return true;
       }
@@ -370,6 +424,10 @@ private static final long serialVersionUID = 0L;
         hash = (53 * hash) + getPrevHash();
         hash = (37 * hash) + PAYLOAD_FIELD_NUMBER;
         hash = (53 * hash) + getPayload().hashCode();
+        if (hasChainHead()) {
+          hash = (37 * hash) + CHAIN_HEAD_FIELD_NUMBER;
+          hash = (53 * hash) + getChainHead().hashCode();
+        }
         hash = (29 * hash) + getUnknownFields().hashCode();
         memoizedHashCode = hash;
         return hash;
@@ -487,13 +545,19 @@ private static final long serialVersionUID = 0L;
 
         // Construct using io.projectliberty.graphsdk.models.Updates.Update.PersistPageUpdate.newBuilder()
         private Builder() {
-
+          maybeForceBuilderInitialization();
         }
 
         private Builder(
             com.google.protobuf.GeneratedMessageV3.BuilderParent parent) {
           super(parent);
-
+          maybeForceBuilderInitialization();
+        }
+        private void maybeForceBuilderInitialization() {
+          if (com.google.protobuf.GeneratedMessageV3
+                  .alwaysUseFieldBuilders) {
+            getChainHeadFieldBuilder();
+          }
         }
         @java.lang.Override
         public Builder clear() {
@@ -504,6 +568,11 @@ private static final long serialVersionUID = 0L;
           pageId_ = 0;
           prevHash_ = 0;
           payload_ = com.google.protobuf.ByteString.EMPTY;
+          chainHead_ = null;
+          if (chainHeadBuilder_ != null) {
+            chainHeadBuilder_.dispose();
+            chainHeadBuilder_ = null;
+          }
           return this;
         }
 
@@ -552,6 +621,14 @@ private static final long serialVersionUID = 0L;
           if (((from_bitField0_ & 0x00000010) != 0)) {
             result.payload_ = payload_;
           }
+          int to_bitField0_ = 0;
+          if (((from_bitField0_ & 0x00000020) != 0)) {
+            result.chainHead_ = chainHeadBuilder_ == null
+                ? chainHead_
+                : chainHeadBuilder_.build();
+            to_bitField0_ |= 0x00000001;
+          }
+          result.bitField0_ |= to_bitField0_;
         }
 
         @java.lang.Override
@@ -613,6 +690,9 @@ private static final long serialVersionUID = 0L;
           if (other.getPayload() != com.google.protobuf.ByteString.EMPTY) {
             setPayload(other.getPayload());
           }
+          if (other.hasChainHead()) {
+            mergeChainHead(other.getChainHead());
+          }
           this.mergeUnknownFields(other.getUnknownFields());
           onChanged();
           return this;
@@ -664,6 +744,13 @@ private static final long serialVersionUID = 0L;
                   bitField0_ |= 0x00000010;
                   break;
                 } // case 42
+                case 50: {
+                  input.readMessage(
+                      getChainHeadFieldBuilder().getBuilder(),
+                      extensionRegistry);
+                  bitField0_ |= 0x00000020;
+                  break;
+                } // case 50
                 default: {
                   if (!super.parseUnknownField(input, extensionRegistry, tag)) {
                     done = true; // was an endgroup tag
@@ -840,6 +927,127 @@ private static final long serialVersionUID = 0L;
           onChanged();
           return this;
         }
+
+        private io.projectliberty.graphsdk.models.ChainHead chainHead_;
+        private com.google.protobuf.SingleFieldBuilderV3<
+            io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder> chainHeadBuilder_;
+        /**
+         * <code>optional .ChainHead chain_head = 6;</code>
+         * @return Whether the chainHead field is set.
+         */
+        public boolean hasChainHead() {
+          return ((bitField0_ & 0x00000020) != 0);
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 6;</code>
+         * @return The chainHead.
+         */
+        public io.projectliberty.graphsdk.models.ChainHead getChainHead() {
+          if (chainHeadBuilder_ == null) {
+            return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+          } else {
+            return chainHeadBuilder_.getMessage();
+          }
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 6;</code>
+         */
+        public Builder setChainHead(io.projectliberty.graphsdk.models.ChainHead value) {
+          if (chainHeadBuilder_ == null) {
+            if (value == null) {
+              throw new NullPointerException();
+            }
+            chainHead_ = value;
+          } else {
+            chainHeadBuilder_.setMessage(value);
+          }
+          bitField0_ |= 0x00000020;
+          onChanged();
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 6;</code>
+         */
+        public Builder setChainHead(
+            io.projectliberty.graphsdk.models.ChainHead.Builder builderForValue) {
+          if (chainHeadBuilder_ == null) {
+            chainHead_ = builderForValue.build();
+          } else {
+            chainHeadBuilder_.setMessage(builderForValue.build());
+          }
+          bitField0_ |= 0x00000020;
+          onChanged();
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 6;</code>
+         */
+        public Builder mergeChainHead(io.projectliberty.graphsdk.models.ChainHead value) {
+          if (chainHeadBuilder_ == null) {
+            if (((bitField0_ & 0x00000020) != 0) &&
+              chainHead_ != null &&
+              chainHead_ != io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance()) {
+              getChainHeadBuilder().mergeFrom(value);
+            } else {
+              chainHead_ = value;
+            }
+          } else {
+            chainHeadBuilder_.mergeFrom(value);
+          }
+          if (chainHead_ != null) {
+            bitField0_ |= 0x00000020;
+            onChanged();
+          }
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 6;</code>
+         */
+        public Builder clearChainHead() {
+          bitField0_ = (bitField0_ & ~0x00000020);
+          chainHead_ = null;
+          if (chainHeadBuilder_ != null) {
+            chainHeadBuilder_.dispose();
+            chainHeadBuilder_ = null;
+          }
+          onChanged();
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 6;</code>
+         */
+        public io.projectliberty.graphsdk.models.ChainHead.Builder getChainHeadBuilder() {
+          bitField0_ |= 0x00000020;
+          onChanged();
+          return getChainHeadFieldBuilder().getBuilder();
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 6;</code>
+         */
+        public io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder() {
+          if (chainHeadBuilder_ != null) {
+            return chainHeadBuilder_.getMessageOrBuilder();
+          } else {
+            return chainHead_ == null ?
+                io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+          }
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 6;</code>
+         */
+        private com.google.protobuf.SingleFieldBuilderV3<
+            io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder> 
+            getChainHeadFieldBuilder() {
+          if (chainHeadBuilder_ == null) {
+            chainHeadBuilder_ = new com.google.protobuf.SingleFieldBuilderV3<
+                io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder>(
+                    getChainHead(),
+                    getParentForChildren(),
+                    isClean());
+            chainHead_ = null;
+          }
+          return chainHeadBuilder_;
+        }
         @java.lang.Override
         public final Builder setUnknownFields(
             final com.google.protobuf.UnknownFieldSet unknownFields) {
@@ -931,6 +1139,21 @@ private static final long serialVersionUID = 0L;
        * @return The prevHash.
        */
       int getPrevHash();
+
+      /**
+       * <code>optional .ChainHead chain_head = 5;</code>
+       * @return Whether the chainHead field is set.
+       */
+      boolean hasChainHead();
+      /**
+       * <code>optional .ChainHead chain_head = 5;</code>
+       * @return The chainHead.
+       */
+      io.projectliberty.graphsdk.models.ChainHead getChainHead();
+      /**
+       * <code>optional .ChainHead chain_head = 5;</code>
+       */
+      io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder();
     }
     /**
      * Protobuf type {@code Updates.Update.DeletePageUpdate}
@@ -972,6 +1195,7 @@ private static final long serialVersionUID = 0L;
                 io.projectliberty.graphsdk.models.Updates.Update.DeletePageUpdate.class, io.projectliberty.graphsdk.models.Updates.Update.DeletePageUpdate.Builder.class);
       }
 
+      private int bitField0_;
       public static final int OWNER_DSNP_USER_ID_FIELD_NUMBER = 1;
       private long ownerDsnpUserId_ = 0L;
       /**
@@ -1016,6 +1240,32 @@ private static final long serialVersionUID = 0L;
         return prevHash_;
       }
 
+      public static final int CHAIN_HEAD_FIELD_NUMBER = 5;
+      private io.projectliberty.graphsdk.models.ChainHead chainHead_;
+      /**
+       * <code>optional .ChainHead chain_head = 5;</code>
+       * @return Whether the chainHead field is set.
+       */
+      @java.lang.Override
+      public boolean hasChainHead() {
+        return ((bitField0_ & 0x00000001) != 0);
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 5;</code>
+       * @return The chainHead.
+       */
+      @java.lang.Override
+      public io.projectliberty.graphsdk.models.ChainHead getChainHead() {
+        return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 5;</code>
+       */
+      @java.lang.Override
+      public io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder() {
+        return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+      }
+
       private byte memoizedIsInitialized = -1;
       @java.lang.Override
       public final boolean isInitialized() {
@@ -1042,6 +1292,9 @@ private static final long serialVersionUID = 0L;
         if (prevHash_ != 0) {
           output.writeUInt32(4, prevHash_);
         }
+        if (((bitField0_ & 0x00000001) != 0)) {
+          output.writeMessage(5, getChainHead());
+        }
         getUnknownFields().writeTo(output);
       }
 
@@ -1067,6 +1320,10 @@ private static final long serialVersionUID = 0L;
           size += com.google.protobuf.CodedOutputStream
             .computeUInt32Size(4, prevHash_);
         }
+        if (((bitField0_ & 0x00000001) != 0)) {
+          size += com.google.protobuf.CodedOutputStream
+            .computeMessageSize(5, getChainHead());
+        }
         size += getUnknownFields().getSerializedSize();
         memoizedSize = size;
         return size;
@@ -1090,6 +1347,11 @@ private static final long serialVersionUID = 0L;
             != other.getPageId()) return false;
         if (getPrevHash()
             != other.getPrevHash()) return false;
+        if (hasChainHead() != other.hasChainHead()) return false;
+        if (hasChainHead()) {
+          if (!getChainHead()
+              .equals(other.getChainHead())) return false;
+        }
         if (!getUnknownFields().equals(other.getUnknownFields())) return false;
         return true;
       }
@@ -1110,6 +1372,10 @@ private static final long serialVersionUID = 0L;
         hash = (53 * hash) + getPageId();
         hash = (37 * hash) + PREV_HASH_FIELD_NUMBER;
         hash = (53 * hash) + getPrevHash();
+        if (hasChainHead()) {
+          hash = (37 * hash) + CHAIN_HEAD_FIELD_NUMBER;
+          hash = (53 * hash) + getChainHead().hashCode();
+        }
         hash = (29 * hash) + getUnknownFields().hashCode();
         memoizedHashCode = hash;
         return hash;
@@ -1227,13 +1493,19 @@ private static final long serialVersionUID = 0L;
 
         // Construct using io.projectliberty.graphsdk.models.Updates.Update.DeletePageUpdate.newBuilder()
         private Builder() {
-
+          maybeForceBuilderInitialization();
         }
 
         private Builder(
             com.google.protobuf.GeneratedMessageV3.BuilderParent parent) {
           super(parent);
-
+          maybeForceBuilderInitialization();
+        }
+        private void maybeForceBuilderInitialization() {
+          if (com.google.protobuf.GeneratedMessageV3
+                  .alwaysUseFieldBuilders) {
+            getChainHeadFieldBuilder();
+          }
         }
         @java.lang.Override
         public Builder clear() {
@@ -1243,6 +1515,11 @@ private static final long serialVersionUID = 0L;
           schemaId_ = 0;
           pageId_ = 0;
           prevHash_ = 0;
+          chainHead_ = null;
+          if (chainHeadBuilder_ != null) {
+            chainHeadBuilder_.dispose();
+            chainHeadBuilder_ = null;
+          }
           return this;
         }
 
@@ -1288,6 +1565,14 @@ private static final long serialVersionUID = 0L;
           if (((from_bitField0_ & 0x00000008) != 0)) {
             result.prevHash_ = prevHash_;
           }
+          int to_bitField0_ = 0;
+          if (((from_bitField0_ & 0x00000010) != 0)) {
+            result.chainHead_ = chainHeadBuilder_ == null
+                ? chainHead_
+                : chainHeadBuilder_.build();
+            to_bitField0_ |= 0x00000001;
+          }
+          result.bitField0_ |= to_bitField0_;
         }
 
         @java.lang.Override
@@ -1346,6 +1631,9 @@ private static final long serialVersionUID = 0L;
           if (other.getPrevHash() != 0) {
             setPrevHash(other.getPrevHash());
           }
+          if (other.hasChainHead()) {
+            mergeChainHead(other.getChainHead());
+          }
           this.mergeUnknownFields(other.getUnknownFields());
           onChanged();
           return this;
@@ -1392,6 +1680,13 @@ private static final long serialVersionUID = 0L;
                   bitField0_ |= 0x00000008;
                   break;
                 } // case 32
+                case 42: {
+                  input.readMessage(
+                      getChainHeadFieldBuilder().getBuilder(),
+                      extensionRegistry);
+                  bitField0_ |= 0x00000010;
+                  break;
+                } // case 42
                 default: {
                   if (!super.parseUnknownField(input, extensionRegistry, tag)) {
                     done = true; // was an endgroup tag
@@ -1536,6 +1831,127 @@ private static final long serialVersionUID = 0L;
           onChanged();
           return this;
         }
+
+        private io.projectliberty.graphsdk.models.ChainHead chainHead_;
+        private com.google.protobuf.SingleFieldBuilderV3<
+            io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder> chainHeadBuilder_;
+        /**
+         * <code>optional .ChainHead chain_head = 5;</code>
+         * @return Whether the chainHead field is set.
+         */
+        public boolean hasChainHead() {
+          return ((bitField0_ & 0x00000010) != 0);
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 5;</code>
+         * @return The chainHead.
+         */
+        public io.projectliberty.graphsdk.models.ChainHead getChainHead() {
+          if (chainHeadBuilder_ == null) {
+            return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+          } else {
+            return chainHeadBuilder_.getMessage();
+          }
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 5;</code>
+         */
+        public Builder setChainHead(io.projectliberty.graphsdk.models.ChainHead value) {
+          if (chainHeadBuilder_ == null) {
+            if (value == null) {
+              throw new NullPointerException();
+            }
+            chainHead_ = value;
+          } else {
+            chainHeadBuilder_.setMessage(value);
+          }
+          bitField0_ |= 0x00000010;
+          onChanged();
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 5;</code>
+         */
+        public Builder setChainHead(
+            io.projectliberty.graphsdk.models.ChainHead.Builder builderForValue) {
+          if (chainHeadBuilder_ == null) {
+            chainHead_ = builderForValue.build();
+          } else {
+            chainHeadBuilder_.setMessage(builderForValue.build());
+          }
+          bitField0_ |= 0x00000010;
+          onChanged();
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 5;</code>
+         */
+        public Builder mergeChainHead(io.projectliberty.graphsdk.models.ChainHead value) {
+          if (chainHeadBuilder_ == null) {
+            if (((bitField0_ & 0x00000010) != 0) &&
+              chainHead_ != null &&
+              chainHead_ != io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance()) {
+              getChainHeadBuilder().mergeFrom(value);
+            } else {
+              chainHead_ = value;
+            }
+          } else {
+            chainHeadBuilder_.mergeFrom(value);
+          }
+          if (chainHead_ != null) {
+            bitField0_ |= 0x00000010;
+            onChanged();
+          }
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 5;</code>
+         */
+        public Builder clearChainHead() {
+          bitField0_ = (bitField0_ & ~0x00000010);
+          chainHead_ = null;
+          if (chainHeadBuilder_ != null) {
+            chainHeadBuilder_.dispose();
+            chainHeadBuilder_ = null;
+          }
+          onChanged();
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 5;</code>
+         */
+        public io.projectliberty.graphsdk.models.ChainHead.Builder getChainHeadBuilder() {
+          bitField0_ |= 0x00000010;
+          onChanged();
+          return getChainHeadFieldBuilder().getBuilder();
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 5;</code>
+         */
+        public io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder() {
+          if (chainHeadBuilder_ != null) {
+            return chainHeadBuilder_.getMessageOrBuilder();
+          } else {
+            return chainHead_ == null ?
+                io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+          }
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 5;</code>
+         */
+        private com.google.protobuf.SingleFieldBuilderV3<
+            io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder> 
+            getChainHeadFieldBuilder() {
+          if (chainHeadBuilder_ == null) {
+            chainHeadBuilder_ = new com.google.protobuf.SingleFieldBuilderV3<
+                io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder>(
+                    getChainHead(),
+                    getParentForChildren(),
+                    isClean());
+            chainHead_ = null;
+          }
+          return chainHeadBuilder_;
+        }
         @java.lang.Override
         public final Builder setUnknownFields(
             final com.google.protobuf.UnknownFieldSet unknownFields) {
@@ -1621,6 +2037,21 @@ private static final long serialVersionUID = 0L;
        * @return The payload.
        */
       com.google.protobuf.ByteString getPayload();
+
+      /**
+       * <code>optional .ChainHead chain_head = 4;</code>
+       * @return Whether the chainHead field is set.
+       */
+      boolean hasChainHead();
+      /**
+       * <code>optional .ChainHead chain_head = 4;</code>
+       * @return The chainHead.
+       */
+      io.projectliberty.graphsdk.models.ChainHead getChainHead();
+      /**
+       * <code>optional .ChainHead chain_head = 4;</code>
+       */
+      io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder();
     }
     /**
      * Protobuf type {@code Updates.Update.AddKeyUpdate}
@@ -1663,6 +2094,7 @@ private static final long serialVersionUID = 0L;
                 io.projectliberty.graphsdk.models.Updates.Update.AddKeyUpdate.class, io.projectliberty.graphsdk.models.Updates.Update.AddKeyUpdate.Builder.class);
       }
 
+      private int bitField0_;
       public static final int OWNER_DSNP_USER_ID_FIELD_NUMBER = 1;
       private long ownerDsnpUserId_ = 0L;
       /**
@@ -1696,6 +2128,32 @@ private static final long serialVersionUID = 0L;
         return payload_;
       }
 
+      public static final int CHAIN_HEAD_FIELD_NUMBER = 4;
+      private io.projectliberty.graphsdk.models.ChainHead chainHead_;
+      /**
+       * <code>optional .ChainHead chain_head = 4;</code>
+       * @return Whether the chainHead field is set.
+       */
+      @java.lang.Override
+      public boolean hasChainHead() {
+        return ((bitField0_ & 0x00000001) != 0);
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 4;</code>
+       * @return The chainHead.
+       */
+      @java.lang.Override
+      public io.projectliberty.graphsdk.models.ChainHead getChainHead() {
+        return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+      }
+      /**
+       * <code>optional .ChainHead chain_head = 4;</code>
+       */
+      @java.lang.Override
+      public io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder() {
+        return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+      }
+
       private byte memoizedIsInitialized = -1;
       @java.lang.Override
       public final boolean isInitialized() {
@@ -1719,6 +2177,9 @@ private static final long serialVersionUID = 0L;
         if (!payload_.isEmpty()) {
           output.writeBytes(3, payload_);
         }
+        if (((bitField0_ & 0x00000001) != 0)) {
+          output.writeMessage(4, getChainHead());
+        }
         getUnknownFields().writeTo(output);
       }
 
@@ -1740,6 +2201,10 @@ private static final long serialVersionUID = 0L;
           size += com.google.protobuf.CodedOutputStream
             .computeBytesSize(3, payload_);
         }
+        if (((bitField0_ & 0x00000001) != 0)) {
+          size += com.google.protobuf.CodedOutputStream
+            .computeMessageSize(4, getChainHead());
+        }
         size += getUnknownFields().getSerializedSize();
         memoizedSize = size;
         return size;
@@ -1761,6 +2226,11 @@ private static final long serialVersionUID = 0L;
             != other.getPrevHash()) return false;
         if (!getPayload()
             .equals(other.getPayload())) return false;
+        if (hasChainHead() != other.hasChainHead()) return false;
+        if (hasChainHead()) {
+          if (!getChainHead()
+              .equals(other.getChainHead())) return false;
+        }
         if (!getUnknownFields().equals(other.getUnknownFields())) return false;
         return true;
       }
@@ -1779,6 +2249,10 @@ private static final long serialVersionUID = 0L;
         hash = (53 * hash) + getPrevHash();
         hash = (37 * hash) + PAYLOAD_FIELD_NUMBER;
         hash = (53 * hash) + getPayload().hashCode();
+        if (hasChainHead()) {
+          hash = (37 * hash) + CHAIN_HEAD_FIELD_NUMBER;
+          hash = (53 * hash) + getChainHead().hashCode();
+        }
         hash = (29 * hash) + getUnknownFields().hashCode();
         memoizedHashCode = hash;
         return hash;
@@ -1896,13 +2370,19 @@ private static final long serialVersionUID = 0L;
 
         // Construct using io.projectliberty.graphsdk.models.Updates.Update.AddKeyUpdate.newBuilder()
         private Builder() {
-
+          maybeForceBuilderInitialization();
         }
 
         private Builder(
             com.google.protobuf.GeneratedMessageV3.BuilderParent parent) {
           super(parent);
-
+          maybeForceBuilderInitialization();
+        }
+        private void maybeForceBuilderInitialization() {
+          if (com.google.protobuf.GeneratedMessageV3
+                  .alwaysUseFieldBuilders) {
+            getChainHeadFieldBuilder();
+          }
         }
         @java.lang.Override
         public Builder clear() {
@@ -1911,6 +2391,11 @@ private static final long serialVersionUID = 0L;
           ownerDsnpUserId_ = 0L;
           prevHash_ = 0;
           payload_ = com.google.protobuf.ByteString.EMPTY;
+          chainHead_ = null;
+          if (chainHeadBuilder_ != null) {
+            chainHeadBuilder_.dispose();
+            chainHeadBuilder_ = null;
+          }
           return this;
         }
 
@@ -1953,6 +2438,14 @@ private static final long serialVersionUID = 0L;
           if (((from_bitField0_ & 0x00000004) != 0)) {
             result.payload_ = payload_;
           }
+          int to_bitField0_ = 0;
+          if (((from_bitField0_ & 0x00000008) != 0)) {
+            result.chainHead_ = chainHeadBuilder_ == null
+                ? chainHead_
+                : chainHeadBuilder_.build();
+            to_bitField0_ |= 0x00000001;
+          }
+          result.bitField0_ |= to_bitField0_;
         }
 
         @java.lang.Override
@@ -2008,6 +2501,9 @@ private static final long serialVersionUID = 0L;
           if (other.getPayload() != com.google.protobuf.ByteString.EMPTY) {
             setPayload(other.getPayload());
           }
+          if (other.hasChainHead()) {
+            mergeChainHead(other.getChainHead());
+          }
           this.mergeUnknownFields(other.getUnknownFields());
           onChanged();
           return this;
@@ -2049,6 +2545,13 @@ private static final long serialVersionUID = 0L;
                   bitField0_ |= 0x00000004;
                   break;
                 } // case 26
+                case 34: {
+                  input.readMessage(
+                      getChainHeadFieldBuilder().getBuilder(),
+                      extensionRegistry);
+                  bitField0_ |= 0x00000008;
+                  break;
+                } // case 34
                 default: {
                   if (!super.parseUnknownField(input, extensionRegistry, tag)) {
                     done = true; // was an endgroup tag
@@ -2161,6 +2664,127 @@ private static final long serialVersionUID = 0L;
           onChanged();
           return this;
         }
+
+        private io.projectliberty.graphsdk.models.ChainHead chainHead_;
+        private com.google.protobuf.SingleFieldBuilderV3<
+            io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder> chainHeadBuilder_;
+        /**
+         * <code>optional .ChainHead chain_head = 4;</code>
+         * @return Whether the chainHead field is set.
+         */
+        public boolean hasChainHead() {
+          return ((bitField0_ & 0x00000008) != 0);
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 4;</code>
+         * @return The chainHead.
+         */
+        public io.projectliberty.graphsdk.models.ChainHead getChainHead() {
+          if (chainHeadBuilder_ == null) {
+            return chainHead_ == null ? io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+          } else {
+            return chainHeadBuilder_.getMessage();
+          }
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 4;</code>
+         */
+        public Builder setChainHead(io.projectliberty.graphsdk.models.ChainHead value) {
+          if (chainHeadBuilder_ == null) {
+            if (value == null) {
+              throw new NullPointerException();
+            }
+            chainHead_ = value;
+          } else {
+            chainHeadBuilder_.setMessage(value);
+          }
+          bitField0_ |= 0x00000008;
+          onChanged();
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 4;</code>
+         */
+        public Builder setChainHead(
+            io.projectliberty.graphsdk.models.ChainHead.Builder builderForValue) {
+          if (chainHeadBuilder_ == null) {
+            chainHead_ = builderForValue.build();
+          } else {
+            chainHeadBuilder_.setMessage(builderForValue.build());
+          }
+          bitField0_ |= 0x00000008;
+          onChanged();
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 4;</code>
+         */
+        public Builder mergeChainHead(io.projectliberty.graphsdk.models.ChainHead value) {
+          if (chainHeadBuilder_ == null) {
+            if (((bitField0_ & 0x00000008) != 0) &&
+              chainHead_ != null &&
+              chainHead_ != io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance()) {
+              getChainHeadBuilder().mergeFrom(value);
+            } else {
+              chainHead_ = value;
+            }
+          } else {
+            chainHeadBuilder_.mergeFrom(value);
+          }
+          if (chainHead_ != null) {
+            bitField0_ |= 0x00000008;
+            onChanged();
+          }
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 4;</code>
+         */
+        public Builder clearChainHead() {
+          bitField0_ = (bitField0_ & ~0x00000008);
+          chainHead_ = null;
+          if (chainHeadBuilder_ != null) {
+            chainHeadBuilder_.dispose();
+            chainHeadBuilder_ = null;
+          }
+          onChanged();
+          return this;
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 4;</code>
+         */
+        public io.projectliberty.graphsdk.models.ChainHead.Builder getChainHeadBuilder() {
+          bitField0_ |= 0x00000008;
+          onChanged();
+          return getChainHeadFieldBuilder().getBuilder();
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 4;</code>
+         */
+        public io.projectliberty.graphsdk.models.ChainHeadOrBuilder getChainHeadOrBuilder() {
+          if (chainHeadBuilder_ != null) {
+            return chainHeadBuilder_.getMessageOrBuilder();
+          } else {
+            return chainHead_ == null ?
+                io.projectliberty.graphsdk.models.ChainHead.getDefaultInstance() : chainHead_;
+          }
+        }
+        /**
+         * <code>optional .ChainHead chain_head = 4;</code>
+         */
+        private com.google.protobuf.SingleFieldBuilderV3<
+            io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder> 
+            getChainHeadFieldBuilder() {
+          if (chainHeadBuilder_ == null) {
+            chainHeadBuilder_ = new com.google.protobuf.SingleFieldBuilderV3<
+                io.projectliberty.graphsdk.models.ChainHead, io.projectliberty.graphsdk.models.ChainHead.Builder, io.projectliberty.graphsdk.models.ChainHeadOrBuilder>(
+                    getChainHead(),
+                    getParentForChildren(),
+                    isClean());
+            chainHead_ = null;
+          }
+          return chainHeadBuilder_;
+        }
         @java.lang.Override
         public final Builder setUnknownFields(
             final com.google.protobuf.UnknownFieldSet unknownFields) {
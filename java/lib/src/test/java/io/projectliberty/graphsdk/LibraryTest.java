@@ -105,6 +105,52 @@ class LibraryTest {
                 graph.finalize();
         }
 
+        @Test
+        void get_key_page_stats_without_keys_should_return_empty_usage() throws Exception {
+                // arrange
+                var configuration = Configuration.getMainNet();
+                var graph = new Graph(configuration);
+
+                // act
+                var stats = graph.getKeyPageStats(1);
+
+                // assert
+                assertEquals(0, stats.keyCount());
+                assertEquals(0, stats.usedBytes());
+                assertTrue(stats.maxBytes() > 0);
+                graph.finalize();
+        }
+
+        @Test
+        void get_next_key_id_without_keys_should_return_first_index() throws Exception {
+                // arrange
+                var graph = new Graph(Configuration.getMainNet());
+
+                // act
+                var keyId = graph.getNextKeyId(1);
+
+                // assert
+                assertEquals(0, keyId);
+                graph.finalize();
+        }
+
+        @Test
+        void set_additional_recipient_keys_with_unknown_key_should_throw_exception() throws Exception {
+                // arrange
+                var graph = new Graph(Configuration.getMainNet());
+
+                // act
+                GraphSdkException exception = assertThrows(GraphSdkException.class, () -> {
+                        graph.setAdditionalRecipientKeys(1, new long[] { 0 });
+                });
+
+                // assert
+                String expectedMessage = "Imported key not found";
+                String actualMessage = exception.getMessage();
+                assertTrue(actualMessage.contains(expectedMessage));
+                graph.finalize();
+        }
+
         @Test
         void graph_schema_id_should_return_correctly() throws Exception {
                 // act
@@ -131,6 +177,25 @@ class LibraryTest {
                 graph.finalize();
         }
 
+        @Test
+        void initiate_custom_state_should_work() throws Exception {
+                // arrange
+                var config = Configuration.registerCustom("custom-test", Environment.newBuilder().getConfigBuilder()
+                                .addDsnpVersions(DsnpVersion.Version1_0)
+                                .setMaxPageId(10)
+                                .setMaxGraphPageSizeBytes(1024)
+                                .setMaxKeyPageSizeBytes(1024)
+                                .build());
+
+                // act
+                var graph = new Graph(config);
+
+                // assert
+                assertEquals("custom-test", config.getCustomEnvironmentName());
+                assertNotEquals(0, graph.unsafeNativeHandleWithoutGuard());
+                graph.finalize();
+        }
+
         @Test
         void invalid_handle_should_throw_InvalidHandleException() throws Exception {
                 // arrange
@@ -213,6 +278,77 @@ class LibraryTest {
                 assertNotEquals(0, graph.unsafeNativeHandleWithoutGuard());
         }
 
+        @Test
+        void graph_applyActions_withProgressListener_should_report_progress_and_limit_batch_size()
+                        throws Exception {
+                // arrange
+                var ownerUserId = 1;
+                var schemaId = Configuration.getMainNet().getSchemaId(ConnectionType.FollowPublic);
+                var builder = Actions.newBuilder();
+                for (var connectionUserId = 1000; connectionUserId < 1003; connectionUserId++) {
+                        builder.addActions(Actions.Action.newBuilder().setConnectAction(
+                                        Actions.Action.ConnectAction.newBuilder()
+                                                        .setOwnerDsnpUserId(ownerUserId)
+                                                        .setConnection(Connection.newBuilder()
+                                                                        .setDsnpUserId(connectionUserId)
+                                                                        .setSchemaId(schemaId)
+                                                                        .build())
+                                                        .build()));
+                }
+                var actions = builder.build();
+                var tooLarge = builder.setOptions(ActionOptions.newBuilder().setMaxBatchSize(2)).build();
+                var graph = new Graph(Configuration.getMainNet());
+                var progress = new java.util.ArrayList<Double>();
+
+                // act
+                var exception = assertThrows(GraphSdkException.class,
+                                () -> graph.applyActions(tooLarge, progress::add));
+                graph.applyActions(actions, progress::add);
+
+                // assert
+                assertTrue(exception.getMessage().matches("^ErrorCode\\(56\\).*"));
+                assertEquals(List.of(1.0), progress);
+                assertEquals(3, graph.getConnections(ownerUserId, ConnectionType.FollowPublic, true).size());
+        }
+
+        @Test
+        void graph_applyActions_withCollectErrors_should_apply_valid_actions_and_report_failures()
+                        throws Exception {
+                // arrange
+                var ownerUserId = 1;
+                var schemaId = Configuration.getMainNet().getSchemaId(ConnectionType.FollowPublic);
+                var actions = Actions.newBuilder()
+                                .setOptions(ActionOptions.newBuilder().setCollectErrors(true).setDisableAutoCommit(true))
+                                .addActions(Actions.Action.newBuilder().setDisconnectAction(
+                                                Actions.Action.DisconnectAction.newBuilder()
+                                                                .setOwnerDsnpUserId(ownerUserId)
+                                                                .setConnection(Connection.newBuilder()
+                                                                                .setDsnpUserId(1000)
+                                                                                .setSchemaId(schemaId)
+                                                                                .build())
+                                                                .build()))
+                                .addActions(Actions.Action.newBuilder().setConnectAction(
+                                                Actions.Action.ConnectAction.newBuilder()
+                                                                .setOwnerDsnpUserId(ownerUserId)
+                                                                .setConnection(Connection.newBuilder()
+                                                                                .setDsnpUserId(1001)
+                                                                                .setSchemaId(schemaId)
+                                                                                .build())
+                                                                .build()))
+                                .build();
+                var graph = new Graph(Configuration.getMainNet());
+
+                // act
+                var exception = assertThrows(GraphSdkException.class, () -> graph.applyActions(actions));
+                var connections = graph.getConnections(ownerUserId, ConnectionType.FollowPublic, true);
+
+                // assert
+                assertTrue(exception.getMessage().matches("^ErrorCode\\(45\\).*"));
+                assertTrue(exception.getMessage().contains("1 action(s) failed"));
+                assertEquals(1, connections.size());
+                assertEquals(1001, connections.get(0).getUserId());
+        }
+
         @Test
         void graph_commit_and_rollback_should_work() throws Exception {
                 // arrange
@@ -584,19 +720,95 @@ class LibraryTest {
                 assertDoesNotThrow(() -> graph.applyActions(actions4));
         }
 
+        @Test
+        void graph_exportUpdates_should_carry_chain_head_of_imported_bundle() throws Exception {
+                // arrange
+                var ownerUserId = 20;
+                var pageId = 0;
+                var schemaId = Configuration.getMainNet().getSchemaId(ConnectionType.FollowPublic);
+                var contentHash = 1000;
+                var blockHash = ByteString.copyFrom(new byte[] { 1, 2, 3, 4 });
+                var bundles = ImportBundles.newBuilder()
+                                .addBundles(
+                                                ImportBundles.ImportBundle.newBuilder()
+                                                                .setDsnpUserId(ownerUserId)
+                                                                .setSchemaId(schemaId)
+                                                                .addPages(
+                                                                                PageData.newBuilder()
+                                                                                                .setPageId(pageId)
+                                                                                                .setContentHash(contentHash)
+                                                                                                .setContent(ByteString
+                                                                                                                .copyFrom(
+                                                                                                                                new byte[] { 20, 99,
+                                                                                                                                                -70,
+                                                                                                                                                -64,
+                                                                                                                                                -33,
+                                                                                                                                                118,
+                                                                                                                                                -13,
+                                                                                                                                                44,
+                                                                                                                                                35,
+                                                                                                                                                3,
+                                                                                                                                                0 }))
+                                                                                                .build())
+                                                                .setChainHead(ChainHead.newBuilder()
+                                                                                .setBlockNumber(42)
+                                                                                .setBlockHash(blockHash)
+                                                                                .build())
+                                                                .build())
+                                .build();
+                var actions = Actions.newBuilder().addActions(
+                                Actions.Action.newBuilder().setConnectAction(
+                                                Actions.Action.ConnectAction.newBuilder()
+                                                                .setOwnerDsnpUserId(ownerUserId)
+                                                                .setConnection(Connection.newBuilder()
+                                                                                .setDsnpUserId(1001)
+                                                                                .setSchemaId(schemaId)
+                                                                                .build())
+                                                                .build()))
+                                .build();
+                var graph = new Graph(Configuration.getMainNet());
+
+                // act
+                graph.importUserData(bundles);
+                graph.applyActions(actions);
+                var updates = graph.exportUpdates();
+
+                // assert
+                assertEquals(1, updates.size());
+                assertTrue(updates.get(0).hasPersist());
+                assertTrue(updates.get(0).getPersist().hasChainHead());
+                assertEquals(42, updates.get(0).getPersist().getChainHead().getBlockNumber());
+                assertEquals(blockHash, updates.get(0).getPersist().getChainHead().getBlockHash());
+        }
+
         @Test
         void graph_usersWithoutImportedKeys_should_work() throws Exception {
                 // arrange
                 var graph = new Graph(Configuration.getMainNet());
 
                 // act
-                var users = graph.getUsersWithoutImportedKeys();
+                var users = graph.getUsersWithoutImportedKeys(true);
 
                 // assert
                 assertEquals(0, users.size());
                 assertNotEquals(0, graph.unsafeNativeHandleWithoutGuard());
         }
 
+        @Test
+        void graph_usersWithoutImportedKeysPage_should_work() throws Exception {
+                // arrange
+                var graph = new Graph(Configuration.getMainNet());
+
+                // act
+                var firstPage = graph.getUsersWithoutImportedKeys(true, null, 10);
+                var nextPage = graph.getUsersWithoutImportedKeys(true, 1L, 0);
+
+                // assert
+                assertEquals(0, firstPage.size());
+                assertEquals(0, nextPage.size());
+                assertThrows(BaseGraphSdkException.class, () -> graph.getUsersWithoutImportedKeys(true, null, -1));
+        }
+
         @Test
         void graph_forceCalculateGraph_should_work() throws Exception {
                 // arrange
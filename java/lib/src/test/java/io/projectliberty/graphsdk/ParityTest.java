@@ -0,0 +1,238 @@
+package io.projectliberty.graphsdk;
+
+import com.google.gson.JsonElement;
+import com.google.gson.JsonObject;
+import com.google.gson.JsonParser;
+import com.google.protobuf.ByteString;
+
+import io.projectliberty.graphsdk.models.*;
+import io.projectliberty.graphsdk.models.Actions.Action;
+import io.projectliberty.graphsdk.models.Actions.ActionOptions;
+import io.projectliberty.graphsdk.models.ImportBundles.ImportBundle;
+import io.projectliberty.graphsdk.models.ImportBundles.ImportBundle.GraphKeyPair;
+import io.projectliberty.graphsdk.models.Updates.Update;
+import org.junit.jupiter.api.DynamicTest;
+import org.junit.jupiter.api.TestFactory;
+
+import java.io.IOException;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.Paths;
+import java.util.ArrayList;
+import java.util.Base64;
+import java.util.Collections;
+import java.util.List;
+import java.util.stream.Collectors;
+import java.util.stream.Stream;
+
+import static org.junit.jupiter.api.Assertions.*;
+import static org.junit.jupiter.api.Assumptions.assumeFalse;
+
+/**
+ * Runs the fixtures shared with the core and the other bridges, see `bridge/parity`, and asserts
+ * that the JNI bridge exports the same updates as the core.
+ */
+class ParityTest {
+        private static final Path FIXTURES_DIR = Paths.get("..", "..", "bridge", "parity", "fixtures");
+
+        @TestFactory
+        Stream<DynamicTest> fixtures_should_export_the_same_updates_as_the_core() throws IOException {
+                List<Path> fixtures;
+                try (Stream<Path> files = Files.list(FIXTURES_DIR)) {
+                        fixtures = files.filter(file -> file.toString().endsWith(".json")).sorted()
+                                        .collect(Collectors.toList());
+                }
+                assertFalse(fixtures.isEmpty());
+
+                return fixtures.stream().map(file -> DynamicTest.dynamicTest(file.getFileName().toString(),
+                                () -> runFixture(JsonParser.parseString(Files.readString(file)).getAsJsonObject())));
+        }
+
+        private static void runFixture(JsonObject fixture) throws Exception {
+                // export options can not be set through the JNI bridge yet, so fixtures relying on them are
+                // only covered by the core and FFI runners
+                assumeFalse(fixture.has("exportOptions"), "export options are not supported by the JNI bridge");
+
+                // arrange
+                var bundles = ImportBundles.newBuilder();
+                for (JsonElement bundle : fixture.getAsJsonArray("imports")) {
+                        bundles.addBundles(importBundleFromFixture(bundle.getAsJsonObject()));
+                }
+                var actions = Actions.newBuilder();
+                for (JsonElement action : fixture.getAsJsonArray("actions")) {
+                        actions.addActions(actionFromFixture(action.getAsJsonObject()));
+                }
+                if (fixture.has("actionOptions")) {
+                        actions.setOptions(actionOptionsFromFixture(fixture.getAsJsonObject("actionOptions")));
+                }
+                var graph = new Graph(Configuration.getMainNet());
+
+                // act
+                graph.importUserData(bundles.build());
+                graph.applyActions(actions.build());
+                var updates = graph.exportUpdates();
+
+                // assert
+                var expected = new ArrayList<String>();
+                for (JsonElement update : fixture.getAsJsonArray("expectedUpdates")) {
+                        expected.add(expectedUpdateToString(update.getAsJsonObject()));
+                }
+                var actual = updates.stream().map(ParityTest::updateToString).collect(Collectors.toList());
+                Collections.sort(expected);
+                Collections.sort(actual);
+                assertEquals(expected, actual);
+        }
+
+        private static ByteString bytes(JsonElement encoded) {
+                return ByteString.copyFrom(Base64.getDecoder().decode(encoded.getAsString()));
+        }
+
+        private static DsnpKeys dsnpKeysFromFixture(JsonObject dsnpKeys) {
+                var builder = DsnpKeys.newBuilder()
+                                .setDsnpUserId(dsnpKeys.get("dsnpUserId").getAsLong())
+                                .setKeysHash((int) dsnpKeys.get("keysHash").getAsLong());
+                for (JsonElement key : dsnpKeys.getAsJsonArray("keys")) {
+                        var keyData = key.getAsJsonObject();
+                        builder.addKeys(KeyData.newBuilder().setIndex(keyData.get("index").getAsInt())
+                                        .setContent(bytes(keyData.get("content"))));
+                }
+                return builder.build();
+        }
+
+        private static Connection connectionFromFixture(JsonObject connection) {
+                return Connection.newBuilder().setDsnpUserId(connection.get("dsnpUserId").getAsLong())
+                                .setSchemaId(connection.get("schemaId").getAsInt()).build();
+        }
+
+        private static ImportBundle importBundleFromFixture(JsonObject bundle) {
+                var builder = ImportBundle.newBuilder()
+                                .setDsnpUserId(bundle.get("dsnpUserId").getAsLong())
+                                .setSchemaId(bundle.get("schemaId").getAsInt());
+                for (JsonElement pair : bundle.getAsJsonArray("keyPairs")) {
+                        var keyPair = pair.getAsJsonObject();
+                        builder.addKeyPairs(GraphKeyPair.newBuilder()
+                                        .setKeyType(GraphKeyType.valueOf(keyPair.get("keyType").getAsString()))
+                                        .setPublicKey(bytes(keyPair.get("publicKey")))
+                                        .setSecretKey(bytes(keyPair.get("secretKey"))));
+                }
+                if (bundle.has("dsnpKeys") && !bundle.get("dsnpKeys").isJsonNull()) {
+                        builder.setDsnpKeys(dsnpKeysFromFixture(bundle.getAsJsonObject("dsnpKeys")));
+                }
+                for (JsonElement page : bundle.getAsJsonArray("pages")) {
+                        var pageData = page.getAsJsonObject();
+                        builder.addPages(PageData.newBuilder().setPageId(pageData.get("pageId").getAsInt())
+                                        .setContent(bytes(pageData.get("content")))
+                                        .setContentHash((int) pageData.get("contentHash").getAsLong()));
+                }
+                if (bundle.has("chainHead") && !bundle.get("chainHead").isJsonNull()) {
+                        var chainHead = bundle.getAsJsonObject("chainHead");
+                        builder.setChainHead(ChainHead.newBuilder()
+                                        .setBlockNumber((int) chainHead.get("blockNumber").getAsLong())
+                                        .setBlockHash(bytes(chainHead.get("blockHash"))));
+                }
+                return builder.build();
+        }
+
+        private static Action actionFromFixture(JsonObject action) {
+                var type = action.keySet().iterator().next();
+                var fields = action.getAsJsonObject(type);
+                switch (type) {
+                        case "Connect":
+                                if (fields.has("preferredPageId") && !fields.get("preferredPageId").isJsonNull()) {
+                                        fail("preferredPageId is not supported by the JNI bridge");
+                                }
+                                var connect = Action.ConnectAction.newBuilder()
+                                                .setOwnerDsnpUserId(fields.get("ownerDsnpUserId").getAsLong())
+                                                .setConnection(connectionFromFixture(fields.getAsJsonObject("connection")));
+                                if (fields.has("dsnpKeys") && !fields.get("dsnpKeys").isJsonNull()) {
+                                        connect.setDsnpKeys(dsnpKeysFromFixture(fields.getAsJsonObject("dsnpKeys")));
+                                }
+                                return Action.newBuilder().setConnectAction(connect).build();
+
+                        case "Disconnect":
+                                return Action.newBuilder().setDisconnectAction(Action.DisconnectAction.newBuilder()
+                                                .setOwnerDsnpUserId(fields.get("ownerDsnpUserId").getAsLong())
+                                                .setConnection(connectionFromFixture(fields.getAsJsonObject("connection"))))
+                                                .build();
+
+                        case "AddGraphKey":
+                                return Action.newBuilder().setAddKeyAction(Action.AddGraphKey.newBuilder()
+                                                .setOwnerDsnpUserId(fields.get("ownerDsnpUserId").getAsLong())
+                                                .setNewPublicKey(bytes(fields.get("newPublicKey"))))
+                                                .build();
+
+                        default:
+                                return fail(type + " actions are not supported by the JNI bridge");
+                }
+        }
+
+        private static ActionOptions actionOptionsFromFixture(JsonObject options) {
+                if (options.has("requireCounterpartKeys") && options.get("requireCounterpartKeys").getAsBoolean()) {
+                        fail("requireCounterpartKeys is not supported by the JNI bridge");
+                }
+                var builder = ActionOptions.newBuilder()
+                                .setIgnoreExistingConnections(options.get("ignoreExistingConnections").getAsBoolean())
+                                .setIgnoreMissingConnections(options.get("ignoreMissingConnections").getAsBoolean())
+                                .setDisableAutoCommit(options.get("disableAutoCommit").getAsBoolean())
+                                .setCollectErrors(options.has("collectErrors") && options.get("collectErrors").getAsBoolean());
+                if (options.has("timestampPolicy")) {
+                        builder.setTimestampPolicy(TimestampPolicy.valueOf(options.get("timestampPolicy").getAsString()));
+                }
+                return builder.build();
+        }
+
+        private static String chainHeadToString(long blockNumber, ByteString blockHash) {
+                return String.format("%d:%s", blockNumber, Base64.getEncoder().encodeToString(blockHash.toByteArray()));
+        }
+
+        private static String chainHeadToString(boolean hasChainHead, ChainHead chainHead) {
+                return hasChainHead
+                                ? chainHeadToString(Integer.toUnsignedLong(chainHead.getBlockNumber()), chainHead.getBlockHash())
+                                : "none";
+        }
+
+        private static String updateToString(String type, long owner, int schemaId, int pageId, long prevHash,
+                        ByteString payload, String chainHead) {
+                return String.format("%s owner=%d schema=%d page=%d prev=%d payload=%s chain_head=%s", type, owner,
+                                schemaId, pageId, prevHash, Base64.getEncoder().encodeToString(payload.toByteArray()),
+                                chainHead);
+        }
+
+        private static String updateToString(Update update) {
+                switch (update.getInnerCase()) {
+                        case PERSIST:
+                                var persist = update.getPersist();
+                                return updateToString("PersistPage", persist.getOwnerDsnpUserId(), persist.getSchemaId(),
+                                                persist.getPageId(), Integer.toUnsignedLong(persist.getPrevHash()),
+                                                persist.getPayload(),
+                                                chainHeadToString(persist.hasChainHead(), persist.getChainHead()));
+
+                        case DELETE:
+                                var delete = update.getDelete();
+                                return updateToString("DeletePage", delete.getOwnerDsnpUserId(), delete.getSchemaId(),
+                                                delete.getPageId(), Integer.toUnsignedLong(delete.getPrevHash()), ByteString.EMPTY,
+                                                chainHeadToString(delete.hasChainHead(), delete.getChainHead()));
+
+                        case ADD_KEY:
+                                var addKey = update.getAddKey();
+                                return updateToString("AddKey", addKey.getOwnerDsnpUserId(), 0, 0,
+                                                Integer.toUnsignedLong(addKey.getPrevHash()), addKey.getPayload(),
+                                                chainHeadToString(addKey.hasChainHead(), addKey.getChainHead()));
+
+                        default:
+                                return fail("unexpected update " + update);
+                }
+        }
+
+        private static String expectedUpdateToString(JsonObject update) {
+                return updateToString(update.get("type").getAsString(), update.get("ownerDsnpUserId").getAsLong(),
+                                update.has("schemaId") ? update.get("schemaId").getAsInt() : 0,
+                                update.has("pageId") ? update.get("pageId").getAsInt() : 0,
+                                update.get("prevHash").getAsLong(),
+                                update.has("payload") ? bytes(update.get("payload")) : ByteString.EMPTY,
+                                update.has("chainHead")
+                                                ? chainHeadToString(update.getAsJsonObject("chainHead").get("blockNumber").getAsLong(),
+                                                                bytes(update.getAsJsonObject("chainHead").get("blockHash")))
+                                                : "none");
+        }
+}
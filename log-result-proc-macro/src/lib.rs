@@ -7,7 +7,8 @@ use syn::parse_macro_input;
 #[proc_macro_attribute]
 /// Attribute macro to enable logging a Result::Err variant if tagged
 /// to a function that returns a Result enum, and it returns an Err.
-/// Note: Returned E of Result<T, E> must implment the Display trait.
+/// Note: The error is formatted using `crate::util::log_redaction::redact_error`, so the crate
+/// must provide it for the returned E of Result<T, E> to redact sensitive data from logs.
 /// Also, crate must have the log crate as a dependency.
 pub fn log_result_err(attr: TokenStream, item: TokenStream) -> TokenStream {
 	let log_level = parse_macro_input!(attr as syn::Expr);
@@ -33,7 +34,12 @@ fn generate_log_result_fn(input: &syn::ItemFn, log_level: &syn::Expr) -> proc_ma
 		#vis #sig {
 			(|| -> Result<_, _> #block)()
 				.map_err(|err| {
-					log::log!(#log_level, "{}: {}", #context, &err);
+					log::log!(
+						#log_level,
+						"{}: {}",
+						#context,
+						crate::util::log_redaction::redact_error(&err)
+					);
 					err
 				})
 		}
@@ -1,5 +1,11 @@
 pub const STATE_FILE: &str = "20000_simulation_state.bin";
 
+/// hex encoded 32 byte key, when set the state file is encrypted at rest
+pub const STATE_KEY_ENV: &str = "SIMULATOR_STATE_KEY";
+
+/// seed of the run, when not set a random seed is used and printed so the run can be repeated
+pub const SEED_ENV: &str = "SIMULATOR_SEED";
+
 pub const USERS: usize = 20_000;
 
 pub const PRIVATE_FOLLOW_CONNECTIONS: usize = 7000;
@@ -11,3 +17,11 @@ pub const MAX_DSNP_USER_ID: u32 = 16_000_000;
 pub const KEY_ROTATIONS: usize = 1000;
 
 pub const PRIVATE_FRIENDSHIP_PAGE_MODIFICATIONS: usize = 100;
+
+pub const CHURN_ROUNDS: usize = 10;
+
+pub const CHURN_USERS_PER_ROUND: usize = 500;
+
+pub const MASS_UNFOLLOWED_USERS: usize = 5;
+
+pub const STALE_FRIENDSHIPS: usize = 100;
@@ -1,22 +1,99 @@
-use crate::{constants, types::*};
-use dryoc::keypair::StackKeyPair;
-use dsnp_graph_config::{DsnpUserId, Environment, GraphKeyType, SchemaId};
+use crate::{constants, rng::SimulationRng, types::*};
+use dsnp_graph_config::{ConnectionType, DsnpUserId, Environment, PrivacyType, SchemaId};
 use dsnp_graph_core::api::{
 	api::{GraphAPI, GraphState},
-	api_types::{
-		Action, Connection, DsnpKeys, GraphKeyPair, ImportBundle, KeyData, PageData, Update,
-	},
+	api_types::{Action, Connection, DsnpKeys, ImportBundle, KeyData, PageData, Update},
 };
-use rand::{prelude::SliceRandom, thread_rng, Rng};
+use rand::{prelude::SliceRandom, Rng};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::{
-	collections::{HashMap, HashSet},
+	collections::{BTreeSet, HashMap},
 	vec,
 };
 
-pub fn choose_users(n: usize) -> HashSet<DsnpUserId> {
-	let mut rng = rand::thread_rng();
-	let mut users = HashSet::new();
+/// builds the initial social graph and chain state, persisting the state after each step so an
+/// interrupted initialization can continue from the last completed step
+pub fn init_state_machine(
+	state: &mut GlobalState,
+	env: Environment,
+	config: &SimulationConfig,
+	rng: &SimulationRng,
+	persist: impl Fn(&GlobalState),
+) {
+	while state.current_status.clone() != Status::End {
+		let current_status = state.current_status.clone();
+		println!("current status {:?}", &current_status);
+		match current_status {
+			Status::Init => {
+				// 1. choose all users
+				state.users = choose_users(config.users, &mut rng.derive("users").rng())
+					.into_iter()
+					.collect();
+				state.current_status = Status::InitialUsersCreated;
+				persist(state);
+			},
+			Status::InitialUsersCreated => {
+				// 2. setup initial key for all users
+				setup_initial_key(env.clone(), state, &rng.derive("initial-keys"));
+				state.current_status = Status::InitialKeysCreated;
+				persist(state);
+			},
+			Status::InitialKeysCreated => {
+				// 3. setup initial private follows
+				let private_follow_schema_id = env
+					.get_config()
+					.get_schema_id_from_connection_type(ConnectionType::Follow(
+						PrivacyType::Private,
+					))
+					.unwrap();
+
+				setup_initial_private_follows(
+					env.clone(),
+					config.private_follow_connections,
+					private_follow_schema_id,
+					state,
+					&rng.derive("initial-private-follows"),
+				);
+
+				state.current_status = Status::PrivateFollowsCreated;
+				persist(state);
+				println!("All private follows are created!");
+			},
+			Status::PrivateFollowsCreated => {
+				// 4. setup initial private friendships
+				let private_friendship_schema_id = env
+					.get_config()
+					.get_schema_id_from_connection_type(ConnectionType::Friendship(
+						PrivacyType::Private,
+					))
+					.unwrap();
+
+				setup_initial_private_friendships(
+					env.clone(),
+					config.private_friendship_connections,
+					private_friendship_schema_id,
+					state,
+					&rng.derive("initial-private-friendships"),
+				);
+
+				state.current_status = Status::PrivateFriendshipsCreated;
+				persist(state);
+				println!("All private friendships are created!");
+			},
+			Status::PrivateFriendshipsCreated => {
+				state.current_status = Status::End;
+			},
+			Status::End => {
+				println!("All connections are created!");
+			},
+		}
+	}
+
+	println!("Initializing is done!");
+}
+
+pub fn choose_users(n: usize, rng: &mut impl Rng) -> BTreeSet<DsnpUserId> {
+	let mut users = BTreeSet::new();
 	while users.len() < n {
 		let user_id: DsnpUserId = rng.gen_range(1u32..constants::MAX_DSNP_USER_ID).into();
 		users.insert(user_id);
@@ -24,29 +101,30 @@ pub fn choose_users(n: usize) -> HashSet<DsnpUserId> {
 	users
 }
 
-pub fn choose_n_users_from(n: usize, users: &Vec<DsnpUserId>) -> HashSet<DsnpUserId> {
-	let mut rng = rand::thread_rng();
-	users.choose_multiple(&mut rng, n).cloned().collect()
+pub fn choose_n_users_from(
+	n: usize,
+	users: &Vec<DsnpUserId>,
+	rng: &mut impl Rng,
+) -> BTreeSet<DsnpUserId> {
+	users.choose_multiple(rng, n).cloned().collect()
 }
 
-pub fn choose_up_to_n_users_from(n: usize, users: &Vec<DsnpUserId>) -> HashSet<DsnpUserId> {
-	let mut rng = rand::thread_rng();
+pub fn choose_up_to_n_users_from(
+	n: usize,
+	users: &Vec<DsnpUserId>,
+	rng: &mut impl Rng,
+) -> BTreeSet<DsnpUserId> {
 	let exact_n: usize = rng.gen_range(0..=n).into();
-	users.choose_multiple(&mut rng, exact_n).cloned().collect()
+	users.choose_multiple(rng, exact_n).cloned().collect()
 }
 
-pub fn setup_initial_key(env: Environment, state: &mut GlobalState) {
+pub fn setup_initial_key(env: Environment, state: &mut GlobalState, rng: &SimulationRng) {
 	let public_key_schema_id = env.get_config().graph_public_key_schema_id;
 	let keys: Vec<_> = state
 		.users
 		.par_iter()
 		.map(|user_id| {
-			let key_pair_raw = StackKeyPair::gen();
-			let graph_key_pair = GraphKeyPair {
-				secret_key: key_pair_raw.secret_key.to_vec(),
-				public_key: key_pair_raw.public_key.to_vec(),
-				key_type: GraphKeyType::X25519,
-			};
+			let graph_key_pair = rng.graph_key_pair(*user_id);
 
 			let mut graph = GraphState::new(env.clone());
 			graph
@@ -61,7 +139,7 @@ pub fn setup_initial_key(env: Environment, state: &mut GlobalState) {
 
 			let mut dsnp_key = None;
 			for a in graph.export_updates().expect("error exporting updates") {
-				if let Update::AddKey { owner_dsnp_user_id, prev_hash, payload } = a {
+				if let Update::AddKey { owner_dsnp_user_id, prev_hash, payload, .. } = a {
 					assert_eq!(dsnp_key, None);
 					dsnp_key = Some(DsnpKeys {
 						dsnp_user_id: owner_dsnp_user_id,
@@ -88,6 +166,7 @@ pub fn setup_initial_private_follows(
 	max_connections: usize,
 	private_follows_schema_id: SchemaId,
 	state: &mut GlobalState,
+	rng: &SimulationRng,
 ) {
 	let public_key_schema_id = env.get_config().graph_public_key_schema_id;
 
@@ -95,7 +174,7 @@ pub fn setup_initial_private_follows(
 		.users
 		.par_iter()
 		.map(|user_id| {
-			let mut rng = thread_rng();
+			let mut rng = rng.for_user(*user_id);
 			// random connection size
 			let connection_size: usize = rng.gen_range(0..=max_connections).into();
 
@@ -121,6 +200,7 @@ pub fn setup_initial_private_follows(
 					pages: vec![],
 					dsnp_keys: Some(user_dsnp_keys.clone()),
 					key_pairs: user_key_pairs.clone(),
+					chain_head: None,
 				}])
 				.expect("Should import");
 
@@ -134,6 +214,8 @@ pub fn setup_initial_private_follows(
 						dsnp_user_id: *c,
 					},
 					dsnp_keys: None,
+					preferred_page_id: None,
+					since: None,
 				})
 				.collect();
 			// apply actions to state
@@ -185,11 +267,17 @@ pub fn setup_initial_private_friendships(
 	max_connections: usize,
 	private_friendship_schema_id: SchemaId,
 	state: &mut GlobalState,
+	rng: &SimulationRng,
 ) {
+	let mut rng = rng.rng();
 	// select friendship users
 	let mut temp_graph: HashMap<DsnpUserId, Vec<DsnpUserId>> = HashMap::new();
-	let selected_users: Vec<_> =
-		choose_n_users_from(state.users.len() / 2, &state.users).into_iter().collect();
+	let mut selected_users: Vec<_> =
+		choose_n_users_from(state.users.len() / 2, &state.users, &mut rng)
+			.into_iter()
+			.collect();
+	// choices are made in a random order of the users
+	selected_users.shuffle(&mut rng);
 	for u in selected_users.iter() {
 		temp_graph.insert(*u, vec![]);
 	}
@@ -202,6 +290,7 @@ pub fn setup_initial_private_friendships(
 		let mut new_friends: Vec<_> = choose_up_to_n_users_from(
 			max_connections - current_friends.len(),
 			&selected_users[(i + 1)..].to_vec(),
+			&mut rng,
 		)
 		.into_iter()
 		.collect();
@@ -233,6 +322,7 @@ pub fn setup_initial_private_friendships(
 						pages: vec![],
 						dsnp_keys: Some(friend_dsnp_keys.clone()),
 						key_pairs: friend_key_pairs.clone(),
+						chain_head: None,
 					}
 				})
 				.collect();
@@ -242,6 +332,7 @@ pub fn setup_initial_private_friendships(
 				pages,
 				dsnp_keys: Some(user_dsnp_keys.clone()),
 				key_pairs: user_key_pairs.clone(),
+				chain_head: None,
 			});
 			// import user data
 			let mut graph = GraphState::new(env.clone());
@@ -258,6 +349,8 @@ pub fn setup_initial_private_friendships(
 						dsnp_user_id: *c,
 					},
 					dsnp_keys: None,
+					preferred_page_id: None,
+					since: None,
 				})
 				.collect();
 			// apply actions to state
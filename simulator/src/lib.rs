@@ -0,0 +1,23 @@
+//! Scenario engine of the graph simulator
+//!
+//! Builds a simulated social graph and chain state for a set of users and runs scenarios against
+//! it using the latest SDK implementation, checking after each scenario that the graphs stored on
+//! the simulated chain match the expected social graph. Each scenario returns a `ScenarioReport`,
+//! or a description of the mismatch or graph error it failed with, and leaves printing to the
+//! caller.
+//!
+//! The sizes of the simulated graph are set using `SimulationConfig` and all random choices are
+//! derived from the seed of a `SimulationRng`, so the same run can be repeated in CI at a small
+//! scale or by downstream teams to reproduce a failure.
+//!
+//! # Scenarios
+//! - `key_rotation` rotates the graph keys of users and modifies their private graphs
+//! - `churn` runs rounds of random connects and disconnects on private follow graphs
+//! - `unfollow` makes all followers of a few users unfollow them in a single wave
+//! - `page_overflow` adds connections to a graph until they no longer fit in the allowed pages
+//! - `stale_friendship` prunes private friendships that were removed by the other side
+pub mod constants;
+pub mod init;
+pub mod rng;
+pub mod scenarios;
+pub mod types;
@@ -1,105 +1,87 @@
-use dsnp_graph_config::{ConnectionType, Environment, PrivacyType};
-use scenarios::key_rotation::execute_key_rotation_private_friendship;
+use dsnp_graph_config::Environment;
+use dsnp_graph_core::util::encrypted_store::EncryptedStateStore;
+use simulator::{
+	constants,
+	init::init_state_machine,
+	rng::SimulationRng,
+	scenarios::{
+		churn::execute_connection_churn,
+		key_rotation::{
+			execute_key_rotation_private_follow, execute_key_rotation_private_friendship,
+		},
+		page_overflow::execute_page_overflow,
+		stale_friendship::execute_stale_friendship_pruning,
+		unfollow::execute_mass_unfollow,
+	},
+	types::*,
+};
 use std::{
 	fs::File,
 	io::{Read, Write},
 };
-mod constants;
-mod init;
-mod scenarios;
-mod types;
-
-use crate::{scenarios::key_rotation::execute_key_rotation_private_follow, types::*};
 
 fn main() {
 	let env = Environment::Mainnet;
+	let config = SimulationConfig::default();
+	let rng = match std::env::var(constants::SEED_ENV) {
+		Ok(seed) => SimulationRng::new(seed.parse().expect("Seed should be a number")),
+		Err(_) => SimulationRng::from_entropy(),
+	};
+	println!("running simulation with seed {}", rng.seed());
 	let mut state: GlobalState = match File::open(constants::STATE_FILE) {
 		Ok(mut file) => {
 			let mut buffer = Vec::<u8>::new();
 			file.read_to_end(&mut buffer).expect("Read to end should work");
+			if let Some(store) = state_store() {
+				buffer = store.decrypt(&buffer).expect("Should decrypt state");
+			}
 			bincode::deserialize(&buffer).unwrap_or_default()
 		},
 		Err(_) => GlobalState::default(),
 	};
 
-	init_state_machine(&mut state, env.clone());
+	init_state_machine(&mut state, env.clone(), &config, &rng, persist_state);
 
-	execute_key_rotation_private_follow(&mut state, env.clone());
+	report(execute_key_rotation_private_follow(&mut state, env.clone(), &config, &rng));
 
-	execute_key_rotation_private_friendship(&mut state, env);
-}
+	report(execute_key_rotation_private_friendship(&mut state, env.clone(), &config, &rng));
 
-fn init_state_machine(state: &mut GlobalState, env: Environment) {
-	while state.current_status.clone() != Status::End {
-		let current_status = state.current_status.clone();
-		println!("current status {:?}", &current_status);
-		match current_status {
-			Status::Init => {
-				// 1. choose all users
-				state.users = init::choose_users(constants::USERS).into_iter().collect();
-				state.current_status = Status::InitialUsersCreated;
-				persist_state(state);
-			},
-			Status::InitialUsersCreated => {
-				// 2. setup initial key for all users
-				init::setup_initial_key(env.clone(), state);
-				state.current_status = Status::InitialKeysCreated;
-				persist_state(state);
-			},
-			Status::InitialKeysCreated => {
-				// 3. setup initial private follows
-				let private_follow_schema_id = env
-					.get_config()
-					.get_schema_id_from_connection_type(ConnectionType::Follow(
-						PrivacyType::Private,
-					))
-					.unwrap();
+	report(execute_connection_churn(&mut state, env.clone(), &config, &rng));
 
-				init::setup_initial_private_follows(
-					env.clone(),
-					constants::PRIVATE_FOLLOW_CONNECTIONS,
-					private_follow_schema_id,
-					state,
-				);
+	report(execute_mass_unfollow(&mut state, env.clone(), &config, &rng));
 
-				state.current_status = Status::PrivateFollowsCreated;
-				persist_state(state);
-				println!("All private follows are created!");
-			},
-			Status::PrivateFollowsCreated => {
-				// 4. setup initial private friendships
-				let private_friendship_schema_id = env
-					.get_config()
-					.get_schema_id_from_connection_type(ConnectionType::Friendship(
-						PrivacyType::Private,
-					))
-					.unwrap();
+	report(execute_stale_friendship_pruning(&mut state, env.clone(), &config, &rng));
 
-				init::setup_initial_private_friendships(
-					env.clone(),
-					constants::PRIVATE_FRIENDSHIP_CONNECTIONS,
-					private_friendship_schema_id,
-					state,
-				);
+	report(execute_page_overflow(&state, env, &rng));
+}
 
-				state.current_status = Status::PrivateFriendshipsCreated;
-				persist_state(state);
-				println!("All private friendships are created!");
-			},
-			Status::PrivateFriendshipsCreated => {
-				state.current_status = Status::End;
-			},
-			Status::End => {
-				println!("All connections are created!");
-			},
-		}
+/// prints the report of a passed scenario, or stops the simulation on the first failed one
+fn report(result: ScenarioResult) {
+	match result {
+		Ok(report) => println!("Success: {}", report),
+		Err(e) => {
+			eprintln!("Failure: {}", e);
+			std::process::exit(1)
+		},
 	}
-
-	println!("Initializing is done!");
 }
 
 fn persist_state(state: &GlobalState) {
-	let encoded: Vec<u8> = bincode::serialize(&state).unwrap();
+	let mut encoded: Vec<u8> = bincode::serialize(&state).unwrap();
+	if let Some(store) = state_store() {
+		encoded = store.encrypt(&encoded).expect("Should encrypt state");
+	}
 	let mut file = File::create(constants::STATE_FILE).expect("Should open file");
 	file.write_all(&encoded[..]).expect("Should write data");
 }
+
+/// returns the store used to encrypt the state file if an encryption key is provided
+fn state_store() -> Option<EncryptedStateStore> {
+	let hex_key = std::env::var(constants::STATE_KEY_ENV).ok()?;
+	let key: Vec<u8> = (0..hex_key.len())
+		.step_by(2)
+		.map(|i| hex_key.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+		.collect::<Option<_>>()
+		.expect("State key should be hex encoded");
+	Some(EncryptedStateStore::new(&key).expect("State key should be valid"))
+}
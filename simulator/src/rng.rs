@@ -0,0 +1,70 @@
+use dryoc::keypair::StackKeyPair;
+use dsnp_graph_config::{DsnpUserId, GraphKeyType};
+use dsnp_graph_core::api::api_types::GraphKeyPair;
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Source of the randomness used by the scenarios
+///
+/// Each step of a scenario derives its own generators from the seed, so a run can be reproduced by
+/// using the same seed, and the generator of each user does not depend on the order in which the
+/// users are processed in parallel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationRng {
+	seed: u64,
+}
+
+impl SimulationRng {
+	/// creates a new instance using the provided seed
+	pub fn new(seed: u64) -> Self {
+		Self { seed }
+	}
+
+	/// creates a new instance using a random seed, which can be read using `seed` to reproduce
+	/// the run later
+	pub fn from_entropy() -> Self {
+		Self::new(thread_rng().gen())
+	}
+
+	/// returns the seed of this instance
+	pub fn seed(&self) -> u64 {
+		self.seed
+	}
+
+	/// returns an instance for a named step, which is independent of the other steps
+	pub fn derive(&self, step: &str) -> Self {
+		Self::new(self.mix(step.as_bytes()))
+	}
+
+	/// returns the generator of this step
+	pub fn rng(&self) -> StdRng {
+		StdRng::seed_from_u64(self.seed)
+	}
+
+	/// returns the generator of this step for a user
+	pub fn for_user(&self, user_id: DsnpUserId) -> StdRng {
+		StdRng::seed_from_u64(self.mix(&user_id.to_le_bytes()))
+	}
+
+	/// generates the graph key pair of a user in this step
+	pub fn graph_key_pair(&self, user_id: DsnpUserId) -> GraphKeyPair {
+		let seed: [u8; 32] = self.derive("graph-key-pair").for_user(user_id).gen();
+		let key_pair_raw = StackKeyPair::from_seed(&seed);
+		GraphKeyPair {
+			secret_key: key_pair_raw.secret_key.to_vec(),
+			public_key: key_pair_raw.public_key.to_vec(),
+			key_type: GraphKeyType::X25519,
+		}
+	}
+
+	/// FNV-1a is used instead of the std hashers since their output may change between releases
+	fn mix(&self, bytes: &[u8]) -> u64 {
+		self.seed
+			.to_le_bytes()
+			.iter()
+			.chain(bytes)
+			.fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ *byte as u64).wrapping_mul(FNV_PRIME))
+	}
+}
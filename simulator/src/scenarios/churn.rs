@@ -0,0 +1,53 @@
+use crate::{
+	rng::SimulationRng,
+	scenarios::common::{compare_on_chain_with_expected, modify_random_pages, schema_id_for},
+	types::{GlobalState, ScenarioReport, ScenarioResult, SimulationConfig},
+};
+use dsnp_graph_config::Environment;
+use dsnp_graph_core::api::api_types::*;
+use rand::prelude::SliceRandom;
+
+/// runs rounds of random connects and disconnects on the private follow graphs of a different set
+/// of users in each round, comparing their graphs with the expected ones after each round
+pub fn execute_connection_churn(
+	state: &mut GlobalState,
+	env: Environment,
+	config: &SimulationConfig,
+	rng: &SimulationRng,
+) -> ScenarioResult {
+	let mut report = ScenarioReport::new("connection churn");
+	let rng = rng.derive("connection-churn");
+	let private_follow_schema_id =
+		schema_id_for(&env, ConnectionType::Follow(PrivacyType::Private))?;
+
+	for round in 0..config.churn_rounds {
+		let round_rng = rng.derive(&round.to_string());
+		let selected_users: Vec<_> = state
+			.users
+			.choose_multiple(&mut round_rng.rng(), config.churn_users_per_round)
+			.copied()
+			.collect();
+
+		modify_random_pages(
+			env.clone(),
+			state,
+			&selected_users,
+			private_follow_schema_id,
+			config.private_follow_connections,
+			false,
+			&round_rng,
+		)?;
+
+		compare_on_chain_with_expected(
+			env.clone(),
+			state,
+			Some(&selected_users),
+			private_follow_schema_id,
+		)
+		.map_err(|e| format!("churn round {}: {}", round, e))?;
+		report.updated_graphs += selected_users.len();
+	}
+
+	compare_on_chain_with_expected(env, state, None, private_follow_schema_id)?;
+	Ok(report)
+}
@@ -1,31 +1,47 @@
 use std::collections::BTreeSet;
 
-use dryoc::keypair::StackKeyPair;
-use dsnp_graph_config::{DsnpUserId, Environment, GraphKeyType, SchemaId};
+use dsnp_graph_config::{
+	errors::{DsnpGraphError, DsnpGraphResult},
+	ConnectionType, DsnpUserId, Environment, SchemaId,
+};
 use dsnp_graph_core::api::{
 	api::{GraphAPI, GraphState},
-	api_types::{Action, Connection, GraphKeyPair, ImportBundle},
+	api_types::{Action, Connection, ImportBundle, Update},
 };
-use rand::{prelude::SliceRandom, thread_rng, Rng};
+use rand::{prelude::SliceRandom, Rng};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-use crate::GlobalState;
+use crate::{rng::SimulationRng, types::GlobalState};
+
+/// describes a failed graph operation of a user
+pub fn graph_error(
+	operation: &str,
+	user_id: DsnpUserId,
+) -> impl FnOnce(DsnpGraphError) -> String + '_ {
+	move |e| format!("{} failed for user {}: {}", operation, user_id, e)
+}
+
+/// returns the schema id of the connection type in the config of the environment
+pub fn schema_id_for(
+	env: &Environment,
+	connection_type: ConnectionType,
+) -> Result<SchemaId, String> {
+	env.get_config()
+		.get_schema_id_from_connection_type(connection_type)
+		.ok_or_else(|| format!("{:?} should have a schema in the config", connection_type))
+}
 
 pub fn add_keys_for_users(
 	env: Environment,
 	state: &mut GlobalState,
 	selected_users: &Vec<DsnpUserId>,
 	schema_id: SchemaId,
-) {
-	let keys_updates: Vec<_> = selected_users
+	rng: &SimulationRng,
+) -> Result<(), String> {
+	let keys_updates = selected_users
 		.par_iter()
 		.map(|user_id| {
-			let key_pair_raw = StackKeyPair::gen();
-			let graph_key_pair = GraphKeyPair {
-				secret_key: key_pair_raw.secret_key.to_vec(),
-				public_key: key_pair_raw.public_key.to_vec(),
-				key_type: GraphKeyType::X25519,
-			};
+			let graph_key_pair = rng.graph_key_pair(*user_id);
 
 			let (existing_keys, key_pairs, pages, _) =
 				state.get_all_data_for_user(env.clone(), *user_id, schema_id);
@@ -37,8 +53,9 @@ pub fn add_keys_for_users(
 					schema_id,
 					key_pairs,
 					pages,
+					chain_head: None,
 				}])
-				.expect("Should import data");
+				.map_err(graph_error("import", *user_id))?;
 			graph
 				.apply_actions(
 					&vec![Action::AddGraphKey {
@@ -47,14 +64,12 @@ pub fn add_keys_for_users(
 					}],
 					&None,
 				)
-				.expect("error adding graph key");
-
-			let updates = graph.export_updates().expect("error exporting updates");
+				.map_err(graph_error("adding graph key", *user_id))?;
 
-			println!("importing keys for user {}", user_id);
-			(*user_id, updates, graph_key_pair)
+			let updates = graph.export_updates().map_err(graph_error("export", *user_id))?;
+			Ok((*user_id, updates, graph_key_pair))
 		})
-		.collect();
+		.collect::<Result<Vec<_>, String>>()?;
 
 	for (user_id, updates, graph_key_pair) in keys_updates {
 		state.apply_updates_for_user(
@@ -67,6 +82,7 @@ pub fn add_keys_for_users(
 			Some(&graph_key_pair),
 		);
 	}
+	Ok(())
 }
 
 pub fn modify_random_pages(
@@ -76,9 +92,10 @@ pub fn modify_random_pages(
 	schema_id: SchemaId,
 	max_connections: usize,
 	is_friendship: bool,
-) {
+	rng: &SimulationRng,
+) -> Result<(), String> {
 	let public_key_schema_id = env.get_config().graph_public_key_schema_id;
-	let changes: Vec<_> = selected_users
+	let changes = selected_users
 		.par_iter()
 		.map(|user_id| {
 			// get all user data
@@ -86,17 +103,19 @@ pub fn modify_random_pages(
 				state.prepare_all_import_bundles(env.clone(), *user_id, schema_id, is_friendship);
 			// import user data
 			let mut graph = GraphState::new(env.clone());
-			graph.import_users_data(&imports).expect("Should import");
-			// assert graphs with expected
+			graph.import_users_data(&imports).map_err(graph_error("import", *user_id))?;
+			// compare graphs with expected
 			let graph_edges = graph
 				.get_connections_for_user_graph(user_id, &schema_id, false)
-				.expect("Should get conections");
+				.map_err(graph_error("getting connections", *user_id))?;
 			let graph_users_set: BTreeSet<_> =
 				graph_edges.clone().iter().map(|e| e.user_id).collect();
 			let social_graph_set: BTreeSet<_> = social_graph.iter().map(|c| *c).collect();
-			assert_eq!(graph_users_set, social_graph_set, "graphs should match for {}", user_id);
+			if graph_users_set != social_graph_set {
+				return Err(format!("graphs should match for {}", user_id))
+			}
 			// choose random number of operations for add and removed
-			let mut rng = thread_rng();
+			let mut rng = rng.for_user(*user_id);
 			let graph_users: Vec<_> = graph_users_set.clone().into_iter().collect();
 			let remove_size: usize = rng.gen_range(0..=(graph_users.len() / 2)).into();
 			let add_size: usize =
@@ -125,31 +144,32 @@ pub fn modify_random_pages(
 							state
 								.on_chain_keys
 								.get(&(*c, public_key_schema_id))
-								.expect("Should key exist")
+								.ok_or_else(|| format!("keys of user {} should exist", c))?
 								.clone(),
 						)
 					} else {
 						None
 					};
 
-					Action::Connect {
+					Ok(Action::Connect {
 						owner_dsnp_user_id: *user_id,
 						connection: Connection { dsnp_user_id: *c, schema_id },
 						dsnp_keys,
-					}
+						preferred_page_id: None,
+						since: None,
+					})
 				})
-				.collect();
+				.collect::<Result<_, String>>()?;
 
 			actions.extend(add_actions);
 			actions.shuffle(&mut rng);
-			graph.apply_actions(&actions, &None).expect("Should apply removals");
+			graph.apply_actions(&actions, &None).map_err(graph_error("apply", *user_id))?;
 
 			// get result
-			println!("modifying user {} graph", user_id);
-			let updates = graph.export_updates().expect("Should work without issues");
-			(*user_id, connections_to_add, connections_to_remove, updates)
+			let updates = graph.export_updates().map_err(graph_error("export", *user_id))?;
+			Ok((*user_id, connections_to_add, connections_to_remove, updates))
 		})
-		.collect();
+		.collect::<Result<Vec<_>, String>>()?;
 
 	// apply all changes
 	for (user_id, adds, removes, updates) in changes {
@@ -163,6 +183,25 @@ pub fn modify_random_pages(
 			None,
 		);
 	}
+	Ok(())
+}
+
+/// imports the graph of a user, including the graphs of its friends for friendship graphs, and
+/// returns the updates exported after applying the actions
+pub fn apply_actions_for_user(
+	env: Environment,
+	state: &GlobalState,
+	user_id: DsnpUserId,
+	schema_id: SchemaId,
+	is_friendship: bool,
+	actions: &[Action],
+) -> DsnpGraphResult<Vec<Update>> {
+	let (imports, _) =
+		state.prepare_all_import_bundles(env.clone(), user_id, schema_id, is_friendship);
+	let mut graph = GraphState::new(env);
+	graph.import_users_data(&imports)?;
+	graph.apply_actions(&actions.to_vec(), &None)?;
+	graph.export_updates()
 }
 
 pub fn compare_on_chain_with_expected(
@@ -170,9 +209,9 @@ pub fn compare_on_chain_with_expected(
 	state: &GlobalState,
 	selected_users: Option<&[u64]>,
 	schema_id: SchemaId,
-) {
+) -> Result<(), String> {
 	let users = selected_users.unwrap_or(&state.users);
-	users.par_iter().for_each(|user_id| {
+	users.par_iter().try_for_each(|user_id| {
 		// get all user data
 		let (user_dsnp_keys, user_key_pairs, user_pages, social_graph) =
 			state.get_all_data_for_user(env.clone(), *user_id, schema_id);
@@ -185,20 +224,26 @@ pub fn compare_on_chain_with_expected(
 				pages: user_pages.clone(),
 				dsnp_keys: Some(user_dsnp_keys.clone()),
 				key_pairs: user_key_pairs.clone(),
+				chain_head: None,
 			}])
-			.expect("Should import");
-		// assert graphs with expected
+			.map_err(graph_error("import", *user_id))?;
+		// compare graphs with expected
 		let graph_edges = graph
 			.get_connections_for_user_graph(user_id, &schema_id, false)
-			.expect("Should get conections");
+			.map_err(graph_error("getting connections", *user_id))?;
 		let graph_users_set: BTreeSet<_> = graph_edges.clone().iter().map(|e| e.user_id).collect();
 		let social_graph_set: BTreeSet<_> = social_graph.iter().map(|c| *c).collect();
-		assert_eq!(
-			graph_edges.len(),
-			social_graph.len(),
-			"graph sizes should match for {}",
-			user_id
-		);
-		assert_eq!(graph_users_set, social_graph_set, "graphs should match for {}", user_id);
-	});
+		if graph_edges.len() != social_graph.len() {
+			return Err(format!(
+				"graph sizes should match for {}: {} != {}",
+				user_id,
+				graph_edges.len(),
+				social_graph.len()
+			))
+		}
+		if graph_users_set != social_graph_set {
+			return Err(format!("graphs should match for {}", user_id))
+		}
+		Ok(())
+	})
 }
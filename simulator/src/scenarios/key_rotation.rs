@@ -1,66 +1,75 @@
 use crate::{
-	constants::{
-		self, PRIVATE_FOLLOW_CONNECTIONS, PRIVATE_FRIENDSHIP_CONNECTIONS,
-		PRIVATE_FRIENDSHIP_PAGE_MODIFICATIONS,
+	rng::SimulationRng,
+	scenarios::common::{
+		add_keys_for_users, compare_on_chain_with_expected, modify_random_pages, schema_id_for,
 	},
-	scenarios::common::{add_keys_for_users, compare_on_chain_with_expected, modify_random_pages},
-	GlobalState,
+	types::{GlobalState, ScenarioReport, ScenarioResult, SimulationConfig},
 };
 use dsnp_graph_config::Environment;
 use dsnp_graph_core::api::api_types::*;
-use rand::{prelude::SliceRandom, thread_rng};
+use rand::prelude::SliceRandom;
 
-pub fn execute_key_rotation_private_follow(state: &mut GlobalState, env: Environment) {
-	let mut rng = thread_rng();
-	let private_follow_schema_id = env
-		.get_config()
-		.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
-		.unwrap();
+pub fn execute_key_rotation_private_follow(
+	state: &mut GlobalState,
+	env: Environment,
+	config: &SimulationConfig,
+	rng: &SimulationRng,
+) -> ScenarioResult {
+	let rng = rng.derive("key-rotation-private-follow");
+	let private_follow_schema_id =
+		schema_id_for(&env, ConnectionType::Follow(PrivacyType::Private))?;
 	let selected_users: Vec<_> = state
 		.users
-		.choose_multiple(&mut rng, constants::KEY_ROTATIONS)
+		.choose_multiple(&mut rng.rng(), config.key_rotations)
 		.cloned()
 		.collect();
 
-	add_keys_for_users(env.clone(), state, &selected_users, private_follow_schema_id);
+	add_keys_for_users(env.clone(), state, &selected_users, private_follow_schema_id, &rng)?;
 
 	modify_random_pages(
 		env.clone(),
 		state,
 		&selected_users,
 		private_follow_schema_id,
-		PRIVATE_FOLLOW_CONNECTIONS,
+		config.private_follow_connections,
 		false,
-	);
+		&rng,
+	)?;
 
-	compare_on_chain_with_expected(env.clone(), state, None, private_follow_schema_id);
-
-	println!("Success: All graphs matched after execute_key_rotation_private_follow!");
+	compare_on_chain_with_expected(env.clone(), state, None, private_follow_schema_id)?;
+	Ok(ScenarioReport {
+		updated_graphs: selected_users.len(),
+		..ScenarioReport::new("key rotation of private follows")
+	})
 }
 
-pub fn execute_key_rotation_private_friendship(state: &mut GlobalState, env: Environment) {
-	let mut rng = thread_rng();
-	let private_friendship_schema_id = env
-		.get_config()
-		.get_schema_id_from_connection_type(ConnectionType::Friendship(PrivacyType::Private))
-		.unwrap();
+pub fn execute_key_rotation_private_friendship(
+	state: &mut GlobalState,
+	env: Environment,
+	config: &SimulationConfig,
+	rng: &SimulationRng,
+) -> ScenarioResult {
+	let rng = rng.derive("key-rotation-private-friendship");
+	let mut choice_rng = rng.rng();
+	let private_friendship_schema_id =
+		schema_id_for(&env, ConnectionType::Friendship(PrivacyType::Private))?;
 	let selected_users: Vec<_> = state
 		.get_all_users_in_graph_for(private_friendship_schema_id)
-		.choose_multiple(&mut rng, constants::KEY_ROTATIONS)
+		.choose_multiple(&mut choice_rng, config.key_rotations)
 		.cloned()
 		.collect();
 
-	add_keys_for_users(env.clone(), state, &selected_users, private_friendship_schema_id);
+	add_keys_for_users(env.clone(), state, &selected_users, private_friendship_schema_id, &rng)?;
 
 	compare_on_chain_with_expected(
 		env.clone(),
 		state,
 		Some(&selected_users),
 		private_friendship_schema_id,
-	);
+	)?;
 
 	let modification_users: Vec<_> = selected_users
-		.choose_multiple(&mut rng, PRIVATE_FRIENDSHIP_PAGE_MODIFICATIONS)
+		.choose_multiple(&mut choice_rng, config.private_friendship_page_modifications)
 		.copied()
 		.collect();
 	modify_random_pages(
@@ -68,11 +77,14 @@ pub fn execute_key_rotation_private_friendship(state: &mut GlobalState, env: Env
 		state,
 		&modification_users,
 		private_friendship_schema_id,
-		PRIVATE_FRIENDSHIP_CONNECTIONS,
+		config.private_friendship_connections,
 		true,
-	);
-
-	compare_on_chain_with_expected(env.clone(), state, None, private_friendship_schema_id);
+		&rng,
+	)?;
 
-	println!("Success: All graphs matched after execute_key_rotation_private_friendship!");
+	compare_on_chain_with_expected(env.clone(), state, None, private_friendship_schema_id)?;
+	Ok(ScenarioReport {
+		updated_graphs: selected_users.len(),
+		..ScenarioReport::new("key rotation of private friendships")
+	})
 }
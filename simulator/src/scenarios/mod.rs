@@ -1,2 +1,6 @@
+pub mod churn;
 pub mod common;
 pub mod key_rotation;
+pub mod page_overflow;
+pub mod stale_friendship;
+pub mod unfollow;
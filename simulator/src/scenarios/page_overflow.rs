@@ -0,0 +1,104 @@
+use crate::{
+	constants,
+	rng::SimulationRng,
+	scenarios::common::{graph_error, schema_id_for},
+	types::{GlobalState, ScenarioReport, ScenarioResult},
+};
+use dsnp_graph_config::{errors::DsnpGraphError, DsnpUserId, Environment};
+use dsnp_graph_core::api::{
+	api::{GraphAPI, GraphState},
+	api_types::*,
+};
+use rand::{prelude::SliceRandom, Rng};
+use std::collections::BTreeSet;
+
+/// first number of connections added to the graph, which doubles until they no longer fit
+const INITIAL_BATCH: usize = 100;
+
+/// adds connections to the public follow graph of a user until they no longer fit in the pages
+/// allowed by the config, expecting the export to fail with `GraphCapacityExceeded`. The graph is
+/// exported again using `PageOverflowPolicy::SkipOverflow` to check that exactly the connections
+/// which fit are exported. The graph is not stored in the simulated chain state.
+pub fn execute_page_overflow(
+	state: &GlobalState,
+	env: Environment,
+	rng: &SimulationRng,
+) -> ScenarioResult {
+	let mut rng = rng.derive("page-overflow").rng();
+	let public_follow_schema_id = schema_id_for(&env, ConnectionType::Follow(PrivacyType::Public))?;
+	let owner = *state.users.choose(&mut rng).ok_or("there are no users to add connections to")?;
+	let mut graph = GraphState::new(env.clone());
+	let mut connections = BTreeSet::new();
+	let mut batch = INITIAL_BATCH;
+
+	let over = loop {
+		let mut actions = vec![];
+		while actions.len() < batch {
+			let user_id: DsnpUserId = rng.gen_range(1u32..constants::MAX_DSNP_USER_ID).into();
+			if user_id != owner && connections.insert(user_id) {
+				actions.push(Action::Connect {
+					owner_dsnp_user_id: owner,
+					connection: Connection {
+						dsnp_user_id: user_id,
+						schema_id: public_follow_schema_id,
+					},
+					dsnp_keys: None,
+					preferred_page_id: None,
+					since: None,
+				});
+			}
+		}
+		graph.apply_actions(&actions, &None).map_err(graph_error("connect", owner))?;
+
+		match graph.export_updates() {
+			Ok(_) => batch *= 2,
+			Err(DsnpGraphError::GraphCapacityExceeded(schema_id, _, over))
+				if schema_id == public_follow_schema_id =>
+				break over,
+			Err(e) => return Err(format!("unexpected export error for user {}: {}", owner, e)),
+		}
+	};
+
+	graph.set_page_overflow_policy(PageOverflowPolicy::SkipOverflow);
+	let pages: Vec<_> = graph
+		.export_updates()
+		.map_err(graph_error("export of connections that fit", owner))?
+		.into_iter()
+		.filter_map(|update| match update {
+			Update::PersistPage { page_id, prev_hash, payload, .. } =>
+				Some(PageData { page_id, content_hash: prev_hash + 1, content: payload }),
+			_ => None,
+		})
+		.collect();
+	let max_page_id = env.get_config().max_page_id;
+	if let Some(page) = pages.iter().find(|p| p.page_id > max_page_id) {
+		return Err(format!("page {} of user {} should not be exported", page.page_id, owner))
+	}
+
+	let mut imported = GraphState::new(env);
+	imported
+		.import_users_data(&vec![ImportBundle {
+			schema_id: public_follow_schema_id,
+			dsnp_user_id: owner,
+			pages,
+			dsnp_keys: None,
+			key_pairs: vec![],
+			chain_head: None,
+		}])
+		.map_err(graph_error("import", owner))?;
+	let exported = imported
+		.get_connections_for_user_graph(&owner, &public_follow_schema_id, false)
+		.map_err(graph_error("getting connections", owner))?;
+	if exported.len() + over != connections.len() ||
+		!exported.iter().all(|e| connections.contains(&e.user_id))
+	{
+		return Err(format!(
+			"only the {} overflowing of {} connections should be skipped, but {} were exported",
+			over,
+			connections.len(),
+			exported.len()
+		))
+	}
+
+	Ok(ScenarioReport { overflowing_connections: over, ..ScenarioReport::new("page overflow") })
+}
@@ -0,0 +1,175 @@
+use crate::{
+	rng::SimulationRng,
+	scenarios::common::{
+		apply_actions_for_user, compare_on_chain_with_expected, graph_error, schema_id_for,
+	},
+	types::{GlobalState, ScenarioReport, ScenarioResult, SimulationConfig},
+};
+use dsnp_graph_config::{DsnpUserId, Environment};
+use dsnp_graph_core::api::{
+	api::{GraphAPI, GraphState},
+	api_types::*,
+};
+use rand::prelude::SliceRandom;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::collections::BTreeSet;
+
+/// makes some users remove one of their private friends and then makes each removed friend find
+/// its friendships which are not verified by the PRIds of the other side and prune them, as a
+/// wallet would do for stale friendships
+pub fn execute_stale_friendship_pruning(
+	state: &mut GlobalState,
+	env: Environment,
+	config: &SimulationConfig,
+	rng: &SimulationRng,
+) -> ScenarioResult {
+	let rng = rng.derive("stale-friendship-pruning");
+	let private_friendship_schema_id =
+		schema_id_for(&env, ConnectionType::Friendship(PrivacyType::Private))?;
+	let selected_users: Vec<_> = state
+		.get_all_users_in_graph_for(private_friendship_schema_id)
+		.choose_multiple(&mut rng.rng(), config.stale_friendships)
+		.copied()
+		.collect();
+
+	// 1. each selected user removes one of its friends
+	let removals = selected_users
+		.par_iter()
+		.filter_map(|user_id| {
+			let friend = *state
+				.social_graph
+				.get(&(*user_id, private_friendship_schema_id))?
+				.choose(&mut rng.for_user(*user_id))?;
+			let removal = apply_actions_for_user(
+				env.clone(),
+				state,
+				*user_id,
+				private_friendship_schema_id,
+				true,
+				&[Action::Disconnect {
+					owner_dsnp_user_id: *user_id,
+					connection: Connection {
+						dsnp_user_id: friend,
+						schema_id: private_friendship_schema_id,
+					},
+				}],
+			)
+			.map(|updates| (*user_id, friend, updates))
+			.map_err(graph_error("friend removal", *user_id));
+			Some(removal)
+		})
+		.collect::<Result<Vec<_>, String>>()?;
+
+	let mut removed_friendships: Vec<(DsnpUserId, DsnpUserId)> = vec![];
+	for (user_id, friend, updates) in removals {
+		state.apply_updates_for_user(
+			env.clone(),
+			user_id,
+			private_friendship_schema_id,
+			&updates,
+			&[],
+			&[friend],
+			None,
+		);
+		removed_friendships.push((user_id, friend));
+	}
+
+	// 2. each removed friend prunes its one sided friendships
+	let removed_friends: BTreeSet<_> = removed_friendships.iter().map(|(_, f)| *f).collect();
+	let removed_friends: Vec<_> = removed_friends.into_iter().collect();
+	let prunes = removed_friends
+		.par_iter()
+		.map(|user_id| {
+			let (imports, _) = state.prepare_all_import_bundles(
+				env.clone(),
+				*user_id,
+				private_friendship_schema_id,
+				true,
+			);
+			let mut graph = GraphState::new(env.clone());
+			graph.import_users_data(&imports).map_err(graph_error("import", *user_id))?;
+			let friends: Vec<_> = graph
+				.get_connections_for_user_graph(user_id, &private_friendship_schema_id, false)
+				.map_err(graph_error("getting connections", *user_id))?
+				.into_iter()
+				.map(|edge| edge.user_id)
+				.collect();
+			// friends without any friendship page have no PRIds to verify and are stale as well
+			let verified = graph
+				.verify_connections(user_id, &friends)
+				.map_err(graph_error("friendship verification", *user_id))?;
+			let one_sided: Vec<_> = friends
+				.into_iter()
+				.zip(verified)
+				.filter(|(_, verified)| *verified != Some(true))
+				.map(|(friend, _)| friend)
+				.collect();
+			Ok((*user_id, one_sided))
+		})
+		.collect::<Result<Vec<_>, String>>()?;
+
+	for (user_id, one_sided) in &prunes {
+		let friends = &state.social_graph[&(*user_id, private_friendship_schema_id)];
+		// friendships removed by both sides are no longer one sided
+		for (remover, _) in
+			removed_friendships.iter().filter(|(r, f)| f == user_id && friends.contains(r))
+		{
+			if !one_sided.contains(remover) {
+				return Err(format!("{} should be a one sided friendship of {}", remover, user_id))
+			}
+		}
+	}
+
+	let changes = prunes
+		.par_iter()
+		.filter(|(_, one_sided)| !one_sided.is_empty())
+		.map(|(user_id, one_sided)| {
+			let actions: Vec<_> = one_sided
+				.iter()
+				.map(|c| Action::Disconnect {
+					owner_dsnp_user_id: *user_id,
+					connection: Connection {
+						dsnp_user_id: *c,
+						schema_id: private_friendship_schema_id,
+					},
+				})
+				.collect();
+			apply_actions_for_user(
+				env.clone(),
+				state,
+				*user_id,
+				private_friendship_schema_id,
+				true,
+				&actions,
+			)
+			.map(|updates| (*user_id, one_sided.clone(), updates))
+			.map_err(graph_error("pruning one sided friendships", *user_id))
+		})
+		.collect::<Result<Vec<_>, String>>()?;
+
+	let report = ScenarioReport {
+		updated_graphs: removed_friendships.len() + changes.len(),
+		pruned_friendships: changes.iter().map(|(_, removes, _)| removes.len()).sum(),
+		..ScenarioReport::new("stale friendship pruning")
+	};
+
+	for (user_id, removes, updates) in changes {
+		state.apply_updates_for_user(
+			env.clone(),
+			user_id,
+			private_friendship_schema_id,
+			&updates,
+			&[],
+			&removes,
+			None,
+		);
+	}
+
+	compare_on_chain_with_expected(
+		env,
+		state,
+		Some(&removed_friends),
+		private_friendship_schema_id,
+	)?;
+	Ok(report)
+}
@@ -0,0 +1,101 @@
+use crate::{
+	rng::SimulationRng,
+	scenarios::common::{
+		apply_actions_for_user, compare_on_chain_with_expected, graph_error, schema_id_for,
+	},
+	types::{GlobalState, ScenarioReport, ScenarioResult, SimulationConfig},
+};
+use dsnp_graph_config::Environment;
+use dsnp_graph_core::api::api_types::*;
+use rand::prelude::SliceRandom;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+/// chooses a few users and makes all of their private followers unfollow them in a single wave,
+/// which removes connections from the pages of many graphs at the same time
+pub fn execute_mass_unfollow(
+	state: &mut GlobalState,
+	env: Environment,
+	config: &SimulationConfig,
+	rng: &SimulationRng,
+) -> ScenarioResult {
+	let rng = rng.derive("mass-unfollow");
+	let private_follow_schema_id =
+		schema_id_for(&env, ConnectionType::Follow(PrivacyType::Private))?;
+	let unfollowed: Vec<_> = state
+		.users
+		.choose_multiple(&mut rng.rng(), config.mass_unfollowed_users)
+		.copied()
+		.collect();
+	let followers = state.get_all_users_in_graph_for(private_follow_schema_id);
+
+	let changes = followers
+		.par_iter()
+		.filter_map(|user_id| {
+			let removes: Vec<_> = state
+				.social_graph
+				.get(&(*user_id, private_follow_schema_id))?
+				.iter()
+				.filter(|c| unfollowed.contains(c))
+				.copied()
+				.collect();
+			if removes.is_empty() {
+				return None
+			}
+			let actions: Vec<_> = removes
+				.iter()
+				.map(|c| Action::Disconnect {
+					owner_dsnp_user_id: *user_id,
+					connection: Connection {
+						dsnp_user_id: *c,
+						schema_id: private_follow_schema_id,
+					},
+				})
+				.collect();
+			let change = apply_actions_for_user(
+				env.clone(),
+				state,
+				*user_id,
+				private_follow_schema_id,
+				false,
+				&actions,
+			)
+			.map(|updates| (*user_id, removes, updates))
+			.map_err(graph_error("unfollow", *user_id));
+			Some(change)
+		})
+		.collect::<Result<Vec<_>, String>>()?;
+
+	let affected_users: Vec<_> = changes.iter().map(|(user_id, ..)| *user_id).collect();
+	for (user_id, removes, updates) in changes {
+		state.apply_updates_for_user(
+			env.clone(),
+			user_id,
+			private_follow_schema_id,
+			&updates,
+			&[],
+			&removes,
+			None,
+		);
+	}
+
+	compare_on_chain_with_expected(
+		env.clone(),
+		state,
+		Some(&affected_users),
+		private_follow_schema_id,
+	)?;
+	if state
+		.social_graph
+		.iter()
+		.filter(|((_, schema_id), _)| *schema_id == private_follow_schema_id)
+		.any(|(_, connections)| connections.iter().any(|c| unfollowed.contains(c)))
+	{
+		return Err("unfollowed users should not have any followers".to_string())
+	}
+
+	Ok(ScenarioReport {
+		updated_graphs: affected_users.len(),
+		unfollowed_users: unfollowed,
+		..ScenarioReport::new("mass unfollow")
+	})
+}
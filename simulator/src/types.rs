@@ -1,7 +1,8 @@
+use crate::constants;
 use dsnp_graph_config::{DsnpUserId, Environment, SchemaId};
 use dsnp_graph_core::api::api_types::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub enum Status {
@@ -29,6 +30,76 @@ pub struct GlobalState {
 	pub wallet_keys: HashMap<DsnpUserId, Vec<GraphKeyPair>>,
 }
 
+/// Sizes of the simulated social graph and scenarios, which default to the ones of the 20k users
+/// simulation
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct SimulationConfig {
+	pub users: usize,
+	pub private_follow_connections: usize,
+	pub private_friendship_connections: usize,
+	pub key_rotations: usize,
+	pub private_friendship_page_modifications: usize,
+	pub churn_rounds: usize,
+	pub churn_users_per_round: usize,
+	pub mass_unfollowed_users: usize,
+	pub stale_friendships: usize,
+}
+
+impl Default for SimulationConfig {
+	fn default() -> Self {
+		Self {
+			users: constants::USERS,
+			private_follow_connections: constants::PRIVATE_FOLLOW_CONNECTIONS,
+			private_friendship_connections: constants::PRIVATE_FRIENDSHIP_CONNECTIONS,
+			key_rotations: constants::KEY_ROTATIONS,
+			private_friendship_page_modifications: constants::PRIVATE_FRIENDSHIP_PAGE_MODIFICATIONS,
+			churn_rounds: constants::CHURN_ROUNDS,
+			churn_users_per_round: constants::CHURN_USERS_PER_ROUND,
+			mass_unfollowed_users: constants::MASS_UNFOLLOWED_USERS,
+			stale_friendships: constants::STALE_FRIENDSHIPS,
+		}
+	}
+}
+
+/// Outcome of a scenario whose graphs all matched the expected ones
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ScenarioReport {
+	pub scenario: &'static str,
+	/// number of graphs whose updates were applied to the simulated chain
+	pub updated_graphs: usize,
+	/// users that lost all their private followers
+	pub unfollowed_users: Vec<DsnpUserId>,
+	/// number of connections that did not fit in the pages allowed by the config
+	pub overflowing_connections: usize,
+	/// number of one sided friendships that were pruned
+	pub pruned_friendships: usize,
+}
+
+impl ScenarioReport {
+	pub fn new(scenario: &'static str) -> Self {
+		Self { scenario, ..Default::default() }
+	}
+}
+
+impl fmt::Display for ScenarioReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {} graphs updated", self.scenario, self.updated_graphs)?;
+		if !self.unfollowed_users.is_empty() {
+			write!(f, ", unfollowed users {:?}", self.unfollowed_users)?;
+		}
+		if self.overflowing_connections > 0 {
+			write!(f, ", {} connections did not fit", self.overflowing_connections)?;
+		}
+		if self.pruned_friendships > 0 {
+			write!(f, ", {} one sided friendships pruned", self.pruned_friendships)?;
+		}
+		Ok(())
+	}
+}
+
+/// Report of a passed scenario, or the description of the mismatch or graph error it failed with
+pub type ScenarioResult = Result<ScenarioReport, String>;
+
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
 pub struct TempData {
 	pub user_id: DsnpUserId,
@@ -81,7 +152,9 @@ impl GlobalState {
 		let on_chain_keys = self.on_chain_keys.get_mut(&(user_id, public_key_schema_id)).unwrap();
 		for u in updates {
 			match u {
-				Update::DeletePage { page_id, prev_hash, owner_dsnp_user_id, schema_id } => {
+				Update::DeletePage {
+					page_id, prev_hash, owner_dsnp_user_id, schema_id, ..
+				} => {
 					assert_eq!(user_id, *owner_dsnp_user_id);
 					assert_eq!(graph_schema_id, *schema_id);
 
@@ -94,6 +167,7 @@ impl GlobalState {
 					payload,
 					owner_dsnp_user_id,
 					schema_id,
+					..
 				} => {
 					assert_eq!(user_id, *owner_dsnp_user_id);
 					assert_eq!(graph_schema_id, *schema_id);
@@ -106,7 +180,7 @@ impl GlobalState {
 						page_id: *page_id,
 					});
 				},
-				Update::AddKey { owner_dsnp_user_id, prev_hash, payload } => {
+				Update::AddKey { owner_dsnp_user_id, prev_hash, payload, .. } => {
 					assert_eq!(user_id, *owner_dsnp_user_id);
 					assert_eq!(&on_chain_keys.keys_hash, prev_hash);
 
@@ -122,14 +196,19 @@ impl GlobalState {
 		}
 	}
 
+	/// returns the users with a graph on chain for the schema, sorted to keep the choices made
+	/// from them reproducible
 	pub fn get_all_users_in_graph_for(&self, schema_id: SchemaId) -> Vec<DsnpUserId> {
-		self.on_chain_graph
+		let mut users: Vec<_> = self
+			.on_chain_graph
 			.iter()
 			.filter_map(|(&(dsnp_user, schema), _)| match schema_id == schema {
 				true => Some(dsnp_user),
 				false => None,
 			})
-			.collect()
+			.collect();
+		users.sort();
+		users
 	}
 
 	pub fn prepare_all_import_bundles(
@@ -148,6 +227,7 @@ impl GlobalState {
 			pages,
 			dsnp_keys: Some(user_dsnp_keys.clone()),
 			key_pairs: user_key_pairs.clone(),
+			chain_head: None,
 		}];
 		if is_friendship {
 			let friend_bundles: Vec<_> = social_graph
@@ -161,6 +241,7 @@ impl GlobalState {
 						pages: friend_pages,
 						dsnp_keys: Some(friend_dsnp_keys.clone()),
 						key_pairs: vec![],
+						chain_head: None,
 					}
 				})
 				.collect();
@@ -0,0 +1,86 @@
+use dsnp_graph_config::{ConnectionType, Environment, PrivacyType, MAINNET_CONFIG};
+use simulator::{
+	init::init_state_machine,
+	rng::SimulationRng,
+	scenarios::{
+		churn::execute_connection_churn,
+		key_rotation::{
+			execute_key_rotation_private_follow, execute_key_rotation_private_friendship,
+		},
+		page_overflow::execute_page_overflow,
+		stale_friendship::execute_stale_friendship_pruning,
+		unfollow::execute_mass_unfollow,
+	},
+	types::{GlobalState, SimulationConfig},
+};
+
+const SEED: u64 = 42;
+
+fn small_config() -> SimulationConfig {
+	SimulationConfig {
+		users: 40,
+		private_follow_connections: 12,
+		private_friendship_connections: 6,
+		key_rotations: 8,
+		private_friendship_page_modifications: 4,
+		churn_rounds: 2,
+		churn_users_per_round: 10,
+		mass_unfollowed_users: 2,
+		stale_friendships: 5,
+	}
+}
+
+fn small_env() -> Environment {
+	let mut config = MAINNET_CONFIG.clone();
+	config.max_page_id = 3;
+	Environment::Dev(config)
+}
+
+fn initialized_state(env: &Environment, rng: &SimulationRng) -> GlobalState {
+	let mut state = GlobalState::default();
+	init_state_machine(&mut state, env.clone(), &small_config(), rng, |_| {});
+	state
+}
+
+#[test]
+fn all_scenarios_should_pass_on_a_small_graph() {
+	let env = small_env();
+	let config = small_config();
+	let rng = SimulationRng::new(SEED);
+	let mut state = initialized_state(&env, &rng);
+
+	execute_key_rotation_private_follow(&mut state, env.clone(), &config, &rng)
+		.expect("key rotation of private follows should pass");
+	execute_key_rotation_private_friendship(&mut state, env.clone(), &config, &rng)
+		.expect("key rotation of private friendships should pass");
+	let churn = execute_connection_churn(&mut state, env.clone(), &config, &rng)
+		.expect("connection churn should pass");
+	let unfollow = execute_mass_unfollow(&mut state, env.clone(), &config, &rng)
+		.expect("mass unfollow should pass");
+	execute_stale_friendship_pruning(&mut state, env.clone(), &config, &rng)
+		.expect("stale friendship pruning should pass");
+	let overflow = execute_page_overflow(&state, env, &rng).expect("page overflow should pass");
+
+	assert_eq!(churn.updated_graphs, config.churn_rounds * config.churn_users_per_round);
+	assert_eq!(unfollow.unfollowed_users.len(), config.mass_unfollowed_users);
+	assert!(overflow.overflowing_connections > 0);
+}
+
+#[test]
+fn initialized_social_graph_should_be_reproducible_from_seed() {
+	let env = small_env();
+	let private_follow_schema_id = env
+		.get_config()
+		.get_schema_id_from_connection_type(ConnectionType::Follow(PrivacyType::Private))
+		.unwrap();
+
+	let state = initialized_state(&env, &SimulationRng::new(SEED));
+	let same_seed = initialized_state(&env, &SimulationRng::new(SEED));
+	let other_seed = initialized_state(&env, &SimulationRng::new(SEED + 1));
+
+	assert_eq!(state.users, same_seed.users);
+	assert_eq!(state.social_graph, same_seed.social_graph);
+	assert_eq!(state.wallet_keys, same_seed.wallet_keys);
+	assert_ne!(state.users, other_seed.users);
+	assert!(state.get_all_users_in_graph_for(private_follow_schema_id).len() > 0);
+}